@@ -0,0 +1,209 @@
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock,
+    },
+};
+
+use btc_heritage_wallet::bitcoin::Network;
+
+use crate::{prelude::state_management::MINIMIZED_FLAG, utils::CCStr, Route};
+
+/// Scheme of the deep links handled by the application
+pub const DEEP_LINK_SCHEME: &str = "heritage://";
+
+const USAGE: &str = "\
+Usage: heritage-gui [OPTIONS] [heritage://<target>]
+
+Options:
+  --network <network>  Bitcoin network: bitcoin, testnet, signet or regtest
+  --datadir <path>     Directory of the application data (default: ~/.heritage-wallet)
+  --profile <name>     Use a separate set of wallets, heirs and settings stored in the data directory
+  --open <target>      Open the application directly on the target
+  --minimized          Start with the window minimized
+  --debug-panel        Show a developer panel dumping the UI state for bug reports
+  -h, --help           Print this help
+
+Targets:
+  dashboard
+  wallet:<name>[/configuration|/spend]
+  heirwallet:<name>[/configuration]
+  heirs
+  config
+  xpub-tool
+  backup-check
+  recovery-rehearsal
+
+Deep links use the same targets, e.g. heritage://wallet/<name>/spend";
+
+/// A view of the application to open directly at startup
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OpenTarget {
+    Wallet { name: String, page: Option<String> },
+    HeirWallet { name: String, page: Option<String> },
+    Dashboard,
+    Heirs,
+    Config,
+    XPubTool,
+    BackupCheck,
+    RecoveryRehearsal,
+}
+impl OpenTarget {
+    /// Parse a target in the `kind:<name>[/page]` or `kind/<name>[/page]` form
+    fn parse(target: &str) -> Result<Self, String> {
+        let (kind, rest) = target
+            .split_once([':', '/'])
+            .map_or((target, None), |(kind, rest)| (kind, Some(rest)));
+        let name_and_page = || {
+            let rest = rest
+                .map(|rest| rest.trim_end_matches('/'))
+                .filter(|rest| !rest.is_empty())
+                .ok_or_else(|| format!("Missing name in target {target}"))?;
+            Ok::<_, String>(match rest.rsplit_once('/') {
+                Some((name, page)) => (name.to_owned(), Some(page.to_owned())),
+                None => (rest.to_owned(), None),
+            })
+        };
+        match kind {
+            "wallet" => {
+                let (name, page) = name_and_page()?;
+                Ok(Self::Wallet { name, page })
+            }
+            "heirwallet" => {
+                let (name, page) = name_and_page()?;
+                Ok(Self::HeirWallet { name, page })
+            }
+            "dashboard" => Ok(Self::Dashboard),
+            "heirs" => Ok(Self::Heirs),
+            "config" => Ok(Self::Config),
+            "xpub-tool" => Ok(Self::XPubTool),
+            "backup-check" => Ok(Self::BackupCheck),
+            "recovery-rehearsal" => Ok(Self::RecoveryRehearsal),
+            _ => Err(format!("Unknown target {target}")),
+        }
+    }
+
+    pub fn route(&self) -> Route {
+        match self {
+            OpenTarget::Wallet { name, page } => {
+                let wallet_name = CCStr::from(name);
+                match page.as_deref() {
+                    Some("configuration") => Route::WalletConfigurationView { wallet_name },
+                    Some("spend") => Route::WalletSpendView { wallet_name },
+                    _ => Route::WalletView { wallet_name },
+                }
+            }
+            OpenTarget::HeirWallet { name, page } => {
+                let heirwallet_name = CCStr::from(name);
+                match page.as_deref() {
+                    Some("configuration") => Route::HeirWalletConfigurationView { heirwallet_name },
+                    _ => Route::HeirWalletView { heirwallet_name },
+                }
+            }
+            OpenTarget::Dashboard => Route::DashboardView {},
+            OpenTarget::Heirs => Route::HeirListView {},
+            OpenTarget::Config => Route::AppConfigView {},
+            OpenTarget::XPubTool => Route::XPubToolView {},
+            OpenTarget::BackupCheck => Route::BackupCheckView {},
+            OpenTarget::RecoveryRehearsal => Route::RecoveryRehearsalView {},
+        }
+    }
+}
+
+/// Command line arguments of the application
+#[derive(Debug, Clone, Default)]
+pub struct CliArgs {
+    pub network: Option<Network>,
+    pub datadir: Option<PathBuf>,
+    pub profile: Option<String>,
+    pub open: Option<OpenTarget>,
+    pub debug_panel: bool,
+}
+impl CliArgs {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut cli_args = Self::default();
+        while let Some(arg) = args.next() {
+            let mut value = |flag: &str| {
+                args.next()
+                    .ok_or_else(|| format!("Missing value for {flag}"))
+            };
+            match arg.as_str() {
+                "--network" => {
+                    let network = value("--network")?;
+                    cli_args.network = Some(
+                        network
+                            .parse()
+                            .map_err(|_| format!("Invalid network {network}"))?,
+                    );
+                }
+                "--datadir" => cli_args.datadir = Some(PathBuf::from(value("--datadir")?)),
+                "--profile" => {
+                    let profile = value("--profile")?;
+                    if profile.is_empty()
+                        || !profile
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+                    {
+                        return Err(format!(
+                            "Invalid profile {profile}, only use letters, digits, dashes and underscores"
+                        ));
+                    }
+                    cli_args.profile = Some(profile);
+                }
+                "--open" => cli_args.open = Some(OpenTarget::parse(&value("--open")?)?),
+                "--debug-panel" => cli_args.debug_panel = true,
+                MINIMIZED_FLAG => (),
+                _ => match arg.strip_prefix(DEEP_LINK_SCHEME) {
+                    Some(target) => cli_args.open = Some(OpenTarget::parse(target)?),
+                    None => return Err(format!("Unknown argument {arg}")),
+                },
+            }
+        }
+        Ok(cli_args)
+    }
+
+    /// The data directory to use, accounting for the profile
+    pub fn datadir(&self, default_datadir: PathBuf) -> PathBuf {
+        let datadir = self.datadir.clone().unwrap_or(default_datadir);
+        match &self.profile {
+            Some(profile) => datadir.join("profiles").join(profile),
+            None => datadir,
+        }
+    }
+}
+
+static CLI_ARGS: OnceLock<CliArgs> = OnceLock::new();
+static OPEN_TARGET_CONSUMED: AtomicBool = AtomicBool::new(false);
+
+/// Parse the command line arguments, printing the usage and exiting when they are invalid
+pub fn init_cli_args() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{USAGE}");
+        std::process::exit(0);
+    }
+    match CliArgs::parse(args.into_iter()) {
+        Ok(cli_args) => {
+            log::info!("Command line arguments: {cli_args:?}");
+            CLI_ARGS
+                .set(cli_args)
+                .expect("command line arguments are only parsed once");
+        }
+        Err(e) => {
+            eprintln!("{e}\n\n{USAGE}");
+            std::process::exit(2);
+        }
+    }
+}
+
+pub fn cli_args() -> &'static CliArgs {
+    CLI_ARGS.get_or_init(CliArgs::default)
+}
+
+/// The route requested on the command line, only returned the first time so that
+/// the application does not navigate there again later
+pub fn take_open_route() -> Option<Route> {
+    let target = cli_args().open.as_ref()?;
+    (!OPEN_TARGET_CONSUMED.swap(true, Ordering::Relaxed)).then(|| target.route())
+}
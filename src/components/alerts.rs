@@ -2,20 +2,23 @@
 
 use crate::prelude::*;
 
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use std::collections::VecDeque;
 use std::time::Duration;
 
 use crate::components::svg::{AlertCircle, Close, DrawSvg, SvgSize::Size5};
+use crate::errors::UIError;
 use crate::utils::CCStr;
+use crate::Route;
 
 static ALERTS: GlobalSignal<VecDeque<Alert>> = Signal::global(|| VecDeque::new());
 
 /// Maximum number of alerts to display at once
 const MAX_ALERTS: usize = 10;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum AlertClass {
     Success,
     Warn,
@@ -33,13 +36,53 @@ impl core::fmt::Display for AlertClass {
     }
 }
 
+/// What an alert is about, to let the user choose how each category is displayed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AlertCategory {
+    #[default]
+    General,
+    /// Routine outcomes of the wallet synchronizations
+    Sync,
+    /// Warnings about the safety of the bitcoins or their inheritance
+    Security,
+}
+impl AlertCategory {
+    pub const ALL: [Self; 3] = [Self::General, Self::Sync, Self::Security];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::General => "General",
+            Self::Sync => "Synchronization",
+            Self::Security => "Security warnings",
+        }
+    }
+}
+
+impl AlertClass {
+    pub fn text_class(self) -> &'static str {
+        match self {
+            Self::Success => "text-success",
+            Self::Warn => "text-warning",
+            Self::Error => "text-error",
+            Self::Info => "text-info",
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Alert {
     uuid: Uuid,
     alert_class: AlertClass,
+    category: AlertCategory,
     title: CCStr,
     message: CCStr,
     timeout_ms: u64,
+    /// What the user can do about it
+    remediation: Option<CCStr>,
+    /// Where to learn more about it
+    doc_url: Option<CCStr>,
+    /// The view where it can be fixed
+    fix_route: Option<Route>,
 }
 impl Alert {
     pub fn custom(
@@ -51,9 +94,13 @@ impl Alert {
         Self {
             uuid: Uuid::new_v4(),
             alert_class,
+            category: AlertCategory::General,
             title: title.into(),
             message: message.into(),
             timeout_ms,
+            remediation: None,
+            doc_url: None,
+            fix_route: None,
         }
     }
     pub fn success(message: impl Into<CCStr>) -> Self {
@@ -68,6 +115,22 @@ impl Alert {
     pub fn info(message: impl Into<CCStr>) -> Self {
         Self::custom(AlertClass::Info, "Info", message, 5000)
     }
+    /// Creates an error alert with the remediation of the kind of the error, displayed
+    /// longer when there is something the user can do about it
+    pub fn failure(error: UIError) -> Self {
+        let kind = error.kind;
+        let alert = Self::custom(AlertClass::Error, kind.title(), error.to_string(), 5000);
+        match kind.remediation() {
+            Some(remediation) => Self {
+                remediation: Some(CCStr::from(remediation)),
+                doc_url: kind.doc_url().map(CCStr::from),
+                fix_route: kind.fix_route(),
+                ..alert
+            }
+            .with_timeout(15000),
+            None => alert,
+        }
+    }
 
     /// Creates a custom alert with a specified title
     pub fn with_title(mut self, title: impl Into<CCStr>) -> Self {
@@ -75,6 +138,12 @@ impl Alert {
         self
     }
 
+    /// Changes the category of this alert
+    pub fn with_category(mut self, category: AlertCategory) -> Self {
+        self.category = category;
+        self
+    }
+
     /// Changes the timeout for this alert
     pub fn with_timeout(mut self, timeout_ms: u64) -> Self {
         self.timeout_ms = timeout_ms;
@@ -84,6 +153,19 @@ impl Alert {
 
 // Helper functions to show alerts
 pub fn add_alert(alert: Alert) {
+    state_management::publish_alert_event(
+        alert.alert_class,
+        alert.title.clone(),
+        alert.message.clone(),
+    );
+    // Every alert lands in the notification center, but only some are displayed as toasts
+    if !state_management::ALERT_ROUTING_CONFIG
+        .peek()
+        .shows_toast(alert.category)
+    {
+        log::debug!("add_alert - Not displayed as a toast: {alert:?}");
+        return;
+    }
     let mut alerts = ALERTS.write();
     // Remove oldest alerts if we've reached the maximum
     while alerts.len() >= MAX_ALERTS {
@@ -108,6 +190,15 @@ pub fn alert_info(message: impl Into<CCStr>) {
     add_alert(Alert::info(message));
 }
 
+/// Logs and displays a failure, with a remediation when its kind is recognized
+///
+/// `context` describes what failed, e.g. "Failed to sync", and is prefixed to the error.
+pub fn alert_failure(context: impl Into<CCStr>, error: impl core::fmt::Display) {
+    let error = UIError::new(context, error);
+    log::error!("{error}");
+    add_alert(Alert::failure(error));
+}
+
 #[component]
 pub fn AlertsContainer() -> Element {
     rsx! {
@@ -144,7 +235,33 @@ pub fn AlertDisplay(alert: Alert) -> Element {
                 DrawSvg::<AlertCircle> { size: Size5 }
                 b { "{alert.title}" }
             }
-            span { class: "text-xs col-start-1 col-span-11 sm:col-auto", "{alert.message}" }
+            div { class: "flex flex-col gap-1 col-start-1 col-span-11 sm:col-auto",
+                span { class: "text-xs", "{alert.message}" }
+                if let Some(remediation) = alert.remediation.as_ref() {
+                    span { class: "text-xs font-semibold", "{remediation}" }
+                    div { class: "flex flex-row gap-2",
+                        if let Some(fix_route) = alert.fix_route.clone() {
+                            button {
+                                class: "btn btn-xs btn-outline",
+                                onclick: move |_| {
+                                    close_alert(alert.uuid);
+                                    navigator().push(fix_route.clone());
+                                },
+                                "Fix it"
+                            }
+                        }
+                        if let Some(doc_url) = alert.doc_url.clone() {
+                            button {
+                                class: "btn btn-xs btn-outline",
+                                onclick: move |_| {
+                                    _ = open::that_in_background(&*doc_url);
+                                },
+                                "Learn more"
+                            }
+                        }
+                    }
+                }
+            }
             button {
                 class: "btn btn-circle btn-outline btn-xs col-start-12 col-span-1 sm:col-auto",
                 onclick: move |_| close_alert(alert.uuid),
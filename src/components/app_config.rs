@@ -24,12 +24,12 @@ pub fn AppConfig() -> Element {
     let current_bitcoin_network =
         use_memo(move || state_management::APPLICATION_CONFIG.read().network);
 
-    let connected_user_name = use_memo(|| match &*state_management::SERVICE_STATUS.read() {
-        Some(ServiceStatus::Connected(css)) => css.user_id.preferred_username.as_ref().to_owned(),
+    let connected_user_name = use_memo(|| match &*state_management::SERVICE_AUTH_STATE.read() {
+        Some(ServiceAuthState::Connected { username, .. }) => username.to_string(),
         _ => String::new(),
     });
-    let connected_user_email = use_memo(|| match &*state_management::SERVICE_STATUS.read() {
-        Some(ServiceStatus::Connected(css)) => css.user_id.email.as_ref().to_owned(),
+    let connected_user_email = use_memo(|| match &*state_management::SERVICE_AUTH_STATE.read() {
+        Some(ServiceAuthState::Connected { email, .. }) => email.to_string(),
         _ => String::new(),
     });
 
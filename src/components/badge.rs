@@ -2,7 +2,10 @@ use crate::prelude::*;
 
 use btc_heritage_wallet::AnyKeyProvider;
 
-use crate::{components::misc::TextTooltip, utils::CCStr};
+use crate::{
+    components::misc::{TextTooltip, Tooltip},
+    utils::CCStr,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[allow(dead_code)]
@@ -220,6 +223,12 @@ const HEIR_SERVICE_BADGE: UIBadge = UIBadge {
     tooltip: "Heir is registered in the Heritage service",
     status: ExternalDependencyStatus::None,
 };
+const HEIR_CONFLICT_BADGE: UIBadge = UIBadge {
+    text: "Conflict",
+    badge_style: UIBadgeStyle::Custom("badge-warning"),
+    tooltip: "Other heirs share the same heir configuration",
+    status: ExternalDependencyStatus::None,
+};
 const HEIR_LOCAL_SEED_BADGE: UIBadge = UIBadge {
     text: "Seed",
     badge_style: UIBadgeStyle::LocalKey,
@@ -227,19 +236,109 @@ const HEIR_LOCAL_SEED_BADGE: UIBadge = UIBadge {
     status: ExternalDependencyStatus::None,
 };
 
+/// Preparedness of an heir to claim an inheritance
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeirReadiness {
+    /// The heir is registered in this app, so its seed is either here or held by the heir
+    key_location_known: bool,
+    /// The heir is registered in the Heritage service, which can contact it
+    service_contact: bool,
+    /// The current Heritage Configuration of a wallet references the heir
+    in_current_config: bool,
+}
+impl HeirReadiness {
+    fn checklist(self) -> [(bool, &'static str); 3] {
+        [
+            (
+                self.key_location_known,
+                if self.key_location_known {
+                    "Key material location known"
+                } else {
+                    "Key material location unknown"
+                },
+            ),
+            (
+                self.service_contact,
+                if self.service_contact {
+                    "Service contact present"
+                } else {
+                    "No contact in the Heritage service"
+                },
+            ),
+            (
+                self.in_current_config,
+                if self.in_current_config {
+                    "In a current Heritage Configuration"
+                } else {
+                    "Not in any current Heritage Configuration"
+                },
+            ),
+        ]
+    }
+    fn ready_count(self) -> usize {
+        self.checklist().iter().filter(|(ok, _)| *ok).count()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
-pub struct UIHeirBadges(Vec<UIBadge>);
+pub struct UIHeirBadges {
+    badges: Vec<UIBadge>,
+    readiness: Option<HeirReadiness>,
+}
+impl UIHeirBadges {
+    /// Add the readiness indicator of `heir`, turning the badges into a preparedness checklist
+    pub fn with_readiness(mut self, heir: &CompositeHeir, in_current_config: bool) -> Self {
+        self.readiness = Some(HeirReadiness {
+            key_location_known: heir.db_heir.is_some(),
+            service_contact: heir
+                .service_heir
+                .as_ref()
+                .is_some_and(|inner| inner.is_some()),
+            in_current_config,
+        });
+        self
+    }
+}
 impl LoadedElement for UIHeirBadges {
     type Loader = TransparentLoader;
     #[inline(always)]
     fn element<M: LoadedComponentInputMapper>(self, m: M) -> Element {
         rsx! {
-            LoadedComponent::<Vec<UIBadge>> { input: m.map(self.0) }
+            LoadedComponent::<Vec<UIBadge>> { input: m.map(self.badges) }
+            if let Some(readiness) = self.readiness {
+                Tooltip {
+                    content: rsx! {
+                        ul { class: "text-left",
+                            for (ok , item) in readiness.checklist() {
+                                li {
+                                    if ok {
+                                        "✓ "
+                                    } else {
+                                        "✗ "
+                                    }
+                                    {item}
+                                }
+                            }
+                        }
+                    },
+                    match readiness.ready_count() {
+                        3 => rsx! {
+                            div { class: "badge shadow-xl text-nowrap badge-success", "Ready" }
+                        },
+                        count => rsx! {
+                            div { class: "badge shadow-xl text-nowrap badge-warning", "Ready {count}/3" }
+                        },
+                    }
+                }
+            }
         }
     }
 
     fn place_holder() -> Self {
-        Self(vec![UIBadge::place_holder(); 2].into())
+        Self {
+            badges: vec![UIBadge::place_holder(); 2].into(),
+            readiness: None,
+        }
     }
 }
 impl LoadedSuccessConversionMarker for TypeCouple<CompositeHeir, UIHeirBadges> {}
@@ -248,6 +347,7 @@ impl FromRef<CompositeHeir> for UIHeirBadges {
         let CompositeHeir {
             db_heir,
             service_heir,
+            conflicts,
             ..
         } = heir;
         let mut badges = vec![];
@@ -265,7 +365,13 @@ impl FromRef<CompositeHeir> for UIHeirBadges {
         if service_heir.as_ref().is_some_and(|inner| inner.is_some()) {
             badges.push(HEIR_SERVICE_BADGE);
         }
-        UIHeirBadges(badges)
+        if !conflicts.is_empty() {
+            badges.push(HEIR_CONFLICT_BADGE);
+        }
+        UIHeirBadges {
+            badges,
+            readiness: None,
+        }
     }
 }
 
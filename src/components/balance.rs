@@ -7,7 +7,9 @@ use btc_heritage_wallet::{
     online_wallet::WalletStatus,
 };
 
-use crate::utils::{amount_to_signed, denomination_for_amount};
+use crate::utils::{amount_to_signed, denomination_for_amount, CheapClone};
+
+use state_management::{DisplayDenomination, FiatCurrency, WalletSettings};
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub struct UIBtcAmount {
@@ -48,7 +50,12 @@ impl LoadedElement for UIBtcAmount {
                     .to_unsigned()
                     .unwrap();
 
-                let amount_s = match denomination_for_amount(abs_amount) {
+                // The wallet settings may impose the denomination
+                let denomination = try_consume_context::<Resource<WalletSettings>>()
+                    .and_then(|wallet_settings| wallet_settings.cloned()?.denomination)
+                    .map(DisplayDenomination::denomination)
+                    .unwrap_or_else(|| denomination_for_amount(abs_amount));
+                let amount_s = match denomination {
                     Denomination::Bitcoin => {
                         format!("{:+} BTC", signed_amount.display_in(Denomination::Bitcoin))
                     }
@@ -59,7 +66,7 @@ impl LoadedElement for UIBtcAmount {
                     Denomination::Satoshi => {
                         format!("{:+} sat", signed_amount.display_in(Denomination::Satoshi))
                     }
-                    _ => unreachable!("denomination_for_amount never return another denom"),
+                    _ => unreachable!("denomination is never another denom"),
                 };
                 (
                     signed_amount.is_positive() || signed_amount == SignedAmount::ZERO,
@@ -223,6 +230,7 @@ impl LoadedElement for UIWalletBalance {
                             LoadedComponent::<UIBtcAmount> { input: m.map(self.0.obs_balance) }
                         }
                     }
+                    AwaitingConfirmationsBalance {}
                 }
             }
         }
@@ -231,3 +239,60 @@ impl LoadedElement for UIWalletBalance {
         Self(UIBalanceInner::place_holder())
     }
 }
+
+/// Part of the balance in UTXOs that do not have the confirmations required to spend them yet
+#[component]
+fn AwaitingConfirmationsBalance() -> Element {
+    let utxos_with_info = try_use_context::<FMemo<CheapClone<[UtxoWithInfo]>>>();
+
+    let awaiting = utxos_with_info.and_then(|utxos_with_info| {
+        utxos_with_info.lrmap_ok(|utxos_with_info| {
+            utxos_with_info
+                .iter()
+                .filter(|utxo| !utxo.spendable)
+                .map(|utxo| utxo.amount)
+                .sum::<Amount>()
+        })
+    });
+
+    rsx! {
+        if let Some(awaiting) = awaiting.filter(|awaiting| *awaiting > Amount::ZERO) {
+            div { class: "font-light text-sm text-warning",
+                "Awaiting confirmations: "
+                span { class: "font-bold",
+                    AlwaysLoadedComponent::<UIBtcAmount> { input: awaiting.into() }
+                }
+            }
+        }
+    }
+}
+
+/// Value of an amount in fiat currency at a past time, e.g. when a UTXO was acquired
+///
+/// It uses the historical exchange rates provided in the context by the wallet views
+/// and renders nothing when they are not available.
+#[component]
+pub fn HistoricalFiatValue(amount: Amount, timestamp: u64, with_rate: Option<bool>) -> Element {
+    let historical_exchange_rates =
+        try_use_context::<Resource<Option<state_management::HistoricalExchangeRates>>>();
+    let Some(historical_exchange_rates) = historical_exchange_rates else {
+        return rsx! {};
+    };
+    let (currency, rate) = match &*historical_exchange_rates.read() {
+        Some(Some(rates)) => match rates.rate_at(timestamp) {
+            Some(rate) => (rates.currency, rate),
+            None => return rsx! {},
+        },
+        _ => return rsx! {},
+    };
+    let value = currency.format(FiatCurrency::value_of(amount, rate));
+    let rate = currency.format(rate);
+    rsx! {
+        span { class: "text-nowrap font-light text-sm", title: "1 BTC = {rate}",
+            "≈ {value}"
+            if with_rate.unwrap_or(false) {
+                span { class: "block", "bought at {rate}/BTC" }
+            }
+        }
+    }
+}
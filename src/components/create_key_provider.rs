@@ -2,12 +2,13 @@ use crate::prelude::*;
 
 use btc_heritage_wallet::{
     btc_heritage::utils::bitcoin_network, heritage_service_api_client::Fingerprint,
-    BoundFingerprint, Language, LocalKey, Mnemonic,
+    AnyKeyProvider, BoundFingerprint, Language, LocalKey, Mnemonic,
 };
 
 use crate::{
     components::{
         app_config::LedgerServiceStatusWithDesc,
+        copy::CopyTextarea,
         inputs::{use_future_error_feedback, InputField, RadioChoice, RadioChoices},
         misc::Divider,
     },
@@ -19,6 +20,8 @@ enum KeyProviderType {
     None,
     Local,
     Ledger,
+    Coldcard,
+    Hwi,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -46,6 +49,34 @@ pub enum LocalKeyCreationConfig {
     },
 }
 
+impl KeyProviderConfig {
+    /// Build the key provider described by this configuration
+    pub async fn build(self) -> Result<AnyKeyProvider, String> {
+        let network = bitcoin_network::get();
+        Ok(match self {
+            KeyProviderConfig::None => AnyKeyProvider::None,
+            KeyProviderConfig::Local(local_key_creation_config) => {
+                let local_key = match local_key_creation_config {
+                    LocalKeyCreationConfig::New {
+                        word_count,
+                        password,
+                    } => LocalKey::generate(word_count, password, network),
+                    LocalKeyCreationConfig::Restore { mnemo, password } => {
+                        LocalKey::restore(mnemo, password, network)
+                    }
+                };
+                AnyKeyProvider::LocalKey(local_key)
+            }
+            KeyProviderConfig::Ledger => {
+                let ledger = btc_heritage_wallet::LedgerKey::new(network)
+                    .await
+                    .map_err(|e| format!("Could not create the Ledger key: {e}"))?;
+                AnyKeyProvider::Ledger(ledger)
+            }
+        })
+    }
+}
+
 pub type KeyProviderConfigState = Signal<Result<KeyProviderConfig, ()>>;
 type LocalKeyCreationConfigState = Signal<Result<LocalKeyCreationConfig, ()>>;
 
@@ -93,7 +124,10 @@ pub fn KeyProviderSection(
     // Update parent signal when internal state changes
     use_effect(move || {
         let result = match key_provider_type() {
-            KeyProviderType::None => Ok(KeyProviderConfig::None),
+            // The Coldcard and HWI devices sign externally, the wallet itself does not hold any key
+            KeyProviderType::None | KeyProviderType::Coldcard | KeyProviderType::Hwi => {
+                Ok(KeyProviderConfig::None)
+            }
             KeyProviderType::Local => {
                 local_key_creation_config_state().map(KeyProviderConfig::Local)
             }
@@ -133,7 +167,7 @@ pub fn KeyProviderSection(
 
                 Divider { "Key Provider Type" }
 
-                RadioChoices { count: 3,
+                RadioChoices { count: 5,
                     RadioChoice {
                         name: "key_provider",
                         state: key_provider_type,
@@ -190,6 +224,44 @@ pub fn KeyProviderSection(
                             KeyProviderSectionFlavor::Heir | KeyProviderSectionFlavor::HeirWallet
                         ),
                     }
+
+                    RadioChoice {
+                        name: "key_provider",
+                        state: key_provider_type,
+                        value: KeyProviderType::Coldcard,
+                        title: "Coldcard (SD Card)",
+                        subtitle: match flavor {
+                            KeyProviderSectionFlavor::Wallet => {
+                                "Sign transactions airgapped on a Coldcard, exchanging them through its SD card"
+                            }
+                            KeyProviderSectionFlavor::Heir | KeyProviderSectionFlavor::HeirWallet => {
+                                "Not supported for heirs and heir wallets yet"
+                            }
+                        },
+                        disabled: matches!(
+                            flavor,
+                            KeyProviderSectionFlavor::Heir | KeyProviderSectionFlavor::HeirWallet
+                        ),
+                    }
+
+                    RadioChoice {
+                        name: "key_provider",
+                        state: key_provider_type,
+                        value: KeyProviderType::Hwi,
+                        title: "Other Hardware Wallet (HWI)",
+                        subtitle: match flavor {
+                            KeyProviderSectionFlavor::Wallet => {
+                                "Use a BitBox02, Jade, Trezor or other device supported by HWI to derive the keys and sign"
+                            }
+                            KeyProviderSectionFlavor::Heir | KeyProviderSectionFlavor::HeirWallet => {
+                                "Not supported for heirs and heir wallets yet"
+                            }
+                        },
+                        disabled: matches!(
+                            flavor,
+                            KeyProviderSectionFlavor::Heir | KeyProviderSectionFlavor::HeirWallet
+                        ),
+                    }
                 }
 
                 div {
@@ -210,6 +282,12 @@ pub fn KeyProviderSection(
                     KeyProviderType::Local => rsx! {
                         LocalKeyOptions { local_key_creation_config_state }
                     },
+                    KeyProviderType::Coldcard => rsx! {
+                        ColdcardKeyOptions {}
+                    },
+                    KeyProviderType::Hwi => rsx! {
+                        HwiKeyOptions {}
+                    },
                     KeyProviderType::None => rsx! {},
                 }
             }
@@ -254,6 +332,123 @@ fn LedgerKeyOptions() -> Element {
     }
 }
 
+/// Coldcard key provider options
+///
+/// The wallet is created watch-only, the Coldcard keeps the keys and signs
+/// the transactions exchanged through its SD card.
+#[component]
+fn ColdcardKeyOptions() -> Element {
+    log::debug!("ColdcardKeyOptions Rendered");
+
+    use_drop(|| log::debug!("ColdcardKeyOptions Dropped"));
+
+    rsx! {
+        Divider { "Coldcard Setup" }
+
+        div { class: "flex flex-col gap-2 text-sm",
+            p {
+                "The wallet will be watch-only in this application: it never holds your private keys and
+                can only create unsigned transactions. Your Coldcard signs them airgapped."
+            }
+            ol { class: "list-decimal list-inside flex flex-col gap-1",
+                li {
+                    "Your Coldcard must run a firmware supporting Taproot Miniscript."
+                }
+                li {
+                    "Export the Taproot (BIP-86) account extended public keys of your Coldcard to its SD card
+                    and provide them to the Online Wallet below."
+                }
+                li {
+                    "Once the Heritage Configuration is set, use \"Backup Descriptors\" and import the
+                    resulting file on your Coldcard so it recognizes the wallet."
+                }
+                li {
+                    "When spending, the \"Sign TX\" tab guides you to sign through the SD card."
+                }
+            }
+        }
+    }
+}
+
+/// HWI key provider options
+///
+/// The wallet is created watch-only, the account xpubs are derived on the device
+/// through HWI and the transactions are signed on it from the "Sign TX" tab.
+#[component]
+fn HwiKeyOptions() -> Element {
+    log::debug!("HwiKeyOptions Rendered");
+
+    let selected_device = use_signal(|| None::<Fingerprint>);
+    let account_count = use_signal(|| 5u32);
+    let account_count_error = use_memo(move || match account_count() {
+        1..=50 => None,
+        _ => Some(CCStr::from("Between 1 and 50 accounts")),
+    });
+    let mut deriving = use_signal(|| false);
+    let mut account_xpubs = use_signal(|| None::<Result<CCStr, CCStr>>);
+
+    let derive_account_xpubs = move |_| async move {
+        let Some(fingerprint) = selected_device() else {
+            return;
+        };
+        *deriving.write() = true;
+        let result = state_management::hwi_account_xpubs(fingerprint, 0..account_count())
+            .await
+            .map(|account_xpubs| CCStr::from(account_xpubs.join("\n")))
+            .map_err(CCStr::from);
+        account_xpubs.set(Some(result));
+        *deriving.write() = false;
+    };
+
+    use_drop(|| log::debug!("HwiKeyOptions Dropped"));
+
+    rsx! {
+        Divider { "HWI Setup" }
+
+        div { class: "flex flex-col gap-4 text-sm",
+            p {
+                "The wallet will be watch-only in this application: it never holds your private keys and
+                can only create unsigned transactions. Your device, driven through HWI, derives the
+                account extended public keys and signs the transactions."
+            }
+
+            crate::components::hwi::HwiDeviceSelect { selected: selected_device }
+
+            div { class: "flex flex-row gap-4 items-end",
+                InputField {
+                    title: "Accounts",
+                    value: account_count,
+                    r#type: "number",
+                    value_error: account_count_error,
+                }
+                button {
+                    class: "btn btn-primary",
+                    disabled: selected_device().is_none() || deriving() || account_count_error().is_some(),
+                    onclick: derive_account_xpubs,
+                    if deriving() {
+                        span { class: "loading loading-spinner" }
+                    }
+                    "Derive Account XPubs"
+                }
+            }
+            match account_xpubs() {
+                Some(Ok(account_xpubs)) => rsx! {
+                    div { "Provide these account xpubs to the Online Wallet below:" }
+                    CopyTextarea { value: account_xpubs, rows: 5 }
+                },
+                Some(Err(e)) => rsx! {
+                    div { class: "text-error", "Could not derive the account xpubs: {e}" }
+                },
+                None => rsx! {
+                    div { class: "text-(--color-base-content)/60",
+                        "Confirm the export on your device if it asks you to."
+                    }
+                },
+            }
+        }
+    }
+}
+
 /// Local key provider options
 #[component]
 fn LocalKeyOptions(local_key_creation_config_state: LocalKeyCreationConfigState) -> Element {
@@ -0,0 +1,79 @@
+use crate::prelude::*;
+
+use crate::components::inputs::FileInput;
+
+/// Whether files can be saved on this platform
+#[cfg(feature = "desktop")]
+pub const CAN_EXPORT: bool = true;
+#[cfg(not(feature = "desktop"))]
+pub const CAN_EXPORT: bool = false;
+
+/// The directory where files are exported, the home directory until the user chooses another
+pub fn use_export_directory() -> Signal<String> {
+    use_signal(|| {
+        dirs_next::home_dir()
+            .unwrap_or_default()
+            .to_str()
+            .unwrap_or_default()
+            .to_owned()
+    })
+}
+
+/// Write the exported files at their paths, alerting the user if one cannot be written
+///
+/// `what` names the content of the files in the logs and alerts.
+/// Returns whether all the files were written.
+pub fn write_export_files<D: AsRef<[u8]>>(
+    what: &str,
+    files: impl IntoIterator<Item = (String, D)>,
+) -> bool {
+    #[cfg(feature = "desktop")]
+    {
+        for (file_path, data) in files {
+            if let Err(e) = std::fs::write(&file_path, data.as_ref()) {
+                alert_failure(format!("Failed to save the {what}"), e);
+                return false;
+            }
+            log::info!("Export of the {what} written successfully to: {file_path}");
+        }
+        true
+    }
+    #[cfg(not(feature = "desktop"))]
+    {
+        let _ = (what, files);
+        log::info!("File save unavailable on this platform");
+        alert_error("File save unavailable on this platform");
+        false
+    }
+}
+
+/// Picker of the export directory followed by the button saving the export (the children)
+///
+/// Nothing is rendered on the platforms where files cannot be saved.
+#[component]
+pub fn ExportToDirectory(
+    mut directory: Signal<String>,
+    /// Path of the exported file(s) in the chosen directory
+    display_path: ReadOnlySignal<String>,
+    class: Option<&'static str>,
+    children: Element,
+) -> Element {
+    rsx! {
+        if CAN_EXPORT {
+            div { class: class.unwrap_or("flex justify-center"),
+                FileInput {
+                    display_path,
+                    directory: true,
+                    onchange: move |evt: Event<FormData>| {
+                        if let Some(file_engine) = evt.files().clone() {
+                            for file in file_engine.files() {
+                                directory.set(file);
+                            }
+                        }
+                    },
+                }
+                {children}
+            }
+        }
+    }
+}
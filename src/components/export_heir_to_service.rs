@@ -8,6 +8,7 @@ use btc_heritage_wallet::heritage_service_api_client::{
 
 use crate::{
     components::{
+        heritages::HeritageAmountDisplaySelect,
         inputs::{
             use_future_error_feedback, use_future_error_feedback_from_parts, use_future_feedback,
         },
@@ -28,28 +29,93 @@ pub enum ExportToServiceConfig {
     },
 }
 
+/// Channels an heir can be contacted through
+///
+/// Only [ContactType::Email] is accepted by the service API for now, the
+/// other channels are validated but cannot be exported yet.
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum ContactType {
     Email,
     Phone,
+    Signal,
+    PostalAddress,
 }
 
 impl ContactType {
-    fn list() -> [Self; 2] {
-        [Self::Email, Self::Phone]
+    fn list() -> [Self; 4] {
+        [Self::Email, Self::Phone, Self::Signal, Self::PostalAddress]
     }
 
     fn display(self) -> &'static str {
         match self {
             ContactType::Email => "Email",
-            ContactType::Phone => "Phone (coming soon)",
+            ContactType::Phone => "Phone/SMS (coming soon)",
+            ContactType::Signal => "Signal (coming soon)",
+            ContactType::PostalAddress => "Postal address (coming soon)",
         }
     }
 
     fn disabled(self) -> bool {
         match self {
-            ContactType::Phone => true,
-            _ => false,
+            ContactType::Email => false,
+            ContactType::Phone | ContactType::Signal | ContactType::PostalAddress => true,
+        }
+    }
+
+    fn type_name(self) -> &'static str {
+        match self {
+            ContactType::Email => "Email",
+            ContactType::Phone => "Phone number",
+            ContactType::Signal => "Signal phone number or username",
+            ContactType::PostalAddress => "Postal address",
+        }
+    }
+
+    fn placeholder(self) -> &'static str {
+        match self {
+            ContactType::Email => "email@example.com",
+            ContactType::Phone => "+1234567890",
+            ContactType::Signal => "+1234567890 or username.01",
+            ContactType::PostalAddress => "Street, Postal code City, Country",
+        }
+    }
+
+    /// Check the format of a contact of this type, `value` being already trimmed
+    fn validate_format(self, value: &str) -> Result<(), CCStr> {
+        let is_phone_number = |value: &str| {
+            let digits = value.strip_prefix('+').unwrap_or_default();
+            (8..=15).contains(&digits.len()) && digits.chars().all(|c| c.is_ascii_digit())
+        };
+        // Signal usernames are a nickname followed by a dot and at least 2 digits
+        let is_signal_username = |value: &str| {
+            value
+                .rsplit_once('.')
+                .is_some_and(|(nickname, discriminator)| {
+                    (3..=32).contains(&nickname.len())
+                        && nickname
+                            .chars()
+                            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+                        && discriminator.len() >= 2
+                        && discriminator.chars().all(|c| c.is_ascii_digit())
+                })
+        };
+        match self {
+            // Emails are validated by their parsing
+            ContactType::Email => Ok(()),
+            ContactType::Phone if !is_phone_number(value) => Err(CCStr::from(
+                "Phone numbers must be in international format, e.g. +1234567890",
+            )),
+            ContactType::Signal if !is_phone_number(value) && !is_signal_username(value) => Err(
+                CCStr::from("Expected a phone number like +1234567890 or a username like name.01"),
+            ),
+            ContactType::PostalAddress
+                if value.split(',').filter(|p| !p.trim().is_empty()).count() < 3 =>
+            {
+                Err(CCStr::from(
+                    "Expected at least a street, a city and a country, separated by commas",
+                ))
+            }
+            _ => Ok(()),
         }
     }
 }
@@ -61,6 +127,8 @@ impl core::str::FromStr for ContactType {
         match s {
             "Email" => Ok(Self::Email),
             "Phone" => Ok(Self::Phone),
+            "Signal" => Ok(Self::Signal),
+            "PostalAddress" => Ok(Self::PostalAddress),
             _ => Err(()),
         }
     }
@@ -71,6 +139,99 @@ impl core::fmt::Display for ContactType {
         f.write_str(match self {
             Self::Email => "Email",
             Self::Phone => "Phone",
+            Self::Signal => "Signal",
+            Self::PostalAddress => "PostalAddress",
+        })
+    }
+}
+
+/// Named sets of [HeirPermission] covering the usual needs
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PermissionPreset {
+    FullVisibility,
+    AmountsHiddenUntilMaturity,
+    NothingUntilMaturity,
+}
+
+impl PermissionPreset {
+    fn list() -> [Self; 3] {
+        [
+            Self::FullVisibility,
+            Self::AmountsHiddenUntilMaturity,
+            Self::NothingUntilMaturity,
+        ]
+    }
+
+    fn display(self) -> &'static str {
+        match self {
+            PermissionPreset::FullVisibility => "Full visibility",
+            PermissionPreset::AmountsHiddenUntilMaturity => "Amounts hidden until maturity",
+            PermissionPreset::NothingUntilMaturity => "Nothing until maturity",
+        }
+    }
+
+    fn explanation(self) -> &'static str {
+        match self {
+            PermissionPreset::FullVisibility => {
+                "The heir knows right away that they will inherit from you, \
+                how much, when, and their position among your heirs."
+            }
+            PermissionPreset::AmountsHiddenUntilMaturity => {
+                "The heir knows right away that they will inherit from you and when, \
+                but only discovers the amount once the inheritance matures."
+            }
+            PermissionPreset::NothingUntilMaturity => {
+                "The heir is not told anything before the inheritance matures. \
+                They are then notified and can see the inheritance details."
+            }
+        }
+    }
+
+    fn permissions(self) -> HeirPermissions {
+        match self {
+            PermissionPreset::FullVisibility => HeirPermissions::from([
+                HeirPermission::IsHeir,
+                HeirPermission::Amount,
+                HeirPermission::Maturity,
+                HeirPermission::OwnerEmail,
+                HeirPermission::Position,
+            ]),
+            PermissionPreset::AmountsHiddenUntilMaturity => HeirPermissions::from([
+                HeirPermission::IsHeir,
+                HeirPermission::Maturity,
+                HeirPermission::OwnerEmail,
+            ]),
+            PermissionPreset::NothingUntilMaturity => HeirPermissions::from([]),
+        }
+    }
+
+    /// The preset matching exactly the given permissions, if any
+    fn matching(permissions: &HeirPermissions) -> Option<Self> {
+        Self::list()
+            .into_iter()
+            .find(|preset| preset.permissions() == *permissions)
+    }
+}
+
+impl core::str::FromStr for PermissionPreset {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "FullVisibility" => Ok(Self::FullVisibility),
+            "AmountsHiddenUntilMaturity" => Ok(Self::AmountsHiddenUntilMaturity),
+            "NothingUntilMaturity" => Ok(Self::NothingUntilMaturity),
+            _ => Err(()),
+        }
+    }
+}
+
+impl core::fmt::Display for PermissionPreset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::FullVisibility => "FullVisibility",
+            Self::AmountsHiddenUntilMaturity => "AmountsHiddenUntilMaturity",
+            Self::NothingUntilMaturity => "NothingUntilMaturity",
         })
     }
 }
@@ -165,10 +326,10 @@ pub fn ExportToServiceSectionForm(
     });
 
     let service_connected = use_memo(move || {
-        matches!(
-            &*state_management::SERVICE_STATUS.read(),
-            Some(ServiceStatus::Connected(_))
-        )
+        state_management::SERVICE_AUTH_STATE
+            .read()
+            .as_ref()
+            .is_some_and(ServiceAuthState::is_connected)
     });
 
     // Update parent signal when internal state changes
@@ -362,6 +523,13 @@ pub fn ExportToServiceSectionForm(
                     // Permissions fieldset
                     ServiceHeirPermissions { service_heir_permissions_state }
 
+                    HeirEmailPreview {
+                        heir_name: if same_as_heir_name() { None } else { Some(service_name()) },
+                        email: main_email_text(),
+                        custom_message: main_message(),
+                        permissions: service_heir_permissions_state(),
+                    }
+
                     // Additional Contacts
                     fieldset { class: "fieldset",
                         legend { class: "fieldset-legend", "Additional Contacts" }
@@ -404,6 +572,9 @@ pub fn ExportToServiceSectionForm(
 fn ServiceHeirPermissions(service_heir_permissions_state: Signal<HeirPermissions>) -> Element {
     log::debug!("ServiceHeirPermissions Rendered");
 
+    let current_preset =
+        use_memo(move || PermissionPreset::matching(&service_heir_permissions_state.read()));
+
     use_drop(|| log::debug!("ServiceHeirPermissions Dropped"));
 
     rsx! {
@@ -414,6 +585,38 @@ fn ServiceHeirPermissions(service_heir_permissions_state: Signal<HeirPermissions
                 "What information the heir can see about their inheritance."
             }
 
+            div { class: "flex flex-row flex-wrap gap-4 items-center mb-2",
+                select {
+                    class: "select w-80",
+                    value: current_preset().map(|preset| preset.to_string()).unwrap_or_default(),
+                    onchange: move |evt| {
+                        if let Ok(preset) = evt.parsed::<PermissionPreset>() {
+                            service_heir_permissions_state.set(preset.permissions());
+                        }
+                    },
+                    option {
+                        value: "",
+                        selected: current_preset().is_none(),
+                        disabled: true,
+                        "Custom"
+                    }
+                    for preset in PermissionPreset::list() {
+                        option {
+                            value: "{preset}",
+                            selected: current_preset() == Some(preset),
+                            "{preset.display()}"
+                        }
+                    }
+                }
+                div { class: "text-sm max-w-xl",
+                    if let Some(preset) = current_preset() {
+                        {preset.explanation()}
+                    } else {
+                        "Custom permissions, see the details below."
+                    }
+                }
+            }
+
             div { class: "flex flex-row flex-wrap gap-8",
                 ServiceHeirPermission {
                     value: HeirPermission::IsHeir,
@@ -453,6 +656,113 @@ fn ServiceHeirPermissions(service_heir_permissions_state: Signal<HeirPermissions
                 span { class: "font-black text-lg", "*" }
                 " This permission will always be avaiable once an inheritance matures"
             }
+            div { class: "flex flex-row flex-wrap gap-4 items-center mt-2",
+                HeritageAmountDisplaySelect {}
+                div { class: "fieldset-label",
+                    "Applies to the heir wallets of this application, the Heritage Service always shows the amounts in BTC."
+                }
+            }
+        }
+    }
+}
+
+/// Approximate rendering of the notification email the service will send to the heir
+///
+/// The actual wording is defined by the Heritage Service, this preview lets the
+/// owner judge how the custom message and the permissions fit in it.
+#[component]
+fn HeirEmailPreview(
+    heir_name: Option<String>,
+    email: String,
+    custom_message: String,
+    permissions: HeirPermissions,
+) -> Element {
+    let owner = match *state_management::SERVICE_AUTH_STATE.read() {
+        Some(ServiceAuthState::Connected { ref email, .. })
+            if permissions.contains(&HeirPermission::OwnerEmail) =>
+        {
+            format!("Your relative ({email})")
+        }
+        _ => "Your relative".to_owned(),
+    };
+    let heir_name = heir_name
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| "[heir name]".to_owned());
+    let email = match email.trim() {
+        "" => "[heir email]".to_owned(),
+        email => email.to_owned(),
+    };
+    // Without the IsHeir permission, the heir only hears from the service at maturity
+    let before_maturity = permissions.contains(&HeirPermission::IsHeir);
+    let visible_infos = [
+        (HeirPermission::Amount, "the amount of the inheritance"),
+        (HeirPermission::Maturity, "when the inheritance will mature"),
+        (HeirPermission::Position, "your position among the heirs"),
+    ]
+    .into_iter()
+    .filter(|(permission, _)| !before_maturity || permissions.contains(permission))
+    .map(|(_, info)| info)
+    .collect::<Vec<_>>();
+    let custom_message = custom_message.trim().to_owned();
+
+    rsx! {
+        div { class: "collapse collapse-arrow bg-base-200 my-2",
+            input { r#type: "checkbox" }
+            div { class: "collapse-title font-semibold", "Preview the email sent to the heir" }
+            div { class: "collapse-content flex flex-col gap-2 text-sm",
+                div { class: "italic font-light",
+                    "Approximate preview, the final wording is defined by the Heritage Service. "
+                    if before_maturity {
+                        "Sent when the heir is declared."
+                    } else {
+                        "Sent when the inheritance matures."
+                    }
+                }
+                div { class: "font-mono",
+                    div { "To: {email}" }
+                    div {
+                        "Subject: "
+                        if before_maturity {
+                            "You have been designated as an heir"
+                        } else {
+                            "An inheritance is available to you"
+                        }
+                    }
+                }
+                div { class: "rounded-box bg-base-100 p-4 flex flex-col gap-2",
+                    div { "Hello {heir_name}," }
+                    if before_maturity {
+                        div {
+                            "{owner} designated you as an heir of their bitcoins \
+                            using the Heritage Service. You will be notified again \
+                            when the inheritance matures."
+                        }
+                    } else {
+                        div {
+                            "{owner} designated you as an heir of their bitcoins \
+                            using the Heritage Service, and the inheritance has now matured."
+                        }
+                    }
+                    if !visible_infos.is_empty() {
+                        div {
+                            "By signing in to the Heritage Service, you can see:"
+                            ul { class: "list-disc list-inside",
+                                for info in visible_infos {
+                                    li { {info} }
+                                }
+                            }
+                        }
+                    }
+                    if !custom_message.is_empty() {
+                        div {
+                            div { "They left you this message:" }
+                            div { class: "border-l-4 border-base-content/20 pl-2 whitespace-pre-wrap",
+                                {custom_message}
+                            }
+                        }
+                    }
+                }
+            }
         }
     }
 }
@@ -519,22 +829,30 @@ fn ServiceHeirAdditionalContact(
 
     // Validation
     let contact_provided = use_memo(move || !contact_text.read().trim().is_empty());
-    let contact = use_memo(move || match contact_type() {
-        ContactType::Email => contact_text
-            .read()
-            .trim()
-            .parse()
-            .map(|email| HeirContact::Email { email })
-            .map_err(log_error_ccstr),
-        ContactType::Phone => Err(CCStr::from("Not supported yet")),
+    let contact = use_memo(move || {
+        let contact_type = contact_type();
+        let contact_text = contact_text.read();
+        let value = contact_text.trim();
+        contact_type.validate_format(value)?;
+        match contact_type {
+            ContactType::Email => value
+                .parse()
+                .map(|email| HeirContact::Email { email })
+                .map_err(log_error_ccstr),
+            ContactType::Phone | ContactType::Signal | ContactType::PostalAddress => {
+                Err(CCStr::from(format!(
+                    "{} is not supported by the service yet",
+                    contact_type.type_name()
+                )))
+            }
+        }
     });
     let contact_error = use_memo(move || {
         if !contact_provided() {
-            let type_name = match contact_type() {
-                ContactType::Email => "Email",
-                ContactType::Phone => "Phone number",
-            };
-            Some(CCStr::from(format!("{type_name} is required")))
+            Some(CCStr::from(format!(
+                "{} is required",
+                contact_type().type_name()
+            )))
         } else if let Err(ref e) = *contact.read() {
             Some(e.clone())
         } else {
@@ -575,10 +893,7 @@ fn ServiceHeirAdditionalContact(
                 r#type: "text",
                 class: "input input-sm w-full",
                 class: if error_display().is_some() { "input-error" },
-                placeholder: match contact_type() {
-                    ContactType::Email => "email@example.com",
-                    ContactType::Phone => "+1234567890",
-                },
+                placeholder: contact_type().placeholder(),
                 value: contact_text,
                 oninput: move |evt| {
                     signal_activity();
@@ -0,0 +1,148 @@
+use crate::prelude::*;
+
+/// Subjects on which a [HelpPopover] can explain things
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HelpTopic {
+    FeePolicy,
+    UtxoSelection,
+    HeritageDelays,
+    MinimumMaturityDelay,
+    LedgerPolicies,
+}
+impl HelpTopic {
+    fn title(self) -> &'static str {
+        match self {
+            HelpTopic::FeePolicy => "Fee Policy",
+            HelpTopic::UtxoSelection => "UTXO Selection",
+            HelpTopic::HeritageDelays => "Maturity Delays",
+            HelpTopic::MinimumMaturityDelay => "Minimum Maturity Delay",
+            HelpTopic::LedgerPolicies => "Ledger Policies",
+        }
+    }
+    fn content(self) -> Element {
+        match self {
+            HelpTopic::FeePolicy => rsx! {
+                p {
+                    "Miners prioritize transactions paying the highest fee per virtual byte.
+                    By default, the fee rate retrieved from the Bitcoin blockchain at the last
+                    synchronization is used."
+                }
+                p {
+                    "Specify a fee rate to get confirmed faster or cheaper, or an absolute fee
+                    if you need the transaction to cost an exact amount."
+                }
+            },
+            HelpTopic::UtxoSelection => rsx! {
+                p {
+                    "Your balance is made of unspent transaction outputs (UTXOs). By default,
+                    the wallet automatically chooses which ones to spend."
+                }
+                ul { class: "list-disc list-inside",
+                    li {
+                        strong { "Include/Exclude: " }
+                        "force or forbid the use of some UTXOs, the wallet completes the selection"
+                    }
+                    li {
+                        strong { "Manual selection: " }
+                        "spend exactly the UTXOs you choose, useful to avoid merging coins of different origins"
+                    }
+                }
+            },
+            HelpTopic::HeritageDelays => rsx! {
+                p {
+                    "Each heir can inherit once the bitcoins have not moved for its maturity delay,
+                    counted from the reference date. Each heir must wait longer than the previous one."
+                }
+                HeritageDelaysTradeOff {}
+            },
+            HelpTopic::MinimumMaturityDelay => rsx! {
+                p {
+                    "Even with an expired Heritage Configuration, heirs have to wait at least this
+                    delay after you receive new bitcoins before being able to inherit them."
+                }
+                p {
+                    "It protects funds received long after the reference date, giving you time
+                    to move them to a new Heritage Configuration."
+                }
+            },
+            HelpTopic::LedgerPolicies => rsx! {
+                p {
+                    "Ledger devices only accept to sign transactions using "
+                    strong { "pre-approved Bitcoin scripts" }
+                    ". Each Heritage Configuration must be registered on the device before
+                    the bitcoins it protects can be spent."
+                }
+                LedgerPoliciesLearnMore {}
+            },
+        }
+    }
+}
+
+/// A small "?" button displaying a short explanation about a [HelpTopic] when clicked
+#[component]
+pub fn HelpPopover(topic: HelpTopic) -> Element {
+    rsx! {
+        div { class: "dropdown dropdown-end align-middle",
+            div {
+                tabindex: "0",
+                role: "button",
+                class: "btn btn-circle btn-ghost btn-xs text-info font-bold",
+                "?"
+            }
+            div {
+                tabindex: "0",
+                class: "dropdown-content card card-sm bg-base-100 z-30 w-96 shadow-lg border border-base-content/10",
+                div { class: "card-body text-sm font-normal text-wrap text-left gap-2",
+                    div { class: "font-bold text-base", {topic.title()} }
+                    {topic.content()}
+                }
+            }
+        }
+    }
+}
+
+/// Trade-off of the maturity delays of a Heritage Configuration
+#[component]
+pub fn HeritageDelaysTradeOff() -> Element {
+    rsx! {
+        div { class: "bg-warning/10 p-4 rounded-lg border border-warning/20",
+            p { class: "font-semibold mb-2 text-warning", "Important Trade-off:" }
+            p { class: "mb-2",
+                "Shorter delays mean your heirs (and your backup) can access funds more
+                quickly, but you'll need to move your funds more frequently to reset
+                the inheritance countdown while you're still active."
+            }
+            p {
+                "A reasonable default is 1 or 2 years before the first heir
+                can inherit, with 3-month intervals between subsequent heirs.
+                This provides adequate protection while not requiring to move
+                fund too often."
+            }
+        }
+    }
+}
+
+/// Background about Ledger wallet policies
+#[component]
+pub fn LedgerPoliciesLearnMore() -> Element {
+    rsx! {
+        div { class: "flex flex-col gap-3 pt-2",
+            p { class: "text-sm opacity-75",
+                "Wallet policies allows the user to securely approve
+                complex Bitcoin scripts once, without compromising
+                useability by requiring explicit user re-approval
+                at each transaction."
+            }
+            div {
+                strong { "BIP 388 - Wallet Policies: " }
+                a {
+                    href: "https://github.com/bitcoin/bips/blob/master/bip-0388.mediawiki",
+                    target: "_blank",
+                    rel: "noopener noreferrer",
+                    class: "text-primary hover:text-primary-focus underline",
+                    "Technical specification for wallet policies"
+                }
+            }
+        }
+    }
+}
@@ -5,6 +5,7 @@ use btc_heritage_wallet::{
     btc_heritage::{
         heritage_config::v1::Heritage, utils::bitcoin_network, HeirConfig, HeritageConfig,
     },
+    heritage_service_api_client::AccountXPubWithStatus,
     OnlineWallet, Wallet,
 };
 use chrono::{Local, NaiveDate};
@@ -13,8 +14,10 @@ use std::collections::{HashMap, HashSet};
 
 use crate::{
     components::{
+        help::{HelpPopover, HelpTopic},
         misc::TextTooltip,
         modal::CloseModalButton,
+        quick_actions::prompt_backup_refresh,
         svg::{
             Close, DrawSvg, Edit, PlusCircle,
             SvgSize::{Size4, Size5},
@@ -24,6 +27,8 @@ use crate::{
     utils::{timestamp_to_date_string, CCStr, CheapClone},
 };
 
+use state_management::{BackupFreshness, LOW_UNUSED_XPUBS};
+
 /// Form for creating or updating a heritage configuration.
 ///
 /// This component provides a user interface for configuring inheritance settings,
@@ -41,6 +46,8 @@ pub fn NewHeritageConfigForm(
     // Context resources
     let mut wallet = use_context::<AsyncSignal<Wallet>>();
     let heirs = use_context::<Memo<Vec<CompositeHeir>>>();
+    let backup_freshness = try_use_context::<FResource<BackupFreshness>>();
+    let database_service = state_management::use_database_service();
 
     // Form state
     let mut creating = use_signal(|| false);
@@ -62,6 +69,17 @@ pub fn NewHeritageConfigForm(
     // Compute if we already have heritage configs
     let has_heritage_configs = use_memo(move || existing_heritage_config.read().is_some());
 
+    // Each new Heritage Configuration consumes an unused Account XPub
+    let account_xpubs = helper_hooks::use_resource_wallet_account_xpubs(wallet);
+    let unused_xpubs_count = use_memo(move || {
+        account_xpubs.lrmap_ok(|account_xpubs| {
+            account_xpubs
+                .iter()
+                .filter(|axpub| matches!(axpub, AccountXPubWithStatus::Unused(_)))
+                .count()
+        })
+    });
+
     // Initialize form with current config data if available
     use_effect(move || {
         if let OnboardingStatus::InProgress(ref onboarding) =
@@ -260,12 +278,13 @@ pub fn NewHeritageConfigForm(
                 alert_success("New Heritage Configuration created");
                 // Close the modal
                 *new_heritage_config_modal.write() = false;
+                let wallet_name = wallet
+                    .with_peek(async |wallet| CCStr::from(wallet.name()))
+                    .await;
+                prompt_backup_refresh(database_service, wallet_name, backup_freshness).await;
             }
             Err(e) => {
-                log::error!("Failed to create a new Heritage Configuration for the wallet: {e}");
-                alert_error(format!(
-                    "Failed to create a new Heritage Configuration: {e}"
-                ));
+                alert_failure("Failed to create a new Heritage Configuration", e);
             }
         };
     };
@@ -282,7 +301,10 @@ pub fn NewHeritageConfigForm(
             // Heirs and Maturity Delays Section
             div { class: "card bg-base-100 shadow-sm",
                 div { class: "card-body",
-                    h3 { class: "card-title text-xl", "Heirs and Maturity Delays" }
+                    h3 { class: "card-title text-xl",
+                        "Heirs and Maturity Delays"
+                        HelpPopover { topic: HelpTopic::HeritageDelays }
+                    }
                     div { class: "mb-2",
                         "Configure which heirs have access to the funds and after how many days."
                     }
@@ -342,7 +364,10 @@ pub fn NewHeritageConfigForm(
             // Minimum Maturity Delay Section
             div { class: "card bg-base-100 shadow-sm",
                 div { class: "card-body",
-                    h3 { class: "card-title text-xl", "Minimum Maturity Delay" }
+                    h3 { class: "card-title text-xl",
+                        "Minimum Maturity Delay"
+                        HelpPopover { topic: HelpTopic::MinimumMaturityDelay }
+                    }
                     div { class: "text-sm mb-4",
                         "The minimum time that heirs must wait after a new transaction is received
                         before being able to inherit it, even if the Heritage Configuration is completely
@@ -382,6 +407,22 @@ pub fn NewHeritageConfigForm(
                 }
             }
 
+            match unused_xpubs_count() {
+                Some(0) => rsx! {
+                    div { class: "alert alert-error",
+                        "The wallet has no unused Account XPub left, the Heritage Configuration cannot be
+                        created. Add Account XPubs from the configuration of the wallet first."
+                    }
+                },
+                Some(count) if count < LOW_UNUSED_XPUBS => rsx! {
+                    div { class: "alert alert-warning",
+                        "Only {count} unused Account XPubs left. Add more from the configuration of the
+                        wallet before they run out, or enable their automatic top-up."
+                    }
+                },
+                _ => rsx! {},
+            }
+
             // Action buttons
             div { class: "flex gap-4 mt-4 justify-center",
 
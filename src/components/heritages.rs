@@ -2,7 +2,10 @@ use crate::prelude::*;
 
 use std::collections::HashMap;
 
-use btc_heritage_wallet::{bitcoin::Amount, btc_heritage::utils::timestamp_now};
+use btc_heritage_wallet::{
+    bitcoin::{Amount, Denomination},
+    btc_heritage::utils::timestamp_now,
+};
 
 use crate::{
     components::{
@@ -14,6 +17,8 @@ use crate::{
     Route,
 };
 
+use state_management::{FiatCurrency, HeritageAmountDisplay};
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct UIHeritage {
     spend_infos: Option<(CCStr, CCStr)>,
@@ -21,8 +26,8 @@ pub struct UIHeritage {
     heir_config_fingerprint: CCStr,
     from_service: bool,
     owner_email: Option<CCStr>,
-    total_amount: Option<UIBtcAmount>,
-    spendable_amount: Option<UIBtcAmount>,
+    total_amount: Option<UIHeritageAmount>,
+    spendable_amount: Option<UIHeritageAmount>,
     spend_disabled: bool,
     heritage_lines: Vec<UIHeritageLine>,
 }
@@ -154,8 +159,8 @@ impl LoadedElement for UIHeritage {
             heir_config_fingerprint: CCStr::place_holder(),
             from_service: false,
             owner_email: None,
-            total_amount: Some(UIBtcAmount::place_holder()),
-            spendable_amount: Some(UIBtcAmount::place_holder()),
+            total_amount: Some(UIHeritageAmount::place_holder()),
+            spendable_amount: Some(UIHeritageAmount::place_holder()),
             spend_disabled: true,
             heritage_lines: vec![UIHeritageLine::place_holder()],
         }
@@ -234,7 +239,7 @@ impl FromRef<ContextualizedHeritages> for UIHeritage {
         for ((maturity, next_heir_maturity, heir_position), group) in grouped_heritages {
             let total_amount = group.iter().map(|h| h.value).sum::<Option<Amount>>();
 
-            let amount = total_amount.map(UIBtcAmount::from);
+            let amount = total_amount.map(UIHeritageAmount);
 
             // Determine if this heritage line is spendable (maturity has passed)
             let spendable = maturity.is_some_and(|ts| ts < now);
@@ -264,8 +269,8 @@ impl FromRef<ContextualizedHeritages> for UIHeritage {
             });
         }
 
-        let total_amount = total_amount.map(UIBtcAmount::from);
-        let spendable_amount = spendable_amount.map(UIBtcAmount::from);
+        let total_amount = total_amount.map(UIHeritageAmount);
+        let spendable_amount = spendable_amount.map(UIHeritageAmount);
 
         Self {
             spend_infos,
@@ -285,7 +290,7 @@ impl FromRef<ContextualizedHeritages> for UIHeritage {
 struct UIHeritageLine {
     spendable: bool,
     maturity: UITimestamp,
-    amount: Option<UIBtcAmount>,
+    amount: Option<UIHeritageAmount>,
     position: UIHeirPosition,
     expiration: UITimestamp,
 }
@@ -318,7 +323,7 @@ impl LoadedElement for UIHeritageLine {
         Self {
             spendable: true,
             maturity: UITimestamp::place_holder(),
-            amount: Some(UIBtcAmount::place_holder()),
+            amount: Some(UIHeritageAmount::place_holder()),
             position: UIHeirPosition::place_holder(),
             expiration: UITimestamp::place_holder(),
         }
@@ -356,3 +361,103 @@ impl LoadedElement for UIHeirPosition {
         }
     }
 }
+
+/// An heritage amount, displayed in the unit chosen with [HeritageAmountDisplaySelect]
+///
+/// It uses the [HeritageAmountDisplayContext] provided in the context by the heir wallet views
+/// and falls back to the usual [UIBtcAmount] display without it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UIHeritageAmount(Amount);
+impl LoadedElement for UIHeritageAmount {
+    type Loader = SkeletonLoader;
+    #[inline(always)]
+    fn element<M: LoadedComponentInputMapper>(self, m: M) -> Element {
+        let amount_display = try_consume_context::<Resource<HeritageAmountDisplayContext>>()
+            .and_then(|amount_display| amount_display.cloned());
+        let Some(HeritageAmountDisplayContext {
+            display,
+            exchange_rate,
+        }) = amount_display
+        else {
+            return UIBtcAmount::from(self.0).element(m);
+        };
+        match (display, exchange_rate) {
+            (HeritageAmountDisplay::Btc, _) => rsx! {
+                span { class: "text-nowrap inline-block",
+                    "{self.0.display_in(Denomination::Bitcoin)} BTC"
+                }
+            },
+            (HeritageAmountDisplay::Sat, _) => rsx! {
+                span { class: "text-nowrap inline-block",
+                    "{self.0.display_in(Denomination::Satoshi)} sat"
+                }
+            },
+            (HeritageAmountDisplay::FiatAtMaturity, Some((currency, rate))) => {
+                let value = currency.format(FiatCurrency::value_of(self.0, rate));
+                let rate = currency.format(rate);
+                rsx! {
+                    span {
+                        class: "text-nowrap inline-block",
+                        title: "{self.0.display_in(Denomination::Bitcoin)} BTC, estimated with the current rate of 1 BTC = {rate}",
+                        "≈ {value}"
+                    }
+                }
+            }
+            // The exchange rate is unavailable
+            (HeritageAmountDisplay::FiatAtMaturity, None) => UIBtcAmount::from(self.0).element(m),
+        }
+    }
+    fn place_holder() -> Self {
+        Self(Amount::from_sat(9_999_999))
+    }
+}
+
+/// Choose the unit of the heritage amounts shown to the heirs
+///
+/// Restarts the [HeritageAmountDisplayContext] found in the context, if any, once saved.
+#[component]
+pub fn HeritageAmountDisplaySelect() -> Element {
+    let database_service = state_management::use_database_service();
+    let mut amount_display = helper_hooks::use_resource_heritage_amount_display();
+    let context_amount_display = try_use_context::<Resource<HeritageAmountDisplayContext>>();
+
+    let mut saving = use_signal(|| false);
+    let save = move |evt: Event<FormData>| async move {
+        let Some(display) = HeritageAmountDisplay::from_code(&evt.value()) else {
+            return;
+        };
+        *saving.write() = true;
+        match state_management::save_heritage_amount_display(database_service, display).await {
+            Ok(()) => {
+                amount_display.restart();
+                if let Some(mut context_amount_display) = context_amount_display {
+                    context_amount_display.restart();
+                }
+            }
+            Err(e) => alert_failure("Failed to save the heritage amounts display", e),
+        }
+        *saving.write() = false;
+    };
+
+    let current = amount_display
+        .cloned()
+        .map(|amount_display| amount_display.display);
+
+    rsx! {
+        label { class: "flex flex-row gap-2 items-center text-sm",
+            span { class: "font-light text-nowrap", "Show the amounts in" }
+            select {
+                class: "select select-sm w-fit",
+                disabled: saving() || current.is_none(),
+                onchange: save,
+                for display in HeritageAmountDisplay::list() {
+                    option {
+                        value: display.code(),
+                        selected: current == Some(display),
+                        {display.display()}
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use crate::prelude::*;
+
+use btc_heritage_wallet::heritage_service_api_client::Fingerprint;
+
+use crate::components::svg::{DrawSvg, Refresh, SvgSize::Size4};
+
+/// Status of the hardware wallets reachable through HWI, with the selection of the one to use
+///
+/// The devices are enumerated when the component is first displayed and on demand,
+/// `selected` is reset when the selected device disappears.
+#[component]
+pub fn HwiDeviceSelect(mut selected: Signal<Option<Fingerprint>>) -> Element {
+    log::debug!("HwiDeviceSelect Rendered");
+
+    let mut refreshing = use_signal(|| false);
+    let mut refresh = move || {
+        refreshing.set(true);
+        state_management::refresh_hwi_status();
+    };
+    use_hook(move || {
+        if state_management::HWI_STATUS.peek().is_none() {
+            refresh();
+        }
+    });
+
+    use_effect(move || {
+        let ready_fingerprints = state_management::hwi_ready_fingerprints();
+        refreshing.set(false);
+        let current = *selected.peek();
+        match current {
+            Some(fg) if ready_fingerprints.contains(&fg) => (),
+            // Select the device directly when it is the only one
+            _ if ready_fingerprints.len() == 1 => selected.set(Some(ready_fingerprints[0])),
+            Some(_) => selected.set(None),
+            None => (),
+        }
+    });
+
+    use_drop(|| log::debug!("HwiDeviceSelect Dropped"));
+
+    rsx! {
+        div { class: "flex flex-col gap-2",
+            div { class: "flex flex-row gap-2 items-center",
+                div { class: "font-semibold grow", "HWI Devices" }
+                button {
+                    class: "btn btn-sm",
+                    disabled: refreshing(),
+                    onclick: move |_| refresh(),
+                    if refreshing() {
+                        span { class: "loading loading-spinner loading-xs" }
+                    } else {
+                        DrawSvg::<Refresh> { size: Size4 }
+                    }
+                    "Refresh"
+                }
+            }
+            match state_management::HWI_STATUS() {
+                None => rsx! {
+                    div { class: "text-sm text-(--color-base-content)/60", "Looking for devices..." }
+                },
+                Some(HwiStatus::Unavailable(e)) => rsx! {
+                    div { class: "alert alert-warning text-sm flex flex-col items-start",
+                        div { "HWI could not be run: {e}" }
+                        div {
+                            "Install HWI (github.com/bitcoin-core/HWI) and make sure the \"hwi\" executable is in your PATH,
+                            or set the HWI_PATH environment variable to its location, then restart the application."
+                        }
+                    }
+                },
+                Some(HwiStatus::Devices(devices)) if devices.is_empty() => rsx! {
+                    div { class: "text-sm text-(--color-base-content)/60",
+                        "No device detected. Connect and unlock your hardware wallet, then refresh."
+                    }
+                },
+                Some(HwiStatus::Devices(devices)) => rsx! {
+                    ul { class: "flex flex-col gap-2",
+                        for device in devices {
+                            li { class: "flex flex-row gap-2 items-center text-sm",
+                                if let Some(fg) = device.fingerprint().filter(|_| device.not_ready_reason().is_none()) {
+                                    input {
+                                        r#type: "radio",
+                                        class: "radio radio-sm",
+                                        name: "hwi_device",
+                                        checked: selected() == Some(fg),
+                                        onchange: move |_| selected.set(Some(fg)),
+                                    }
+                                    span { class: "font-semibold", "{device.model}" }
+                                    span { class: "font-mono", "{fg}" }
+                                } else {
+                                    span { class: "status status-warning" }
+                                    span { class: "font-semibold", "{device.model}" }
+                                    span { class: "text-(--color-base-content)/60",
+                                        {device.not_ready_reason().unwrap_or_default()}
+                                    }
+                                }
+                            }
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
@@ -15,7 +15,10 @@ use btc_heritage_wallet::{
 
 use crate::{
     components::svg::{Cancel, DrawSvg, Update},
-    utils::CCStr,
+    utils::{
+        check_keys_network, decrypt_with_passphrase, descriptor_checksum, CCStr,
+        PASSPHRASE_ENCRYPTED_PREFIX,
+    },
 };
 
 #[component]
@@ -171,7 +174,165 @@ pub fn FileInput(
     }
 }
 
+/// A descriptor found in backup data that is not in the Heritage backup format
+struct ImportedDescriptor {
+    /// The descriptor, with or without its checksum
+    desc: String,
+    /// Index of the next address to derive, if known
+    next_index: Option<u64>,
+    /// The descriptor currently generates the new addresses of the wallet
+    active: bool,
+}
+
+/// Read the descriptors of a JSON document that is not in the Heritage backup format
+///
+/// Returns [None] if the document is not in a known format.
+fn json_imported_descriptors(
+    json: &serde_json::Value,
+) -> Option<Result<Vec<ImportedDescriptor>, CCStr>> {
+    // Electrum wallet files describe their keys with a keystore and cannot hold Taproot scripts
+    if json.get("keystore").is_some() || json.get("wallet_type").is_some() {
+        return Some(Err(CCStr::from(
+            "Electrum wallets cannot hold the Taproot scripts of a Heritage wallet, \
+            export the descriptors from the wallet software that created them",
+        )));
+    }
+    // Bitcoin Core `listdescriptors` output, or an `importdescriptors` payload
+    let entries = match json.get("descriptors") {
+        Some(descriptors) => descriptors.as_array()?,
+        None => json
+            .as_array()
+            .filter(|entries| entries.iter().all(|entry| entry.get("desc").is_some()))?,
+    };
+    Some(
+        entries
+            .iter()
+            .map(|entry| {
+                let desc = entry
+                    .get("desc")
+                    .and_then(serde_json::Value::as_str)
+                    .ok_or_else(|| {
+                        CCStr::from("A descriptor entry has an invalid \"desc\" field")
+                    })?;
+                Ok(ImportedDescriptor {
+                    desc: desc.to_owned(),
+                    next_index: entry
+                        .get("next_index")
+                        .or_else(|| entry.get("next"))
+                        .and_then(serde_json::Value::as_u64),
+                    active: entry
+                        .get("active")
+                        .and_then(serde_json::Value::as_bool)
+                        .unwrap_or_default(),
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Map imported descriptors into an Heritage backup
+///
+/// Heritage wallets only use Taproot descriptors deriving their external addresses
+/// with `/0/*` and their change addresses with `/1/*`, so a missing change descriptor
+/// can be deduced from its external descriptor. Active descriptors come last, as the
+/// current Heritage Configuration is the last one of a backup.
+fn imported_descriptors_to_backup(
+    mut descriptors: Vec<ImportedDescriptor>,
+) -> Result<HeritageWalletBackup, CCStr> {
+    descriptors.sort_by_key(|descriptor| descriptor.active);
+
+    // External descriptor, last external index and last change index
+    let mut subwallets: Vec<(String, Option<u64>, Option<u64>)> = vec![];
+    for descriptor in descriptors {
+        let desc = descriptor.desc.split('#').next().unwrap_or_default().trim();
+        if !desc.starts_with("tr(") {
+            return Err(CCStr::from(
+                "Only the Taproot descriptors of a Heritage wallet can be imported",
+            ));
+        }
+        let last_index = descriptor.next_index.and_then(|next| next.checked_sub(1));
+        let (external, last_external_index, last_change_index) = if desc.contains("<0;1>/*") {
+            (desc.replace("<0;1>/*", "0/*"), last_index, None)
+        } else if desc.contains("/0/*") {
+            (desc.to_owned(), last_index, None)
+        } else if desc.contains("/1/*") {
+            (desc.replace("/1/*", "/0/*"), None, last_index)
+        } else {
+            return Err(CCStr::from(
+                "Descriptors must derive their addresses with /0/* or /1/*",
+            ));
+        };
+        match subwallets.iter_mut().find(|(e, _, _)| *e == external) {
+            Some((_, external_index, change_index)) => {
+                *external_index = external_index.or(last_external_index);
+                *change_index = change_index.or(last_change_index);
+            }
+            None => subwallets.push((external, last_external_index, last_change_index)),
+        }
+    }
+    if subwallets.is_empty() {
+        return Err(CCStr::from("No descriptor found in the backup data"));
+    }
+
+    let with_checksum = |desc: &str| {
+        descriptor_checksum(desc)
+            .map(|checksum| format!("{desc}#{checksum}"))
+            .ok_or_else(|| CCStr::from("Invalid character in a descriptor"))
+    };
+    let backup = subwallets
+        .into_iter()
+        .map(|(external, last_external_index, last_change_index)| {
+            Ok(serde_json::json!({
+                "external_descriptor": with_checksum(&external)?,
+                "change_descriptor": with_checksum(&external.replace("/0/*", "/1/*"))?,
+                "first_use_ts": null,
+                "last_external_index": last_external_index,
+                "last_change_index": last_change_index,
+            }))
+        })
+        .collect::<Result<Vec<_>, CCStr>>()?;
+    serde_json::from_value(serde_json::Value::Array(backup)).map_err(|e| CCStr::from(e.to_string()))
+}
+
+/// Read backup data provided by the user
+///
+/// Besides the Heritage backup format, it accepts the output of Bitcoin Core `listdescriptors`,
+/// an `importdescriptors` payload and plain text with one descriptor per line, as long as they
+/// describe the descriptors of a Heritage wallet.
+fn parse_backup_data(data: &str) -> Result<HeritageWalletBackup, CCStr> {
+    if data.trim_start().starts_with(PASSPHRASE_ENCRYPTED_PREFIX) {
+        return Err(CCStr::from(
+            "The backup data are encrypted, provide the passphrase to decrypt them",
+        ));
+    }
+    check_keys_network(data).map_err(CCStr::from)?;
+    let heritage_backup_error = match serde_json::from_str::<HeritageWalletBackup>(data) {
+        Ok(backup) => return Ok(backup),
+        Err(e) => CCStr::from(e.to_string()),
+    };
+    let descriptors = match serde_json::from_str::<serde_json::Value>(data) {
+        Ok(json) => match json_imported_descriptors(&json) {
+            Some(descriptors) => descriptors?,
+            None => return Err(heritage_backup_error),
+        },
+        Err(_) => data
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| ImportedDescriptor {
+                desc: line.to_owned(),
+                next_index: None,
+                active: false,
+            })
+            .collect(),
+    };
+    imported_descriptors_to_backup(descriptors)
+}
+
 /// Backup restore section
+///
+/// Accepts the Heritage backup format and the other descriptor formats of [parse_backup_data].
+/// Backup data encrypted with a passphrase (printable backup sheet) are decrypted in place.
 #[component]
 pub fn BackupRestoreSection(
     heritage_wallet_backup_state: Signal<Result<HeritageWalletBackup, CCStr>>,
@@ -180,6 +341,33 @@ pub fn BackupRestoreSection(
     // Internal state - not exposed to parent
     let mut backup_data = use_signal(String::new);
     let mut backup_file_path = use_signal(String::new);
+    let mut passphrase = use_signal(String::new);
+    let mut decrypting = use_signal(|| false);
+    let mut decrypt_error = use_signal(|| None::<CCStr>);
+    let is_encrypted = use_memo(move || {
+        backup_data
+            .read()
+            .trim_start()
+            .starts_with(PASSPHRASE_ENCRYPTED_PREFIX)
+    });
+
+    let decrypt_backup = move || async move {
+        *decrypting.write() = true;
+        let data = backup_data();
+        let passphrase = passphrase();
+        match tokio::task::spawn_blocking(move || decrypt_with_passphrase(&data, &passphrase))
+            .await
+            .map_err(log_error)
+            .and_then(|decrypted| decrypted)
+        {
+            Ok(decrypted) => {
+                decrypt_error.set(None);
+                backup_data.set(decrypted);
+            }
+            Err(e) => decrypt_error.set(Some(CCStr::from(e))),
+        }
+        *decrypting.write() = false;
+    };
 
     // Internal validation
 
@@ -227,14 +415,8 @@ pub fn BackupRestoreSection(
 
     // Update parent signal when internal state changes
     use_effect(move || {
-        heritage_wallet_backup_state.set(
-            serde_json::from_str::<HeritageWalletBackup>(backup_data.read().as_str()).map_err(
-                |e| {
-                    log::warn!("{e}");
-                    CCStr::from(e.to_string())
-                },
-            ),
-        );
+        heritage_wallet_backup_state
+            .set(parse_backup_data(backup_data.read().as_str()).inspect_err(|e| log::warn!("{e}")));
     });
 
     rsx! {
@@ -267,7 +449,7 @@ pub fn BackupRestoreSection(
                     class: if error_display().is_some() { "textarea-error" },
                     class: if success_display() { "textarea-success" },
                     rows: "12",
-                    placeholder: "Paste your backup string here...",
+                    placeholder: "Paste your backup string or descriptors here...",
                     value: backup_data(),
                     oninput: move |evt| {
                         signal_activity();
@@ -290,6 +472,39 @@ pub fn BackupRestoreSection(
                     }
                 }
             }
+            if is_encrypted() {
+                fieldset { class: "fieldset",
+                    legend { class: "fieldset-legend", "Backup Passphrase" }
+                    div { class: "flex gap-2",
+                        input {
+                            r#type: "password",
+                            class: "input w-full",
+                            class: if decrypt_error().is_some() { "input-error" },
+                            placeholder: "Enter the passphrase of the backup",
+                            value: passphrase(),
+                            oninput: move |evt| *passphrase.write() = evt.value(),
+                            onkeydown: move |evt| {
+                                if evt.key() == Key::Enter && !decrypting() {
+                                    spawn(decrypt_backup());
+                                }
+                            },
+                            disabled: decrypting(),
+                        }
+                        button {
+                            class: "btn btn-primary",
+                            disabled: decrypting() || passphrase.read().is_empty(),
+                            onclick: move |_| decrypt_backup(),
+                            if decrypting() {
+                                span { class: "loading loading-spinner" }
+                            }
+                            "Decrypt"
+                        }
+                    }
+                    if let Some(e) = decrypt_error() {
+                        div { class: "fieldset-label text-error", "{e}" }
+                    }
+                }
+            }
         }
     }
 }
@@ -363,6 +578,11 @@ pub fn use_future_feedback_with_delay(
     (feed_back_active, signal_activity, onfocusout)
 }
 
+/// Rename section of a database item
+///
+/// Unlike the other edits, the rename is not applied optimistically: the database items
+/// can only be renamed through the database and the user leaves the view once it succeeds,
+/// so there is no displayed value to update ahead of the database.
 #[component]
 pub fn RenameDatabaseItem<DBI: DatabaseItem + 'static + Send>() -> Element {
     let database_item = use_context::<AsyncSignal<DBI>>();
@@ -370,7 +590,7 @@ pub fn RenameDatabaseItem<DBI: DatabaseItem + 'static + Send>() -> Element {
     let database_service = state_management::use_database_service();
 
     let existing_dbi_names = use_resource(move || async move {
-        state_management::blocking_db_service_operation(database_service, |db| {
+        state_management::blocking_db_service_operation(database_service, "List the names", |db| {
             DBI::list_names(&db).unwrap_or_default()
         })
         .await
@@ -430,22 +650,18 @@ pub fn RenameDatabaseItem<DBI: DatabaseItem + 'static + Send>() -> Element {
         };
 
         let s_current_name = current_name().to_string();
-        let Ok(mut owned_dbi) =
-            state_management::blocking_db_service_operation(database_service, move |db| {
-                DBI::load(&db, &s_current_name).map_err(log_error)
-            })
-            .await
+        let Ok(owned_dbi) = state_management::blocking_db_service_operation(
+            database_service,
+            "Load the item to rename",
+            move |db| DBI::load(&db, &s_current_name).map_err(log_error),
+        )
+        .await
         else {
             return abort(&format!("Internal error"));
         };
 
-        // Change in database
-        let s_new_name = new_name();
-        match state_management::blocking_db_service_operation(database_service, move |mut db| {
-            owned_dbi.db_rename(&mut db, s_new_name)
-        })
-        .await
-        {
+        // Change in database, along with the references to the item
+        match state_management::rename_dbitem(database_service, owned_dbi, new_name()).await {
             Ok(()) => {
                 let msg = format!("Name changed successfully to {}", new_name.read());
                 alert_success(&msg);
@@ -0,0 +1,158 @@
+use crate::prelude::*;
+
+use std::collections::BTreeMap;
+
+use btc_heritage_wallet::{btc_heritage::AccountXPubId, ledger::WalletPolicy, LedgerPolicy};
+
+use crate::utils::CCStr;
+
+/// UI representation of a wallet policy for display
+#[derive(Debug, Clone, PartialEq)]
+pub struct UIWalletPolicy {
+    name: CCStr,
+    descriptor_template: CCStr,
+    keys: Vec<CCStr>,
+}
+
+impl LoadedElement for UIWalletPolicy {
+    type Loader = TransparentLoader;
+
+    fn element<M: LoadedComponentInputMapper>(self, _m: M) -> Element {
+        rsx! {
+            div { class: "text-base flex flex-col gap-4",
+                div {
+                    h4 { class: "font-semibold text-base mb-2", "Account Name:" }
+                    div { class: "font-mono p-2", "{self.name}" }
+                }
+
+                div {
+                    h4 { class: "font-semibold text-base mb-2", "Wallet Policy:" }
+                    div { class: "font-mono p-2 break-all", "{self.descriptor_template}" }
+                }
+
+                if !self.keys.is_empty() {
+                    div {
+                        h4 { class: "font-semibold text-base mb-2", "Keys" }
+                        div { class: "flex flex-col gap-2",
+                            for (index , key) in self.keys.iter().enumerate() {
+                                div { class: "flex items-center gap-2",
+                                    div { class: "font-mono font-bold text-sm bg-primary text-primary-content rounded px-2 py-1 flex-shrink-0",
+                                        "@{index}"
+                                    }
+                                    div { class: "font-mono p-2 break-all flex-1", "{key}" }
+                                }
+                            }
+                        }
+                    }
+                } else {
+                    div {
+                        h4 { class: "font-semibold text-base mb-2", "Keys" }
+                        div { class: "text-sm text-base-content/60 italic",
+                            "Key details are not available for this policy type"
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn place_holder() -> Self {
+        Self {
+            name: CCStr::place_holder(),
+            descriptor_template: CCStr::place_holder(),
+            keys: vec![CCStr::place_holder()],
+        }
+    }
+}
+
+impl FromRef<WalletPolicy> for UIWalletPolicy {
+    fn from_ref(policy: &WalletPolicy) -> Self {
+        Self {
+            name: CCStr::from(policy.name.clone()),
+            descriptor_template: CCStr::from(policy.descriptor_template.clone()),
+            keys: policy
+                .keys
+                .iter()
+                .map(|k| CCStr::from(k.to_string()))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct UILedgerPolicyLine {
+    policy_id: AccountXPubId,
+    policy: CCStr,
+}
+impl LoadedElement for UILedgerPolicyLine {
+    type Loader = TransparentLoader;
+
+    fn element<M: LoadedComponentInputMapper>(self, m: M) -> Element {
+        rsx! {
+            tr {
+                td { class: "text-center align-middle",
+                    LoadedComponent { input: m.map(self.policy_id) }
+                }
+                td { class: "font-mono text-sm break-all",
+                    LoadedComponent { input: m.map(self.policy) }
+                }
+            }
+        }
+    }
+
+    fn place_holder() -> Self {
+        Self {
+            policy_id: 0,
+            policy: CCStr::place_holder(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UILedgerPoliciesTable(BTreeMap<AccountXPubId, UILedgerPolicyLine>);
+impl LoadedElement for UILedgerPoliciesTable {
+    type Loader = TransparentLoader;
+
+    fn element<M: LoadedComponentInputMapper>(self, m: M) -> Element {
+        rsx! {
+            table { class: "table table-zebra",
+                thead {
+                    tr {
+                        th { class: "w-32", "Account ID" }
+                        th { "Policy" }
+                    }
+                }
+                tbody {
+                    LoadedComponent { input: m.map(self.0) }
+                }
+            }
+        }
+    }
+
+    fn place_holder() -> Self {
+        let line = UILedgerPolicyLine::place_holder();
+        Self([(line.policy_id, line)].into())
+    }
+}
+impl LoadedSuccessConversionMarker
+    for TypeCouple<BTreeMap<AccountXPubId, LedgerPolicy>, UILedgerPoliciesTable>
+{
+}
+impl FromRef<BTreeMap<AccountXPubId, LedgerPolicy>> for UILedgerPoliciesTable {
+    fn from_ref(policies: &BTreeMap<AccountXPubId, LedgerPolicy>) -> Self {
+        Self(
+            policies
+                .iter()
+                .map(|(id, policy)| {
+                    (
+                        *id,
+                        UILedgerPolicyLine {
+                            policy_id: *id,
+                            policy: CCStr::from(policy.to_string()),
+                        },
+                    )
+                })
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,284 @@
+use crate::prelude::*;
+
+use std::{future::Future, rc::Rc};
+
+use btc_heritage_wallet::{
+    bitcoin::{Address, Amount},
+    btc_heritage::{utils::bitcoin_network, PartiallySignedTransaction},
+    errors::Error,
+    heritage_service_api_client::AccountXPubWithStatus,
+    AnyKeyProvider, AnyOnlineWallet, DatabaseItem, HeirWallet, KeyProvider, OnlineWallet, Wallet,
+};
+use futures_util::future::{select, Either};
+use tokio::sync::Notify;
+
+use crate::{
+    components::{
+        modal::Modal,
+        svg::{Cancel, DrawSvg},
+    },
+    utils::{log_error, CCStr},
+};
+
+/// Past this delay (5 minutes) without an answer from the device, the operation is abandoned
+const LEDGER_PROMPT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+
+/// Signers that may be backed by a Ledger device
+pub trait LedgerSigner {
+    fn uses_ledger(&self) -> bool;
+    /// Identifies the signer among the wallets and heir wallets
+    fn ledger_signer_id(&self) -> String;
+    /// Check that the connected Ledger holds the seed of the signer and not only a seed
+    /// with the same fingerprint, so any device restored from that seed can be used
+    async fn verify_ledger_seed(&self) -> Result<bool, String>;
+}
+impl LedgerSigner for Wallet {
+    fn uses_ledger(&self) -> bool {
+        matches!(self.key_provider(), AnyKeyProvider::Ledger(_))
+    }
+    fn ledger_signer_id(&self) -> String {
+        format!("{}{}", Wallet::item_key_prefix(), self.name())
+    }
+    async fn verify_ledger_seed(&self) -> Result<bool, String> {
+        // Sign-only wallets have no Account XPub to compare with
+        if matches!(self.online_wallet(), AnyOnlineWallet::None) {
+            return Ok(true);
+        }
+        let known_account_xpub = match self.list_account_xpubs().await {
+            Ok(account_xpubs) => account_xpubs.into_iter().next().map(|axpub| match axpub {
+                AccountXPubWithStatus::Used(axpub) | AccountXPubWithStatus::Unused(axpub) => axpub,
+            }),
+            Err(e) => {
+                log::warn!("Cannot verify the Ledger seed, relying on its fingerprint: {e}");
+                None
+            }
+        };
+        let Some(known_account_xpub) = known_account_xpub else {
+            return Ok(true);
+        };
+        let id = known_account_xpub.descriptor_id();
+        let derived_account_xpubs = self
+            .derive_accounts_xpubs(id..id + 1)
+            .await
+            .map_err(log_error)?;
+        Ok(derived_account_xpubs.first() == Some(&known_account_xpub))
+    }
+}
+impl LedgerSigner for HeirWallet {
+    fn uses_ledger(&self) -> bool {
+        matches!(self.key_provider(), AnyKeyProvider::Ledger(_))
+    }
+    fn ledger_signer_id(&self) -> String {
+        format!("{}{}", HeirWallet::item_key_prefix(), self.name())
+    }
+    async fn verify_ledger_seed(&self) -> Result<bool, String> {
+        // An heir wallet only knows the fingerprint of its key, the heritages
+        // it lists are already bound to the seed of the connected device
+        Ok(true)
+    }
+}
+
+/// A screen the user is expected to go through on the Ledger device
+#[derive(Debug, Clone, PartialEq)]
+pub struct LedgerPrompt {
+    pub title: CCStr,
+    pub detail: Option<CCStr>,
+}
+impl LedgerPrompt {
+    fn new(title: impl Into<CCStr>, detail: Option<String>) -> Self {
+        Self {
+            title: title.into(),
+            detail: detail.map(CCStr::from),
+        }
+    }
+
+    /// The screens the Ledger Bitcoin application displays to sign `psbt`
+    ///
+    /// Outputs carrying key origins belong to the wallet (change) and are not displayed by the device.
+    pub fn for_psbt(psbt: &PartiallySignedTransaction) -> Vec<Self> {
+        let external_outputs = psbt
+            .unsigned_tx
+            .output
+            .iter()
+            .zip(psbt.outputs.iter())
+            .filter(|(_, output)| output.tap_key_origins.is_empty())
+            .map(|(txout, _)| txout)
+            .collect::<Vec<_>>();
+        let count = external_outputs.len();
+
+        let mut prompts = vec![LedgerPrompt::new(
+            "Confirm the account",
+            Some("Spending from a registered Heritage account".to_owned()),
+        )];
+        prompts.extend(external_outputs.into_iter().enumerate().map(|(i, txout)| {
+            let address = Address::from_script(&txout.script_pubkey, bitcoin_network::get())
+                .map(|address| address.to_string())
+                .unwrap_or_else(|_| "an unknown script".to_owned());
+            LedgerPrompt::new(
+                format!("Review output {} of {count}", i + 1),
+                Some(format!("{} to {address}", Amount::from_sat(txout.value))),
+            )
+        }));
+        let input_value = psbt
+            .inputs
+            .iter()
+            .map(|input| input.witness_utxo.as_ref().map(|utxo| utxo.value))
+            .sum::<Option<u64>>();
+        let output_value = psbt
+            .unsigned_tx
+            .output
+            .iter()
+            .map(|txout| txout.value)
+            .sum::<u64>();
+        prompts.push(LedgerPrompt::new(
+            "Review the fees",
+            input_value
+                .and_then(|input_value| input_value.checked_sub(output_value))
+                .map(|fee| Amount::from_sat(fee).to_string()),
+        ));
+        prompts.push(LedgerPrompt::new("Sign the transaction", None));
+        prompts
+    }
+}
+
+/// Why an operation on the Ledger device did not succeed
+#[derive(Debug, Clone, PartialEq)]
+pub enum LedgerOperationError {
+    /// The user rejected the operation on the device
+    Refused,
+    /// The device did not answer in time
+    Timeout,
+    /// The device is not on the Bitcoin application
+    WrongApp,
+    /// The user stopped waiting for the device
+    Cancelled,
+    Other(CCStr),
+}
+impl From<Error> for LedgerOperationError {
+    fn from(e: Error) -> Self {
+        if matches!(e, Error::WrongLedgerApplication) {
+            return LedgerOperationError::WrongApp;
+        }
+        let message = e.to_string();
+        let lowercase = message.to_lowercase();
+        // 0x6985 is the status word of a user rejection on the device
+        if lowercase.contains("6985")
+            || lowercase.contains("denied")
+            || lowercase.contains("reject")
+        {
+            LedgerOperationError::Refused
+        } else {
+            LedgerOperationError::Other(CCStr::from(message))
+        }
+    }
+}
+impl core::fmt::Display for LedgerOperationError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            LedgerOperationError::Refused => write!(f, "the operation was rejected on the Ledger"),
+            LedgerOperationError::Timeout => write!(f, "the Ledger did not answer in time"),
+            LedgerOperationError::WrongApp => {
+                write!(f, "open the Bitcoin application on the Ledger and retry")
+            }
+            LedgerOperationError::Cancelled => write!(
+                f,
+                "cancelled, reject the operation on the Ledger if it is still displayed"
+            ),
+            LedgerOperationError::Other(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// Lets the user stop waiting for the Ledger device
+#[derive(Clone, Default)]
+pub struct LedgerCancel(Rc<Notify>);
+impl LedgerCancel {
+    pub fn cancel(&self) {
+        // Only wakes the running operation, a cancel never carries over to the next one
+        self.0.notify_waiters();
+    }
+
+    /// Drive `work` to completion, unless it times out or the user cancels it
+    pub async fn run<T>(
+        &self,
+        work: impl Future<Output = Result<T, Error>>,
+    ) -> Result<T, LedgerOperationError> {
+        let cancelled = std::pin::pin!(self.0.notified());
+        let work = std::pin::pin!(tokio::time::timeout(LEDGER_PROMPT_TIMEOUT, work));
+        let result = match select(work, cancelled).await {
+            Either::Left((Ok(Ok(output)), _)) => Ok(output),
+            Either::Left((Ok(Err(e)), _)) => Err(LedgerOperationError::from(e)),
+            Either::Left((Err(_), _)) => Err(LedgerOperationError::Timeout),
+            Either::Right(_) => Err(LedgerOperationError::Cancelled),
+        };
+        if matches!(result, Err(LedgerOperationError::WrongApp)) {
+            state_management::refresh_ledger_status();
+        }
+        result
+    }
+}
+impl PartialEq for LedgerCancel {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Overlay mirroring the screens displayed by the Ledger during an operation
+///
+/// `current_prompt` highlights the screen being displayed when it is known,
+/// otherwise all the screens are listed as what to expect.
+#[component]
+pub fn LedgerPromptsOverlay(
+    is_open: Signal<bool>,
+    title: &'static str,
+    prompts: ReadOnlySignal<Vec<LedgerPrompt>>,
+    current_prompt: ReadOnlySignal<Option<usize>>,
+    cancel: LedgerCancel,
+    children: Element,
+) -> Element {
+    rsx! {
+        Modal { is_open, persistent: true,
+            div { class: "w-xl flex flex-col gap-4",
+                div { class: "flex flex-row items-center gap-4",
+                    span { class: "loading loading-spinner loading-lg text-primary" }
+                    h2 { class: "text-2xl font-bold grow", {title} }
+                }
+                div { class: "text-base font-light",
+                    if current_prompt().is_some() {
+                        "Follow the instructions on your Ledger device."
+                    } else {
+                        "Your Ledger device will successively ask you to:"
+                    }
+                }
+                ul { class: "steps steps-vertical",
+                    for (index , prompt) in prompts().into_iter().enumerate() {
+                        li {
+                            key: "{index}",
+                            class: "step",
+                            class: if current_prompt().is_some_and(|current| index <= current) { "step-primary" },
+                            div { class: "flex flex-col items-start text-left",
+                                div {
+                                    class: "font-semibold",
+                                    class: if current_prompt() == Some(index) { "text-primary" },
+                                    {prompt.title}
+                                }
+                                if let Some(detail) = prompt.detail {
+                                    div { class: "text-sm font-mono break-all", {detail} }
+                                }
+                            }
+                        }
+                    }
+                }
+                {children}
+                div { class: "flex justify-end",
+                    button {
+                        class: "btn btn-outline btn-primary",
+                        onclick: move |_| cancel.cancel(),
+                        DrawSvg::<Cancel> {}
+                        "Cancel"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -86,6 +86,43 @@ pub fn Teleport(children: Element) -> Element {
     }
 }
 
+/// Call `on_visible` once, the first time its content is scrolled into view
+#[component]
+pub fn OnVisible(on_visible: EventHandler<()>, children: Element) -> Element {
+    let id = use_hook(|| uuid::Uuid::new_v4());
+
+    use_future(move || async move {
+        let mut eval = document::eval(&format!(
+            r#"
+            const observe = () => {{
+                const element = document.getElementById("{id}");
+                if (!element) {{
+                    requestAnimationFrame(observe);
+                    return;
+                }}
+                const observer = new IntersectionObserver((entries) => {{
+                    if (entries.some((entry) => entry.isIntersecting)) {{
+                        observer.disconnect();
+                        dioxus.send(true);
+                    }}
+                }}, {{ rootMargin: "200px" }});
+                observer.observe(element);
+            }};
+            observe();
+            await new Promise(() => {{}});
+        "#
+        ));
+        if eval.recv::<bool>().await.is_ok() {
+            log::debug!("OnVisible {id} - visible");
+            on_visible.call(());
+        }
+    });
+
+    rsx! {
+        div { id: "{id}", {children} }
+    }
+}
+
 macro_rules! arcstr_loaded_elem {
     ($name:ident, $ph:literal) => {
         #[derive(Debug, Clone, PartialEq)]
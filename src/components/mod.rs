@@ -5,16 +5,25 @@ pub mod balance;
 pub mod copy;
 pub mod create_key_provider;
 pub mod delete;
+pub mod export;
 pub mod export_heir_to_service;
+pub mod help;
 pub mod heritage_configuration;
 pub mod heritages;
+pub mod hwi;
 pub mod inputs;
+pub mod ledger_policies;
+pub mod ledger_prompt;
 pub mod misc;
 pub mod modal;
+pub mod notes;
+pub mod notifications;
 pub mod onboarding;
+pub mod qr_scanner;
 pub mod qrcode;
 pub mod quick_actions;
 pub mod spend;
 pub mod svg;
+pub mod tags;
 pub mod timestamp;
 pub mod transaction;
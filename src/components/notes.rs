@@ -0,0 +1,91 @@
+use crate::prelude::*;
+
+use crate::utils::CCStr;
+
+/// A block of notes written with the lightweight markup of [NotesText]
+#[derive(Debug, Clone, PartialEq)]
+enum NotesBlock {
+    Heading(String),
+    List(Vec<String>),
+    Paragraph(Vec<String>),
+}
+
+/// Split notes into blocks: `# ` starts a heading, `- ` or `* ` a list item,
+/// and an empty line ends a paragraph
+fn notes_blocks(text: &str) -> Vec<NotesBlock> {
+    let mut blocks = Vec::new();
+    for line in text.lines().map(str::trim_end) {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() {
+            blocks.push(NotesBlock::Paragraph(Vec::new()));
+        } else if let Some(heading) = trimmed.strip_prefix("# ") {
+            blocks.push(NotesBlock::Heading(heading.to_owned()));
+        } else if let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+        {
+            match blocks.last_mut() {
+                Some(NotesBlock::List(items)) => items.push(item.to_owned()),
+                _ => blocks.push(NotesBlock::List(vec![item.to_owned()])),
+            }
+        } else {
+            match blocks.last_mut() {
+                Some(NotesBlock::Paragraph(lines)) => lines.push(trimmed.to_owned()),
+                _ => blocks.push(NotesBlock::Paragraph(vec![trimmed.to_owned()])),
+            }
+        }
+    }
+    blocks.retain(|block| !matches!(block, NotesBlock::Paragraph(lines) if lines.is_empty()));
+    blocks
+}
+
+/// Render a line of notes, `**text**` being displayed in bold
+fn notes_inline(line: &str) -> Element {
+    rsx! {
+        for (i, part) in line.split("**").enumerate() {
+            // Odd parts are between a pair of `**`
+            if i % 2 == 1 {
+                span { class: "font-bold", "{part}" }
+            } else {
+                "{part}"
+            }
+        }
+    }
+}
+
+/// Display notes written with a lightweight markup
+///
+/// Supports headings (`# `), list items (`- ` or `* `), bold text (`**text**`)
+/// and paragraphs separated by empty lines.
+#[component]
+pub fn NotesText(text: CCStr) -> Element {
+    let blocks = notes_blocks(&text);
+    rsx! {
+        div { class: "flex flex-col gap-2 text-base wrap-break-word",
+            for block in blocks {
+                match block {
+                    NotesBlock::Heading(heading) => rsx! {
+                        h3 { class: "text-lg font-bold", {notes_inline(&heading)} }
+                    },
+                    NotesBlock::List(items) => rsx! {
+                        ul { class: "list-disc list-inside",
+                            for item in items {
+                                li { {notes_inline(&item)} }
+                            }
+                        }
+                    },
+                    NotesBlock::Paragraph(lines) => rsx! {
+                        p {
+                            for (i, line) in lines.iter().enumerate() {
+                                if i > 0 {
+                                    br {}
+                                }
+                                {notes_inline(line)}
+                            }
+                        }
+                    },
+                }
+            }
+        }
+    }
+}
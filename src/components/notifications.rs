@@ -0,0 +1,135 @@
+use crate::prelude::*;
+
+use crate::{
+    components::{
+        alerts::AlertClass,
+        app_config::AppConfigDropDown,
+        svg::{Bell, DrawSvg, SvgSize::Size8},
+    },
+    utils::timestamp_to_string,
+};
+
+/// Notification center dropdown, listing the history of the alerts displayed to the user
+#[component]
+pub fn NotificationCenter() -> Element {
+    log::debug!("NotificationCenter reload");
+
+    let mut severity_filter: Signal<Option<AlertClass>> = use_signal(|| None);
+
+    let unread_count = use_memo(|| {
+        state_management::NOTIFICATIONS
+            .read()
+            .iter()
+            .filter(|n| !n.read)
+            .count()
+    });
+    let filtered_notifications = use_memo(move || {
+        let filter = severity_filter();
+        state_management::NOTIFICATIONS
+            .read()
+            .iter()
+            .filter(|n| filter.map_or(true, |f| f == n.alert_class))
+            .cloned()
+            .collect::<Vec<_>>()
+    });
+
+    use_drop(|| log::debug!("NotificationCenter Dropped"));
+
+    rsx! {
+        AppConfigDropDown {
+            head: rsx! {
+                div { class: "indicator",
+                    if unread_count() > 0 {
+                        span { class: "indicator-item badge badge-xs badge-primary", "{unread_count}" }
+                    }
+                    DrawSvg::<Bell> { size: Size8 }
+                }
+            },
+            div { class: "flex flex-col gap-2 px-3",
+                div { class: "text-lg font-bold text-center", "Notifications" }
+                div { class: "flex flex-row gap-2 items-center",
+                    select {
+                        class: "select select-sm grow",
+                        onchange: move |evt| {
+                            *severity_filter.write() = match evt.value().as_str() {
+                                "success" => Some(AlertClass::Success),
+                                "info" => Some(AlertClass::Info),
+                                "warn" => Some(AlertClass::Warn),
+                                "error" => Some(AlertClass::Error),
+                                _ => None,
+                            };
+                        },
+                        option { value: "all", selected: severity_filter().is_none(), "All severities" }
+                        option {
+                            value: "error",
+                            selected: severity_filter() == Some(AlertClass::Error),
+                            "Errors"
+                        }
+                        option {
+                            value: "warn",
+                            selected: severity_filter() == Some(AlertClass::Warn),
+                            "Warnings"
+                        }
+                        option {
+                            value: "info",
+                            selected: severity_filter() == Some(AlertClass::Info),
+                            "Info"
+                        }
+                        option {
+                            value: "success",
+                            selected: severity_filter() == Some(AlertClass::Success),
+                            "Success"
+                        }
+                    }
+                    button {
+                        class: "btn btn-sm btn-outline",
+                        disabled: unread_count() == 0,
+                        onclick: move |_| state_management::mark_all_notifications_read(),
+                        "Mark all read"
+                    }
+                    button {
+                        class: "btn btn-sm btn-outline btn-error",
+                        disabled: state_management::NOTIFICATIONS.read().is_empty(),
+                        onclick: move |_| state_management::clear_notifications(),
+                        "Clear"
+                    }
+                }
+                hr { class: "h-px border-t border-solid border-gray-500" }
+                if filtered_notifications.read().is_empty() {
+                    div { class: "text-sm italic text-center py-4", "No notification" }
+                }
+                for notification in filtered_notifications() {
+                    NotificationItem { key: "{notification.id}", notification }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn NotificationItem(notification: Notification) -> Element {
+    let Notification {
+        id,
+        alert_class,
+        title,
+        message,
+        timestamp,
+        read,
+    } = notification;
+    rsx! {
+        div {
+            class: "flex flex-col p-2 rounded-box cursor-pointer hover:bg-base-200",
+            class: if !read { "bg-base-300" },
+            onclick: move |_| state_management::mark_notification_read(id),
+            div { class: "flex flex-row gap-2 items-center",
+                if !read {
+                    span { class: "status status-primary" }
+                }
+                span { class: "text-sm font-bold {alert_class.text_class()}", "{title}" }
+                div { class: "grow" }
+                span { class: "text-xs font-light", {timestamp_to_string(timestamp)} }
+            }
+            div { class: "text-sm", "{message}" }
+        }
+    }
+}
@@ -1,20 +1,27 @@
-use btc_heritage_wallet::btc_heritage::utils::timestamp_now;
+use btc_heritage_wallet::{
+    btc_heritage::utils::{bitcoin_network, timestamp_now},
+    BoundFingerprint, KeyProvider, LedgerKey,
+};
 
 use crate::prelude::*;
 
 use crate::{
     components::{
+        app_config::LedgerServiceStatusWithDesc,
         misc::TextTooltip,
         modal::Modal,
         svg::{
-            AlertCircle, Close, DrawSvg, InfoCircle,
-            SvgSize::{Size5, Size8},
+            AlertCircle, CheckCircle, ChevronRight, Close, DrawSvg, InfoCircle,
+            SvgSize::{Size4, Size5, Size8},
         },
     },
     onboarding::Exclusive,
-    utils::async_sleep,
+    utils::{async_sleep, log_error, CCStr},
 };
 
+/// Explanation modal step the user asked to see again from the progress checklist
+static REPLAYED_MODAL_STEP: GlobalSignal<Option<OnboardingStep>> = Signal::global(|| None);
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MaybeHighlightProgressType {
     Click,
@@ -253,7 +260,8 @@ pub fn OnboardingMessage() -> Element {
 
 /// Modal component linked to an onboarding step.
 ///
-/// The modal is displayed when the onboarding is at the specified step.
+/// The modal is displayed when the onboarding is at the specified step,
+/// or when the user asks to see it again from the [OnboardingProgressSidebar].
 /// It can be closed by a "Got it!" button which will progress the onboarding
 /// to the next step.
 #[component]
@@ -266,7 +274,9 @@ pub fn OnboardingInfoModal(
 
     // Check if this modal should be shown
     let should_show = use_memo(move || match *state_management::ONBOARDING_STATUS.read() {
-        OnboardingStatus::InProgress(ref onboarding) => onboarding.is_active(step, None),
+        OnboardingStatus::InProgress(ref onboarding) => {
+            onboarding.is_active(step, None) || REPLAYED_MODAL_STEP() == Some(step)
+        }
         _ => false,
     });
     let mut is_open = use_signal(|| false);
@@ -277,6 +287,13 @@ pub fn OnboardingInfoModal(
     let handle_close = move |_| {
         log::debug!("OnboardingInfoModal closed - Step ID: {:?}", step);
 
+        if REPLAYED_MODAL_STEP
+            .peek()
+            .is_some_and(|replayed| replayed == step)
+        {
+            *REPLAYED_MODAL_STEP.write() = None;
+            return;
+        }
         if let OnboardingStatus::InProgress(ref mut onboarding) =
             *state_management::ONBOARDING_STATUS.write()
         {
@@ -308,3 +325,336 @@ pub fn OnboardingInfoModal(
         }
     }
 }
+
+/// Collapsible checklist of the steps of the in-progress onboarding, grouped by chapter.
+///
+/// Completed explanation steps can be clicked to display their modal again.
+#[component]
+pub fn OnboardingProgressSidebar() -> Element {
+    log::debug!("OnboardingProgressSidebar Rendered");
+
+    let mut collapsed = use_signal(|| true);
+
+    // (chapter name, [(step index, step)])
+    let chapters = use_memo(move || match *state_management::ONBOARDING_STATUS.read() {
+        OnboardingStatus::InProgress(ref onboarding) => {
+            let chapters = onboarding.chapters();
+            chapters
+                .iter()
+                .enumerate()
+                .map(|(i, (chapter, start))| {
+                    let end = chapters
+                        .get(i + 1)
+                        .map(|(_, next_start)| *next_start)
+                        .unwrap_or(onboarding.steps().len());
+                    let steps = onboarding.steps()[*start..end]
+                        .iter()
+                        .copied()
+                        .enumerate()
+                        .map(|(j, step)| (start + j, step))
+                        .collect::<Vec<_>>();
+                    (chapter.name(), steps)
+                })
+                .collect::<Vec<_>>()
+        }
+        _ => vec![],
+    });
+    let current_step_index = use_memo(move || match *state_management::ONBOARDING_STATUS.read() {
+        OnboardingStatus::InProgress(ref onboarding) => onboarding.current_step_index(),
+        _ => 0,
+    });
+    let total_steps = use_memo(move || {
+        chapters
+            .read()
+            .iter()
+            .map(|(_, steps)| steps.len())
+            .sum::<usize>()
+    });
+
+    use_drop(|| log::debug!("OnboardingProgressSidebar Dropped"));
+
+    rsx! {
+        if !chapters.read().is_empty() {
+            div { class: "fixed z-30 top-14 right-0 flex flex-row items-start",
+                button {
+                    class: "btn btn-sm btn-info rounded-r-none mt-2",
+                    onclick: move |_| collapsed.toggle(),
+                    DrawSvg::<ChevronRight> {
+                        size: Size4,
+                        base_class: if collapsed() { "rotate-180 fill-current" } else { "fill-current" },
+                    }
+                    "{current_step_index}/{total_steps}"
+                }
+                div {
+                    class: "w-80 max-h-[calc(100dvh-8rem)] overflow-y-auto bg-base-200 rounded-bl-box shadow-xl p-2",
+                    class: if collapsed() { "hidden" },
+                    div { class: "text-lg font-bold px-2", "Onboarding progress" }
+                    progress {
+                        class: "progress progress-info w-full px-2",
+                        value: "{current_step_index}",
+                        max: "{total_steps}",
+                    }
+                    for (chapter_name , steps) in chapters() {
+                        OnboardingProgressChapter {
+                            key: "{chapter_name}",
+                            chapter_name,
+                            steps,
+                            current_step_index: current_step_index(),
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn OnboardingProgressChapter(
+    chapter_name: &'static str,
+    steps: Vec<(usize, OnboardingStep)>,
+    current_step_index: usize,
+) -> Element {
+    let is_current = steps
+        .first()
+        .is_some_and(|(first, _)| *first <= current_step_index)
+        && steps
+            .last()
+            .is_some_and(|(last, _)| current_step_index <= *last);
+    let is_done = steps
+        .last()
+        .is_some_and(|(last, _)| *last < current_step_index);
+
+    rsx! {
+        details { class: "collapse collapse-arrow", open: is_current,
+            summary { class: "collapse-title font-semibold py-2 min-h-0",
+                span { class: if is_done { "line-through opacity-60" }, "{chapter_name}" }
+            }
+            ul { class: "collapse-content text-sm flex flex-col gap-1",
+                for (index , step) in steps {
+                    OnboardingProgressStep {
+                        key: "{index}",
+                        step,
+                        done: index < current_step_index,
+                        current: index == current_step_index,
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn OnboardingProgressStep(step: OnboardingStep, done: bool, current: bool) -> Element {
+    // Only already seen explanations can be replayed, the current one is displayed anyway
+    let replayable = done && step.is_modal();
+    let router = try_consume_context::<RouterContext>().expect("Router is present");
+
+    let replay = move |_| {
+        let route = match *state_management::ONBOARDING_STATUS.peek() {
+            OnboardingStatus::InProgress(ref onboarding) => onboarding.step_route(step),
+            _ => None,
+        };
+        *REPLAYED_MODAL_STEP.write() = Some(step);
+        if let Some(route) = route {
+            if route != router.current::<crate::Route>() {
+                navigator().push(route);
+            }
+        }
+    };
+
+    rsx! {
+        li {
+            class: "flex flex-row gap-2 items-center rounded-box px-1",
+            class: if current { "bg-info/20 font-bold" },
+            class: if replayable { "cursor-pointer hover:bg-base-300" },
+            onclick: move |evt| {
+                if replayable {
+                    replay(evt);
+                }
+            },
+            if done {
+                DrawSvg::<CheckCircle> { size: Size4, base_class: "fill-success" }
+            } else {
+                span { class: "size-4 flex-none rounded-full border border-base-content/40" }
+            }
+            span { class: if done { "opacity-60" }, {step.title()} }
+            if replayable {
+                DrawSvg::<InfoCircle> { size: Size4, base_class: "fill-info" }
+            }
+        }
+    }
+}
+
+const LEDGER_SETUP_STEPS: &[OnboardingStep] = &[
+    OnboardingStep::LedgerInstallBitcoinApp,
+    OnboardingStep::LedgerCheckNetwork,
+    OnboardingStep::LedgerVerifyTestDerivation,
+];
+
+/// Outcome of the test key derivation on the Ledger
+#[derive(Debug, Clone, PartialEq)]
+enum TestDerivation {
+    Running,
+    Success(CCStr),
+    Failure(CCStr),
+}
+
+/// Guide to prepare the Ledger device before creating a wallet with it.
+///
+/// Displayed over any view when the onboarding is at one of the hardware device steps.
+/// The Bitcoin application and network mode checks progress on their own as the
+/// `LEDGER_STATUS` changes, the test derivation is started by the user.
+#[component]
+pub fn LedgerSetupGuide() -> Element {
+    log::debug!("LedgerSetupGuide Rendered");
+
+    let current_step = use_memo(move || match *state_management::ONBOARDING_STATUS.read() {
+        OnboardingStatus::InProgress(ref onboarding) if !onboarding.is_paused() => onboarding
+            .current_step()
+            .filter(|step| LEDGER_SETUP_STEPS.contains(step)),
+        _ => None,
+    });
+    let current_index = use_memo(move || {
+        current_step().and_then(|step| LEDGER_SETUP_STEPS.iter().position(|s| *s == step))
+    });
+
+    let bitcoin_app_opened = use_memo(move || {
+        matches!(
+            state_management::LEDGER_STATUS(),
+            Some(LedgerStatus::Ready(_) | LedgerStatus::WrongNetwork)
+        )
+    });
+    let ledger_ready = use_memo(move || state_management::ledger_is_ready().is_some());
+
+    let mut test_derivation = use_signal(|| None::<TestDerivation>);
+
+    let progress = move |step: OnboardingStep| {
+        if let OnboardingStatus::InProgress(ref mut onboarding) =
+            *state_management::ONBOARDING_STATUS.write()
+        {
+            onboarding.progress(step);
+        }
+    };
+    use_effect(move || match current_step() {
+        Some(OnboardingStep::LedgerInstallBitcoinApp) if bitcoin_app_opened() => {
+            progress(OnboardingStep::LedgerInstallBitcoinApp)
+        }
+        Some(OnboardingStep::LedgerCheckNetwork) if ledger_ready() => {
+            progress(OnboardingStep::LedgerCheckNetwork)
+        }
+        _ => (),
+    });
+
+    let run_test_derivation = move |_| async move {
+        *test_derivation.write() = Some(TestDerivation::Running);
+        let result = async {
+            let ledger = LedgerKey::new(bitcoin_network::get()).await?;
+            let account_xpubs = ledger.derive_accounts_xpubs(0..1).await?;
+            Ok::<_, btc_heritage_wallet::errors::Error>((ledger.fingerprint()?, account_xpubs))
+        }
+        .await;
+        let outcome = match result {
+            Ok((fingerprint, account_xpubs)) => match account_xpubs.first() {
+                Some(account_xpub)
+                    if state_management::ledger_is_ready() == Some(fingerprint) =>
+                {
+                    TestDerivation::Success(CCStr::from(account_xpub.to_string()))
+                }
+                Some(_) => TestDerivation::Failure(CCStr::from(
+                    "The Ledger answered with another seed than the connected one, reconnect it and retry",
+                )),
+                None => TestDerivation::Failure(CCStr::from("The Ledger did not derive any key")),
+            },
+            Err(e) => TestDerivation::Failure(CCStr::from(log_error(e))),
+        };
+        *test_derivation.write() = Some(outcome);
+    };
+
+    use_drop(|| log::debug!("LedgerSetupGuide Dropped"));
+
+    let Some(current_index) = current_index() else {
+        return rsx! {};
+    };
+    let network = bitcoin_network::get();
+
+    rsx! {
+        div { class: "fixed z-40 bottom-16 right-8 w-md",
+            div { class: "card bg-base-200 shadow-xl border border-info",
+                div { class: "card-body gap-4",
+                    h2 { class: "card-title", "Prepare your Ledger" }
+                    ul { class: "steps steps-vertical",
+                        for (index , step) in LEDGER_SETUP_STEPS.iter().enumerate() {
+                            li {
+                                key: "{index}",
+                                class: "step",
+                                class: if index <= current_index { "step-primary" },
+                                class: if index == current_index { "font-bold" },
+                                {step.title()}
+                            }
+                        }
+                    }
+                    match current_step() {
+                        Some(OnboardingStep::LedgerInstallBitcoinApp) => rsx! {
+                            div { class: "text-sm",
+                                "Connect your Ledger via USB and unlock it. If it is not installed yet, install the "
+                                span { class: "font-bold", "Bitcoin" }
+                                " application from the "
+                                span { class: "font-bold", "My Ledger" }
+                                " section of Ledger Live, then open it on the device."
+                            }
+                        },
+                        Some(OnboardingStep::LedgerCheckNetwork) => rsx! {
+                            div { class: "text-sm",
+                                "This application works on the "
+                                span { class: "font-bold", "{network}" }
+                                " network. "
+                                if network == btc_heritage_wallet::bitcoin::Network::Bitcoin {
+                                    "Open the \"Bitcoin\" application, not the \"Bitcoin Test\" one."
+                                } else {
+                                    "Open the \"Bitcoin Test\" application, not the \"Bitcoin\" one."
+                                }
+                            }
+                        },
+                        _ => rsx! {
+                            div { class: "text-sm",
+                                "Ask your Ledger for the key of your first Heritage account to verify it derives keys from your seed."
+                            }
+                            match test_derivation() {
+                                Some(TestDerivation::Success(account_xpub)) => rsx! {
+                                    div { class: "text-sm font-mono break-all", {account_xpub} }
+                                },
+                                Some(TestDerivation::Failure(e)) => rsx! {
+                                    div { class: "text-sm text-error", {e} }
+                                },
+                                Some(TestDerivation::Running) | None => rsx! {},
+                            }
+                        },
+                    }
+                    LedgerServiceStatusWithDesc { class: "flex justify-center items-center gap-8" }
+                    if current_step() == Some(OnboardingStep::LedgerVerifyTestDerivation) {
+                        div { class: "card-actions justify-end",
+                            if matches!(test_derivation(), Some(TestDerivation::Success(_))) {
+                                button {
+                                    class: "btn btn-primary",
+                                    onclick: move |_| progress(OnboardingStep::LedgerVerifyTestDerivation),
+                                    "Continue"
+                                }
+                            } else {
+                                button {
+                                    class: "btn btn-primary",
+                                    disabled: !ledger_ready()
+                                        || matches!(test_derivation(), Some(TestDerivation::Running)),
+                                    onclick: run_test_derivation,
+                                    if matches!(test_derivation(), Some(TestDerivation::Running)) {
+                                        span { class: "loading loading-spinner loading-sm" }
+                                    }
+                                    "Run the Test"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use crate::prelude::*;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::Deserialize;
+
+use crate::utils::CCStr;
+
+/// Delay between two frames of the camera sent for decoding
+const FRAME_INTERVAL_MS: u64 = 150;
+
+/// Frames are downscaled to this width before decoding, enough for QR codes held in front of the camera
+const MAX_FRAME_WIDTH: u32 = 640;
+
+/// Message of the camera script of a [QRScanner]
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type")]
+enum CameraMessage {
+    /// A greyscale frame, one base64-encoded byte per pixel
+    Frame {
+        width: usize,
+        height: usize,
+        data: String,
+    },
+    /// The camera could not be opened
+    Error { message: String },
+}
+
+/// Decode the QR codes visible in a greyscale frame
+fn decode_frame(width: usize, height: usize, data: String) -> Vec<String> {
+    let Ok(pixels) = BASE64.decode(data) else {
+        return vec![];
+    };
+    if pixels.len() != width * height {
+        return vec![];
+    }
+    let mut image =
+        rqrr::PreparedImage::prepare_from_greyscale(width, height, |x, y| pixels[y * width + x]);
+    image
+        .detect_grids()
+        .into_iter()
+        .filter_map(|grid| grid.decode().ok())
+        .map(|(_, content)| content)
+        .collect()
+}
+
+/// Webcam preview calling `onscan` with the content of every QR code it sees
+///
+/// The camera is released as soon as the component is removed from the page.
+/// The same content is only reported once in a row, so an animated QR code
+/// yields each of its frames once per cycle.
+#[component]
+pub fn QRScanner(onscan: EventHandler<CCStr>) -> Element {
+    log::debug!("QRScanner Rendered");
+
+    let id = use_hook(|| uuid::Uuid::new_v4());
+    let mut camera_error = use_signal(|| None::<CCStr>);
+
+    use_future(move || async move {
+        let mut eval = document::eval(&format!(
+            r#"
+            let video = document.getElementById("{id}");
+            while (!video) {{
+                await new Promise((resolve) => requestAnimationFrame(resolve));
+                video = document.getElementById("{id}");
+            }}
+            let stream;
+            try {{
+                stream = await navigator.mediaDevices.getUserMedia({{
+                    video: {{ facingMode: "environment" }},
+                    audio: false,
+                }});
+            }} catch (e) {{
+                dioxus.send({{ type: "Error", message: String(e) }});
+                return;
+            }}
+            video.srcObject = stream;
+            await video.play();
+            const canvas = document.createElement("canvas");
+            const context = canvas.getContext("2d", {{ willReadFrequently: true }});
+            while (document.getElementById("{id}")) {{
+                await new Promise((resolve) => setTimeout(resolve, {FRAME_INTERVAL_MS}));
+                if (!video.videoWidth) {{
+                    continue;
+                }}
+                const scale = Math.min(1, {MAX_FRAME_WIDTH} / video.videoWidth);
+                canvas.width = Math.round(video.videoWidth * scale);
+                canvas.height = Math.round(video.videoHeight * scale);
+                context.drawImage(video, 0, 0, canvas.width, canvas.height);
+                const rgba = context.getImageData(0, 0, canvas.width, canvas.height).data;
+                let grey = "";
+                for (let i = 0; i < rgba.length; i += 4) {{
+                    grey += String.fromCharCode((rgba[i] * 77 + rgba[i + 1] * 150 + rgba[i + 2] * 29) >> 8);
+                }}
+                dioxus.send({{ type: "Frame", width: canvas.width, height: canvas.height, data: btoa(grey) }});
+                // Wait for the frame to be decoded, without hanging if the component is gone
+                await Promise.race([
+                    dioxus.recv(),
+                    new Promise((resolve) => setTimeout(resolve, 2000)),
+                ]);
+            }}
+            stream.getTracks().forEach((track) => track.stop());
+        "#
+        ));
+        let mut last_scanned = None::<String>;
+        while let Ok(message) = eval.recv::<CameraMessage>().await {
+            match message {
+                CameraMessage::Frame {
+                    width,
+                    height,
+                    data,
+                } => {
+                    let contents =
+                        tokio::task::spawn_blocking(move || decode_frame(width, height, data))
+                            .await
+                            .unwrap_or_default();
+                    for content in contents {
+                        if last_scanned.as_ref() != Some(&content) {
+                            log::debug!("QRScanner {id} - scanned {content}");
+                            onscan.call(CCStr::from(content.as_str()));
+                            last_scanned = Some(content);
+                        }
+                    }
+                    let _ = eval.send(true);
+                }
+                CameraMessage::Error { message } => {
+                    log::error!("QRScanner {id} - could not open the camera: {message}");
+                    camera_error.set(Some(CCStr::from(message)));
+                    break;
+                }
+            }
+        }
+    });
+
+    use_drop(|| log::debug!("QRScanner Dropped"));
+
+    rsx! {
+        if let Some(camera_error) = camera_error() {
+            div { class: "alert alert-error", "Could not open the camera: {camera_error}" }
+        } else {
+            video {
+                id: "{id}",
+                class: "w-80 rounded-box bg-black",
+                autoplay: true,
+                muted: true,
+            }
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use crate::prelude::*;
 
 use core::fmt::Write;
+use std::collections::{BTreeMap, BTreeSet};
+
+use btc_heritage_wallet::bitcoin::hashes::{sha256, Hash};
 
 use qrcode::{
     render::{Canvas, Pixel},
@@ -8,7 +11,7 @@ use qrcode::{
     QrCode,
 };
 
-use crate::utils::CCStr;
+use crate::utils::{async_sleep, CCStr, CheapClone};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct QRCodeSvg {
@@ -72,6 +75,652 @@ pub fn QRCode(data: CCStr) -> Element {
     }
 }
 
+/// Render `data` as a standalone SVG document, for the files exported by the application
+///
+/// Returns [None] if the data do not fit in a QR code.
+pub fn qr_code_svg(data: &str) -> Option<String> {
+    let qr_code = QrCode::new(data.as_bytes())
+        .ok()?
+        .render::<DummyPixel>()
+        .quiet_zone(false)
+        .build();
+    let width = qr_code.width;
+    Some(format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {width} {width}"><path fill="black" d="{}"/></svg>"#,
+        qr_code.path
+    ))
+}
+
+/// Maximum length of the fragments of multi-part URs, keeps the QR codes easy to scan
+pub const UR_MAX_FRAGMENT_LEN: usize = 200;
+
+/// Maximum number of parts of the multi-part URs accepted when scanning
+const UR_MAX_SEQ_LEN: usize = 4096;
+
+/// Delay between two frames of an [AnimatedQRCode]
+const ANIMATION_FRAME_MS: u64 = 300;
+
+/// A QR code cycling through the parts of a multi-part Uniform Resource (see [ur_encode])
+///
+/// With a single part, it is a static [QRCode].
+#[component]
+pub fn AnimatedQRCode(parts: CheapClone<[CCStr]>) -> Element {
+    let mut frame = use_signal(|| 0usize);
+    use_future(move || async move {
+        loop {
+            async_sleep(ANIMATION_FRAME_MS).await;
+            *frame.write() += 1;
+        }
+    });
+
+    let data = (!parts.is_empty()).then(|| parts[frame() % parts.len()].clone());
+
+    rsx! {
+        if let Some(data) = data {
+            QRCode { data }
+        }
+    }
+}
+
+/// Minimal encoding of the Bytewords (BCR-2020-012): the first and last letters
+/// of each of the 256 words, in byte order
+const BYTEWORDS_MINIMAL: &[u8; 512] = b"\
+    aeadaoaxaaahamatayasbkbdbnbtbabsbebybgbwbbbzcmchcscfcycwcecackct\
+    cxclcpcndkdadsdidedtdrdndwdpdmdldyeheyeoeeecenemetesftfrfnfsfmfh\
+    fzfpfwfxfyfefgflfdgagegrgsgtglgwgdgygmgughgohfhghdhkhthphhhlhyhe\
+    hnhsidiaieihiyioisinimjejzjnjtjljojsjpjkjykpkoktkskkknkgkekikblb\
+    lalylflslrlplnltloldlelulklgmnmymhmemomumwmdmtmsmknlnyndnsntnnne\
+    nboyoeotoxonolospdptpkpypspmplpepfpaprqdqzrerprlrorhrdrkrfryrnrs\
+    rtsesasrssskswstspsosgsbsfsntotktitttdtetytltbtstptatnuyuoutueur\
+    vtvyvovlvevwvavdvswlwdwmwpwewywswtwnwzwfwkykynylyaytzszoztzczezm";
+
+/// CRC-32 (ISO-HDLC), as used by the Uniform Resources
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb88320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// Encode `data` followed by its CRC-32 using minimal Bytewords, in uppercase
+fn bytewords_minimal(data: &[u8]) -> String {
+    data.iter()
+        .chain(crc32(data).to_be_bytes().iter())
+        .flat_map(|byte| {
+            let i = *byte as usize * 2;
+            [BYTEWORDS_MINIMAL[i], BYTEWORDS_MINIMAL[i + 1]]
+        })
+        .map(|c| c.to_ascii_uppercase() as char)
+        .collect()
+}
+
+/// Minimal CBOR (RFC 8949) encoder for the payloads of Uniform Resources
+///
+/// Only definite lengths are supported, callers are responsible for the item counts
+/// of arrays and maps and for ordering map keys canonically.
+#[derive(Debug, Default)]
+pub struct Cbor(Vec<u8>);
+impl Cbor {
+    fn head(&mut self, major_type: u8, value: u64) -> &mut Self {
+        let major_type = major_type << 5;
+        match value {
+            0..=23 => self.0.push(major_type | value as u8),
+            24..=0xff => self.0.extend([major_type | 24, value as u8]),
+            0x100..=0xffff => {
+                self.0.push(major_type | 25);
+                self.0.extend((value as u16).to_be_bytes());
+            }
+            0x10000..=0xffffffff => {
+                self.0.push(major_type | 26);
+                self.0.extend((value as u32).to_be_bytes());
+            }
+            _ => {
+                self.0.push(major_type | 27);
+                self.0.extend(value.to_be_bytes());
+            }
+        }
+        self
+    }
+    pub fn uint(&mut self, value: u64) -> &mut Self {
+        self.head(0, value)
+    }
+    pub fn bytes(&mut self, value: &[u8]) -> &mut Self {
+        self.head(2, value.len() as u64);
+        self.0.extend_from_slice(value);
+        self
+    }
+    pub fn text(&mut self, value: &str) -> &mut Self {
+        self.head(3, value.len() as u64);
+        self.0.extend_from_slice(value.as_bytes());
+        self
+    }
+    pub fn array(&mut self, len: usize) -> &mut Self {
+        self.head(4, len as u64)
+    }
+    pub fn map(&mut self, len: usize) -> &mut Self {
+        self.head(5, len as u64)
+    }
+    pub fn tag(&mut self, tag: u64) -> &mut Self {
+        self.head(6, tag)
+    }
+    pub fn bool(&mut self, value: bool) -> &mut Self {
+        self.0.push(if value { 0xf5 } else { 0xf4 });
+        self
+    }
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+/// Encode the CBOR `message` as a Uniform Resource (BCR-2020-005) of type `ur_type`
+///
+/// Messages longer than `max_fragment_len` are split into a multi-part UR, to display
+/// with an [AnimatedQRCode]. Only the simple parts of the fountain encoding are
+/// produced: cycling through them is enough for decoders to reassemble the message.
+/// The result is in uppercase, which QR codes encode more efficiently.
+pub fn ur_encode(ur_type: &str, message: &[u8], max_fragment_len: usize) -> Vec<CCStr> {
+    let ur_type = ur_type.to_ascii_uppercase();
+    if message.len() <= max_fragment_len {
+        return vec![CCStr::from(format!(
+            "UR:{ur_type}/{}",
+            bytewords_minimal(message)
+        ))];
+    }
+
+    let fragment_count = message.len().div_ceil(max_fragment_len);
+    let fragment_len = message.len().div_ceil(fragment_count);
+    let checksum = crc32(message);
+    (0..fragment_count)
+        .map(|i| {
+            let mut fragment = message[i * fragment_len..]
+                .iter()
+                .take(fragment_len)
+                .copied()
+                .collect::<Vec<_>>();
+            fragment.resize(fragment_len, 0);
+            let mut part = Cbor::default();
+            part.array(5)
+                .uint(i as u64 + 1)
+                .uint(fragment_count as u64)
+                .uint(message.len() as u64)
+                .uint(checksum as u64)
+                .bytes(&fragment);
+            CCStr::from(format!(
+                "UR:{ur_type}/{}-{fragment_count}/{}",
+                i + 1,
+                bytewords_minimal(&part.into_bytes())
+            ))
+        })
+        .collect()
+}
+
+/// Decode minimal Bytewords (see [bytewords_minimal]), verifying and stripping the CRC-32
+fn bytewords_minimal_decode(encoded: &str) -> Result<Vec<u8>, String> {
+    let encoded = encoded.to_ascii_lowercase();
+    if encoded.len() % 2 != 0 || encoded.len() < 8 {
+        return Err("Invalid Bytewords length".to_owned());
+    }
+    let mut data = encoded
+        .as_bytes()
+        .chunks(2)
+        .map(|word| {
+            BYTEWORDS_MINIMAL
+                .chunks(2)
+                .position(|candidate| candidate == word)
+                .map(|byte| byte as u8)
+                .ok_or_else(|| format!("Invalid Byteword {}", String::from_utf8_lossy(word)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let checksum = data.split_off(data.len() - 4);
+    if crc32(&data).to_be_bytes()[..] != checksum[..] {
+        return Err("Invalid Bytewords checksum".to_owned());
+    }
+    Ok(data)
+}
+
+/// Minimal CBOR (RFC 8949) reader, the counterpart of [Cbor]
+struct CborReader<'a>(&'a [u8]);
+impl<'a> CborReader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.0.len() < len {
+            return None;
+        }
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Some(taken)
+    }
+    fn head(&mut self, major_type: u8) -> Option<u64> {
+        let initial = *self.take(1)?.first()?;
+        if initial >> 5 != major_type {
+            return None;
+        }
+        let value_len = match initial & 0x1f {
+            value @ 0..=23 => return Some(value as u64),
+            24 => 1,
+            25 => 2,
+            26 => 4,
+            27 => 8,
+            _ => return None,
+        };
+        Some(
+            self.take(value_len)?
+                .iter()
+                .fold(0u64, |value, byte| value << 8 | *byte as u64),
+        )
+    }
+    fn uint(&mut self) -> Option<u64> {
+        self.head(0)
+    }
+    fn bytes(&mut self) -> Option<&'a [u8]> {
+        let len = self.head(2)?;
+        self.take(usize::try_from(len).ok()?)
+    }
+    fn array(&mut self) -> Option<u64> {
+        self.head(4)
+    }
+}
+
+/// Content of a CBOR message made of a single byte string, like the `crypto-psbt` URs
+pub fn cbor_byte_string(message: &[u8]) -> Option<&[u8]> {
+    let mut cbor = CborReader(message);
+    let bytes = cbor.bytes()?;
+    cbor.0.is_empty().then_some(bytes)
+}
+
+/// Xoshiro256** PRNG, as seeded by the fountain encoding of the Uniform Resources
+struct Xoshiro256([u64; 4]);
+impl Xoshiro256 {
+    fn from_seed(seed: &[u8]) -> Self {
+        let digest = sha256::Hash::hash(seed).to_byte_array();
+        let mut state = [0u64; 4];
+        for (i, s) in state.iter_mut().enumerate() {
+            *s = u64::from_be_bytes(digest[i * 8..(i + 1) * 8].try_into().unwrap());
+        }
+        Self(state)
+    }
+    fn next(&mut self) -> u64 {
+        let s = &mut self.0;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+    fn next_double(&mut self) -> f64 {
+        self.next() as f64 / (u64::MAX as f64 + 1.0)
+    }
+    fn next_int(&mut self, low: usize, high: usize) -> usize {
+        (self.next_double() * (high - low + 1) as f64) as usize + low
+    }
+}
+
+/// Pick the degree of a mixed part, with the probability of degree `d` proportional
+/// to `1/d`, using the alias method of the reference implementation
+fn fountain_degree(seq_len: usize, rng: &mut Xoshiro256) -> usize {
+    let mut probs = (1..=seq_len).map(|i| 1.0 / i as f64).collect::<Vec<_>>();
+    let sum = probs.iter().sum::<f64>();
+    probs
+        .iter_mut()
+        .for_each(|p| *p = *p * seq_len as f64 / sum);
+    let (mut small, mut large): (Vec<usize>, Vec<usize>) =
+        (0..seq_len).rev().partition(|i| probs[*i] < 1.0);
+    let mut alias_probs = vec![0.0; seq_len];
+    let mut aliases = vec![0; seq_len];
+    while let (Some(a), Some(g)) = (small.last().copied(), large.last().copied()) {
+        small.pop();
+        large.pop();
+        alias_probs[a] = probs[a];
+        aliases[a] = g;
+        probs[g] += probs[a] - 1.0;
+        if probs[g] < 1.0 {
+            small.push(g);
+        } else {
+            large.push(g);
+        }
+    }
+    for i in large.into_iter().chain(small) {
+        alias_probs[i] = 1.0;
+    }
+    let r1 = rng.next_double();
+    let r2 = rng.next_double();
+    let i = (seq_len as f64 * r1) as usize;
+    (if r2 < alias_probs[i] { i } else { aliases[i] }) + 1
+}
+
+/// Indexes of the fragments XOR-ed together in the part `seq_num` of a fountain encoding
+fn fountain_fragments(seq_num: u64, seq_len: usize, checksum: u32) -> BTreeSet<usize> {
+    if seq_num <= seq_len as u64 {
+        return BTreeSet::from([seq_num as usize - 1]);
+    }
+    let mut seed = (seq_num as u32).to_be_bytes().to_vec();
+    seed.extend(checksum.to_be_bytes());
+    let mut rng = Xoshiro256::from_seed(&seed);
+    let degree = fountain_degree(seq_len, &mut rng);
+    let mut remaining = (0..seq_len).collect::<Vec<_>>();
+    let mut shuffled = Vec::with_capacity(seq_len);
+    while !remaining.is_empty() {
+        let index = rng.next_int(0, remaining.len() - 1);
+        shuffled.push(remaining.remove(index));
+    }
+    shuffled.into_iter().take(degree).collect()
+}
+
+fn xor_into(target: &mut [u8], other: &[u8]) {
+    target
+        .iter_mut()
+        .zip(other)
+        .for_each(|(byte, other)| *byte ^= other);
+}
+
+/// Reassemble the message of a multi-part Uniform Resource from its fountain-encoded parts
+#[derive(Debug)]
+struct URDecoder {
+    ur_type: String,
+    seq_len: usize,
+    message_len: usize,
+    checksum: u32,
+    fragments: BTreeMap<usize, Vec<u8>>,
+    mixed_parts: Vec<(BTreeSet<usize>, Vec<u8>)>,
+}
+impl URDecoder {
+    /// Add a part, then reduce the mixed parts with the fragments known so far
+    fn receive(&mut self, seq_num: u64, fragment: Vec<u8>) {
+        let mut pending = vec![(
+            fountain_fragments(seq_num, self.seq_len, self.checksum),
+            fragment,
+        )];
+        while let Some((mut indexes, mut data)) = pending.pop() {
+            for index in indexes.clone() {
+                if let Some(known) = self.fragments.get(&index) {
+                    xor_into(&mut data, known);
+                    indexes.remove(&index);
+                }
+            }
+            match indexes.len() {
+                0 => (),
+                1 => {
+                    let index = indexes.into_iter().next().unwrap();
+                    self.fragments.insert(index, data);
+                    // The new fragment may reduce the mixed parts received earlier
+                    let (reducible, kept): (Vec<_>, Vec<_>) =
+                        core::mem::take(&mut self.mixed_parts)
+                            .into_iter()
+                            .partition(|(mixed_indexes, _)| mixed_indexes.contains(&index));
+                    self.mixed_parts = kept;
+                    pending.extend(reducible);
+                }
+                _ => {
+                    if !self.mixed_parts.iter().any(|(known, _)| *known == indexes) {
+                        self.mixed_parts.push((indexes, data));
+                    }
+                }
+            }
+        }
+    }
+
+    fn message(&self) -> Option<Result<Vec<u8>, String>> {
+        if self.fragments.len() < self.seq_len {
+            return None;
+        }
+        let mut message = self
+            .fragments
+            .values()
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>();
+        message.truncate(self.message_len);
+        Some(if crc32(&message) == self.checksum {
+            Ok(message)
+        } else {
+            Err("The reassembled message does not match its checksum".to_owned())
+        })
+    }
+}
+
+/// Reassemble the data of a BBQr (Better Bitcoin QR) sequence
+#[derive(Debug)]
+struct BBQrDecoder {
+    encoding: u8,
+    file_type: u8,
+    total: usize,
+    parts: BTreeMap<usize, String>,
+}
+impl BBQrDecoder {
+    fn data(&self) -> Option<Result<Vec<u8>, String>> {
+        if self.parts.len() < self.total {
+            return None;
+        }
+        let encoded = self.parts.values().map(String::as_str).collect::<String>();
+        Some(match self.encoding {
+            b'H' => (0..encoded.len())
+                .step_by(2)
+                .map(|i| {
+                    encoded
+                        .get(i..i + 2)
+                        .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                        .ok_or_else(|| "Invalid hexadecimal data".to_owned())
+                })
+                .collect(),
+            b'2' => base32_decode(&encoded),
+            b'Z' => base32_decode(&encoded).and_then(|compressed| {
+                miniz_oxide::inflate::decompress_to_vec(&compressed)
+                    .map_err(|e| format!("Invalid compressed data: {e:?}"))
+            }),
+            encoding => Err(format!("Unsupported BBQr encoding {}", encoding as char)),
+        })
+    }
+}
+
+/// Decode unpadded base32 (RFC 4648), as used by BBQr
+fn base32_decode(encoded: &str) -> Result<Vec<u8>, String> {
+    const ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+    let mut data = Vec::with_capacity(encoded.len() * 5 / 8);
+    let (mut buffer, mut bits) = (0u32, 0);
+    for c in encoded.bytes() {
+        let value = ALPHABET
+            .iter()
+            .position(|a| *a == c)
+            .ok_or_else(|| format!("Invalid base32 character {}", c as char))?;
+        buffer = buffer << 5 | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            data.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Ok(data)
+}
+
+/// Content of a QR code, or of a sequence of QR codes, fully decoded
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodedQRContent {
+    /// The CBOR message of a Uniform Resource, with its type in lowercase
+    UR { ur_type: String, message: Vec<u8> },
+    /// The data of a BBQr sequence, with its file type (`P` for a PSBT, `T` for a transaction...)
+    BBQr { file_type: char, data: Vec<u8> },
+    /// Any other QR code
+    Text(String),
+}
+
+#[derive(Debug)]
+enum QRSequence {
+    UR(URDecoder),
+    BBQr(BBQrDecoder),
+}
+
+/// Decoder of the QR codes scanned one after the other, reassembling the
+/// multi-part Uniform Resources (BC-UR) and BBQr sequences whatever the order
+/// of their parts
+#[derive(Debug, Default)]
+pub struct QRSequenceDecoder(Option<QRSequence>);
+impl QRSequenceDecoder {
+    /// Number of parts decoded and expected of the sequence in progress, if any
+    pub fn progress(&self) -> Option<(usize, usize)> {
+        match &self.0 {
+            Some(QRSequence::UR(decoder)) => Some((decoder.fragments.len(), decoder.seq_len)),
+            Some(QRSequence::BBQr(decoder)) => Some((decoder.parts.len(), decoder.total)),
+            None => None,
+        }
+    }
+
+    /// Decode a scanned QR code, returning the content once the sequence is complete
+    ///
+    /// A part belonging to another sequence than the one in progress starts a new one.
+    pub fn receive(&mut self, scanned: &str) -> Result<Option<DecodedQRContent>, String> {
+        let scanned = scanned.trim();
+        let content = if scanned
+            .get(..3)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case("ur:"))
+        {
+            self.receive_ur(&scanned[3..])?
+        } else if scanned.starts_with("B$") {
+            self.receive_bbqr(scanned)?
+        } else {
+            self.0 = None;
+            Some(DecodedQRContent::Text(scanned.to_owned()))
+        };
+        if content.is_some() {
+            self.0 = None;
+        }
+        Ok(content)
+    }
+
+    fn receive_ur(&mut self, ur: &str) -> Result<Option<DecodedQRContent>, String> {
+        let ur = ur.to_ascii_lowercase();
+        let mut components = ur.split('/');
+        let ur_type = components.next().unwrap_or_default().to_owned();
+        let (sequence, payload) = match (components.next(), components.next()) {
+            (Some(payload), None) => (None, payload),
+            (Some(sequence), Some(payload)) => (Some(sequence), payload),
+            _ => return Err("Invalid UR".to_owned()),
+        };
+        let payload = bytewords_minimal_decode(payload)?;
+        let Some(sequence) = sequence else {
+            return Ok(Some(DecodedQRContent::UR {
+                ur_type,
+                message: payload,
+            }));
+        };
+        if sequence.split_once('-').is_none() {
+            return Err(format!("Invalid UR sequence {sequence}"));
+        }
+
+        let mut cbor = CborReader(&payload);
+        let (seq_num, seq_len, message_len, checksum, fragment) = (|| {
+            (cbor.array()? == 5).then_some(())?;
+            Some((
+                cbor.uint()?,
+                usize::try_from(cbor.uint()?).ok()?,
+                usize::try_from(cbor.uint()?).ok()?,
+                u32::try_from(cbor.uint()?).ok()?,
+                cbor.bytes()?,
+            ))
+        })()
+        .ok_or_else(|| "Invalid UR part".to_owned())?;
+        // Like the reference decoder, the sequence length must be the one implied by
+        // the fragment length, and it is capped as it sizes the fountain decoding
+        if seq_num == 0
+            || fragment.is_empty()
+            || seq_len == 0
+            || seq_len > UR_MAX_SEQ_LEN
+            || fragment.len().checked_mul(seq_len).is_none()
+            || seq_len != message_len.div_ceil(fragment.len())
+        {
+            return Err("Invalid UR part".to_owned());
+        }
+
+        let same_sequence = matches!(&self.0, Some(QRSequence::UR(decoder))
+            if decoder.ur_type == ur_type
+                && decoder.seq_len == seq_len
+                && decoder.message_len == message_len
+                && decoder.checksum == checksum);
+        if !same_sequence {
+            self.0 = Some(QRSequence::UR(URDecoder {
+                ur_type: ur_type.clone(),
+                seq_len,
+                message_len,
+                checksum,
+                fragments: BTreeMap::new(),
+                mixed_parts: vec![],
+            }));
+        }
+        let Some(QRSequence::UR(decoder)) = &mut self.0 else {
+            unreachable!("the UR decoder was just set")
+        };
+        decoder.receive(seq_num, fragment.to_vec());
+        let content = decoder
+            .message()
+            .transpose()
+            .map(|message| message.map(|message| DecodedQRContent::UR { ur_type, message }));
+        if content.is_err() {
+            // A complete but corrupted sequence cannot be recovered, start over
+            self.0 = None;
+        }
+        content
+    }
+
+    fn receive_bbqr(&mut self, bbqr: &str) -> Result<Option<DecodedQRContent>, String> {
+        let header = bbqr
+            .get(..8)
+            .filter(|header| header.is_ascii())
+            .ok_or_else(|| "Invalid BBQr header".to_owned())?
+            .as_bytes();
+        let (encoding, file_type) = (header[2], header[3]);
+        let base36 = |digits: &[u8]| {
+            core::str::from_utf8(digits)
+                .ok()
+                .and_then(|digits| usize::from_str_radix(digits, 36).ok())
+                .ok_or_else(|| "Invalid BBQr header".to_owned())
+        };
+        let total = base36(&header[4..6])?;
+        let index = base36(&header[6..8])?;
+        if total == 0 || index >= total {
+            return Err("Invalid BBQr part number".to_owned());
+        }
+
+        let same_sequence = matches!(&self.0, Some(QRSequence::BBQr(decoder))
+            if decoder.encoding == encoding
+                && decoder.file_type == file_type
+                && decoder.total == total);
+        if !same_sequence {
+            self.0 = Some(QRSequence::BBQr(BBQrDecoder {
+                encoding,
+                file_type,
+                total,
+                parts: BTreeMap::new(),
+            }));
+        }
+        let Some(QRSequence::BBQr(decoder)) = &mut self.0 else {
+            unreachable!("the BBQr decoder was just set")
+        };
+        decoder.parts.insert(index, bbqr[8..].to_owned());
+        let content = decoder.data().transpose().map(|data| {
+            data.map(|data| DecodedQRContent::BBQr {
+                file_type: file_type as char,
+                data,
+            })
+        });
+        if content.is_err() {
+            // A complete but corrupted sequence cannot be recovered, start over
+            self.0 = None;
+        }
+        content
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct UIQRCode(CCStr);
 impl LoadedElement for UIQRCode {
@@ -97,3 +746,189 @@ where
         Self(CCStr::from(value))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_all<'a>(parts: impl IntoIterator<Item = &'a str>) -> Option<DecodedQRContent> {
+        let mut decoder = QRSequenceDecoder::default();
+        parts
+            .into_iter()
+            .filter_map(|part| decoder.receive(part).unwrap())
+            .last()
+    }
+
+    fn psbt_message(len: usize) -> Vec<u8> {
+        let mut message = Cbor::default();
+        message.bytes(&(0..len).map(|i| i as u8).collect::<Vec<_>>());
+        message.into_bytes()
+    }
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xcbf43926);
+    }
+
+    #[test]
+    fn bytewords_round_trip() {
+        let data = (0..=255).collect::<Vec<u8>>();
+        assert_eq!(
+            bytewords_minimal_decode(&bytewords_minimal(&data)),
+            Ok(data)
+        );
+        let mut corrupted = bytewords_minimal(b"heritage");
+        corrupted.replace_range(..2, "AE");
+        assert!(bytewords_minimal_decode(&corrupted).is_err());
+    }
+
+    #[test]
+    fn ur_single_part() {
+        let message = psbt_message(50);
+        let parts = ur_encode("crypto-psbt", &message, UR_MAX_FRAGMENT_LEN);
+        assert_eq!(parts.len(), 1);
+        assert_eq!(
+            decode_all(parts.iter().map(|part| &**part)),
+            Some(DecodedQRContent::UR {
+                ur_type: "crypto-psbt".to_owned(),
+                message
+            })
+        );
+    }
+
+    #[test]
+    fn ur_multi_part_in_any_order() {
+        let message = psbt_message(1000);
+        let parts = ur_encode("crypto-psbt", &message, 100);
+        assert!(parts.len() > 1);
+        let mut decoder = QRSequenceDecoder::default();
+        for part in parts.iter().skip(1) {
+            assert_eq!(decoder.receive(part), Ok(None));
+        }
+        assert_eq!(decoder.progress(), Some((parts.len() - 1, parts.len())));
+        assert_eq!(
+            decoder.receive(&parts[0]),
+            Ok(Some(DecodedQRContent::UR {
+                ur_type: "crypto-psbt".to_owned(),
+                message
+            }))
+        );
+        assert_eq!(decoder.progress(), None);
+    }
+
+    #[test]
+    fn ur_mixed_part_recovers_a_missing_fragment() {
+        let message = psbt_message(1000);
+        let seq_len = 10;
+        let fragment_len = message.len().div_ceil(seq_len);
+        let checksum = crc32(&message);
+        let fragment = |i: usize| {
+            let mut fragment = message[(i * fragment_len).min(message.len())..]
+                .iter()
+                .take(fragment_len)
+                .copied()
+                .collect::<Vec<_>>();
+            fragment.resize(fragment_len, 0);
+            fragment
+        };
+        let part = |seq_num: u64, data: &[u8]| {
+            let mut part = Cbor::default();
+            part.array(5)
+                .uint(seq_num)
+                .uint(seq_len as u64)
+                .uint(message.len() as u64)
+                .uint(checksum as u64)
+                .bytes(data);
+            format!(
+                "ur:crypto-psbt/{seq_num}-{seq_len}/{}",
+                bytewords_minimal(&part.into_bytes())
+            )
+        };
+
+        let seq_num = seq_len as u64 + 1;
+        let mixed = fountain_fragments(seq_num, seq_len, checksum);
+        let missing = *mixed.first().unwrap();
+        let mut mixed_data = vec![0; fragment_len];
+        mixed
+            .iter()
+            .for_each(|i| xor_into(&mut mixed_data, &fragment(*i)));
+
+        let mut parts = vec![part(seq_num, &mixed_data)];
+        parts.extend(
+            (0..seq_len)
+                .filter(|i| *i != missing)
+                .map(|i| part(i as u64 + 1, &fragment(i))),
+        );
+        assert_eq!(
+            decode_all(parts.iter().map(String::as_str)),
+            Some(DecodedQRContent::UR {
+                ur_type: "crypto-psbt".to_owned(),
+                message
+            })
+        );
+    }
+
+    #[test]
+    fn ur_part_with_inconsistent_sequence_length() {
+        let part = |seq_len: u64, message_len: u64| {
+            let mut part = Cbor::default();
+            part.array(5)
+                .uint(1)
+                .uint(seq_len)
+                .uint(message_len)
+                .uint(0)
+                .bytes(&[0; 10]);
+            format!(
+                "ur:crypto-psbt/1-{seq_len}/{}",
+                bytewords_minimal(&part.into_bytes())
+            )
+        };
+        let mut decoder = QRSequenceDecoder::default();
+        assert!(decoder.receive(&part(u64::MAX, 10)).is_err());
+        assert!(decoder.receive(&part(1_000_000, 10_000_000)).is_err());
+        assert!(decoder.receive(&part(3, 10)).is_err());
+        assert!(decoder.receive(&part(1, 11)).is_err());
+        assert_eq!(decoder.receive(&part(2, 11)), Ok(None));
+    }
+
+    #[test]
+    fn non_ascii_text() {
+        assert_eq!(
+            decode_all(["éàü"]),
+            Some(DecodedQRContent::Text("éàü".to_owned()))
+        );
+        assert_eq!(
+            decode_all(["uré"]),
+            Some(DecodedQRContent::Text("uré".to_owned()))
+        );
+    }
+
+    #[test]
+    fn bbqr_hex_in_any_order() {
+        assert_eq!(
+            decode_all(["B$HP0201BEEF", "B$HP0200DEAD"]),
+            Some(DecodedQRContent::BBQr {
+                file_type: 'P',
+                data: vec![0xde, 0xad, 0xbe, 0xef]
+            })
+        );
+    }
+
+    #[test]
+    fn bbqr_base32() {
+        assert_eq!(
+            decode_all(["B$2T0100NBUQ"]),
+            Some(DecodedQRContent::BBQr {
+                file_type: 'T',
+                data: b"hi".to_vec()
+            })
+        );
+    }
+
+    #[test]
+    fn bbqr_invalid_header() {
+        let mut decoder = QRSequenceDecoder::default();
+        assert!(decoder.receive("B$HP0101DEAD").is_err());
+        assert!(decoder.receive("B$HPé").is_err());
+    }
+}
@@ -10,6 +10,7 @@ use btc_heritage_wallet::{
 use crate::{
     components::{
         copy::CopyTextarea,
+        export::{use_export_directory, write_export_files, ExportToDirectory},
         modal::InfoModal,
         svg::{
             Alert, Cancel, DrawSvg, FileDownload, InfoCircleOutline, Seed, SvgSize::Size4, Unlock,
@@ -18,11 +19,7 @@ use crate::{
     utils::{log_error, log_error_ccstr, timestamp_to_file_string, CCStr},
 };
 
-#[cfg(feature = "desktop")]
-use crate::components::inputs::FileInput;
-
-#[cfg(feature = "desktop")]
-use std::fs;
+use state_management::{BackupFreshness, DatabaseCommand};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ShowKeyProviderMnemonicFlavor {
@@ -193,6 +190,27 @@ pub fn ShowKeyProviderMnemonic<KP: KeyProvider + 'static>(
     }
 }
 
+/// Record that the key material or the descriptors of a wallet changed and prompt
+/// the user to export a fresh descriptors backup
+pub async fn prompt_backup_refresh(
+    database_service: Coroutine<DatabaseCommand>,
+    wallet_name: CCStr,
+    backup_freshness: Option<FResource<BackupFreshness>>,
+) {
+    if let Err(e) =
+        state_management::record_key_material_change(database_service, wallet_name).await
+    {
+        log::warn!("Could not record the key material change: {e}");
+    }
+    if let Some(mut backup_freshness) = backup_freshness {
+        backup_freshness.restart();
+    }
+    alert_info(
+        "The descriptors of the wallet changed, \
+        use \"Backup Descriptors\" to export a fresh backup of the wallet.",
+    );
+}
+
 /// Component that displays a button to backup online wallet data
 /// When clicked, opens a modal with backup data and download functionality
 #[component]
@@ -200,6 +218,17 @@ pub fn BackupOnlineWallet(wallet_name: CCStr) -> Element {
     log::debug!("BackupOnlineWallet Rendered");
 
     let descriptor_backup = use_context::<FResource<HeritageWalletBackup>>();
+    let backup_freshness = try_use_context::<FResource<BackupFreshness>>();
+    let database_service = state_management::use_database_service();
+
+    let backup_outdated = use_memo(move || {
+        backup_freshness.is_some_and(|backup_freshness| {
+            matches!(
+                *backup_freshness.read(),
+                Some(Ok(BackupFreshness::Outdated))
+            )
+        })
+    });
 
     let mut display_modal = use_signal(|| false);
 
@@ -217,13 +246,8 @@ pub fn BackupOnlineWallet(wallet_name: CCStr) -> Element {
             .unwrap_or_default()
     });
 
-    let mut backup_directory = use_signal(|| {
-        dirs_next::home_dir()
-            .unwrap_or_default()
-            .to_str()
-            .unwrap_or_default()
-            .to_owned()
-    });
+    let backup_directory = use_export_directory();
+    let export_wallet_name = wallet_name.clone();
     let backup_path = use_memo(move || {
         let ts_str = timestamp_to_file_string(timestamp_now());
         format!(
@@ -232,34 +256,38 @@ pub fn BackupOnlineWallet(wallet_name: CCStr) -> Element {
         )
     });
 
-    #[cfg(feature = "desktop")]
-    const CAN_DOWNLOAD: bool = true;
-    #[cfg(not(feature = "desktop"))]
-    const CAN_DOWNLOAD: bool = false;
-
-    let download_backup = move |_| async move {
-        #[cfg(feature = "desktop")]
-        {
-            let file_path = backup_path.read();
+    let download_backup = move |_| {
+        let export_wallet_name = export_wallet_name.clone();
+        async move {
+            let file_path = backup_path.peek().clone();
             let data = backup_data();
-
-            match fs::write(file_path.as_str(), data.as_ref()) {
+            if !write_export_files("backup", [(file_path.clone(), data.as_bytes())]) {
+                return;
+            }
+            alert_info(format!("Backup saved to: {}", file_path));
+            *display_modal.write() = false;
+            // Remember the export for the wallet health check
+            let backup_export = state_management::BackupExport {
+                timestamp: timestamp_now(),
+                subwallet_count: descriptor_backup
+                    .lrmap_ok(|backup| backup.iter().count())
+                    .unwrap_or_default(),
+            };
+            match state_management::save_backup_export(
+                database_service,
+                export_wallet_name,
+                backup_export,
+            )
+            .await
+            {
                 Ok(()) => {
-                    log::info!("Backup file written successfully to: {}", file_path);
-                    alert_info(format!("Backup saved to: {}", file_path));
-                    *display_modal.write() = false;
-                }
-                Err(e) => {
-                    log::error!("Failed to write backup file: {}", e);
-                    alert_error(format!("Failed to save backup: {}", e));
+                    if let Some(mut backup_freshness) = backup_freshness {
+                        backup_freshness.restart();
+                    }
                 }
+                Err(e) => log::warn!("Could not record the backup export: {e}"),
             }
         }
-        #[cfg(not(feature = "desktop"))]
-        {
-            log::info!("File save unavailable on this platform");
-            alert_error("File save unavailable on this platform");
-        }
     };
 
     use_drop(|| log::debug!("BackupOnlineWallet Dropped"));
@@ -273,6 +301,13 @@ pub fn BackupOnlineWallet(wallet_name: CCStr) -> Element {
                 "Backup Descriptors"
             }
         }
+        if backup_outdated() {
+            span {
+                class: "badge badge-sm badge-warning",
+                title: "The descriptors of the wallet changed since the last backup export",
+                "Backup out of date"
+            }
+        }
         InfoModal { is_open: display_modal, title: "Online Wallet Descriptors Backup",
             div { class: "flex flex-col gap-4 max-w-2xl",
                 div { class: "alert alert-info",
@@ -288,31 +323,28 @@ pub fn BackupOnlineWallet(wallet_name: CCStr) -> Element {
                     }
                 }
 
+                if backup_outdated() {
+                    div { class: "alert alert-warning",
+                        DrawSvg::<Alert> {}
+                        "The descriptors of the wallet changed since your last export. \
+                        Save this backup again to replace the previous one."
+                    }
+                }
+
                 div { class: "flex flex-col gap-2",
                     div { class: "font-semibold", "Backup Data:" }
                     CopyTextarea { value: backup_data(), rows: 12 }
                 }
 
-                if CAN_DOWNLOAD {
-                    div { class: "flex justify-center",
-                        FileInput {
-                            display_path: ReadOnlySignal::from(backup_path),
-                            directory: true,
-                            onchange: move |evt: Event<FormData>| async move {
-                                if let Some(file_engine) = evt.files().clone() {
-                                    for file in file_engine.files() {
-                                        backup_directory.set(file);
-                                    }
-                                }
-                            },
-                        }
-                        MaybeHighlight {
-                            step: OnboardingStep::ClickSaveBackup,
-                            context_filter: consume_onboarding_context(),
-                            button { class: "btn", onclick: download_backup,
-                                DrawSvg::<FileDownload> { size: Size4 }
-                                "Save Backup"
-                            }
+                ExportToDirectory {
+                    directory: backup_directory,
+                    display_path: ReadOnlySignal::from(backup_path),
+                    MaybeHighlight {
+                        step: OnboardingStep::ClickSaveBackup,
+                        context_filter: consume_onboarding_context(),
+                        button { class: "btn", onclick: download_backup,
+                            DrawSvg::<FileDownload> { size: Size4 }
+                            "Save Backup"
                         }
                     }
                 }
@@ -370,8 +402,7 @@ pub fn UnlockLocalKey<LKU: LocalKeyUnlocker + 'static>() -> Element {
                 *display_modal.write() = false;
             }
             Err(e) => {
-                log::error!("Failed to unlock Local Key Provider: {e}");
-                alert_error(e);
+                alert_failure("Failed to unlock Local Key Provider", e);
             }
         }
         *is_unlocking.write() = false;
@@ -1,8 +1,16 @@
 use crate::prelude::*;
 
-use btc_heritage_wallet::Broadcaster;
+use std::collections::HashSet;
 
-use super::SpendStage;
+use btc_heritage_wallet::{bitcoin::Amount, btc_heritage::PartiallySignedTransaction, Broadcaster};
+use state_management::WalletSettings;
+
+use super::{
+    save_tx_note,
+    scan_psbt::ScanPsbtQRCode,
+    spend_limits::{psbt_sent_amount, use_memo_wallet_descriptors},
+    SpendStage, SpendTabsType, TxNote, TxNoteInput,
+};
 
 use crate::{
     components::{
@@ -24,10 +32,17 @@ pub(super) fn BroadcastTx<B: Broadcaster + 'static>() -> Element {
     let mut signed_psbt = use_context::<Signal<Option<super::SignedPsbt>>>();
     let signed_psbt_status = use_context::<Memo<super::SignedPsbtStatus>>();
     let psbt_overview = use_context::<FMemo<UITxDetails>>();
+    let spendtabs_type = use_context::<Signal<SpendTabsType>>();
+    let tx_note = use_context::<Signal<TxNote>>();
+    let database_service = state_management::use_database_service();
+    // Only the wallets of the owner have settings
+    let wallet_settings = try_use_context::<Resource<WalletSettings>>();
+    let wallet_descriptors = use_memo_wallet_descriptors();
 
     // Form state
 
     let mut show_import = use_signal(|| false);
+    let mut scanning = use_signal(|| false);
     use_effect(move || {
         if matches!(current_stage(), SpendStage::Broadcast) {
             *show_import.write() = signed_psbt.peek().is_none();
@@ -41,6 +56,34 @@ pub(super) fn BroadcastTx<B: Broadcaster + 'static>() -> Element {
         use_memo(move || matches!(*signed_psbt_status.read(), super::SignedPsbtStatus::Ok));
     let has_broadcast = use_memo(move || broadcast_txid.read().is_some());
 
+    // Checklist of the wallet, if the transaction sends enough to require it
+    let checklist_items = use_memo(move || {
+        let Some(broadcast_checklist) = wallet_settings
+            .and_then(|wallet_settings| wallet_settings.cloned())
+            .map(|wallet_settings| wallet_settings.broadcast_checklist)
+        else {
+            return vec![];
+        };
+        let sent_amount = signed_psbt
+            .lmap(|s| s.0.parse::<PartiallySignedTransaction>().ok())
+            .flatten()
+            .map(|psbt| psbt_sent_amount(&psbt, &wallet_descriptors.read()))
+            .unwrap_or(Amount::ZERO);
+        if broadcast_checklist.applies_to(sent_amount.to_sat()) {
+            broadcast_checklist.items()
+        } else {
+            vec![]
+        }
+    });
+    let mut checked_items: Signal<HashSet<usize>> = use_signal(HashSet::new);
+    use_effect(move || {
+        // A new transaction must be verified again
+        signed_psbt.read();
+        checked_items.write().clear();
+    });
+    let checklist_complete =
+        use_memo(move || checked_items.read().len() == checklist_items.read().len());
+
     let cant_broadcast_error = use_memo(move || match signed_psbt_status() {
         super::SignedPsbtStatus::Invalid(ccstr) => Some(ccstr),
         super::SignedPsbtStatus::NotSigned => Some(CCStr::from(
@@ -55,8 +98,7 @@ pub(super) fn BroadcastTx<B: Broadcaster + 'static>() -> Element {
             let psbt_to_broadcast = match psbt.0.parse() {
                 Ok(psbt) => psbt,
                 Err(e) => {
-                    alert_error(format!("Failed to parse transaction: {e}"));
-                    log::error!("Failed to parse transaction: {e}");
+                    alert_failure("Failed to parse transaction", e);
                     return;
                 }
             };
@@ -68,13 +110,13 @@ pub(super) fn BroadcastTx<B: Broadcaster + 'static>() -> Element {
                 .await
             {
                 Ok(txid) => {
+                    save_tx_note(database_service, spendtabs_type(), tx_note(), txid).await;
                     broadcast_txid.write().replace(txid);
                     alert_success("Transaction broadcast successfully: {txid}");
                     log::info!("Transaction broadcast successfully: {txid}");
                 }
                 Err(e) => {
-                    alert_error(format!("Failed to broadcast transaction: {e}"));
-                    log::error!("Failed to broadcast transaction: {e}");
+                    alert_failure("Failed to broadcast transaction", e);
                 }
             }
 
@@ -99,7 +141,7 @@ pub(super) fn BroadcastTx<B: Broadcaster + 'static>() -> Element {
                 div { class: "collapse-content",
                     div { class: "flex flex-col gap-2",
                         div { class: "text-sm text-(--color-base-content)/60",
-                            "Paste the encoded signed transaction (PSBT) to broadcast:"
+                            "Paste the encoded signed transaction (PSBT) to broadcast, or scan it from an airgapped signer:"
                         }
                         textarea {
                             class: "textarea textarea-bordered font-mono text-xs w-full",
@@ -119,6 +161,25 @@ pub(super) fn BroadcastTx<B: Broadcaster + 'static>() -> Element {
                         if let Some(msg) = cant_broadcast_error() {
                             div { class: "text-sm text-error", {msg} }
                         }
+                        if !has_broadcast() {
+                            button {
+                                class: "btn btn-outline btn-sm self-start",
+                                onclick: move |_| scanning.set(!scanning()),
+                                if scanning() {
+                                    "Stop Scanning"
+                                } else {
+                                    "Scan QR Code"
+                                }
+                            }
+                        }
+                        if scanning() {
+                            ScanPsbtQRCode {
+                                onscanned: move |psbt| {
+                                    scanning.set(false);
+                                    *signed_psbt.write() = Some(super::SignedPsbt(psbt));
+                                },
+                            }
+                        }
                     }
                 }
             }
@@ -148,6 +209,36 @@ pub(super) fn BroadcastTx<B: Broadcaster + 'static>() -> Element {
                 }
             }
 
+            TxNoteInput { disabled: broadcasting() || has_broadcast() }
+
+            if can_broadcast() && !has_broadcast() && !checklist_items.read().is_empty() {
+                div { class: "card shadow-xl",
+                    div { class: "card-body",
+                        h2 { class: "card-title", "Before broadcasting" }
+                        div { class: "text-sm font-light",
+                            "This transaction sends more than the checklist threshold of the wallet, confirm each verification."
+                        }
+                        for (index , item) in checklist_items.read().iter().enumerate() {
+                            label { key: "{index}", class: "label",
+                                input {
+                                    r#type: "checkbox",
+                                    class: "checkbox",
+                                    checked: checked_items.read().contains(&index),
+                                    onchange: move |evt| {
+                                        if evt.checked() {
+                                            checked_items.write().insert(index);
+                                        } else {
+                                            checked_items.write().remove(&index);
+                                        }
+                                    },
+                                }
+                                "{item}"
+                            }
+                        }
+                    }
+                }
+            }
+
             // Broadcasting section
             div { class: "card-actions justify-center mt-6",
                 MaybeHighlight {
@@ -156,7 +247,8 @@ pub(super) fn BroadcastTx<B: Broadcaster + 'static>() -> Element {
                     context_filter: consume_onboarding_context(),
                     button {
                         class: "btn btn-primary",
-                        disabled: !can_broadcast() || broadcasting() || has_broadcast(),
+                        disabled: !can_broadcast() || !checklist_complete() || broadcasting()
+                            || has_broadcast(),
                         onclick: broadcast_transaction,
                         if broadcasting() {
                             span { class: "loading loading-spinner loading-sm mr-2" }
@@ -0,0 +1,188 @@
+use crate::prelude::*;
+
+use btc_heritage_wallet::btc_heritage::PartiallySignedTransaction;
+
+use crate::{
+    components::{
+        export::{write_export_files, ExportToDirectory},
+        inputs::FileInput,
+        svg::{DrawSvg, FileDownload, SvgSize::Size4},
+    },
+    utils::CCStr,
+};
+
+/// Magic bytes starting a binary PSBT file
+const PSBT_MAGIC: &[u8] = b"psbt\xff";
+
+/// Name of the PSBT file written on the SD card, the Coldcard derives the names of the signed files from it
+fn coldcard_psbt_file_name(psbt: &PartiallySignedTransaction) -> String {
+    let txid = psbt.unsigned_tx.txid().to_string();
+    format!("heritage-{}.psbt", &txid[..8])
+}
+
+/// Parse a PSBT file written by a Coldcard, binary or base64 encoded
+fn psbt_from_coldcard_file(content: &[u8]) -> Result<CCStr, CCStr> {
+    let psbt = if content.starts_with(PSBT_MAGIC) {
+        PartiallySignedTransaction::deserialize(content).map_err(|e| CCStr::from(e.to_string()))?
+    } else {
+        std::str::from_utf8(content)
+            .ok()
+            .and_then(|text| text.trim().parse::<PartiallySignedTransaction>().ok())
+            .ok_or_else(|| {
+                CCStr::from(
+                    "The file does not contain a PSBT. \
+                    Make sure to pick the \"-signed.psbt\" or \"-part.psbt\" file, not the \".txn\" one.",
+                )
+            })?
+    };
+    Ok(CCStr::from(psbt.to_string()))
+}
+
+/// Guided signing of a PSBT on a Coldcard, exchanging it through the SD card
+///
+/// The unsigned PSBT is saved in the binary format expected by the Coldcard,
+/// then the signed file it writes back on the SD card is imported.
+#[component]
+pub(super) fn ColdcardSdCardSigning(
+    psbt: ReadOnlySignal<CCStr>,
+    onimported: EventHandler<CCStr>,
+) -> Element {
+    log::debug!("ColdcardSdCardSigning Rendered");
+
+    let parsed_psbt = use_memo(move || {
+        psbt.read()
+            .parse::<PartiallySignedTransaction>()
+            .map_err(|e| CCStr::from(e.to_string()))
+    });
+
+    let sd_card_directory = use_signal(String::new);
+    let psbt_path = use_memo(move || {
+        let directory = sd_card_directory.read();
+        match &*parsed_psbt.read() {
+            Ok(psbt) if !directory.is_empty() => {
+                format!("{directory}/{}", coldcard_psbt_file_name(psbt))
+            }
+            _ => String::new(),
+        }
+    });
+    // Path the PSBT was last saved to, the steps after the first are highlighted once it is
+    // saved on the SD card currently chosen
+    let mut saved_path = use_signal(|| None::<String>);
+    let saved = use_memo(move || saved_path.read().as_ref() == Some(&*psbt_path.read()));
+    let mut import_error = use_signal(|| None::<CCStr>);
+
+    let save_psbt = move |_| {
+        let Ok(ref psbt) = *parsed_psbt.read() else {
+            return;
+        };
+        let file_path = psbt_path.peek().clone();
+        if write_export_files("transaction", [(file_path.clone(), psbt.serialize())]) {
+            alert_info(format!("Transaction saved to: {file_path}"));
+            saved_path.set(Some(file_path));
+        }
+    };
+
+    use_drop(|| log::debug!("ColdcardSdCardSigning Dropped"));
+
+    rsx! {
+        div { class: "flex flex-col gap-4 rounded-box bg-base-200 p-4",
+            if let Err(e) = parsed_psbt() {
+                div { class: "alert alert-error", "Invalid transaction: {e}" }
+            }
+
+            div { class: "flex flex-col gap-2",
+                div { class: "font-semibold", "1. Save the transaction on the SD card" }
+                div { class: "text-sm text-(--color-base-content)/60",
+                    "Insert the SD card of your Coldcard in this computer and choose it."
+                }
+                ExportToDirectory {
+                    directory: sd_card_directory,
+                    display_path: ReadOnlySignal::from(psbt_path),
+                    class: "flex flex-row gap-2",
+                    button {
+                        class: "btn",
+                        disabled: psbt_path.read().is_empty(),
+                        onclick: save_psbt,
+                        DrawSvg::<FileDownload> { size: Size4 }
+                        "Save"
+                    }
+                }
+            }
+
+            div {
+                class: "flex flex-col gap-2",
+                class: if !saved() { "opacity-60" },
+                div { class: "font-semibold", "2. Sign on the Coldcard" }
+                ol { class: "list-decimal list-inside text-sm flex flex-col gap-1",
+                    li { "Eject the SD card and insert it in your Coldcard." }
+                    li {
+                        "Choose \"Ready To Sign\", select the file and carefully verify the amounts and addresses on the Coldcard screen."
+                    }
+                    li {
+                        "Approve: the Coldcard writes the signed transaction on the SD card, next to the original file."
+                    }
+                }
+            }
+
+            div { class: "flex flex-col gap-2",
+                div { class: "font-semibold", "3. Import the signed transaction" }
+                div { class: "text-sm text-(--color-base-content)/60",
+                    "Insert the SD card back in this computer and choose the \"-signed.psbt\" file (\"-part.psbt\" if other signatures are still missing)."
+                }
+                FileInput {
+                    accept: ".psbt,.txt",
+                    onchange: move |evt: Event<FormData>| async move {
+                        if let Some(file_engine) = evt.files().clone() {
+                            for file in file_engine.files() {
+                                let Some(content) = file_engine.read_file(&file).await else {
+                                    import_error.set(Some(CCStr::from(format!("Could not read {file}"))));
+                                    continue;
+                                };
+                                match psbt_from_coldcard_file(&content) {
+                                    Ok(signed_psbt) => {
+                                        import_error.set(None);
+                                        onimported.call(signed_psbt);
+                                    }
+                                    Err(e) => import_error.set(Some(e)),
+                                }
+                            }
+                        }
+                    },
+                }
+                if let Some(e) = import_error() {
+                    div { class: "text-sm text-error", {e} }
+                }
+            }
+        }
+    }
+}
+
+/// Signing on a Coldcard for wallets that cannot sign by themselves, like watch-only ones
+#[component]
+pub(super) fn ColdcardSignTx(
+    cant_broadcast: ReadOnlySignal<bool>,
+    show_export: Signal<bool>,
+) -> Element {
+    log::debug!("ColdcardSignTx Rendered");
+
+    let psbt_to_sign = use_context::<Signal<Option<super::PsbtToSign>>>();
+
+    let import_coldcard_psbt = move |psbt: CCStr| {
+        super::sign_tx::accept_airgapped_psbt(psbt, cant_broadcast(), show_export)
+    };
+
+    use_drop(|| log::debug!("ColdcardSignTx Dropped"));
+
+    rsx! {
+        div { class: "flex flex-col gap-4 mt-4",
+            h3 { class: "text-lg font-semibold", "Sign with a Coldcard (SD Card)" }
+            if let Some(psbt) = psbt_to_sign() {
+                ColdcardSdCardSigning { psbt: psbt.0, onimported: import_coldcard_psbt }
+            } else {
+                div { class: "text-sm text-(--color-base-content)/60",
+                    "Create a transaction first, it can then be signed on your Coldcard through its SD card."
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,138 @@
 use super::*;
 
+use btc_heritage_wallet::bitcoin::{psbt::PartiallySignedTransaction, TxOut};
+
+use crate::components::{inputs::FileInput, qr_scanner::QRScanner};
+
+/// Below this value, an output is not relayed by the Bitcoin network
+const DUST_LIMIT_SAT: u64 = 546;
+
+/// A co-heir receiving a share of the inheritance
+#[derive(Debug, Clone, PartialEq)]
+struct CoHeir {
+    address: Address,
+    /// Share of the inheritance in basis points
+    share: u64,
+}
+
+/// Parse the co-heirs, one per line as an address (or a `bitcoin:` URI) followed by a percentage
+fn parse_co_heirs(text: &str) -> Result<Vec<CoHeir>, String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let mut parts = line
+                .split(|c: char| c == ',' || c == ';' || c.is_whitespace())
+                .filter(|part| !part.is_empty());
+            let (Some(address), Some(percent), None) = (parts.next(), parts.next(), parts.next())
+            else {
+                return Err(format!("Expected an address and a percentage: {line}"));
+            };
+            let address = address
+                .strip_prefix("bitcoin:")
+                .map(|uri| uri.split('?').next().unwrap_or_default())
+                .unwrap_or(address);
+            let address = address
+                .parse::<Address<NetworkUnchecked>>()
+                .map_err(|e| format!("Address invalid: {e}"))
+                .and_then(check_address_network)?;
+            let share = percent
+                .trim_end_matches('%')
+                .parse::<f64>()
+                .ok()
+                .filter(|percent| *percent > 0.0 && *percent < 100.0)
+                .map(|percent| (percent * 100.0).round() as u64)
+                .ok_or_else(|| format!("Invalid percentage: {percent}"))?;
+            Ok(CoHeir { address, share })
+        })
+        .collect()
+}
+
+/// Replace the output draining the inheritance to `own_address` by one output per co-heir
+///
+/// The fee of the additional outputs is taken from the inheritance, at the fee rate of the
+/// transaction, and the heir keeps what remains after the shares of the co-heirs.
+/// Returns this additional fee.
+fn split_drain_output(
+    psbt: &mut PartiallySignedTransaction,
+    own_address: &Address,
+    fee: Amount,
+    co_heirs: &[CoHeir],
+) -> Result<Amount, String> {
+    let own_script = own_address.script_pubkey();
+    let Some(own_output_index) = psbt
+        .unsigned_tx
+        .output
+        .iter()
+        .position(|txout| txout.script_pubkey == own_script)
+    else {
+        return Err("The transaction does not pay the inheritance to your address".to_owned());
+    };
+    let vsize = psbt.unsigned_tx.vsize() as u64;
+    let extra_fee = (fee.to_sat() * MAX_OUTPUT_VBYTES * co_heirs.len() as u64).div_ceil(vsize);
+    let available = psbt.unsigned_tx.output[own_output_index]
+        .value
+        .checked_sub(extra_fee)
+        .ok_or("The inheritance cannot pay the fee of the co-heirs outputs")?;
+
+    let mut co_heirs_outputs = Vec::with_capacity(co_heirs.len());
+    for co_heir in co_heirs {
+        let value = available * co_heir.share / 10_000;
+        if value < DUST_LIMIT_SAT {
+            return Err(format!("The share of {} is too small", co_heir.address));
+        }
+        co_heirs_outputs.push(TxOut {
+            value,
+            script_pubkey: co_heir.address.script_pubkey(),
+        });
+    }
+    let co_heirs_total = co_heirs_outputs
+        .iter()
+        .map(|txout| txout.value)
+        .sum::<u64>();
+    let own_value = available - co_heirs_total;
+    if own_value < DUST_LIMIT_SAT {
+        return Err("Your own share is too small".to_owned());
+    }
+    psbt.unsigned_tx.output[own_output_index].value = own_value;
+
+    psbt.outputs
+        .extend(co_heirs_outputs.iter().map(|_| Default::default()));
+    psbt.unsigned_tx.output.extend(co_heirs_outputs);
+    Ok(Amount::from_sat(extra_fee))
+}
+
+/// Update the summary of a transaction after [split_drain_output] added the co-heirs outputs
+///
+/// The additional outputs pay the fee rate of the transaction, which is kept.
+fn update_split_tx_summary(
+    tx_summary: &mut TransactionSummary,
+    psbt: &PartiallySignedTransaction,
+    extra_fee: Amount,
+) {
+    let txid = psbt.unsigned_tx.txid();
+    tx_summary.txid = txid;
+    tx_summary.fee += extra_fee;
+    for owned_output in tx_summary.owned_outputs.iter_mut() {
+        owned_output.outpoint.txid = txid;
+        if let Some(txout) = psbt
+            .unsigned_tx
+            .output
+            .get(owned_output.outpoint.vout as usize)
+        {
+            owned_output.amount = Amount::from_sat(txout.value);
+        }
+    }
+    tx_summary.outputs_totals.count = psbt.unsigned_tx.output.len();
+    tx_summary.outputs_totals.amount = Amount::from_sat(
+        psbt.unsigned_tx
+            .output
+            .iter()
+            .map(|txout| txout.value)
+            .sum(),
+    );
+}
+
 /// Component for creating new transactions
 #[component]
 pub fn CreateHeirTx(heritage_id: CCStr, cant_sign: ReadOnlySignal<bool>) -> Element {
@@ -50,15 +183,45 @@ pub fn CreateHeirTx(heritage_id: CCStr, cant_sign: ReadOnlySignal<bool>) -> Elem
     let recipient_address = use_memo(move || {
         let recipient_address = &recipient.read().address;
         match recipient_address.parse::<Address<NetworkUnchecked>>() {
-            Ok(addr) => addr
-                .require_network(bitcoin_network::get())
-                .map(Some)
-                .map_err(|e| e.to_string()),
+            Ok(addr) => check_address_network(addr).map(Some),
             Err(e) if !recipient_address.is_empty() => Err(format!("Address invalid: {e}")),
             _ => Ok(None),
         }
     });
 
+    // Co-heirs of the same heritage receiving their share in the same transaction
+    let mut split_with_co_heirs = use_signal(|| false);
+    let mut co_heirs_text = use_signal(String::new);
+    let mut scan_co_heir = use_signal(|| false);
+    // Add co-heirs lines, scanned or imported, after the ones already entered
+    let mut append_co_heirs = move |lines: &str| {
+        let mut text = co_heirs_text.write();
+        if !text.is_empty() && !text.ends_with('\n') {
+            text.push('\n');
+        }
+        text.push_str(lines);
+    };
+    let co_heirs = use_memo(move || {
+        if !split_with_co_heirs() {
+            return Ok(vec![]);
+        }
+        let co_heirs = parse_co_heirs(&co_heirs_text.read())?;
+        if co_heirs.is_empty() {
+            return Err("Provide the address and the share of at least one co-heir".to_owned());
+        }
+        if co_heirs.iter().map(|co_heir| co_heir.share).sum::<u64>() >= 10_000 {
+            return Err("The shares of the co-heirs must leave some for you".to_owned());
+        }
+        Ok(co_heirs)
+    });
+    let own_share = use_memo(move || {
+        co_heirs
+            .read()
+            .as_ref()
+            .map(|co_heirs| 10_000 - co_heirs.iter().map(|co_heir| co_heir.share).sum::<u64>())
+            .unwrap_or(10_000)
+    });
+
     let mut show_advanced = use_signal(|| false);
 
     // Advanced options state
@@ -71,7 +234,9 @@ pub fn CreateHeirTx(heritage_id: CCStr, cant_sign: ReadOnlySignal<bool>) -> Elem
     let mut show_export = use_signal(|| false);
 
     // Validation
-    let form_valid = use_memo(move || recipient_address.read().as_ref().is_ok_and(Option::is_some));
+    let form_valid = use_memo(move || {
+        recipient_address.read().as_ref().is_ok_and(Option::is_some) && co_heirs.read().is_ok()
+    });
 
     // Create transaction handler
     let create_transaction = {
@@ -81,13 +246,28 @@ pub fn CreateHeirTx(heritage_id: CCStr, cant_sign: ReadOnlySignal<bool>) -> Elem
                 *creating.write() = true;
 
                 let address = recipient_address.cloned().unwrap().unwrap();
+                let co_heirs = co_heirs.cloned().unwrap_or_default();
 
-                match heirwallet
+                let own_address = address.clone();
+                let result = heirwallet
                     .with(async |hw: &HeirWallet| {
                         hw.create_psbt(heritage_id.as_ref(), address).await
                     })
                     .await
-                {
+                    .map_err(|e| e.to_string())
+                    .and_then(|(mut psbt, mut tx_summary)| {
+                        if !co_heirs.is_empty() {
+                            let extra_fee = split_drain_output(
+                                &mut psbt,
+                                &own_address,
+                                tx_summary.fee,
+                                &co_heirs,
+                            )?;
+                            update_split_tx_summary(&mut tx_summary, &psbt, extra_fee);
+                        }
+                        Ok((psbt, tx_summary))
+                    });
+                match result {
                     Ok((psbt, tx_summary)) => {
                         // Share with other stages
                         *psbt_to_sign.write() = Some(PsbtToSign(CCStr::from(psbt.to_string())));
@@ -101,8 +281,7 @@ pub fn CreateHeirTx(heritage_id: CCStr, cant_sign: ReadOnlySignal<bool>) -> Elem
                         log::info!("Transaction created successfully");
                     }
                     Err(e) => {
-                        alert_error(format!("Failed to create transaction: {e}"));
-                        log::error!("Failed to create transaction: {e}");
+                        alert_failure("Failed to create transaction", e);
                     }
                 }
 
@@ -135,6 +314,82 @@ pub fn CreateHeirTx(heritage_id: CCStr, cant_sign: ReadOnlySignal<bool>) -> Elem
                     div { class: "flex flex-col gap-4",
                         HeirTxRecipientInput { recipient }
                     }
+
+                    // Co-heirs
+                    div { class: "flex flex-col gap-2 p-4 border border-base-300 rounded-lg",
+                        label { class: "label",
+                            input {
+                                r#type: "checkbox",
+                                class: "toggle toggle-secondary",
+                                checked: split_with_co_heirs(),
+                                onchange: move |evt| split_with_co_heirs.set(evt.checked()),
+                            }
+                            span { class: "text-base ml-2", "Split with co-heirs" }
+                        }
+                        if split_with_co_heirs() {
+                            div { class: "text-sm font-light",
+                                "Pay each co-heir their agreed share in this transaction. One co-heir per line: \
+                                their address, or the content of their address QR code, followed by their share in percent. \
+                                You keep the rest, minus the fee of the additional outputs."
+                            }
+                            div { class: "flex flex-row gap-2 items-center flex-wrap",
+                                button {
+                                    class: "btn btn-sm btn-outline",
+                                    onclick: move |_| scan_co_heir.toggle(),
+                                    if scan_co_heir() {
+                                        "Stop Scanning"
+                                    } else {
+                                        "Scan an Address QR Code"
+                                    }
+                                }
+                                span { class: "text-sm", "or import a file of co-heirs lines:" }
+                                FileInput {
+                                    accept: ".txt,.csv",
+                                    onchange: move |evt: Event<FormData>| async move {
+                                        if let Some(file_engine) = evt.files() {
+                                            for file in file_engine.files() {
+                                                match file_engine.read_file_to_string(&file).await {
+                                                    Some(content) => append_co_heirs(content.trim()),
+                                                    None => {
+                                                        alert_failure(
+                                                            "Failed to import the co-heirs",
+                                                            format!("Could not read {file}"),
+                                                        )
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    },
+                                }
+                            }
+                            if scan_co_heir() {
+                                div { class: "text-sm", "Show the address QR code of a co-heir, then enter their share." }
+                                QRScanner {
+                                    onscan: move |scanned: CCStr| {
+                                        scan_co_heir.set(false);
+                                        append_co_heirs(&format!("{} ", scanned.trim()));
+                                    },
+                                }
+                            }
+                            textarea {
+                                class: "textarea w-full font-mono",
+                                rows: "4",
+                                placeholder: "bc1q... 25\nbitcoin:bc1p... 25",
+                                value: "{co_heirs_text}",
+                                oninput: move |evt| co_heirs_text.set(evt.value()),
+                            }
+                            match &*co_heirs.read() {
+                                Ok(_) => rsx! {
+                                    div { class: "text-sm",
+                                        "Your share: {own_share() as f64 / 100.0}%"
+                                    }
+                                },
+                                Err(e) => rsx! {
+                                    div { class: "text-sm text-error", "{e}" }
+                                },
+                            }
+                        }
+                    }
                 }
             }
 
@@ -209,10 +464,7 @@ fn HeirTxRecipientInput(recipient: Signal<RecipientState>) -> Element {
             .address
             .parse::<Address<NetworkUnchecked>>()
         {
-            Ok(addr) => match addr.require_network(bitcoin_network::get()) {
-                Ok(_) => None,
-                Err(e) => Some(e.to_string()),
-            },
+            Ok(addr) => check_address_network(addr).err(),
             Err(e) if !recipient.read().address.is_empty() => Some(format!("Address invalid: {e}")),
             _ => None,
         }
@@ -282,3 +534,53 @@ fn HeirTxRecipientInput(recipient: Signal<RecipientState>) -> Element {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use btc_heritage_wallet::{bitcoin::Network, btc_heritage::utils::bitcoin_network};
+
+    const P2WPKH: &str = "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq";
+    const P2TR: &str = "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr";
+
+    fn parse(text: &str) -> Result<Vec<(String, u64)>, String> {
+        bitcoin_network::set(Network::Bitcoin);
+        parse_co_heirs(text).map(|co_heirs| {
+            co_heirs
+                .into_iter()
+                .map(|co_heir| (co_heir.address.to_string(), co_heir.share))
+                .collect()
+        })
+    }
+
+    #[test]
+    fn addresses_and_uris_with_percentages() {
+        assert_eq!(
+            parse(&format!(
+                "{P2WPKH} 25\n\n  bitcoin:{P2TR}?amount=0.1&label=heir, 12.5%  \n"
+            )),
+            Ok(vec![(P2WPKH.to_owned(), 2500), (P2TR.to_owned(), 1250)])
+        );
+        assert_eq!(
+            parse(&format!("{P2WPKH};33.33")),
+            Ok(vec![(P2WPKH.to_owned(), 3333)])
+        );
+        assert_eq!(parse(""), Ok(vec![]));
+    }
+
+    #[test]
+    fn invalid_lines() {
+        assert!(parse(P2WPKH).is_err());
+        assert!(parse(&format!("{P2WPKH} 25 10")).is_err());
+        assert!(parse(&format!("{P2WPKH} 0")).is_err());
+        assert!(parse(&format!("{P2WPKH} 100%")).is_err());
+        assert!(parse(&format!("{P2WPKH} abc")).is_err());
+        assert!(parse("bc1qinvalid 25").is_err());
+    }
+
+    #[test]
+    fn address_of_another_network() {
+        assert!(parse("tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx 25").is_err());
+    }
+}
@@ -4,7 +4,6 @@ use std::collections::{HashMap, HashSet};
 
 use btc_heritage_wallet::{
     bitcoin::{address::NetworkUnchecked, Address, Amount, Denomination, OutPoint},
-    btc_heritage::utils::bitcoin_network,
     heritage_service_api_client::{
         NewTx, NewTxDrainTo, NewTxFeePolicy, NewTxRecipient, NewTxSpendingConfig,
         NewTxUtxoSelection, TransactionSummary,
@@ -16,13 +15,25 @@ use btc_heritage_wallet::{
 use crate::{
     components::{
         balance::UIBtcAmount,
-        svg::{BankPlus, Close, DrawSvg, Plus, SvgSize::Size3},
+        help::{HelpPopover, HelpTopic},
+        svg::{AlertOutline, BankPlus, Close, DrawSvg, Plus, SvgSize::Size3},
         transaction::{UITxDetails, UIUtxo},
     },
-    utils::{denomination_for_amount, feerate_sat_per_vb, CCStr, CheapClone},
+    helper_hooks::ExpirationStatus,
+    utils::{
+        check_address_network, denomination_for_amount, feerate_sat_per_vb, CCStr, CheapClone,
+    },
+    Route,
 };
 
-use super::{ExportEncodedTransaction, PsbtToSign, SignedPsbt, SpendStage};
+use state_management::CoolingOffRequest;
+
+use super::{
+    save_tx_note,
+    spend_limits::{use_spend_limits_guard, CoolingOffStatus},
+    ExportEncodedTransaction, PsbtToSign, SignedPsbt, SpendStage, SpendTabsType, TxNote,
+    TxNoteInput,
+};
 
 mod heir;
 mod owner;
@@ -58,6 +69,12 @@ impl core::str::FromStr for RecipientAmountUnit {
         }
     }
 }
+/// Addresses of the wallet belonging to a Heritage Configuration that is not the current one
+///
+/// Sending bitcoins to them would keep them under an outdated configuration.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct OutdatedConfigAddresses(HashSet<String>);
+
 /// State for a single recipient
 #[derive(Debug, Clone, PartialEq)]
 struct RecipientState {
@@ -66,6 +83,12 @@ struct RecipientState {
     amount: String,
     unit: RecipientAmountUnit,
     drain_to: bool,
+    /// The amount follows the [MaxSendInputs::max_amount] of the recipient
+    use_max: bool,
+    /// Share of the balance sent to the recipient, when splitting by percentage
+    percent: String,
+    /// The user confirmed sending to an address of the wallet itself, e.g. to consolidate
+    self_send_intended: bool,
 }
 impl Default for RecipientState {
     fn default() -> Self {
@@ -75,9 +98,201 @@ impl Default for RecipientState {
             amount: String::new(),
             unit: RecipientAmountUnit::default(),
             drain_to: false,
+            use_max: false,
+            percent: String::new(),
+            self_send_intended: false,
         }
     }
 }
+impl RecipientState {
+    fn parsed_amount(&self) -> Option<Amount> {
+        Amount::from_str_in(
+            &self.amount,
+            match self.unit {
+                RecipientAmountUnit::Sat => Denomination::Satoshi,
+                RecipientAmountUnit::MilliBtc => Denomination::MilliBitcoin,
+                RecipientAmountUnit::Btc => Denomination::Bitcoin,
+            },
+        )
+        .ok()
+    }
+
+    /// The address of the recipient belongs to the wallet
+    fn is_self_send(&self, addresses_set: &HashSet<Address>) -> bool {
+        self.address
+            .parse::<Address<NetworkUnchecked>>()
+            .is_ok_and(|address| addresses_set.contains(&address.assume_checked()))
+    }
+
+    /// Share of the recipient in basis points, `None` if invalid
+    fn parsed_percent(&self) -> Option<u64> {
+        self.percent
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|percent| *percent > 0.0 && *percent <= 100.0)
+            .map(|percent| (percent * 100.0).round() as u64)
+    }
+
+    /// Set the amount, displayed in the most readable unit
+    fn set_amount(&mut self, amount: Amount) {
+        let denomination = denomination_for_amount(amount);
+        self.amount = format!("{}", amount.display_in(denomination));
+        self.unit = match denomination {
+            Denomination::Bitcoin => RecipientAmountUnit::Btc,
+            Denomination::MilliBitcoin => RecipientAmountUnit::MilliBtc,
+            _ => RecipientAmountUnit::Sat,
+        };
+    }
+}
+
+// Virtual sizes used to estimate the fee of a transaction before creating it.
+// Owners spend through the Taproot key path, and no output is bigger than a Taproot one.
+const TX_OVERHEAD_VBYTES: u64 = 11;
+const TAPROOT_KEYPATH_INPUT_VBYTES: u64 = 58;
+const MAX_OUTPUT_VBYTES: u64 = 43;
+
+/// What the largest amount a recipient can receive is computed from
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct MaxSendInputs {
+    /// Total of the UTXOs the transaction can spend
+    inputs_total: Amount,
+    input_count: u64,
+    fee_rate: f32,
+    /// Fee of the transaction, if fixed by the user
+    absolute_fee: Option<Amount>,
+}
+impl MaxSendInputs {
+    fn new(
+        utxos_with_info: &[UtxoWithInfo],
+        utxo_selection: Option<&NewTxUtxoSelection>,
+        fee_policy: Option<&NewTxFeePolicy>,
+        default_fee_rate: f32,
+    ) -> Self {
+        let spent = |outpoint: &OutPoint| match utxo_selection {
+            Some(NewTxUtxoSelection::UseOnly { use_only }) => use_only.contains(outpoint),
+            Some(NewTxUtxoSelection::Exclude { exclude })
+            | Some(NewTxUtxoSelection::IncludeExclude { exclude, .. }) => {
+                !exclude.contains(outpoint)
+            }
+            Some(NewTxUtxoSelection::Include { .. }) | None => true,
+        };
+        let (inputs_total, input_count) = utxos_with_info
+            .iter()
+            .filter(|utxo| spent(&utxo.outpoint))
+            .fold((Amount::ZERO, 0), |(total, count), utxo| {
+                (total + utxo.amount, count + 1)
+            });
+        let (fee_rate, absolute_fee) = match fee_policy {
+            Some(NewTxFeePolicy::Absolute { amount }) => {
+                (default_fee_rate, Some(Amount::from_sat(*amount)))
+            }
+            Some(NewTxFeePolicy::Rate { rate }) => (*rate, None),
+            None => (default_fee_rate, None),
+        };
+        Self {
+            inputs_total,
+            input_count,
+            fee_rate,
+            absolute_fee,
+        }
+    }
+
+    /// Largest amount a recipient can receive when the `others_total` is sent to the
+    /// other recipients, spending all the UTXOs without change
+    fn max_amount(&self, recipient_count: u64, others_total: Amount) -> Option<Amount> {
+        let fee = self.absolute_fee.unwrap_or_else(|| {
+            let vsize = TX_OVERHEAD_VBYTES
+                + self.input_count * TAPROOT_KEYPATH_INPUT_VBYTES
+                + recipient_count * MAX_OUTPUT_VBYTES;
+            Amount::from_sat((vsize as f32 * self.fee_rate).ceil() as u64)
+        });
+        self.inputs_total
+            .checked_sub(others_total)?
+            .checked_sub(fee)
+            .filter(|amount| *amount > Amount::ZERO)
+    }
+
+    /// Split all the balance between the recipients by percentage
+    ///
+    /// The fee is paid by the recipients in proportion of their share, and the last
+    /// recipient gets the rounding remainder.
+    fn split_amounts(&self, recipients: &[RecipientState]) -> Option<Vec<Amount>> {
+        let shares = recipients
+            .iter()
+            .map(RecipientState::parsed_percent)
+            .collect::<Option<Vec<_>>>()?;
+        if shares.is_empty() || shares.iter().sum::<u64>() != 10_000 {
+            return None;
+        }
+        let total = self.max_amount(shares.len() as u64, Amount::ZERO)?;
+        let mut amounts = shares
+            .iter()
+            .map(|share| Amount::from_sat(total.to_sat() * share / 10_000))
+            .collect::<Vec<_>>();
+        let distributed = amounts.iter().copied().sum::<Amount>();
+        *amounts.last_mut().expect("not empty") += total - distributed;
+        Some(amounts)
+    }
+}
+
+/// Exclude the UTXOs without the confirmations required by the wallet settings
+/// from a selection, so that they are never spent
+fn exclude_unspendable_utxos(
+    utxo_selection: Option<NewTxUtxoSelection>,
+    utxos_with_info: &[UtxoWithInfo],
+) -> Option<NewTxUtxoSelection> {
+    let unspendable = utxos_with_info
+        .iter()
+        .filter(|utxo| !utxo.spendable)
+        .map(|utxo| utxo.outpoint)
+        .collect::<HashSet<_>>();
+    if unspendable.is_empty() {
+        return utxo_selection;
+    }
+    Some(match utxo_selection {
+        None => NewTxUtxoSelection::Exclude {
+            exclude: unspendable.into_iter().collect(),
+        },
+        Some(NewTxUtxoSelection::Include { mut include }) => {
+            include.retain(|outpoint| !unspendable.contains(outpoint));
+            let exclude = unspendable.into_iter().collect();
+            if include.is_empty() {
+                NewTxUtxoSelection::Exclude { exclude }
+            } else {
+                NewTxUtxoSelection::IncludeExclude { include, exclude }
+            }
+        }
+        Some(NewTxUtxoSelection::Exclude { mut exclude }) => {
+            let missing = unspendable
+                .into_iter()
+                .filter(|outpoint| !exclude.contains(outpoint))
+                .collect::<Vec<_>>();
+            exclude.extend(missing);
+            NewTxUtxoSelection::Exclude { exclude }
+        }
+        Some(NewTxUtxoSelection::IncludeExclude {
+            mut include,
+            mut exclude,
+        }) => {
+            include.retain(|outpoint| !unspendable.contains(outpoint));
+            let missing = unspendable
+                .into_iter()
+                .filter(|outpoint| !exclude.contains(outpoint))
+                .collect::<Vec<_>>();
+            exclude.extend(missing);
+            if include.is_empty() {
+                NewTxUtxoSelection::Exclude { exclude }
+            } else {
+                NewTxUtxoSelection::IncludeExclude { include, exclude }
+            }
+        }
+        Some(NewTxUtxoSelection::UseOnly { mut use_only }) => {
+            use_only.retain(|outpoint| !unspendable.contains(outpoint));
+            NewTxUtxoSelection::UseOnly { use_only }
+        }
+    })
+}
 
 /// Advanced settings components
 #[component]
@@ -110,7 +325,10 @@ fn FeePolicyInput(
     rsx! {
         // Fee policy
         fieldset { class: "fieldset",
-            legend { class: "fieldset-legend", "Fee Policy" }
+            legend { class: "fieldset-legend",
+                "Fee Policy"
+                HelpPopover { topic: HelpTopic::FeePolicy }
+            }
             div { class: "flex flex-col gap-4",
                 div { class: "flex flex-row gap-6",
                     label { class: "label",
@@ -237,7 +455,10 @@ fn UtxoSelection(utxo_selection: Signal<Option<NewTxUtxoSelection>>) -> Element
     rsx! {
         // UTXO selection placeholder
         fieldset { class: "fieldset",
-            legend { class: "fieldset-legend", "UTXO Selection" }
+            legend { class: "fieldset-legend",
+                "UTXO Selection"
+                HelpPopover { topic: HelpTopic::UtxoSelection }
+            }
             div { class: "flex flex-row gap-6",
                 label { class: "label",
                     input {
@@ -298,6 +519,7 @@ fn UtxoSelection(utxo_selection: Signal<Option<NewTxUtxoSelection>>) -> Element
                                                             r#type: "checkbox",
                                                             name: "inc_exc",
                                                             class: "checkbox",
+                                                            disabled: !utxo_with_info.spendable,
                                                             checked: utxo_inc_exc_status.read().get(&utxo_with_info.outpoint).is_some_and(|b| *b),
                                                             onchange: {
                                                                 let outpoint = utxo_with_info.outpoint;
@@ -339,6 +561,7 @@ fn UtxoSelection(utxo_selection: Signal<Option<NewTxUtxoSelection>>) -> Element
                                                         r#type: "checkbox",
                                                         name: "manual_select",
                                                         class: "checkbox",
+                                                        disabled: !utxo_with_info.spendable,
                                                         checked: utxo_selected_status.read().contains(&utxo_with_info.outpoint),
                                                         onchange: {
                                                             let outpoint = utxo_with_info.outpoint;
@@ -359,6 +582,13 @@ fn UtxoSelection(utxo_selection: Signal<Option<NewTxUtxoSelection>>) -> Element
                                     }
                                     td {
                                         AlwaysLoadedComponent::<UIUtxo> { input: utxo_with_info.ref_into() }
+                                        if !utxo_with_info.spendable {
+                                            div {
+                                                class: "badge badge-sm badge-warning",
+                                                title: "The wallet settings require more confirmations to spend this UTXO",
+                                                "{utxo_with_info.confirmations.unwrap_or_default()} confirmation(s), not spendable yet"
+                                            }
+                                        }
                                     }
                                 }
                             }
@@ -20,6 +20,42 @@ pub fn CreateOwnerTx(cant_sign: ReadOnlySignal<bool>) -> Element {
     let mut signed_psbt = use_context::<Signal<Option<SignedPsbt>>>();
     let mut shared_tx_summary = use_context::<Signal<Option<TransactionSummary>>>();
     let psbt_overview = use_context::<FMemo<UITxDetails>>();
+    let spendtabs_type = use_context::<Signal<SpendTabsType>>();
+    let tx_note = use_context::<Signal<TxNote>>();
+    let database_service = state_management::use_database_service();
+    let heritage_configs_with_info =
+        use_context::<FMemo<CheapClone<[helper_hooks::HeritageConfigWithInfo]>>>();
+    let addresses_with_info =
+        use_context::<FMemo<CheapClone<[helper_hooks::WalletAddressWithInfo]>>>();
+
+    // The first Heritage Configuration is the current one
+    let current_config_status = use_memo(move || {
+        match heritage_configs_with_info.lrmap(|hcwi| hcwi.first().map(|h| h.expiration_status)) {
+            Some(Ok(status)) => status,
+            _ => None,
+        }
+    });
+    let outdated_config_addresses = use_memo(move || {
+        OutdatedConfigAddresses(
+            addresses_with_info
+                .lrmap_ok(|addresses_with_info| {
+                    addresses_with_info
+                        .iter()
+                        .filter(|awi| {
+                            matches!(
+                                awi.heritage_config_infos,
+                                Some(Ok(ref hcwi)) if hcwi.expiration_status != ExpirationStatus::Current
+                            )
+                        })
+                        .map(|awi| awi.wallet_address.address().to_string())
+                        .collect()
+                })
+                .unwrap_or_default(),
+        )
+    });
+    use_context_provider(|| outdated_config_addresses);
+    let utxos_with_info = use_context::<FMemo<CheapClone<[UtxoWithInfo]>>>();
+    let addresses_set = use_context::<FMemo<HashSet<Address>>>();
 
     // Form state
     let mut recipients = use_signal(|| vec![RecipientState::default()]);
@@ -38,14 +74,59 @@ pub fn CreateOwnerTx(cant_sign: ReadOnlySignal<bool>) -> Element {
 
     let utxo_selection: Signal<Option<NewTxUtxoSelection>> = use_signal(|| None);
 
+    let max_send_inputs = use_memo(move || {
+        utxos_with_info.lrmap_ok(|utxos_with_info| {
+            MaxSendInputs::new(
+                utxos_with_info,
+                exclude_unspendable_utxos(utxo_selection.cloned(), utxos_with_info).as_ref(),
+                fee_policy.read().as_ref(),
+                default_fee_rate(),
+            )
+        })
+    });
+    use_context_provider(|| max_send_inputs);
+
     // Transaction creation state
     let mut creating = use_signal(|| false);
 
     let mut show_export = use_signal(|| false);
 
+    // Drain the wallet to the recipients, split by percentage
+    let mut split_by_percent = use_signal(|| false);
+    let split_amounts = use_memo(move || {
+        if !split_by_percent() {
+            return None;
+        }
+        max_send_inputs()?.split_amounts(&recipients.read())
+    });
+    let percent_sum_error = use_memo(move || {
+        if !split_by_percent() || split_amounts.read().is_some() {
+            return None;
+        }
+        let percents = recipients
+            .read()
+            .iter()
+            .map(|r| r.parsed_percent())
+            .collect::<Option<Vec<_>>>();
+        Some(match percents {
+            Some(percents) if percents.iter().sum::<u64>() != 10_000 => format!(
+                "The percentages must add up to 100% (currently {}%)",
+                percents.iter().sum::<u64>() as f64 / 100.0
+            ),
+            Some(_) => "The balance is too low to pay the fee".to_owned(),
+            None => "Each recipient needs a percentage between 0 and 100".to_owned(),
+        })
+    });
+
+    // Self-imposed spend limits of the wallet
+    let spend_limits_guard = use_spend_limits_guard();
+
     // Validation
 
     let total_spend = use_memo(move || {
+        if let Some(ref split_amounts) = *split_amounts.read() {
+            return split_amounts.iter().copied().sum::<Amount>();
+        }
         recipients()
             .iter()
             .filter_map(|r| {
@@ -80,26 +161,90 @@ pub fn CreateOwnerTx(cant_sign: ReadOnlySignal<bool>) -> Element {
         recipients()
             .iter()
             .all(|r| match r.address.parse::<Address<NetworkUnchecked>>() {
-                Ok(addr) => addr.require_network(bitcoin_network::get()).is_ok(),
+                Ok(addr) => check_address_network(addr).is_ok(),
                 Err(_) => false,
             })
     });
 
+    let unintended_self_send = use_memo(move || {
+        let Some(Ok(ref addresses_set)) = *addresses_set.read() else {
+            return false;
+        };
+        recipients()
+            .iter()
+            .any(|r| !r.self_send_intended && r.is_self_send(addresses_set))
+    });
+
+    let outdated_config_recipient = use_memo(move || {
+        recipients()
+            .iter()
+            .any(|r| outdated_config_addresses.read().0.contains(&r.address))
+    });
+
+    let spend_limit_error = use_memo(move || spend_limits_guard.limit_error(total_spend()));
+
+    // Identifies the transaction a cooling-off request is for
+    let spend_key = use_memo(move || {
+        CoolingOffRequest::spend_key(
+            recipients().iter().map(|r| {
+                r.address
+                    .parse::<Address<NetworkUnchecked>>()
+                    .map(|addr| addr.assume_checked().to_string())
+                    .unwrap_or_else(|_| r.address.clone())
+            }),
+            total_spend(),
+        )
+    });
+    let cooling_off_status =
+        use_memo(move || spend_limits_guard.cooling_off_status(&spend_key.read(), total_spend()));
+
     let form_valid = use_memo(move || {
         at_least_one_recipient()
             && all_addresses_ok()
+            && !outdated_config_recipient()
+            && !unintended_self_send()
+            && (!split_by_percent() || split_amounts.read().is_some())
             && !multiple_drain_to_error()
             && !max_one_recipient_error()
             && !max_spend_error()
+            && spend_limit_error.read().is_none()
     });
 
+    let request_transaction = move |_| spend_limits_guard.request(spend_key());
+
     // Create transaction handler
     let create_transaction = move |_| async move {
+        let effective_utxo_selection = match &*utxos_with_info.read() {
+            Some(Ok(utxos_with_info)) => {
+                exclude_unspendable_utxos(utxo_selection.cloned(), utxos_with_info)
+            }
+            _ => {
+                alert_error("The UTXOs of the wallet are not loaded yet");
+                return;
+            }
+        };
         *creating.write() = true;
 
         let recipients = &*recipients.read();
 
-        let spending_config = if recipients.len() == 1 && recipients[0].drain_to {
+        let spending_config = if let Some(split_amounts) = split_amounts() {
+            if recipients.len() == 1 {
+                NewTxSpendingConfig::DrainTo(NewTxDrainTo {
+                    drain_to: recipients[0].address.clone(),
+                })
+            } else {
+                NewTxSpendingConfig::Recipients(
+                    recipients
+                        .iter()
+                        .zip(split_amounts)
+                        .map(|(r, amount)| NewTxRecipient {
+                            address: r.address.clone(),
+                            amount: amount.to_sat(),
+                        })
+                        .collect(),
+                )
+            }
+        } else if recipients.len() == 1 && recipients[0].drain_to {
             NewTxSpendingConfig::DrainTo(NewTxDrainTo {
                 drain_to: recipients[0].address.clone(),
             })
@@ -129,7 +274,7 @@ pub fn CreateOwnerTx(cant_sign: ReadOnlySignal<bool>) -> Element {
         let new_tx = NewTx {
             spending_config,
             fee_policy: fee_policy(),
-            utxo_selection: utxo_selection.cloned(),
+            utxo_selection: effective_utxo_selection,
             disable_rbf: None,
         };
 
@@ -138,6 +283,21 @@ pub fn CreateOwnerTx(cant_sign: ReadOnlySignal<bool>) -> Element {
             .await
         {
             Ok((psbt, tx_summary)) => {
+                // The TXID does not change when signing a SegWit transaction
+                save_tx_note(
+                    database_service,
+                    spendtabs_type(),
+                    tx_note(),
+                    tx_summary.txid,
+                )
+                .await;
+                // The transaction still has to go through the verification of the spend
+                // limits before being signed, which needs the elapsed cooling-off delay
+                if cooling_off_status() == CoolingOffStatus::Ready {
+                    spend_limits_guard
+                        .transfer_request(spend_limits_guard.spend_key(&psbt))
+                        .await;
+                }
                 // Share with other stages
                 *psbt_to_sign.write() = Some(PsbtToSign(CCStr::from(psbt.to_string())));
                 *shared_tx_summary.write() = Some(tx_summary);
@@ -150,8 +310,7 @@ pub fn CreateOwnerTx(cant_sign: ReadOnlySignal<bool>) -> Element {
                 log::info!("Transaction created successfully");
             }
             Err(e) => {
-                alert_error(format!("Failed to create transaction: {e}"));
-                log::error!("Failed to create transaction: {e}");
+                alert_failure("Failed to create transaction", e);
             }
         }
 
@@ -165,8 +324,38 @@ pub fn CreateOwnerTx(cant_sign: ReadOnlySignal<bool>) -> Element {
 
     use_drop(|| log::debug!("CreateOwnerTx Dropped"));
 
+    let go_to_configuration = move |_| {
+        if let SpendTabsType::Owner(ref wallet_name) = *spendtabs_type.read() {
+            navigator().push(Route::WalletConfigurationView {
+                wallet_name: wallet_name.clone(),
+            });
+        }
+    };
+
     rsx! {
         div { class: "flex flex-col gap-6",
+            if let Some(status @ (ExpirationStatus::ExpireSoon | ExpirationStatus::Expired)) = current_config_status() {
+                div { class: "alert alert-warning",
+                    DrawSvg::<AlertOutline> {}
+                    div {
+                        div { class: "font-medium",
+                            if status == ExpirationStatus::Expired {
+                                "The current Heritage Configuration of this wallet is expired"
+                            } else {
+                                "The current Heritage Configuration of this wallet expires soon"
+                            }
+                        }
+                        div { class: "text-sm mt-1",
+                            "The change of this transaction would go back to this configuration. Create a new Heritage Configuration first so that the change is protected by it."
+                        }
+                    }
+                    button {
+                        class: "btn btn-sm",
+                        onclick: go_to_configuration,
+                        "Wallet Configuration"
+                    }
+                }
+            }
             // Main form
             div { class: "card shadow-xl",
                 div { class: "card-body",
@@ -192,12 +381,30 @@ pub fn CreateOwnerTx(cant_sign: ReadOnlySignal<bool>) -> Element {
                         }
                     }
 
+                    label { class: "label mb-2",
+                        input {
+                            r#type: "checkbox",
+                            class: "toggle",
+                            checked: split_by_percent(),
+                            onchange: move |evt| {
+                                *split_by_percent.write() = evt.checked();
+                                for recipient in recipients.write().iter_mut() {
+                                    recipient.drain_to = false;
+                                    recipient.use_max = false;
+                                }
+                            },
+                        }
+                        "Send all the balance, split between the recipients by percentage"
+                    }
+
                     // Recipients
                     div { class: "flex flex-col gap-4",
                         for (index , recipient) in recipients.read().iter().enumerate() {
                             OwnerTxRecipientInput {
                                 key: "{recipient.uuid}",
                                 index,
+                                split_by_percent,
+                                split_amount: split_amounts.read().as_ref().and_then(|amounts| amounts.get(index).copied()),
                                 max_one_recipient_error,
                                 multiple_drain_to_error,
                                 max_spend_error,
@@ -205,6 +412,10 @@ pub fn CreateOwnerTx(cant_sign: ReadOnlySignal<bool>) -> Element {
                         }
                     }
 
+                    if let Some(percent_sum_error) = percent_sum_error() {
+                        div { class: "text-sm text-error", {percent_sum_error} }
+                    }
+
                     // Action buttons
                     div { class: "card-actions",
                         button {
@@ -234,18 +445,51 @@ pub fn CreateOwnerTx(cant_sign: ReadOnlySignal<bool>) -> Element {
                 }
             }
 
-            div { class: "card-actions justify-center mt-6",
-                button {
-                    class: "btn btn-primary",
-                    disabled: !form_valid() || creating(),
-                    onclick: create_transaction,
-                    if creating() {
-                        span { class: "loading loading-spinner loading-sm mr-2" }
-                        "Creating..."
-                    } else {
-                        DrawSvg::<BankPlus> {}
-                        "Create Transaction"
-                    }
+            TxNoteInput { disabled: creating() }
+
+            if let Some(spend_limit_error) = spend_limit_error() {
+                div { class: "alert alert-error", {spend_limit_error} }
+            }
+            div { class: "card-actions justify-center items-center mt-6",
+                match cooling_off_status() {
+                    CoolingOffStatus::NotNeeded | CoolingOffStatus::Ready => rsx! {
+                        button {
+                            class: "btn btn-primary",
+                            disabled: !form_valid() || creating(),
+                            onclick: create_transaction,
+                            if creating() {
+                                span { class: "loading loading-spinner loading-sm mr-2" }
+                                "Creating..."
+                            } else {
+                                DrawSvg::<BankPlus> {}
+                                if cooling_off_status() == CoolingOffStatus::Ready {
+                                    "Confirm and Create Transaction"
+                                } else {
+                                    "Create Transaction"
+                                }
+                            }
+                        }
+                    },
+                    CoolingOffStatus::ToRequest => rsx! {
+                        button {
+                            class: "btn btn-primary",
+                            disabled: !form_valid(),
+                            onclick: request_transaction,
+                            "Request Transaction"
+                        }
+                        span { class: "text-sm",
+                            "This amount is above the cooling-off threshold of the wallet, \
+                            you will confirm the transaction after {spend_limits_guard.spend_limits().cooling_off_minutes} minutes."
+                        }
+                    },
+                    CoolingOffStatus::Waiting(remaining_secs) => rsx! {
+                        button { class: "btn btn-primary", disabled: true,
+                            "Confirm in {remaining_secs.div_ceil(60)} min"
+                        }
+                        span { class: "text-sm",
+                            "Cooling-off period in progress, the transaction can be confirmed once it is over."
+                        }
+                    },
                 }
             }
             if let Some(psbt) = psbt_to_sign() {
@@ -275,6 +519,8 @@ pub fn CreateOwnerTx(cant_sign: ReadOnlySignal<bool>) -> Element {
 #[component]
 fn OwnerTxRecipientInput(
     index: usize,
+    split_by_percent: ReadOnlySignal<bool>,
+    split_amount: Option<Amount>,
     max_one_recipient_error: ReadOnlySignal<bool>,
     multiple_drain_to_error: ReadOnlySignal<bool>,
     max_spend_error: ReadOnlySignal<bool>,
@@ -283,6 +529,9 @@ fn OwnerTxRecipientInput(
 
     let mut recipients = use_context::<Signal<Vec<RecipientState>>>();
     let max_spendable_amount = use_context::<FMemo<Amount>>();
+    let outdated_config_addresses = use_context::<Memo<OutdatedConfigAddresses>>();
+    let max_send_inputs = use_context::<Memo<Option<MaxSendInputs>>>();
+    let addresses_set = use_context::<FMemo<HashSet<Address>>>();
 
     let recipient = use_memo(move || {
         log::debug!("OwnerTxRecipientInput {index} - Compute recipient");
@@ -296,10 +545,7 @@ fn OwnerTxRecipientInput(
             .address
             .parse::<Address<NetworkUnchecked>>()
         {
-            Ok(addr) => match addr.require_network(bitcoin_network::get()) {
-                Ok(_) => None,
-                Err(e) => Some(e.to_string()),
-            },
+            Ok(addr) => check_address_network(addr).err(),
             Err(e) if !recipient.read().address.is_empty() => Some(format!("Address invalid: {e}")),
             _ => None,
         }
@@ -328,6 +574,51 @@ fn OwnerTxRecipientInput(
         }
     });
 
+    let duplicate_warning = use_memo(move || {
+        log::debug!("OwnerTxRecipientInput {index} - Compute duplicate_warning");
+        let recipients = recipients.read();
+        let address = recipients[index].address.trim();
+        (!address.is_empty()
+            && recipients
+                .iter()
+                .enumerate()
+                .any(|(i, r)| i != index && r.address.trim() == address))
+        .then_some("Another recipient has the same address")
+    });
+    let is_self_send = use_memo(move || {
+        log::debug!("OwnerTxRecipientInput {index} - Compute is_self_send");
+        match *addresses_set.read() {
+            Some(Ok(ref addresses_set)) => recipient.read().is_self_send(addresses_set),
+            _ => false,
+        }
+    });
+
+    let outdated_config_error = use_memo(move || {
+        log::debug!("OwnerTxRecipientInput {index} - Compute outdated_config_error");
+        if outdated_config_addresses
+            .read()
+            .0
+            .contains(&recipient.read().address)
+        {
+            Some("This address belongs to an outdated Heritage Configuration of your wallet, use a new address")
+        } else {
+            None
+        }
+    });
+
+    let max_amount = use_memo(move || {
+        log::debug!("OwnerTxRecipientInput {index} - Compute max_amount");
+        let max_send_inputs = max_send_inputs()?;
+        let recipients = recipients.read();
+        let others_total = recipients
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != index)
+            .filter_map(|(_, r)| r.parsed_amount())
+            .sum::<Amount>();
+        max_send_inputs.max_amount(recipients.len() as u64, others_total)
+    });
+
     let max_spend_error = use_memo(move || {
         log::debug!("OwnerTxRecipientInput {index} - Compute max_spend_error");
         if max_spend_error() {
@@ -341,30 +632,29 @@ fn OwnerTxRecipientInput(
         let recipient = &mut recipients.write()[index];
         recipient.drain_to = evt.checked();
         if recipient.drain_to {
+            recipient.use_max = false;
             if let Some(Ok(ref max_spendable_amount)) = *max_spendable_amount.read() {
-                (recipient.amount, recipient.unit) =
-                    match denomination_for_amount(*max_spendable_amount) {
-                        Denomination::Bitcoin => (
-                            format!("{}", max_spendable_amount.display_in(Denomination::Bitcoin),),
-                            RecipientAmountUnit::Btc,
-                        ),
-                        Denomination::MilliBitcoin => (
-                            format!(
-                                "{}",
-                                max_spendable_amount.display_in(Denomination::MilliBitcoin),
-                            ),
-                            RecipientAmountUnit::MilliBtc,
-                        ),
-                        Denomination::Satoshi => (
-                            format!("{}", max_spendable_amount.display_in(Denomination::Satoshi),),
-                            RecipientAmountUnit::Sat,
-                        ),
-                        _ => {
-                            unreachable!("denomination_for_amount never return another denom",)
-                        }
-                    };
+                recipient.set_amount(*max_spendable_amount);
+            }
+        }
+    };
+
+    // Keep the amount of the recipient at its maximum, when asked
+    use_effect(move || {
+        if !recipient.read().use_max {
+            return;
+        }
+        if let Some(max_amount) = max_amount() {
+            if recipient.read().parsed_amount() != Some(max_amount) {
+                recipients.write()[index].set_amount(max_amount);
             }
         }
+    });
+    let use_max_click = move |_| {
+        for (i, recipient) in recipients.write().iter_mut().enumerate() {
+            // Only one recipient can follow its maximum, they would depend on each other
+            recipient.use_max = i == index;
+        }
     };
 
     use_drop(move || log::debug!("OwnerTxRecipientInput {index} Dropped"));
@@ -395,6 +685,23 @@ fn OwnerTxRecipientInput(
                     if let Some(ref address_error) = *address_error.read() {
                         div { class: "label text-error", "{address_error}" }
                     }
+                    if let Some(outdated_config_error) = outdated_config_error() {
+                        div { class: "label text-error", {outdated_config_error} }
+                    } else if is_self_send() {
+                        label { class: "label",
+                            class: if !recipient.read().self_send_intended { "text-error" },
+                            input {
+                                r#type: "checkbox",
+                                class: "checkbox checkbox-sm",
+                                checked: recipient.read().self_send_intended,
+                                onchange: move |evt| recipients.write()[index].self_send_intended = evt.checked(),
+                            }
+                            "This address belongs to this wallet, check to confirm the transfer to yourself (e.g. consolidation)"
+                        }
+                    }
+                    if let Some(duplicate_warning) = duplicate_warning() {
+                        div { class: "label text-warning", {duplicate_warning} }
+                    }
                     if let Some(ref multiple_drain_to_error) = *multiple_drain_to_error.read() {
                         div { class: "label text-error", {multiple_drain_to_error} }
                     }
@@ -404,41 +711,77 @@ fn OwnerTxRecipientInput(
                         div { class: "label {text_color}", {max_one_recipient_error} }
                     }
                 }
-                fieldset { class: "fieldset",
-                    legend { class: "fieldset-legend", "Amount" }
-                    div { class: "flex flex-row gap-2",
-                        div { class: "join",
-                            input {
-                                r#type: "number",
-                                class: "join-item input w-40",
-                                placeholder: "Amount",
-                                disabled: recipient.read().drain_to,
-                                value: "{recipient.read().amount}",
-                                oninput: move |evt| recipients.write()[index].amount = evt.value().to_string(),
+                if split_by_percent() {
+                    fieldset { class: "fieldset",
+                        legend { class: "fieldset-legend", "Share" }
+                        div { class: "flex flex-row gap-2 items-center",
+                            label { class: "input w-32",
+                                input {
+                                    r#type: "number",
+                                    placeholder: "Percent",
+                                    value: "{recipient.read().percent}",
+                                    oninput: move |evt| recipients.write()[index].percent = evt.value().to_string(),
+                                }
+                                span { class: "label", "%" }
                             }
-                            select {
-                                class: "join-item select w-24",
-                                disabled: recipient.read().drain_to,
-                                value: recipient.read().unit.to_string(),
-                                onchange: move |evt| recipients.write()[index].unit = evt.value().parse().unwrap(),
-                                option { value: "sat", "sat" }
-                                option { value: "mBTC", "mBTC" }
-                                option { value: "BTC", "BTC" }
+                            if let Some(split_amount) = split_amount {
+                                AlwaysLoadedComponent::<UIBtcAmount> { input: split_amount.into() }
                             }
                         }
-                        label { class: "label",
-                            input {
-                                r#type: "checkbox",
-                                class: "checkbox",
-                                checked: recipient.read().drain_to,
-                                onchange: send_all_check,
+                    }
+                } else {
+                    fieldset { class: "fieldset",
+                        legend { class: "fieldset-legend", "Amount" }
+                        div { class: "flex flex-row gap-2",
+                            div { class: "join",
+                                input {
+                                    r#type: "number",
+                                    class: "join-item input w-40",
+                                    placeholder: "Amount",
+                                    disabled: recipient.read().drain_to,
+                                    value: "{recipient.read().amount}",
+                                    oninput: move |evt| {
+                                        let recipient = &mut recipients.write()[index];
+                                        recipient.amount = evt.value().to_string();
+                                        recipient.use_max = false;
+                                    },
+                                }
+                                select {
+                                    class: "join-item select w-24",
+                                    disabled: recipient.read().drain_to,
+                                    value: recipient.read().unit.to_string(),
+                                    onchange: move |evt| {
+                                        let recipient = &mut recipients.write()[index];
+                                        recipient.unit = evt.value().parse().unwrap();
+                                        recipient.use_max = false;
+                                    },
+                                    option { value: "sat", "sat" }
+                                    option { value: "mBTC", "mBTC" }
+                                    option { value: "BTC", "BTC" }
+                                }
+                                button {
+                                    class: "join-item btn",
+                                    class: if recipient.read().use_max { "btn-primary" },
+                                    title: "Largest amount this recipient can receive, given the other recipients, the UTXO selection and the fee policy",
+                                    disabled: recipient.read().drain_to || max_amount.read().is_none(),
+                                    onclick: use_max_click,
+                                    "Max"
+                                }
+                            }
+                            label { class: "label",
+                                input {
+                                    r#type: "checkbox",
+                                    class: "checkbox",
+                                    checked: recipient.read().drain_to,
+                                    onchange: send_all_check,
+                                }
+                                "Send All"
                             }
-                            "Send All"
                         }
-                    }
-                    if let Some(ref max_spend_error) = *max_spend_error.read() {
-                        div { class: "label text-error", {max_spend_error} }
-                    }
+                        if let Some(ref max_spend_error) = *max_spend_error.read() {
+                            div { class: "label text-error", {max_spend_error} }
+                        }
+                }
                 }
             }
         }
@@ -0,0 +1,85 @@
+use crate::prelude::*;
+
+use btc_heritage_wallet::heritage_service_api_client::Fingerprint;
+
+use crate::components::{
+    hwi::HwiDeviceSelect,
+    svg::{Cancel, DrawSvg, Signature},
+};
+
+/// Signing on a hardware wallet driven through HWI, for wallets that cannot sign by themselves
+#[component]
+pub(super) fn HwiSignTx(
+    cant_broadcast: ReadOnlySignal<bool>,
+    show_export: Signal<bool>,
+) -> Element {
+    log::debug!("HwiSignTx Rendered");
+
+    let psbt_to_sign = use_context::<Signal<Option<super::PsbtToSign>>>();
+    let selected_device = use_signal(|| None::<Fingerprint>);
+    // Cancelling the task stops HWI, the device stops waiting for the approval
+    let mut signing_task = use_signal(|| None::<Task>);
+    let signing = use_memo(move || signing_task.read().is_some());
+
+    let sign = move |_| {
+        let (Some(fingerprint), Some(psbt)) = (selected_device(), psbt_to_sign()) else {
+            return;
+        };
+        signing_task.set(Some(spawn(async move {
+            match state_management::hwi_sign_psbt(fingerprint, psbt.0).await {
+                Ok(signed_psbt) => super::sign_tx::accept_airgapped_psbt(
+                    signed_psbt,
+                    cant_broadcast(),
+                    show_export,
+                ),
+                Err(e) => alert_failure("Failed to sign the transaction with the device", e),
+            }
+            signing_task.set(None);
+        })));
+    };
+    let cancel = move |_| {
+        if let Some(task) = signing_task.take() {
+            log::info!("HWI signing cancelled");
+            task.cancel();
+        }
+    };
+
+    use_drop(|| log::debug!("HwiSignTx Dropped"));
+
+    rsx! {
+        div { class: "flex flex-col gap-4 mt-4",
+            h3 { class: "text-lg font-semibold", "Sign with a Hardware Wallet (HWI)" }
+            div { class: "flex flex-col gap-4 rounded-box bg-base-200 p-4",
+                HwiDeviceSelect { selected: selected_device }
+                if psbt_to_sign.read().is_some() {
+                    div { class: "text-sm text-(--color-base-content)/60",
+                        "Carefully verify the amounts and addresses on the screen of the device before approving."
+                    }
+                    div { class: "flex flex-row gap-2 self-center",
+                        button {
+                            class: "btn btn-primary",
+                            disabled: selected_device().is_none() || signing(),
+                            onclick: sign,
+                            if signing() {
+                                span { class: "loading loading-spinner" }
+                            } else {
+                                DrawSvg::<Signature> {}
+                            }
+                            "Sign on the Device"
+                        }
+                        if signing() {
+                            button { class: "btn btn-outline btn-primary", onclick: cancel,
+                                DrawSvg::<Cancel> {}
+                                "Cancel"
+                            }
+                        }
+                    }
+                } else {
+                    div { class: "text-sm text-(--color-base-content)/60",
+                        "Create a transaction first, it can then be signed on your device."
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,42 +1,55 @@
 use crate::prelude::*;
 
 mod broadcast_tx;
+mod coldcard;
 mod create_tx;
+mod hwi;
+mod scan_psbt;
 mod sign_tx;
+mod signing_request;
+pub mod signing_session;
+mod spend_limits;
 
 use std::collections::HashSet;
 
 use btc_heritage_wallet::{
-    bitcoin::Address, btc_heritage::PartiallySignedTransaction,
-    heritage_service_api_client::TransactionSummary, Broadcaster, KeyProvider,
+    bitcoin::{Address, Txid},
+    btc_heritage::PartiallySignedTransaction,
+    heritage_service_api_client::TransactionSummary,
+    Broadcaster, KeyProvider,
 };
+use serde::{Deserialize, Serialize};
 
 use crate::{
     components::{
         copy::CopyTextarea,
+        qrcode::{ur_encode, AnimatedQRCode, Cbor, UR_MAX_FRAGMENT_LEN},
         quick_actions::UnlockLocalKey,
         svg::{Alert, ChevronRight, DrawSvg, One, SvgSize::Size8, Three, Two},
         transaction::UITxDetails,
     },
-    utils::{is_psbt_fully_signed, log_error_ccstr, CCStr},
+    state_management::DatabaseCommand,
+    utils::{check_psbt_network, is_psbt_fully_signed, log_error_ccstr, CCStr, CheapClone},
+    Route,
 };
 
-use super::quick_actions::LocalKeyUnlocker;
+use super::{ledger_prompt::LedgerSigner, quick_actions::LocalKeyUnlocker};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SpendTabsType {
-    Owner,
+    /// Spending from the wallet with the given name
+    Owner(CCStr),
     Heir(CCStr),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 enum SpendStage {
     Create,
     Sign,
     Broadcast,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 struct PsbtToSign(CCStr);
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum PsbtToSignStatus {
@@ -46,8 +59,12 @@ enum PsbtToSignStatus {
     Ok,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 struct SignedPsbt(CCStr);
+
+/// Note of the transaction being spent, saved as its label in the wallet
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct TxNote(String);
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum SignedPsbtStatus {
     Absent,
@@ -56,6 +73,15 @@ enum SignedPsbtStatus {
     Ok,
 }
 
+/// Parse a PSBT, refusing the ones spending coins of another network than the application
+fn parse_network_checked_psbt(psbt: &str) -> Result<PartiallySignedTransaction, String> {
+    let psbt = psbt
+        .parse::<PartiallySignedTransaction>()
+        .map_err(|e| e.to_string())?;
+    check_psbt_network(&psbt)?;
+    Ok(psbt)
+}
+
 #[doc = "Properties for the [`SpendTabs`] component."]
 #[allow(missing_docs)]
 #[derive(Props, Clone, PartialEq)]
@@ -74,7 +100,7 @@ pub struct SpendTabsProps {
 #[doc = "- [`cannot_broadcast_reason`](SpendTabsProps::cannot_broadcast_reason) : `ReadOnlySignal<Option<&'staticstr>>`"]
 #[doc = "- [`addresses_set`](SpendTabsProps::addresses_set) : `ReadOnlySignal<Option<HashSet<Address>>>`"]
 #[allow(non_snake_case)]
-pub fn SpendTabs<T: KeyProvider + Broadcaster + LocalKeyUnlocker + 'static>(
+pub fn SpendTabs<T: KeyProvider + Broadcaster + LocalKeyUnlocker + LedgerSigner + 'static>(
     SpendTabsProps {
         spendtabs_type,
         cannot_create_reason,
@@ -91,32 +117,47 @@ pub fn SpendTabs<T: KeyProvider + Broadcaster + LocalKeyUnlocker + 'static>(
 
     let cant_broadcast = use_memo(move || cannot_broadcast_reason.read().is_some());
 
-    // Shared PSBT data between stages
-    let current_stage = use_signal(|| SpendStage::Create);
-    let psbt_to_sign: Signal<Option<PsbtToSign>> = use_signal(|| None);
+    // Shared PSBT data between stages, kept as drafts of the spend view in case of crash
+    let draft_key = use_route::<Route>().to_string();
+    let current_stage = helper_hooks::use_signal_with_crash_draft(
+        CCStr::from(format!("{draft_key}#stage")),
+        || SpendStage::Create,
+    );
+    let psbt_to_sign: Signal<Option<PsbtToSign>> = helper_hooks::use_signal_with_crash_draft(
+        CCStr::from(format!("{draft_key}#psbt_to_sign")),
+        || None,
+    );
     let psbt_to_sign_status =
         use_memo(
-            move || match psbt_to_sign.lmap(|s| s.0.parse::<PartiallySignedTransaction>()) {
+            move || match psbt_to_sign.lmap(|s| parse_network_checked_psbt(&s.0)) {
                 Some(Ok(psbt)) if is_psbt_fully_signed(&psbt) => PsbtToSignStatus::AlreadySigned,
                 Some(Ok(_)) => PsbtToSignStatus::Ok,
-                Some(Err(e)) => PsbtToSignStatus::Invalid(CCStr::from(e.to_string())),
+                Some(Err(e)) => PsbtToSignStatus::Invalid(CCStr::from(e)),
                 None => PsbtToSignStatus::Absent,
             },
         );
 
-    let signed_psbt: Signal<Option<SignedPsbt>> = use_signal(|| None);
+    let signed_psbt: Signal<Option<SignedPsbt>> = helper_hooks::use_signal_with_crash_draft(
+        CCStr::from(format!("{draft_key}#signed_psbt")),
+        || None,
+    );
     let signed_psbt_status =
         use_memo(
-            move || match signed_psbt.lmap(|s| s.0.parse::<PartiallySignedTransaction>()) {
+            move || match signed_psbt.lmap(|s| parse_network_checked_psbt(&s.0)) {
                 Some(Ok(psbt)) if is_psbt_fully_signed(&psbt) => SignedPsbtStatus::Ok,
                 Some(Ok(_)) => SignedPsbtStatus::NotSigned,
-                Some(Err(e)) => SignedPsbtStatus::Invalid(CCStr::from(e.to_string())),
+                Some(Err(e)) => SignedPsbtStatus::Invalid(CCStr::from(e)),
                 None => SignedPsbtStatus::Absent,
             },
         );
 
     let shared_tx_summary: Signal<Option<TransactionSummary>> = use_signal(|| None);
 
+    let tx_note: Signal<TxNote> = helper_hooks::use_signal_with_crash_draft(
+        CCStr::from(format!("{draft_key}#tx_note")),
+        TxNote::default,
+    );
+
     // Provide shared state to all tabs
     use_context_provider(|| current_stage);
     use_context_provider(|| psbt_to_sign);
@@ -124,6 +165,11 @@ pub fn SpendTabs<T: KeyProvider + Broadcaster + LocalKeyUnlocker + 'static>(
     use_context_provider(|| signed_psbt);
     use_context_provider(|| signed_psbt_status);
     use_context_provider(|| shared_tx_summary);
+    use_context_provider(|| tx_note);
+    use_context_provider({
+        let spendtabs_type = spendtabs_type.clone();
+        move || Signal::new(spendtabs_type)
+    });
 
     // Compute the PSBT overview
     let psbt_overview = use_memo(move || {
@@ -175,7 +221,7 @@ pub fn SpendTabs<T: KeyProvider + Broadcaster + LocalKeyUnlocker + 'static>(
                         },
                         None => {
                             match spendtabs_type {
-                                SpendTabsType::Owner => rsx! {
+                                SpendTabsType::Owner(_) => rsx! {
                                     create_tx::CreateOwnerTx { cant_sign }
                                 },
                                 SpendTabsType::Heir(heritage_id) => rsx! {
@@ -199,6 +245,8 @@ pub fn SpendTabs<T: KeyProvider + Broadcaster + LocalKeyUnlocker + 'static>(
                             {reason}
                             if need_unlock {
                                 div { class: "m-4", UnlockLocalKey::<T> {} }
+                            } else {
+                                sign_tx::ExternalSignTx { cant_broadcast }
                             }
                         },
                         None => rsx! {
@@ -259,6 +307,69 @@ fn SendTabContent(children: Element) -> Element {
     }
 }
 
+/// Input of the note of the transaction, only owners can label their transactions
+#[component]
+fn TxNoteInput(disabled: bool) -> Element {
+    let spendtabs_type = use_context::<Signal<SpendTabsType>>();
+    let mut tx_note = use_context::<Signal<TxNote>>();
+
+    if !matches!(*spendtabs_type.read(), SpendTabsType::Owner(_)) {
+        return rsx! {};
+    }
+    rsx! {
+        fieldset { class: "fieldset",
+            legend { class: "fieldset-legend", "Note" }
+            input {
+                r#type: "text",
+                class: "input w-full",
+                placeholder: "What is this transaction for? (optional)",
+                disabled,
+                value: "{tx_note.read().0}",
+                oninput: move |evt| *tx_note.write() = TxNote(evt.value()),
+            }
+            div { class: "fieldset-label",
+                "Saved as the label of the transaction and shown in the transactions history"
+            }
+        }
+    }
+}
+
+/// Save the note of the transaction as its label, if any
+async fn save_tx_note(
+    database_service: Coroutine<DatabaseCommand>,
+    spendtabs_type: SpendTabsType,
+    tx_note: TxNote,
+    txid: Txid,
+) {
+    let SpendTabsType::Owner(wallet_name) = spendtabs_type else {
+        return;
+    };
+    let note = tx_note.0.trim();
+    if note.is_empty() {
+        return;
+    }
+    match state_management::set_wallet_label(
+        database_service,
+        wallet_name,
+        state_management::WalletLabelType::Tx,
+        txid.to_string(),
+        Some(note.to_owned()),
+    )
+    .await
+    {
+        Ok(()) => log::info!("Note of transaction {txid} saved"),
+        Err(e) => alert_failure("Failed to save the note of the transaction", e),
+    }
+}
+
+/// Encode a PSBT as a `crypto-psbt` UR (BCR-2020-006) for airgapped signers
+fn crypto_psbt_ur(psbt: &str) -> Result<CheapClone<[CCStr]>, CCStr> {
+    let psbt = parse_network_checked_psbt(psbt)?;
+    let mut cbor = Cbor::default();
+    cbor.bytes(&psbt.serialize());
+    Ok(ur_encode("crypto-psbt", &cbor.into_bytes(), UR_MAX_FRAGMENT_LEN).into())
+}
+
 #[component]
 fn ExportEncodedTransaction(
     title: &'static str,
@@ -266,6 +377,9 @@ fn ExportEncodedTransaction(
     show_export: Signal<bool>,
     psbt: CCStr,
 ) -> Element {
+    let mut show_ur = use_signal(|| false);
+    let psbt_ur = show_ur().then(|| crypto_psbt_ur(&psbt));
+
     rsx! {
         div { class: "collapse collapse-arrow bg-base-200 text-base-content mt-4",
             input {
@@ -278,6 +392,29 @@ fn ExportEncodedTransaction(
                 div { class: "flex flex-col gap-2",
                     div { class: "text-sm text-(--color-base-content)/60", {description} }
                     CopyTextarea { value: psbt.clone(), rows: 8 }
+                    label { class: "label",
+                        input {
+                            r#type: "checkbox",
+                            class: "toggle toggle-secondary",
+                            checked: show_ur(),
+                            onchange: move |evt| show_ur.set(evt.checked()),
+                        }
+                        span { class: "text-base", "Show as an animated QR code (BC-UR)" }
+                    }
+                    match psbt_ur {
+                        Some(Ok(parts)) => rsx! {
+                            div { class: "text-sm text-(--color-base-content)/60",
+                                "Scan it with an airgapped signer supporting the crypto-psbt format (SeedSigner, Keystone, Passport...)."
+                            }
+                            div { class: "w-80",
+                                AnimatedQRCode { parts }
+                            }
+                        },
+                        Some(Err(e)) => rsx! {
+                            div { class: "alert alert-error", "Could not encode the transaction: {e}" }
+                        },
+                        None => rsx! {},
+                    }
                 }
             }
         }
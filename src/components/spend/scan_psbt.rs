@@ -0,0 +1,96 @@
+use crate::prelude::*;
+
+use btc_heritage_wallet::btc_heritage::PartiallySignedTransaction;
+
+use crate::{
+    components::{
+        qr_scanner::QRScanner,
+        qrcode::{cbor_byte_string, DecodedQRContent, QRSequenceDecoder},
+    },
+    utils::CCStr,
+};
+
+/// Extract the encoded PSBT from the content of a scanned QR code or sequence of QR codes
+fn psbt_from_qr_content(content: DecodedQRContent) -> Result<CCStr, CCStr> {
+    let psbt = match content {
+        DecodedQRContent::UR { ur_type, message }
+            if ur_type == "crypto-psbt" || ur_type == "psbt" =>
+        {
+            let psbt = cbor_byte_string(&message)
+                .ok_or_else(|| CCStr::from("Invalid crypto-psbt payload"))?;
+            PartiallySignedTransaction::deserialize(psbt).map_err(|e| CCStr::from(e.to_string()))?
+        }
+        DecodedQRContent::UR { ur_type, .. } => {
+            return Err(CCStr::from(format!(
+                "Expected a crypto-psbt QR code, got a {ur_type} one"
+            )))
+        }
+        DecodedQRContent::BBQr {
+            file_type: 'P',
+            data,
+        } => PartiallySignedTransaction::deserialize(&data)
+            .map_err(|e| CCStr::from(e.to_string()))?,
+        DecodedQRContent::BBQr { file_type, .. } => {
+            return Err(CCStr::from(format!(
+                "Expected a PSBT BBQr code, got one of file type {file_type}"
+            )))
+        }
+        DecodedQRContent::Text(text) => text
+            .parse::<PartiallySignedTransaction>()
+            .map_err(|_| CCStr::from("The QR code does not contain a PSBT"))?,
+    };
+    Ok(CCStr::from(psbt.to_string()))
+}
+
+/// Webcam scanner of a PSBT displayed as a QR code, animated or not, by an airgapped signer
+///
+/// Multi-part BC-UR (`crypto-psbt`) and BBQr sequences are reassembled whatever
+/// the order in which their parts are scanned.
+#[component]
+pub(super) fn ScanPsbtQRCode(onscanned: EventHandler<CCStr>) -> Element {
+    log::debug!("ScanPsbtQRCode Rendered");
+
+    let mut decoder = use_signal(QRSequenceDecoder::default);
+    let mut parts_progress = use_signal(|| None::<(usize, usize)>);
+    let mut scan_error = use_signal(|| None::<CCStr>);
+
+    let onscan = move |scanned: CCStr| {
+        let content = decoder.write().receive(&scanned);
+        parts_progress.set(decoder.peek().progress());
+        match content {
+            Ok(Some(content)) => match psbt_from_qr_content(content) {
+                Ok(psbt) => {
+                    scan_error.set(None);
+                    onscanned.call(psbt);
+                }
+                Err(e) => scan_error.set(Some(e)),
+            },
+            Ok(None) => scan_error.set(None),
+            Err(e) => scan_error.set(Some(CCStr::from(e))),
+        }
+    };
+
+    use_drop(|| log::debug!("ScanPsbtQRCode Dropped"));
+
+    rsx! {
+        div { class: "flex flex-col gap-2 items-center",
+            div { class: "text-sm text-(--color-base-content)/60",
+                "Show the QR code of the transaction to the camera. Animated BC-UR and BBQr codes are reassembled automatically."
+            }
+            QRScanner { onscan }
+            if let Some((received, expected)) = parts_progress() {
+                div { class: "flex flex-col gap-1 items-center w-80",
+                    progress {
+                        class: "progress progress-primary w-full",
+                        value: "{received}",
+                        max: "{expected}",
+                    }
+                    div { class: "text-sm", "{received} of {expected} parts" }
+                }
+            }
+            if let Some(e) = scan_error() {
+                div { class: "text-sm text-error", {e} }
+            }
+        }
+    }
+}
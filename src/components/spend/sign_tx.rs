@@ -1,16 +1,48 @@
 use crate::prelude::*;
 
-use btc_heritage_wallet::KeyProvider;
+use btc_heritage_wallet::{btc_heritage::PartiallySignedTransaction, KeyProvider};
 
 use crate::{
     components::{
+        ledger_prompt::{LedgerPromptsOverlay, LedgerSigner},
         svg::{CheckCircleOutline, DrawSvg, Signature},
         transaction::UITxDetails,
     },
     utils::{is_psbt_fully_signed, CCStr},
 };
 
-use super::{ExportEncodedTransaction, SpendStage};
+use super::{
+    coldcard::ColdcardSdCardSigning,
+    scan_psbt::ScanPsbtQRCode,
+    signing_request::SigningRequests,
+    signing_session::{sign_psbt_with, use_ledger_signing_ui},
+    spend_limits::{use_spend_limits_guard, CoolingOffStatus},
+    ExportEncodedTransaction, SpendStage,
+};
+
+/// Take the PSBT signed by an airgapped signer as the signed transaction and move on to its broadcast
+pub(super) fn accept_airgapped_psbt(
+    psbt: CCStr,
+    cant_broadcast: bool,
+    mut show_export: Signal<bool>,
+) {
+    let mut current_stage = consume_context::<Signal<SpendStage>>();
+    let mut signed_psbt = consume_context::<Signal<Option<super::SignedPsbt>>>();
+    let fully_signed = psbt
+        .parse::<PartiallySignedTransaction>()
+        .is_ok_and(|psbt| is_psbt_fully_signed(&psbt));
+    *signed_psbt.write() = Some(super::SignedPsbt(psbt));
+    if fully_signed {
+        alert_success("Signed transaction imported, it is ready to broadcast");
+        if cant_broadcast {
+            *show_export.write() = true;
+        } else {
+            *current_stage.write() = SpendStage::Broadcast;
+        }
+    } else {
+        alert_warn("The imported transaction is not fully signed and cannot be broadcasted yet");
+    }
+}
 
 #[doc = "Properties for the [`SignTx`] component."]
 #[allow(missing_docs)]
@@ -23,7 +55,7 @@ pub(super) struct SignTxProps {
 #[doc = "# Props\n*For details, see the [props struct definition](SignTxProps).*"]
 #[doc = "- [`cant_broadcast`](SignTxProps::cant_broadcast) : `ReadOnlySignal<bool>`"]
 #[allow(non_snake_case)]
-pub(super) fn SignTx<S: KeyProvider + 'static>(
+pub(super) fn SignTx<S: KeyProvider + LedgerSigner + 'static>(
     SignTxProps { cant_broadcast }: SignTxProps,
 ) -> Element {
     log::debug!("SignTx Rendered");
@@ -47,6 +79,8 @@ pub(super) fn SignTx<S: KeyProvider + 'static>(
     });
 
     let mut show_export = use_signal(|| false);
+    let mut scanning = use_signal(|| false);
+    let mut coldcard_signing = use_signal(|| false);
 
     let can_sign =
         use_memo(move || matches!(*psbt_to_sign_status.read(), super::PsbtToSignStatus::Ok));
@@ -61,58 +95,104 @@ pub(super) fn SignTx<S: KeyProvider + 'static>(
         }
         super::PsbtToSignStatus::Ok | super::PsbtToSignStatus::Absent => None,
     });
+
+    // The spend limits of the wallet are verified again, as the transaction may have been
+    // imported instead of created in the Create tab
+    let spend_limits_guard = use_spend_limits_guard();
+    let spend_to_sign = use_memo(move || {
+        let psbt = psbt_to_sign
+            .lmap(|s| s.0.parse::<PartiallySignedTransaction>().ok())
+            .flatten()?;
+        Some((
+            spend_limits_guard.spend_key(&psbt),
+            spend_limits_guard.sent_amount(&psbt),
+        ))
+    });
+    let spend_limit_error = use_memo(move || {
+        spend_to_sign().and_then(|(_, amount)| spend_limits_guard.limit_error(amount))
+    });
+    let cooling_off_status = use_memo(move || match spend_to_sign() {
+        Some((spend_key, amount)) => spend_limits_guard.cooling_off_status(&spend_key, amount),
+        None => CoolingOffStatus::NotNeeded,
+    });
+    let within_spend_limits = use_memo(move || {
+        spend_limit_error.read().is_none()
+            && matches!(
+                cooling_off_status(),
+                CoolingOffStatus::NotNeeded | CoolingOffStatus::Ready
+            )
+    });
+
     // Signing state
     let mut signing = use_signal(|| false);
 
+    // Ledger interaction state
+    let ledger_ui = use_ledger_signing_ui();
+
     // Sign transaction handler
-    let sign_transaction = move |_| async move {
-        if let Some(ref psbt) = psbt_to_sign() {
-            let mut psbt_to_sign = match psbt.0.parse() {
-                Ok(psbt) => psbt,
-                Err(e) => {
-                    alert_error(format!("Failed to parse transaction: {e}"));
-                    log::error!("Failed to parse transaction: {e}");
-                    return;
-                }
-            };
-
-            *signing.write() = true;
-            match signer
-                .with(async |s: &S| s.sign_psbt(&mut psbt_to_sign).await)
-                .await
-            {
-                Ok(signed_count) if signed_count > 0 => {
-                    *signed_psbt.write() =
-                        Some(super::SignedPsbt(CCStr::from(psbt_to_sign.to_string())));
-                    alert_success(format!(
-                        "Transaction signed successfully. Inputs signed: {signed_count}"
-                    ));
-                    if is_psbt_fully_signed(&psbt_to_sign) {
-                        if cant_broadcast() {
-                            *show_export.write() = true;
-                        } else {
-                            *current_stage.write() = SpendStage::Broadcast;
+    let sign_transaction = {
+        let ledger_ui = ledger_ui.clone();
+        move |_| {
+            let ledger_ui = ledger_ui.clone();
+            async move {
+                if let Some(ref psbt) = psbt_to_sign() {
+                    let mut psbt_to_sign = match psbt.0.parse() {
+                        Ok(psbt) => psbt,
+                        Err(e) => {
+                            alert_failure("Failed to parse transaction", e);
+                            return;
                         }
-                        alert_success("Transaction is ready to broadcast");
-                    } else {
-                        alert_warn("Transaction is not fully signed and cannot be broadcasted yet");
+                    };
+
+                    *signing.write() = true;
+                    let sign_result =
+                        sign_psbt_with(signer, &mut psbt_to_sign, ledger_ui.clone()).await;
+                    match sign_result {
+                        Ok(signed_count) if signed_count > 0 => {
+                            if cooling_off_status() == CoolingOffStatus::Ready {
+                                spend_limits_guard.clear_request().await;
+                            }
+                            *signed_psbt.write() =
+                                Some(super::SignedPsbt(CCStr::from(psbt_to_sign.to_string())));
+                            alert_success(format!(
+                                "Transaction signed successfully. Inputs signed: {signed_count}"
+                            ));
+                            if is_psbt_fully_signed(&psbt_to_sign) {
+                                if cant_broadcast() {
+                                    *show_export.write() = true;
+                                } else {
+                                    *current_stage.write() = SpendStage::Broadcast;
+                                }
+                                alert_success("Transaction is ready to broadcast");
+                            } else {
+                                alert_warn(
+                                    "Transaction is not fully signed and cannot be broadcasted yet",
+                                );
+                            }
+                            log::info!(
+                                "Transaction signed successfully. Inputs signed: {signed_count}"
+                            );
+                        }
+                        Ok(_) => {
+                            alert_warn(format!("No inputs owned by this wallet. Nothing signed."));
+                            log::info!("No inputs owned by this wallet. Nothing signed.");
+                        }
+                        Err(e) => e.alert(),
                     }
-                    log::info!("Transaction signed successfully. Inputs signed: {signed_count}");
-                }
-                Ok(_) => {
-                    alert_warn(format!("No inputs owned by this wallet. Nothing signed."));
-                    log::info!("No inputs owned by this wallet. Nothing signed.");
-                }
-                Err(e) => {
-                    alert_error(format!("Failed to sign transaction: {e}"));
-                    log::error!("Failed to sign transaction: {e}");
+
+                    *signing.write() = false;
                 }
             }
-
-            *signing.write() = false;
         }
     };
 
+    // Signature coming back from an airgapped signer
+    let import_airgapped_psbt = move |psbt: CCStr| {
+        scanning.set(false);
+        coldcard_signing.set(false);
+        accept_airgapped_psbt(psbt, cant_broadcast(), show_export);
+    };
+
     use_drop(|| log::debug!("SignTx Dropped"));
 
     rsx! {
@@ -163,6 +243,36 @@ pub(super) fn SignTx<S: KeyProvider + 'static>(
                 }
             }
 
+            if let Some(spend_limit_error) = spend_limit_error() {
+                div { class: "alert alert-error", {spend_limit_error} }
+            }
+            match cooling_off_status() {
+                CoolingOffStatus::NotNeeded | CoolingOffStatus::Ready => rsx! {},
+                CoolingOffStatus::ToRequest => rsx! {
+                    div { class: "alert alert-warning flex flex-row justify-between",
+                        span {
+                            "This transaction is above the cooling-off threshold of the wallet, \
+                            you will be able to sign it {spend_limits_guard.spend_limits().cooling_off_minutes} minutes \
+                            after requesting it."
+                        }
+                        button {
+                            class: "btn btn-sm",
+                            onclick: move |_| async move {
+                                if let Some((spend_key, _)) = spend_to_sign() {
+                                    spend_limits_guard.request(spend_key).await;
+                                }
+                            },
+                            "Request Transaction"
+                        }
+                    }
+                },
+                CoolingOffStatus::Waiting(remaining_secs) => rsx! {
+                    div { class: "alert alert-info",
+                        "Cooling-off period in progress, the transaction can be signed in {remaining_secs.div_ceil(60)} min."
+                    }
+                },
+            }
+
             div { class: "card-actions justify-center mt-6",
                 MaybeHighlight {
                     step: OnboardingStep::ClickInheritanceSignTransaction,
@@ -170,7 +280,7 @@ pub(super) fn SignTx<S: KeyProvider + 'static>(
                     context_filter: consume_onboarding_context(),
                     button {
                         class: "btn btn-primary",
-                        disabled: !can_sign() || signing() || has_signed(),
+                        disabled: !can_sign() || !within_spend_limits() || signing() || has_signed(),
                         onclick: sign_transaction,
                         if signing() {
                             span { class: "loading loading-spinner loading-sm mr-2" }
@@ -184,6 +294,50 @@ pub(super) fn SignTx<S: KeyProvider + 'static>(
                         }
                     }
                 }
+                button {
+                    class: "btn btn-outline",
+                    disabled: signing() || has_signed(),
+                    onclick: move |_| {
+                        coldcard_signing.set(false);
+                        scanning.set(!scanning());
+                    },
+                    if scanning() {
+                        "Stop Scanning"
+                    } else {
+                        "Scan Signed Transaction"
+                    }
+                }
+                button {
+                    class: "btn btn-outline",
+                    disabled: !can_sign() || !within_spend_limits() || signing() || has_signed(),
+                    onclick: move |_| {
+                        scanning.set(false);
+                        coldcard_signing.set(!coldcard_signing());
+                    },
+                    if coldcard_signing() {
+                        "Cancel Coldcard Signing"
+                    } else {
+                        "Sign with a Coldcard (SD Card)"
+                    }
+                }
+            }
+            if scanning() {
+                ScanPsbtQRCode { onscanned: import_airgapped_psbt }
+            }
+            if coldcard_signing() {
+                if let Some(psbt) = psbt_to_sign() {
+                    ColdcardSdCardSigning { psbt: psbt.0, onimported: import_airgapped_psbt }
+                }
+            }
+
+            SigningRequests { can_sign: true, cant_broadcast, show_export }
+
+            LedgerPromptsOverlay {
+                is_open: ledger_ui.overlay,
+                title: "Signing with the Ledger",
+                prompts: ledger_ui.prompts,
+                current_prompt: None,
+                cancel: ledger_ui.cancel.clone(),
             }
 
             if let Some(psbt) = signed_psbt() {
@@ -197,3 +351,30 @@ pub(super) fn SignTx<S: KeyProvider + 'static>(
         }
     }
 }
+
+/// Signing on external devices for wallets that cannot sign by themselves, like watch-only ones
+#[component]
+pub(super) fn ExternalSignTx(cant_broadcast: ReadOnlySignal<bool>) -> Element {
+    log::debug!("ExternalSignTx Rendered");
+
+    let signed_psbt = use_context::<Signal<Option<super::SignedPsbt>>>();
+    let show_export = use_signal(|| false);
+
+    use_drop(|| log::debug!("ExternalSignTx Dropped"));
+
+    rsx! {
+        super::hwi::HwiSignTx { cant_broadcast, show_export }
+        super::coldcard::ColdcardSignTx { cant_broadcast, show_export }
+        SigningRequests { can_sign: false, cant_broadcast, show_export }
+        if let Some(psbt) = signed_psbt() {
+            div { class: "mt-4",
+                ExportEncodedTransaction {
+                    title: "Export Signed Transaction",
+                    description: "Copy this encoded transaction to broadcast on another device or save for later:",
+                    show_export,
+                    psbt: psbt.0.clone(),
+                }
+            }
+        }
+    }
+}
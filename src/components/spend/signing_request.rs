@@ -0,0 +1,432 @@
+use crate::prelude::*;
+
+use btc_heritage_wallet::{
+    bitcoin::{Address, Amount, Denomination},
+    btc_heritage::{
+        bitcoincore_rpc::jsonrpc::serde_json,
+        utils::{bitcoin_network, timestamp_now},
+        PartiallySignedTransaction,
+    },
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::{
+        export::{use_export_directory, write_export_files, ExportToDirectory},
+        inputs::FileInput,
+        svg::{DrawSvg, FileDownload, SvgSize::Size4},
+    },
+    utils::{timestamp_to_string, CCStr},
+};
+
+use super::{parse_network_checked_psbt, PsbtToSign, SignedPsbt, SpendTabsType, TxNote};
+
+/// Version of the format of the signing files, bumped on incompatible changes
+const SIGNING_FILE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum SigningFileKind {
+    /// Sent to a remote signer, with the transaction to sign
+    Request,
+    /// Sent back by the remote signer, with its signatures added
+    Response,
+}
+
+/// File exchanged with a remote cosigner or heir to collect their signatures on a transaction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct SigningFile {
+    version: u32,
+    kind: SigningFileKind,
+    /// Network of the transaction, so the file is refused by an application on another network
+    network: String,
+    /// Wallet, or inheritance, spending the transaction
+    wallet: String,
+    note: Option<String>,
+    /// Human readable description of the transaction, for the people handling the file
+    summary: String,
+    created_ts: u64,
+    psbt: String,
+}
+impl SigningFile {
+    fn new(
+        kind: SigningFileKind,
+        wallet: String,
+        note: Option<String>,
+        psbt: &PartiallySignedTransaction,
+    ) -> Result<Self, CCStr> {
+        Ok(Self {
+            version: SIGNING_FILE_VERSION,
+            kind,
+            network: bitcoin_network::get().to_string(),
+            wallet,
+            note,
+            summary: transaction_summary(psbt)?,
+            created_ts: timestamp_now(),
+            psbt: psbt.to_string(),
+        })
+    }
+
+    fn file_name(&self, psbt: &PartiallySignedTransaction) -> String {
+        let txid = psbt.unsigned_tx.txid().to_string();
+        let kind = match self.kind {
+            SigningFileKind::Request => "request",
+            SigningFileKind::Response => "response",
+        };
+        format!("signing-{kind}-{}.json", &txid[..8])
+    }
+
+    /// Parse a signing file of the expected `kind`, returning it with its PSBT
+    fn read(
+        content: &[u8],
+        kind: SigningFileKind,
+    ) -> Result<(Self, PartiallySignedTransaction), CCStr> {
+        let signing_file = serde_json::from_slice::<SigningFile>(content)
+            .map_err(|_| CCStr::from("The file is not a signing request or response"))?;
+        if signing_file.version > SIGNING_FILE_VERSION {
+            return Err(CCStr::from(
+                "The file was created by a newer version of the application, update it first",
+            ));
+        }
+        if signing_file.kind != kind {
+            return Err(CCStr::from(match kind {
+                SigningFileKind::Request => "The file is a signing response, not a request",
+                SigningFileKind::Response => "The file is a signing request, not a response",
+            }));
+        }
+        let network = bitcoin_network::get().to_string();
+        if signing_file.network != network {
+            return Err(CCStr::from(format!(
+                "The file is for the {} network but the application is on {network}",
+                signing_file.network
+            )));
+        }
+        let psbt = parse_network_checked_psbt(&signing_file.psbt).map_err(CCStr::from)?;
+        Ok((signing_file, psbt))
+    }
+
+    fn to_json(&self) -> Result<String, CCStr> {
+        serde_json::to_string_pretty(self).map_err(|e| CCStr::from(e.to_string()))
+    }
+}
+
+/// Human readable description of the amounts spent and paid by `psbt`
+fn transaction_summary(psbt: &PartiallySignedTransaction) -> Result<String, CCStr> {
+    let network = bitcoin_network::get();
+    let btc = |amount: Amount| {
+        amount
+            .display_in(Denomination::Bitcoin)
+            .show_denomination()
+            .to_string()
+    };
+
+    let mut input_amount = Amount::ZERO;
+    for (tx_input, psbt_input) in psbt.unsigned_tx.input.iter().zip(psbt.inputs.iter()) {
+        let value = if let Some(witness_utxo) = &psbt_input.witness_utxo {
+            witness_utxo.value
+        } else if let Some(non_witness_utxo) = &psbt_input.non_witness_utxo {
+            non_witness_utxo
+                .output
+                .get(tx_input.previous_output.vout as usize)
+                .ok_or_else(|| CCStr::from("Malformed input"))?
+                .value
+        } else {
+            return Err(CCStr::from("Malformed input"));
+        };
+        input_amount += Amount::from_sat(value);
+    }
+
+    let mut lines = vec![format!(
+        "Spends {} input(s) totaling {}",
+        psbt.unsigned_tx.input.len(),
+        btc(input_amount)
+    )];
+    let mut output_amount = Amount::ZERO;
+    for tx_output in psbt.unsigned_tx.output.iter() {
+        let amount = Amount::from_sat(tx_output.value);
+        output_amount += amount;
+        // OP_RETURN and non-standard outputs have no address, show their script instead
+        match Address::from_script(&tx_output.script_pubkey, network) {
+            Ok(address) => lines.push(format!("Pays {} to {address}", btc(amount))),
+            Err(_) => lines.push(format!(
+                "Pays {} to script {:x}",
+                btc(amount),
+                tx_output.script_pubkey
+            )),
+        }
+    }
+    let fee = input_amount
+        .checked_sub(output_amount)
+        .ok_or_else(|| CCStr::from("The outputs exceed the inputs"))?;
+    lines.push(format!("Fee: {}", btc(fee)));
+    Ok(lines.join("\n"))
+}
+
+/// A file ready to be saved, with its name and content
+type PreparedFile = Result<(String, String), CCStr>;
+
+/// Prepare the signing file of `kind` for the encoded `psbt`
+fn prepare_signing_file(
+    kind: SigningFileKind,
+    wallet: String,
+    note: Option<String>,
+    psbt: &str,
+) -> PreparedFile {
+    let psbt = parse_network_checked_psbt(psbt).map_err(CCStr::from)?;
+    let signing_file = SigningFile::new(kind, wallet, note, &psbt)?;
+    Ok((signing_file.file_name(&psbt), signing_file.to_json()?))
+}
+
+/// Remote signing through signing request files
+///
+/// The owner of the transaction saves a signing request bundling the PSBT, the wallet
+/// and a description of the transaction, and sends it to a remote cosigner or heir.
+/// They open it in their own application, sign and save a signing response to send back,
+/// whose signatures are then merged in the transaction.
+#[component]
+pub(super) fn SigningRequests(
+    can_sign: bool,
+    cant_broadcast: ReadOnlySignal<bool>,
+    show_export: Signal<bool>,
+) -> Element {
+    log::debug!("SigningRequests Rendered");
+
+    let mut psbt_to_sign = use_context::<Signal<Option<PsbtToSign>>>();
+    let mut signed_psbt = use_context::<Signal<Option<SignedPsbt>>>();
+    let spendtabs_type = use_context::<Signal<SpendTabsType>>();
+    let tx_note = use_context::<Signal<TxNote>>();
+
+    let mut show_signing_requests = use_signal(|| false);
+    // The request opened by the remote signer, with the summary computed from its PSBT
+    let mut opened_request = use_signal(|| None::<(SigningFile, CCStr)>);
+    // A request opened while a transaction is in progress, waiting for the user to confirm
+    let mut pending_request = use_signal(|| None::<(SigningFile, CCStr)>);
+    let mut file_error = use_signal(|| None::<CCStr>);
+
+    let directory = use_export_directory();
+
+    let request_file = use_memo(move || -> Option<PreparedFile> {
+        if opened_request.read().is_some() {
+            return None;
+        }
+        let psbt = psbt_to_sign.read().as_ref()?.0.clone();
+        let wallet = match &*spendtabs_type.read() {
+            SpendTabsType::Owner(wallet_name) => wallet_name.to_string(),
+            SpendTabsType::Heir(_) => "Inheritance".to_owned(),
+        };
+        let note = Some(tx_note.read().0.trim().to_owned()).filter(|note| !note.is_empty());
+        Some(prepare_signing_file(
+            SigningFileKind::Request,
+            wallet,
+            note,
+            &psbt,
+        ))
+    });
+    let response_file = use_memo(move || -> Option<PreparedFile> {
+        let request = opened_request.read().as_ref()?.0.clone();
+        let psbt = signed_psbt.read().as_ref()?.0.clone();
+        Some(prepare_signing_file(
+            SigningFileKind::Response,
+            request.wallet,
+            request.note,
+            &psbt,
+        ))
+    });
+    let file_path = move |file: Memo<Option<PreparedFile>>| match &*file.read() {
+        Some(Ok((name, _))) => format!("{}/{name}", directory.read()),
+        _ => String::new(),
+    };
+    let request_path = use_memo(move || file_path(request_file));
+    let response_path = use_memo(move || file_path(response_file));
+
+    let save_file = move |file: Memo<Option<PreparedFile>>| {
+        let Some(Ok((name, content))) = file.peek().clone() else {
+            return;
+        };
+        let file_path = format!("{}/{name}", directory.peek());
+        if write_export_files("signing file", [(file_path.clone(), content)]) {
+            alert_info(format!(
+                "Saved to: {file_path}. Send it to the other party through any channel."
+            ));
+        }
+    };
+
+    let mut apply_request = move |request: SigningFile, summary: CCStr| {
+        *signed_psbt.write() = None;
+        *psbt_to_sign.write() = Some(PsbtToSign(CCStr::from(request.psbt.as_str())));
+        opened_request.set(Some((request, summary)));
+        alert_info("Signing request opened, review the transaction before signing it");
+    };
+    let open_request = move |content: Vec<u8>| {
+        let (request, psbt) = SigningFile::read(&content, SigningFileKind::Request)?;
+        let summary = CCStr::from(transaction_summary(&psbt)?);
+        // Opening the request replaces the transaction in progress and its signatures,
+        // the user must confirm it first
+        if psbt_to_sign.peek().is_some() || signed_psbt.peek().is_some() {
+            pending_request.set(Some((request, summary)));
+        } else {
+            apply_request(request, summary);
+        }
+        Ok::<_, CCStr>(())
+    };
+
+    let import_response = move |content: Vec<u8>| {
+        let (_, response_psbt) = SigningFile::read(&content, SigningFileKind::Response)?;
+        // Merge the signatures of the response with the ones already collected
+        let base_psbt = match signed_psbt.peek().as_ref() {
+            Some(signed_psbt) => signed_psbt.0.clone(),
+            None => psbt_to_sign
+                .peek()
+                .as_ref()
+                .map(|psbt| psbt.0.clone())
+                .ok_or_else(|| CCStr::from("Create the transaction first"))?,
+        };
+        let mut psbt = parse_network_checked_psbt(&base_psbt).map_err(CCStr::from)?;
+        if psbt.unsigned_tx.txid() != response_psbt.unsigned_tx.txid() {
+            return Err(CCStr::from(
+                "The signing response is for another transaction than this one",
+            ));
+        }
+        psbt.combine(response_psbt)
+            .map_err(|e| CCStr::from(e.to_string()))?;
+        super::sign_tx::accept_airgapped_psbt(
+            CCStr::from(psbt.to_string()),
+            cant_broadcast(),
+            show_export,
+        );
+        Ok::<_, CCStr>(())
+    };
+
+    let read_selected_file =
+        move |evt: Event<FormData>, process: Callback<Vec<u8>, Result<(), CCStr>>| async move {
+            if let Some(file_engine) = evt.files().clone() {
+                for file in file_engine.files() {
+                    let result = match file_engine.read_file(&file).await {
+                        Some(content) => process.call(content),
+                        None => Err(CCStr::from(format!("Could not read {file}"))),
+                    };
+                    file_error.set(result.err());
+                }
+            }
+        };
+    let open_request = use_callback(open_request);
+    let import_response = use_callback(import_response);
+
+    use_drop(|| log::debug!("SigningRequests Dropped"));
+
+    rsx! {
+        div { class: "collapse collapse-arrow bg-base-200 text-base-content mt-4",
+            input {
+                r#type: "checkbox",
+                checked: show_signing_requests(),
+                onchange: move |evt| show_signing_requests.set(evt.checked()),
+            }
+            div { class: "collapse-title font-medium", "Remote Signing (Signing Request Files)" }
+            div { class: "collapse-content",
+                div { class: "flex flex-col gap-4",
+                    if let Some((request, summary)) = opened_request() {
+                        div { class: "flex flex-col gap-2",
+                            div { class: "font-semibold", "Signing request of \"{request.wallet}\"" }
+                            div { class: "text-sm text-(--color-base-content)/60",
+                                "Created on "
+                                {timestamp_to_string(request.created_ts)}
+                            }
+                            if let Some(note) = request.note.clone() {
+                                div { class: "text-sm", "Note: {note}" }
+                            }
+                            div { class: "font-mono text-sm whitespace-pre-wrap break-all bg-base-100 rounded-box p-2",
+                                {summary}
+                            }
+                            div { class: "text-sm text-(--color-base-content)/60",
+                                "Carefully review the transaction overview, sign it, then save the signing response and send it back."
+                            }
+                        }
+                        ExportToDirectory {
+                            directory,
+                            display_path: ReadOnlySignal::from(response_path),
+                            class: "flex flex-row gap-2",
+                            button {
+                                class: "btn",
+                                disabled: !matches!(response_file(), Some(Ok(_))),
+                                onclick: move |_| save_file(response_file),
+                                DrawSvg::<FileDownload> { size: Size4 }
+                                "Save Signing Response"
+                            }
+                        }
+                        button {
+                            class: "btn btn-ghost btn-sm self-start",
+                            onclick: move |_| {
+                                opened_request.set(None);
+                                *psbt_to_sign.write() = None;
+                                *signed_psbt.write() = None;
+                            },
+                            "Close the Signing Request"
+                        }
+                    } else {
+                        div { class: "text-sm text-(--color-base-content)/60",
+                            "Send the transaction to a remote cosigner or heir as a signing request file.
+                            They open it in their Heritage application, sign and send you back a signing response."
+                        }
+                        ExportToDirectory {
+                            directory,
+                            display_path: ReadOnlySignal::from(request_path),
+                            class: "flex flex-row gap-2",
+                            button {
+                                class: "btn",
+                                disabled: !matches!(request_file(), Some(Ok(_))),
+                                onclick: move |_| save_file(request_file),
+                                DrawSvg::<FileDownload> { size: Size4 }
+                                "Save Signing Request"
+                            }
+                        }
+                        if let Some(Err(e)) = request_file() {
+                            div { class: "text-sm text-error", {e} }
+                        }
+                        div { class: "flex flex-col gap-2",
+                            div { class: "text-sm", "Import the signing response you received:" }
+                            FileInput {
+                                accept: ".json",
+                                onchange: move |evt: Event<FormData>| read_selected_file(evt, import_response),
+                            }
+                        }
+                        if can_sign {
+                            div { class: "flex flex-col gap-2",
+                                div { class: "text-sm", "Or open a signing request you received to sign it:" }
+                                FileInput {
+                                    accept: ".json",
+                                    onchange: move |evt: Event<FormData>| read_selected_file(evt, open_request),
+                                }
+                            }
+                        }
+                        if let Some((request, _)) = pending_request() {
+                            div { class: "flex flex-col gap-2 text-sm",
+                                div { class: "text-warning",
+                                    "Opening the signing request of \"{request.wallet}\" replaces the transaction in progress. The signatures already collected on it will be lost."
+                                }
+                                div { class: "flex flex-row gap-2",
+                                    button {
+                                        class: "btn btn-warning btn-sm",
+                                        onclick: move |_| {
+                                            if let Some((request, summary)) = pending_request.take() {
+                                                apply_request(request, summary);
+                                            }
+                                        },
+                                        "Replace the Transaction"
+                                    }
+                                    button {
+                                        class: "btn btn-ghost btn-sm",
+                                        onclick: move |_| pending_request.set(None),
+                                        "Cancel"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    if let Some(e) = file_error() {
+                        div { class: "text-sm text-error", {e} }
+                    }
+                }
+            }
+        }
+    }
+}
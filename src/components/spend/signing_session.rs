@@ -0,0 +1,338 @@
+use crate::prelude::*;
+
+use std::collections::BTreeSet;
+
+use btc_heritage_wallet::{
+    bitcoin::Txid, btc_heritage::PartiallySignedTransaction, Broadcaster, KeyProvider,
+};
+
+use crate::{
+    components::{
+        alerts::{add_alert, Alert, AlertCategory},
+        ledger_prompt::{LedgerCancel, LedgerPrompt, LedgerPromptsOverlay, LedgerSigner},
+        svg::{Broadcast, CheckCircleOutline, DrawSvg, Signature},
+    },
+    utils::{is_psbt_fully_signed, CCStr},
+};
+
+/// What a signing shows while waiting for a Ledger device
+#[derive(Clone, PartialEq)]
+pub struct LedgerSigningUi {
+    pub overlay: Signal<bool>,
+    pub prompts: Signal<Vec<LedgerPrompt>>,
+    pub current_prompt: Signal<Option<usize>>,
+    pub cancel: LedgerCancel,
+}
+
+pub fn use_ledger_signing_ui() -> LedgerSigningUi {
+    LedgerSigningUi {
+        overlay: use_signal(|| false),
+        prompts: use_signal(Vec::new),
+        current_prompt: use_signal(|| None),
+        cancel: use_hook(LedgerCancel::default),
+    }
+}
+
+/// Why a signer could not sign a PSBT
+#[derive(Debug, Clone, PartialEq)]
+pub enum SigningError {
+    /// The connected Ledger has the fingerprint of the signer but not its seed
+    SeedMismatch,
+    SeedVerification(String),
+    Signing(String),
+}
+impl SigningError {
+    /// Display the error to the user
+    pub fn alert(self) {
+        match self {
+            SigningError::SeedMismatch => add_alert(
+                Alert::error(
+                    "The connected Ledger has the fingerprint of this wallet \
+                    but does not hold its seed, it cannot sign for it",
+                )
+                .with_category(AlertCategory::Security),
+            ),
+            SigningError::SeedVerification(e) => {
+                alert_failure("Failed to verify the seed of the connected Ledger", e)
+            }
+            SigningError::Signing(e) => alert_failure("Failed to sign transaction", e),
+        }
+    }
+}
+
+/// Sign `psbt` with `signer`, returning the number of inputs signed
+///
+/// When the signer is backed by a Ledger, the device is first verified to hold the seed
+/// of the signer, and the screens it will display are shown to the user while signing.
+pub async fn sign_psbt_with<S: KeyProvider + LedgerSigner + 'static>(
+    signer: AsyncSignal<S>,
+    psbt: &mut PartiallySignedTransaction,
+    mut ledger_ui: LedgerSigningUi,
+) -> Result<usize, SigningError> {
+    let uses_ledger = signer.lmap(|s| s.uses_ledger()).unwrap_or_default();
+    if !uses_ledger {
+        return signer
+            .with(async |s: &S| s.sign_psbt(psbt).await)
+            .await
+            .map_err(|e| SigningError::Signing(e.to_string()));
+    }
+
+    // Any device restored from the seed can sign, once verified
+    let signer_id = signer.with(async |s: &S| s.ledger_signer_id()).await;
+    if !state_management::ledger_seed_verified(&signer_id) {
+        match signer
+            .with(async |s: &S| s.verify_ledger_seed().await)
+            .await
+        {
+            Ok(true) => state_management::set_ledger_seed_verified(signer_id),
+            Ok(false) => return Err(SigningError::SeedMismatch),
+            Err(e) => return Err(SigningError::SeedVerification(e)),
+        }
+    }
+    *ledger_ui.prompts.write() = LedgerPrompt::for_psbt(psbt);
+    *ledger_ui.overlay.write() = true;
+    let sign_result = ledger_ui
+        .cancel
+        .run(signer.with(async |s: &S| s.sign_psbt(psbt).await))
+        .await
+        .map_err(|e| SigningError::Signing(e.to_string()));
+    *ledger_ui.overlay.write() = false;
+    sign_result
+}
+
+/// A PSBT of a [SigningSession]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionPsbt {
+    pub label: CCStr,
+    pub psbt: PartiallySignedTransaction,
+    /// The signers (see [LedgerSigner::ledger_signer_id]) that signed inputs of the PSBT
+    pub signed_by: BTreeSet<String>,
+    /// Set once the PSBT is broadcast
+    pub txid: Option<Txid>,
+}
+impl SessionPsbt {
+    pub fn is_fully_signed(&self) -> bool {
+        is_psbt_fully_signed(&self.psbt)
+    }
+}
+
+/// PSBTs signed together, possibly by several signers, and broadcast together
+///
+/// Operations needing several transactions or signatures collect their PSBTs in a session
+/// and hand it to one [SigningSessionPanel] per signer, which signs all the PSBTs in
+/// sequence, prompting for the device each time, and broadcasts them once all are complete.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SigningSession {
+    psbts: Vec<SessionPsbt>,
+}
+impl SigningSession {
+    pub fn add(&mut self, label: impl Into<CCStr>, psbt: PartiallySignedTransaction) {
+        self.psbts.push(SessionPsbt {
+            label: label.into(),
+            psbt,
+            signed_by: BTreeSet::new(),
+            txid: None,
+        });
+    }
+
+    pub fn psbts(&self) -> &[SessionPsbt] {
+        &self.psbts
+    }
+
+    /// Number of PSBTs still missing signatures
+    pub fn pending_count(&self) -> usize {
+        self.psbts.iter().filter(|p| !p.is_fully_signed()).count()
+    }
+
+    /// All the PSBTs are fully signed and can be broadcast
+    pub fn is_complete(&self) -> bool {
+        !self.psbts.is_empty() && self.pending_count() == 0
+    }
+
+    /// All the PSBTs were broadcast
+    pub fn is_broadcast(&self) -> bool {
+        !self.psbts.is_empty() && self.psbts.iter().all(|p| p.txid.is_some())
+    }
+
+    /// Whether the signer already signed every PSBT it could
+    pub fn signed_by(&self, signer_id: &str) -> bool {
+        self.psbts
+            .iter()
+            .all(|p| p.is_fully_signed() || p.signed_by.contains(signer_id))
+    }
+}
+
+#[doc = "Properties for the [`SigningSessionPanel`] component."]
+#[allow(missing_docs)]
+#[derive(Props, Clone, PartialEq)]
+#[allow(non_camel_case_types)]
+pub struct SigningSessionPanelProps {
+    pub session: Signal<SigningSession>,
+}
+#[doc = " Sign and broadcast the PSBTs of a [SigningSession] with the signer in context"]
+#[doc = "# Props\n*For details, see the [props struct definition](SigningSessionPanelProps).*"]
+#[doc = "- [`session`](SigningSessionPanelProps::session) : `Signal<SigningSession>`"]
+#[allow(non_snake_case)]
+pub fn SigningSessionPanel<S: KeyProvider + LedgerSigner + Broadcaster + 'static>(
+    SigningSessionPanelProps { mut session }: SigningSessionPanelProps,
+) -> Element {
+    log::debug!("SigningSessionPanel Rendered");
+
+    let signer = use_context::<AsyncSignal<S>>();
+    let signer_id = use_memo(move || signer.lmap(|s| s.ledger_signer_id()));
+
+    let ledger_ui = use_ledger_signing_ui();
+    let mut in_operation = use_signal(|| false);
+
+    let has_signed = use_memo(move || {
+        signer_id
+            .read()
+            .as_ref()
+            .is_some_and(|signer_id| session.read().signed_by(signer_id))
+    });
+
+    let sign_all = {
+        let ledger_ui = ledger_ui.clone();
+        move |_| {
+            let ledger_ui = ledger_ui.clone();
+            async move {
+                let Some(signer_id) = signer_id() else {
+                    return;
+                };
+                *in_operation.write() = true;
+                let pending = session
+                    .peek()
+                    .psbts()
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, p)| !p.is_fully_signed() && !p.signed_by.contains(&signer_id))
+                    .map(|(index, p)| (index, p.psbt.clone()))
+                    .collect::<Vec<_>>();
+                let mut signed_total = 0;
+                for (index, mut psbt) in pending {
+                    match sign_psbt_with(signer, &mut psbt, ledger_ui.clone()).await {
+                        Ok(signed_count) => {
+                            signed_total += signed_count;
+                            let mut session_ref = session.write();
+                            let session_psbt = &mut session_ref.psbts[index];
+                            session_psbt.psbt = psbt;
+                            if signed_count > 0 {
+                                session_psbt.signed_by.insert(signer_id.clone());
+                            }
+                        }
+                        Err(e) => {
+                            // The next PSBTs would most likely fail the same way
+                            e.alert();
+                            break;
+                        }
+                    }
+                }
+                if signed_total > 0 {
+                    alert_success(format!("Inputs signed: {signed_total}"));
+                }
+                match session.peek().pending_count() {
+                    0 => alert_success("All the transactions are ready to broadcast"),
+                    pending => alert_warn(format!(
+                        "{pending} transaction(s) still need signatures of other signers"
+                    )),
+                }
+                *in_operation.write() = false;
+            }
+        }
+    };
+
+    let broadcast_all = move |_| async move {
+        *in_operation.write() = true;
+        let to_broadcast = session
+            .peek()
+            .psbts()
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.txid.is_none())
+            .map(|(index, p)| (index, p.psbt.clone()))
+            .collect::<Vec<_>>();
+        for (index, psbt) in to_broadcast {
+            match signer.with(async |b: &S| b.broadcast(psbt).await).await {
+                Ok(txid) => {
+                    log::info!("Transaction broadcast successfully: {txid}");
+                    session.write().psbts[index].txid = Some(txid);
+                }
+                Err(e) => {
+                    alert_failure("Failed to broadcast transaction", e);
+                    break;
+                }
+            }
+        }
+        if session.peek().is_broadcast() {
+            alert_success("All the transactions were broadcast");
+        }
+        *in_operation.write() = false;
+    };
+
+    use_drop(|| log::debug!("SigningSessionPanel Dropped"));
+
+    rsx! {
+        div { class: "flex flex-col gap-6",
+            table { class: "table",
+                thead {
+                    tr {
+                        th { "Transaction" }
+                        th { "Signed by" }
+                        th { "Status" }
+                    }
+                }
+                tbody {
+                    for (index , session_psbt) in session.read().psbts().iter().enumerate() {
+                        tr { key: "{index}",
+                            td { {session_psbt.label.clone()} }
+                            td { class: "font-mono text-sm",
+                                {session_psbt.signed_by.iter().cloned().collect::<Vec<_>>().join(", ")}
+                            }
+                            td {
+                                if let Some(txid) = session_psbt.txid {
+                                    span { class: "badge badge-success", "Broadcast" }
+                                    div { class: "font-mono text-xs break-all", "{txid}" }
+                                } else if session_psbt.is_fully_signed() {
+                                    span { class: "badge badge-success", "Signed" }
+                                } else {
+                                    span { class: "badge badge-warning", "Needs signatures" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            div { class: "card-actions justify-center",
+                button {
+                    class: "btn btn-primary",
+                    disabled: in_operation() || has_signed() || signer_id.read().is_none(),
+                    onclick: sign_all,
+                    if has_signed() {
+                        DrawSvg::<CheckCircleOutline> {}
+                        "Signed"
+                    } else {
+                        DrawSvg::<Signature> {}
+                        "Sign All"
+                    }
+                }
+                button {
+                    class: "btn btn-primary",
+                    disabled: in_operation() || !session.read().is_complete()
+                        || session.read().is_broadcast(),
+                    onclick: broadcast_all,
+                    DrawSvg::<Broadcast> {}
+                    "Broadcast All"
+                }
+            }
+
+            LedgerPromptsOverlay {
+                is_open: ledger_ui.overlay,
+                title: "Signing with the Ledger",
+                prompts: ledger_ui.prompts,
+                current_prompt: ledger_ui.current_prompt,
+                cancel: ledger_ui.cancel.clone(),
+            }
+        }
+    }
+}
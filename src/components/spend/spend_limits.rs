@@ -0,0 +1,346 @@
+use crate::prelude::*;
+
+use btc_heritage_wallet::{
+    bitcoin::{
+        bip32::{ChildNumber, Fingerprint},
+        psbt::Output,
+        Address, Amount, TxOut,
+    },
+    btc_heritage::{
+        bitcoincore_rpc::jsonrpc::serde_json,
+        utils::{bitcoin_network, timestamp_now},
+        HeritageWalletBackup, PartiallySignedTransaction,
+    },
+    heritage_service_api_client::TransactionSummary,
+    miniscript::{Descriptor, DescriptorPublicKey},
+};
+use state_management::{CoolingOffRequest, SpendLimits, WalletSettings};
+
+use crate::{
+    state_management::DatabaseCommand,
+    utils::{log_error, CCStr, CheapClone},
+};
+
+use super::SpendTabsType;
+
+/// Where a transaction above the cooling-off threshold of the wallet stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum CoolingOffStatus {
+    /// The transaction is below the threshold
+    NotNeeded,
+    /// The transaction must be requested first
+    ToRequest,
+    /// The transaction was requested, it can be confirmed after the given seconds
+    Waiting(u64),
+    /// The cooling-off delay elapsed, the transaction can be confirmed
+    Ready,
+}
+
+/// The descriptors of the wallet being spent, recognizing the outputs paying to it
+///
+/// The key origins of a PSBT output are only hints that anyone can add, e.g. to an
+/// imported PSBT: an output belongs to the wallet only if one of its key origins is
+/// from the wallet fingerprint and one of the wallet descriptors, derived at the index
+/// of this origin, gives the script of the output.
+/// Without the descriptors, no output is recognized and every output is counted as sent.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(super) struct WalletDescriptors {
+    fingerprint: Option<Fingerprint>,
+    descriptors: Vec<Descriptor<DescriptorPublicKey>>,
+}
+impl WalletDescriptors {
+    fn from_backup(backup: &HeritageWalletBackup) -> Result<Self, String> {
+        let fingerprint = backup.fingerprint().map_err(|e| e.to_string())?;
+        let backup = serde_json::to_value(backup).map_err(|e| e.to_string())?;
+        let descriptors = backup
+            .as_array()
+            .ok_or("Unexpected descriptors backup format")?
+            .iter()
+            .flat_map(|subwallet| {
+                [
+                    &subwallet["external_descriptor"],
+                    &subwallet["change_descriptor"],
+                ]
+            })
+            .map(|descriptor| {
+                descriptor
+                    .as_str()
+                    .ok_or("Missing descriptor in backup")?
+                    .parse::<Descriptor<DescriptorPublicKey>>()
+                    .map_err(|e| e.to_string())
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(Self {
+            fingerprint,
+            descriptors,
+        })
+    }
+
+    /// Whether the output pays to the wallet
+    fn owns(&self, txout: &TxOut, output: &Output) -> bool {
+        let Some(fingerprint) = self.fingerprint else {
+            return false;
+        };
+        output
+            .tap_key_origins
+            .values()
+            .map(|(_, origin)| origin)
+            .chain(output.bip32_derivation.values())
+            .filter(|(origin_fingerprint, _)| *origin_fingerprint == fingerprint)
+            .filter_map(|(_, path)| match path.as_ref().last() {
+                Some(ChildNumber::Normal { index }) => Some(*index),
+                _ => None,
+            })
+            .any(|index| {
+                self.descriptors.iter().any(|descriptor| {
+                    descriptor
+                        .at_derivation_index(index)
+                        .is_ok_and(|derived| derived.script_pubkey() == txout.script_pubkey)
+                })
+            })
+    }
+
+    /// The outputs of the PSBT not paying to the wallet
+    fn sent_outputs<'a>(
+        &'a self,
+        psbt: &'a PartiallySignedTransaction,
+    ) -> impl Iterator<Item = &'a TxOut> {
+        psbt.unsigned_tx
+            .output
+            .iter()
+            .zip(psbt.outputs.iter())
+            .filter(|(txout, output)| !self.owns(txout, output))
+            .map(|(txout, _)| txout)
+    }
+}
+
+/// Memo of the [WalletDescriptors] of the wallet being spent, from its descriptors backup
+///
+/// Heirs have no descriptors backup, none of the outputs are recognized then.
+pub(super) fn use_memo_wallet_descriptors() -> Memo<WalletDescriptors> {
+    let backup = try_use_context::<FResource<HeritageWalletBackup>>();
+    use_memo(move || {
+        backup
+            .and_then(|backup| backup.lrmap_ok(WalletDescriptors::from_backup))
+            .and_then(|wallet_descriptors| wallet_descriptors.map_err(log_error).ok())
+            .unwrap_or_default()
+    })
+}
+
+/// Amount a PSBT sends outside of the wallet
+pub(super) fn psbt_sent_amount(
+    psbt: &PartiallySignedTransaction,
+    wallet_descriptors: &WalletDescriptors,
+) -> Amount {
+    wallet_descriptors
+        .sent_outputs(psbt)
+        .map(|txout| Amount::from_sat(txout.value))
+        .sum()
+}
+
+/// Identifies the transaction of a PSBT for a [CoolingOffRequest]
+pub(super) fn psbt_spend_key(
+    psbt: &PartiallySignedTransaction,
+    wallet_descriptors: &WalletDescriptors,
+) -> String {
+    let network = bitcoin_network::get();
+    let recipients = wallet_descriptors
+        .sent_outputs(psbt)
+        .filter_map(|txout| Address::from_script(&txout.script_pubkey, network).ok())
+        .map(|address| address.to_string());
+    CoolingOffRequest::spend_key(recipients, psbt_sent_amount(psbt, wallet_descriptors))
+}
+
+/// Self-imposed spend limits of the wallet being spent
+///
+/// They are enforced when a transaction is created and again before it is signed,
+/// so that a transaction imported in the Sign tab cannot get around them.
+/// Heirs have no spend limits.
+#[derive(Clone, Copy)]
+pub(super) struct SpendLimitsGuard {
+    database_service: Coroutine<DatabaseCommand>,
+    wallet_name: Memo<Option<CCStr>>,
+    wallet_descriptors: Memo<WalletDescriptors>,
+    spend_limits: Memo<SpendLimits>,
+    spent_last_day: Memo<Amount>,
+    cooling_off_request: Resource<Option<CoolingOffRequest>>,
+    now: Signal<u64>,
+}
+
+pub(super) fn use_spend_limits_guard() -> SpendLimitsGuard {
+    let database_service = state_management::use_database_service();
+    let spendtabs_type = use_context::<Signal<SpendTabsType>>();
+    // Only the wallets of the owner have settings
+    let wallet_settings = try_use_context::<Resource<WalletSettings>>();
+    let wallet_transactions =
+        try_use_context::<FMemo<ContentHashed<CheapClone<[TransactionSummary]>>>>();
+
+    let wallet_name = use_memo(move || match *spendtabs_type.read() {
+        SpendTabsType::Owner(ref wallet_name) => Some(wallet_name.clone()),
+        SpendTabsType::Heir(_) => None,
+    });
+    let wallet_descriptors = use_memo_wallet_descriptors();
+    let mut now = use_signal(timestamp_now);
+    use_future(move || async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+            now.set(timestamp_now());
+        }
+    });
+    let spend_limits = use_memo(move || {
+        wallet_settings
+            .and_then(|wallet_settings| wallet_settings.cloned())
+            .map(|wallet_settings| wallet_settings.effective_spend_limits(now()))
+            .unwrap_or_default()
+    });
+    // Net amount that left the wallet during the last 24 hours, unconfirmed transactions included
+    let spent_last_day = use_memo(move || {
+        let since = now() - 24 * 60 * 60;
+        wallet_transactions
+            .and_then(|wallet_transactions| {
+                wallet_transactions.lrmap_ok(|wallet_transactions| {
+                    wallet_transactions
+                        .iter()
+                        .filter(|tx_sum| {
+                            tx_sum
+                                .confirmation_time
+                                .as_ref()
+                                .is_none_or(|bt| bt.timestamp >= since)
+                        })
+                        .map(|tx_sum| {
+                            let spent = tx_sum
+                                .owned_inputs
+                                .iter()
+                                .map(|io| io.amount)
+                                .sum::<Amount>();
+                            let received = tx_sum.owned_outputs.iter().map(|io| io.amount).sum();
+                            spent.checked_sub(received).unwrap_or(Amount::ZERO)
+                        })
+                        .sum::<Amount>()
+                })
+            })
+            .unwrap_or(Amount::ZERO)
+    });
+    let cooling_off_request = use_resource(move || async move {
+        let wallet_name = wallet_name()?;
+        state_management::load_cooling_off_request(database_service, wallet_name)
+            .await
+            .ok()
+            .flatten()
+    });
+
+    SpendLimitsGuard {
+        database_service,
+        wallet_name,
+        wallet_descriptors,
+        spend_limits,
+        spent_last_day,
+        cooling_off_request,
+        now,
+    }
+}
+
+impl SpendLimitsGuard {
+    /// Amount the PSBT sends outside of the wallet, see [psbt_sent_amount]
+    pub(super) fn sent_amount(&self, psbt: &PartiallySignedTransaction) -> Amount {
+        psbt_sent_amount(psbt, &self.wallet_descriptors.read())
+    }
+
+    /// Identifies the transaction of the PSBT, see [psbt_spend_key]
+    pub(super) fn spend_key(&self, psbt: &PartiallySignedTransaction) -> String {
+        psbt_spend_key(psbt, &self.wallet_descriptors.read())
+    }
+
+    pub(super) fn spend_limits(&self) -> SpendLimits {
+        (self.spend_limits)()
+    }
+
+    pub(super) fn spent_last_day(&self) -> Amount {
+        (self.spent_last_day)()
+    }
+
+    /// Reason why a transaction sending `amount` outside of the wallet is refused, if any
+    pub(super) fn limit_error(&self, amount: Amount) -> Option<String> {
+        self.spend_limits().violation(amount, self.spent_last_day())
+    }
+
+    /// Cooling-off status of the transaction sending `amount` outside of the wallet,
+    /// identified by `spend_key`
+    pub(super) fn cooling_off_status(&self, spend_key: &str, amount: Amount) -> CoolingOffStatus {
+        let spend_limits = self.spend_limits();
+        if !spend_limits.needs_cooling_off(amount) {
+            return CoolingOffStatus::NotNeeded;
+        }
+        match *self.cooling_off_request.read() {
+            Some(Some(ref request)) if request.spend_key == spend_key => {
+                let now = (self.now)();
+                let ready_at = request.requested_at + spend_limits.cooling_off_minutes as u64 * 60;
+                if ready_at <= now {
+                    CoolingOffStatus::Ready
+                } else {
+                    CoolingOffStatus::Waiting(ready_at - now)
+                }
+            }
+            _ => CoolingOffStatus::ToRequest,
+        }
+    }
+
+    /// Start the cooling-off delay of the transaction identified by `spend_key`
+    pub(super) async fn request(self, spend_key: String) {
+        let request = CoolingOffRequest {
+            spend_key,
+            requested_at: timestamp_now(),
+        };
+        if self
+            .save_request(Some(request), "Failed to request the transaction")
+            .await
+        {
+            alert_info(format!(
+                "Transaction requested, you can confirm it in {} minutes",
+                self.spend_limits().cooling_off_minutes
+            ));
+        }
+    }
+
+    /// Carry the elapsed cooling-off delay over to the transaction created from the request,
+    /// now identified by `spend_key`, so that it can be signed
+    pub(super) async fn transfer_request(self, spend_key: String) {
+        let Some(Some(request)) = self.cooling_off_request.cloned() else {
+            return;
+        };
+        let request = CoolingOffRequest {
+            spend_key,
+            requested_at: request.requested_at,
+        };
+        self.save_request(Some(request), "Failed to update the cooling-off request")
+            .await;
+    }
+
+    /// Forget the cooling-off request once its transaction is signed
+    pub(super) async fn clear_request(self) {
+        self.save_request(None, "Failed to clear the cooling-off request")
+            .await;
+    }
+
+    async fn save_request(mut self, request: Option<CoolingOffRequest>, failure: &str) -> bool {
+        let Some(wallet_name) = (self.wallet_name)() else {
+            return false;
+        };
+        match state_management::save_cooling_off_request(
+            self.database_service,
+            wallet_name,
+            request,
+        )
+        .await
+        {
+            Ok(()) => {
+                self.cooling_off_request.restart();
+                true
+            }
+            Err(e) => {
+                alert_failure(failure, e);
+                false
+            }
+        }
+    }
+}
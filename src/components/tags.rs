@@ -0,0 +1,198 @@
+use crate::prelude::*;
+
+use std::collections::BTreeSet;
+
+use btc_heritage_wallet::DatabaseItem;
+
+use crate::utils::CCStr;
+
+/// Edit the tags of the database item in the context, e.g. "cold", "family" or "business"
+#[component]
+pub fn EditTags<DBI: DatabaseItem + 'static>() -> Element {
+    let database_item = use_context::<AsyncSignal<DBI>>();
+
+    let database_service = state_management::use_database_service();
+
+    let name = use_memo(move || database_item.lmap(|dbi| CCStr::from(dbi.name())));
+    let item_tags = use_resource(move || async move {
+        state_management::load_item_tags(database_service)
+            .await
+            .map_err(CCStr::from)
+    });
+
+    let current_tags = use_memo(move || {
+        let key = state_management::item_tags_key::<DBI>(name()?.as_ref());
+        item_tags.lrmap_ok(|item_tags| item_tags.get(&key).cloned().unwrap_or_default())
+    });
+    // Tags of the other items, suggested for quick reuse
+    let suggested_tags = use_memo(move || {
+        let current_tags = current_tags().unwrap_or_default();
+        item_tags
+            .lrmap_ok(|item_tags| {
+                item_tags
+                    .values()
+                    .flatten()
+                    .filter(|tag| !current_tags.contains(*tag))
+                    .cloned()
+                    .collect::<BTreeSet<_>>()
+            })
+            .unwrap_or_default()
+    });
+
+    let mut new_tag = use_signal(String::new);
+    let mut updating = use_signal(|| false);
+
+    let save_tags = move |tags: BTreeSet<String>| async move {
+        let Some(name) = name() else {
+            return;
+        };
+        let Some(Ok(mut new_item_tags)) = item_tags.cloned() else {
+            return;
+        };
+        let key = state_management::item_tags_key::<DBI>(name.as_ref());
+        if tags.is_empty() {
+            new_item_tags.remove(&key);
+        } else {
+            new_item_tags.insert(key, tags.clone());
+        }
+        *updating.write() = true;
+        if let Err(e) = optimistic_update(
+            item_tags,
+            Ok(new_item_tags),
+            state_management::save_item_tags::<DBI>(database_service, name, tags),
+        )
+        .await
+        {
+            alert_failure("Failed to save the tags", e);
+        }
+        *updating.write() = false;
+    };
+    let add_tag = move |tag: String| async move {
+        let tag = tag.trim().to_lowercase();
+        let Some(mut tags) = current_tags() else {
+            return;
+        };
+        if tag.is_empty() || !tags.insert(tag) {
+            return;
+        }
+        new_tag.set(String::new());
+        save_tags(tags).await
+    };
+
+    rsx! {
+        div { class: "card [--cardtitle-fs:var(--text-2xl)] border border-base-content/5 shadow-md my-4",
+            div { class: "card-body",
+                h2 { class: "card-title", "Tags" }
+                div { class: "card-subtitle",
+                    "Organize your items with tags, then filter them on the list view."
+                }
+                div { class: "flex flex-row flex-wrap gap-2 min-h-8 items-center",
+                    if let Some(tags) = current_tags() {
+                        if tags.is_empty() {
+                            span { class: "text-sm font-light", "No tag" }
+                        }
+                        for tag in tags.clone() {
+                            div { key: "{tag}", class: "badge badge-primary gap-1",
+                                "{tag}"
+                                button {
+                                    class: "cursor-pointer",
+                                    title: "Remove the tag",
+                                    disabled: updating(),
+                                    onclick: {
+                                        let mut tags = tags.clone();
+                                        tags.remove(&tag);
+                                        move |_| save_tags(tags.clone())
+                                    },
+                                    "✕"
+                                }
+                            }
+                        }
+                    }
+                }
+                div { class: "join",
+                    input {
+                        r#type: "text",
+                        class: "input join-item w-64",
+                        placeholder: "New tag...",
+                        value: "{new_tag}",
+                        disabled: updating(),
+                        oninput: move |evt| new_tag.set(evt.value()),
+                        onkeydown: move |evt| {
+                            if evt.key() == Key::Enter {
+                                spawn(add_tag(new_tag()));
+                            }
+                        },
+                    }
+                    button {
+                        class: "btn btn-primary join-item",
+                        disabled: updating() || new_tag.read().trim().is_empty(),
+                        onclick: move |_| add_tag(new_tag()),
+                        "Add"
+                    }
+                }
+                if !suggested_tags.read().is_empty() {
+                    div { class: "flex flex-row flex-wrap gap-2 items-center text-sm font-light",
+                        "Existing tags: "
+                        for tag in suggested_tags() {
+                            button {
+                                key: "{tag}",
+                                class: "badge badge-outline cursor-pointer",
+                                disabled: updating(),
+                                onclick: move |_| add_tag(tag.clone()),
+                                "+ {tag}"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Filter chips to only display the items with the selected tag
+#[component]
+pub fn TagFilter(tags: BTreeSet<String>, mut selected: Signal<Option<String>>) -> Element {
+    if tags.is_empty() {
+        return rsx! {};
+    }
+    rsx! {
+        div { class: "flex flex-row flex-wrap gap-2 items-center",
+            span { class: "text-sm font-light", "Tags:" }
+            button {
+                class: "badge cursor-pointer",
+                class: if selected.read().is_none() { "badge-primary" } else { "badge-outline" },
+                onclick: move |_| selected.set(None),
+                "All"
+            }
+            for tag in tags {
+                button {
+                    key: "{tag}",
+                    class: "badge cursor-pointer",
+                    class: if selected.read().as_ref() == Some(&tag) { "badge-primary" } else { "badge-outline" },
+                    onclick: {
+                        let tag = tag.clone();
+                        move |_| {
+                            let tag = tag.clone();
+                            selected.with_mut(|selected| {
+                                *selected = (selected.as_ref() != Some(&tag)).then_some(tag)
+                            });
+                        }
+                    },
+                    "{tag}"
+                }
+            }
+        }
+    }
+}
+
+/// Small badges listing the tags of an item
+#[component]
+pub fn TagBadges(tags: BTreeSet<String>) -> Element {
+    rsx! {
+        div { class: "flex flex-row flex-wrap gap-1",
+            for tag in tags {
+                span { key: "{tag}", class: "badge badge-sm badge-soft", "{tag}" }
+            }
+        }
+    }
+}
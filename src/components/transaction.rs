@@ -20,7 +20,7 @@ use crate::{
 };
 
 use super::{
-    balance::UIBtcAmount,
+    balance::{HistoricalFiatValue, UIBtcAmount},
     heritage_configuration::UIExpirationBadge,
     misc::{UIBtcAddr, UITxId},
     timestamp::UITimestamp,
@@ -634,6 +634,8 @@ pub struct UIUtxo {
     address: UIBtcAddr,
     amount: UIBtcAmount,
     heritage_config_expiration: Option<UIExpirationBadge>,
+    /// Amount and confirmation time, to display the acquisition value
+    acquisition: Option<(Amount, u64)>,
 }
 impl LoadedElement for UIUtxo {
     type Loader = TransparentLoader;
@@ -649,6 +651,9 @@ impl LoadedElement for UIUtxo {
                     div { class: "text-xl",
                         LoadedComponent { input: m.map(self.amount) }
                     }
+                    if let Some((amount, timestamp)) = self.acquisition {
+                        HistoricalFiatValue { amount, timestamp, with_rate: true }
+                    }
                 }
                 div { class: "min-w-xl",
                     div {
@@ -698,6 +703,7 @@ impl LoadedElement for UIUtxo {
             address: UIBtcAddr::place_holder(),
             amount: UIBtcAmount::place_holder(),
             heritage_config_expiration: None,
+            acquisition: None,
         }
     }
 }
@@ -716,6 +722,10 @@ impl FromRef<UtxoWithInfo> for UIUtxo {
             .unwrap_or_else(|| (UITimestamp::none(), CCStr::from("-")));
         let address = UIBtcAddr::from(utxo_with_info.address.clone());
         let amount = UIBtcAmount::from(utxo_with_info.amount);
+        let acquisition = utxo_with_info
+            .confirmation_time
+            .as_ref()
+            .map(|bt| (utxo_with_info.amount, bt.timestamp));
 
         let heritage_config_expiration =
             utxo_with_info
@@ -735,6 +745,7 @@ impl FromRef<UtxoWithInfo> for UIUtxo {
             address,
             amount,
             heritage_config_expiration,
+            acquisition,
         }
     }
 }
@@ -0,0 +1,208 @@
+use std::{
+    collections::BTreeMap,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+    time::Instant,
+};
+
+use btc_heritage_wallet::btc_heritage::bitcoincore_rpc::jsonrpc::serde_json;
+use serde::{Deserialize, Serialize};
+
+const CRASH_REPORTS_DIR: &str = "crash-reports";
+const RECOVERY_FILE_NAME: &str = "crash-recovery.json";
+/// A crash sooner than this after the start (30 seconds) is likely to happen again
+/// at each start, so the application is not restarted automatically
+const MIN_UPTIME_FOR_RESTART: std::time::Duration = std::time::Duration::from_secs(30);
+
+static START: OnceLock<Instant> = OnceLock::new();
+static DATADIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+/// The serialized drafts of the forms currently displayed, saved when crashing
+static DRAFTS: Mutex<BTreeMap<String, String>> = Mutex::new(BTreeMap::new());
+/// The drafts saved by the previous crash, not yet restored by their form
+static RECOVERED_DRAFTS: Mutex<BTreeMap<String, String>> = Mutex::new(BTreeMap::new());
+/// The crash report of the previous run, if it crashed
+static PREVIOUS_CRASH_REPORT: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// What the previous run of the application left when it crashed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CrashRecovery {
+    crash_report: PathBuf,
+    drafts: BTreeMap<String, String>,
+}
+
+/// Install the panic hook writing a crash report and the unsaved drafts in the datadir
+///
+/// A panic on the main thread takes the window down, so the application is then
+/// restarted to let the user recover the drafts.
+pub fn install_panic_hook() {
+    START.get_or_init(Instant::now);
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |panic_info| {
+        default_hook(panic_info);
+
+        let Some(datadir) = DATADIR.lock().ok().and_then(|datadir| datadir.clone()) else {
+            return;
+        };
+        let crash_report = match write_crash_report(&datadir, panic_info) {
+            Ok(crash_report) => crash_report,
+            Err(e) => {
+                eprintln!("Could not write the crash report: {e}");
+                return;
+            }
+        };
+        // Panics of the background threads do not bring the window down
+        if std::thread::current().name() != Some("main") {
+            return;
+        }
+        let drafts = DRAFTS
+            .lock()
+            .map(|drafts| drafts.clone())
+            .unwrap_or_default();
+        let recovery = CrashRecovery {
+            crash_report,
+            drafts,
+        };
+        if let Err(e) = serde_json::to_vec(&recovery)
+            .map_err(|e| e.to_string())
+            .and_then(|content| {
+                std::fs::write(datadir.join(RECOVERY_FILE_NAME), content).map_err(|e| e.to_string())
+            })
+        {
+            eprintln!("Could not save the drafts: {e}");
+        }
+        if START
+            .get()
+            .is_some_and(|start| start.elapsed() >= MIN_UPTIME_FOR_RESTART)
+        {
+            restart();
+        }
+    }));
+}
+
+fn write_crash_report(
+    datadir: &Path,
+    panic_info: &std::panic::PanicHookInfo,
+) -> std::io::Result<PathBuf> {
+    let reports_dir = datadir.join(CRASH_REPORTS_DIR);
+    std::fs::create_dir_all(&reports_dir)?;
+    let now = chrono::Local::now();
+    let crash_report = reports_dir.join(format!("crash-{}.txt", now.format("%Y%m%d-%H%M%S")));
+    let mut file = std::fs::File::create(&crash_report)?;
+    writeln!(
+        file,
+        "Heritage Wallet {} crashed on {}",
+        env!("CARGO_PKG_VERSION"),
+        now.to_rfc3339()
+    )?;
+    writeln!(
+        file,
+        "OS: {} ({})",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    )?;
+    writeln!(
+        file,
+        "Thread: {}",
+        std::thread::current().name().unwrap_or("unnamed")
+    )?;
+    writeln!(file, "{panic_info}")?;
+    writeln!(file)?;
+    writeln!(file, "{}", std::backtrace::Backtrace::force_capture())?;
+    Ok(crash_report)
+}
+
+/// Start a new instance of the application with the same arguments and stop this one
+fn restart() {
+    let Ok(exe) = std::env::current_exe() else {
+        return;
+    };
+    match std::process::Command::new(exe)
+        .args(std::env::args_os().skip(1))
+        .spawn()
+    {
+        Ok(_) => std::process::exit(1),
+        Err(e) => eprintln!("Could not restart the application: {e}"),
+    }
+}
+
+/// Set the directory of the crash reports and take what the previous crash left in it
+///
+/// Must be called at startup, before the window is created, so a crash happening
+/// while the application loads is reported too.
+pub fn init_datadir(datadir: PathBuf) {
+    if let Some(crash_report) = take_crash_recovery(&datadir) {
+        if let Ok(mut previous_crash_report) = PREVIOUS_CRASH_REPORT.lock() {
+            *previous_crash_report = Some(crash_report);
+        }
+    }
+    if let Ok(mut current) = DATADIR.lock() {
+        *current = Some(datadir);
+    }
+}
+
+/// The path of the crash report if the previous run crashed
+pub fn previous_crash_report() -> Option<PathBuf> {
+    PREVIOUS_CRASH_REPORT.lock().ok()?.clone()
+}
+
+fn take_crash_recovery(datadir: &Path) -> Option<PathBuf> {
+    let recovery_file = datadir.join(RECOVERY_FILE_NAME);
+    let content = std::fs::read(&recovery_file).ok()?;
+    if let Err(e) = std::fs::remove_file(&recovery_file) {
+        log::error!("Could not remove the crash recovery file: {e}");
+    }
+    let recovery = serde_json::from_slice::<CrashRecovery>(&content)
+        .map_err(|e| log::error!("Invalid crash recovery file: {e}"))
+        .ok()?;
+    log::warn!(
+        "The previous run crashed, recovering {} draft(s)",
+        recovery.drafts.len()
+    );
+    if let Ok(mut recovered_drafts) = RECOVERED_DRAFTS.lock() {
+        *recovered_drafts = recovery.drafts;
+    }
+    Some(recovery.crash_report)
+}
+
+/// List the crash reports, most recent first
+pub fn list_crash_reports(datadir: &Path) -> Vec<PathBuf> {
+    let mut crash_reports = std::fs::read_dir(datadir.join(CRASH_REPORTS_DIR))
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    // The file names embed the date, so they sort chronologically
+    crash_reports.sort_unstable_by(|a, b| b.cmp(a));
+    crash_reports
+}
+
+/// Keep the draft of a form so it can be restored if the application crashes
+pub fn save_draft<T: Serialize>(key: &str, draft: &T) {
+    match serde_json::to_string(draft) {
+        Ok(draft) => {
+            if let Ok(mut drafts) = DRAFTS.lock() {
+                drafts.insert(key.to_owned(), draft);
+            }
+        }
+        Err(e) => log::warn!("Could not serialize the draft {key}: {e}"),
+    }
+}
+
+/// Forget the draft of a form, typically when it is left or submitted
+pub fn clear_draft(key: &str) {
+    if let Ok(mut drafts) = DRAFTS.lock() {
+        drafts.remove(key);
+    }
+}
+
+/// Take the draft of a form saved by the previous crash, if any
+pub fn take_recovered_draft<T: for<'de> Deserialize<'de>>(key: &str) -> Option<T> {
+    let draft = RECOVERED_DRAFTS.lock().ok()?.remove(key)?;
+    serde_json::from_str(&draft)
+        .map_err(|e| log::warn!("Could not restore the draft {key}: {e}"))
+        .ok()
+}
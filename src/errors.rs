@@ -0,0 +1,175 @@
+use crate::{utils::CCStr, Route};
+
+/// Base URL of the documentation the remediations point to
+const DOCUMENTATION_URL: &str = "https://btc-heritage.com/docs";
+
+/// Common failures the user can fix, recognized from the error messages of the
+/// underlying libraries which do not expose typed errors across the board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The Heritage Service rejected the credentials (HTTP 401 or 403)
+    ServiceUnauthorized,
+    /// The Heritage Service could not be reached
+    ServiceUnreachable,
+    /// The blockchain provider (Electrum or Bitcoin Core) could not be reached
+    NodeUnreachable,
+    /// The Ledger device is locked, or the Bitcoin application is not opened
+    LedgerLocked,
+    /// No Ledger device could be found
+    LedgerNotConnected,
+    /// The data belongs to another Bitcoin network than the one of the application
+    WrongNetwork,
+    /// Any other failure, displayed as is
+    Other,
+}
+
+impl ErrorKind {
+    /// Recognize the kind of failure from its error message
+    pub fn classify(error: &str) -> Self {
+        let error = error.to_lowercase();
+        let has = |patterns: &[&str]| patterns.iter().any(|p| error.contains(p));
+        let mentions_service = has(&["heritage service", "service api", "api.btc-heritage"]);
+        let mentions_node = has(&[
+            "electrum",
+            "bitcoin core",
+            "bitcoincore",
+            "rpc",
+            "blockchain",
+        ]);
+
+        if has(&["ledger", "apdu", "hidapi"]) {
+            if has(&[
+                "0x5515",
+                "locked",
+                "0x6e00",
+                "0x6d00",
+                "0x6511",
+                "wrong app",
+            ]) {
+                ErrorKind::LedgerLocked
+            } else if has(&["not found", "no device", "not connected", "disconnected"]) {
+                ErrorKind::LedgerNotConnected
+            } else if has(&["network"]) {
+                ErrorKind::WrongNetwork
+            } else {
+                ErrorKind::Other
+            }
+        } else if has(&["unauthorized", "forbidden", "not authenticated"])
+            || (mentions_service && has(&["token", "expired"]))
+        {
+            ErrorKind::ServiceUnauthorized
+        } else if has(&["network mismatch", "wrong network", "invalid network"]) {
+            ErrorKind::WrongNetwork
+        } else if has(&[
+            "connection refused",
+            "timed out",
+            "timeout",
+            "error sending request",
+            "dns error",
+            "unreachable",
+            "could not connect",
+        ]) {
+            if mentions_node && !mentions_service {
+                ErrorKind::NodeUnreachable
+            } else {
+                ErrorKind::ServiceUnreachable
+            }
+        } else {
+            ErrorKind::Other
+        }
+    }
+
+    /// Short description of the failure, used as the title of the alerts
+    pub fn title(self) -> &'static str {
+        match self {
+            ErrorKind::ServiceUnauthorized => "Not connected to the Heritage Service",
+            ErrorKind::ServiceUnreachable => "Heritage Service unreachable",
+            ErrorKind::NodeUnreachable => "Blockchain provider unreachable",
+            ErrorKind::LedgerLocked => "Ledger not ready",
+            ErrorKind::LedgerNotConnected => "Ledger not found",
+            ErrorKind::WrongNetwork => "Wrong Bitcoin network",
+            ErrorKind::Other => "Error",
+        }
+    }
+
+    /// What the user can do to fix the failure
+    pub fn remediation(self) -> Option<&'static str> {
+        match self {
+            ErrorKind::ServiceUnauthorized => {
+                Some("Your session expired or was revoked, connect to the Heritage Service again.")
+            }
+            ErrorKind::ServiceUnreachable => Some(
+                "Verify your Internet connection and the Heritage Service URL in the Application Configuration.",
+            ),
+            ErrorKind::NodeUnreachable => Some(
+                "Verify your node is running and its URL and credentials in the Application Configuration.",
+            ),
+            ErrorKind::LedgerLocked => {
+                Some("Unlock your Ledger device and open the Bitcoin application, then retry.")
+            }
+            ErrorKind::LedgerNotConnected => {
+                Some("Plug your Ledger device in and close Ledger Live, which may hold it, then retry.")
+            }
+            ErrorKind::WrongNetwork => Some(
+                "The Bitcoin network of the application, of your device and of your data must match.",
+            ),
+            ErrorKind::Other => None,
+        }
+    }
+
+    /// Anchor of the section of the documentation about the failure
+    pub fn doc_anchor(self) -> Option<&'static str> {
+        match self {
+            ErrorKind::ServiceUnauthorized => Some("service-authentication"),
+            ErrorKind::ServiceUnreachable => Some("service-connectivity"),
+            ErrorKind::NodeUnreachable => Some("blockchain-provider"),
+            ErrorKind::LedgerLocked | ErrorKind::LedgerNotConnected => Some("ledger"),
+            ErrorKind::WrongNetwork => Some("bitcoin-networks"),
+            ErrorKind::Other => None,
+        }
+    }
+
+    /// URL of the documentation about the failure
+    pub fn doc_url(self) -> Option<String> {
+        self.doc_anchor()
+            .map(|anchor| format!("{DOCUMENTATION_URL}/troubleshooting#{anchor}"))
+    }
+
+    /// The view where the failure can be fixed, if any
+    pub fn fix_route(self) -> Option<Route> {
+        match self {
+            ErrorKind::ServiceUnauthorized
+            | ErrorKind::ServiceUnreachable
+            | ErrorKind::NodeUnreachable
+            | ErrorKind::WrongNetwork => Some(Route::AppConfigView {}),
+            ErrorKind::LedgerLocked | ErrorKind::LedgerNotConnected | ErrorKind::Other => None,
+        }
+    }
+}
+
+/// A failure ready to be displayed to the user: what failed, why, and how to fix it
+#[derive(Debug, Clone, PartialEq)]
+pub struct UIError {
+    pub kind: ErrorKind,
+    /// What the application was doing, e.g. "Failed to sync"
+    pub context: CCStr,
+    /// The original error message
+    pub details: CCStr,
+}
+
+impl UIError {
+    pub fn new(context: impl Into<CCStr>, error: impl core::fmt::Display) -> Self {
+        let details = error.to_string();
+        Self {
+            kind: ErrorKind::classify(&details),
+            context: context.into(),
+            details: CCStr::from(details),
+        }
+    }
+}
+
+impl core::fmt::Display for UIError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}: {}", self.context, self.details)
+    }
+}
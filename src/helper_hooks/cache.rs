@@ -0,0 +1,372 @@
+use crate::prelude::*;
+
+use std::{cell::RefCell, collections::HashMap, future::Future, rc::Rc};
+
+use btc_heritage_wallet::btc_heritage::bitcoincore_rpc::jsonrpc::serde_json;
+use futures_util::future::{FutureExt, LocalBoxFuture, Shared, WeakShared};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{state_management::DatabaseCommand, utils::CCStr};
+
+/// Last successful values of an expensive resource, and the requests currently
+/// running to refresh them, indexed by a cache key (e.g. the wallet fingerprint)
+pub(super) type ResourceCache<T> = GlobalSignal<HashMap<CCStr, CacheEntry<T>>>;
+
+pub(super) struct CacheEntry<T: 'static> {
+    value: Option<T>,
+    /// The value comes from a previous run of the application and was not refreshed yet
+    stale: bool,
+    in_flight: Option<WeakShared<LocalBoxFuture<'static, T>>>,
+}
+impl<T> Default for CacheEntry<T> {
+    fn default() -> Self {
+        Self {
+            value: None,
+            stale: false,
+            in_flight: None,
+        }
+    }
+}
+impl<T> CacheEntry<T> {
+    fn set_stale(&mut self, key: &CCStr, stale: bool) {
+        if self.stale == stale {
+            return;
+        }
+        self.stale = stale;
+        count_stale(key, stale);
+    }
+}
+
+/// For each cache key, the number of [ResourceCache] entries and persisted memos
+/// currently serving a value loaded from the disk that was not refreshed yet
+static STALE_CACHE_KEYS: GlobalSignal<HashMap<CCStr, usize>> = Signal::global(HashMap::new);
+
+/// Count one more (`stale`) or one less (`!stale`) value served stale for `key`
+fn count_stale(key: &CCStr, stale: bool) {
+    let mut stale_keys = STALE_CACHE_KEYS.write();
+    let count = stale_keys.entry(key.clone()).or_default();
+    if stale {
+        *count += 1;
+    } else {
+        *count -= 1;
+        if *count == 0 {
+            stale_keys.remove(key);
+        }
+    }
+}
+
+/// Returns true if some of the resources using `key` display the last-known state
+/// loaded from the disk, waiting for a fresh value
+///
+/// Reads the [STALE_CACHE_KEYS] so that the caller refreshes when it changes.
+pub(super) fn cache_key_is_stale(key: &CCStr) -> bool {
+    STALE_CACHE_KEYS.read().contains_key(key)
+}
+
+/// Where the values of a [ResourceCache] are kept between runs of the application
+pub(super) trait CachePersistence<T>: Copy + 'static {
+    async fn load(self, key: &CCStr) -> Option<T>;
+    async fn save(self, key: &CCStr, value: &T);
+}
+
+/// The values are only kept in memory and lost when the application exits
+#[derive(Debug, Clone, Copy)]
+pub(super) struct InMemoryOnly;
+impl<T> CachePersistence<T> for InMemoryOnly {
+    async fn load(self, _key: &CCStr) -> Option<T> {
+        None
+    }
+    async fn save(self, _key: &CCStr, _value: &T) {}
+}
+
+/// The values are also persisted in the database, so that they can be displayed
+/// as soon as the application starts
+#[derive(Clone, Copy)]
+pub(super) struct PersistedInDatabase {
+    name: &'static str,
+    database_service: Coroutine<DatabaseCommand>,
+}
+impl PersistedInDatabase {
+    fn db_key(&self, key: &CCStr) -> String {
+        state_management::resource_cache_db_key(self.name, key)
+    }
+}
+impl<T: Serialize + DeserializeOwned> CachePersistence<T> for PersistedInDatabase {
+    async fn load(self, key: &CCStr) -> Option<T> {
+        let db_key = self.db_key(key);
+        // Values may not be Send (CheapClone), so only their JSON representation
+        // crosses the blocking thread boundary
+        let json = state_management::queued_db_operation(
+            self.database_service,
+            "Load the cached data",
+            DbOperationPriority::Interactive,
+            move |db| db.get_item::<serde_json::Value>(&db_key),
+        )
+        .await
+        .map_err(|e| log::warn!("Could not load the cache {}: {e}", self.name))
+        .ok()
+        .flatten()?;
+        serde_json::from_value(json)
+            .map_err(|e| log::warn!("Could not read the cache {}: {e}", self.name))
+            .ok()
+    }
+
+    async fn save(self, key: &CCStr, value: &T) {
+        let db_key = self.db_key(key);
+        let json = match serde_json::to_value(value) {
+            Ok(json) => json,
+            Err(e) => {
+                log::warn!("Could not serialize the cache {}: {e}", self.name);
+                return;
+            }
+        };
+        if let Err(e) = state_management::queued_db_operation(
+            self.database_service,
+            "Save the cached data",
+            DbOperationPriority::Background,
+            move |mut db| db.update_item(&db_key, &json),
+        )
+        .await
+        {
+            log::warn!("Could not save the cache {}: {e}", self.name);
+        }
+    }
+}
+
+/// Persist a [ResourceCache] in the database, `name` must be unique among caches
+pub(super) fn use_persisted_in_database(name: &'static str) -> PersistedInDatabase {
+    PersistedInDatabase {
+        name,
+        database_service: state_management::use_database_service(),
+    }
+}
+
+/// Value produced by a resource that can be cached
+pub(super) trait Cacheable: Clone + 'static {
+    /// Only successful values are worth caching
+    fn is_success(&self) -> bool;
+}
+impl<T: Clone + 'static> Cacheable for Result<T, CCStr> {
+    fn is_success(&self) -> bool {
+        self.is_ok()
+    }
+}
+/// For resources hiding their errors behind an empty list,
+/// an empty list is not cached so it never replaces a good value
+impl<T: Clone + 'static> Cacheable for Vec<T> {
+    fn is_success(&self) -> bool {
+        !self.is_empty()
+    }
+}
+
+/// A [use_resource] with a stale-while-revalidate cache and request coalescing.
+///
+/// When the hook is created and `cache` holds a value for the key returned by `key`,
+/// this value is returned instantly and the resource immediately re-runs `future`
+/// in the background. As a [Resource] keeps its current value while it re-runs,
+/// consumers display the cached value until the fresh one replaces it.
+///
+/// When a request for the same key is already running, the resource waits for its
+/// result instead of sending a new one. In that case `future` is never polled,
+/// so the reactive dependencies of the resource must be read by `key`, and the
+/// [LoadTrigger] deferring the resource, if any, must be given as `load_trigger`:
+/// it is awaited before joining or sending a request, never inside `future`.
+///
+/// Each successful value of `future` is stored in `cache`. A `key` returning [None]
+/// disables the cache.
+///
+/// With a [PersistedInDatabase] `persistence`, successful values are also saved on
+/// disk. When `cache` has nothing for the key, the value saved by a previous run of
+/// the application is served instead and flagged as stale (see [cache_key_is_stale])
+/// until the fresh value replaces it.
+pub(super) fn use_cached_resource<T, P, K, F>(
+    cache: &'static ResourceCache<T>,
+    persistence: P,
+    load_trigger: Option<LoadTrigger>,
+    mut key: impl FnMut() -> K + 'static,
+    mut future: impl FnMut() -> F + 'static,
+) -> Resource<T>
+where
+    T: Cacheable,
+    P: CachePersistence<T>,
+    K: Future<Output = Option<CCStr>> + 'static,
+    F: Future<Output = T> + 'static,
+{
+    let mut first_run = use_signal(|| true);
+    let mut served_from_cache = use_signal(|| false);
+
+    let mut resource = use_resource(move || {
+        let key = key();
+        let future = future();
+        async move {
+            let first_run = std::mem::take(&mut *first_run.write());
+            let Some(key) = key.await else {
+                return future.await;
+            };
+
+            if first_run {
+                let cached = cache.peek().get(&key).and_then(|entry| entry.value.clone());
+                if let Some(cached) = cached {
+                    log::debug!("use_cached_resource - serving cached value");
+                    served_from_cache.set(true);
+                    return cached;
+                }
+                if let Some(persisted) = persistence.load(&key).await {
+                    log::debug!("use_cached_resource - serving persisted value");
+                    let mut cache = cache.write();
+                    let entry = cache.entry(key.clone()).or_default();
+                    // Another resource may have refreshed the entry in the meantime
+                    if entry.value.is_none() {
+                        entry.value = Some(persisted.clone());
+                        entry.set_stale(&key, true);
+                    }
+                    served_from_cache.set(true);
+                    return entry.value.clone().unwrap_or(persisted);
+                }
+            }
+
+            if let Some(load_trigger) = load_trigger {
+                load_trigger.fired().await;
+            }
+
+            let in_flight = cache
+                .peek()
+                .get(&key)
+                .and_then(|entry| entry.in_flight.as_ref())
+                .and_then(WeakShared::upgrade);
+            let (request, owned) = match in_flight {
+                Some(request) => {
+                    log::debug!("use_cached_resource - joining the request in flight");
+                    (request, false)
+                }
+                None => {
+                    let request: Shared<LocalBoxFuture<'static, T>> = future.boxed_local().shared();
+                    cache.write().entry(key.clone()).or_default().in_flight = request.downgrade();
+                    (request, true)
+                }
+            };
+            let value = request.await;
+
+            let success = value.is_success();
+            {
+                let mut cache = cache.write();
+                let entry = cache.entry(key.clone()).or_default();
+                entry.in_flight = None;
+                if success {
+                    entry.value = Some(value.clone());
+                    entry.set_stale(&key, false);
+                }
+            }
+            // Only the resource that sent the request saves its result
+            if success && owned {
+                persistence.save(&key, &value).await;
+            }
+            value
+        }
+    });
+
+    // Revalidate as soon as the cached value has been served
+    use_effect(move || {
+        if served_from_cache() {
+            log::debug!("use_cached_resource - revalidating");
+            resource.restart();
+        }
+    });
+
+    resource
+}
+
+/// A memo of data derived from the wallet resources, persisted in the database
+///
+/// While `memo` is loading, the value persisted by a previous run of the application
+/// is served instead and the key returned by `key` is flagged as stale (see
+/// [cache_key_is_stale]) until `memo` produces its own value. Each successful value
+/// of `memo` is persisted, so what is kept on the disk is the data the views display
+/// rather than the raw wallet data it is computed from.
+pub(super) fn use_persisted_memo<T, K>(
+    persistence: PersistedInDatabase,
+    mut key: impl FnMut() -> K + 'static,
+    memo: FMemo<T>,
+) -> FMemo<T>
+where
+    T: Clone + PartialEq + Serialize + DeserializeOwned + 'static,
+    K: Future<Output = Option<CCStr>> + 'static,
+{
+    let cache_key = use_resource(move || key());
+    let persisted = use_resource(move || async move {
+        let key = cache_key.cloned().flatten()?;
+        CachePersistence::<T>::load(persistence, &key).await
+    });
+
+    use_effect(move || {
+        if let (Some(Some(key)), Some(Ok(value))) = (cache_key.cloned(), memo.cloned()) {
+            spawn(async move { persistence.save(&key, &value).await });
+        }
+    });
+
+    // Key currently counted in the STALE_CACHE_KEYS, outside of the signals as it
+    // must still be readable when the component is dropped
+    let stale_key = use_hook(|| Rc::new(RefCell::new(None::<CCStr>)));
+    use_effect({
+        let stale_key = stale_key.clone();
+        move || {
+            let serving_persisted =
+                memo.read().is_none() && persisted.read().as_ref().is_some_and(Option::is_some);
+            let new_stale_key = serving_persisted
+                .then(|| cache_key.cloned().flatten())
+                .flatten();
+            let mut stale_key = stale_key.borrow_mut();
+            if *stale_key != new_stale_key {
+                if let Some(ref key) = *stale_key {
+                    count_stale(key, false);
+                }
+                if let Some(ref key) = new_stale_key {
+                    count_stale(key, true);
+                }
+                *stale_key = new_stale_key;
+            }
+        }
+    });
+    use_drop(move || {
+        if let Some(ref key) = *stale_key.borrow() {
+            count_stale(key, false);
+        }
+    });
+
+    use_memo(move || match memo() {
+        None => persisted.cloned().flatten().map(Ok),
+        fresh => fresh,
+    })
+}
+
+/// Update in place the value cached for `key`, if any
+///
+/// Used after a change to a single element of a cached value, so that the resources
+/// created later serve the up-to-date value without requesting it again.
+pub(super) fn update_cached_value<T>(
+    cache: &'static ResourceCache<T>,
+    key: &CCStr,
+    update: impl FnOnce(&mut T),
+) {
+    if let Some(value) = cache
+        .write()
+        .get_mut(key)
+        .and_then(|entry| entry.value.as_mut())
+    {
+        update(value);
+    }
+}
+
+/// Cache key for the resources of the Heritage Service account currently connected
+///
+/// It reads the [SERVICE_AUTH_STATE](state_management::SERVICE_AUTH_STATE) so that
+/// the resources using it refresh when the user logs in or out.
+pub(super) fn service_cache_key() -> Option<CCStr> {
+    match &*state_management::SERVICE_AUTH_STATE.read() {
+        Some(ServiceAuthState::Connected { email, .. }) => Some(CCStr::from(format!(
+            "{}/{email}",
+            state_management::APPLICATION_CONFIG.peek().network,
+        ))),
+        _ => None,
+    }
+}
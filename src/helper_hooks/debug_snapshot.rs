@@ -0,0 +1,67 @@
+use crate::prelude::*;
+
+use std::{collections::BTreeMap, fmt::Debug};
+
+use btc_heritage_wallet::btc_heritage::bitcoincore_rpc::jsonrpc::serde_json;
+
+/// Debug representation of the resources of the views currently displayed,
+/// indexed by the name they were registered with
+static DEBUG_SNAPSHOTS: GlobalSignal<BTreeMap<String, String>> = Signal::global(BTreeMap::new);
+
+/// Register the current value of `resource` in the developer snapshot, as `name`
+///
+/// Does nothing unless the application was started with the `--debug-panel` flag.
+/// The value is removed from the snapshot when the component is dropped.
+/// The whole [Debug] representation is dumped, so resources that may hold secrets
+/// must use [use_debug_snapshot_with] instead.
+pub fn use_debug_snapshot<T: Debug + 'static>(name: String, resource: Resource<T>) {
+    use_debug_snapshot_with(name, resource, |value| format!("{value:#?}"));
+}
+
+/// Register the value of `resource` in the developer snapshot, as `name`, the way
+/// `describe` represents it
+///
+/// Used for the resources that may hold secrets, `describe` only keeping what is safe
+/// to attach to a bug report.
+pub fn use_debug_snapshot_with<T: 'static>(
+    name: String,
+    resource: Resource<T>,
+    describe: fn(&Option<T>) -> String,
+) {
+    let enabled = crate::cli::cli_args().debug_panel;
+    let key = use_hook(move || name);
+    use_effect({
+        let key = key.clone();
+        move || {
+            if enabled {
+                let snapshot = describe(&resource.read());
+                DEBUG_SNAPSHOTS.write().insert(key.clone(), snapshot);
+            }
+        }
+    });
+    use_drop(move || {
+        if enabled {
+            DEBUG_SNAPSHOTS.write().remove(&key);
+        }
+    });
+}
+
+/// Dump the global state of the application and the registered resources as JSON,
+/// to be attached to a bug report
+pub fn debug_snapshot_json() -> String {
+    let onboarding_status = serde_json::to_value(&*state_management::ONBOARDING_STATUS.read())
+        .unwrap_or_else(|e| serde_json::Value::String(e.to_string()));
+    let snapshot = serde_json::json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "timestamp": btc_heritage_wallet::btc_heritage::utils::timestamp_now(),
+        "globals": {
+            "SERVICE_STATUS": format!("{:?}", *state_management::SERVICE_STATUS.read()),
+            "BLOCKCHAIN_PROVIDER_STATUS": format!("{:?}", state_management::BLOCKCHAIN_PROVIDER_STATUS()),
+            "LEDGER_STATUS": format!("{:?}", state_management::LEDGER_STATUS()),
+            "HWI_STATUS": format!("{:?}", *state_management::HWI_STATUS.read()),
+            "ONBOARDING_STATUS": onboarding_status,
+        },
+        "resources": DEBUG_SNAPSHOTS(),
+    });
+    serde_json::to_string_pretty(&snapshot).unwrap_or_else(|e| e.to_string())
+}
@@ -0,0 +1,22 @@
+use crate::prelude::*;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::utils::CCStr;
+
+/// Signal hook whose value is kept as a draft restored if the application crashes
+///
+/// The signal starts with the draft saved by the previous crash under `key`, if any,
+/// else with `init`. The draft is forgotten when the component is dropped.
+pub fn use_signal_with_crash_draft<T>(key: CCStr, init: impl FnOnce() -> T) -> Signal<T>
+where
+    T: Serialize + DeserializeOwned + 'static,
+{
+    let signal = use_signal(|| crate::crash::take_recovered_draft(&key).unwrap_or_else(init));
+
+    let k = key.clone();
+    use_effect(move || crate::crash::save_draft(&k, &*signal.read()));
+    use_drop(move || crate::crash::clear_draft(&key));
+
+    signal
+}
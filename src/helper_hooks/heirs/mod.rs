@@ -1,6 +1,6 @@
 use crate::prelude::*;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use btc_heritage_wallet::{
     btc_heritage::HeirConfig, heritage_service_api_client::Heir as ServiceHeir, Heir as DbHeir,
@@ -8,6 +8,10 @@ use btc_heritage_wallet::{
 
 use crate::utils::{log_error_ccstr, CCStr, CheapClone, EqCheapClone};
 
+use super::cache::{
+    service_cache_key, update_cached_value, use_cached_resource, InMemoryOnly, ResourceCache,
+};
+
 pub fn use_resource_database_heirs() -> Resource<Vec<CheapClone<DbHeir>>> {
     let database_service = state_management::use_database_service();
     use_resource(move || async move {
@@ -35,91 +39,210 @@ pub fn use_async_heir(name: CCStr) -> AsyncSignal<DbHeir> {
     })
 }
 
+static SERVICE_HEIRS_CACHE: ResourceCache<Result<Vec<CheapClone<ServiceHeir>>, CCStr>> =
+    Signal::global(HashMap::new);
+
 pub fn use_resource_service_heirs() -> FResource<Vec<CheapClone<ServiceHeir>>> {
     let service_client_service = state_management::use_service_client_service();
-    use_resource(move || async move {
-        log::debug!("use_resource_service_heirs - start");
-
-        // Read the SERVICE_STATUS so that we refresh when the SERVICE_STATUS is refreshed
-        let _ = *state_management::SERVICE_STATUS.read();
+    use_cached_resource(
+        &SERVICE_HEIRS_CACHE,
+        InMemoryOnly,
+        None,
+        move || async move { service_cache_key() },
+        move || async move {
+            log::debug!("use_resource_service_heirs - start");
 
-        let service_client =
-            state_management::heritage_service_client(service_client_service).await;
+            // Read the SERVICE_STATUS_LAST_REFRESH so that we refresh when the service status is refreshed
+            let _ = *state_management::SERVICE_STATUS_LAST_REFRESH.read();
 
-        let heirs = service_client
-            .list_heirs()
+            let heirs = state_management::scheduled_service_call(
+                service_client_service,
+                Some("list_heirs"),
+                |client| async move { client.list_heirs().await },
+            )
             .await
             .map_err(log_error_ccstr)
             .map(|heirs| heirs.into_iter().map(CheapClone::from).collect());
 
-        log::debug!("use_resource_service_heirs - loaded");
+            log::debug!("use_resource_service_heirs - loaded");
 
-        heirs
+            heirs
+        },
+    )
+}
+
+/// Insert or replace `service_heir` in the service heirs, in place
+///
+/// Avoids listing all the heirs of the service again after a change to a single one,
+/// and the flicker of the views while they reload. The resource and its cache are updated
+/// immediately, the next refresh of the service status lazily reconciles them with the service.
+pub fn upsert_service_heir(
+    mut service_heirs: FResource<Vec<CheapClone<ServiceHeir>>>,
+    service_heir: ServiceHeir,
+) {
+    log::debug!("upsert_service_heir({})", service_heir.id);
+    let service_heir = CheapClone::new(service_heir);
+    let upsert = |heirs: &mut Result<Vec<CheapClone<ServiceHeir>>, CCStr>| {
+        let Ok(heirs) = heirs else {
+            return;
+        };
+        match heirs.iter_mut().find(|h| h.id == service_heir.id) {
+            Some(heir) => *heir = service_heir.clone(),
+            None => heirs.push(service_heir.clone()),
+        }
+    };
+    let updated = match &mut *service_heirs.write() {
+        Some(heirs) if heirs.is_ok() => {
+            upsert(heirs);
+            true
+        }
+        _ => false,
+    };
+    if !updated {
+        // Nothing to update in place, fallback to a full refresh
+        service_heirs.restart();
+    }
+    if let Some(key) = service_cache_key() {
+        update_cached_value(&SERVICE_HEIRS_CACHE, &key, upsert);
+    }
+}
+
+pub fn use_resource_heir_service_links() -> Resource<HashMap<String, String>> {
+    let database_service = state_management::use_database_service();
+    use_resource(move || async move {
+        log::debug!("use_resource_heir_service_links - start");
+        let links = state_management::load_heir_service_links(database_service)
+            .await
+            .unwrap_or_default();
+        log::debug!("use_resource_heir_service_links - loaded");
+        links
     })
 }
 
+/// Heir configurations referenced by the current Heritage Configuration of any wallet
+pub fn use_resource_current_heir_configs() -> Resource<CheapClone<HashSet<HeirConfig>>> {
+    let database_service = state_management::use_database_service();
+    let service_client_service = state_management::use_service_client_service();
+    let blockchain_provider_service = state_management::use_blockchain_provider_service();
+    use_resource(move || async move {
+        log::debug!("use_resource_current_heir_configs - start");
+        let heir_configs = state_management::current_heir_configs(
+            database_service,
+            service_client_service,
+            blockchain_provider_service,
+        )
+        .await;
+        log::debug!("use_resource_current_heir_configs - loaded");
+        CheapClone::new(heir_configs)
+    })
+}
+
+/// Other heirs sharing the heir configuration of a [CompositeHeir]
+///
+/// The composite logic matches local and service heirs by heir configuration,
+/// so any collision must be resolved by the user.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HeirConflicts {
+    /// Names of the other local heirs with the same heir configuration
+    pub local_heir_names: Vec<CCStr>,
+    /// All the service heirs with the same heir configuration, when there are several
+    pub service_heirs: Vec<EqCheapClone<ServiceHeir>>,
+}
+impl HeirConflicts {
+    pub fn is_empty(&self) -> bool {
+        self.local_heir_names.is_empty() && self.service_heirs.is_empty()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct CompositeHeir {
     pub name: CCStr,
     pub heir_config: CheapClone<HeirConfig>,
     pub db_heir: Option<EqCheapClone<DbHeir>>,
     pub service_heir: Option<Option<EqCheapClone<ServiceHeir>>>,
+    pub conflicts: HeirConflicts,
 }
 
 pub fn use_memo_heirs(
     database_heirs: Resource<Vec<CheapClone<DbHeir>>>,
     service_heirs: FResource<Vec<CheapClone<ServiceHeir>>>,
+    heir_service_links: Resource<HashMap<String, String>>,
 ) -> Memo<Vec<CompositeHeir>> {
     use_memo(move || {
         log::debug!("use_memo_heirs - start");
-        let mut heirs = if let Some(ref database_heirs) = *database_heirs.read() {
-            database_heirs
-                .into_iter()
-                .map(|db_heir| {
-                    let name = CCStr::from(&db_heir.name);
-
-                    let heir_config = CheapClone::from(db_heir.heir_config.clone());
-
-                    (
-                        heir_config.clone(),
-                        CompositeHeir {
-                            name,
-                            heir_config,
-                            db_heir: Some(db_heir.clone().into()),
-                            service_heir: None,
-                        },
-                    )
-                })
-                .collect::<HashMap<_, _>>()
-        } else {
-            Default::default()
-        };
+        // Group the local and service heirs by heir configuration
+        let mut db_heirs_by_config = HashMap::<_, Vec<CheapClone<DbHeir>>>::new();
+        if let Some(ref database_heirs) = *database_heirs.read() {
+            for db_heir in database_heirs {
+                db_heirs_by_config
+                    .entry(CheapClone::from(db_heir.heir_config.clone()))
+                    .or_default()
+                    .push(db_heir.clone());
+            }
+        }
+        let service_loaded = matches!(*service_heirs.read(), Some(Ok(_)));
+        let mut service_heirs_by_config = HashMap::<_, Vec<CheapClone<ServiceHeir>>>::new();
         if let Some(Ok(ref service_heirs)) = *service_heirs.read() {
             for service_heir in service_heirs {
-                heirs
+                service_heirs_by_config
                     .entry(CheapClone::from(service_heir.heir_config.clone()))
-                    .and_modify(|composite_heir| {
-                        composite_heir.service_heir = Some(Some(service_heir.clone().into()));
-                    })
-                    .or_insert_with(|| {
-                        let name = CCStr::from(&service_heir.display_name);
-                        let heir_config = CheapClone::from(service_heir.heir_config.clone());
-                        CompositeHeir {
-                            name,
-                            heir_config,
-                            db_heir: None,
-                            service_heir: Some(Some(service_heir.clone().into())),
-                        }
-                    });
+                    .or_default()
+                    .push(service_heir.clone());
             }
-            // Service is loaded, so we need to ensure service_heir file is Some(...)
-            for (_, heir) in heirs.iter_mut() {
-                if heir.service_heir.is_none() {
-                    heir.service_heir = Some(None);
+        }
+        let heir_service_links = heir_service_links.cloned().unwrap_or_default();
+
+        let conflicting_service_heirs =
+            |service_heirs: &[CheapClone<ServiceHeir>]| -> Vec<EqCheapClone<ServiceHeir>> {
+                if service_heirs.len() > 1 {
+                    service_heirs.iter().cloned().map(Into::into).collect()
+                } else {
+                    vec![]
                 }
+            };
+
+        let mut heirs = vec![];
+        for (heir_config, db_heirs) in db_heirs_by_config {
+            let service_heirs = service_heirs_by_config
+                .remove(&heir_config)
+                .unwrap_or_default();
+            for db_heir in db_heirs.iter() {
+                // Prefer the service heir explicitly linked to this local heir
+                let service_heir = heir_service_links
+                    .get(&db_heir.name)
+                    .and_then(|id| service_heirs.iter().find(|sh| &sh.id == id))
+                    .or(service_heirs.first())
+                    .map(|sh| EqCheapClone::from(sh.clone()));
+                heirs.push(CompositeHeir {
+                    name: CCStr::from(&db_heir.name),
+                    heir_config: heir_config.clone(),
+                    db_heir: Some(db_heir.clone().into()),
+                    service_heir: service_loaded.then_some(service_heir),
+                    conflicts: HeirConflicts {
+                        local_heir_names: db_heirs
+                            .iter()
+                            .filter(|other| other.name != db_heir.name)
+                            .map(|other| CCStr::from(&other.name))
+                            .collect(),
+                        service_heirs: conflicting_service_heirs(&service_heirs),
+                    },
+                });
             }
         }
-        let mut heirs = heirs.into_iter().map(|(_, ch)| ch).collect::<Vec<_>>();
+        // Remaining service heirs have no local counterpart
+        for (heir_config, service_heirs) in service_heirs_by_config {
+            let service_heir = &service_heirs[0];
+            heirs.push(CompositeHeir {
+                name: CCStr::from(&service_heir.display_name),
+                heir_config,
+                db_heir: None,
+                service_heir: Some(Some(service_heir.clone().into())),
+                conflicts: HeirConflicts {
+                    local_heir_names: vec![],
+                    service_heirs: conflicting_service_heirs(&service_heirs),
+                },
+            });
+        }
         heirs.sort_by_key(|ch| ch.name.clone());
         log::debug!("use_memo_heirs - loaded");
         heirs
@@ -1,10 +1,10 @@
 use crate::prelude::*;
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use btc_heritage_wallet::{
-    AnyHeritageProvider, BoundFingerprint, DatabaseItem, HeirWallet, Heritage, HeritageProvider,
-    OnlineWallet,
+    btc_heritage::AccountXPubId, AnyHeritageProvider, AnyKeyProvider, BoundFingerprint,
+    DatabaseItem, HeirWallet, Heritage, HeritageProvider, LedgerPolicy, OnlineWallet,
 };
 
 use crate::{
@@ -12,6 +12,10 @@ use crate::{
     utils::{CCStr, CheapClone, EqCheapClone},
 };
 
+use state_management::{FiatCurrency, HeritageAmountDisplay};
+
+use super::cache::{service_cache_key, use_cached_resource, InMemoryOnly, ResourceCache};
+
 pub fn use_resource_heirwallet_names() -> Resource<Vec<CCStr>> {
     let database_service = state_management::use_database_service();
     use_resource(move || async move {
@@ -195,11 +199,13 @@ pub fn use_memo_heritage_provider_status(
             }
             AnyHeritageProvider::Service(service_binding) => (
                 HeritageProviderType::Service,
-                match state_management::SERVICE_STATUS.read().as_ref() {
-                    Some(ss) if ss.can_serve_heritage(service_binding) => {
-                        ExternalDependencyStatus::Available
-                    }
-                    _ => ExternalDependencyStatus::Unavailable,
+                if state_management::SERVICE_SERVICEABILITY
+                    .read()
+                    .can_serve_heritage(service_binding)
+                {
+                    ExternalDependencyStatus::Available
+                } else {
+                    ExternalDependencyStatus::Unavailable
                 },
             ),
             AnyHeritageProvider::LocalWallet(_) => (
@@ -228,6 +234,33 @@ pub fn use_memo_heirwallet_keyprovider_status(
     })
 }
 
+pub fn use_memo_heirwallet_uses_ledger(heirwallet: AsyncSignal<HeirWallet>) -> Memo<bool> {
+    use_memo(move || {
+        log::debug!("use_memo_heirwallet_uses_ledger - start compute");
+        let uses_ledger = heirwallet
+            .lmap(|heirwallet| matches!(heirwallet.key_provider(), AnyKeyProvider::Ledger(_)))
+            .is_some_and(|b| b);
+        log::debug!("use_memo_heirwallet_uses_ledger - finish compute");
+        uses_ledger
+    })
+}
+
+/// Memo hook of the Ledger policies registered for the heir wallet
+pub fn use_memo_heirwallet_ledger_registered_policies(
+    heirwallet: AsyncSignal<HeirWallet>,
+) -> Memo<Option<BTreeMap<AccountXPubId, LedgerPolicy>>> {
+    use_memo(move || {
+        log::debug!("use_memo_heirwallet_ledger_registered_policies - start compute");
+        let ledger_registered_policies = heirwallet
+            .lmap(|heirwallet| {
+                super::wallets::ledger_registered_policies(heirwallet.key_provider())
+            })
+            .flatten();
+        log::debug!("use_memo_heirwallet_ledger_registered_policies - finish compute");
+        ledger_registered_policies
+    })
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum HeritageContext {
     WalletLocal {
@@ -241,23 +274,69 @@ pub enum HeritageContext {
         owner: Option<CCStr>,
     },
 }
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ContextualizedHeritages {
     pub context: HeritageContext,
     pub heritages: Vec<EqCheapClone<btc_heritage_wallet::Heritage>>,
 }
+/// How to display the heritage amounts, with the exchange rate needed for the fiat estimate
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeritageAmountDisplayContext {
+    pub display: HeritageAmountDisplay,
+    pub exchange_rate: Option<(FiatCurrency, f64)>,
+}
+
+pub fn use_resource_heritage_amount_display() -> Resource<HeritageAmountDisplayContext> {
+    let database_service = state_management::use_database_service();
+    use_resource(move || async move {
+        log::debug!("use_resource_heritage_amount_display - start");
+        let display = state_management::load_heritage_amount_display(database_service)
+            .await
+            .unwrap_or_default();
+        // The value at maturity is unknown, the current exchange rate is the best estimate
+        let exchange_rate = if display == HeritageAmountDisplay::FiatAtMaturity {
+            let currency = state_management::load_fiat_currency(database_service)
+                .await
+                .ok()
+                .flatten()
+                .unwrap_or_default();
+            state_management::fetch_btc_exchange_rate(currency)
+                .await
+                .ok()
+                .map(|rate| (currency, rate))
+        } else {
+            None
+        };
+        log::debug!("use_resource_heritage_amount_display - loaded");
+        HeritageAmountDisplayContext {
+            display,
+            exchange_rate,
+        }
+    })
+}
+
+static SERVICE_HERITAGES_CACHE: ResourceCache<
+    Result<HashMap<CCStr, ContextualizedHeritages>, CCStr>,
+> = Signal::global(HashMap::new);
+
 pub fn use_resource_service_heritages() -> FResource<HashMap<CCStr, ContextualizedHeritages>> {
     let service_client_service = state_management::use_service_client_service();
-    use_resource(move || async move {
-        log::debug!("use_resource_service_heritages - start");
+    use_cached_resource(
+        &SERVICE_HERITAGES_CACHE,
+        InMemoryOnly,
+        None,
+        move || async move { service_cache_key() },
+        move || async move {
+            log::debug!("use_resource_service_heritages - start");
 
-        // Read the SERVICE_STATUS so that we refresh when the SERVICE_STATUS is refreshed
-        let _ = *state_management::SERVICE_STATUS.read();
+            // Read the SERVICE_STATUS_LAST_REFRESH so that we refresh when the service status is refreshed
+            let _ = *state_management::SERVICE_STATUS_LAST_REFRESH.read();
 
-        let heritage_service =
-            state_management::heritage_service_client(service_client_service).await;
-        let service_heritages = heritage_service
-            .list_heritages()
+            let service_heritages = state_management::scheduled_service_call(
+                service_client_service,
+                Some("list_heritages"),
+                |client| async move { client.list_heritages().await },
+            )
             .await
             .map(|heritages| {
                 heritages
@@ -297,9 +376,10 @@ pub fn use_resource_service_heritages() -> FResource<HashMap<CCStr, Contextualiz
                 log::error!("Error querying heritages from service: {e}");
                 CCStr::from(e.to_string())
             });
-        log::debug!("use_resource_service_heritages - loaded");
-        service_heritages
-    })
+            log::debug!("use_resource_service_heritages - loaded");
+            service_heritages
+        },
+    )
 }
 
 pub fn use_memo_service_only_heritages(
@@ -0,0 +1,33 @@
+use dioxus::prelude::*;
+
+/// Allows to defer the loading of resources until they are actually needed
+///
+/// Resources await [LoadTrigger::fired] before doing anything and stay in the
+/// loading state until [LoadTrigger::fire] is called. Once fired, a trigger stays fired.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoadTrigger(Signal<bool>);
+impl LoadTrigger {
+    pub fn is_fired(&self) -> bool {
+        *self.0.peek()
+    }
+
+    pub fn fire(mut self) {
+        if !self.is_fired() {
+            self.0.set(true);
+        }
+    }
+
+    /// Completes once the trigger has been fired
+    ///
+    /// When awaited inside a [Resource], the resource subscribes to the trigger
+    /// and re-runs when it is fired.
+    pub async fn fired(self) {
+        if !(self.0)() {
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
+pub fn use_load_trigger() -> LoadTrigger {
+    LoadTrigger(use_signal(|| false))
+}
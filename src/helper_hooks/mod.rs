@@ -1,48 +1,72 @@
 mod async_init;
+mod cache;
+mod debug_snapshot;
+mod drafts;
 mod heirs;
 mod heirwallets;
+mod load_trigger;
+mod optimistic;
 mod utils;
 mod wallets;
 
 pub mod prelude {
     pub use super::async_init::AsyncSignal;
-    pub use super::heirs::CompositeHeir;
-    pub use super::heirwallets::{ContextualizedHeritages, HeritageContext};
-    pub use super::utils::{FMemo, FResource, LResult, LoadableFaillibleMapper, LoadableMapper};
+    pub use super::debug_snapshot::debug_snapshot_json;
+    pub use super::heirs::{CompositeHeir, HeirConflicts};
+    pub use super::heirwallets::{
+        ContextualizedHeritages, HeritageAmountDisplayContext, HeritageContext,
+    };
+    pub use super::load_trigger::LoadTrigger;
+    pub use super::optimistic::optimistic_update;
+    pub use super::utils::{
+        ContentHashed, FMemo, FResource, LResult, LoadableFaillibleMapper, LoadableMapper,
+    };
     pub use super::wallets::{
-        AccountXPubOrigin, ExpirationStatus, HeritageConfigWithInfo, SimpleUtxo,
-        TransactionHistoryItem, TransactionHistoryItemOwnedIO, TransactionStats, TxIO, UtxoStats,
-        UtxoWithInfo, WalletAddressWithInfo,
+        heir_spendable_ts, heritage_config_expiration_ts, AccountXPubOrigin, ExpirationStatus,
+        HeritageConfigWithInfo, SimpleUtxo, TransactionHistoryItem, TransactionHistoryItemOwnedIO,
+        TransactionStats, TxIO, UtxoStats, UtxoWithInfo, WalletAddressWithInfo,
     };
     pub mod helper_hooks {
         pub use super::super::async_init::use_async_init;
+        pub use super::super::debug_snapshot::{use_debug_snapshot, use_debug_snapshot_with};
+        pub use super::super::drafts::use_signal_with_crash_draft;
         pub use super::super::heirs::{
-            use_async_heir, use_memo_heirs, use_resource_database_heirs, use_resource_service_heirs,
+            upsert_service_heir, use_async_heir, use_memo_heirs, use_resource_current_heir_configs,
+            use_resource_database_heirs, use_resource_heir_service_links,
+            use_resource_service_heirs,
         };
         pub use super::super::heirwallets::{
             use_async_heirwallet, use_memo_heirwallet_contextualized_heritages,
             use_memo_heirwallet_fingerprint, use_memo_heirwallet_keyprovider_status,
+            use_memo_heirwallet_ledger_registered_policies, use_memo_heirwallet_uses_ledger,
             use_memo_heritage_provider_status, use_memo_service_only_heritages,
             use_resource_heirwallet_heritages, use_resource_heirwallet_local_lastsync,
-            use_resource_heirwallet_names, use_resource_service_heritages,
+            use_resource_heirwallet_names, use_resource_heritage_amount_display,
+            use_resource_service_heritages,
         };
-        pub use super::super::utils::use_memo_resource;
+        pub use super::super::load_trigger::use_load_trigger;
+        pub use super::super::utils::{use_memo_resource, use_memo_resource_content};
         pub use super::super::wallets::{
             use_async_wallet, use_memo_addresses_set, use_memo_addresses_with_info,
-            use_memo_balance_by_heritage_config, use_memo_fingerprint,
-            use_memo_heritage_configs_with_info,
+            use_memo_balance_by_heritage_config, use_memo_current_heritage_config_expiration,
+            use_memo_fingerprint, use_memo_heritage_configs_with_info,
             use_memo_heritage_configs_with_info_indexed_by_heritage_config,
             use_memo_heritage_configs_with_info_indexed_by_origin_info,
             use_memo_ledger_registered_policies, use_memo_ledger_unregistered_policies,
-            use_memo_ready_to_use_address, use_memo_transaction_history_items,
-            use_memo_tx_stats_by_address, use_memo_utxo_stats_by_address, use_memo_utxo_with_info,
+            use_memo_persisted_addresses_with_info, use_memo_persisted_heritage_configs_with_info,
+            use_memo_persisted_transaction_history_items, use_memo_ready_to_use_address,
+            use_memo_transaction_history_items, use_memo_tx_stats_by_address,
+            use_memo_utxo_stats_by_address, use_memo_utxo_with_info,
             use_memo_wallet_keyprovider_status, use_memo_wallet_online_status,
-            use_memo_wallet_uses_ledger, use_resource_service_only_wallets,
+            use_memo_wallet_shows_stale_data, use_memo_wallet_uses_ledger,
+            use_resource_historical_exchange_rates, use_resource_service_only_wallets,
             use_resource_service_wallets, use_resource_wallet_account_xpubs,
-            use_resource_wallet_addresses, use_resource_wallet_descriptor_backup,
-            use_resource_wallet_names, use_resource_wallet_status,
+            use_resource_wallet_addresses, use_resource_wallet_backup_freshness,
+            use_resource_wallet_descriptor_backup, use_resource_wallet_names,
+            use_resource_wallet_notes, use_resource_wallet_reserved_addresses,
+            use_resource_wallet_settings, use_resource_wallet_status,
             use_resource_wallet_subwallet_configs, use_resource_wallet_transactions,
-            use_resource_wallet_utxos,
+            use_resource_wallet_utxos, use_wallet_prefetch,
         };
     }
 }
@@ -0,0 +1,22 @@
+use crate::prelude::*;
+
+use std::future::Future;
+
+/// Optimistically replace the value of `resource` by `new_value`, then run `mutation`
+///
+/// The UI reflects the change immediately instead of waiting for the database round-trip
+/// and a full restart of the resource. If `mutation` fails, the previous value of the
+/// resource is restored and the error is returned.
+pub async fn optimistic_update<T: 'static, E>(
+    mut resource: Resource<T>,
+    new_value: T,
+    mutation: impl Future<Output = Result<(), E>>,
+) -> Result<(), E> {
+    let previous = resource.write().replace(new_value);
+    let result = mutation.await;
+    if result.is_err() {
+        log::warn!("Optimistic update failed, rolling back");
+        *resource.write() = previous;
+    }
+    result
+}
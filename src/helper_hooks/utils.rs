@@ -1,11 +1,20 @@
 use crate::prelude::*;
 
-use btc_heritage_wallet::{AnyKeyProvider, BoundFingerprint};
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    ops::Deref,
+};
+
+use btc_heritage_wallet::{
+    btc_heritage::{bdk_types::BlockTime, heritage_wallet::WalletAddress},
+    heritage_service_api_client::{HeritageUtxo, SubwalletConfigMeta, TransactionSummary},
+    AnyKeyProvider, BoundFingerprint,
+};
 
 use crate::{
     components::badge::{ExternalDependencyStatus, KeyProviderType},
     state_management::prelude::*,
-    utils::CCStr,
+    utils::{CCStr, CheapClone},
 };
 
 pub(super) fn keyprovider_status(
@@ -42,10 +51,130 @@ pub fn use_memo_resource<T: Clone + PartialEq>(r: Resource<T>) -> Memo<Option<T>
     use_memo(move || r())
 }
 
+/// A value along with a hash of its content, two [ContentHashed] are equal if their hashes are
+#[derive(Debug, Clone)]
+pub struct ContentHashed<T> {
+    hash: u64,
+    value: T,
+}
+impl<T: ContentDigest> ContentHashed<T> {
+    fn new(value: T) -> Self {
+        let mut hasher = DefaultHasher::new();
+        value.digest(&mut hasher);
+        Self {
+            hash: hasher.finish(),
+            value,
+        }
+    }
+}
+
+/// Cheap identification of the content of a wallet resource
+///
+/// It only hashes what can change for a given element (e.g. the txid and the
+/// confirmation of a transaction), so that it stays fast enough to be computed
+/// on the UI thread each time a resource completes.
+pub trait ContentDigest {
+    fn digest(&self, hasher: &mut DefaultHasher);
+}
+impl<T: ContentDigest> ContentDigest for [T] {
+    fn digest(&self, hasher: &mut DefaultHasher) {
+        self.len().hash(hasher);
+        for element in self {
+            element.digest(hasher);
+        }
+    }
+}
+impl<T: ContentDigest + ?Sized> ContentDigest for CheapClone<T> {
+    fn digest(&self, hasher: &mut DefaultHasher) {
+        (**self).digest(hasher);
+    }
+}
+fn digest_block_time(block_time: Option<&BlockTime>, hasher: &mut DefaultHasher) {
+    block_time.map(|bt| (bt.height, bt.timestamp)).hash(hasher);
+}
+impl ContentDigest for TransactionSummary {
+    fn digest(&self, hasher: &mut DefaultHasher) {
+        self.txid.hash(hasher);
+        digest_block_time(self.confirmation_time.as_ref(), hasher);
+    }
+}
+impl ContentDigest for HeritageUtxo {
+    fn digest(&self, hasher: &mut DefaultHasher) {
+        self.outpoint.hash(hasher);
+        digest_block_time(self.confirmation_time.as_ref(), hasher);
+    }
+}
+impl ContentDigest for WalletAddress {
+    fn digest(&self, hasher: &mut DefaultHasher) {
+        self.address().hash(hasher);
+    }
+}
+impl ContentDigest for SubwalletConfigMeta {
+    fn digest(&self, hasher: &mut DefaultHasher) {
+        self.heritage_config.hash(hasher);
+        self.firstuse_ts.hash(hasher);
+    }
+}
+impl<T> PartialEq for ContentHashed<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash
+    }
+}
+impl<T> Deref for ContentHashed<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+/// Memo hook following a [FResource], only notifying its subscribers when the content
+/// of the resource actually changes
+///
+/// A resource notifies its subscribers each time it completes, even when it produces
+/// the same value again (e.g. when a cached value is revalidated). The heavy memos
+/// depending on wallet data read the resources through this hook, so they skip their
+/// computation unless their inputs changed: the cheap [ContentDigest] is computed once
+/// per completion of the resource, and comparing it is instant.
+pub fn use_memo_resource_content<T: Clone + ContentDigest + 'static>(
+    r: FResource<T>,
+) -> FMemo<ContentHashed<T>> {
+    use_memo(move || {
+        r.read()
+            .as_ref()
+            .map(|result| result.clone().map(ContentHashed::new))
+    })
+}
+
 pub type LResult<T> = Option<Result<T, CCStr>>;
+
 pub type FResource<T> = Resource<Result<T, CCStr>>;
 pub type FMemo<T> = Memo<Option<Result<T, CCStr>>>;
 
+/// Serialize a loadable [Amount](btc_heritage_wallet::bitcoin::Amount) as satoshis,
+/// for the derived data persisted in the database
+pub(super) mod lresult_amount_as_sat {
+    use btc_heritage_wallet::bitcoin::Amount;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::LResult;
+
+    pub fn serialize<S: Serializer>(
+        value: &LResult<Amount>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value
+            .as_ref()
+            .map(|result| result.as_ref().map(|amount| amount.to_sat()))
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<LResult<Amount>, D::Error> {
+        Ok(LResult::<u64>::deserialize(deserializer)?.map(|result| result.map(Amount::from_sat)))
+    }
+}
+
 pub trait LoadableMapper<T> {
     fn lmap<R, F: FnOnce(&T) -> R>(&self, f: F) -> Option<R>;
 }
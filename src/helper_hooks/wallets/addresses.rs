@@ -8,66 +8,97 @@ use btc_heritage_wallet::{
     DatabaseItem, OnlineWallet, Wallet,
 };
 
+use serde::{Deserialize, Serialize};
+
 use crate::utils::{CCStr, CheapClone};
 
+use super::{
+    use_cached_resource, use_persisted_in_database, use_persisted_memo, InMemoryOnly, ResourceCache,
+};
+
+static WALLET_ADDRESSES_CACHE: ResourceCache<Result<CheapClone<[WalletAddress]>, CCStr>> =
+    Signal::global(HashMap::new);
+
 /// Resource hook for retrieving all addresses associated with a wallet
 ///
 /// # Examples
 ///
 /// ```
 /// let wallet = use_resource_wallet("my_wallet".into());
-/// let addresses = use_resource_wallet_addresses(wallet);
+/// let load_trigger = use_load_trigger();
+/// let addresses = use_resource_wallet_addresses(wallet, load_trigger);
 /// ```
 pub fn use_resource_wallet_addresses(
     wallet: AsyncSignal<Wallet>,
+    load_trigger: LoadTrigger,
 ) -> FResource<CheapClone<[WalletAddress]>> {
-    use_resource(move || async move {
-        log::debug!("use_resource_wallet_addresses - start");
-
-        super::subscribe_service_status_if_service_wallet(&wallet);
-
-        let wallet_addresses = wallet
-            .with(async |wallet| {
-                let wallet_name = wallet.name().to_owned();
-                wallet
-                    .list_addresses()
-                    .await
-                    .map_err(|e| {
-                        let error = format!(
-                            "Error retrieving the wallet addresses of wallet {}: {e}",
-                            wallet_name
-                        );
-                        log::error!("{error}");
-                        CCStr::from(error)
-                    })
-                    .map(Into::into)
-            })
-            .await;
-        log::debug!("use_resource_wallet_addresses - loaded");
-
-        wallet_addresses
-    })
+    use_cached_resource(
+        &WALLET_ADDRESSES_CACHE,
+        InMemoryOnly,
+        Some(load_trigger),
+        move || super::wallet_cache_key(wallet),
+        move || async move {
+            log::debug!("use_resource_wallet_addresses - start");
+            super::subscribe_service_status_if_service_wallet(&wallet);
+
+            let wallet_addresses = wallet
+                .with(async |wallet| {
+                    let wallet_name = wallet.name().to_owned();
+                    wallet
+                        .list_addresses()
+                        .await
+                        .map_err(|e| {
+                            let error = format!(
+                                "Error retrieving the wallet addresses of wallet {}: {e}",
+                                wallet_name
+                            );
+                            log::error!("{error}");
+                            CCStr::from(error)
+                        })
+                        .map(Into::into)
+                })
+                .await;
+            log::debug!("use_resource_wallet_addresses - loaded");
+
+            wallet_addresses
+        },
+    )
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct WalletAddressWithInfo {
     pub wallet_address: CheapClone<WalletAddress>,
     pub heritage_config_infos: LResult<HeritageConfigWithInfo>,
     pub tx_stats: LResult<CheapClone<[TransactionStats]>>,
     pub utxo_stats: LResult<UtxoStats>,
+    /// Purpose of the address if it is reserved, it must not be handed out then
+    pub reserved_purpose: LResult<Option<CCStr>>,
 }
 
 pub fn use_memo_addresses_with_info(
-    wallet_addresses: FResource<CheapClone<[WalletAddress]>>,
+    wallet_addresses: FMemo<ContentHashed<CheapClone<[WalletAddress]>>>,
     heritage_configs_with_info_indexed_by_origin_info: FMemo<
         HashMap<AccountXPubOrigin, HeritageConfigWithInfo>,
     >,
     tx_stats_by_address: FMemo<HashMap<Address, CheapClone<[TransactionStats]>>>,
     utxo_stats_by_address: FMemo<HashMap<Address, UtxoStats>>,
+    reserved_addresses: FResource<Vec<state_management::ReservedAddress>>,
 ) -> FMemo<CheapClone<[WalletAddressWithInfo]>> {
     use_memo(move || {
         log::debug!("use_memo_addresses_with_info - start compute");
 
+        let reserved_purpose_by_address = reserved_addresses.lrmap(|reserved_addresses| {
+            reserved_addresses
+                .iter()
+                .map(|reserved_address| {
+                    (
+                        reserved_address.address.clone(),
+                        CCStr::from(reserved_address.purpose.as_str()),
+                    )
+                })
+                .collect::<HashMap<_, _>>()
+        });
+
         let heritage_configs_with_info_indexed_by_origin_info =
             &*heritage_configs_with_info_indexed_by_origin_info.read();
         let tx_stats_by_address = &*tx_stats_by_address.read();
@@ -102,11 +133,18 @@ pub fn use_memo_addresses_with_info(
                             .cloned()
                             .unwrap_or_default()
                     });
+                    let reserved_purpose =
+                        reserved_purpose_by_address.lrmap(|reserved_purpose_by_address| {
+                            reserved_purpose_by_address
+                                .get(&address.to_string())
+                                .cloned()
+                        });
                     WalletAddressWithInfo {
                         wallet_address,
                         heritage_config_infos,
                         tx_stats,
                         utxo_stats,
+                        reserved_purpose,
                     }
                 })
                 .collect()
@@ -116,6 +154,19 @@ pub fn use_memo_addresses_with_info(
     })
 }
 
+/// Memo hook serving the addresses with their info persisted by a previous run of
+/// the application until they are computed again
+pub fn use_memo_persisted_addresses_with_info(
+    wallet: AsyncSignal<Wallet>,
+    addresses_with_info: FMemo<CheapClone<[WalletAddressWithInfo]>>,
+) -> FMemo<CheapClone<[WalletAddressWithInfo]>> {
+    use_persisted_memo(
+        use_persisted_in_database(state_management::WALLET_ADDRESSES_WITH_INFO_CACHE),
+        move || super::wallet_cache_key(wallet),
+        addresses_with_info,
+    )
+}
+
 pub fn use_memo_ready_to_use_address(
     addresses_with_info: FMemo<CheapClone<[WalletAddressWithInfo]>>,
 ) -> Memo<Option<Option<CheapClone<WalletAddress>>>> {
@@ -144,7 +195,12 @@ pub fn use_memo_ready_to_use_address(
                         }
                         _ => false,
                     };
-                    (address_is_unused && address_is_current && address_is_not_change)
+                    let address_is_not_reserved =
+                        matches!(wallet_address_with_info.reserved_purpose, Some(Ok(None)));
+                    (address_is_unused
+                        && address_is_current
+                        && address_is_not_change
+                        && address_is_not_reserved)
                         .then(|| wallet_address_with_info.wallet_address.clone())
                 })
                 .rev()
@@ -156,7 +212,7 @@ pub fn use_memo_ready_to_use_address(
 }
 
 pub fn use_memo_addresses_set(
-    wallet_addresses: FResource<CheapClone<[WalletAddress]>>,
+    wallet_addresses: FMemo<ContentHashed<CheapClone<[WalletAddress]>>>,
 ) -> FMemo<HashSet<Address>> {
     use_memo(move || {
         log::debug!("use_memo_addresses_set - start compute");
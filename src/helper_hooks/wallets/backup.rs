@@ -7,9 +7,11 @@ use crate::utils::log_error_ccstr;
 /// Resource hook for retrieving wallet descriptor backups
 pub fn use_resource_wallet_descriptor_backup(
     wallet: AsyncSignal<Wallet>,
+    load_trigger: LoadTrigger,
 ) -> FResource<HeritageWalletBackup> {
     use_resource(move || async move {
         log::debug!("use_resource_wallet_descriptor_backup - start");
+        load_trigger.fired().await;
 
         let backup = wallet
             .with(async |wallet| wallet.backup_descriptors().await.map_err(log_error_ccstr))
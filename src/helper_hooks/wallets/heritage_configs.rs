@@ -5,19 +5,26 @@ use std::collections::HashMap;
 use btc_heritage_wallet::{
     bitcoin::{bip32::DerivationPath, Amount},
     btc_heritage::{
-        heritage_config::HeritageExplorerTrait, utils::timestamp_now, AccountXPub, HeritageConfig,
+        heritage_config::HeritageExplorerTrait, utils::timestamp_now, AccountXPub, HeirConfig,
+        HeritageConfig,
     },
     heritage_service_api_client::{Fingerprint, SubwalletConfigMeta},
     DatabaseItem, OnlineWallet, Wallet,
 };
 
+use serde::{Deserialize, Serialize};
+
 use crate::utils::{CCStr, CheapClone};
 
+use super::{use_persisted_in_database, use_persisted_memo};
+
 pub fn use_resource_wallet_subwallet_configs(
     wallet: AsyncSignal<Wallet>,
+    load_trigger: LoadTrigger,
 ) -> FResource<CheapClone<[SubwalletConfigMeta]>> {
     use_resource(move || async move {
         log::debug!("use_resource_wallet_subwallet_configs - start");
+        load_trigger.fired().await;
 
         super::subscribe_service_status_if_service_wallet(&wallet);
 
@@ -43,7 +50,7 @@ pub fn use_resource_wallet_subwallet_configs(
     })
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ExpirationStatus {
     Current,
     Outdated,
@@ -54,20 +61,51 @@ impl ExpirationStatus {
     // SOON = 1 month
     // 30 days x 24 hours x 60 mins x 60 secs
     pub const SOON: u64 = 30 * 24 * 60 * 60;
+
+    fn new(expiration_ts: Option<u64>, is_newest: bool, now: u64) -> Self {
+        if expiration_ts.is_some_and(|ts| ts < now) {
+            ExpirationStatus::Expired
+        } else if expiration_ts.is_some_and(|ts| ts < now + ExpirationStatus::SOON) {
+            ExpirationStatus::ExpireSoon
+        } else if is_newest {
+            ExpirationStatus::Current
+        } else {
+            ExpirationStatus::Outdated
+        }
+    }
+}
+
+/// When the heir of `heir_config` can spend the coins of a Heritage Configuration
+pub fn heir_spendable_ts(
+    heritage_config: &HeritageConfig,
+    heir_config: &HeirConfig,
+) -> Option<u64> {
+    heritage_config
+        .get_heritage_explorer(heir_config)
+        .and_then(|he| he.get_spend_conditions().get_spendable_timestamp())
+}
+
+/// Expiration of a Heritage Configuration: when its first heir can spend
+pub fn heritage_config_expiration_ts(heritage_config: &HeritageConfig) -> Option<u64> {
+    heritage_config
+        .iter_heir_configs()
+        .next()
+        .and_then(|hc| heir_spendable_ts(heritage_config, hc))
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct HeritageConfigWithInfo {
     pub account_xpub: CheapClone<AccountXPub>,
     pub heritage_config: CheapClone<HeritageConfig>,
     pub firstuse_ts: Option<u64>,
     pub expiration_ts: Option<u64>,
     pub expiration_status: ExpirationStatus,
+    #[serde(with = "crate::helper_hooks::utils::lresult_amount_as_sat")]
     pub balance: LResult<Amount>,
 }
 
 pub fn use_memo_heritage_configs_with_info(
-    wallet_subwallet_configs: FResource<CheapClone<[SubwalletConfigMeta]>>,
+    wallet_subwallet_configs: FMemo<ContentHashed<CheapClone<[SubwalletConfigMeta]>>>,
     balance_by_heritage_config: FMemo<HashMap<HeritageConfig, Amount>>,
 ) -> FMemo<CheapClone<[HeritageConfigWithInfo]>> {
     let memo_balances = use_memo(move || {
@@ -98,21 +136,7 @@ pub fn use_memo_heritage_configs_with_info(
             .lrmap_ok(|wallet_subwallet_configs| {
                 wallet_subwallet_configs
                     .iter()
-                    .map(|swcm| {
-                        let heritage_config = &swcm.heritage_config;
-                        heritage_config
-                            .iter_heir_configs()
-                            .take(1)
-                            .map(|hc| {
-                                heritage_config
-                                    .get_heritage_explorer(hc)
-                                    .expect("cannot be None as we are iterating heir_configs")
-                                    .get_spend_conditions()
-                                    .get_spendable_timestamp()
-                                    .expect("always present for heirs")
-                            })
-                            .next()
-                    })
+                    .map(|swcm| heritage_config_expiration_ts(&swcm.heritage_config))
                     .collect::<Vec<_>>()
             })
             .unwrap_or_default();
@@ -138,16 +162,8 @@ pub fn use_memo_heritage_configs_with_info(
                         let heritage_config = CheapClone::new(swcm.heritage_config.clone());
                         let firstuse_ts = swcm.firstuse_ts;
                         let is_newest = idx == 0;
-                        let expiration_status = if expiration_ts.is_some_and(|ts| ts < now) {
-                            ExpirationStatus::Expired
-                        } else if expiration_ts.is_some_and(|ts| ts < now + ExpirationStatus::SOON)
-                        {
-                            ExpirationStatus::ExpireSoon
-                        } else if is_newest {
-                            ExpirationStatus::Current
-                        } else {
-                            ExpirationStatus::Outdated
-                        };
+                        let expiration_status =
+                            ExpirationStatus::new(expiration_ts, is_newest, now);
                         HeritageConfigWithInfo {
                             account_xpub,
                             heritage_config,
@@ -164,6 +180,41 @@ pub fn use_memo_heritage_configs_with_info(
     })
 }
 
+/// Memo hook serving the Heritage Configurations and their balances persisted by
+/// a previous run of the application until they are computed again
+pub fn use_memo_persisted_heritage_configs_with_info(
+    wallet: AsyncSignal<Wallet>,
+    heritage_configs_with_info: FMemo<CheapClone<[HeritageConfigWithInfo]>>,
+) -> FMemo<CheapClone<[HeritageConfigWithInfo]>> {
+    use_persisted_memo(
+        use_persisted_in_database(state_management::WALLET_HERITAGE_CONFIGS_CACHE),
+        move || super::wallet_cache_key(wallet),
+        heritage_configs_with_info,
+    )
+}
+
+/// Memo hook with the expiration of the current Heritage Configuration of a wallet
+///
+/// [None] while loading, `Some(None)` if the wallet has no Heritage Configuration
+/// or if they cannot be retrieved.
+pub fn use_memo_current_heritage_config_expiration(
+    wallet_subwallet_configs: FResource<CheapClone<[SubwalletConfigMeta]>>,
+) -> Memo<Option<Option<(ExpirationStatus, u64)>>> {
+    use_memo(move || {
+        wallet_subwallet_configs
+            .lrmap(|wallet_subwallet_configs| {
+                // The first subwallet config is the current one
+                let expiration_ts = heritage_config_expiration_ts(
+                    &wallet_subwallet_configs.first()?.heritage_config,
+                )?;
+                let expiration_status =
+                    ExpirationStatus::new(Some(expiration_ts), true, timestamp_now());
+                Some((expiration_status, expiration_ts))
+            })
+            .map(|result| result.ok().flatten())
+    })
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AccountXPubOrigin {
     fingerprint: Fingerprint,
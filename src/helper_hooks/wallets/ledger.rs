@@ -4,32 +4,92 @@ use std::collections::BTreeMap;
 
 use btc_heritage_wallet::{
     btc_heritage::{AccountXPubId, HeritageWalletBackup},
-    AnyKeyProvider, LedgerPolicy, Wallet,
+    AnyKeyProvider, BoundFingerprint, LedgerPolicy, Wallet,
 };
 
-use crate::utils::log_error;
+use crate::utils::{log_error, CCStr};
 
+pub(crate) fn ledger_registered_policies(
+    key_provider: &AnyKeyProvider,
+) -> Option<BTreeMap<AccountXPubId, LedgerPolicy>> {
+    if let AnyKeyProvider::Ledger(ledger_key) = key_provider {
+        Some(
+            ledger_key
+                .list_registered_policies()
+                .into_iter()
+                .map(|(index, policy, _, _)| (index, policy))
+                .collect(),
+        )
+    } else {
+        None
+    }
+}
+
+/// Memo hook of the Ledger policies registered for the wallet
+///
+/// They are cached in the database along with the fingerprint of the device, so the
+/// cached policies are served while the wallet is loading and the cache is refreshed
+/// once it is loaded.
 pub fn use_memo_ledger_registered_policies(
     wallet: AsyncSignal<Wallet>,
+    wallet_name: CCStr,
 ) -> Memo<Option<BTreeMap<AccountXPubId, LedgerPolicy>>> {
+    let database_service = state_management::use_database_service();
+
+    let wn = wallet_name.clone();
+    let ledger_policies_cache = use_resource(move || {
+        let wallet_name = wn.clone();
+        async move {
+            state_management::load_ledger_policies_cache(database_service, wallet_name)
+                .await
+                .ok()
+                .flatten()
+        }
+    });
+
+    use_effect(move || {
+        let Some(Some(policies)) =
+            wallet.lmap(|wallet| ledger_registered_policies(wallet.key_provider()))
+        else {
+            return;
+        };
+        let Some(fingerprint) = wallet.lmap(|wallet| wallet.fingerprint().ok()).flatten() else {
+            return;
+        };
+        let up_to_date = matches!(
+            &*ledger_policies_cache.peek(),
+            Some(Some(cache)) if cache.fingerprint == fingerprint && cache.policies == policies
+        );
+        if up_to_date {
+            return;
+        }
+        let wallet_name = wallet_name.clone();
+        spawn(async move {
+            let _ = state_management::save_ledger_policies_cache(
+                database_service,
+                wallet_name,
+                state_management::LedgerPoliciesCache {
+                    fingerprint,
+                    policies,
+                },
+            )
+            .await;
+        });
+    });
+
     use_memo(move || {
         log::debug!("use_memo_ledger_registered_policies - start compute");
 
-        let ledger_registered_policies = wallet
-            .lmap(|wallet| {
-                if let AnyKeyProvider::Ledger(ledger_key) = wallet.key_provider() {
-                    Some(
-                        ledger_key
-                            .list_registered_policies()
-                            .into_iter()
-                            .map(|(index, policy, _, _)| (index, policy))
-                            .collect(),
-                    )
-                } else {
-                    None
-                }
-            })
-            .flatten();
+        let ledger_registered_policies =
+            match wallet.lmap(|wallet| ledger_registered_policies(wallet.key_provider())) {
+                Some(ledger_registered_policies) => ledger_registered_policies,
+                // The wallet is still loading, warm up with the cached policies
+                None => ledger_policies_cache
+                    .read()
+                    .as_ref()
+                    .and_then(|cache| cache.as_ref())
+                    .map(|cache| cache.policies.clone()),
+            };
 
         log::debug!("use_memo_ledger_registered_policies - finish compute");
         ledger_registered_policies
@@ -18,24 +18,91 @@ pub use status::*;
 pub use transactions::*;
 pub use utxos::*;
 
-use std::collections::HashSet;
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
 
 use btc_heritage_wallet::{
-    heritage_service_api_client::HeritageWalletMeta, AnyOnlineWallet, Wallet,
+    heritage_service_api_client::HeritageWalletMeta, AnyOnlineWallet, BoundFingerprint, Wallet,
 };
 
 use crate::utils::{CCStr, CheapClone};
 
 use super::{
     async_init::{use_async_init, AsyncSignal},
+    cache::{
+        cache_key_is_stale, service_cache_key, use_cached_resource, use_persisted_in_database,
+        use_persisted_memo, InMemoryOnly, ResourceCache,
+    },
     utils::LoadableMapper,
 };
 
+/// Cache key for the resources of a wallet, [None] if it has no fingerprint yet
+///
+/// It reads the same reactive dependencies as the resources querying the online wallet.
+async fn wallet_cache_key(wallet: AsyncSignal<Wallet>) -> Option<CCStr> {
+    subscribe_service_status_if_service_wallet(&wallet);
+    let network = state_management::APPLICATION_CONFIG.peek().network;
+    wallet
+        .with(async |wallet| {
+            wallet
+                .fingerprint()
+                .ok()
+                .map(|fg| state_management::wallet_resource_cache_key(network, fg, wallet.name()))
+        })
+        .await
+}
+
+/// Memo hook telling whether the wallet resources display the last-known state
+/// persisted by a previous run of the application, waiting for fresh data
+pub fn use_memo_wallet_shows_stale_data(wallet: AsyncSignal<Wallet>) -> Memo<bool> {
+    let cache_key = use_resource(move || wallet_cache_key(wallet));
+    use_memo(move || match &*cache_key.read() {
+        Some(Some(key)) => cache_key_is_stale(key),
+        _ => false,
+    })
+}
+
+/// Limits the number of wallets prefetched at the same time by [use_wallet_prefetch]
+static PREFETCH_SLOTS: tokio::sync::Semaphore = tokio::sync::Semaphore::const_new(2);
+
+/// Hook loading the transactions and UTXOs of a wallet in the background
+///
+/// The values land in the caches of [use_resource_wallet_transactions] and
+/// [use_resource_wallet_utxos], so that opening the wallet displays them instantly.
+/// At most two wallets are prefetched at the same time, not to overload the
+/// Heritage Service or the Blockchain Provider.
+pub fn use_wallet_prefetch(wallet: AsyncSignal<Wallet>) {
+    let prefetch_trigger = super::load_trigger::use_load_trigger();
+    let wallet_transactions = use_resource_wallet_transactions(wallet, prefetch_trigger);
+    let wallet_utxos = use_resource_wallet_utxos(wallet, prefetch_trigger);
+    use_future(move || async move {
+        let sign_only = wallet
+            .with(async |wallet| matches!(wallet.online_wallet(), AnyOnlineWallet::None))
+            .await;
+        if sign_only {
+            return;
+        }
+        let _slot = PREFETCH_SLOTS
+            .acquire()
+            .await
+            .expect("the semaphore is never closed");
+        log::debug!("use_wallet_prefetch - start");
+        prefetch_trigger.fire();
+        // Keep the slot until the prefetch is over
+        while !(wallet_transactions.finished() && wallet_utxos.finished()) {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        log::debug!("use_wallet_prefetch - finished");
+    });
+}
+
 fn subscribe_service_status_if_service_wallet(wallet: &AsyncSignal<Wallet>) {
     if let Some(ref wallet) = *wallet.read() {
         if matches!(wallet.online_wallet(), AnyOnlineWallet::Service(_)) {
-            // Read the SERVICE_STATUS so that we refresh when the SERVICE_STATUS is refreshed
-            let _ = *state_management::SERVICE_STATUS.read();
+            // Read the SERVICE_STATUS_LAST_REFRESH so that we refresh when the service status is refreshed
+            let _ = *state_management::SERVICE_STATUS_LAST_REFRESH.read();
         }
     }
 }
@@ -52,6 +119,62 @@ pub fn use_resource_wallet_names() -> Resource<Vec<CCStr>> {
     })
 }
 
+/// The settings of the wallet overriding the global ones, defaulting to none on error
+pub fn use_resource_wallet_settings(
+    wallet_name: CCStr,
+) -> Resource<state_management::WalletSettings> {
+    let database_service = state_management::use_database_service();
+    use_resource(move || {
+        let wallet_name = wallet_name.clone();
+        async move {
+            state_management::load_wallet_settings(database_service, wallet_name)
+                .await
+                .unwrap_or_default()
+        }
+    })
+}
+
+pub fn use_resource_wallet_notes(wallet_name: CCStr) -> FResource<CCStr> {
+    let database_service = state_management::use_database_service();
+    use_resource(move || {
+        let wallet_name = wallet_name.clone();
+        async move {
+            state_management::load_wallet_notes(database_service, wallet_name)
+                .await
+                .map(CCStr::from)
+                .map_err(CCStr::from)
+        }
+    })
+}
+
+pub fn use_resource_wallet_backup_freshness(
+    wallet_name: CCStr,
+) -> FResource<state_management::BackupFreshness> {
+    let database_service = state_management::use_database_service();
+    use_resource(move || {
+        let wallet_name = wallet_name.clone();
+        async move {
+            state_management::load_backup_freshness(database_service, wallet_name)
+                .await
+                .map_err(CCStr::from)
+        }
+    })
+}
+
+pub fn use_resource_wallet_reserved_addresses(
+    wallet_name: CCStr,
+) -> FResource<Vec<state_management::ReservedAddress>> {
+    let database_service = state_management::use_database_service();
+    use_resource(move || {
+        let wallet_name = wallet_name.clone();
+        async move {
+            state_management::load_reserved_addresses(database_service, wallet_name)
+                .await
+                .map_err(CCStr::from)
+        }
+    })
+}
+
 pub fn use_async_wallet(name: CCStr) -> AsyncSignal<Wallet> {
     let database_service = state_management::use_database_service();
     let service_client_service = state_management::use_service_client_service();
@@ -73,22 +196,35 @@ pub fn use_async_wallet(name: CCStr) -> AsyncSignal<Wallet> {
         }
     })
 }
+static SERVICE_WALLETS_CACHE: ResourceCache<Vec<CheapClone<HeritageWalletMeta>>> =
+    Signal::global(HashMap::new);
+
 pub fn use_resource_service_wallets() -> Resource<Vec<CheapClone<HeritageWalletMeta>>> {
     let service_client_service = state_management::use_service_client_service();
-    use_resource(move || async move {
-        log::debug!("use_resource_service_wallets - start");
+    use_cached_resource(
+        &SERVICE_WALLETS_CACHE,
+        InMemoryOnly,
+        None,
+        move || async move { service_cache_key() },
+        move || async move {
+            log::debug!("use_resource_service_wallets - start");
 
-        // Read the SERVICE_STATUS so that we refresh when the SERVICE_STATUS is refreshed
-        let _ = *state_management::SERVICE_STATUS.read();
+            // Read the SERVICE_STATUS_LAST_REFRESH so that we refresh when the service status is refreshed
+            let _ = *state_management::SERVICE_STATUS_LAST_REFRESH.read();
 
-        let heritage_service =
-            state_management::heritage_service_client(service_client_service).await;
-        let service_wallets = heritage_service.list_wallets().await.unwrap_or_default();
+            let service_wallets = state_management::scheduled_service_call(
+                service_client_service,
+                Some("list_wallets"),
+                |client| async move { client.list_wallets().await },
+            )
+            .await
+            .unwrap_or_default();
 
-        let service_wallets = service_wallets.into_iter().map(|w| w.into()).collect();
-        log::debug!("use_resource_service_wallets - loaded");
-        service_wallets
-    })
+            let service_wallets = service_wallets.into_iter().map(|w| w.into()).collect();
+            log::debug!("use_resource_service_wallets - loaded");
+            service_wallets
+        },
+    )
 }
 
 pub fn use_resource_service_only_wallets() -> Resource<Vec<CheapClone<HeritageWalletMeta>>> {
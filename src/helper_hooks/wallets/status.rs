@@ -1,6 +1,6 @@
 use crate::prelude::*;
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 use btc_heritage_wallet::{
     btc_heritage::AccountXPubId, online_wallet::WalletStatus, AnyOnlineWallet, BoundFingerprint,
@@ -13,28 +13,39 @@ use crate::{
     utils::CCStr,
 };
 
+use super::{use_cached_resource, InMemoryOnly, ResourceCache};
+
+static WALLET_STATUS_CACHE: ResourceCache<Result<WalletStatus, CCStr>> =
+    Signal::global(HashMap::new);
+
 pub fn use_resource_wallet_status(wallet: AsyncSignal<Wallet>) -> FResource<WalletStatus> {
-    use_resource(move || async move {
-        log::debug!("use_resource_wallet_status - start");
+    use_cached_resource(
+        &WALLET_STATUS_CACHE,
+        InMemoryOnly,
+        None,
+        move || super::wallet_cache_key(wallet),
+        move || async move {
+            log::debug!("use_resource_wallet_status - start");
 
-        super::subscribe_service_status_if_service_wallet(&wallet);
+            super::subscribe_service_status_if_service_wallet(&wallet);
 
-        let wallet_status = wallet
-            .with(async |wallet| {
-                wallet.get_wallet_status().await.map_err(|e| {
-                    log::error!(
-                        "Error retrieving the wallet status of wallet {}: {e}",
-                        wallet.name()
-                    );
-                    CCStr::from(e.to_string())
+            let wallet_status = wallet
+                .with(async |wallet| {
+                    wallet.get_wallet_status().await.map_err(|e| {
+                        log::error!(
+                            "Error retrieving the wallet status of wallet {}: {e}",
+                            wallet.name()
+                        );
+                        CCStr::from(e.to_string())
+                    })
                 })
-            })
-            .await;
+                .await;
 
-        log::debug!("use_resource_wallet_status - loaded");
+            log::debug!("use_resource_wallet_status - loaded");
 
-        wallet_status
-    })
+            wallet_status
+        },
+    )
 }
 
 pub fn use_memo_fingerprint(wallet: AsyncSignal<Wallet>) -> Memo<CCStr> {
@@ -65,9 +76,13 @@ pub fn use_memo_wallet_online_status(
             AnyOnlineWallet::None => (OnlineWalletType::None, ExternalDependencyStatus::None),
             AnyOnlineWallet::Service(sb) => (
                 OnlineWalletType::Service,
-                match state_management::SERVICE_STATUS.read().as_ref() {
-                    Some(ss) if ss.can_serve_wallet(sb) => ExternalDependencyStatus::Available,
-                    _ => ExternalDependencyStatus::Unavailable,
+                if state_management::SERVICE_SERVICEABILITY
+                    .read()
+                    .can_serve_wallet(sb)
+                {
+                    ExternalDependencyStatus::Available
+                } else {
+                    ExternalDependencyStatus::Unavailable
                 },
             ),
             AnyOnlineWallet::Local(_) => (
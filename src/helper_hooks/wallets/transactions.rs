@@ -3,48 +3,66 @@ use crate::prelude::*;
 use std::collections::HashMap;
 
 use btc_heritage_wallet::{
-    bitcoin::{Address, Amount, FeeRate, OutPoint, SignedAmount, Txid},
+    bitcoin::{self, Address, Amount, FeeRate, Network, OutPoint, SignedAmount, Txid},
     btc_heritage::{bdk_types::BlockTime, heritage_wallet::TransactionSummaryIOTotals},
     heritage_service_api_client::{TransactionSummary, TransactionSummaryOwnedIO},
     DatabaseItem, OnlineWallet, Wallet,
 };
 
+use serde::{Deserialize, Serialize};
+
 use crate::utils::{amount_to_signed, CCStr, CheapClone};
 
+use state_management::HistoricalExchangeRates;
+
+use super::{
+    use_cached_resource, use_persisted_in_database, use_persisted_memo, InMemoryOnly, ResourceCache,
+};
+
+static WALLET_TRANSACTIONS_CACHE: ResourceCache<Result<CheapClone<[TransactionSummary]>, CCStr>> =
+    Signal::global(HashMap::new);
+
 pub fn use_resource_wallet_transactions(
     wallet: AsyncSignal<Wallet>,
+    load_trigger: LoadTrigger,
 ) -> FResource<CheapClone<[TransactionSummary]>> {
-    use_resource(move || async move {
-        log::debug!("use_resource_wallet_transactions - start");
-
-        super::subscribe_service_status_if_service_wallet(&wallet);
-
-        let wallet_txs = wallet
-            .with(async |wallet| {
-                let wallet_name = wallet.name().to_owned();
-                wallet
-                    .list_transactions()
-                    .await
-                    .map_err(|e| {
-                        let error = format!(
-                            "Error retrieving the wallet transactions of wallet {}: {e}",
-                            wallet_name
-                        );
-                        log::error!("{error}");
-                        CCStr::from(error)
-                    })
-                    .map(Into::into)
-            })
-            .await;
-        log::debug!("use_resource_wallet_transactions - loaded");
+    use_cached_resource(
+        &WALLET_TRANSACTIONS_CACHE,
+        InMemoryOnly,
+        Some(load_trigger),
+        move || super::wallet_cache_key(wallet),
+        move || async move {
+            log::debug!("use_resource_wallet_transactions - start");
+            super::subscribe_service_status_if_service_wallet(&wallet);
 
-        wallet_txs
-    })
+            let wallet_txs = wallet
+                .with(async |wallet| {
+                    let wallet_name = wallet.name().to_owned();
+                    wallet
+                        .list_transactions()
+                        .await
+                        .map_err(|e| {
+                            let error = format!(
+                                "Error retrieving the wallet transactions of wallet {}: {e}",
+                                wallet_name
+                            );
+                            log::error!("{error}");
+                            CCStr::from(error)
+                        })
+                        .map(Into::into)
+                })
+                .await;
+            log::debug!("use_resource_wallet_transactions - loaded");
+
+            wallet_txs
+        },
+    )
 }
 
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TxIOInner {
     pub outpoint: OutPoint,
+    #[serde(with = "bitcoin::amount::serde::as_sat")]
     pub amount: Amount,
 }
 impl From<&TransactionSummaryOwnedIO> for TxIOInner {
@@ -55,7 +73,7 @@ impl From<&TransactionSummaryOwnedIO> for TxIOInner {
         }
     }
 }
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TxIO {
     Incoming(TxIOInner),
     Outgoing(TxIOInner),
@@ -70,7 +88,7 @@ impl core::ops::Deref for TxIO {
         }
     }
 }
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TransactionStats {
     pub id: Txid,
     pub block_time: Option<BlockTime>,
@@ -111,7 +129,7 @@ impl TransactionStats {
 }
 
 pub fn use_memo_tx_stats_by_address(
-    wallet_transactions: FResource<CheapClone<[TransactionSummary]>>,
+    wallet_transactions: FMemo<ContentHashed<CheapClone<[TransactionSummary]>>>,
 ) -> FMemo<HashMap<Address, CheapClone<[TransactionStats]>>> {
     use_memo(move || {
         log::debug!("use_memo_tx_stats_by_address - start compute");
@@ -135,9 +153,10 @@ pub fn use_memo_tx_stats_by_address(
     })
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TransactionHistoryItemOwnedIO {
     pub address: CCStr,
+    #[serde(with = "bitcoin::amount::serde::as_sat")]
     pub amount: Amount,
 }
 impl From<&TransactionSummaryOwnedIO> for TransactionHistoryItemOwnedIO {
@@ -148,24 +167,29 @@ impl From<&TransactionSummaryOwnedIO> for TransactionHistoryItemOwnedIO {
         }
     }
 }
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TransactionHistoryItem {
     pub txid: Txid,
     pub confirmation_time: Option<BlockTime>,
+    #[serde(with = "bitcoin::amount::serde::as_sat")]
     pub balance_spent: Amount,
+    #[serde(with = "bitcoin::amount::serde::as_sat")]
     pub balance_received: Amount,
+    #[serde(with = "bitcoin::amount::serde::as_sat")]
     pub balance_change: SignedAmount,
+    #[serde(with = "bitcoin::amount::serde::as_sat")]
     pub balance_after: Amount,
     pub inputs_totals: TransactionSummaryIOTotals,
     pub owned_inputs: CheapClone<[TransactionHistoryItemOwnedIO]>,
     pub outputs_totals: TransactionSummaryIOTotals,
     pub owned_outputs: CheapClone<[TransactionHistoryItemOwnedIO]>,
+    #[serde(with = "bitcoin::amount::serde::as_sat")]
     pub fee: Amount,
     pub fee_rate: FeeRate,
 }
 
 pub fn use_memo_transaction_history_items(
-    wallet_transactions: FResource<CheapClone<[TransactionSummary]>>,
+    wallet_transactions: FMemo<ContentHashed<CheapClone<[TransactionSummary]>>>,
 ) -> FMemo<CheapClone<[TransactionHistoryItem]>> {
     use_memo(move || {
         log::debug!("use_memo_transaction_history_items - start compute");
@@ -225,3 +249,50 @@ pub fn use_memo_transaction_history_items(
         transaction_history_items
     })
 }
+
+/// Memo hook serving the transaction history items persisted by a previous run of
+/// the application until they are computed again
+pub fn use_memo_persisted_transaction_history_items(
+    wallet: AsyncSignal<Wallet>,
+    transaction_history_items: FMemo<CheapClone<[TransactionHistoryItem]>>,
+) -> FMemo<CheapClone<[TransactionHistoryItem]>> {
+    use_persisted_memo(
+        use_persisted_in_database(state_management::WALLET_TRANSACTION_HISTORY_CACHE),
+        move || super::wallet_cache_key(wallet),
+        transaction_history_items,
+    )
+}
+
+/// Resource with the exchange rates of 1 BTC at the confirmation time of the wallet transactions,
+/// which are also the acquisition time of the UTXOs
+///
+/// [None] if the user did not choose a fiat currency or if the wallet is not on the Bitcoin
+/// mainnet, as testnet coins have no value.
+pub fn use_resource_historical_exchange_rates(
+    wallet_transactions: FMemo<ContentHashed<CheapClone<[TransactionSummary]>>>,
+) -> Resource<Option<HistoricalExchangeRates>> {
+    let database_service = state_management::use_database_service();
+    use_resource(move || async move {
+        let timestamps = match &*wallet_transactions.read() {
+            Some(Ok(wallet_transactions)) => wallet_transactions
+                .iter()
+                .filter_map(|tx_sum| tx_sum.confirmation_time.as_ref().map(|bt| bt.timestamp))
+                .collect::<Vec<_>>(),
+            _ => return None,
+        };
+        if state_management::APPLICATION_CONFIG.read().network != Network::Bitcoin {
+            return None;
+        }
+        let currency = state_management::load_fiat_currency(database_service)
+            .await
+            .ok()
+            .flatten()?;
+        log::debug!("use_resource_historical_exchange_rates - start");
+        let historical_exchange_rates =
+            state_management::historical_btc_exchange_rates(database_service, currency, timestamps)
+                .await
+                .ok();
+        log::debug!("use_resource_historical_exchange_rates - loaded");
+        historical_exchange_rates
+    })
+}
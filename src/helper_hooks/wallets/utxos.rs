@@ -3,57 +3,80 @@ use crate::prelude::*;
 use std::collections::HashMap;
 
 use btc_heritage_wallet::{
-    bitcoin::{Address, Amount, OutPoint},
+    bitcoin::{self, Address, Amount, OutPoint},
     btc_heritage::{bdk_types::BlockTime, HeritageConfig},
     heritage_service_api_client::HeritageUtxo,
     DatabaseItem, OnlineWallet, Wallet,
 };
 
+use serde::{Deserialize, Serialize};
+
 use crate::utils::{CCStr, CheapClone};
 
-use super::{ExpirationStatus, HeritageConfigWithInfo};
+use super::{
+    use_cached_resource, ExpirationStatus, HeritageConfigWithInfo, InMemoryOnly, ResourceCache,
+};
+
+static WALLET_UTXOS_CACHE: ResourceCache<Result<CheapClone<[HeritageUtxo]>, CCStr>> =
+    Signal::global(HashMap::new);
 
 pub fn use_resource_wallet_utxos(
     wallet: AsyncSignal<Wallet>,
+    load_trigger: LoadTrigger,
 ) -> FResource<CheapClone<[HeritageUtxo]>> {
-    use_resource(move || async move {
-        log::debug!("use_resource_wallet_utxos - start");
-
-        super::subscribe_service_status_if_service_wallet(&wallet);
-
-        let wallet_utxos = wallet
-            .with(async |wallet| {
-                let wallet_name = wallet.name().to_owned();
-                wallet
-                    .list_heritage_utxos()
-                    .await
-                    .map_err(|e| {
-                        let error = format!(
-                            "Error retrieving the wallet UTXOs of wallet {}: {e}",
-                            wallet_name
-                        );
-                        log::error!("{error}");
-                        CCStr::from(error)
-                    })
-                    .map(Into::into)
-            })
-            .await;
-
-        log::debug!("use_resource_wallet_utxos - loaded");
-
-        wallet_utxos
-    })
+    use_cached_resource(
+        &WALLET_UTXOS_CACHE,
+        InMemoryOnly,
+        Some(load_trigger),
+        move || super::wallet_cache_key(wallet),
+        move || async move {
+            log::debug!("use_resource_wallet_utxos - start");
+            super::subscribe_service_status_if_service_wallet(&wallet);
+
+            let wallet_utxos = wallet
+                .with(async |wallet| {
+                    let wallet_name = wallet.name().to_owned();
+                    wallet
+                        .list_heritage_utxos()
+                        .await
+                        .map_err(|e| {
+                            let error = format!(
+                                "Error retrieving the wallet UTXOs of wallet {}: {e}",
+                                wallet_name
+                            );
+                            log::error!("{error}");
+                            CCStr::from(error)
+                        })
+                        .map(Into::into)
+                })
+                .await;
+
+            log::debug!("use_resource_wallet_utxos - loaded");
+
+            wallet_utxos
+        },
+    )
 }
 
+/// Balance of each Heritage Configuration, only counting the UTXOs with the confirmations
+/// the wallet settings require to spend them
 pub fn use_memo_balance_by_heritage_config(
-    wallet_utxos: FResource<CheapClone<[HeritageUtxo]>>,
+    wallet_utxos: FMemo<ContentHashed<CheapClone<[HeritageUtxo]>>>,
+    min_confirmations: Memo<u32>,
 ) -> FMemo<HashMap<HeritageConfig, Amount>> {
     use_memo(move || {
         log::debug!("use_memo_balance_by_heritage_config - start compute");
 
+        let min_confirmations = min_confirmations();
+        let tip_height = tip_height();
         let balance_by_heritage_config = wallet_utxos.lrmap(|utxos| {
             let mut balance_by_heritage_config = HashMap::new();
-            for utxo in utxos.iter() {
+            for utxo in utxos.iter().filter(|utxo| {
+                is_spendable(
+                    confirmations(utxo.confirmation_time.as_ref(), tip_height),
+                    min_confirmations,
+                )
+            }) {
                 let heritage_config = utxo.heritage_config.clone();
                 let utxo_amount = utxo.amount;
                 balance_by_heritage_config
@@ -68,9 +91,10 @@ pub fn use_memo_balance_by_heritage_config(
         balance_by_heritage_config
     })
 }
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SimpleUtxo {
     pub outpoint: OutPoint,
+    #[serde(with = "bitcoin::amount::serde::as_sat")]
     pub amount: Amount,
 }
 impl From<&HeritageUtxo> for SimpleUtxo {
@@ -81,9 +105,10 @@ impl From<&HeritageUtxo> for SimpleUtxo {
         }
     }
 }
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct UtxoStats {
     pub count: usize,
+    #[serde(with = "bitcoin::amount::serde::as_sat")]
     pub balance: Amount,
     pub utxos: CheapClone<[SimpleUtxo]>,
 }
@@ -95,7 +120,7 @@ impl UtxoStats {
 }
 
 pub fn use_memo_utxo_stats_by_address(
-    wallet_utxos: FResource<CheapClone<[HeritageUtxo]>>,
+    wallet_utxos: FMemo<ContentHashed<CheapClone<[HeritageUtxo]>>>,
 ) -> FMemo<HashMap<Address, UtxoStats>> {
     use_memo(move || {
         log::debug!("use_memo_utxo_stats_by_address - start compute");
@@ -135,16 +160,56 @@ pub struct UtxoWithInfo {
     pub confirmation_time: Option<BlockTime>,
     pub address: CCStr,
     pub heritage_config_expiration: Option<ExpirationStatus>,
+    /// [None] if the UTXO is confirmed but the height of the blockchain tip is unknown
+    pub confirmations: Option<u32>,
+    /// The UTXO has the confirmations the wallet settings require to spend it
+    pub spendable: bool,
 }
+
+/// Height of the blockchain tip, only known with a connected blockchain provider
+///
+/// Wallets synchronized through the service do not expose the height they were synchronized at.
+fn tip_height() -> Option<u32> {
+    match *state_management::BLOCKCHAIN_PROVIDER_STATUS.read() {
+        Some(BlockchainProviderStatus::Connected(height)) => Some(height),
+        _ => None,
+    }
+}
+
+/// Confirmations of a transaction given the height of the blockchain tip
+///
+/// [None] if the transaction is confirmed but the tip height is unknown.
+fn confirmations(confirmation_time: Option<&BlockTime>, tip_height: Option<u32>) -> Option<u32> {
+    match (confirmation_time, tip_height) {
+        (None, _) => Some(0),
+        (Some(bt), Some(tip_height)) => {
+            Some(tip_height.saturating_sub(bt.height).saturating_add(1))
+        }
+        (Some(_), None) => None,
+    }
+}
+
+/// Whether a UTXO with `confirmations` can be spent
+///
+/// A confirmed UTXO whose confirmations are unknown stays spendable: the threshold cannot be
+/// checked without the tip height and refusing it would lock the funds of the wallet.
+fn is_spendable(confirmations: Option<u32>, min_confirmations: u32) -> bool {
+    confirmations.is_none_or(|confirmations| confirmations >= min_confirmations)
+}
+
 pub fn use_memo_utxo_with_info(
-    wallet_utxos: FResource<CheapClone<[HeritageUtxo]>>,
+    wallet_utxos: FMemo<ContentHashed<CheapClone<[HeritageUtxo]>>>,
     heritage_configs_with_info_indexed_by_heritage_config: FMemo<
         HashMap<CheapClone<HeritageConfig>, HeritageConfigWithInfo>,
     >,
+    min_confirmations: Memo<u32>,
 ) -> FMemo<CheapClone<[UtxoWithInfo]>> {
     use_memo(move || {
         log::debug!("use_memo_utxo_with_info - start compute");
 
+        let min_confirmations = min_confirmations();
+        let tip_height = tip_height();
+
         let utxo_with_info = wallet_utxos.lrmap(|wallet_utxos| {
             wallet_utxos
                 .iter()
@@ -162,12 +227,15 @@ pub fn use_memo_utxo_with_info(
                                 .expect("data integrity mandates that it is present")
                                 .expiration_status
                         });
+                    let confirmations = confirmations(confirmation_time.as_ref(), tip_height);
                     UtxoWithInfo {
                         outpoint,
                         amount,
                         confirmation_time,
                         address,
                         heritage_config_expiration,
+                        confirmations,
+                        spendable: is_spendable(confirmations, min_confirmations),
                     }
                 })
                 .collect()
@@ -0,0 +1,211 @@
+use std::time::Duration;
+
+use dioxus::prelude::*;
+
+use crate::utils::CCStr;
+
+/// How long a resource may stay loading before [ResourceErrorBoundary]
+/// replaces its skeleton with a "taking longer than expected" card
+pub const DEFAULT_LOADING_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// A card explaining that some data could not be loaded.
+///
+/// It displays the first line of the error, an expandable section with the
+/// complete technical details and, if `on_retry` is provided, a "Retry" button.
+///
+/// # Parameters
+///
+/// * `title` - What failed to load, e.g. "Transactions History"
+/// * `error` - The error message
+/// * `on_retry` - Called when the user clicks the "Retry" button
+///
+/// # Examples
+///
+/// ```rust
+/// use crate::loaded::prelude::*;
+///
+/// rsx! {
+///     LoadedErrorCard {
+///         title: "Wallet balance",
+///         error: CCStr::from("connection refused"),
+///         on_retry: move |_| wallet_status.restart(),
+///     }
+/// }
+/// ```
+#[component]
+pub fn LoadedErrorCard(
+    title: &'static str,
+    error: CCStr,
+    on_retry: Option<EventHandler<()>>,
+) -> Element {
+    let headline = error.lines().next().unwrap_or_default().to_owned();
+    rsx! {
+        div { class: "card bg-error/10 border border-error/30 w-full max-w-2xl mx-auto my-4",
+            div { class: "card-body gap-3",
+                h2 { class: "card-title text-error", "{title} could not be loaded" }
+                p { class: "text-sm", {headline} }
+                details { class: "collapse collapse-arrow bg-base-100 border border-base-content/10",
+                    summary { class: "collapse-title text-sm font-semibold", "Technical details" }
+                    div { class: "collapse-content",
+                        pre { class: "text-xs font-mono whitespace-pre-wrap break-all", "{error}" }
+                    }
+                }
+                if let Some(on_retry) = on_retry {
+                    div { class: "card-actions justify-end",
+                        button {
+                            class: "btn btn-error btn-outline",
+                            onclick: move |_| on_retry.call(()),
+                            "Retry"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A card explaining that some data is taking longer than expected to load.
+///
+/// It offers to retry the loading and, if `on_cancel` is provided, to cancel it.
+/// Once cancelled, only the "Retry" button remains.
+///
+/// # Parameters
+///
+/// * `title` - What is loading, e.g. "Transactions History"
+/// * `cancelled` - If the loading was cancelled by the user
+/// * `on_retry` - Called when the user clicks the "Retry" button
+/// * `on_cancel` - Called when the user clicks the "Cancel" button
+#[component]
+pub fn LoadingTimeoutCard(
+    title: &'static str,
+    cancelled: bool,
+    on_retry: EventHandler<()>,
+    on_cancel: Option<EventHandler<()>>,
+) -> Element {
+    rsx! {
+        div { class: "card bg-warning/10 border border-warning/30 w-full max-w-2xl mx-auto my-4",
+            div { class: "card-body gap-3",
+                if cancelled {
+                    h2 { class: "card-title text-warning", "{title} loading was cancelled" }
+                } else {
+                    h2 { class: "card-title text-warning",
+                        "{title} is taking longer than expected"
+                        span { class: "loading loading-dots loading-sm" }
+                    }
+                    p { class: "text-sm",
+                        "A backend (blockchain provider, service or local database) may be unresponsive. \
+                        You can keep waiting, retry or cancel."
+                    }
+                }
+                div { class: "card-actions justify-end",
+                    if !cancelled {
+                        if let Some(on_cancel) = on_cancel {
+                            button {
+                                class: "btn btn-outline",
+                                onclick: move |_| on_cancel.call(()),
+                                "Cancel"
+                            }
+                        }
+                    }
+                    button {
+                        class: "btn btn-warning btn-outline",
+                        onclick: move |_| on_retry.call(()),
+                        "Retry"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A component rendering its children, unless `resource` failed to load
+/// or is still loading after `timeout`.
+///
+/// If the resource failed, a [LoadedErrorCard] replaces the children and its "Retry"
+/// button restarts the resource.
+///
+/// If the resource is still loading after `timeout`, a [LoadingTimeoutCard] replaces
+/// the children so that a hung backend does not leave skeletons forever. The user
+/// can restart the resource or cancel it.
+///
+/// # Type Parameters
+///
+/// * `T` - The value produced by the resource when it succeeds
+///
+/// # Parameters
+///
+/// * `title` - What failed to load, e.g. "Transactions History"
+/// * `resource` - The resource to watch and restart
+/// * `timeout` - How long to wait before showing the timeout card, defaults to [DEFAULT_LOADING_TIMEOUT]
+/// * `children` - The content to display while loading or on success
+///
+/// # Examples
+///
+/// ```rust
+/// use crate::loaded::prelude::*;
+///
+/// rsx! {
+///     ResourceErrorBoundary { title: "Wallet balance", resource: wallet_status,
+///         LoadedComponent::<UIWalletBalance> { input: wallet_status.into() }
+///     }
+/// }
+/// ```
+#[component]
+pub fn ResourceErrorBoundary<T: Clone + PartialEq + 'static>(
+    title: &'static str,
+    resource: Resource<Result<T, CCStr>>,
+    #[props(default = DEFAULT_LOADING_TIMEOUT)] timeout: Duration,
+    children: Element,
+) -> Element {
+    // Incremented on each retry, so that the timeout restarts too
+    let mut attempt = use_signal(|| 0u32);
+    let is_loading = use_memo(move || {
+        resource.read().is_none() && *resource.state().read() == UseResourceState::Pending
+    });
+    let is_cancelled = use_memo(move || {
+        resource.read().is_none() && *resource.state().read() == UseResourceState::Stopped
+    });
+    let timed_out = use_resource(move || async move {
+        let _ = attempt();
+        if is_loading() {
+            tokio::time::sleep(timeout).await;
+            log::warn!("{title} - still loading after {}s", timeout.as_secs());
+            true
+        } else {
+            false
+        }
+    });
+
+    let retry = move || {
+        log::debug!("{title} - retry");
+        let mut resource = resource;
+        resource.restart();
+        *attempt.write() += 1;
+    };
+
+    let error = match &*resource.read() {
+        Some(Err(e)) => Some(e.clone()),
+        _ => None,
+    };
+    match error {
+        Some(error) => rsx! {
+            LoadedErrorCard { title, error, on_retry: move |_| retry() }
+        },
+        None if is_cancelled() => rsx! {
+            LoadingTimeoutCard { title, cancelled: true, on_retry: move |_| retry() }
+        },
+        None if is_loading() && timed_out() == Some(true) => rsx! {
+            LoadingTimeoutCard {
+                title,
+                cancelled: false,
+                on_retry: move |_| retry(),
+                on_cancel: move |_| {
+                    log::debug!("{title} - cancel");
+                    let mut resource = resource;
+                    resource.cancel();
+                },
+            }
+        },
+        None => children,
+    }
+}
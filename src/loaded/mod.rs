@@ -12,6 +12,8 @@
 //! - [`LoadedElement`](element::LoadedElement): Trait for components that can be displayed in different loading states
 //! - [`LoadedComponent`](component::LoadedComponent): A wrapper component that handles displaying appropriate UI based on loading state
 //! - [`FromRef`](converters::FromRef)/[`RefInto`](converters::RefInto): Conversion traits for transforming data into components
+//! - [`ResourceErrorBoundary`](error::ResourceErrorBoundary): Replaces content with a retryable error card when a resource fails,
+//!   or with a retry/cancel card when it is still loading after a timeout
 //! - Loaders: Different strategies for displaying loading states (skeleton, transparent)
 //!
 //! ## Example Usage
@@ -42,6 +44,7 @@
 pub mod component;
 mod converters;
 mod element;
+pub mod error;
 pub mod loaders;
 pub mod mapper;
 
@@ -59,6 +62,9 @@ pub mod prelude {
     pub use super::component::{AlwaysLoadedComponent, LoadedComponent, StaticLoadedComponent};
     pub use super::converters::{FromRef, LoadedSuccessConversionMarker, RefInto, TypeCouple};
     pub use super::element::{Display, Display::Show, LoadedElement};
+    pub use super::error::{
+        LoadedErrorCard, LoadingTimeoutCard, ResourceErrorBoundary, DEFAULT_LOADING_TIMEOUT,
+    };
     pub use super::loaders::{SkeletonLoader, TransparentLoader};
     pub use super::mapper::LoadedComponentInputMapper;
 }
@@ -0,0 +1,180 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock, RwLock},
+};
+
+/// Filter used when neither the configuration nor `RUST_LOG` provides one
+const DEFAULT_FILTER: &str = "warn";
+const LOG_FILE_NAME: &str = "heritage-gui.log";
+/// Past this size (5 MiB), the log file is rotated
+const MAX_LOG_FILE_SIZE: u64 = 5 * 1024 * 1024;
+/// Number of log files kept, including the current one
+const LOG_FILE_COUNT: usize = 5;
+
+/// Logger whose filter and file output can be changed while the application runs
+///
+/// `env_logger` does not allow to change its filter once installed, so a new
+/// [env_logger::Logger] is built for each change and swapped in place.
+struct RuntimeLogger {
+    inner: RwLock<env_logger::Logger>,
+    file: Mutex<Option<LogFile>>,
+}
+
+static LOGGER: OnceLock<RuntimeLogger> = OnceLock::new();
+
+impl log::Log for RuntimeLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        self.inner.read().is_ok_and(|inner| inner.enabled(metadata))
+    }
+
+    fn log(&self, record: &log::Record) {
+        let Ok(inner) = self.inner.read() else {
+            return;
+        };
+        if !inner.matches(record) {
+            return;
+        }
+        inner.log(record);
+        drop(inner);
+        if let Ok(mut file) = self.file.lock() {
+            if let Some(file) = file.as_mut() {
+                file.write_record(record);
+            }
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(inner) = self.inner.read() {
+            inner.flush();
+        }
+        if let Ok(mut file) = self.file.lock() {
+            if let Some(file) = file.as_mut() {
+                let _ = file.file.flush();
+            }
+        }
+    }
+}
+
+fn build_logger(filter: Option<&str>) -> env_logger::Logger {
+    let mut builder = match filter {
+        Some(filter) => {
+            let mut builder = env_logger::Builder::new();
+            builder.parse_filters(filter);
+            builder
+        }
+        None => env_logger::Builder::from_env(
+            env_logger::Env::default().default_filter_or(DEFAULT_FILTER),
+        ),
+    };
+    builder.format_timestamp_micros().build()
+}
+
+/// Install the logger, filtering with `RUST_LOG` until [reconfigure] is called
+pub fn init() {
+    let logger = LOGGER.get_or_init(|| RuntimeLogger {
+        inner: RwLock::new(build_logger(None)),
+        file: Mutex::new(None),
+    });
+    log::set_max_level(logger.inner.read().expect("not poisoned").filter());
+    log::set_logger(logger).expect("logger is only installed once");
+}
+
+/// Change the filter and the file output of the logger
+///
+/// An empty `filter` falls back to `RUST_LOG`, or `warn` if it is not set.
+/// The syntax is the one of `RUST_LOG`, e.g. `info,heritage_gui=debug`.
+/// When `log_dir` is provided, the logs are also written in rotating files in it.
+pub fn reconfigure(filter: &str, log_dir: Option<PathBuf>) -> Result<(), String> {
+    let Some(logger) = LOGGER.get() else {
+        return Err("The logger is not initialized".to_owned());
+    };
+    let filter = filter.trim();
+    let inner = build_logger((!filter.is_empty()).then_some(filter));
+    log::set_max_level(inner.filter());
+    *logger
+        .inner
+        .write()
+        .map_err(|_| "The logger is poisoned".to_owned())? = inner;
+
+    let mut file = logger
+        .file
+        .lock()
+        .map_err(|_| "The log file is poisoned".to_owned())?;
+    match log_dir {
+        Some(log_dir) => {
+            if file.as_ref().is_none_or(|file| file.dir != log_dir) {
+                *file = None;
+                *file = Some(
+                    LogFile::open(log_dir)
+                        .map_err(|e| format!("Could not open the log file: {e}"))?,
+                );
+            }
+        }
+        None => *file = None,
+    }
+    Ok(())
+}
+
+/// Path of the current log file in `log_dir`
+pub fn log_file_path(log_dir: &Path) -> PathBuf {
+    LogFile::path(log_dir, 0)
+}
+
+struct LogFile {
+    dir: PathBuf,
+    file: File,
+    size: u64,
+}
+impl LogFile {
+    fn path(dir: &Path, index: usize) -> PathBuf {
+        if index == 0 {
+            dir.join(LOG_FILE_NAME)
+        } else {
+            dir.join(format!("{LOG_FILE_NAME}.{index}"))
+        }
+    }
+
+    fn open(dir: PathBuf) -> std::io::Result<Self> {
+        std::fs::create_dir_all(&dir)?;
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(Self::path(&dir, 0))?;
+        let size = file.metadata()?.len();
+        Ok(Self { dir, file, size })
+    }
+
+    /// Shift the older files (the oldest is dropped) and start a new current file
+    fn rotate(&mut self) -> std::io::Result<()> {
+        for index in (1..LOG_FILE_COUNT).rev() {
+            let from = Self::path(&self.dir, index - 1);
+            if from.exists() {
+                std::fs::rename(from, Self::path(&self.dir, index))?;
+            }
+        }
+        *self = Self::open(self.dir.clone())?;
+        Ok(())
+    }
+
+    fn write_record(&mut self, record: &log::Record) {
+        let line = format!(
+            "[{} {:<5} {}] {}\n",
+            chrono::Local::now().format("%Y-%m-%dT%H:%M:%S%.6f"),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        if self.size + line.len() as u64 > MAX_LOG_FILE_SIZE {
+            if let Err(e) = self.rotate() {
+                eprintln!("Could not rotate the log files: {e}");
+            }
+        }
+        // Logging from the logger would recurse, errors can only be reported on stderr
+        match self.file.write_all(line.as_bytes()) {
+            Ok(_) => self.size += line.len() as u64,
+            Err(e) => eprintln!("Could not write in the log file: {e}"),
+        }
+    }
+}
@@ -1,7 +1,11 @@
 #![windows_subsystem = "windows"]
+mod cli;
 mod components;
+mod crash;
+mod errors;
 mod helper_hooks;
 mod loaded;
+mod logger;
 mod onboarding;
 mod state_management;
 mod utils;
@@ -9,7 +13,7 @@ mod views;
 
 mod prelude {
     pub use super::components::{
-        alerts::{alert_error, alert_info, alert_success, alert_warn},
+        alerts::{alert_error, alert_failure, alert_info, alert_success, alert_warn},
         onboarding::{MaybeHighlight, MaybeHighlightProgressType, OnboardingInfoModal},
     };
     pub use super::helper_hooks::prelude::*;
@@ -29,6 +33,10 @@ use prelude::*;
 use utils::CCStr;
 use views::{
     app_config::AppConfigView,
+    backup_check::BackupCheckView,
+    dashboard::DashboardView,
+    diagnostics::DiagnosticsView,
+    estate::EstateView,
     heirs::{
         configuration::HeirConfigurationView,
         heir::{HeirView, HeirWrapperLayout},
@@ -46,6 +54,7 @@ use views::{
     onboarding::{
         OnboardingHowPrivateView, OnboardingHowPublicView, OnboardingLayout, OnboardingWhoView,
     },
+    recovery_rehearsal::RecoveryRehearsalView,
     splashscreen::SplashScreenView,
     wallet::{
         configuration::WalletConfigurationView, spend::WalletSpendView, WalletView,
@@ -53,6 +62,7 @@ use views::{
     },
     wallet_create::WalletCreateView,
     wallet_list::WalletListView,
+    xpub_tool::XPubToolView,
 };
 
 #[derive(Clone, Routable, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -72,8 +82,20 @@ pub enum Route {
     #[end_nest]
     #[nest("/main")]
     #[layout(MainLayout)]
+        #[route("/dashboard")]
+        DashboardView {},
+        #[route("/estate")]
+        EstateView {},
         #[route("/config")]
         AppConfigView {},
+        #[route("/diagnostics")]
+        DiagnosticsView {},
+        #[route("/tools/xpub")]
+        XPubToolView {},
+        #[route("/tools/backup")]
+        BackupCheckView {},
+        #[route("/tools/rehearsal")]
+        RecoveryRehearsalView {},
         #[nest("/wallets")]
             #[route("/")]
             WalletListView {},
@@ -136,6 +158,14 @@ fn App() -> Element {
 
     _ = crate::state_management::use_init_services();
 
+    // When started at login, the application may be asked to stay out of the way
+    use_hook(|| {
+        if std::env::args().any(|arg| arg == state_management::MINIMIZED_FLAG) {
+            log::info!("Starting minimized");
+            dioxus::desktop::window().set_minimized(true);
+        }
+    });
+
     use_drop(|| log::debug!("App Dropped"));
 
     rsx! {
@@ -147,6 +177,7 @@ fn App() -> Element {
             id: "app",
             class: "text-base",
             class: if matches!(prelude::state_management::THEME(), Theme::Dark) { "dark" },
+            style: prelude::state_management::THEME_CUSTOMIZATION.read().style(),
             AlertsContainer {}
             Router::<Route> {}
         }
@@ -163,11 +194,13 @@ fn PageNotFound(route: Vec<String>) -> Element {
 }
 
 fn main() {
-    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn"))
-        .format_timestamp_micros()
-        .init();
+    logger::init();
+    crash::install_panic_hook();
 
     log::info!("starting app");
+    cli::init_cli_args();
+    crash::init_datadir(state_management::startup_datadir());
+    state_management::remove_stale_demo_datadirs();
     use dioxus::desktop::{tao::window::Icon, Config, WindowBuilder};
     LaunchBuilder::desktop()
         .with_cfg(
@@ -30,6 +30,19 @@ impl OnboardingContext {
             .flatten()
             .map(|s| s.as_str())
     }
+    /// Replace the `old_content` of the `context_key` context by `new_content`
+    pub fn rename_context(
+        &mut self,
+        context_key: OnboardingContextItemId,
+        old_content: &str,
+        new_content: &str,
+    ) {
+        if let Some(vec) = self.0.get_mut(&context_key) {
+            for content in vec.iter_mut().filter(|content| *content == old_content) {
+                *content = new_content.to_owned();
+            }
+        }
+    }
 }
 impl core::ops::Deref for OnboardingContext {
     type Target = HashMap<OnboardingContextItemId, Vec<String>>;
@@ -77,9 +90,14 @@ pub enum OnboardingStep {
     // Before we start modals
     ModalCreateAccountOnTheService,
     ModalInstallBlockchainProviderNode,
+    ModalExplainHeirJourney,
     // Config
     ClickConnectService,
     ConfigureBlockchainProvider,
+    // Hardware device
+    LedgerInstallBitcoinApp,
+    LedgerCheckNetwork,
+    LedgerVerifyTestDerivation,
     // Wallet
     ClickCreateWalletCard,
     ModalExplainWalletSplit,
@@ -120,12 +138,14 @@ pub enum OnboardingStep {
     // HeirWallet
     ModalLocalInheritance,
     ClickCreateHeirWalletCard,
+    SelectServiceHeritageProvider,
     SelectLocalHeritageProvider,
     ProvideLocalWalletBackup,
     ClickCreateHeirWalletButton,
     ClickHeirWalletCard,
     SynchronizeLocalHeritage,
     ModalExplainInheritancesList,
+    ModalHeirWaitForMaturity,
     ClickInheritanceSpendButton,
     ModalExplainInheritanceSpend,
     InputInheritanceSpendAddress,
@@ -143,6 +163,107 @@ pub enum OnboardingStep {
     RestoreKeyProviderSeed,
 }
 impl OnboardingStep {
+    /// Short description of the step, displayed in the onboarding progress checklist
+    pub fn title(self) -> &'static str {
+        use OnboardingStep::*;
+        match self {
+            ModalCreateAccountOnTheService => "Create a Heritage Service account",
+            ModalInstallBlockchainProviderNode => "Install a Bitcoin node",
+            ModalExplainHeirJourney => "The heir journey",
+            ClickConnectService => "Connect to the Heritage Service",
+            ConfigureBlockchainProvider => "Configure the blockchain provider",
+            LedgerInstallBitcoinApp => "Open the Bitcoin application",
+            LedgerCheckNetwork => "Check the network mode",
+            LedgerVerifyTestDerivation => "Verify a test key derivation",
+            ClickCreateWalletCard => "Open the wallet creation",
+            ModalExplainWalletSplit => "The split wallet architecture",
+            EnsureLedgerIsConnected => "Connect your Ledger",
+            SelectLocalOnlineWallet => "Select the Local Node online wallet",
+            ClickCreateWalletButton => "Create the wallet",
+            ClickWalletCardAfterHeirsCreation => "Go back to your wallet",
+            OpenWalletConfiguration => "Open the wallet configuration",
+            ClickCreateHeritageConfigurationButton1 => "Start the Heritage Configuration",
+            ModalExplainHeritageConfiguration => "The Heritage Configuration",
+            ClickCreateHeritageConfigurationButton2 => "Create the Heritage Configuration",
+            ModalExplainLedgerPolicies => "The Ledger policies",
+            ClickRegisterLedgerPolicies => "Register the Ledger policies",
+            ClickWalletBackFromConfig => "Go back to the wallet",
+            ModalExplainHeritageBackup => "The descriptors backups",
+            ClickBackupDescriptors => "Backup the descriptors",
+            ClickSaveBackup => "Save the backup",
+            ClickWalletReceive => "Generate your first address",
+            ModalFinishCreatingFirstWallet => "First wallet created",
+            ModalExplainHeirs => "The heirs",
+            ClickCreateHeirCard => "Open the heir creation",
+            InputBackupHeirName => "Name the backup heir",
+            ModalExplainHeirKeyProvider => "The heir key provider",
+            ClickExportHeirToService => "Export the heir to the service",
+            InputEmailAddress => "Enter the heir email address",
+            ModalExplainExportHeirToServiceOptions => "The export options",
+            ClickCreateHeirButton => "Create the heir",
+            ClickHeirShowMnemonic => "Show the heir mnemonic",
+            ModalExplainStoreHeirMnemonic => "Storing the heir mnemonic",
+            CheckHeirRevealMnemonic => "Reveal the heir mnemonic",
+            HoverHeirMnemonic => "Copy the heir mnemonic",
+            CloseHeirShowMnemonic => "Close the mnemonic window",
+            CheckConfirmStripHeirSeed => "Confirm the mnemonic backup",
+            StripHeirSeed => "Strip the heir seed",
+            ModalMoreHeirOrWallet => "More heirs or wallets",
+            ClickHeirCard => "Open the heir",
+            ModalLocalInheritance => "Inheriting without the service",
+            ClickCreateHeirWalletCard => "Open the heir wallet creation",
+            SelectServiceHeritageProvider => "Select the Service heritage provider",
+            SelectLocalHeritageProvider => "Select the Local heritage provider",
+            ProvideLocalWalletBackup => "Provide the descriptors backup",
+            ClickCreateHeirWalletButton => "Create the heir wallet",
+            ClickHeirWalletCard => "Open the heir wallet",
+            SynchronizeLocalHeritage => "Synchronize the heir wallet",
+            ModalExplainInheritancesList => "The inheritances list",
+            ModalHeirWaitForMaturity => "Waiting for maturity",
+            ClickInheritanceSpendButton => "Start claiming the inheritance",
+            ModalExplainInheritanceSpend => "Claiming an inheritance",
+            InputInheritanceSpendAddress => "Enter the destination address",
+            ClickInheritanceCreateTransaction => "Create the transaction",
+            ClickInheritanceSignTransaction => "Sign the transaction",
+            ModalInheritanceVerifyTransaction => "Verify the transaction",
+            HoverTransactionRecipientAddress => "Check the recipient address",
+            ClickInheritanceBroadcastTransaction => "Broadcast the transaction",
+            ModalFinishClaimingFirstInheritance => "First inheritance claimed",
+            InputName => "Choose a name",
+            SelectLocalKeyStorage => "Select the local key storage",
+            InputTheSeedPassword => "Enter the seed password",
+            SelectRestoreSeed => "Select the seed restoration",
+            RestoreKeyProviderSeed => "Restore the seed",
+        }
+    }
+
+    /// Returns `true` if the step is an explanation modal
+    pub fn is_modal(self) -> bool {
+        use OnboardingStep::*;
+        matches!(
+            self,
+            ModalCreateAccountOnTheService
+                | ModalInstallBlockchainProviderNode
+                | ModalExplainHeirJourney
+                | ModalExplainWalletSplit
+                | ModalExplainHeritageConfiguration
+                | ModalExplainLedgerPolicies
+                | ModalExplainHeritageBackup
+                | ModalFinishCreatingFirstWallet
+                | ModalExplainHeirs
+                | ModalExplainHeirKeyProvider
+                | ModalExplainExportHeirToServiceOptions
+                | ModalExplainStoreHeirMnemonic
+                | ModalMoreHeirOrWallet
+                | ModalLocalInheritance
+                | ModalExplainInheritancesList
+                | ModalHeirWaitForMaturity
+                | ModalExplainInheritanceSpend
+                | ModalInheritanceVerifyTransaction
+                | ModalFinishClaimingFirstInheritance
+        )
+    }
+
     fn message(self, context: &OnboardingContext) -> Option<CCStr> {
         use OnboardingContextItemId::*;
         use OnboardingStep::*;
@@ -150,6 +271,7 @@ impl OnboardingStep {
             // Before we start modals
             ModalCreateAccountOnTheService => None,
             ModalInstallBlockchainProviderNode => None,
+            ModalExplainHeirJourney => None,
             // Config
             ClickConnectService => Some(CCStr::from(
                 "Click on \"Status\" -> \"Connect\" to connect this application with the Heritage Service",
@@ -157,6 +279,16 @@ impl OnboardingStep {
             ConfigureBlockchainProvider => {
                 Some(CCStr::from("Click on \"Status\" -> \"Open Configuration\" and configure the blockchain provider settings"))
             }
+            // Hardware device
+            LedgerInstallBitcoinApp => Some(CCStr::from(
+                "Install the Bitcoin application on your Ledger using Ledger Live, then open it on the device",
+            )),
+            LedgerCheckNetwork => Some(CCStr::from(
+                "Open the Bitcoin application matching the network of this application",
+            )),
+            LedgerVerifyTestDerivation => Some(CCStr::from(
+                "Click \"Run the Test\" to verify your Ledger derives your keys",
+            )),
             // Wallet
             ClickCreateWalletCard => Some(CCStr::from("Click on the \"Create Wallet\" card")),
             ModalExplainWalletSplit => None,
@@ -248,6 +380,9 @@ impl OnboardingStep {
             ClickCreateHeirWalletCard => {
                 Some(CCStr::from("Click on the \"Create Heir Wallet\" card"))
             }
+            SelectServiceHeritageProvider => {
+                Some(CCStr::from("Select the \"Service\" Heritage Provider"))
+            }
             SelectLocalHeritageProvider => {
                 Some(CCStr::from("Select the \"Local\" Heritage Provider"))
             }
@@ -267,6 +402,7 @@ impl OnboardingStep {
             }
             SynchronizeLocalHeritage => Some(CCStr::from("Synchronize your local Heir Wallet")),
             ModalExplainInheritancesList => None,
+            ModalHeirWaitForMaturity => None,
             ClickInheritanceSpendButton => {
                 Some(CCStr::from("Click the \"Spend Inheritance\" button"))
             }
@@ -308,15 +444,131 @@ impl OnboardingStep {
         }
     }
 
+    /// The [OnboardingChapter] this step belongs to.
+    /// Creation commons steps return `None` as they belong to the chapter of the steps preceding them
+    fn chapter(self) -> Option<OnboardingChapter> {
+        use OnboardingStep::*;
+        match self {
+            ModalCreateAccountOnTheService
+            | ModalInstallBlockchainProviderNode
+            | ClickConnectService
+            | ConfigureBlockchainProvider => Some(OnboardingChapter::Configuration),
+            LedgerInstallBitcoinApp | LedgerCheckNetwork | LedgerVerifyTestDerivation => {
+                Some(OnboardingChapter::HardwareDevice)
+            }
+            ClickCreateWalletCard
+            | ModalExplainWalletSplit
+            | EnsureLedgerIsConnected
+            | SelectLocalOnlineWallet
+            | ClickCreateWalletButton => Some(OnboardingChapter::Wallet),
+            ModalExplainHeirs
+            | ClickCreateHeirCard
+            | InputBackupHeirName
+            | ModalExplainHeirKeyProvider
+            | ClickExportHeirToService
+            | InputEmailAddress
+            | ModalExplainExportHeirToServiceOptions
+            | ClickCreateHeirButton
+            | ClickHeirShowMnemonic
+            | ModalExplainStoreHeirMnemonic
+            | CheckHeirRevealMnemonic
+            | HoverHeirMnemonic
+            | CloseHeirShowMnemonic
+            | CheckConfirmStripHeirSeed
+            | StripHeirSeed
+            | ModalMoreHeirOrWallet
+            | ClickHeirCard => Some(OnboardingChapter::Heir),
+            ClickWalletCardAfterHeirsCreation
+            | OpenWalletConfiguration
+            | ClickCreateHeritageConfigurationButton1
+            | ModalExplainHeritageConfiguration
+            | ClickCreateHeritageConfigurationButton2
+            | ModalExplainLedgerPolicies
+            | ClickRegisterLedgerPolicies
+            | ClickWalletBackFromConfig
+            | ModalExplainHeritageBackup
+            | ClickBackupDescriptors
+            | ClickSaveBackup
+            | ClickWalletReceive
+            | ModalFinishCreatingFirstWallet => Some(OnboardingChapter::HeritageConfiguration),
+            ModalExplainHeirJourney
+            | ModalLocalInheritance
+            | ClickCreateHeirWalletCard
+            | SelectServiceHeritageProvider
+            | SelectLocalHeritageProvider
+            | ProvideLocalWalletBackup
+            | ClickCreateHeirWalletButton => Some(OnboardingChapter::HeirWallet),
+            ClickHeirWalletCard
+            | SynchronizeLocalHeritage
+            | ModalExplainInheritancesList
+            | ModalHeirWaitForMaturity
+            | ClickInheritanceSpendButton
+            | ModalExplainInheritanceSpend
+            | InputInheritanceSpendAddress
+            | ClickInheritanceCreateTransaction
+            | ClickInheritanceSignTransaction
+            | ModalInheritanceVerifyTransaction
+            | HoverTransactionRecipientAddress
+            | ClickInheritanceBroadcastTransaction
+            | ModalFinishClaimingFirstInheritance => Some(OnboardingChapter::Inheritance),
+            InputName
+            | SelectLocalKeyStorage
+            | InputTheSeedPassword
+            | SelectRestoreSeed
+            | RestoreKeyProviderSeed => None,
+        }
+    }
+
+    /// The context items that must be present for [OnboardingStep::message] and
+    /// [OnboardingStep::associated_route] to work with this step
+    fn required_context(self) -> &'static [OnboardingContextItemId] {
+        use OnboardingContextItemId::*;
+        use OnboardingStep::*;
+        match self {
+            ClickWalletCardAfterHeirsCreation
+            | OpenWalletConfiguration
+            | ClickCreateHeritageConfigurationButton1
+            | ModalExplainHeritageConfiguration
+            | ClickCreateHeritageConfigurationButton2
+            | ModalExplainLedgerPolicies
+            | ClickRegisterLedgerPolicies
+            | ClickWalletBackFromConfig
+            | ModalExplainHeritageBackup
+            | ClickBackupDescriptors
+            | ClickSaveBackup
+            | ClickWalletReceive
+            | ModalFinishCreatingFirstWallet => &[WalletName],
+            ClickHeirCard => &[HeirName],
+            ClickHeirWalletCard
+            | SynchronizeLocalHeritage
+            | ModalExplainInheritancesList
+            | ModalHeirWaitForMaturity
+            | ClickInheritanceSpendButton => &[HeirWalletName],
+            ModalExplainInheritanceSpend
+            | InputInheritanceSpendAddress
+            | ClickInheritanceCreateTransaction
+            | ClickInheritanceSignTransaction
+            | ModalInheritanceVerifyTransaction
+            | HoverTransactionRecipientAddress
+            | ClickInheritanceBroadcastTransaction
+            | ModalFinishClaimingFirstInheritance => &[HeirWalletName, HeritageId],
+            _ => &[],
+        }
+    }
+
     /// When reloading an in_progress onboarding, can tell on which
     /// view the user is supposed to be for this step
     fn associated_route(self, context: &OnboardingContext) -> Option<Route> {
         use OnboardingContextItemId::*;
         use OnboardingStep::*;
         match self {
-            ModalCreateAccountOnTheService | ModalInstallBlockchainProviderNode => None,
+            ModalCreateAccountOnTheService
+            | ModalInstallBlockchainProviderNode
+            | ModalExplainHeirJourney => None,
             ClickConnectService => None,
             ConfigureBlockchainProvider => Some(Route::AppConfigView {}),
+            // The hardware device guide is displayed over any view
+            LedgerInstallBitcoinApp | LedgerCheckNetwork | LedgerVerifyTestDerivation => None,
             ClickCreateWalletCard => Some(Route::WalletListView {}),
             ModalExplainWalletSplit
             | EnsureLedgerIsConnected
@@ -368,12 +620,14 @@ impl OnboardingStep {
             | StripHeirSeed => Some(Route::HeirListView {}),
             ModalLocalInheritance => None,
             ClickCreateHeirWalletCard => Some(Route::HeirWalletListView {}),
-            SelectLocalHeritageProvider
+            SelectServiceHeritageProvider
+            | SelectLocalHeritageProvider
             | ProvideLocalWalletBackup
             | ClickCreateHeirWalletButton => Some(Route::HeirWalletCreateView {}),
             ClickHeirWalletCard => Some(Route::HeirWalletListView {}),
             SynchronizeLocalHeritage
             | ModalExplainInheritancesList
+            | ModalHeirWaitForMaturity
             | ClickInheritanceSpendButton => {
                 let heirwallet_name = CCStr::from(
                     context
@@ -419,6 +673,31 @@ impl OnboardingStep {
     }
 }
 
+/// Group of consecutive [OnboardingStep]s that can be restarted together
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnboardingChapter {
+    Configuration,
+    HardwareDevice,
+    Wallet,
+    Heir,
+    HeritageConfiguration,
+    HeirWallet,
+    Inheritance,
+}
+impl OnboardingChapter {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Configuration => "Configure the providers",
+            Self::HardwareDevice => "Prepare your hardware device",
+            Self::Wallet => "Create your first wallet",
+            Self::Heir => "Create your first heir",
+            Self::HeritageConfiguration => "Configure the heritage of your wallet",
+            Self::HeirWallet => "Restore your heir wallet",
+            Self::Inheritance => "Claim your inheritance",
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Onboarding {
     steps: Vec<OnboardingStep>,
@@ -518,10 +797,116 @@ impl Onboarding {
         &self.context
     }
 
+    /// The steps of this onboarding, in order
+    pub fn steps(&self) -> &[OnboardingStep] {
+        &self.steps
+    }
+
+    /// Index of the current step in [Onboarding::steps], equal to its length if finished
+    pub fn current_step_index(&self) -> usize {
+        self.current_step
+    }
+
+    /// The route associated with the given step, if any and if the context it needs is present
+    pub fn step_route(&self, step: OnboardingStep) -> Option<Route> {
+        step.required_context()
+            .iter()
+            .all(|id| self.context.get_first_context(*id).is_some())
+            .then(|| step.associated_route(&self.context))
+            .flatten()
+    }
+
+    /// The chapter of the current step, if the onboarding is not finished
+    pub fn current_chapter(&self) -> Option<OnboardingChapter> {
+        if self.finished() {
+            return None;
+        }
+        self.chapters()
+            .into_iter()
+            .take_while(|(_, start)| *start <= self.current_step)
+            .last()
+            .map(|(chapter, _)| chapter)
+    }
+
+    /// List the chapters of this onboarding, in order, with the index of their first step
+    pub fn chapters(&self) -> Vec<(OnboardingChapter, usize)> {
+        let mut chapters: Vec<(OnboardingChapter, usize)> = Vec::new();
+        for (idx, chapter) in self
+            .steps
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, step)| step.chapter().map(|c| (idx, c)))
+        {
+            if chapters.last().is_none_or(|(last, _)| *last != chapter) {
+                chapters.push((chapter, idx));
+            }
+        }
+        chapters
+    }
+
+    /// List the chapters that were already started, and can therefore be restarted
+    pub fn restartable_chapters(&self) -> Vec<OnboardingChapter> {
+        self.chapters()
+            .into_iter()
+            .filter(|(_, start)| *start <= self.current_step)
+            .map(|(chapter, _)| chapter)
+            .collect()
+    }
+
+    /// Restarts the onboarding at the first step of the given chapter.
+    ///
+    /// Only chapters that were already started can be restarted. Returns `true` if the
+    /// onboarding was effectively moved back.
+    pub fn restart_chapter(&mut self, chapter: OnboardingChapter) -> bool {
+        match self
+            .chapters()
+            .into_iter()
+            .find(|(c, start)| *c == chapter && *start <= self.current_step)
+        {
+            Some((_, start)) => {
+                self.current_step = start;
+                self.is_paused = false;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns `true` if the current step can be skipped, i.e. the following step
+    /// does not need context the skipped step was supposed to provide
+    pub fn can_skip_step(&self) -> bool {
+        if self.finished() {
+            return false;
+        }
+        self.steps.get(self.current_step + 1).is_none_or(|next| {
+            next.required_context()
+                .iter()
+                .all(|id| self.context.get_first_context(*id).is_some())
+        })
+    }
+
+    /// Skips the current step, if [Onboarding::can_skip_step] allows it
+    pub fn skip_step(&mut self) {
+        if self.can_skip_step() {
+            self.current_step += 1;
+        }
+    }
+
     /// exclusive: Make sure to remove any other context sharing the same enum variant before adding this one
     pub fn add_context(&mut self, context: OnboardingContextItem, exclusive: Exclusive) {
         self.context.add_context(context, exclusive);
     }
+
+    /// Follow the rename of an item referenced by the context
+    pub fn rename_context(
+        &mut self,
+        context_key: OnboardingContextItemId,
+        old_content: &str,
+        new_content: &str,
+    ) {
+        self.context
+            .rename_context(context_key, old_content, new_content)
+    }
 }
 
 /// Builder for creating [Onboarding] instances
@@ -0,0 +1,49 @@
+use dioxus::prelude::*;
+
+use std::collections::{HashMap, VecDeque};
+
+use btc_heritage_wallet::btc_heritage::utils::timestamp_now;
+
+use crate::utils::CCStr;
+
+use super::{
+    event_bus::{subscribe_event, EventBus},
+    event_hooks::{MonitoringEvent, MonitoringEventKind},
+};
+
+/// Maximum number of entries kept in the session activity of each wallet
+const MAX_WALLET_ACTIVITY: usize = 50;
+
+/// A [MonitoringEvent] of the current session, recorded in the [WALLET_ACTIVITY] of its wallet
+#[derive(Debug, Clone, PartialEq)]
+pub struct WalletActivity {
+    pub kind: MonitoringEventKind,
+    pub message: CCStr,
+    pub timestamp: u64,
+}
+
+/// Activity of the current session, by wallet name, most recent first
+pub static WALLET_ACTIVITY: GlobalSignal<HashMap<CCStr, VecDeque<WalletActivity>>> =
+    Signal::global(|| HashMap::new());
+
+/// Wallet activity service, recording every [MonitoringEvent] into the [WALLET_ACTIVITY] log
+pub(super) fn use_wallet_activity_service(event_bus: EventBus) {
+    use_hook(move || {
+        log::info!("wallet_activity_service - start");
+        subscribe_event(event_bus, move |event: MonitoringEvent| {
+            let MonitoringEvent {
+                kind,
+                wallet_name,
+                message,
+            } = event;
+            let mut wallet_activity = WALLET_ACTIVITY.write();
+            let wallet_activity = wallet_activity.entry(wallet_name).or_default();
+            wallet_activity.truncate(MAX_WALLET_ACTIVITY - 1);
+            wallet_activity.push_front(WalletActivity {
+                kind,
+                message,
+                timestamp: timestamp_now(),
+            });
+        });
+    })
+}
@@ -0,0 +1,192 @@
+use dioxus::prelude::*;
+
+use btc_heritage_wallet::DatabaseSingleItem;
+use chrono::Timelike;
+use futures_util::stream::StreamExt;
+use serde::{Deserialize, Serialize};
+
+use crate::{components::alerts::AlertCategory, prelude::alert_error};
+
+use super::{
+    database::{DatabaseCommand, DatabaseReloadEvent},
+    event_bus::{subscribe_event, EventBus},
+};
+
+/// Where the alerts of an [AlertCategory] are displayed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AlertRoute {
+    /// As a toast, and in the notification center
+    #[default]
+    Toast,
+    /// Only in the notification center
+    NotificationCenter,
+}
+
+/// How the alerts are displayed to the user
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlertRoutingConfig {
+    pub general: AlertRoute,
+    pub sync: AlertRoute,
+    pub security: AlertRoute,
+    /// Route every alert to the notification center only during the do-not-disturb hours
+    pub do_not_disturb: bool,
+    /// Local hour (0-23) the do-not-disturb period starts
+    pub dnd_start_hour: u32,
+    /// Local hour (0-23) the do-not-disturb period ends, it may be before the start
+    /// to span midnight
+    pub dnd_end_hour: u32,
+}
+impl Default for AlertRoutingConfig {
+    fn default() -> Self {
+        Self {
+            general: AlertRoute::Toast,
+            sync: AlertRoute::Toast,
+            security: AlertRoute::Toast,
+            do_not_disturb: false,
+            dnd_start_hour: 22,
+            dnd_end_hour: 8,
+        }
+    }
+}
+impl AlertRoutingConfig {
+    pub fn route(&self, category: AlertCategory) -> AlertRoute {
+        match category {
+            AlertCategory::General => self.general,
+            AlertCategory::Sync => self.sync,
+            AlertCategory::Security => self.security,
+        }
+    }
+
+    pub fn route_mut(&mut self, category: AlertCategory) -> &mut AlertRoute {
+        match category {
+            AlertCategory::General => &mut self.general,
+            AlertCategory::Sync => &mut self.sync,
+            AlertCategory::Security => &mut self.security,
+        }
+    }
+
+    /// Whether the current local time is in the do-not-disturb period
+    pub fn in_do_not_disturb(&self) -> bool {
+        if !self.do_not_disturb {
+            return false;
+        }
+        let hour = chrono::Local::now().hour();
+        if self.dnd_start_hour <= self.dnd_end_hour {
+            self.dnd_start_hour <= hour && hour < self.dnd_end_hour
+        } else {
+            self.dnd_start_hour <= hour || hour < self.dnd_end_hour
+        }
+    }
+
+    /// Whether an alert of the given category should be displayed as a toast now
+    pub fn shows_toast(&self, category: AlertCategory) -> bool {
+        self.route(category) == AlertRoute::Toast && !self.in_do_not_disturb()
+    }
+}
+
+impl DatabaseSingleItem for AlertRoutingConfig {
+    fn item_key() -> &'static str {
+        "gui_alert_routing"
+    }
+}
+
+pub static ALERT_ROUTING_CONFIG: GlobalSignal<AlertRoutingConfig> =
+    Signal::global(|| AlertRoutingConfig::default());
+
+/// Commands for the alert routing service
+#[derive(Debug)]
+pub(super) enum AlertRoutingCommand {
+    /// Internal trigger a save into the DB
+    Persist { config: AlertRoutingConfig },
+    /// Internal trigger a refresh from the DB
+    Refresh,
+}
+
+/// Alert routing service coroutine
+pub(super) fn use_alert_routing_service(
+    event_bus: EventBus,
+    database_service: Coroutine<DatabaseCommand>,
+) -> Coroutine<AlertRoutingCommand> {
+    let service_handle = use_coroutine(
+        move |mut rx: UnboundedReceiver<AlertRoutingCommand>| async move {
+            log::info!("alert_routing_service (coroutine) - start");
+
+            let mut cached_value = load_config(database_service).await;
+            *ALERT_ROUTING_CONFIG.write() = cached_value;
+
+            // Flag to trash the first persist command that will come from the initial run of the "use_effect"
+            let mut discard_next_persit = true;
+
+            while let Some(cmd) = rx.next().await {
+                log::debug!("alert_routing_service (coroutine) - Processing command {cmd:?}...");
+
+                match cmd {
+                    AlertRoutingCommand::Persist { config } => {
+                        if discard_next_persit {
+                            log::debug!(
+                                "alert_routing_service (coroutine) - Ignoring first Persist cmd."
+                            );
+                            discard_next_persit = false;
+                        }
+                        if config != cached_value {
+                            log::debug!(
+                                "alert_routing_service (coroutine) - Config changed, persisting..."
+                            );
+                            cached_value = config;
+                            match save_config(database_service, config).await {
+                                Ok(_) => (),
+                                Err(msg) => {
+                                    log::error!("{msg}");
+                                    alert_error(msg);
+                                }
+                            };
+                        } else {
+                            log::debug!("alert_routing_service (coroutine) - Ignoring Persist cmd: already in database.");
+                        }
+                    }
+                    AlertRoutingCommand::Refresh => {
+                        cached_value = load_config(database_service).await;
+                        *ALERT_ROUTING_CONFIG.write() = cached_value
+                    }
+                }
+
+                log::debug!("alert_routing_service (coroutine) - Command processed");
+            }
+        },
+    );
+    subscribe_event(event_bus, move |_event: DatabaseReloadEvent| {
+        service_handle.send(AlertRoutingCommand::Refresh);
+    });
+    use_effect(move || {
+        service_handle.send(AlertRoutingCommand::Persist {
+            config: ALERT_ROUTING_CONFIG(),
+        });
+    });
+    service_handle
+}
+
+async fn load_config(database_service: Coroutine<DatabaseCommand>) -> AlertRoutingConfig {
+    match super::helpers::blocking_db_service_operation(
+        database_service,
+        "Load the alert routing config",
+        |db| AlertRoutingConfig::load(&db),
+    )
+    .await
+    {
+        Ok(config) => config,
+        Err(_) => AlertRoutingConfig::default(),
+    }
+}
+
+async fn save_config(
+    database_service: Coroutine<DatabaseCommand>,
+    config: AlertRoutingConfig,
+) -> Result<(), String> {
+    super::helpers::blocking_db_service_operation(
+        database_service,
+        "Save the alert routing config",
+        move |mut db| config.save(&mut db),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
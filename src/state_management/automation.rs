@@ -0,0 +1,532 @@
+use dioxus::prelude::*;
+
+use std::net::Ipv4Addr;
+
+use btc_heritage_wallet::{
+    btc_heritage::{
+        bitcoincore_rpc::jsonrpc::serde_json::{self, json, Value},
+        utils::timestamp_now,
+    },
+    heritage_service_api_client::{NewTx, NewTxFeePolicy, NewTxRecipient, NewTxSpendingConfig},
+    DatabaseSingleItem, OnlineWallet, Wallet,
+};
+use futures_util::stream::StreamExt;
+use serde::{Deserialize, Serialize};
+use tokio::{
+    io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+};
+
+use crate::{
+    prelude::{alert_error, heir_spendable_ts, heritage_config_expiration_ts, ExpirationStatus},
+    utils::{heir_config_type_to_string, CCStr},
+};
+
+use super::{
+    blockchain::BlockchainProviderCommand,
+    database::{DatabaseCommand, DatabaseReloadEvent},
+    event_bus::{subscribe_event, EventBus},
+    service::ServiceClientCommand,
+};
+
+/// Maximum size of the requests accepted by the automation interface
+const AUTOMATION_MAX_REQUEST_LEN: usize = 64 * 1024;
+
+/// Time given to a client to send its whole request
+const AUTOMATION_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Local JSON-RPC interface allowing scripts to query the wallets
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AutomationConfig {
+    /// Start the interface, listening on the loopback interface only
+    pub enabled: bool,
+    pub port: u16,
+    /// Secret expected in the `Authorization: Bearer` header of the requests
+    pub token: String,
+    /// Allow the actions (synchronization, PSBT creation) besides the read-only queries
+    pub allow_actions: bool,
+}
+impl AutomationConfig {
+    pub fn new_token() -> String {
+        uuid::Uuid::new_v4().simple().to_string()
+    }
+}
+impl Default for AutomationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 9737,
+            token: Self::new_token(),
+            allow_actions: false,
+        }
+    }
+}
+
+impl DatabaseSingleItem for AutomationConfig {
+    fn item_key() -> &'static str {
+        "gui_automation"
+    }
+}
+
+pub static AUTOMATION_CONFIG: GlobalSignal<AutomationConfig> =
+    Signal::global(|| AutomationConfig::default());
+
+/// Commands for the automation service
+#[derive(Debug)]
+pub(super) enum AutomationCommand {
+    /// Internal trigger a save into the DB
+    Persist { config: AutomationConfig },
+    /// Internal trigger a refresh from the DB
+    Refresh,
+}
+
+/// The services used to answer the requests
+#[derive(Debug, Clone, Copy)]
+struct AutomationServices {
+    database_service: Coroutine<DatabaseCommand>,
+    service_client_service: Coroutine<ServiceClientCommand>,
+    blockchain_provider_service: Coroutine<BlockchainProviderCommand>,
+}
+
+/// Automation service coroutine, (re)starting the JSON-RPC server when its configuration changes
+pub(super) fn use_automation_service(
+    event_bus: EventBus,
+    database_service: Coroutine<DatabaseCommand>,
+    service_client_service: Coroutine<ServiceClientCommand>,
+    blockchain_provider_service: Coroutine<BlockchainProviderCommand>,
+) -> Coroutine<AutomationCommand> {
+    let services = AutomationServices {
+        database_service,
+        service_client_service,
+        blockchain_provider_service,
+    };
+    let service_handle = use_coroutine(
+        move |mut rx: UnboundedReceiver<AutomationCommand>| async move {
+            log::info!("automation_service (coroutine) - start");
+
+            let mut cached_value = load_config(database_service).await;
+            *AUTOMATION_CONFIG.write() = cached_value.clone();
+            let mut server = start_server(&cached_value, services);
+
+            // Flag to trash the first persist command that will come from the initial run of the "use_effect"
+            let mut discard_next_persit = true;
+
+            while let Some(cmd) = rx.next().await {
+                log::debug!("automation_service (coroutine) - Processing command {cmd:?}...");
+
+                match cmd {
+                    AutomationCommand::Persist { config } => {
+                        if discard_next_persit {
+                            log::debug!(
+                                "automation_service (coroutine) - Ignoring first Persist cmd."
+                            );
+                            discard_next_persit = false;
+                            continue;
+                        }
+                        if config != cached_value {
+                            log::debug!(
+                                "automation_service (coroutine) - Config changed, persisting..."
+                            );
+                            cached_value = config;
+                            if let Err(msg) =
+                                save_config(database_service, cached_value.clone()).await
+                            {
+                                log::error!("{msg}");
+                                alert_error(msg);
+                            }
+                            if let Some(task) = server.take() {
+                                task.cancel();
+                            }
+                            server = start_server(&cached_value, services);
+                        } else {
+                            log::debug!("automation_service (coroutine) - Ignoring Persist cmd: already in database.");
+                        }
+                    }
+                    AutomationCommand::Refresh => {
+                        cached_value = load_config(database_service).await;
+                        *AUTOMATION_CONFIG.write() = cached_value.clone();
+                        if let Some(task) = server.take() {
+                            task.cancel();
+                        }
+                        server = start_server(&cached_value, services);
+                    }
+                }
+
+                log::debug!("automation_service (coroutine) - Command processed");
+            }
+        },
+    );
+    subscribe_event(event_bus, move |_event: DatabaseReloadEvent| {
+        service_handle.send(AutomationCommand::Refresh);
+    });
+    use_effect(move || {
+        service_handle.send(AutomationCommand::Persist {
+            config: AUTOMATION_CONFIG(),
+        });
+    });
+    service_handle
+}
+
+fn start_server(config: &AutomationConfig, services: AutomationServices) -> Option<Task> {
+    config
+        .enabled
+        .then(|| spawn(serve(config.clone(), services)))
+}
+
+async fn serve(config: AutomationConfig, services: AutomationServices) {
+    let listener = match TcpListener::bind((Ipv4Addr::LOCALHOST, config.port)).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            let msg = format!(
+                "Could not start the automation interface on port {}: {e}",
+                config.port
+            );
+            log::error!("{msg}");
+            alert_error(msg);
+            return;
+        }
+    };
+    log::info!(
+        "Automation interface listening on 127.0.0.1:{}",
+        config.port
+    );
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                spawn(handle_connection(stream, config.clone(), services));
+            }
+            Err(e) => log::warn!("Automation interface - Could not accept connection: {e}"),
+        }
+    }
+}
+
+fn http_response(status: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+        Connection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+/// Read an HTTP request, returning its method, `Authorization` header and body
+///
+/// The request line and the headers together, and the body, are each limited to
+/// [AUTOMATION_MAX_REQUEST_LEN]: the caller bounds the `reader` accordingly.
+async fn read_http_request(
+    mut reader: impl AsyncBufRead + Unpin,
+) -> Result<(String, Option<String>, Vec<u8>), String> {
+    let mut request_line = String::new();
+    let mut headers_len = reader
+        .read_line(&mut request_line)
+        .await
+        .map_err(|e| e.to_string())?;
+    let http_method = request_line
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_owned();
+
+    let mut content_length = 0;
+    let mut authorization = None;
+    loop {
+        let mut header = String::new();
+        let read = reader
+            .read_line(&mut header)
+            .await
+            .map_err(|e| e.to_string())?;
+        headers_len += read;
+        if read == 0 || headers_len > AUTOMATION_MAX_REQUEST_LEN {
+            return Err("Invalid request headers".to_owned());
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            match name.trim().to_ascii_lowercase().as_str() {
+                "content-length" => {
+                    content_length = value.trim().parse().map_err(|_| "Invalid Content-Length")?
+                }
+                "authorization" => authorization = Some(value.trim().to_owned()),
+                _ => (),
+            }
+        }
+    }
+    if content_length > AUTOMATION_MAX_REQUEST_LEN {
+        return Err("Request too large".to_owned());
+    }
+    let mut body = vec![0u8; content_length];
+    reader
+        .read_exact(&mut body)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok((http_method, authorization, body))
+}
+
+async fn handle_connection(
+    stream: TcpStream,
+    config: AutomationConfig,
+    services: AutomationServices,
+) {
+    let mut reader = BufReader::new(stream);
+    // An idle or endless client must not hold the task forever
+    let request = tokio::time::timeout(
+        AUTOMATION_READ_TIMEOUT,
+        read_http_request((&mut reader).take(2 * AUTOMATION_MAX_REQUEST_LEN as u64)),
+    )
+    .await
+    .unwrap_or_else(|_| Err("Timed out reading the request".to_owned()));
+    let expected_authorization = format!("Bearer {}", config.token);
+    let response = match request {
+        Err(e) => {
+            log::warn!("Automation interface - Invalid request: {e}");
+            http_response("400 Bad Request", "")
+        }
+        Ok((http_method, _, _)) if http_method != "POST" => {
+            http_response("405 Method Not Allowed", "")
+        }
+        Ok((_, authorization, _))
+            if !constant_time_eq(
+                authorization.as_deref().unwrap_or_default().as_bytes(),
+                expected_authorization.as_bytes(),
+            ) =>
+        {
+            log::warn!("Automation interface - Rejected unauthorized request");
+            http_response("401 Unauthorized", "")
+        }
+        Ok((_, _, body)) => {
+            let response = match serde_json::from_slice::<Value>(&body) {
+                Ok(request) => {
+                    let id = request["id"].clone();
+                    let method = request["method"].as_str().unwrap_or_default();
+                    log::info!("Automation interface - Processing {method}");
+                    match handle_rpc(method, &request["params"], &config, services).await {
+                        Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+                        Err((code, message)) => json!({
+                            "jsonrpc": "2.0",
+                            "id": id,
+                            "error": {"code": code, "message": message},
+                        }),
+                    }
+                }
+                Err(e) => json!({
+                    "jsonrpc": "2.0",
+                    "id": null,
+                    "error": {"code": -32700, "message": e.to_string()},
+                }),
+            };
+            http_response("200 OK", &response.to_string())
+        }
+    };
+    if let Err(e) = reader.get_mut().write_all(response.as_bytes()).await {
+        log::warn!("Automation interface - Could not send response: {e}");
+    }
+}
+
+/// Compare a secret without revealing through the timing where the first difference is
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    let diff = a.iter().zip(b).fold(0u8, |diff, (a, b)| diff | (a ^ b));
+    core::hint::black_box(diff) == 0 && a.len() == b.len()
+}
+
+type RpcError = (i64, String);
+
+fn invalid_params(message: &str) -> RpcError {
+    (-32602, message.to_owned())
+}
+fn server_error<E: core::fmt::Display>(e: E) -> RpcError {
+    (-32000, e.to_string())
+}
+
+/// Answer a JSON-RPC request
+///
+/// Available methods:
+/// - `listwallets`: names of the wallets
+/// - `getwalletstatus {wallet}`: balances (in sat) and last synchronization of a wallet
+/// - `listheritageconfigs {wallet}`: Heritage Configurations of a wallet, current first
+/// - `getexpirations`: expiration of the current Heritage Configuration of every wallet
+/// - `syncwallet {wallet}`: synchronize a wallet (action)
+/// - `createpsbt {wallet, recipients: [{address, amount}], fee_rate?}`: create an
+///   unsigned PSBT paying the recipients, amounts in sat and fee rate in sat/vB (action)
+async fn handle_rpc(
+    method: &str,
+    params: &Value,
+    config: &AutomationConfig,
+    services: AutomationServices,
+) -> Result<Value, RpcError> {
+    let is_action = matches!(method, "syncwallet" | "createpsbt");
+    if is_action && !config.allow_actions {
+        return Err((-32001, format!("Action {method} is not allowed")));
+    }
+    match method {
+        "listwallets" => {
+            let wallet_names = super::helpers::list_wallet_names(services.database_service)
+                .await
+                .map_err(server_error)?;
+            Ok(json!(wallet_names))
+        }
+        "getwalletstatus" => {
+            let wallet = load_wallet(params, services).await?;
+            let wallet_status = wallet.get_wallet_status().await.map_err(server_error)?;
+            let balance = wallet_status.balance.total_balance();
+            Ok(json!({
+                "balance": balance.get_total(),
+                "spendable": balance.get_spendable(),
+                "last_sync_ts": wallet_status.last_sync_ts,
+            }))
+        }
+        "listheritageconfigs" => {
+            let wallet = load_wallet(params, services).await?;
+            let subwallet_configs = wallet
+                .list_subwallet_configs()
+                .await
+                .map_err(server_error)?;
+            let heirs = super::helpers::list_heirs(services.database_service)
+                .await
+                .map_err(server_error)?;
+            Ok(json!(subwallet_configs
+                .iter()
+                .enumerate()
+                .map(|(idx, swcm)| {
+                    let heritage_config = &swcm.heritage_config;
+                    let heirs = heritage_config
+                        .iter_heir_configs()
+                        .map(|hc| {
+                            json!({
+                                "name": heirs
+                                    .iter()
+                                    .find(|heir| heir.heir_config == *hc)
+                                    .map(|heir| heir.name()),
+                                "fingerprint": hc.fingerprint().to_string(),
+                                "type": heir_config_type_to_string(hc),
+                                "spendable_ts": heir_spendable_ts(heritage_config, hc),
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    json!({
+                        "current": idx == 0,
+                        "firstuse_ts": swcm.firstuse_ts,
+                        "expiration_ts": heritage_config_expiration_ts(heritage_config),
+                        "heirs": heirs,
+                    })
+                })
+                .collect::<Vec<_>>()))
+        }
+        "getexpirations" => {
+            let wallet_names = super::helpers::list_wallet_names(services.database_service)
+                .await
+                .map_err(server_error)?;
+            let now = timestamp_now();
+            let mut expirations = vec![];
+            for wallet_name in wallet_names {
+                let wallet = get_wallet(wallet_name.clone(), services).await?;
+                let subwallet_configs = wallet
+                    .list_subwallet_configs()
+                    .await
+                    .map_err(server_error)?;
+                // The first subwallet config is the current one
+                let expiration_ts = subwallet_configs.first().and_then(expiration_ts);
+                let status = match expiration_ts {
+                    Some(ts) if ts < now => "expired",
+                    Some(ts) if ts < now + ExpirationStatus::SOON => "expire_soon",
+                    Some(_) => "current",
+                    None => "none",
+                };
+                expirations.push(json!({
+                    "wallet": wallet_name,
+                    "expiration_ts": expiration_ts,
+                    "status": status,
+                }));
+            }
+            Ok(json!(expirations))
+        }
+        "syncwallet" => {
+            let wallet_name = wallet_name_param(params)?;
+            let mut wallet = get_wallet(wallet_name.clone(), services).await?;
+            super::background::sync_wallet(&wallet_name, &mut wallet)
+                .await
+                .map_err(server_error)?;
+            Ok(json!(true))
+        }
+        "createpsbt" => {
+            let wallet = load_wallet(params, services).await?;
+            let recipients = params["recipients"]
+                .as_array()
+                .filter(|recipients| !recipients.is_empty())
+                .ok_or_else(|| invalid_params("Missing recipients"))?
+                .iter()
+                .map(|recipient| {
+                    Ok(NewTxRecipient {
+                        address: recipient["address"]
+                            .as_str()
+                            .ok_or_else(|| invalid_params("Missing recipient address"))?
+                            .to_owned(),
+                        amount: recipient["amount"]
+                            .as_u64()
+                            .ok_or_else(|| invalid_params("Missing recipient amount"))?,
+                    })
+                })
+                .collect::<Result<Vec<_>, RpcError>>()?;
+            let fee_policy = params["fee_rate"]
+                .as_f64()
+                .map(|rate| NewTxFeePolicy::Rate { rate: rate as f32 });
+            let new_tx = NewTx {
+                spending_config: NewTxSpendingConfig::Recipients(recipients),
+                fee_policy,
+                utxo_selection: None,
+                disable_rbf: None,
+            };
+            let (psbt, _) = wallet.create_psbt(new_tx).await.map_err(server_error)?;
+            Ok(json!({ "psbt": psbt.to_string() }))
+        }
+        _ => Err((-32601, format!("Method not found: {method}"))),
+    }
+}
+
+fn wallet_name_param(params: &Value) -> Result<CCStr, RpcError> {
+    params["wallet"]
+        .as_str()
+        .map(CCStr::from)
+        .ok_or_else(|| invalid_params("Missing wallet"))
+}
+
+async fn load_wallet(params: &Value, services: AutomationServices) -> Result<Wallet, RpcError> {
+    get_wallet(wallet_name_param(params)?, services).await
+}
+
+async fn get_wallet(wallet_name: CCStr, services: AutomationServices) -> Result<Wallet, RpcError> {
+    super::helpers::get_wallet(
+        services.database_service,
+        services.service_client_service,
+        services.blockchain_provider_service,
+        wallet_name,
+    )
+    .await
+    .map_err(server_error)
+}
+
+async fn load_config(database_service: Coroutine<DatabaseCommand>) -> AutomationConfig {
+    match super::helpers::blocking_db_service_operation(
+        database_service,
+        "Load the automation config",
+        |db| AutomationConfig::load(&db),
+    )
+    .await
+    {
+        Ok(config) => config,
+        Err(_) => AutomationConfig::default(),
+    }
+}
+
+async fn save_config(
+    database_service: Coroutine<DatabaseCommand>,
+    config: AutomationConfig,
+) -> Result<(), String> {
+    super::helpers::blocking_db_service_operation(
+        database_service,
+        "Save the automation config",
+        move |mut db| config.save(&mut db),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
@@ -0,0 +1,577 @@
+use dioxus::prelude::*;
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+
+use btc_heritage_wallet::{
+    btc_heritage::utils::timestamp_now, DatabaseSingleItem, OnlineWallet, Wallet,
+};
+use futures_util::stream::StreamExt;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    components::alerts::{add_alert, Alert, AlertCategory},
+    prelude::{alert_error, heritage_config_expiration_ts, ExpirationStatus},
+    utils::CCStr,
+};
+
+use super::{
+    blockchain::BlockchainProviderCommand,
+    database::{DatabaseCommand, DatabaseReloadEvent},
+    event_bus::{publish_event, subscribe_event, EventBus},
+    event_hooks::{MonitoringEvent, MonitoringEventKind},
+    service::ServiceClientCommand,
+};
+
+/// Command line flag used by the login entry to start the application minimized
+pub const MINIMIZED_FLAG: &str = "--minimized";
+
+/// Background behavior of the application
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackgroundSyncConfig {
+    /// Register the application to start when the user logs in
+    pub start_on_login: bool,
+    /// When started at login, start with the window minimized to the taskbar (there is no tray icon)
+    pub start_minimized: bool,
+    /// Interval between two background synchronizations, 0 to disable them
+    pub sync_interval_minutes: u32,
+}
+impl Default for BackgroundSyncConfig {
+    fn default() -> Self {
+        Self {
+            start_on_login: false,
+            start_minimized: true,
+            sync_interval_minutes: 0,
+        }
+    }
+}
+
+impl DatabaseSingleItem for BackgroundSyncConfig {
+    fn item_key() -> &'static str {
+        "gui_background_sync"
+    }
+}
+
+pub static BACKGROUND_SYNC_CONFIG: GlobalSignal<BackgroundSyncConfig> =
+    Signal::global(|| BackgroundSyncConfig::default());
+
+/// Synchronization state of a wallet, as observed by the application
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WalletSyncState {
+    /// Timestamp of the next scheduled background synchronization, if any
+    pub next_sync_ts: Option<u64>,
+    /// Timestamp and message of the last failed synchronization, cleared by a successful one
+    pub last_error: Option<(u64, CCStr)>,
+    /// A synchronization of the wallet is running, see [sync_wallet]
+    pub syncing: bool,
+    /// Expiration timestamp of the Heritage Configuration already reported as expiring
+    /// soon (`false`) or expired (`true`), so the user is only warned on a change
+    pub reported_expiration: Option<(u64, bool)>,
+}
+
+/// Synchronization state of each wallet, by wallet name
+pub static WALLET_SYNC_STATES: GlobalSignal<HashMap<CCStr, WalletSyncState>> =
+    Signal::global(HashMap::new);
+
+/// Record the outcome of a synchronization of the wallet
+pub fn record_wallet_sync_result<E: core::fmt::Display>(
+    wallet_name: &CCStr,
+    result: Result<(), E>,
+) {
+    let last_error = result
+        .err()
+        .map(|e| (timestamp_now(), CCStr::from(e.to_string())));
+    WALLET_SYNC_STATES
+        .write()
+        .entry(wallet_name.clone())
+        .or_default()
+        .last_error = last_error;
+}
+
+/// Synchronize the wallet and record the outcome in its [WalletSyncState]
+///
+/// The UI, the background synchronization and the automation interface each load their
+/// own instance of the wallet: only one of them can synchronize it at a time.
+pub async fn sync_wallet(wallet_name: &CCStr, wallet: &mut Wallet) -> Result<(), String> {
+    log::debug!("sync_wallet({wallet_name}) - start");
+    {
+        let mut sync_states = WALLET_SYNC_STATES.write();
+        let sync_state = sync_states.entry(wallet_name.clone()).or_default();
+        if sync_state.syncing {
+            return Err(format!(
+                "Wallet {wallet_name} is already being synchronized"
+            ));
+        }
+        sync_state.syncing = true;
+    }
+    // Cleared even if the synchronization is cancelled
+    let _syncing = SyncingFlag(wallet_name.clone());
+    let result = wallet.sync().await.map_err(|e| e.to_string());
+    record_wallet_sync_result(wallet_name, result.as_ref().map(|_| ()));
+    log::debug!("sync_wallet - finished");
+    result
+}
+
+/// Clears the [WalletSyncState::syncing] flag of the wallet when dropped
+struct SyncingFlag(CCStr);
+impl Drop for SyncingFlag {
+    fn drop(&mut self) {
+        if let Some(sync_state) = WALLET_SYNC_STATES.write().get_mut(&self.0) {
+            sync_state.syncing = false;
+        }
+    }
+}
+
+/// Commands for the background sync service
+#[derive(Debug)]
+pub(super) enum BackgroundSyncCommand {
+    /// Internal trigger a save into the DB
+    Persist { config: BackgroundSyncConfig },
+    /// Internal trigger a refresh from the DB
+    Refresh,
+}
+
+/// Background sync service coroutine
+pub(super) fn use_background_sync_service(
+    event_bus: EventBus,
+    database_service: Coroutine<DatabaseCommand>,
+    service_client_service: Coroutine<ServiceClientCommand>,
+    blockchain_provider_service: Coroutine<BlockchainProviderCommand>,
+) -> Coroutine<BackgroundSyncCommand> {
+    let service_handle = use_coroutine(
+        move |mut rx: UnboundedReceiver<BackgroundSyncCommand>| async move {
+            log::info!("background_sync_service (coroutine) - start");
+
+            let mut cached_value = load_config(database_service).await;
+            *BACKGROUND_SYNC_CONFIG.write() = cached_value;
+
+            // Flag to trash the first persist command that will come from the initial run of the "use_effect"
+            let mut discard_next_persit = true;
+
+            while let Some(cmd) = rx.next().await {
+                log::debug!("background_sync_service (coroutine) - Processing command {cmd:?}...");
+
+                match cmd {
+                    BackgroundSyncCommand::Persist { config } => {
+                        if discard_next_persit {
+                            log::debug!(
+                                "background_sync_service (coroutine) - Ignoring first Persist cmd."
+                            );
+                            discard_next_persit = false;
+                        }
+                        if config != cached_value {
+                            log::debug!(
+                                "background_sync_service (coroutine) - Config changed, persisting..."
+                            );
+                            let login_entry_changed = config.start_on_login
+                                != cached_value.start_on_login
+                                || config.start_minimized != cached_value.start_minimized;
+                            cached_value = config;
+
+                            if login_entry_changed {
+                                if let Err(msg) = tokio::task::spawn_blocking(move || {
+                                    update_login_entry(config)
+                                })
+                                .await
+                                .unwrap()
+                                {
+                                    log::error!("{msg}");
+                                    alert_error(msg);
+                                }
+                            }
+
+                            match save_config(database_service, config).await {
+                                Ok(_) => (),
+                                Err(msg) => {
+                                    log::error!("{msg}");
+                                    alert_error(msg);
+                                }
+                            };
+                        } else {
+                            log::debug!("background_sync_service (coroutine) - Ignoring Persist cmd: already in database.");
+                        }
+                    }
+                    BackgroundSyncCommand::Refresh => {
+                        cached_value = load_config(database_service).await;
+                        *BACKGROUND_SYNC_CONFIG.write() = cached_value
+                    }
+                }
+
+                log::debug!("background_sync_service (coroutine) - Command processed");
+            }
+        },
+    );
+    subscribe_event(event_bus, move |_event: DatabaseReloadEvent| {
+        service_handle.send(BackgroundSyncCommand::Refresh);
+    });
+    use_effect(move || {
+        service_handle.send(BackgroundSyncCommand::Persist {
+            config: BACKGROUND_SYNC_CONFIG(),
+        });
+    });
+    use_future(move || async move {
+        // Minutes elapsed since the last background sync of each wallet
+        let mut elapsed_minutes = HashMap::<CCStr, u32>::new();
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+            let Ok(wallet_names) = super::helpers::list_wallet_names(database_service).await else {
+                continue;
+            };
+            let global_sync_interval_minutes = BACKGROUND_SYNC_CONFIG.peek().sync_interval_minutes;
+            let now = timestamp_now();
+            let mut due_wallet_names = Vec::new();
+            for wallet_name in wallet_names {
+                // The wallet settings may override the global interval
+                let sync_interval_minutes =
+                    super::helpers::load_wallet_settings(database_service, wallet_name.clone())
+                        .await
+                        .ok()
+                        .and_then(|wallet_settings| wallet_settings.sync_interval_minutes)
+                        .unwrap_or(global_sync_interval_minutes);
+                let elapsed = elapsed_minutes.entry(wallet_name.clone()).or_default();
+                *elapsed += 1;
+                let is_due = sync_interval_minutes != 0 && *elapsed >= sync_interval_minutes;
+                if is_due {
+                    *elapsed = 0;
+                }
+                let next_sync_ts = (sync_interval_minutes != 0)
+                    .then(|| now + u64::from(sync_interval_minutes - *elapsed) * 60);
+                WALLET_SYNC_STATES
+                    .write()
+                    .entry(wallet_name.clone())
+                    .or_default()
+                    .next_sync_ts = next_sync_ts;
+                if is_due {
+                    due_wallet_names.push(wallet_name);
+                }
+            }
+            if due_wallet_names.is_empty() {
+                continue;
+            }
+            background_sync(
+                event_bus,
+                database_service,
+                service_client_service,
+                blockchain_provider_service,
+                due_wallet_names,
+            )
+            .await;
+        }
+    });
+    use_future(move || async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+            remind_activation_workflows(database_service).await;
+        }
+    });
+    service_handle
+}
+
+/// Remind the heir of the maturity of the inheritances followed by an activation workflow
+async fn remind_activation_workflows(database_service: Coroutine<DatabaseCommand>) {
+    let Ok(heirwallet_names) = super::helpers::list_heirwallet_names(database_service).await else {
+        return;
+    };
+    let now = timestamp_now();
+    for heirwallet_name in heirwallet_names {
+        let Ok(Some(mut workflow)) =
+            super::helpers::load_activation_workflow(database_service, heirwallet_name.clone())
+                .await
+        else {
+            continue;
+        };
+        if !workflow.reminder_ts.is_some_and(|ts| ts <= now) {
+            continue;
+        }
+        workflow.reminder_ts = None;
+        match super::helpers::save_activation_workflow(
+            database_service,
+            heirwallet_name.clone(),
+            Some(workflow),
+        )
+        .await
+        {
+            Ok(()) => add_alert(
+                Alert::info(CCStr::from(format!(
+                    "An inheritance of heir wallet {heirwallet_name} has matured. \
+                    You can now claim it."
+                )))
+                .with_category(AlertCategory::Security),
+            ),
+            Err(e) => log::warn!(
+                "remind_activation_workflows - Could not save the workflow of {heirwallet_name}: {e}"
+            ),
+        }
+    }
+}
+
+/// Synchronize the given online wallets and warn the user about expiring Heritage Configurations
+///
+/// A [MonitoringEvent] is published for each newly confirmed transaction, failed
+/// synchronization and expiring Heritage Configuration.
+async fn background_sync(
+    event_bus: EventBus,
+    database_service: Coroutine<DatabaseCommand>,
+    service_client_service: Coroutine<ServiceClientCommand>,
+    blockchain_provider_service: Coroutine<BlockchainProviderCommand>,
+    wallet_names: Vec<CCStr>,
+) {
+    log::info!("background_sync - start");
+    let now = timestamp_now();
+    for wallet_name in wallet_names {
+        if WALLET_SYNC_STATES
+            .peek()
+            .get(&wallet_name)
+            .is_some_and(|sync_state| sync_state.syncing)
+        {
+            log::info!("background_sync - Wallet {wallet_name} is already being synchronized");
+            continue;
+        }
+        let mut wallet: Wallet = match super::helpers::get_wallet(
+            database_service,
+            service_client_service,
+            blockchain_provider_service,
+            wallet_name.clone(),
+        )
+        .await
+        {
+            Ok(wallet) => wallet,
+            Err(e) => {
+                log::warn!("background_sync - Could not load wallet {wallet_name}: {e}");
+                continue;
+            }
+        };
+        let publish = |kind, message: String| {
+            publish_event(
+                event_bus,
+                MonitoringEvent {
+                    kind,
+                    wallet_name: wallet_name.clone(),
+                    message: CCStr::from(message),
+                },
+            )
+        };
+        let unconfirmed_txids = wallet
+            .list_transactions()
+            .await
+            .map(|transactions| {
+                transactions
+                    .into_iter()
+                    .filter(|tx| tx.confirmation_time.is_none())
+                    .map(|tx| tx.txid)
+                    .collect::<HashSet<_>>()
+            })
+            .unwrap_or_default();
+        if let Err(e) = sync_wallet(&wallet_name, &mut wallet).await {
+            log::warn!("background_sync - Could not sync wallet {wallet_name}: {e}");
+            publish(
+                MonitoringEventKind::SyncFailed,
+                format!("Could not synchronize wallet {wallet_name}: {e}"),
+            );
+            continue;
+        }
+        if !unconfirmed_txids.is_empty() {
+            match wallet.list_transactions().await {
+                Ok(transactions) => {
+                    for tx in transactions.iter().filter(|tx| {
+                        tx.confirmation_time.is_some() && unconfirmed_txids.contains(&tx.txid)
+                    }) {
+                        publish(
+                            MonitoringEventKind::TransactionConfirmed,
+                            format!(
+                                "Transaction {} of wallet {wallet_name} is confirmed",
+                                tx.txid
+                            ),
+                        );
+                    }
+                }
+                Err(e) => log::warn!(
+                    "background_sync - Could not list transactions of wallet {wallet_name}: {e}"
+                ),
+            }
+        }
+        match super::helpers::load_wallet_settings(database_service, wallet_name.clone()).await {
+            Ok(wallet_settings) if wallet_settings.auto_topup_xpubs => {
+                match super::helpers::topup_account_xpubs(
+                    database_service,
+                    service_client_service,
+                    blockchain_provider_service,
+                    &mut wallet,
+                )
+                .await
+                {
+                    Ok(0) => (),
+                    Ok(fed_count) => add_alert(Alert::info(CCStr::from(format!(
+                        "{fed_count} new Account XPubs were fed to wallet {wallet_name}. \
+                        Export a new backup of its descriptors."
+                    )))),
+                    Err(e) => log::warn!(
+                        "background_sync - Could not top up the Account XPubs of wallet {wallet_name}: {e}"
+                    ),
+                }
+            }
+            Ok(_) => (),
+            Err(e) => {
+                log::warn!("background_sync - Could not load the settings of wallet {wallet_name}: {e}")
+            }
+        }
+        // The first subwallet config is the current one
+        let current_expiration_ts = match wallet.list_subwallet_configs().await {
+            Ok(subwallet_configs) => subwallet_configs
+                .first()
+                .and_then(|swcm| heritage_config_expiration_ts(&swcm.heritage_config)),
+            Err(e) => {
+                log::warn!("background_sync - Could not list configs of wallet {wallet_name}: {e}");
+                continue;
+            }
+        };
+        // Only warn when the expiration status changed since the last report
+        let expiration = current_expiration_ts
+            .filter(|expiration_ts| *expiration_ts < now + ExpirationStatus::SOON)
+            .map(|expiration_ts| (expiration_ts, expiration_ts < now));
+        let reported_expiration = core::mem::replace(
+            &mut WALLET_SYNC_STATES
+                .write()
+                .entry(wallet_name.clone())
+                .or_default()
+                .reported_expiration,
+            expiration,
+        );
+        if let Some((_, expired)) = expiration.filter(|_| expiration != reported_expiration) {
+            let message = if expired {
+                format!(
+                    "The Heritage Configuration of wallet {wallet_name} has expired. \
+                    Your heirs can now spend your bitcoins."
+                )
+            } else {
+                format!(
+                    "The Heritage Configuration of wallet {wallet_name} will expire soon. \
+                    Consider renewing it."
+                )
+            };
+            add_alert(
+                Alert::warn(CCStr::from(message.as_str())).with_category(AlertCategory::Security),
+            );
+            publish(MonitoringEventKind::HeritageExpiring, message);
+        }
+    }
+    log::info!("background_sync - finished");
+}
+
+/// Create or remove the entry starting the application when the user logs in
+fn update_login_entry(config: BackgroundSyncConfig) -> Result<(), String> {
+    let entry_path = login_entry_path().ok_or("Cannot locate the login entry directory")?;
+    if !config.start_on_login {
+        if entry_path.exists() {
+            std::fs::remove_file(&entry_path)
+                .map_err(|e| format!("Could not remove the start-on-login entry: {e}"))?;
+        }
+        return Ok(());
+    }
+
+    let exe = std::env::current_exe()
+        .map_err(|e| format!("Could not locate the application executable: {e}"))?;
+    let exe = exe.to_string_lossy();
+    let args = if config.start_minimized {
+        MINIMIZED_FLAG
+    } else {
+        ""
+    };
+    if let Some(parent) = entry_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Could not create the login entry directory: {e}"))?;
+    }
+    std::fs::write(&entry_path, login_entry_content(&exe, args))
+        .map_err(|e| format!("Could not write the start-on-login entry: {e}"))
+}
+
+#[cfg(target_os = "linux")]
+fn login_entry_path() -> Option<PathBuf> {
+    dirs_next::config_dir().map(|p| p.join("autostart").join("heritage-gui.desktop"))
+}
+#[cfg(target_os = "linux")]
+fn login_entry_content(exe: &str, args: &str) -> String {
+    format!(
+        "[Desktop Entry]\nType=Application\nName=Heritage Wallet\n\
+        Exec=\"{exe}\" {args}\nX-GNOME-Autostart-enabled=true\n"
+    )
+}
+
+#[cfg(target_os = "windows")]
+fn login_entry_path() -> Option<PathBuf> {
+    dirs_next::data_dir().map(|p| {
+        p.join("Microsoft")
+            .join("Windows")
+            .join("Start Menu")
+            .join("Programs")
+            .join("Startup")
+            .join("heritage-gui.cmd")
+    })
+}
+#[cfg(target_os = "windows")]
+fn login_entry_content(exe: &str, args: &str) -> String {
+    format!("@start \"\" \"{exe}\" {args}\r\n")
+}
+
+#[cfg(target_os = "macos")]
+fn login_entry_path() -> Option<PathBuf> {
+    dirs_next::home_dir().map(|p| {
+        p.join("Library")
+            .join("LaunchAgents")
+            .join("world.crypto7.heritage-gui.plist")
+    })
+}
+#[cfg(target_os = "macos")]
+fn login_entry_content(exe: &str, args: &str) -> String {
+    let args = if args.is_empty() {
+        String::new()
+    } else {
+        format!("<string>{args}</string>")
+    };
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+        <!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n\
+        <plist version=\"1.0\"><dict>\
+        <key>Label</key><string>world.crypto7.heritage-gui</string>\
+        <key>ProgramArguments</key><array><string>{exe}</string>{args}</array>\
+        <key>RunAtLoad</key><true/>\
+        </dict></plist>\n"
+    )
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+fn login_entry_path() -> Option<PathBuf> {
+    None
+}
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+fn login_entry_content(_exe: &str, _args: &str) -> String {
+    String::new()
+}
+
+async fn load_config(database_service: Coroutine<DatabaseCommand>) -> BackgroundSyncConfig {
+    match super::helpers::blocking_db_service_operation(
+        database_service,
+        "Load the background sync config",
+        |db| BackgroundSyncConfig::load(&db),
+    )
+    .await
+    {
+        Ok(config) => config,
+        Err(_) => BackgroundSyncConfig::default(),
+    }
+}
+
+async fn save_config(
+    database_service: Coroutine<DatabaseCommand>,
+    config: BackgroundSyncConfig,
+) -> Result<(), String> {
+    super::helpers::blocking_db_service_operation(
+        database_service,
+        "Save the background sync config",
+        move |mut db| config.save(&mut db),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
@@ -11,6 +11,7 @@ use btc_heritage_wallet::{
 };
 
 use super::{
+    cancellation::CommandResult,
     database::{DatabaseCommand, DatabaseReloadEvent},
     event_bus::{subscribe_event, EventBus},
 };
@@ -68,9 +69,7 @@ pub(super) fn use_blockchain_provider_service(
 
                 match cmd {
                     BlockchainProviderCommand::GetBlockchainFactory { result } => {
-                        result
-                            .send(cached_factory.clone())
-                            .expect("channel failure");
+                        result.complete("GetBlockchainFactory", cached_factory.clone());
                     }
                     BlockchainProviderCommand::RefreshStatus => {
                         cached_factory = AnyBlockchainFactory::try_from(curent_config.clone())
@@ -78,7 +77,7 @@ pub(super) fn use_blockchain_provider_service(
                         update_blockchain_status(cached_factory.clone())
                     }
                     BlockchainProviderCommand::GetConfig { result } => {
-                        result.send(curent_config.clone()).expect("chanel failure")
+                        result.complete("GetConfig", curent_config.clone())
                     }
                     BlockchainProviderCommand::UpdateConfig { config } => {
                         save_config(database_service, &config).await;
@@ -139,8 +138,13 @@ fn update_blockchain_status(bcf: Result<AnyBlockchainFactory, String>) {
 }
 
 async fn create_config(database_service: Coroutine<DatabaseCommand>) -> BlockchainProviderConfig {
-    let database = super::helpers::get_database(database_service).await;
-    match BlockchainProviderConfig::load(&database) {
+    match super::helpers::blocking_db_service_operation(
+        database_service,
+        "Load the blockchain provider config",
+        |db| BlockchainProviderConfig::load(&db),
+    )
+    .await
+    {
         Ok(config) => config,
         Err(e) => {
             match e {
@@ -156,8 +160,14 @@ async fn save_config(
     database_service: Coroutine<DatabaseCommand>,
     config: &BlockchainProviderConfig,
 ) {
-    let mut database = super::helpers::get_database(database_service).await;
-    match config.save(&mut database) {
+    let config = config.clone();
+    match super::helpers::blocking_db_service_operation(
+        database_service,
+        "Save the blockchain provider config",
+        move |mut db| config.save(&mut db),
+    )
+    .await
+    {
         Ok(()) => (),
         Err(e) => {
             log::error!("Could not save Blockchain Provider Config in database: {e}");
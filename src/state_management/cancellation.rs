@@ -0,0 +1,38 @@
+use std::future::Future;
+
+use futures_util::future::{select, Either};
+use tokio::sync::oneshot;
+
+/// Cooperative cancellation for the commands of the services
+///
+/// The result channel of a command doubles as its cancellation token: when the
+/// requester is dropped (e.g. the view that sent the command is no longer displayed),
+/// its receiver is dropped too and the service can stop working on the command.
+pub(super) trait CommandResult<T> {
+    /// Drive `work` to completion, unless the requester goes away first
+    ///
+    /// Returns [None] if the command was cancelled. Work running in a blocking
+    /// thread cannot be interrupted, but the service stops waiting for it.
+    async fn unless_cancelled<F: Future>(&mut self, work: F) -> Option<F::Output>;
+    /// Send the result of `command`, the requester may not be waiting for it anymore
+    fn complete(self, command: &str, value: T);
+}
+impl<T> CommandResult<T> for oneshot::Sender<T> {
+    async fn unless_cancelled<F: Future>(&mut self, work: F) -> Option<F::Output> {
+        if self.is_closed() {
+            return None;
+        }
+        let work = std::pin::pin!(work);
+        let cancelled = std::pin::pin!(self.closed());
+        match select(work, cancelled).await {
+            Either::Left((output, _)) => Some(output),
+            Either::Right(_) => None,
+        }
+    }
+
+    fn complete(self, command: &str, value: T) {
+        if self.send(value).is_err() {
+            log::debug!("{command} - the requester is gone, result discarded");
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use dioxus::prelude::*;
+
+use crate::components::alerts::{add_alert, Alert, AlertCategory};
+
+use super::{
+    blockchain::BlockchainProviderCommand,
+    helpers::{measure_clock_drift, ClockDrift},
+    service::ServiceClientCommand,
+};
+
+/// Delay between two measures of the drift of the local clock
+const CLOCK_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(3600);
+
+/// Last measured drift of the local clock, [None] if it could not be measured
+pub static CLOCK_DRIFT: GlobalSignal<Option<ClockDrift>> = Signal::global(|| None);
+/// Whether the drift was measured at least once, the startup checks report the first measure
+pub(super) static CLOCK_DRIFT_MEASURED: GlobalSignal<bool> = Signal::global(|| false);
+
+/// Periodically measure the drift of the local clock and warn the user when it is excessive
+///
+/// The maturity of the Heritages is evaluated against the local clock, so a drifting
+/// clock makes the remaining time displayed to owners and heirs misleading.
+pub(super) fn use_clock_check_service(
+    service_client_service: Coroutine<ServiceClientCommand>,
+    blockchain_provider_service: Coroutine<BlockchainProviderCommand>,
+) {
+    use_future(move || async move {
+        loop {
+            log::debug!("clock_check_service: Measuring the clock drift...");
+            let drift =
+                measure_clock_drift(service_client_service, blockchain_provider_service).await;
+            let was_excessive = CLOCK_DRIFT.peek().is_some_and(|drift| drift.is_excessive());
+            match drift {
+                Some(drift) if drift.is_excessive() => {
+                    log::warn!("clock_check_service: {}", drift.describe());
+                    if !was_excessive {
+                        add_alert(
+                            Alert::warn(format!(
+                                "{}. Synchronize it, or the Heritage maturity dates will be wrong.",
+                                drift.describe()
+                            ))
+                            .with_category(AlertCategory::Security),
+                        );
+                    }
+                }
+                Some(drift) => {
+                    log::debug!("clock_check_service: drift of {}s", drift.drift_secs)
+                }
+                None => log::info!("clock_check_service: The clock drift could not be measured"),
+            }
+            *CLOCK_DRIFT.write() = drift;
+            if !*CLOCK_DRIFT_MEASURED.peek() {
+                *CLOCK_DRIFT_MEASURED.write() = true;
+            }
+            tokio::time::sleep(CLOCK_CHECK_INTERVAL).await;
+        }
+    });
+}
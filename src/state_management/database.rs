@@ -10,7 +10,11 @@ use btc_heritage_wallet::{
     heritage_service_api_client::TokenCache, Database, DatabaseItem, Heir, HeirWallet, Wallet,
 };
 
-use super::event_bus::EventBus;
+use super::{
+    cancellation::CommandResult,
+    database_queue::{enter_database_queue, DbOperationPriority},
+    event_bus::EventBus,
+};
 
 pub enum DatabaseItemCommand<DBI: DatabaseItem + Send + 'static> {
     ListNames {
@@ -94,19 +98,28 @@ pub(super) fn use_database_service(event_bus_service: EventBus) -> Coroutine<Dat
                 log::debug!("database_service (coroutine) - Processing commmand {cmd:?}...");
                 match cmd {
                     DatabaseCommand::Wallet(database_item_command) => {
-                        process_db_item_command(&database, database_item_command).await
+                        spawn(process_db_item_command(
+                            database.clone(),
+                            database_item_command,
+                        ));
                     }
                     DatabaseCommand::Heir(database_item_command) => {
-                        process_db_item_command(&database, database_item_command).await
+                        spawn(process_db_item_command(
+                            database.clone(),
+                            database_item_command,
+                        ));
                     }
                     DatabaseCommand::HeirWallet(database_item_command) => {
-                        process_db_item_command(&database, database_item_command).await
+                        spawn(process_db_item_command(
+                            database.clone(),
+                            database_item_command,
+                        ));
+                    }
+                    DatabaseCommand::ClearTokens { result } => {
+                        result.complete("ClearTokens", TokenCache::clear(&mut database).await)
                     }
-                    DatabaseCommand::ClearTokens { result } => result
-                        .send(TokenCache::clear(&mut database).await)
-                        .expect("chanel failure"),
                     DatabaseCommand::GetDatabase { result } => {
-                        result.send(database.clone()).expect("chanel failure")
+                        result.complete("GetDatabase", database.clone())
                     }
                     DatabaseCommand::UpdateConfig { config, result } => {
                         if APPLICATION_CONFIG.peek().network != config.network {
@@ -126,11 +139,16 @@ pub(super) fn use_database_service(event_bus_service: EventBus) -> Coroutine<Dat
                                     event_bus_service,
                                     DatabaseReloadEvent,
                                 );
-                                result.send(Ok(())).expect("chanel failure")
+                                result.complete("UpdateConfig", Ok(()))
+                            }
+                            Err(e) => {
+                                // The current database stays in use, so does its network
+                                bitcoin_network::set(APPLICATION_CONFIG.peek().network);
+                                result.complete(
+                                    "UpdateConfig",
+                                    Err(format!("Could not open the database: {e}")),
+                                )
                             }
-                            Err(e) => result
-                                .send(Err(format!("Could not open the database: {e}")))
-                                .expect("chanel failure"),
                         }
                     }
                 }
@@ -140,41 +158,56 @@ pub(super) fn use_database_service(event_bus_service: EventBus) -> Coroutine<Dat
     )
 }
 
+/// Process the command in its own task, so that a slow listing does not hold the other commands
 async fn process_db_item_command<DBI: std::fmt::Debug + DatabaseItem + Send>(
-    db: &Database,
+    db: Database,
     cmd: DatabaseItemCommand<DBI>,
 ) {
     match cmd {
-        DatabaseItemCommand::ListNames { result } => {
-            result
-                .send(
-                    db.clone()
-                        .blocking_operation(move |db| DBI::list_names(&db))
-                        .await,
-                )
-                .expect("chanel failure");
+        DatabaseItemCommand::ListNames { mut result } => {
+            let _slot = enter_database_queue("List names", DbOperationPriority::Interactive).await;
+            let names = result
+                .unless_cancelled(db.blocking_operation(move |db| DBI::list_names(&db)))
+                .await;
+            match names {
+                Some(names) => result.complete("ListNames", names),
+                None => log::debug!("ListNames - cancelled"),
+            }
         }
-        DatabaseItemCommand::ListItems { result } => {
-            result
-                .send(
-                    db.clone()
-                        .blocking_operation(move |db| DBI::all_in_db(&db))
-                        .await,
-                )
-                .expect("chanel failure");
+        DatabaseItemCommand::ListItems { mut result } => {
+            let _slot = enter_database_queue("List items", DbOperationPriority::Interactive).await;
+            let items = result
+                .unless_cancelled(db.blocking_operation(move |db| DBI::all_in_db(&db)))
+                .await;
+            match items {
+                Some(items) => result.complete("ListItems", items),
+                None => log::debug!("ListItems - cancelled"),
+            }
         }
     }
 }
 
+/// The data directory of this run, from the environment and the command line arguments
+///
+/// Available before the database is opened, typically to write the crash reports.
+pub(crate) fn startup_datadir() -> PathBuf {
+    // First check if the env var is present
+    let datadir = std::env::var("HERITAGE_WALLET_HOME")
+        .ok()
+        .map(|s| s.parse().expect("valid path string"))
+        .unwrap_or_else(|| ApplicationConfig::default().datadir);
+
+    // Then the command line arguments take precedence
+    crate::cli::cli_args().datadir(datadir)
+}
+
 fn create_config() -> ApplicationConfig {
     let mut default_config = ApplicationConfig::default();
+    default_config.datadir = startup_datadir();
 
-    // First check if env vars are present, if yes create the config from it (with defaults from missing vars)
-    if let Some(datadir) = std::env::var("HERITAGE_WALLET_HOME")
-        .ok()
-        .map(|s| s.parse().expect("valid path string"))
-    {
-        default_config.datadir = datadir;
+    if let Some(network) = crate::cli::cli_args().network {
+        bitcoin_network::set(network);
+        default_config.network = network;
     }
     default_config
 }
@@ -0,0 +1,160 @@
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BinaryHeap},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use dioxus::prelude::*;
+use tokio::sync::oneshot;
+
+/// Number of database operations running at the same time, the others wait in the queue
+const MAX_CONCURRENT_OPERATIONS: usize = 2;
+/// Operations running for longer are shown to the user, so that the UI does not
+/// appear frozen without explanation
+pub const SLOW_OPERATION_THRESHOLD: Duration = Duration::from_secs(2);
+/// Operations running for longer are reported as taking longer than expected
+pub const OPERATION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Priority of a database operation in the queue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DbOperationPriority {
+    /// Refreshes and caches nobody is actively waiting for
+    Background,
+    /// Operations the user is waiting for, served first
+    Interactive,
+}
+
+/// A database operation, waiting in the queue or running
+#[derive(Debug, Clone, PartialEq)]
+pub struct DbOperationInfo {
+    pub name: &'static str,
+    pub priority: DbOperationPriority,
+    pub queued_at: Instant,
+    pub started_at: Option<Instant>,
+}
+impl DbOperationInfo {
+    /// How long the operation has been running, [None] if it is still waiting in the queue
+    pub fn running_for(&self) -> Option<Duration> {
+        self.started_at.map(|started_at| started_at.elapsed())
+    }
+}
+
+/// Database operations waiting in the queue or running, indexed by their order of arrival
+pub static DATABASE_OPERATIONS: GlobalSignal<BTreeMap<u64, DbOperationInfo>> =
+    Signal::global(BTreeMap::new);
+
+struct Waiter {
+    priority: DbOperationPriority,
+    seq: u64,
+    wake: oneshot::Sender<()>,
+}
+impl PartialEq for Waiter {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl Eq for Waiter {}
+impl PartialOrd for Waiter {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Waiter {
+    /// Highest priority first, then first arrived first
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+struct QueueState {
+    running: usize,
+    next_seq: u64,
+    waiting: BinaryHeap<Waiter>,
+}
+static QUEUE: Mutex<QueueState> = Mutex::new(QueueState {
+    running: 0,
+    next_seq: 0,
+    waiting: BinaryHeap::new(),
+});
+
+/// Hand the slot of a finished operation over to the next waiting one, if any
+fn release_slot(queue: &mut QueueState) {
+    while let Some(waiter) = queue.waiting.pop() {
+        // The waiter may have been cancelled in the meantime
+        if waiter.wake.send(()).is_ok() {
+            return;
+        }
+    }
+    queue.running -= 1;
+}
+
+/// A place in the database queue, released when dropped
+pub(super) struct DbOperationSlot {
+    seq: u64,
+    waiting: Option<oneshot::Receiver<()>>,
+}
+impl Drop for DbOperationSlot {
+    fn drop(&mut self) {
+        DATABASE_OPERATIONS.write().remove(&self.seq);
+        // The receiver is checked and dropped under the lock, so that the slot cannot
+        // be handed over to it in-between by a concurrent release
+        let mut queue = QUEUE.lock().expect("queue lock is never poisoned");
+        let holds_slot = match self.waiting.take() {
+            None => true,
+            // Cancelled while waiting, the slot may have been handed over already
+            Some(mut waiting) => waiting.try_recv().is_ok(),
+        };
+        if holds_slot {
+            release_slot(&mut queue);
+        }
+    }
+}
+
+/// Wait for a place in the database queue to run the operation `name`
+pub(super) async fn enter_database_queue(
+    name: &'static str,
+    priority: DbOperationPriority,
+) -> DbOperationSlot {
+    let mut slot = {
+        let mut queue = QUEUE.lock().expect("queue lock is never poisoned");
+        let seq = queue.next_seq;
+        queue.next_seq += 1;
+        if queue.running < MAX_CONCURRENT_OPERATIONS {
+            queue.running += 1;
+            DbOperationSlot { seq, waiting: None }
+        } else {
+            let (wake, waiting) = oneshot::channel();
+            queue.waiting.push(Waiter {
+                priority,
+                seq,
+                wake,
+            });
+            DbOperationSlot {
+                seq,
+                waiting: Some(waiting),
+            }
+        }
+    };
+    DATABASE_OPERATIONS.write().insert(
+        slot.seq,
+        DbOperationInfo {
+            name,
+            priority,
+            queued_at: Instant::now(),
+            started_at: None,
+        },
+    );
+    if let Some(waiting) = slot.waiting.as_mut() {
+        log::debug!("Database operation \"{name}\" - waiting in the queue");
+        // The sender is only dropped after a successful send
+        let _ = waiting.await;
+    }
+    slot.waiting = None;
+    if let Some(operation) = DATABASE_OPERATIONS.write().get_mut(&slot.seq) {
+        operation.started_at = Some(Instant::now());
+    }
+    slot
+}
@@ -0,0 +1,162 @@
+use dioxus::prelude::*;
+
+use btc_heritage_wallet::{
+    bitcoin::Network,
+    btc_heritage::{
+        heritage_config::v1::Heritage, utils::timestamp_now, BlockInclusionObjective,
+        HeritageConfig,
+    },
+    online_wallet::LocalHeritageWallet,
+    AnyHeritageProvider, AnyKeyProvider, AnyOnlineWallet, Database, DatabaseItem,
+    DatabaseSingleItem, Heir, HeirConfigType, HeirWallet, KeyProvider, LocalKey, LocalWallet,
+    OnlineWallet, Wallet,
+};
+
+use crate::utils::log_error;
+
+use super::{database::ApplicationConfig, onboarding::OnboardingStatus};
+
+/// Network used by the demo database
+pub const DEMO_NETWORK: Network = Network::Regtest;
+
+/// When the demo mode is active, holds the [ApplicationConfig] to restore when exiting it
+pub static DEMO_MODE: GlobalSignal<Option<ApplicationConfig>> = Signal::global(|| None);
+
+const DEMO_WALLET_NAME: &str = "Demo Wallet";
+const DEMO_WALLET_ACCOUNT_COUNT: u32 = 5;
+/// Heirs of the demo wallet with their time-lock in days
+const DEMO_HEIRS: [(&str, u16); 2] = [("Alice", 180), ("Bob", 360)];
+/// The reference time of the demo Heritage Configuration is set in the past
+/// so that the inheritance of the first heir is already mature
+const DEMO_REFERENCE_DAYS_AGO: u64 = 200;
+
+/// Prefix of the temporary data directories of the demo mode
+pub(super) const DEMO_DATADIR_PREFIX: &str = "heritage-wallet-demo-";
+
+/// Remove the demo data directories left behind by a previous run,
+/// typically when the application was quit in demo mode
+pub(crate) fn remove_stale_demo_datadirs() {
+    let Ok(entries) = std::fs::read_dir(std::env::temp_dir()) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let is_demo_datadir = entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| name.starts_with(DEMO_DATADIR_PREFIX));
+        if is_demo_datadir {
+            log::info!("Removing the stale demo data directory {:?}", entry.path());
+            if let Err(e) = std::fs::remove_dir_all(entry.path()) {
+                log::warn!(
+                    "Could not remove the demo data directory {:?}: {e}",
+                    entry.path()
+                );
+            }
+        }
+    }
+}
+
+/// Fill an empty database with a sample dataset: an owner wallet with a
+/// Heritage Configuration, its heirs and, for each heir, the heir wallet
+/// able to claim the inheritance.
+///
+/// Returns an address of the owner wallet, to fund the demo on a regtest node.
+pub(super) async fn populate_demo_database(database: Database) -> Result<String, String> {
+    log::debug!("populate_demo_database - start");
+
+    // Heirs: their keys are kept to build their heir wallets later
+    let mut heir_keys = Vec::with_capacity(DEMO_HEIRS.len());
+    let mut heritages = Vec::with_capacity(DEMO_HEIRS.len());
+    for (heir_name, time_lock) in DEMO_HEIRS {
+        let heir_key = LocalKey::generate(12, None, DEMO_NETWORK);
+        let heir_config = heir_key
+            .derive_heir_config(HeirConfigType::HeirXPubkey)
+            .await
+            .map_err(log_error)?;
+        heritages.push(Heritage::new(heir_config.clone()).time_lock(time_lock));
+
+        // The owner only knows the Heir Configuration of its heirs
+        let heir = Heir::new(heir_name.to_owned(), heir_config, AnyKeyProvider::None);
+        database
+            .clone()
+            .blocking_operation(move |mut db| heir.create(&mut db))
+            .await
+            .map_err(log_error)?;
+        heir_keys.push((heir_name, heir_key));
+    }
+
+    // Owner wallet
+    let local_wallet = database
+        .clone()
+        .blocking_operation(|db| {
+            LocalHeritageWallet::create(&db, None, BlockInclusionObjective::default())
+        })
+        .await
+        .map_err(log_error)?;
+    let mut wallet = Wallet::new(
+        DEMO_WALLET_NAME.to_owned(),
+        AnyKeyProvider::LocalKey(LocalKey::generate(12, None, DEMO_NETWORK)),
+        AnyOnlineWallet::Local(local_wallet),
+    )
+    .map_err(log_error)?;
+    let account_xpubs = wallet
+        .derive_accounts_xpubs(0..DEMO_WALLET_ACCOUNT_COUNT)
+        .await
+        .map_err(log_error)?;
+    wallet
+        .feed_account_xpubs(account_xpubs)
+        .await
+        .map_err(log_error)?;
+    let heritage_config = HeritageConfig::builder_v1()
+        .reference_time(timestamp_now() - DEMO_REFERENCE_DAYS_AGO * 24 * 3600)
+        .minimum_lock_time(1)
+        .expand_heritages(heritages)
+        .build();
+    wallet
+        .set_heritage_config(heritage_config)
+        .await
+        .map_err(log_error)?;
+    let backup = wallet.backup_descriptors().await.map_err(log_error)?;
+    let funding_address = wallet
+        .get_address()
+        .await
+        .map_err(log_error)?
+        .address()
+        .to_string();
+    database
+        .clone()
+        .blocking_operation(move |mut db| wallet.create(&mut db))
+        .await
+        .map_err(log_error)?;
+
+    // Heir wallets, watching the owner wallet through its descriptors backup
+    for (heir_name, heir_key) in heir_keys {
+        let fingerprint = heir_key.fingerprint().map_err(log_error)?;
+        let backup = backup.clone();
+        let local_wallet = database
+            .clone()
+            .blocking_operation(move |db| LocalWallet::create(fingerprint, &db, backup))
+            .await
+            .map_err(log_error)?;
+        let heirwallet = HeirWallet::new(
+            format!("{heir_name} Inheritance"),
+            AnyKeyProvider::LocalKey(heir_key),
+            AnyHeritageProvider::LocalWallet(local_wallet),
+        )
+        .map_err(log_error)?;
+        database
+            .clone()
+            .blocking_operation(move |mut db| heirwallet.create(&mut db))
+            .await
+            .map_err(log_error)?;
+    }
+
+    // The demo does not need the onboarding
+    database
+        .blocking_operation(|mut db| OnboardingStatus::Completed.save(&mut db))
+        .await
+        .map_err(log_error)?;
+
+    log::debug!("populate_demo_database - finished");
+    Ok(funding_address)
+}
@@ -0,0 +1,312 @@
+use dioxus::prelude::*;
+
+use std::{process::Stdio, time::Duration};
+
+use btc_heritage_wallet::{btc_heritage::bitcoincore_rpc::jsonrpc::serde_json, DatabaseSingleItem};
+use futures_util::stream::StreamExt;
+use serde::{Deserialize, Serialize};
+
+use crate::{prelude::alert_error, utils::CCStr};
+
+use super::{
+    database::{DatabaseCommand, DatabaseReloadEvent},
+    event_bus::{subscribe_event, EventBus, EventId},
+};
+
+/// Time given to a hook to complete, a hung command is killed
+const EVENT_HOOK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Kinds of [MonitoringEvent] that can trigger an [EventHook]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MonitoringEventKind {
+    /// A transaction of a wallet received its first confirmation
+    TransactionConfirmed,
+    /// The current Heritage Configuration of a wallet expired or will expire soon
+    HeritageExpiring,
+    /// The background synchronization of a wallet failed
+    SyncFailed,
+}
+impl MonitoringEventKind {
+    pub const ALL: [Self; 3] = [
+        Self::TransactionConfirmed,
+        Self::HeritageExpiring,
+        Self::SyncFailed,
+    ];
+
+    /// Identifier given to the hooks, as `HERITAGE_EVENT` or in the webhook payload
+    pub fn id(self) -> &'static str {
+        match self {
+            MonitoringEventKind::TransactionConfirmed => "tx_confirmed",
+            MonitoringEventKind::HeritageExpiring => "heritage_expiring",
+            MonitoringEventKind::SyncFailed => "sync_failed",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            MonitoringEventKind::TransactionConfirmed => "Transaction confirmed",
+            MonitoringEventKind::HeritageExpiring => "Heritage expiring",
+            MonitoringEventKind::SyncFailed => "Synchronization failed",
+        }
+    }
+}
+
+/// Event published by the background synchronization about a wallet
+#[derive(Debug, Clone)]
+pub struct MonitoringEvent {
+    pub kind: MonitoringEventKind,
+    pub wallet_name: CCStr,
+    pub message: CCStr,
+}
+impl EventId for MonitoringEvent {
+    fn event_id() -> &'static str {
+        "monitoring"
+    }
+}
+
+/// What an [EventHook] does when its event is published
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EventHookAction {
+    /// Run a shell command, the event is passed in the `HERITAGE_EVENT`,
+    /// `HERITAGE_WALLET` and `HERITAGE_MESSAGE` environment variables
+    Command(String),
+    /// POST the event as JSON to an URL
+    Webhook(String),
+}
+impl EventHookAction {
+    /// A [EventHookAction::Webhook] to the given URL, which must be an http(s) URL
+    pub fn webhook(url: &str) -> Result<Self, String> {
+        check_webhook_url(url)?;
+        Ok(Self::Webhook(url.to_owned()))
+    }
+}
+
+fn check_webhook_url(url: &str) -> Result<(), String> {
+    let url = reqwest::Url::parse(url).map_err(|e| format!("Invalid webhook URL: {e}"))?;
+    if !matches!(url.scheme(), "http" | "https") || url.host().is_none() {
+        return Err("The webhook URL must be an http(s) URL".to_owned());
+    }
+    Ok(())
+}
+
+/// An external integration reacting to a [MonitoringEvent]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventHook {
+    pub event: MonitoringEventKind,
+    pub action: EventHookAction,
+    pub enabled: bool,
+}
+
+/// The external hooks configured by the user
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventHooksConfig {
+    pub hooks: Vec<EventHook>,
+}
+
+impl DatabaseSingleItem for EventHooksConfig {
+    fn item_key() -> &'static str {
+        "gui_event_hooks"
+    }
+}
+
+pub static EVENT_HOOKS_CONFIG: GlobalSignal<EventHooksConfig> =
+    Signal::global(|| EventHooksConfig::default());
+
+/// Commands for the event hooks service
+#[derive(Debug)]
+pub(super) enum EventHooksCommand {
+    /// Internal trigger a save into the DB
+    Persist { config: EventHooksConfig },
+    /// Internal trigger a refresh from the DB
+    Refresh,
+    /// Run the hooks of an event
+    Run { event: MonitoringEvent },
+}
+
+/// Event hooks service coroutine
+pub(super) fn use_event_hooks_service(
+    event_bus: EventBus,
+    database_service: Coroutine<DatabaseCommand>,
+) -> Coroutine<EventHooksCommand> {
+    let service_handle = use_coroutine(
+        move |mut rx: UnboundedReceiver<EventHooksCommand>| async move {
+            log::info!("event_hooks_service (coroutine) - start");
+
+            let mut cached_value = load_config(database_service).await;
+            *EVENT_HOOKS_CONFIG.write() = cached_value.clone();
+
+            // Flag to trash the first persist command that will come from the initial run of the "use_effect"
+            let mut discard_next_persit = true;
+
+            while let Some(cmd) = rx.next().await {
+                log::debug!("event_hooks_service (coroutine) - Processing command {cmd:?}...");
+
+                match cmd {
+                    EventHooksCommand::Persist { config } => {
+                        if discard_next_persit {
+                            log::debug!(
+                                "event_hooks_service (coroutine) - Ignoring first Persist cmd."
+                            );
+                            discard_next_persit = false;
+                        }
+                        if config != cached_value {
+                            log::debug!(
+                                "event_hooks_service (coroutine) - Config changed, persisting..."
+                            );
+                            cached_value = config.clone();
+                            match save_config(database_service, config).await {
+                                Ok(_) => (),
+                                Err(msg) => {
+                                    log::error!("{msg}");
+                                    alert_error(msg);
+                                }
+                            };
+                        } else {
+                            log::debug!("event_hooks_service (coroutine) - Ignoring Persist cmd: already in database.");
+                        }
+                    }
+                    EventHooksCommand::Refresh => {
+                        cached_value = load_config(database_service).await;
+                        *EVENT_HOOKS_CONFIG.write() = cached_value.clone()
+                    }
+                    EventHooksCommand::Run { event } => {
+                        for hook in cached_value
+                            .hooks
+                            .iter()
+                            .filter(|hook| hook.enabled && hook.event == event.kind)
+                        {
+                            run_hook(hook.action.clone(), event.clone());
+                        }
+                    }
+                }
+
+                log::debug!("event_hooks_service (coroutine) - Command processed");
+            }
+        },
+    );
+    subscribe_event(event_bus, move |_event: DatabaseReloadEvent| {
+        service_handle.send(EventHooksCommand::Refresh);
+    });
+    subscribe_event(event_bus, move |event: MonitoringEvent| {
+        service_handle.send(EventHooksCommand::Run { event });
+    });
+    use_effect(move || {
+        service_handle.send(EventHooksCommand::Persist {
+            config: EVENT_HOOKS_CONFIG(),
+        });
+    });
+    service_handle
+}
+
+/// Run the action of a hook in the background, failures are only logged
+///
+/// Commands and webhooks are given [EVENT_HOOK_TIMEOUT] to complete.
+fn run_hook(action: EventHookAction, event: MonitoringEvent) {
+    match action {
+        EventHookAction::Command(command) => {
+            tokio::spawn(async move {
+                log::info!(
+                    "event_hooks - Running command for {} event",
+                    event.kind.id()
+                );
+                let mut shell = if cfg!(target_os = "windows") {
+                    let mut shell = tokio::process::Command::new("cmd");
+                    shell.arg("/C");
+                    shell
+                } else {
+                    let mut shell = tokio::process::Command::new("sh");
+                    shell.arg("-c");
+                    shell
+                };
+                let output = shell
+                    .arg(&command)
+                    .env("HERITAGE_EVENT", event.kind.id())
+                    .env("HERITAGE_WALLET", &*event.wallet_name)
+                    .env("HERITAGE_MESSAGE", &*event.message)
+                    .stdin(Stdio::null())
+                    // The command is killed if it times out
+                    .kill_on_drop(true)
+                    .output();
+                match tokio::time::timeout(EVENT_HOOK_TIMEOUT, output).await {
+                    Ok(Ok(output)) if output.status.success() => {
+                        log::debug!("event_hooks - Command succeeded: {command}")
+                    }
+                    Ok(Ok(output)) => log::warn!(
+                        "event_hooks - Command failed ({}): {command}: {}",
+                        output.status,
+                        String::from_utf8_lossy(&output.stderr)
+                    ),
+                    Ok(Err(e)) => log::warn!("event_hooks - Could not run command {command}: {e}"),
+                    Err(_) => {
+                        log::warn!("event_hooks - Command timed out and was killed: {command}")
+                    }
+                }
+            });
+        }
+        EventHookAction::Webhook(url) => {
+            if let Err(e) = check_webhook_url(&url) {
+                log::warn!("event_hooks - Ignoring webhook {url}: {e}");
+                return;
+            }
+            let payload = serde_json::json!({
+                "event": event.kind.id(),
+                "wallet": &*event.wallet_name,
+                "message": &*event.message,
+                "timestamp": chrono::Utc::now().timestamp(),
+            });
+            tokio::spawn(async move {
+                log::info!(
+                    "event_hooks - Calling webhook for {} event",
+                    event.kind.id()
+                );
+                let client = match reqwest::Client::builder()
+                    .timeout(EVENT_HOOK_TIMEOUT)
+                    .build()
+                {
+                    Ok(client) => client,
+                    Err(e) => {
+                        log::warn!("event_hooks - Could not create the webhook client: {e}");
+                        return;
+                    }
+                };
+                match client
+                    .post(&url)
+                    .header(reqwest::header::CONTENT_TYPE, "application/json")
+                    .body(payload.to_string())
+                    .send()
+                    .await
+                    .and_then(|response| response.error_for_status())
+                {
+                    Ok(_) => log::debug!("event_hooks - Webhook succeeded: {url}"),
+                    Err(e) => log::warn!("event_hooks - Webhook {url} failed: {e}"),
+                }
+            });
+        }
+    }
+}
+
+async fn load_config(database_service: Coroutine<DatabaseCommand>) -> EventHooksConfig {
+    match super::helpers::blocking_db_service_operation(
+        database_service,
+        "Load the event hooks",
+        |db| EventHooksConfig::load(&db),
+    )
+    .await
+    {
+        Ok(config) => config,
+        Err(_) => EventHooksConfig::default(),
+    }
+}
+
+async fn save_config(
+    database_service: Coroutine<DatabaseCommand>,
+    config: EventHooksConfig,
+) -> Result<(), String> {
+    super::helpers::blocking_db_service_operation(
+        database_service,
+        "Save the event hooks",
+        move |mut db| config.save(&mut db),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
@@ -0,0 +1,117 @@
+use super::*;
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+/// Steps of the guided workflow an heir follows once the owner has passed away
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ActivationStep {
+    WaitMaturity,
+    GatherDocuments,
+    NotifyService,
+    ClaimInheritance,
+}
+impl ActivationStep {
+    pub fn list() -> [Self; 4] {
+        [
+            Self::WaitMaturity,
+            Self::GatherDocuments,
+            Self::NotifyService,
+            Self::ClaimInheritance,
+        ]
+    }
+    pub fn title(self) -> &'static str {
+        match self {
+            ActivationStep::WaitMaturity => "Wait for the maturity of the inheritance",
+            ActivationStep::GatherDocuments => "Gather the documents",
+            ActivationStep::NotifyService => "Notify the Heritage Service (optional)",
+            ActivationStep::ClaimInheritance => "Claim the inheritance",
+        }
+    }
+    pub fn explanation(self) -> &'static str {
+        match self {
+            ActivationStep::WaitMaturity => {
+                "The bitcoins can only be claimed once the Heritage Configuration of the owner \
+                expires. Nothing can speed it up: the owner can no longer renew it."
+            }
+            ActivationStep::GatherDocuments => {
+                "Get the death certificate and any document the estate settlement may require, \
+                and keep your seed or hardware wallet at hand."
+            }
+            ActivationStep::NotifyService => {
+                "If the owner used the Heritage Service, contact its support to let them know, \
+                they can help you through the process."
+            }
+            ActivationStep::ClaimInheritance => {
+                "Once matured, spend the inheritance to an address you control."
+            }
+        }
+    }
+}
+
+/// Progress of an heir wallet through the [ActivationStep]s
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ActivationWorkflow {
+    pub started_at: u64,
+    pub completed_steps: BTreeSet<ActivationStep>,
+    /// Timestamp at which the heir wants to be reminded of the maturity, cleared once notified
+    pub reminder_ts: Option<u64>,
+}
+
+fn activation_workflow_key(heirwallet_name: &str) -> String {
+    format!("gui_heirwallet_activation#{heirwallet_name}")
+}
+
+pub async fn load_activation_workflow(
+    database_service: Coroutine<DatabaseCommand>,
+    heirwallet_name: CCStr,
+) -> Result<Option<ActivationWorkflow>, String> {
+    let key = activation_workflow_key(&heirwallet_name);
+    blocking_db_service_operation(
+        database_service,
+        "Load the activation workflow",
+        move |db| db.get_item::<ActivationWorkflow>(&key),
+    )
+    .await
+    .map_err(log_error)
+}
+
+/// Save the activation workflow of an heir wallet, or remove it if `workflow` is [None]
+pub async fn save_activation_workflow(
+    database_service: Coroutine<DatabaseCommand>,
+    heirwallet_name: CCStr,
+    workflow: Option<ActivationWorkflow>,
+) -> Result<(), String> {
+    let key = activation_workflow_key(&heirwallet_name);
+    blocking_db_service_operation(
+        database_service,
+        "Save the activation workflow",
+        move |mut db| match workflow {
+            Some(workflow) => db.update_item(&key, &workflow),
+            None => db.delete_item::<ActivationWorkflow>(&key).map(|_| ()),
+        },
+    )
+    .await
+    .map_err(log_error)
+}
+
+/// Move the activation workflow of a renamed heir wallet to its new name
+pub(super) fn rename_activation_workflow(
+    db: &mut Database,
+    old_name: &str,
+    new_name: &str,
+) -> Result<(), DbError> {
+    let old_key = activation_workflow_key(old_name);
+    if let Some(workflow) = db.get_item::<ActivationWorkflow>(&old_key)? {
+        db.update_item(&activation_workflow_key(new_name), &workflow)?;
+        db.delete_item::<ActivationWorkflow>(&old_key)?;
+    }
+    Ok(())
+}
+
+/// Delete the activation workflow of a deleted heir wallet
+pub(super) fn delete_activation_workflow(db: &mut Database, name: &str) -> Result<(), DbError> {
+    db.delete_item::<ActivationWorkflow>(&activation_workflow_key(name))?;
+    Ok(())
+}
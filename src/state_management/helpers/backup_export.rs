@@ -0,0 +1,131 @@
+use super::*;
+
+use btc_heritage_wallet::btc_heritage::utils::timestamp_now;
+use serde::{Deserialize, Serialize};
+
+/// The last export of the descriptors backup of a wallet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupExport {
+    pub timestamp: u64,
+    /// Number of Heritage Configurations in the exported backup, to detect
+    /// the backups that do not cover the newest ones
+    pub subwallet_count: usize,
+}
+
+fn backup_export_key(wallet_name: &str) -> String {
+    format!("gui_wallet_backup_export#{wallet_name}")
+}
+
+pub async fn load_backup_export(
+    database_service: Coroutine<DatabaseCommand>,
+    wallet_name: CCStr,
+) -> Result<Option<BackupExport>, String> {
+    log::debug!("load_backup_export({wallet_name}) - start");
+    let key = backup_export_key(&wallet_name);
+    let backup_export =
+        blocking_db_service_operation(database_service, "Load the backup export", move |db| {
+            db.get_item::<BackupExport>(&key)
+        })
+        .await
+        .map_err(log_error);
+    log::debug!("load_backup_export({wallet_name}) - loaded");
+    backup_export
+}
+
+pub async fn save_backup_export(
+    database_service: Coroutine<DatabaseCommand>,
+    wallet_name: CCStr,
+    backup_export: BackupExport,
+) -> Result<(), String> {
+    log::debug!("save_backup_export({wallet_name}) - start");
+    let key = backup_export_key(&wallet_name);
+    blocking_db_service_operation(database_service, "Save the backup export", move |mut db| {
+        db.update_item(&key, &backup_export)
+    })
+    .await
+    .map_err(log_error)?;
+    log::debug!("save_backup_export({wallet_name}) - finished");
+    Ok(())
+}
+
+/// Whether the last export of the descriptors backup of a wallet covers its key material
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackupFreshness {
+    UpToDate,
+    /// The key material or the descriptors of the wallet changed since the last export
+    Outdated,
+}
+
+fn key_material_change_key(wallet_name: &str) -> String {
+    format!("gui_wallet_key_material_change#{wallet_name}")
+}
+
+/// Remember that the key material or the descriptors of a wallet just changed,
+/// which makes its previous descriptors backup exports out of date
+pub async fn record_key_material_change(
+    database_service: Coroutine<DatabaseCommand>,
+    wallet_name: CCStr,
+) -> Result<(), String> {
+    log::debug!("record_key_material_change({wallet_name}) - start");
+    let key = key_material_change_key(&wallet_name);
+    let timestamp = timestamp_now();
+    blocking_db_service_operation(
+        database_service,
+        "Record a key material change",
+        move |mut db| db.update_item(&key, &timestamp),
+    )
+    .await
+    .map_err(log_error)?;
+    log::debug!("record_key_material_change({wallet_name}) - finished");
+    Ok(())
+}
+
+pub async fn load_backup_freshness(
+    database_service: Coroutine<DatabaseCommand>,
+    wallet_name: CCStr,
+) -> Result<BackupFreshness, String> {
+    log::debug!("load_backup_freshness({wallet_name}) - start");
+    let change_key = key_material_change_key(&wallet_name);
+    let key_material_change =
+        blocking_db_service_operation(database_service, "Load the backup freshness", move |db| {
+            db.get_item::<u64>(&change_key)
+        })
+        .await
+        .map_err(log_error)?;
+    let backup_export = load_backup_export(database_service, wallet_name.clone()).await?;
+    let backup_freshness = match (key_material_change, backup_export) {
+        (Some(change_ts), Some(backup_export)) if change_ts > backup_export.timestamp => {
+            BackupFreshness::Outdated
+        }
+        (Some(_), None) => BackupFreshness::Outdated,
+        _ => BackupFreshness::UpToDate,
+    };
+    log::debug!("load_backup_freshness({wallet_name}) - loaded");
+    Ok(backup_freshness)
+}
+
+/// Move the backup export and the key material change of a renamed wallet to its new name
+pub(super) fn rename_backup_export(
+    db: &mut Database,
+    old_name: &str,
+    new_name: &str,
+) -> Result<(), DbError> {
+    let old_key = backup_export_key(old_name);
+    if let Some(backup_export) = db.get_item::<BackupExport>(&old_key)? {
+        db.update_item(&backup_export_key(new_name), &backup_export)?;
+        db.delete_item::<BackupExport>(&old_key)?;
+    }
+    let old_key = key_material_change_key(old_name);
+    if let Some(key_material_change) = db.get_item::<u64>(&old_key)? {
+        db.update_item(&key_material_change_key(new_name), &key_material_change)?;
+        db.delete_item::<u64>(&old_key)?;
+    }
+    Ok(())
+}
+
+/// Delete the backup export and the key material change of a deleted wallet
+pub(super) fn delete_backup_export(db: &mut Database, name: &str) -> Result<(), DbError> {
+    db.delete_item::<BackupExport>(&backup_export_key(name))?;
+    db.delete_item::<u64>(&key_material_change_key(name))?;
+    Ok(())
+}
@@ -1,4 +1,19 @@
-use btc_heritage_wallet::online_wallet::AnyBlockchainFactory;
+use std::{path::PathBuf, time::Duration};
+
+use btc_heritage_wallet::{
+    bitcoin::{bip32::ChildNumber, Amount, FeeRate},
+    btc_heritage::{
+        bitcoincore_rpc::{
+            jsonrpc::{self, serde_json},
+            Auth, Client, RpcApi,
+        },
+        heritage_wallet::WalletAddress,
+        HeritageWalletBackup,
+    },
+    online_wallet::{AnyBlockchainFactory, AuthConfig, BlockchainProviderConfig},
+};
+
+use crate::utils::descriptor_checksum;
 
 use super::*;
 
@@ -21,3 +36,348 @@ pub async fn blockchain_factory(
     log::debug!("blockchain_factory - finished");
     result
 }
+
+/// Timeout of the RPC calls to the Bitcoin Core node when setting up a watch-only wallet
+///
+/// Importing descriptors only returns once the node rescanned the blockchain
+/// from the first use of the wallet, which can take a long time.
+const WATCH_ONLY_RPC_TIMEOUT: Duration = Duration::from_secs(3600);
+
+/// Build an RPC client for the Bitcoin Core node of the blockchain provider,
+/// optionally targeting one of its wallets
+fn bitcoin_core_client(
+    config: &BlockchainProviderConfig,
+    node_wallet_name: Option<&str>,
+) -> Result<Client, String> {
+    let BlockchainProviderConfig::BitcoinCore { url, auth } = config else {
+        return Err("The blockchain provider is not a Bitcoin Core node".to_owned());
+    };
+    let auth = match auth {
+        AuthConfig::Cookie { file } => Auth::CookieFile(PathBuf::from(file.as_ref())),
+        AuthConfig::UserPass { username, password } => {
+            Auth::UserPass(username.to_string(), password.to_string())
+        }
+    };
+    let (user, pass) = auth.get_user_pass().map_err(log_error)?;
+    let url = match node_wallet_name {
+        Some(node_wallet_name) => {
+            format!("{}/wallet/{node_wallet_name}", url.trim_end_matches('/'))
+        }
+        None => url.to_string(),
+    };
+    let mut builder = jsonrpc::simple_http::Builder::new()
+        .timeout(WATCH_ONLY_RPC_TIMEOUT)
+        .url(&url)
+        .map_err(log_error)?;
+    if let Some(user) = user {
+        builder = builder.auth(user, pass);
+    }
+    Ok(Client::from_jsonrpc(jsonrpc::Client::with_transport(
+        builder.build(),
+    )))
+}
+
+/// Whether the blockchain provider is a Bitcoin Core node running a regtest chain
+///
+/// Blocking: to be called from [tokio::task::spawn_blocking].
+pub(super) fn is_regtest_bitcoin_core(config: &BlockchainProviderConfig) -> bool {
+    bitcoin_core_client(config, None)
+        .and_then(|node_client| {
+            node_client
+                .call::<serde_json::Value>("getblockchaininfo", &[])
+                .map_err(log_error)
+        })
+        .is_ok_and(|info| info["chain"].as_str() == Some("regtest"))
+}
+
+/// Mine `blocks` blocks on the regtest Bitcoin Core node of the blockchain provider,
+/// paying their reward to `address`
+///
+/// Blocking: to be called from [tokio::task::spawn_blocking].
+pub(super) fn generate_regtest_blocks(
+    config: &BlockchainProviderConfig,
+    blocks: u64,
+    address: &str,
+) -> Result<(), String> {
+    bitcoin_core_client(config, None)?
+        .call::<serde_json::Value>(
+            "generatetoaddress",
+            &[serde_json::json!(blocks), serde_json::json!(address)],
+        )
+        .map_err(log_error)?;
+    Ok(())
+}
+
+/// Create (or load) a descriptor watch-only wallet on the Bitcoin Core node of the
+/// blockchain provider, then import the `importdescriptors` requests in it
+pub async fn setup_bitcoin_core_watch_only_wallet(
+    blockchain_provider_service: Coroutine<BlockchainProviderCommand>,
+    node_wallet_name: CCStr,
+    import_requests: serde_json::Value,
+) -> Result<(), String> {
+    log::debug!("setup_bitcoin_core_watch_only_wallet({node_wallet_name}) - start");
+    let config = get_blockchain_provider_config(blockchain_provider_service).await;
+    let node_wallet_name = node_wallet_name.to_string();
+    tokio::task::spawn_blocking(move || {
+        let node_client = bitcoin_core_client(&config, None)?;
+        let loaded_wallets = node_client
+            .call::<Vec<String>>("listwallets", &[])
+            .map_err(log_error)?;
+        if !loaded_wallets
+            .iter()
+            .any(|w| w == node_wallet_name.as_str())
+        {
+            // Load the wallet if it already exists on the node, else create it
+            if let Err(e) = node_client.call::<serde_json::Value>(
+                "loadwallet",
+                &[serde_json::json!(node_wallet_name.as_str())],
+            ) {
+                log::info!("Could not load wallet {node_wallet_name} ({e}), creating it");
+                node_client
+                    .call::<serde_json::Value>(
+                        "createwallet",
+                        &[
+                            serde_json::json!(node_wallet_name.as_str()),
+                            // disable_private_keys
+                            serde_json::json!(true),
+                            // blank
+                            serde_json::json!(true),
+                            // passphrase
+                            serde_json::json!(""),
+                            // avoid_reuse
+                            serde_json::json!(false),
+                            // descriptors
+                            serde_json::json!(true),
+                            // load_on_startup
+                            serde_json::json!(true),
+                        ],
+                    )
+                    .map_err(log_error)?;
+            }
+        }
+
+        let wallet_client = bitcoin_core_client(&config, Some(node_wallet_name.as_str()))?;
+        let wallet_info = wallet_client
+            .call::<serde_json::Value>("getwalletinfo", &[])
+            .map_err(log_error)?;
+        if wallet_info["private_keys_enabled"].as_bool() != Some(false)
+            || wallet_info["descriptors"].as_bool() != Some(true)
+        {
+            return Err(log_error(format!(
+                "The wallet {node_wallet_name} of the node is not a descriptor watch-only wallet"
+            )));
+        }
+
+        let results = wallet_client
+            .call::<Vec<serde_json::Value>>("importdescriptors", &[import_requests])
+            .map_err(log_error)?;
+        let errors = results
+            .iter()
+            .filter(|result| result["success"].as_bool() != Some(true))
+            .map(|result| {
+                result["error"]["message"]
+                    .as_str()
+                    .unwrap_or("unknown error")
+                    .to_owned()
+            })
+            .collect::<Vec<_>>();
+        if !errors.is_empty() {
+            return Err(log_error(format!(
+                "Could not import the descriptors: {}",
+                errors.join(", ")
+            )));
+        }
+        Ok(())
+    })
+    .await
+    .map_err(log_error)??;
+    log::debug!("setup_bitcoin_core_watch_only_wallet - finished");
+    Ok(())
+}
+
+/// Result of the verification of a Bitcoin Core watch-only wallet
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatchOnlyWalletReport {
+    pub tracked_addresses: usize,
+    /// Addresses of the Heritage wallet unknown to the node wallet
+    pub untracked_addresses: Vec<String>,
+    /// Progress of the rescan of the blockchain, if the node is rescanning
+    pub scanning_progress: Option<f64>,
+    pub balance: Amount,
+}
+
+/// Verify that a watch-only wallet of the Bitcoin Core node of the blockchain provider
+/// tracks the given addresses
+pub async fn verify_bitcoin_core_watch_only_wallet(
+    blockchain_provider_service: Coroutine<BlockchainProviderCommand>,
+    node_wallet_name: CCStr,
+    addresses: Vec<String>,
+) -> Result<WatchOnlyWalletReport, String> {
+    log::debug!("verify_bitcoin_core_watch_only_wallet({node_wallet_name}) - start");
+    let config = get_blockchain_provider_config(blockchain_provider_service).await;
+    let node_wallet_name = node_wallet_name.to_string();
+    let report = tokio::task::spawn_blocking(move || {
+        let wallet_client = bitcoin_core_client(&config, Some(node_wallet_name.as_str()))?;
+
+        let mut tracked_addresses = 0;
+        let mut untracked_addresses = vec![];
+        for address in addresses {
+            let address_info = wallet_client
+                .call::<serde_json::Value>("getaddressinfo", &[serde_json::json!(address)])
+                .map_err(log_error)?;
+            if address_info["ismine"].as_bool() == Some(true) {
+                tracked_addresses += 1;
+            } else {
+                untracked_addresses.push(address);
+            }
+        }
+
+        let wallet_info = wallet_client
+            .call::<serde_json::Value>("getwalletinfo", &[])
+            .map_err(log_error)?;
+        let scanning_progress = wallet_info["scanning"]["progress"].as_f64();
+
+        let balances = wallet_client
+            .call::<serde_json::Value>("getbalances", &[])
+            .map_err(log_error)?;
+        let balance = ["trusted", "untrusted_pending", "immature"]
+            .into_iter()
+            .filter_map(|field| balances["mine"][field].as_f64())
+            .map(|btc| Amount::from_btc(btc).map_err(log_error))
+            .sum::<Result<Amount, String>>()?;
+
+        Ok::<_, String>(WatchOnlyWalletReport {
+            tracked_addresses,
+            untracked_addresses,
+            scanning_progress,
+            balance,
+        })
+    })
+    .await
+    .map_err(log_error)??;
+    log::debug!("verify_bitcoin_core_watch_only_wallet - finished");
+    Ok(report)
+}
+
+/// Retrieve the timestamp of the best block known by the Bitcoin Core node
+/// of the blockchain provider
+///
+/// Returns [None] if the blockchain provider is not a Bitcoin Core node, or if
+/// it could not be reached.
+pub async fn bitcoin_core_tip_time(
+    blockchain_provider_service: Coroutine<BlockchainProviderCommand>,
+) -> Option<i64> {
+    log::debug!("bitcoin_core_tip_time - start");
+    let config = get_blockchain_provider_config(blockchain_provider_service).await;
+    let tip_time = tokio::task::spawn_blocking(move || {
+        let node_client = bitcoin_core_client(&config, None).ok()?;
+        let best_block_hash = node_client
+            .call::<serde_json::Value>("getbestblockhash", &[])
+            .map_err(log_error)
+            .ok()?;
+        let header = node_client
+            .call::<serde_json::Value>("getblockheader", &[best_block_hash])
+            .map_err(log_error)
+            .ok()?;
+        header["time"].as_i64()
+    })
+    .await
+    .ok()
+    .flatten();
+    log::debug!("bitcoin_core_tip_time - finished");
+    tip_time
+}
+
+/// Estimate with the Bitcoin Core node of the blockchain provider the fee rate
+/// a transaction needs to be included in the next block
+///
+/// Returns [None] if the blockchain provider is not a Bitcoin Core node, if
+/// it could not be reached or if it does not have enough data for an estimation.
+pub async fn bitcoin_core_next_block_fee_rate(
+    blockchain_provider_service: Coroutine<BlockchainProviderCommand>,
+) -> Option<FeeRate> {
+    log::debug!("bitcoin_core_next_block_fee_rate - start");
+    let config = get_blockchain_provider_config(blockchain_provider_service).await;
+    let fee_rate = tokio::task::spawn_blocking(move || {
+        let node_client = bitcoin_core_client(&config, None).ok()?;
+        let estimation = node_client
+            .call::<serde_json::Value>("estimatesmartfee", &[serde_json::json!(1)])
+            .map_err(log_error)
+            .ok()?;
+        // The estimation is in BTC/kvB
+        let btc_per_kvb = estimation["feerate"].as_f64()?;
+        let sat_per_kvb = Amount::from_btc(btc_per_kvb).ok()?.to_sat();
+        Some(FeeRate::from_sat_per_kwu(sat_per_kvb / 4))
+    })
+    .await
+    .ok()
+    .flatten();
+    log::debug!("bitcoin_core_next_block_fee_rate - finished");
+    fee_rate
+}
+
+/// Re-derive an address of a wallet with the Bitcoin Core node of the blockchain provider,
+/// an implementation independent from the one generating the addresses of the wallet
+///
+/// Returns whether the external descriptor of one of the Heritage Configurations of the
+/// backup derives the very same address at the index of its derivation path.
+pub async fn cross_check_wallet_address(
+    blockchain_provider_service: Coroutine<BlockchainProviderCommand>,
+    backup: HeritageWalletBackup,
+    wallet_address: CheapClone<WalletAddress>,
+) -> Result<bool, String> {
+    log::debug!("cross_check_wallet_address({wallet_address}) - start");
+    let index = match wallet_address.origin().1.as_ref() {
+        [.., ChildNumber::Normal { index: 0 }, ChildNumber::Normal { index }] => *index,
+        _ => {
+            return Err(log_error(
+                "The address is not an external address of the wallet",
+            ))
+        }
+    };
+    let backup = serde_json::to_value(&backup).map_err(log_error)?;
+    let descriptors = backup
+        .as_array()
+        .ok_or_else(|| log_error("Unexpected descriptors backup format"))?
+        .iter()
+        // Newest Heritage Configuration first, it generates the new addresses
+        .rev()
+        .map(|subwallet| {
+            let descriptor = subwallet["external_descriptor"]
+                .as_str()
+                .and_then(|descriptor| descriptor.split('#').next())
+                .ok_or_else(|| log_error("Missing external_descriptor in backup"))?;
+            let checksum = descriptor_checksum(descriptor)
+                .ok_or_else(|| log_error("Invalid external_descriptor in backup"))?;
+            Ok(format!("{descriptor}#{checksum}"))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let address = wallet_address.address().to_string();
+
+    let config = get_blockchain_provider_config(blockchain_provider_service).await;
+    let verified = tokio::task::spawn_blocking(move || {
+        let node_client = bitcoin_core_client(&config, None)?;
+        for descriptor in descriptors {
+            let derived_addresses = node_client
+                .call::<Vec<String>>(
+                    "deriveaddresses",
+                    &[
+                        serde_json::json!(descriptor),
+                        serde_json::json!([index, index]),
+                    ],
+                )
+                .map_err(log_error)?;
+            if derived_addresses.first() == Some(&address) {
+                return Ok(true);
+            }
+        }
+        Ok::<_, String>(false)
+    })
+    .await
+    .map_err(log_error)??;
+    if !verified {
+        log::error!("cross_check_wallet_address - The node derived a different address");
+    }
+    log::debug!("cross_check_wallet_address - finished");
+    Ok(verified)
+}
@@ -1,5 +1,9 @@
 use super::*;
 
+/// The database handle itself, for the library APIs that keep it or take it directly
+///
+/// Operations on the database go through [blocking_db_service_operation] instead, so that
+/// they wait in the database queue.
 pub async fn get_database(database_service: Coroutine<DatabaseCommand>) -> Database {
     log::debug!("get_database - start");
     let (result, rx) = oneshot::channel();
@@ -9,17 +13,40 @@ pub async fn get_database(database_service: Coroutine<DatabaseCommand>) -> Datab
     database
 }
 
+/// Run `f` on the database once the database queue allows it
+///
+/// The operation is named so that the user knows what the application is waiting for
+/// when it is slow (see [DATABASE_OPERATIONS](crate::state_management::DATABASE_OPERATIONS)).
+pub async fn queued_db_operation<R: Send + 'static, F: FnOnce(Database) -> R + Send + 'static>(
+    database_service: Coroutine<DatabaseCommand>,
+    name: &'static str,
+    priority: DbOperationPriority,
+    f: F,
+) -> R {
+    let database = get_database(database_service).await;
+    let _slot = super::super::database_queue::enter_database_queue(name, priority).await;
+    let mut operation = std::pin::pin!(database.blocking_operation(f));
+    match tokio::time::timeout(OPERATION_TIMEOUT, &mut operation).await {
+        Ok(result) => result,
+        Err(_) => {
+            log::warn!(
+                "Database operation \"{name}\" is still running after {}s",
+                OPERATION_TIMEOUT.as_secs()
+            );
+            operation.await
+        }
+    }
+}
+
 pub async fn blocking_db_service_operation<
     R: Send + 'static,
     F: FnOnce(Database) -> R + Send + 'static,
 >(
     database_service: Coroutine<DatabaseCommand>,
+    name: &'static str,
     f: F,
 ) -> R {
-    get_database(database_service)
-        .await
-        .blocking_operation(f)
-        .await
+    queued_db_operation(database_service, name, DbOperationPriority::Interactive, f).await
 }
 
 pub async fn list_wallet_names(
@@ -113,12 +140,15 @@ pub async fn delete_dbitem<T: DatabaseItem + Send + 'static>(
     // take ownership of it in order to send it to the blocking thread.
     // So instead we fall back on calling db.delete_item directly on the item key
     // This allow to present a delete_dbitem interface that take an item reference
-    let item_key = T::name_to_key(item.name());
-    get_database(database_service)
-        .await
-        .blocking_operation(move |mut db| db.delete_item::<T>(&item_key))
-        .await
-        .map_err(log_error)?;
+    let name = item.name().to_owned();
+    let item_key = T::name_to_key(&name);
+    blocking_db_service_operation(database_service, "Delete an item", move |mut db| {
+        // If the references cannot be deleted, the item is kept so the user can retry
+        super::references::delete_references::<T>(&mut db, &name)?;
+        db.delete_item::<T>(&item_key)
+    })
+    .await
+    .map_err(log_error)?;
 
     Ok(())
 }
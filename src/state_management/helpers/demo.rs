@@ -0,0 +1,130 @@
+use super::*;
+
+use btc_heritage_wallet::{
+    btc_heritage::utils::bitcoin_network, online_wallet::BlockchainProviderConfig,
+    DatabaseSingleItem,
+};
+
+/// Number of blocks mined to fund the demo wallet, so that the first block reward is mature
+const DEMO_FUNDING_BLOCKS: u64 = 101;
+
+/// Switch the application to a temporary regtest database filled with sample data
+///
+/// If the blockchain provider is a Bitcoin Core node running a regtest chain, the demo
+/// uses it and the demo wallet is funded by mining blocks to it. Returns whether it was.
+///
+/// The current [ApplicationConfig] is kept in [DEMO_MODE] so [exit_demo_mode] can restore it.
+pub async fn enter_demo_mode(
+    database_service: Coroutine<DatabaseCommand>,
+    blockchain_provider_service: Coroutine<BlockchainProviderCommand>,
+) -> Result<bool, String> {
+    log::debug!("enter_demo_mode - start");
+    if DEMO_MODE.peek().is_some() {
+        return Err("The demo mode is already active".to_owned());
+    }
+    let previous_config = APPLICATION_CONFIG.peek().clone();
+    let regtest_node = {
+        let config = get_blockchain_provider_config(blockchain_provider_service).await;
+        tokio::task::spawn_blocking(move || is_regtest_bitcoin_core(&config).then_some(config))
+            .await
+            .unwrap()
+    };
+    let datadir = std::env::temp_dir().join(format!(
+        "{DEMO_DATADIR_PREFIX}{}",
+        uuid::Uuid::new_v4().simple()
+    ));
+
+    let database = {
+        let datadir = datadir.clone();
+        tokio::task::spawn_blocking(move || Database::new(&datadir, DEMO_NETWORK))
+            .await
+            .unwrap()
+    };
+    let database = match database {
+        Ok(database) => database,
+        Err(e) => {
+            remove_demo_datadir(datadir).await;
+            return Err(log_error(format!(
+                "Could not create the demo database: {e}"
+            )));
+        }
+    };
+
+    // Key generation and wallet creation rely on the global network
+    bitcoin_network::set(DEMO_NETWORK);
+    let entered = async {
+        let funding_address = populate_demo_database(database.clone()).await?;
+        let funded = match regtest_node {
+            Some(config) => fund_demo_wallet(database.clone(), config, funding_address)
+                .await
+                .map_err(|e| log::warn!("Could not fund the demo wallet: {e}"))
+                .is_ok(),
+            None => false,
+        };
+        // The database service opens the demo database in its turn
+        drop(database);
+        update_application_config(
+            database_service,
+            ApplicationConfig {
+                network: DEMO_NETWORK,
+                datadir: datadir.clone(),
+            },
+        )
+        .await?;
+        Ok::<_, String>(funded)
+    }
+    .await;
+    let funded = match entered {
+        Ok(funded) => funded,
+        Err(e) => {
+            bitcoin_network::set(previous_config.network);
+            remove_demo_datadir(datadir).await;
+            return Err(e);
+        }
+    };
+
+    *DEMO_MODE.write() = Some(previous_config);
+    log::debug!("enter_demo_mode - finished");
+    Ok(funded)
+}
+
+/// Use the regtest node in the demo database and mine blocks to the demo wallet
+async fn fund_demo_wallet(
+    database: Database,
+    config: BlockchainProviderConfig,
+    funding_address: String,
+) -> Result<(), String> {
+    let node_config = config.clone();
+    database
+        .blocking_operation(move |mut db| node_config.save(&mut db))
+        .await
+        .map_err(log_error)?;
+    tokio::task::spawn_blocking(move || {
+        generate_regtest_blocks(&config, DEMO_FUNDING_BLOCKS, &funding_address)
+    })
+    .await
+    .unwrap()
+}
+
+/// Restore the [ApplicationConfig] in use before entering the demo mode and delete the demo data
+pub async fn exit_demo_mode(database_service: Coroutine<DatabaseCommand>) -> Result<(), String> {
+    log::debug!("exit_demo_mode - start");
+    let Some(previous_config) = DEMO_MODE.peek().clone() else {
+        return Ok(());
+    };
+    let demo_datadir = APPLICATION_CONFIG.peek().datadir.clone();
+    update_application_config(database_service, previous_config).await?;
+    *DEMO_MODE.write() = None;
+    remove_demo_datadir(demo_datadir).await;
+    log::debug!("exit_demo_mode - finished");
+    Ok(())
+}
+
+async fn remove_demo_datadir(datadir: std::path::PathBuf) {
+    if let Err(e) = tokio::task::spawn_blocking(move || std::fs::remove_dir_all(datadir))
+        .await
+        .unwrap()
+    {
+        log::warn!("Could not remove the demo data directory: {e}");
+    }
+}
@@ -0,0 +1,167 @@
+use super::*;
+
+use std::collections::{HashMap, HashSet};
+
+use btc_heritage_wallet::{
+    bitcoin::Amount,
+    btc_heritage::{HeirConfig, HeritageConfig},
+    OnlineWallet,
+};
+
+use crate::prelude::heir_spendable_ts;
+
+/// An heir of a Heritage Configuration and when it can inherit
+#[derive(Debug, Clone, PartialEq)]
+pub struct EstateHeir {
+    pub heir_config: HeirConfig,
+    pub spendable_ts: Option<u64>,
+}
+
+/// A Heritage Configuration of a wallet with the value it protects
+#[derive(Debug, Clone, PartialEq)]
+pub struct EstateHeritageConfig {
+    /// `true` for the newest Heritage Configuration of the wallet
+    pub current: bool,
+    /// Value of the UTXOs locked by this Heritage Configuration
+    pub balance: Amount,
+    /// The heirs, in the order they can inherit
+    pub heirs: Vec<EstateHeir>,
+}
+
+/// The Heritage Configurations of a wallet for the estate overview
+#[derive(Debug, Clone, PartialEq)]
+pub struct EstateWallet {
+    pub name: CCStr,
+    pub heritage_configs: Vec<EstateHeritageConfig>,
+    pub error: Option<CCStr>,
+}
+
+pub async fn estate_wallet(
+    database_service: Coroutine<DatabaseCommand>,
+    service_client_service: Coroutine<ServiceClientCommand>,
+    blockchain_provider_service: Coroutine<BlockchainProviderCommand>,
+    name: CCStr,
+) -> EstateWallet {
+    log::debug!("estate_wallet({name}) - start");
+    let mut estate_wallet = EstateWallet {
+        name: name.clone(),
+        heritage_configs: vec![],
+        error: None,
+    };
+    let wallet = match get_wallet(
+        database_service,
+        service_client_service,
+        blockchain_provider_service,
+        name.clone(),
+    )
+    .await
+    {
+        Ok(wallet) => wallet,
+        Err(e) => {
+            estate_wallet.error = Some(CCStr::from(e));
+            return estate_wallet;
+        }
+    };
+    // Sign-only wallets have no Heritage Configuration
+    if matches!(wallet.online_wallet(), AnyOnlineWallet::None) {
+        return estate_wallet;
+    }
+
+    let (subwallet_configs, utxos) = futures_util::join!(
+        wallet.list_subwallet_configs(),
+        wallet.list_heritage_utxos(),
+    );
+    let subwallet_configs = match subwallet_configs {
+        Ok(subwallet_configs) => subwallet_configs,
+        Err(e) => {
+            estate_wallet.error = Some(CCStr::from(log_error(e)));
+            return estate_wallet;
+        }
+    };
+    let mut balance_by_heritage_config = HashMap::<HeritageConfig, Amount>::new();
+    match utxos {
+        Ok(utxos) => {
+            for utxo in utxos {
+                *balance_by_heritage_config
+                    .entry(utxo.heritage_config)
+                    .or_default() += utxo.amount;
+            }
+        }
+        Err(e) => estate_wallet.error = Some(CCStr::from(log_error(e))),
+    }
+
+    // The first subwallet config is the current one
+    estate_wallet.heritage_configs = subwallet_configs
+        .into_iter()
+        .enumerate()
+        .map(|(index, swcm)| {
+            let heritage_config = swcm.heritage_config;
+            let heirs = heritage_config
+                .iter_heir_configs()
+                .map(|heir_config| EstateHeir {
+                    heir_config: heir_config.clone(),
+                    spendable_ts: heir_spendable_ts(&heritage_config, heir_config),
+                })
+                .collect();
+            EstateHeritageConfig {
+                current: index == 0,
+                balance: balance_by_heritage_config
+                    .get(&heritage_config)
+                    .cloned()
+                    .unwrap_or_default(),
+                heirs,
+            }
+        })
+        // Obsolete configurations locking nothing do not protect anything anymore
+        .filter(|ehc| ehc.current || ehc.balance > Amount::ZERO)
+        .collect();
+    log::debug!("estate_wallet({name}) - loaded");
+    estate_wallet
+}
+
+/// Heir configurations of the current Heritage Configuration of every wallet
+///
+/// Wallets that cannot be loaded or have no Heritage Configuration are skipped.
+pub async fn current_heir_configs(
+    database_service: Coroutine<DatabaseCommand>,
+    service_client_service: Coroutine<ServiceClientCommand>,
+    blockchain_provider_service: Coroutine<BlockchainProviderCommand>,
+) -> HashSet<HeirConfig> {
+    log::debug!("current_heir_configs - start");
+    let wallet_names = list_wallet_names(database_service)
+        .await
+        .unwrap_or_default();
+    let mut heir_configs = HashSet::new();
+    for name in wallet_names {
+        let wallet = match get_wallet(
+            database_service,
+            service_client_service,
+            blockchain_provider_service,
+            name.clone(),
+        )
+        .await
+        {
+            Ok(wallet) => wallet,
+            Err(e) => {
+                log::warn!("current_heir_configs - Could not load wallet {name}: {e}");
+                continue;
+            }
+        };
+        if matches!(wallet.online_wallet(), AnyOnlineWallet::None) {
+            continue;
+        }
+        // The first subwallet config is the current one
+        match wallet.list_subwallet_configs().await {
+            Ok(subwallet_configs) => {
+                if let Some(swcm) = subwallet_configs.first() {
+                    heir_configs.extend(swcm.heritage_config.iter_heir_configs().cloned());
+                }
+            }
+            Err(e) => {
+                log::warn!("current_heir_configs - Could not list configs of wallet {name}: {e}")
+            }
+        }
+    }
+    log::debug!("current_heir_configs - loaded");
+    heir_configs
+}
@@ -0,0 +1,336 @@
+use super::*;
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    rc::Rc,
+};
+
+use btc_heritage_wallet::{
+    bitcoin::Amount,
+    btc_heritage::{bitcoincore_rpc::jsonrpc::serde_json, utils::timestamp_now},
+};
+use serde::{Deserialize, Serialize};
+
+/// Public API providing the BTC exchange rates
+const EXCHANGE_RATE_API_URL: &str = "https://mempool.space/api/v1";
+
+/// The fiat currencies for which a BTC exchange rate is available
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum FiatCurrency {
+    #[default]
+    Usd,
+    Eur,
+    Gbp,
+    Cad,
+    Chf,
+    Aud,
+    Jpy,
+}
+impl FiatCurrency {
+    pub fn list() -> [Self; 7] {
+        [
+            Self::Usd,
+            Self::Eur,
+            Self::Gbp,
+            Self::Cad,
+            Self::Chf,
+            Self::Aud,
+            Self::Jpy,
+        ]
+    }
+    pub fn code(self) -> &'static str {
+        match self {
+            FiatCurrency::Usd => "USD",
+            FiatCurrency::Eur => "EUR",
+            FiatCurrency::Gbp => "GBP",
+            FiatCurrency::Cad => "CAD",
+            FiatCurrency::Chf => "CHF",
+            FiatCurrency::Aud => "AUD",
+            FiatCurrency::Jpy => "JPY",
+        }
+    }
+    /// Format a fiat value in this currency, e.g. `1234.56 USD`
+    pub fn format(self, value: f64) -> String {
+        match self {
+            FiatCurrency::Jpy => format!("{value:.0} {}", self.code()),
+            _ => format!("{value:.2} {}", self.code()),
+        }
+    }
+    /// Value of a bitcoin amount in this currency, given the exchange rate of 1 BTC
+    pub fn value_of(amount: Amount, exchange_rate: f64) -> f64 {
+        amount.to_btc() * exchange_rate
+    }
+}
+impl core::str::FromStr for FiatCurrency {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::list()
+            .into_iter()
+            .find(|currency| currency.code() == s)
+            .ok_or_else(|| format!("Unknown currency {s}"))
+    }
+}
+
+/// Fetch the current exchange rate of 1 BTC in the given currency
+pub async fn fetch_btc_exchange_rate(currency: FiatCurrency) -> Result<f64, String> {
+    log::debug!("fetch_btc_exchange_rate({currency:?}) - start");
+    let prices = reqwest::get(format!("{EXCHANGE_RATE_API_URL}/prices"))
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(log_error)?
+        .text()
+        .await
+        .map_err(log_error)?;
+    let prices = serde_json::from_str::<serde_json::Value>(&prices).map_err(log_error)?;
+    let exchange_rate = prices[currency.code()]
+        .as_f64()
+        .ok_or_else(|| log_error(format!("No exchange rate for {}", currency.code())))?;
+    log::debug!("fetch_btc_exchange_rate({currency:?}) - loaded");
+    Ok(exchange_rate)
+}
+
+const FIAT_CURRENCY_KEY: &str = "gui_fiat_currency";
+
+/// Load the currency used to display fiat values, `None` if the user did not enable them
+pub async fn load_fiat_currency(
+    database_service: Coroutine<DatabaseCommand>,
+) -> Result<Option<FiatCurrency>, String> {
+    blocking_db_service_operation(database_service, "Load the fiat currency", |db| {
+        db.get_item::<Option<FiatCurrency>>(FIAT_CURRENCY_KEY)
+    })
+    .await
+    .map(Option::flatten)
+    .map_err(log_error)
+}
+
+pub async fn save_fiat_currency(
+    database_service: Coroutine<DatabaseCommand>,
+    currency: Option<FiatCurrency>,
+) -> Result<(), String> {
+    blocking_db_service_operation(database_service, "Save the fiat currency", move |mut db| {
+        db.update_item(FIAT_CURRENCY_KEY, &currency)
+    })
+    .await
+    .map_err(log_error)
+}
+
+/// How the amounts of the heritages are displayed to the heirs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum HeritageAmountDisplay {
+    #[default]
+    Btc,
+    Sat,
+    /// Value in the fiat currency of the user, estimated with the current exchange rate
+    FiatAtMaturity,
+}
+impl HeritageAmountDisplay {
+    pub fn list() -> [Self; 3] {
+        [Self::Btc, Self::Sat, Self::FiatAtMaturity]
+    }
+    pub fn code(self) -> &'static str {
+        match self {
+            HeritageAmountDisplay::Btc => "BTC",
+            HeritageAmountDisplay::Sat => "sat",
+            HeritageAmountDisplay::FiatAtMaturity => "fiat",
+        }
+    }
+    pub fn display(self) -> &'static str {
+        match self {
+            HeritageAmountDisplay::Btc => "BTC",
+            HeritageAmountDisplay::Sat => "sat",
+            HeritageAmountDisplay::FiatAtMaturity => "Fiat estimate at maturity",
+        }
+    }
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::list().into_iter().find(|d| d.code() == code)
+    }
+}
+
+const HERITAGE_AMOUNT_DISPLAY_KEY: &str = "gui_heritage_amount_display";
+
+pub async fn load_heritage_amount_display(
+    database_service: Coroutine<DatabaseCommand>,
+) -> Result<HeritageAmountDisplay, String> {
+    blocking_db_service_operation(database_service, "Load the amount display", |db| {
+        db.get_item::<HeritageAmountDisplay>(HERITAGE_AMOUNT_DISPLAY_KEY)
+    })
+    .await
+    .map(Option::unwrap_or_default)
+    .map_err(log_error)
+}
+
+pub async fn save_heritage_amount_display(
+    database_service: Coroutine<DatabaseCommand>,
+    heritage_amount_display: HeritageAmountDisplay,
+) -> Result<(), String> {
+    blocking_db_service_operation(
+        database_service,
+        "Save the amount display",
+        move |mut db| db.update_item(HERITAGE_AMOUNT_DISPLAY_KEY, &heritage_amount_display),
+    )
+    .await
+    .map_err(log_error)
+}
+
+const HISTORICAL_EXCHANGE_RATES_KEY: &str = "gui_historical_exchange_rates";
+
+/// Historical exchange rates are looked up with a daily granularity
+const EXCHANGE_RATE_PERIOD: u64 = 24 * 60 * 60;
+
+/// The past exchange rates of 1 BTC in a currency, by day
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoricalExchangeRates {
+    pub currency: FiatCurrency,
+    rates: BTreeMap<u64, f64>,
+}
+impl HistoricalExchangeRates {
+    fn period_start(ts: u64) -> u64 {
+        ts - ts % EXCHANGE_RATE_PERIOD
+    }
+    /// The exchange rate of 1 BTC on the day of the timestamp
+    pub fn rate_at(&self, ts: u64) -> Option<f64> {
+        self.rates.get(&Self::period_start(ts)).copied()
+    }
+    /// The value of a bitcoin amount on the day of the timestamp
+    pub fn value_at(&self, amount: Amount, ts: u64) -> Option<f64> {
+        self.rate_at(ts)
+            .map(|rate| FiatCurrency::value_of(amount, rate))
+    }
+}
+
+/// Fetch the whole price history of 1 BTC in the given currency, by day
+///
+/// The series is requested at once so that the API does not learn the dates of the
+/// transactions of the user.
+async fn fetch_historical_btc_exchange_rates(
+    currency: FiatCurrency,
+) -> Result<BTreeMap<u64, f64>, String> {
+    log::debug!("fetch_historical_btc_exchange_rates({currency:?}) - start");
+    let prices = reqwest::get(format!(
+        "{EXCHANGE_RATE_API_URL}/historical-price?currency={}",
+        currency.code()
+    ))
+    .await
+    .and_then(|response| response.error_for_status())
+    .map_err(log_error)?
+    .text()
+    .await
+    .map_err(log_error)?;
+    let prices = serde_json::from_str::<serde_json::Value>(&prices).map_err(log_error)?;
+    let rates = prices["prices"]
+        .as_array()
+        .ok_or_else(|| log_error(format!("No price history for {}", currency.code())))?
+        .iter()
+        .filter_map(|price| {
+            let time = price["time"].as_u64()?;
+            // The oldest points of the history have no price in some currencies
+            let rate = price[currency.code()].as_f64().filter(|rate| *rate > 0.0)?;
+            Some((HistoricalExchangeRates::period_start(time), rate))
+        })
+        .collect::<BTreeMap<_, _>>();
+    log::debug!(
+        "fetch_historical_btc_exchange_rates({currency:?}) - loaded {} prices",
+        rates.len()
+    );
+    Ok(rates)
+}
+
+/// Time during which a fetched price history is reused, for all the lookups
+const HISTORICAL_SERIES_TTL: u64 = 60 * 60;
+
+/// Price history of 1 BTC, by day
+type PriceSeries = BTreeMap<u64, f64>;
+
+/// The price histories fetched recently, by currency, with the timestamp of their fetch
+static HISTORICAL_SERIES_CACHE: GlobalSignal<HashMap<FiatCurrency, (u64, Rc<PriceSeries>)>> =
+    Signal::global(HashMap::new);
+
+/// The price history of 1 BTC in the given currency, fetched at most once
+/// per [HISTORICAL_SERIES_TTL]
+async fn historical_btc_exchange_rates_series(
+    currency: FiatCurrency,
+) -> Result<Rc<PriceSeries>, String> {
+    let now = timestamp_now();
+    if let Some((fetch_ts, series)) = HISTORICAL_SERIES_CACHE.peek().get(&currency) {
+        if fetch_ts + HISTORICAL_SERIES_TTL > now {
+            return Ok(series.clone());
+        }
+    }
+    let series = Rc::new(fetch_historical_btc_exchange_rates(currency).await?);
+    HISTORICAL_SERIES_CACHE
+        .write()
+        .insert(currency, (now, series.clone()));
+    Ok(series)
+}
+
+/// Lookup the exchange rates of 1 BTC at the given timestamps
+///
+/// The price history is only fetched when some past days are missing from the database,
+/// the rates of the past days being stored once looked up, and at most once per
+/// [HISTORICAL_SERIES_TTL] for the days without a price. Today's rate is still moving:
+/// it is taken from the price history if it was fetched recently, and never stored.
+/// Days for which the rate cannot be found are left out.
+pub async fn historical_btc_exchange_rates(
+    database_service: Coroutine<DatabaseCommand>,
+    currency: FiatCurrency,
+    timestamps: impl IntoIterator<Item = u64>,
+) -> Result<HistoricalExchangeRates, String> {
+    log::debug!("historical_btc_exchange_rates({currency:?}) - start");
+    let mut stored_rates = blocking_db_service_operation(
+        database_service,
+        "Load the historical exchange rates",
+        |db| db.get_item::<Vec<(FiatCurrency, u64, f64)>>(HISTORICAL_EXCHANGE_RATES_KEY),
+    )
+    .await
+    .map_err(log_error)?
+    .unwrap_or_default();
+
+    let mut rates = stored_rates
+        .iter()
+        .filter(|(c, _, _)| *c == currency)
+        .map(|(_, day, rate)| (*day, *rate))
+        .collect::<BTreeMap<_, _>>();
+    // Today's rate is still moving, only store past days
+    let today = HistoricalExchangeRates::period_start(timestamp_now());
+    let (missing_days, current_days): (BTreeSet<_>, BTreeSet<_>) = timestamps
+        .into_iter()
+        .map(HistoricalExchangeRates::period_start)
+        .filter(|day| !rates.contains_key(day))
+        .partition(|day| *day < today);
+    let series = if missing_days.is_empty() {
+        HISTORICAL_SERIES_CACHE
+            .peek()
+            .get(&currency)
+            .filter(|(fetch_ts, _)| fetch_ts + HISTORICAL_SERIES_TTL > timestamp_now())
+            .map(|(_, series)| series.clone())
+    } else {
+        // Keep the rates already stored if the API is unreachable
+        historical_btc_exchange_rates_series(currency).await.ok()
+    };
+    let mut new_rates = 0;
+    if let Some(series) = series {
+        for day in missing_days.into_iter().chain(current_days) {
+            // The history may be coarser than a day, use the closest earlier price
+            let Some((_, rate)) = series.range(..=day).next_back() else {
+                continue;
+            };
+            rates.insert(day, *rate);
+            if day < today {
+                stored_rates.push((currency, day, *rate));
+                new_rates += 1;
+            }
+        }
+    }
+    if new_rates > 0 {
+        blocking_db_service_operation(
+            database_service,
+            "Save the historical exchange rates",
+            move |mut db| db.update_item(HISTORICAL_EXCHANGE_RATES_KEY, &stored_rates),
+        )
+        .await
+        .map_err(log_error)?;
+    }
+    log::debug!("historical_btc_exchange_rates({currency:?}) - loaded {new_rates} new rates");
+    Ok(HistoricalExchangeRates { currency, rates })
+}
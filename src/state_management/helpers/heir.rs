@@ -1,13 +1,18 @@
 use super::*;
 
+use std::collections::HashMap;
+
 pub async fn get_heir(
     database_service: Coroutine<DatabaseCommand>,
     name: CCStr,
 ) -> Result<Heir, String> {
     log::debug!("get_heir({name}) - start");
-    let database = get_database(database_service).await;
-
-    let heir = Heir::load(&database, name.as_ref()).map_err(log_error)?;
+    let heir_name = name.clone();
+    let heir = blocking_db_service_operation(database_service, "Load the heir", move |db| {
+        Heir::load(&db, heir_name.as_ref())
+    })
+    .await
+    .map_err(log_error)?;
 
     log::debug!("get_heir({name}) - loaded");
 
@@ -30,15 +35,87 @@ pub async fn strip_heir_seed(
 ) -> Result<(), String> {
     log::debug!("strip_heir_seed({heir:?}) - start");
     let heir_name = heir.name().to_owned();
-    get_database(database_service)
-        .await
-        .blocking_operation(move |mut db| {
-            let mut heir = Heir::load(&db, &heir_name)?;
-            heir.strip_key_provider();
-            heir.save(&mut db)
+    blocking_db_service_operation(database_service, "Strip the heir seed", move |mut db| {
+        let mut heir = Heir::load(&db, &heir_name)?;
+        heir.strip_key_provider();
+        heir.save(&mut db)
+    })
+    .await
+    .map_err(log_error)?;
+    log::debug!("strip_heir_seed({heir:?}) - finished");
+    Ok(())
+}
+
+const HEIR_SERVICE_LINKS_KEY: &str = "gui_heir_service_links";
+
+/// Load the service heir explicitly linked to each local heir, by local heir name
+///
+/// Only needed when several service heirs share the heir configuration of a local heir.
+pub async fn load_heir_service_links(
+    database_service: Coroutine<DatabaseCommand>,
+) -> Result<HashMap<String, String>, String> {
+    log::debug!("load_heir_service_links - start");
+    let links =
+        blocking_db_service_operation(database_service, "Load the heir service links", move |db| {
+            db.get_item::<HashMap<String, String>>(HEIR_SERVICE_LINKS_KEY)
         })
         .await
-        .map_err(log_error)?;
-    log::debug!("strip_heir_seed({heir:?}) - finished");
+        .map(Option::unwrap_or_default)
+        .map_err(log_error);
+    log::debug!("load_heir_service_links - loaded");
+    links
+}
+
+/// Link the local heir to the service heir with the given id
+pub async fn save_heir_service_link(
+    database_service: Coroutine<DatabaseCommand>,
+    heir_name: CCStr,
+    service_heir_id: String,
+) -> Result<(), String> {
+    log::debug!("save_heir_service_link({heir_name}, {service_heir_id}) - start");
+    let name = heir_name.to_string();
+    blocking_db_service_operation(
+        database_service,
+        "Save an heir service link",
+        move |mut db| {
+            let mut links = db
+                .get_item::<HashMap<String, String>>(HEIR_SERVICE_LINKS_KEY)?
+                .unwrap_or_default();
+            links.insert(name, service_heir_id);
+            db.update_item(HEIR_SERVICE_LINKS_KEY, &links)
+        },
+    )
+    .await
+    .map_err(log_error)?;
+    log::debug!("save_heir_service_link({heir_name}) - finished");
     Ok(())
 }
+
+/// Move the service heir link of a renamed local heir to its new name
+pub(super) fn rename_heir_service_link(
+    db: &mut Database,
+    old_name: &str,
+    new_name: &str,
+) -> Result<(), DbError> {
+    let mut links = db
+        .get_item::<HashMap<String, String>>(HEIR_SERVICE_LINKS_KEY)?
+        .unwrap_or_default();
+    match links.remove(old_name) {
+        Some(service_heir_id) => {
+            links.insert(new_name.to_owned(), service_heir_id);
+            db.update_item(HEIR_SERVICE_LINKS_KEY, &links)
+        }
+        None => Ok(()),
+    }
+}
+
+/// Delete the service heir link of a deleted local heir
+pub(super) fn delete_heir_service_link(db: &mut Database, name: &str) -> Result<(), DbError> {
+    let mut links = db
+        .get_item::<HashMap<String, String>>(HEIR_SERVICE_LINKS_KEY)?
+        .unwrap_or_default();
+    match links.remove(name) {
+        Some(_) => db.update_item(HEIR_SERVICE_LINKS_KEY, &links),
+        None => Ok(()),
+    }
+}
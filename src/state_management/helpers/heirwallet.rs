@@ -1,3 +1,11 @@
+use std::sync::Arc;
+
+use btc_heritage_wallet::{
+    btc_heritage::HeritageWalletBackup,
+    heritage_provider::{LocalWallet, ServiceBinding},
+    AnyKeyProvider, BoundFingerprint,
+};
+
 use super::*;
 
 pub async fn get_heirwallet(
@@ -6,9 +14,13 @@ pub async fn get_heirwallet(
     name: CCStr,
 ) -> Result<HeirWallet, String> {
     log::debug!("get_heirwallet({name}) - start");
-    let database = get_database(database_service).await;
-
-    let mut heirwallet = HeirWallet::load(&database, name.as_ref()).map_err(log_error)?;
+    let heirwallet_name = name.clone();
+    let mut heirwallet =
+        blocking_db_service_operation(database_service, "Load the heir wallet", move |db| {
+            HeirWallet::load(&db, heirwallet_name.as_ref())
+        })
+        .await
+        .map_err(log_error)?;
 
     match heirwallet.heritage_provider_mut() {
         AnyHeritageProvider::None => (),
@@ -19,7 +31,7 @@ pub async fn get_heirwallet(
         AnyHeritageProvider::LocalWallet(local_wallet) => {
             local_wallet
                 .local_heritage_wallet_mut()
-                .init_heritage_wallet(database)
+                .init_heritage_wallet(get_database(database_service).await)
                 .await
                 .map_err(log_error)?;
         }
@@ -39,3 +51,87 @@ pub async fn delete_heirwallet(
     log::debug!("delete_heirwallet({heirwallet:?}) - finished");
     Ok(())
 }
+
+pub async fn save_heirwallet(
+    database_service: Coroutine<DatabaseCommand>,
+    heirwallet: Arc<HeirWallet>,
+) -> Result<(), String> {
+    log::debug!("save_heirwallet({heirwallet:?}) - start");
+
+    blocking_db_service_operation(database_service, "Save the heir wallet", move |mut db| {
+        heirwallet.save(&mut db)
+    })
+    .await
+    .map_err(log_error)?;
+
+    log::debug!("save_heirwallet - finished");
+    Ok(())
+}
+
+/// Replace the Heritage Provider of an heir wallet, keeping its name and Key Provider
+///
+/// With an owner wallet `backup`, the new provider is a Local Heritage Provider,
+/// else it is the Heritage Service.
+pub async fn switch_heirwallet_heritage_provider(
+    database_service: Coroutine<DatabaseCommand>,
+    service_client_service: Coroutine<ServiceClientCommand>,
+    name: CCStr,
+    backup: Option<HeritageWalletBackup>,
+) -> Result<HeirWallet, String> {
+    log::debug!("switch_heirwallet_heritage_provider({name}) - start");
+    let mut heirwallet =
+        get_heirwallet(database_service, service_client_service, name.clone()).await?;
+    let fingerprint = heirwallet.fingerprint().map_err(log_error)?;
+
+    let heritage_provider = match backup {
+        Some(backup) => {
+            let local_wallet = blocking_db_service_operation(
+                database_service,
+                "Create the local heritage provider",
+                move |db| LocalWallet::create(fingerprint, &db, backup),
+            )
+            .await
+            .map_err(log_error)?;
+            AnyHeritageProvider::LocalWallet(local_wallet)
+        }
+        None => {
+            let service_client = heritage_service_client(service_client_service).await;
+            AnyHeritageProvider::Service(ServiceBinding::new(fingerprint, service_client))
+        }
+    };
+    *heirwallet.heritage_provider_mut() = heritage_provider;
+
+    save_heirwallet(database_service, Arc::new(heirwallet)).await?;
+    log::debug!("switch_heirwallet_heritage_provider({name}) - finished");
+
+    // Reload it so the new Heritage Provider is properly initialized
+    get_heirwallet(database_service, service_client_service, name).await
+}
+
+/// Give a Key Provider to a watch-only heir wallet
+///
+/// The Key Provider must hold the key of the heir the wallet was created for.
+pub async fn set_heirwallet_key_provider(
+    database_service: Coroutine<DatabaseCommand>,
+    service_client_service: Coroutine<ServiceClientCommand>,
+    name: CCStr,
+    key_provider: AnyKeyProvider,
+) -> Result<HeirWallet, String> {
+    log::debug!("set_heirwallet_key_provider({name}) - start");
+    let mut heirwallet =
+        get_heirwallet(database_service, service_client_service, name.clone()).await?;
+    let expected_fingerprint = heirwallet.fingerprint().map_err(log_error)?;
+    let fingerprint = key_provider.fingerprint().map_err(log_error)?;
+    if fingerprint != expected_fingerprint {
+        return Err(log_error(format!(
+            "The key has the fingerprint {fingerprint} but the heir wallet expects \
+            {expected_fingerprint}"
+        )));
+    }
+    *heirwallet.key_provider_mut() = key_provider;
+
+    save_heirwallet(database_service, Arc::new(heirwallet)).await?;
+    log::debug!("set_heirwallet_key_provider({name}) - finished");
+
+    get_heirwallet(database_service, service_client_service, name).await
+}
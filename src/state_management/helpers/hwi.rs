@@ -0,0 +1,98 @@
+use std::time::Duration;
+
+use btc_heritage_wallet::{
+    bitcoin::Network, btc_heritage::utils::bitcoin_network,
+    heritage_service_api_client::Fingerprint,
+};
+use serde::Deserialize;
+
+use super::*;
+
+/// Time the user is given to review and approve a transaction on the device
+const HWI_SIGNING_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Enumerate again the hardware wallets reachable through HWI
+pub fn refresh_hwi_status() {
+    log::debug!("refresh_hwi_status - start");
+    HWI_REFRESH.notify_one();
+    log::debug!("refresh_hwi_status - finished");
+}
+
+/// Returns the fingerprints of the HWI devices ready to be used
+///
+/// Devices still locked, or waiting for their passphrase, cannot report their fingerprint
+/// and are not returned.
+pub fn hwi_ready_fingerprints() -> Vec<Fingerprint> {
+    match &*HWI_STATUS.read() {
+        Some(HwiStatus::Devices(devices)) => devices
+            .iter()
+            .filter(|device| device.not_ready_reason().is_none())
+            .filter_map(|device| device.fingerprint())
+            .collect(),
+        _ => vec![],
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct HwiXPub {
+    xpub: String,
+}
+
+/// Derive the Taproot (BIP-86) account xpubs `accounts` of the HWI device with the
+/// fingerprint `fingerprint`, as descriptor keys with their key origin
+pub async fn hwi_account_xpubs(
+    fingerprint: Fingerprint,
+    accounts: std::ops::Range<u32>,
+) -> Result<Vec<String>, String> {
+    log::debug!("hwi_account_xpubs({fingerprint}, {accounts:?}) - start");
+    let coin_type = match bitcoin_network::get() {
+        Network::Bitcoin => 0,
+        _ => 1,
+    };
+    let mut account_xpubs = vec![];
+    for account in accounts {
+        let HwiXPub { xpub } = run_hwi(
+            vec![
+                "-f".to_owned(),
+                fingerprint.to_string(),
+                "getxpub".to_owned(),
+                format!("m/86h/{coin_type}h/{account}h"),
+            ],
+            HWI_TIMEOUT,
+        )
+        .await
+        .map_err(log_error)?;
+        account_xpubs.push(format!(
+            "[{fingerprint}/86'/{coin_type}'/{account}']{xpub}/*"
+        ));
+    }
+    log::debug!("hwi_account_xpubs({fingerprint}) - finished");
+    Ok(account_xpubs)
+}
+
+#[derive(Debug, Deserialize)]
+struct HwiSignedPsbt {
+    psbt: String,
+}
+
+/// Sign `psbt` with the HWI device with the fingerprint `fingerprint`
+///
+/// The user must approve the transaction on the device, so this can take a while.
+/// Dropping the returned future stops waiting for the device.
+/// Returns the base64 encoded PSBT with the signatures of the device added.
+pub async fn hwi_sign_psbt(fingerprint: Fingerprint, psbt: CCStr) -> Result<CCStr, String> {
+    log::debug!("hwi_sign_psbt({fingerprint}) - start");
+    let HwiSignedPsbt { psbt } = run_hwi(
+        vec![
+            "-f".to_owned(),
+            fingerprint.to_string(),
+            "signtx".to_owned(),
+            psbt.to_string(),
+        ],
+        HWI_SIGNING_TIMEOUT,
+    )
+    .await
+    .map_err(log_error)?;
+    log::debug!("hwi_sign_psbt({fingerprint}) - finished");
+    Ok(CCStr::from(psbt))
+}
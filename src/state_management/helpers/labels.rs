@@ -0,0 +1,187 @@
+use super::*;
+
+use btc_heritage_wallet::btc_heritage::bitcoincore_rpc::jsonrpc::serde_json;
+use serde::{Deserialize, Serialize};
+
+/// The kind of object a [WalletLabel] refers to, as defined by BIP-329
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WalletLabelType {
+    Tx,
+    Addr,
+    Pubkey,
+    Input,
+    Output,
+    Xpub,
+}
+impl WalletLabelType {
+    pub fn list() -> [Self; 6] {
+        [
+            Self::Tx,
+            Self::Addr,
+            Self::Pubkey,
+            Self::Input,
+            Self::Output,
+            Self::Xpub,
+        ]
+    }
+    pub fn name(self) -> &'static str {
+        match self {
+            WalletLabelType::Tx => "Transactions",
+            WalletLabelType::Addr => "Addresses",
+            WalletLabelType::Pubkey => "Public keys",
+            WalletLabelType::Input => "Inputs",
+            WalletLabelType::Output => "Outputs",
+            WalletLabelType::Xpub => "Extended public keys",
+        }
+    }
+}
+
+/// A label of a wallet, in the BIP-329 format
+///
+/// Unknown fields of imported labels are not kept.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalletLabel {
+    #[serde(rename = "type")]
+    pub label_type: WalletLabelType,
+    #[serde(rename = "ref")]
+    pub reference: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spendable: Option<bool>,
+}
+
+/// Parse labels in the BIP-329 JSON Lines format, empty lines are ignored
+pub fn parse_wallet_labels(data: &str) -> Result<Vec<WalletLabel>, String> {
+    data.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str::<WalletLabel>(line)
+                .map_err(|e| format!("Invalid label on line {}: {e}", i + 1))
+        })
+        .collect()
+}
+
+/// Serialize labels in the BIP-329 JSON Lines format
+pub fn wallet_labels_to_jsonl(labels: &[WalletLabel]) -> String {
+    labels
+        .iter()
+        .map(|label| serde_json::to_string(label).expect("WalletLabel is serializable"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Merge `imported` labels into `labels`
+///
+/// An imported label replaces the existing label of the same object.
+/// Returns the number of labels added and the number of labels replaced.
+pub fn merge_wallet_labels(
+    labels: &mut Vec<WalletLabel>,
+    imported: Vec<WalletLabel>,
+) -> (usize, usize) {
+    let (mut added, mut replaced) = (0, 0);
+    for label in imported {
+        match labels
+            .iter_mut()
+            .find(|l| l.label_type == label.label_type && l.reference == label.reference)
+        {
+            Some(existing) => {
+                if *existing != label {
+                    *existing = label;
+                    replaced += 1;
+                }
+            }
+            None => {
+                labels.push(label);
+                added += 1;
+            }
+        }
+    }
+    (added, replaced)
+}
+
+fn wallet_labels_key(wallet_name: &str) -> String {
+    format!("gui_wallet_labels#{wallet_name}")
+}
+
+pub async fn load_wallet_labels(
+    database_service: Coroutine<DatabaseCommand>,
+    wallet_name: CCStr,
+) -> Result<Vec<WalletLabel>, String> {
+    log::debug!("load_wallet_labels({wallet_name}) - start");
+    let key = wallet_labels_key(&wallet_name);
+    let labels =
+        blocking_db_service_operation(database_service, "Load the wallet labels", move |db| {
+            db.get_item::<Vec<WalletLabel>>(&key)
+        })
+        .await
+        .map(Option::unwrap_or_default)
+        .map_err(log_error);
+    log::debug!("load_wallet_labels({wallet_name}) - loaded");
+    labels
+}
+
+pub async fn save_wallet_labels(
+    database_service: Coroutine<DatabaseCommand>,
+    wallet_name: CCStr,
+    labels: Vec<WalletLabel>,
+) -> Result<(), String> {
+    log::debug!("save_wallet_labels({wallet_name}) - start");
+    let key = wallet_labels_key(&wallet_name);
+    blocking_db_service_operation(database_service, "Save the wallet labels", move |mut db| {
+        db.update_item(&key, &labels)
+    })
+    .await
+    .map_err(log_error)?;
+    log::debug!("save_wallet_labels({wallet_name}) - finished");
+    Ok(())
+}
+
+/// Set the label of an object of the wallet, or remove it if `label` is [None]
+pub async fn set_wallet_label(
+    database_service: Coroutine<DatabaseCommand>,
+    wallet_name: CCStr,
+    label_type: WalletLabelType,
+    reference: String,
+    label: Option<String>,
+) -> Result<(), String> {
+    log::debug!("set_wallet_label({wallet_name}, {reference}) - start");
+    let mut labels = load_wallet_labels(database_service, wallet_name.clone()).await?;
+    labels.retain(|l| !(l.label_type == label_type && l.reference == reference));
+    if let Some(label) = label {
+        labels.push(WalletLabel {
+            label_type,
+            reference,
+            label: Some(label),
+            origin: None,
+            spendable: None,
+        });
+    }
+    save_wallet_labels(database_service, wallet_name, labels).await?;
+    log::debug!("set_wallet_label - finished");
+    Ok(())
+}
+
+/// Move the labels of a renamed wallet to its new name
+pub(super) fn rename_wallet_labels(
+    db: &mut Database,
+    old_name: &str,
+    new_name: &str,
+) -> Result<(), DbError> {
+    let old_key = wallet_labels_key(old_name);
+    if let Some(labels) = db.get_item::<Vec<WalletLabel>>(&old_key)? {
+        db.update_item(&wallet_labels_key(new_name), &labels)?;
+        db.delete_item::<Vec<WalletLabel>>(&old_key)?;
+    }
+    Ok(())
+}
+
+/// Delete the labels of a deleted wallet
+pub(super) fn delete_wallet_labels(db: &mut Database, name: &str) -> Result<(), DbError> {
+    db.delete_item::<Vec<WalletLabel>>(&wallet_labels_key(name))?;
+    Ok(())
+}
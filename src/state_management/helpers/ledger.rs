@@ -1,15 +1,15 @@
-use btc_heritage_wallet::heritage_service_api_client::Fingerprint;
+use std::collections::BTreeMap;
+
+use btc_heritage_wallet::{
+    btc_heritage::AccountXPubId, heritage_service_api_client::Fingerprint, LedgerPolicy,
+};
+use serde::{Deserialize, Serialize};
 
 use super::*;
 
 pub fn refresh_ledger_status() {
     log::debug!("refresh_ledger_status - start");
-    spawn(async move {
-        let new_status = Some(LedgerStatus::current().await);
-        if LEDGER_STATUS() != new_status {
-            *LEDGER_STATUS.write() = new_status;
-        }
-    });
+    LEDGER_REFRESH.notify_one();
     log::debug!("refresh_ledger_status - finished");
 }
 
@@ -29,3 +29,79 @@ pub fn ledger_is_ready() -> Option<Fingerprint> {
         None
     }
 }
+
+/// Returns `true` if the seed of `signer` was already verified on the connected Ledger
+pub fn ledger_seed_verified(signer: &str) -> bool {
+    LEDGER_VERIFIED_SIGNERS.peek().contains(signer)
+}
+
+/// Remember that the connected Ledger holds the seed of `signer`, until it is disconnected
+pub fn set_ledger_seed_verified(signer: String) {
+    LEDGER_VERIFIED_SIGNERS.write().insert(signer);
+}
+
+/// The Ledger policies registered for a wallet, remembered to display them before the wallet is loaded
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LedgerPoliciesCache {
+    /// Fingerprint of the Ledger device the policies are registered on
+    pub fingerprint: Fingerprint,
+    pub policies: BTreeMap<AccountXPubId, LedgerPolicy>,
+}
+
+fn ledger_policies_cache_key(wallet_name: &str) -> String {
+    format!("gui_ledger_policies_cache#{wallet_name}")
+}
+
+pub async fn load_ledger_policies_cache(
+    database_service: Coroutine<DatabaseCommand>,
+    wallet_name: CCStr,
+) -> Result<Option<LedgerPoliciesCache>, String> {
+    log::debug!("load_ledger_policies_cache({wallet_name}) - start");
+    let key = ledger_policies_cache_key(&wallet_name);
+    let ledger_policies_cache =
+        blocking_db_service_operation(database_service, "Load the Ledger policies", move |db| {
+            db.get_item::<LedgerPoliciesCache>(&key)
+        })
+        .await
+        .map_err(log_error);
+    log::debug!("load_ledger_policies_cache({wallet_name}) - loaded");
+    ledger_policies_cache
+}
+
+pub async fn save_ledger_policies_cache(
+    database_service: Coroutine<DatabaseCommand>,
+    wallet_name: CCStr,
+    ledger_policies_cache: LedgerPoliciesCache,
+) -> Result<(), String> {
+    log::debug!("save_ledger_policies_cache({wallet_name}) - start");
+    let key = ledger_policies_cache_key(&wallet_name);
+    blocking_db_service_operation(
+        database_service,
+        "Save the Ledger policies",
+        move |mut db| db.update_item(&key, &ledger_policies_cache),
+    )
+    .await
+    .map_err(log_error)?;
+    log::debug!("save_ledger_policies_cache({wallet_name}) - finished");
+    Ok(())
+}
+
+/// Move the Ledger policies cache of a renamed wallet to its new name
+pub(super) fn rename_ledger_policies_cache(
+    db: &mut Database,
+    old_name: &str,
+    new_name: &str,
+) -> Result<(), DbError> {
+    let old_key = ledger_policies_cache_key(old_name);
+    if let Some(ledger_policies_cache) = db.get_item::<LedgerPoliciesCache>(&old_key)? {
+        db.update_item(&ledger_policies_cache_key(new_name), &ledger_policies_cache)?;
+        db.delete_item::<LedgerPoliciesCache>(&old_key)?;
+    }
+    Ok(())
+}
+
+/// Delete the Ledger policies cache of a deleted wallet
+pub(super) fn delete_ledger_policies_cache(db: &mut Database, name: &str) -> Result<(), DbError> {
+    db.delete_item::<LedgerPoliciesCache>(&ledger_policies_cache_key(name))?;
+    Ok(())
+}
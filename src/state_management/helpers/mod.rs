@@ -1,16 +1,34 @@
+mod activation;
+mod backup_export;
 mod blockchain;
 mod clipboard;
 mod config;
 mod database;
+mod demo;
+mod estate;
+mod fiat;
 mod heir;
 mod heirwallet;
+mod hwi;
+mod labels;
 mod ledger;
+mod notifications;
+mod portfolio;
+mod references;
+mod rehearsal;
+mod reserved_addresses;
+mod seed_roles;
 mod service;
+mod startup_checks;
+mod tags;
 mod wallet;
+mod wallet_notes;
+mod wallet_settings;
 
 use dioxus::prelude::*;
 
 use btc_heritage_wallet::{
+    errors::DbError,
     heritage_service_api_client::{DeviceAuthorizationResponse, HeritageServiceClient},
     AnyHeritageProvider, AnyOnlineWallet, Database, DatabaseItem, Heir, HeirWallet, Wallet,
 };
@@ -22,21 +40,45 @@ use crate::{
 };
 
 use super::{
-    blockchain::BlockchainProviderCommand,
-    database::{ApplicationConfig, DatabaseCommand, DatabaseItemCommand},
-    ledger::{LedgerStatus, LEDGER_STATUS},
-    service::ServiceClientCommand,
+    blockchain::{BlockchainProviderCommand, BlockchainProviderStatus, BLOCKCHAIN_PROVIDER_STATUS},
+    clock::{CLOCK_DRIFT, CLOCK_DRIFT_MEASURED},
+    database::{ApplicationConfig, DatabaseCommand, DatabaseItemCommand, APPLICATION_CONFIG},
+    database_queue::{DbOperationPriority, OPERATION_TIMEOUT},
+    demo::{populate_demo_database, DEMO_DATADIR_PREFIX, DEMO_MODE, DEMO_NETWORK},
+    event_bus::{publish_event, EventBus},
+    hwi::{run_hwi, HwiStatus, HWI_REFRESH, HWI_STATUS, HWI_TIMEOUT},
+    ledger::{LedgerStatus, LEDGER_REFRESH, LEDGER_STATUS, LEDGER_VERIFIED_SIGNERS},
+    notifications::{AlertEvent, NOTIFICATIONS},
+    onboarding::{OnboardingStatus, ONBOARDING_STATUS},
+    service::{ServiceAuthState, ServiceClientCommand, SERVICE_AUTH_STATE, SERVICE_STATUS},
 };
 
+pub use activation::*;
+pub use backup_export::*;
 pub use blockchain::*;
 pub use clipboard::*;
 pub use config::*;
 pub use database::*;
+pub use demo::*;
+pub use estate::*;
+pub use fiat::*;
 pub use heir::*;
 pub use heirwallet::*;
+pub use hwi::*;
+pub use labels::*;
 pub use ledger::*;
+pub use notifications::*;
+pub use portfolio::*;
+pub use references::*;
+pub use rehearsal::*;
+pub use reserved_addresses::*;
+pub use seed_roles::*;
 pub use service::*;
+pub use startup_checks::*;
+pub use tags::*;
 pub use wallet::*;
+pub use wallet_notes::*;
+pub use wallet_settings::*;
 
 pub fn use_blockchain_provider_service() -> Coroutine<BlockchainProviderCommand> {
     use_coroutine_handle()
@@ -0,0 +1,49 @@
+use uuid::Uuid;
+
+use crate::components::alerts::AlertClass;
+
+use super::*;
+
+/// Publishes an [AlertEvent] on the event bus so that the alert is recorded
+/// in the notification center history.
+///
+/// This is a no-op (except for a warning log) if called outside of the
+/// application scope, where the event bus is not reachable.
+pub fn publish_alert_event(alert_class: AlertClass, title: CCStr, message: CCStr) {
+    log::debug!("publish_alert_event - start");
+    match try_consume_context::<EventBus>() {
+        Some(event_bus) => publish_event(
+            event_bus,
+            AlertEvent {
+                alert_class,
+                title,
+                message,
+            },
+        ),
+        None => log::warn!("publish_alert_event - Event bus is not reachable, alert not recorded"),
+    }
+    log::debug!("publish_alert_event - finished");
+}
+
+pub fn mark_notification_read(id: Uuid) {
+    log::debug!("mark_notification_read - start");
+    if let Some(notification) = NOTIFICATIONS.write().iter_mut().find(|n| n.id == id) {
+        notification.read = true;
+    }
+    log::debug!("mark_notification_read - finished");
+}
+
+pub fn mark_all_notifications_read() {
+    log::debug!("mark_all_notifications_read - start");
+    NOTIFICATIONS
+        .write()
+        .iter_mut()
+        .for_each(|notification| notification.read = true);
+    log::debug!("mark_all_notifications_read - finished");
+}
+
+pub fn clear_notifications() {
+    log::debug!("clear_notifications - start");
+    NOTIFICATIONS.write().clear();
+    log::debug!("clear_notifications - finished");
+}
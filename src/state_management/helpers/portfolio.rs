@@ -0,0 +1,154 @@
+use super::*;
+
+use btc_heritage_wallet::{
+    bitcoin::Amount, btc_heritage::utils::timestamp_now, HeritageProvider, OnlineWallet,
+};
+
+use crate::prelude::heritage_config_expiration_ts;
+
+/// Summary of a wallet for the portfolio dashboard
+#[derive(Debug, Clone, PartialEq)]
+pub struct PortfolioWallet {
+    pub name: CCStr,
+    /// `false` for sign-only wallets, which have no balance
+    pub online: bool,
+    pub balance: Amount,
+    pub spendable: Amount,
+    pub last_sync_ts: Option<u64>,
+    /// Expiration of the current Heritage Configuration
+    pub expiration_ts: Option<u64>,
+    pub pending_transactions: usize,
+    pub error: Option<CCStr>,
+}
+
+/// Summary of an heir wallet for the portfolio dashboard
+#[derive(Debug, Clone, PartialEq)]
+pub struct PortfolioHeirWallet {
+    pub name: CCStr,
+    /// Total value of the heritages
+    pub inheritable: Amount,
+    /// Value of the heritages that can already be spent
+    pub spendable: Amount,
+    /// Next time a heritage becomes spendable
+    pub next_maturity_ts: Option<u64>,
+    pub error: Option<CCStr>,
+}
+
+pub async fn portfolio_wallet(
+    database_service: Coroutine<DatabaseCommand>,
+    service_client_service: Coroutine<ServiceClientCommand>,
+    blockchain_provider_service: Coroutine<BlockchainProviderCommand>,
+    name: CCStr,
+) -> PortfolioWallet {
+    log::debug!("portfolio_wallet({name}) - start");
+    let mut portfolio_wallet = PortfolioWallet {
+        name: name.clone(),
+        online: true,
+        balance: Amount::ZERO,
+        spendable: Amount::ZERO,
+        last_sync_ts: None,
+        expiration_ts: None,
+        pending_transactions: 0,
+        error: None,
+    };
+    let wallet = match get_wallet(
+        database_service,
+        service_client_service,
+        blockchain_provider_service,
+        name.clone(),
+    )
+    .await
+    {
+        Ok(wallet) => wallet,
+        Err(e) => {
+            portfolio_wallet.error = Some(CCStr::from(e));
+            return portfolio_wallet;
+        }
+    };
+    if matches!(wallet.online_wallet(), AnyOnlineWallet::None) {
+        portfolio_wallet.online = false;
+        return portfolio_wallet;
+    }
+
+    let (wallet_status, subwallet_configs, transactions) = futures_util::join!(
+        wallet.get_wallet_status(),
+        wallet.list_subwallet_configs(),
+        wallet.list_transactions(),
+    );
+    let mut errors = vec![];
+    match wallet_status {
+        Ok(wallet_status) => {
+            let balance = wallet_status.balance.total_balance();
+            portfolio_wallet.balance = Amount::from_sat(balance.get_total());
+            portfolio_wallet.spendable = Amount::from_sat(balance.get_spendable());
+            portfolio_wallet.last_sync_ts = Some(wallet_status.last_sync_ts);
+        }
+        Err(e) => errors.push(log_error(e)),
+    }
+    match subwallet_configs {
+        // The first subwallet config is the current one
+        Ok(subwallet_configs) => {
+            portfolio_wallet.expiration_ts = subwallet_configs
+                .first()
+                .and_then(|swcm| heritage_config_expiration_ts(&swcm.heritage_config))
+        }
+        Err(e) => errors.push(log_error(e)),
+    }
+    match transactions {
+        Ok(transactions) => {
+            portfolio_wallet.pending_transactions = transactions
+                .iter()
+                .filter(|tx| tx.confirmation_time.is_none())
+                .count()
+        }
+        Err(e) => errors.push(log_error(e)),
+    }
+    if !errors.is_empty() {
+        portfolio_wallet.error = Some(CCStr::from(errors.join(", ")));
+    }
+    log::debug!("portfolio_wallet({name}) - loaded");
+    portfolio_wallet
+}
+
+pub async fn portfolio_heirwallet(
+    database_service: Coroutine<DatabaseCommand>,
+    service_client_service: Coroutine<ServiceClientCommand>,
+    name: CCStr,
+) -> PortfolioHeirWallet {
+    log::debug!("portfolio_heirwallet({name}) - start");
+    let mut portfolio_heirwallet = PortfolioHeirWallet {
+        name: name.clone(),
+        inheritable: Amount::ZERO,
+        spendable: Amount::ZERO,
+        next_maturity_ts: None,
+        error: None,
+    };
+    let heritages =
+        match get_heirwallet(database_service, service_client_service, name.clone()).await {
+            Ok(heirwallet) => heirwallet.list_heritages().await.map_err(log_error),
+            Err(e) => Err(e),
+        };
+    match heritages {
+        Ok(heritages) => {
+            let now = timestamp_now();
+            for heritage in heritages {
+                let value = heritage.value.unwrap_or(Amount::ZERO);
+                portfolio_heirwallet.inheritable += value;
+                match heritage.maturity {
+                    Some(maturity) if maturity < now => portfolio_heirwallet.spendable += value,
+                    Some(maturity) => {
+                        portfolio_heirwallet.next_maturity_ts = Some(
+                            portfolio_heirwallet
+                                .next_maturity_ts
+                                .map_or(maturity, |ts| ts.min(maturity)),
+                        )
+                    }
+                    None => (),
+                }
+            }
+        }
+        Err(e) => portfolio_heirwallet.error = Some(CCStr::from(e)),
+    }
+    log::debug!("portfolio_heirwallet({name}) - loaded");
+    portfolio_heirwallet
+}
@@ -0,0 +1,151 @@
+use super::*;
+
+use btc_heritage_wallet::{
+    bitcoin::{bip32::Fingerprint, Network},
+    btc_heritage::bitcoincore_rpc::jsonrpc::serde_json,
+};
+
+use crate::onboarding::OnboardingContextItemId;
+
+/// Rename a database item, along with everything referencing it by its name
+///
+/// The references stored in the database (tags, labels, settings, notes, reserved addresses,
+/// caches) are moved in the same blocking operation as the rename and, if that fails, the item
+/// gets its old name back so nothing ends up pointing to a name that does not exist anymore.
+/// The onboarding context is updated once the rename succeeded.
+pub async fn rename_dbitem<DBI: DatabaseItem + Send + 'static>(
+    database_service: Coroutine<DatabaseCommand>,
+    mut item: DBI,
+    new_name: String,
+) -> Result<(), String> {
+    let old_name = item.name().to_owned();
+    log::debug!("rename_dbitem({old_name}, {new_name}) - start");
+    let (s_old_name, s_new_name) = (old_name.clone(), new_name.clone());
+    blocking_db_service_operation(database_service, "Rename an item", move |mut db| {
+        item.db_rename(&mut db, s_new_name.clone())
+            .map_err(|e| e.to_string())?;
+        if let Err(e) = rename_references::<DBI>(&mut db, &s_old_name, &s_new_name) {
+            if let Err(rollback_error) = item.db_rename(&mut db, s_old_name.clone()) {
+                log::error!("Could not restore the name {s_old_name}: {rollback_error}");
+            }
+            return Err(format!(
+                "Could not update the references to {s_old_name}: {e}"
+            ));
+        }
+        Ok(())
+    })
+    .await
+    .map_err(log_error)?;
+    rename_onboarding_context::<DBI>(&old_name, &new_name);
+    log::debug!("rename_dbitem({old_name}, {new_name}) - finished");
+    Ok(())
+}
+
+fn rename_references<DBI: DatabaseItem>(
+    db: &mut Database,
+    old_name: &str,
+    new_name: &str,
+) -> Result<(), DbError> {
+    rename_item_tags::<DBI>(db, old_name, new_name)?;
+    if DBI::item_key_prefix() == Wallet::item_key_prefix() {
+        rename_wallet_labels(db, old_name, new_name)?;
+        rename_wallet_settings(db, old_name, new_name)?;
+        rename_wallet_notes(db, old_name, new_name)?;
+        rename_backup_export(db, old_name, new_name)?;
+        rename_ledger_policies_cache(db, old_name, new_name)?;
+        rename_reserved_addresses(db, old_name, new_name)?;
+    }
+    if DBI::item_key_prefix() == Heir::item_key_prefix() {
+        rename_heir_service_link(db, old_name, new_name)?;
+    }
+    if DBI::item_key_prefix() == HeirWallet::item_key_prefix() {
+        rename_activation_workflow(db, old_name, new_name)?;
+    }
+    Ok(())
+}
+
+/// Delete the references to a database item that is being deleted
+///
+/// Called in the same blocking operation as the deletion, before it, so that nothing stays
+/// behind for a new item created later with the same name.
+pub(super) fn delete_references<DBI: DatabaseItem>(
+    db: &mut Database,
+    name: &str,
+) -> Result<(), DbError> {
+    delete_item_tags::<DBI>(db, name)?;
+    if DBI::item_key_prefix() == Wallet::item_key_prefix() {
+        delete_wallet_labels(db, name)?;
+        delete_wallet_settings(db, name)?;
+        delete_wallet_notes(db, name)?;
+        delete_backup_export(db, name)?;
+        delete_ledger_policies_cache(db, name)?;
+        delete_reserved_addresses(db, name)?;
+    }
+    if DBI::item_key_prefix() == Heir::item_key_prefix() {
+        delete_heir_service_link(db, name)?;
+    }
+    if DBI::item_key_prefix() == HeirWallet::item_key_prefix() {
+        delete_activation_workflow(db, name)?;
+    }
+    Ok(())
+}
+
+/// Names of the persisted caches of the data derived from the wallet resources
+pub const WALLET_TRANSACTION_HISTORY_CACHE: &str = "wallet_transaction_history";
+pub const WALLET_ADDRESSES_WITH_INFO_CACHE: &str = "wallet_addresses_with_info";
+pub const WALLET_HERITAGE_CONFIGS_CACHE: &str = "wallet_heritage_configs_with_info";
+const WALLET_RESOURCE_CACHES: [&str; 3] = [
+    WALLET_TRANSACTION_HISTORY_CACHE,
+    WALLET_ADDRESSES_WITH_INFO_CACHE,
+    WALLET_HERITAGE_CONFIGS_CACHE,
+];
+
+/// Key identifying the cached resources of a wallet
+///
+/// The fingerprint is part of it so that the cache of a wallet is never served
+/// for another wallet with the same name.
+pub fn wallet_resource_cache_key(
+    network: Network,
+    fingerprint: Fingerprint,
+    wallet_name: &str,
+) -> CCStr {
+    CCStr::from(format!("{network}/{fingerprint}/{wallet_name}"))
+}
+
+/// Database key of the value persisted by the resource cache `cache_name` for `cache_key`
+pub fn resource_cache_db_key(cache_name: &str, cache_key: &str) -> String {
+    format!("gui_resource_cache#{cache_name}#{cache_key}")
+}
+
+/// Evict the persisted resource caches of a deleted wallet
+pub(super) fn delete_wallet_resource_caches(
+    db: &mut Database,
+    cache_key: &str,
+) -> Result<(), DbError> {
+    for cache_name in WALLET_RESOURCE_CACHES {
+        db.delete_item::<serde_json::Value>(&resource_cache_db_key(cache_name, cache_key))?;
+    }
+    Ok(())
+}
+
+fn rename_onboarding_context<DBI: DatabaseItem>(old_name: &str, new_name: &str) {
+    let context_key = match DBI::item_key_prefix() {
+        "wallet#" => OnboardingContextItemId::WalletName,
+        "heir#" => OnboardingContextItemId::HeirName,
+        "heirwallet#" => OnboardingContextItemId::HeirWalletName,
+        _ => return,
+    };
+    let references_old_name = match *ONBOARDING_STATUS.peek() {
+        OnboardingStatus::InProgress(ref onboarding) => onboarding
+            .context()
+            .get(&context_key)
+            .is_some_and(|contents| contents.iter().any(|content| content == old_name)),
+        _ => false,
+    };
+    // Only write when needed, as it triggers the persistence of the onboarding
+    if references_old_name {
+        if let OnboardingStatus::InProgress(ref mut onboarding) = *ONBOARDING_STATUS.write() {
+            onboarding.rename_context(context_key, old_name, new_name);
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use super::*;
+
+use std::path::PathBuf;
+
+use btc_heritage_wallet::btc_heritage::utils::bitcoin_network;
+
+/// Temporary database, isolated from the data of the application, in which a
+/// recovery is rehearsed before being deleted with [remove_rehearsal_profile]
+pub struct RehearsalProfile {
+    pub database: Database,
+    datadir: PathBuf,
+}
+impl RehearsalProfile {
+    pub fn datadir(&self) -> &std::path::Path {
+        &self.datadir
+    }
+}
+
+/// Create an empty [RehearsalProfile] in the temporary directory of the system
+///
+/// The profile uses the network of the application: the descriptors of a backup
+/// are bound to their network and could not be restored on another one.
+pub async fn create_rehearsal_profile() -> Result<RehearsalProfile, String> {
+    log::debug!("create_rehearsal_profile - start");
+    let datadir = std::env::temp_dir().join(format!(
+        "heritage-wallet-rehearsal-{}",
+        uuid::Uuid::new_v4().simple()
+    ));
+    let network = bitcoin_network::get();
+    let database = {
+        let datadir = datadir.clone();
+        tokio::task::spawn_blocking(move || Database::new(&datadir, network))
+            .await
+            .unwrap()
+            .map_err(|e| format!("Could not create the rehearsal database: {e}"))?
+    };
+    log::debug!("create_rehearsal_profile - finished");
+    Ok(RehearsalProfile { database, datadir })
+}
+
+/// Delete a [RehearsalProfile] and everything restored in it
+pub async fn remove_rehearsal_profile(profile: RehearsalProfile) -> Result<(), String> {
+    log::debug!("remove_rehearsal_profile - start");
+    let RehearsalProfile { database, datadir } = profile;
+    // The database must be closed before its files can be removed
+    drop(database);
+    tokio::task::spawn_blocking(move || std::fs::remove_dir_all(datadir))
+        .await
+        .unwrap()
+        .map_err(|e| format!("Could not remove the rehearsal data directory: {e}"))?;
+    log::debug!("remove_rehearsal_profile - finished");
+    Ok(())
+}
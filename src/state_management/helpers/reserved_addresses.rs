@@ -0,0 +1,72 @@
+use super::*;
+
+use serde::{Deserialize, Serialize};
+
+/// A receive address of a wallet reserved for a recurring purpose (a salary, a DCA bot...)
+///
+/// Reserved addresses are never handed out by the "Receive" button.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReservedAddress {
+    pub address: String,
+    pub purpose: String,
+    pub reserved_at: u64,
+}
+
+fn reserved_addresses_key(wallet_name: &str) -> String {
+    format!("gui_reserved_addresses#{wallet_name}")
+}
+
+pub async fn load_reserved_addresses(
+    database_service: Coroutine<DatabaseCommand>,
+    wallet_name: CCStr,
+) -> Result<Vec<ReservedAddress>, String> {
+    log::debug!("load_reserved_addresses({wallet_name}) - start");
+    let key = reserved_addresses_key(&wallet_name);
+    let reserved_addresses =
+        blocking_db_service_operation(database_service, "Load the reserved addresses", move |db| {
+            db.get_item::<Vec<ReservedAddress>>(&key)
+        })
+        .await
+        .map(Option::unwrap_or_default)
+        .map_err(log_error);
+    log::debug!("load_reserved_addresses({wallet_name}) - loaded");
+    reserved_addresses
+}
+
+pub async fn save_reserved_addresses(
+    database_service: Coroutine<DatabaseCommand>,
+    wallet_name: CCStr,
+    reserved_addresses: Vec<ReservedAddress>,
+) -> Result<(), String> {
+    log::debug!("save_reserved_addresses({wallet_name}) - start");
+    let key = reserved_addresses_key(&wallet_name);
+    blocking_db_service_operation(
+        database_service,
+        "Save the reserved addresses",
+        move |mut db| db.update_item(&key, &reserved_addresses),
+    )
+    .await
+    .map_err(log_error)?;
+    log::debug!("save_reserved_addresses({wallet_name}) - finished");
+    Ok(())
+}
+
+/// Move the reserved addresses of a renamed wallet to its new name
+pub(super) fn rename_reserved_addresses(
+    db: &mut Database,
+    old_name: &str,
+    new_name: &str,
+) -> Result<(), DbError> {
+    let old_key = reserved_addresses_key(old_name);
+    if let Some(reserved_addresses) = db.get_item::<Vec<ReservedAddress>>(&old_key)? {
+        db.update_item(&reserved_addresses_key(new_name), &reserved_addresses)?;
+        db.delete_item::<Vec<ReservedAddress>>(&old_key)?;
+    }
+    Ok(())
+}
+
+/// Delete the reserved addresses of a deleted wallet
+pub(super) fn delete_reserved_addresses(db: &mut Database, name: &str) -> Result<(), DbError> {
+    db.delete_item::<Vec<ReservedAddress>>(&reserved_addresses_key(name))?;
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use super::*;
+
+use btc_heritage_wallet::bitcoin::bip32::Fingerprint;
+
+use crate::prelude::alert_warn;
+
+/// Role a seed plays in a database item
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeedRole {
+    /// The seed of a [Wallet], owning the coins
+    Owner,
+    /// The seed of an [Heir], inheriting the coins of the owner
+    Heir,
+    /// The seed of an [HeirWallet], spending the inherited coins
+    HeirWallet,
+}
+impl SeedRole {
+    fn item_kind(self) -> &'static str {
+        match self {
+            SeedRole::Owner => "wallet",
+            SeedRole::Heir => "heir",
+            SeedRole::HeirWallet => "heir wallet",
+        }
+    }
+    /// Whether the same seed playing both roles defeats the inheritance
+    ///
+    /// An heir and an heir wallet sharing a seed is the expected setup of the
+    /// person inheriting, but the owner must never be its own heir.
+    fn conflicts_with(self, other: SeedRole) -> bool {
+        self != other && (self == SeedRole::Owner || other == SeedRole::Owner)
+    }
+}
+
+/// Database items whose seed has the given fingerprint but a role conflicting with the given one
+pub async fn seed_role_conflicts(
+    database_service: Coroutine<DatabaseCommand>,
+    fingerprint: Fingerprint,
+    role: SeedRole,
+) -> Vec<(SeedRole, CCStr)> {
+    log::debug!("seed_role_conflicts({fingerprint}, {role:?}) - start");
+    let mut conflicts = vec![];
+    if role.conflicts_with(SeedRole::Owner) {
+        conflicts.extend(
+            list_wallets(database_service)
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|wallet| wallet.fingerprint().ok() == Some(fingerprint))
+                .map(|wallet| (SeedRole::Owner, CCStr::from(wallet.name()))),
+        );
+    }
+    if role.conflicts_with(SeedRole::Heir) {
+        conflicts.extend(
+            list_heirs(database_service)
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|heir| heir.heir_config.fingerprint() == fingerprint)
+                .map(|heir| (SeedRole::Heir, CCStr::from(heir.name()))),
+        );
+    }
+    if role.conflicts_with(SeedRole::HeirWallet) {
+        conflicts.extend(
+            list_heirwallets(database_service)
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|heirwallet| heirwallet.fingerprint().ok() == Some(fingerprint))
+                .map(|heirwallet| (SeedRole::HeirWallet, CCStr::from(heirwallet.name()))),
+        );
+    }
+    log::debug!("seed_role_conflicts({fingerprint}, {role:?}) - finished");
+    conflicts
+}
+
+/// Warn the user if the seed of a newly created database item already plays
+/// a conflicting role in other database items
+pub async fn warn_seed_role_conflicts(
+    database_service: Coroutine<DatabaseCommand>,
+    fingerprint: Fingerprint,
+    role: SeedRole,
+) {
+    let conflicts = seed_role_conflicts(database_service, fingerprint, role).await;
+    if conflicts.is_empty() {
+        return;
+    }
+    let items = conflicts
+        .iter()
+        .map(|(other_role, name)| format!("{} '{name}'", other_role.item_kind()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let message = format!(
+        "The seed {fingerprint} of this new {} is also used by: {items}. \
+        The owner of the coins and its heirs must have distinct seeds: \
+        with the same seed, whoever can spend as the owner can also inherit and vice versa, \
+        which defeats the Heritage Configuration.",
+        role.item_kind(),
+    );
+    log::warn!("{message}");
+    alert_warn(message);
+}
@@ -1,6 +1,9 @@
 use crate::prelude::*;
 
-use btc_heritage_wallet::heritage_service_api_client::Fingerprint;
+use std::{any::Any, cell::RefCell, collections::HashMap, future::Future};
+
+use btc_heritage_wallet::heritage_service_api_client::{Error as ServiceError, Fingerprint};
+use futures_util::future::{FutureExt, LocalBoxFuture, Shared, WeakShared};
 
 use super::*;
 
@@ -56,6 +59,88 @@ pub async fn heritage_service_client(
     result
 }
 
+/// Maximum number of calls to the Heritage Service running at the same time
+const MAX_CONCURRENT_SERVICE_CALLS: usize = 4;
+static SERVICE_CALL_SLOTS: tokio::sync::Semaphore =
+    tokio::sync::Semaphore::const_new(MAX_CONCURRENT_SERVICE_CALLS);
+
+type ServiceCall<T> = Shared<LocalBoxFuture<'static, Result<T, CCStr>>>;
+thread_local! {
+    /// The deduplicated calls currently running, indexed by request key.
+    /// Values are [WeakShared] of [ServiceCall], typed by the caller.
+    static IN_FLIGHT_SERVICE_CALLS: RefCell<HashMap<&'static str, Box<dyn Any>>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Call the Heritage Service through the request scheduler
+///
+/// At most [MAX_CONCURRENT_SERVICE_CALLS] calls run at the same time, the others
+/// wait for a slot. When `request_key` is provided and a call with the same key is
+/// already running (e.g. several views listing the heirs at the same time), its
+/// result is shared instead of sending a new request. Only idempotent calls must
+/// be given a `request_key`.
+///
+/// # Examples
+///
+/// ```
+/// let heirs = state_management::scheduled_service_call(
+///     service_client_service,
+///     Some("list_heirs"),
+///     |client| async move { client.list_heirs().await },
+/// )
+/// .await;
+/// ```
+pub async fn scheduled_service_call<T, F, Fut>(
+    service_client_service: Coroutine<ServiceClientCommand>,
+    request_key: Option<&'static str>,
+    call: F,
+) -> Result<T, CCStr>
+where
+    T: Clone + 'static,
+    F: FnOnce(HeritageServiceClient) -> Fut + 'static,
+    Fut: Future<Output = Result<T, ServiceError>> + 'static,
+{
+    let run = async move {
+        let _slot = SERVICE_CALL_SLOTS
+            .acquire()
+            .await
+            .expect("the semaphore is never closed");
+        let client = heritage_service_client(service_client_service).await;
+        call(client).await.map_err(|e| CCStr::from(e.to_string()))
+    };
+    let Some(request_key) = request_key else {
+        return run.await;
+    };
+
+    let in_flight = IN_FLIGHT_SERVICE_CALLS.with_borrow(|calls| {
+        calls
+            .get(request_key)
+            .and_then(|call| {
+                call.downcast_ref::<WeakShared<LocalBoxFuture<'static, Result<T, CCStr>>>>()
+            })
+            .and_then(WeakShared::upgrade)
+    });
+    let (request, owned): (ServiceCall<T>, bool) = match in_flight {
+        Some(request) => {
+            log::debug!("scheduled_service_call({request_key}) - joining the call in flight");
+            (request, false)
+        }
+        None => {
+            let request = run.boxed_local().shared();
+            if let Some(weak) = request.downgrade() {
+                IN_FLIGHT_SERVICE_CALLS
+                    .with_borrow_mut(|calls| calls.insert(request_key, Box::new(weak)));
+            }
+            (request, true)
+        }
+    };
+    let result = request.await;
+    if owned {
+        IN_FLIGHT_SERVICE_CALLS.with_borrow_mut(|calls| calls.remove(request_key));
+    }
+    result
+}
+
 pub fn refresh_service_status(service_client_service: Coroutine<ServiceClientCommand>) {
     log::debug!("refresh_service_status - start");
     service_client_service.send(ServiceClientCommand::RefreshStatus);
@@ -76,11 +161,9 @@ pub fn inject_serviceable_wallet(
 }
 
 pub fn use_service_key() -> Memo<CCStr> {
-    use_memo(move || match *state_management::SERVICE_STATUS.read() {
-        Some(ServiceStatus::Connected(ref css)) => {
-            CCStr::from(css.user_id.preferred_username.as_ref())
-        }
-        Some(ServiceStatus::Disconnected) => CCStr::from("disconnected"),
+    use_memo(move || match *state_management::SERVICE_AUTH_STATE.read() {
+        Some(ServiceAuthState::Connected { ref username, .. }) => username.clone(),
+        Some(ServiceAuthState::Disconnected) => CCStr::from("disconnected"),
         None => CCStr::from("none"),
     })
 }
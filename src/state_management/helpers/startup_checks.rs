@@ -0,0 +1,483 @@
+use std::{
+    net::{ToSocketAddrs, UdpSocket},
+    path::Path,
+    time::Duration,
+};
+
+use tokio::time::{timeout_at, Instant};
+
+use super::*;
+
+/// Maximum time all the startup checks are allowed to take together
+const STARTUP_CHECKS_TIMEOUT: Duration = Duration::from_secs(15);
+/// Maximum time a single measure of the clock drift is allowed to take
+const MEASURE_TIMEOUT: Duration = Duration::from_secs(10);
+/// Below this amount of free space in the data directory, warn the user
+const LOW_DISK_SPACE_MB: u64 = 200;
+/// Above this difference with a reference clock, warn the user
+const MAX_CLOCK_SKEW_SECS: i64 = 5 * 60;
+/// Consensus rules allow the timestamp of a block to be 2 hours ahead of the network time
+const MAX_BLOCK_TIME_AHEAD_SECS: i64 = 2 * 60 * 60;
+/// Time servers queried to measure the drift of the local clock
+const NTP_SERVERS: [&str; 4] = [
+    "0.pool.ntp.org",
+    "1.pool.ntp.org",
+    "2.pool.ntp.org",
+    "time.cloudflare.com",
+];
+/// Seconds between the NTP epoch (1900) and the Unix epoch (1970)
+const NTP_UNIX_EPOCH_DELTA: i64 = 2_208_988_800;
+
+/// Severity of the outcome of a startup check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StartupCheckSeverity {
+    Ok,
+    Info,
+    Warn,
+    Error,
+}
+
+/// Result of a single startup check, with a human readable explanation
+#[derive(Debug, Clone, PartialEq)]
+pub struct StartupCheckOutcome {
+    pub severity: StartupCheckSeverity,
+    pub message: CCStr,
+}
+impl StartupCheckOutcome {
+    fn new(severity: StartupCheckSeverity, message: impl Into<CCStr>) -> Self {
+        Self {
+            severity,
+            message: message.into(),
+        }
+    }
+}
+
+/// Instant by which all the startup checks must have completed
+///
+/// Every check gets the same deadline, so that together they never hold
+/// the splash screen longer than [STARTUP_CHECKS_TIMEOUT].
+pub fn startup_checks_deadline() -> Instant {
+    Instant::now() + STARTUP_CHECKS_TIMEOUT
+}
+
+/// Verify the database is opened and readable
+pub async fn check_database(
+    database_service: Coroutine<DatabaseCommand>,
+    deadline: Instant,
+) -> StartupCheckOutcome {
+    log::debug!("check_database - start");
+    let outcome = match timeout_at(deadline, list_wallet_names(database_service)).await {
+        Ok(Ok(_)) => StartupCheckOutcome::new(
+            StartupCheckSeverity::Ok,
+            format!(
+                "Database opened in {}",
+                APPLICATION_CONFIG.peek().datadir.display()
+            ),
+        ),
+        Ok(Err(e)) => StartupCheckOutcome::new(
+            StartupCheckSeverity::Error,
+            format!("The database cannot be read: {e}"),
+        ),
+        Err(_) => StartupCheckOutcome::new(
+            StartupCheckSeverity::Error,
+            "The database did not respond in time",
+        ),
+    };
+    log::debug!("check_database - finished");
+    outcome
+}
+
+/// Verify the data directory is writable and has enough free space
+pub async fn check_disk_space(deadline: Instant) -> StartupCheckOutcome {
+    log::debug!("check_disk_space - start");
+    let datadir = APPLICATION_CONFIG.peek().datadir.clone();
+    let check = tokio::task::spawn_blocking(move || {
+        let probe = datadir.join(".heritage-gui-write-probe");
+        if let Err(e) = std::fs::write(&probe, b"probe").and_then(|_| std::fs::remove_file(&probe))
+        {
+            return StartupCheckOutcome::new(
+                StartupCheckSeverity::Error,
+                format!("The data directory is not writable: {e}"),
+            );
+        }
+        match available_space_mb(&datadir) {
+            Some(mb) if mb < LOW_DISK_SPACE_MB => StartupCheckOutcome::new(
+                StartupCheckSeverity::Warn,
+                format!("Only {mb} MB left on the disk of the data directory"),
+            ),
+            Some(mb) => StartupCheckOutcome::new(
+                StartupCheckSeverity::Ok,
+                format!("{mb} MB available for the data directory"),
+            ),
+            None => StartupCheckOutcome::new(
+                StartupCheckSeverity::Info,
+                "The data directory is writable (free space could not be determined)",
+            ),
+        }
+    });
+    let outcome = match timeout_at(deadline, check).await {
+        Ok(outcome) => outcome.unwrap(),
+        Err(_) => StartupCheckOutcome::new(
+            StartupCheckSeverity::Warn,
+            "The data directory could not be checked in time",
+        ),
+    };
+    log::debug!("check_disk_space - finished");
+    outcome
+}
+
+#[cfg(unix)]
+fn available_space_mb(dir: &Path) -> Option<u64> {
+    // POSIX output: Filesystem 1024-blocks Used Available Capacity Mounted-on
+    let output = std::process::Command::new("df")
+        .arg("-Pk")
+        .arg(dir)
+        .output()
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .nth(1)?
+        .split_whitespace()
+        .nth(3)?
+        .parse::<u64>()
+        .ok()
+        .map(|kb| kb / 1024)
+}
+#[cfg(not(unix))]
+fn available_space_mb(_dir: &Path) -> Option<u64> {
+    None
+}
+
+/// Verify the Heritage Service answers, only if the user connected to it
+///
+/// The status of the service client is refreshed at startup with authenticated requests
+/// to the service, so its outcome tells if the service can be reached: the check does not
+/// contact the service itself.
+pub async fn check_service_reachable(deadline: Instant) -> StartupCheckOutcome {
+    log::debug!("check_service_reachable - start");
+    let status = timeout_at(deadline, async {
+        loop {
+            // The service wallets cannot be served until the status is known
+            if SERVICE_STATUS.peek().is_some() {
+                if let Some(ref auth_state) = *SERVICE_AUTH_STATE.peek() {
+                    return auth_state.is_connected();
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(250)).await;
+        }
+    })
+    .await;
+    let outcome = match status {
+        Ok(true) => StartupCheckOutcome::new(
+            StartupCheckSeverity::Ok,
+            "The Heritage Service is reachable",
+        ),
+        Ok(false) => StartupCheckOutcome::new(
+            StartupCheckSeverity::Info,
+            "Not connected to the Heritage Service (only needed for the wallets and heirs it serves)",
+        ),
+        Err(_) => StartupCheckOutcome::new(
+            StartupCheckSeverity::Warn,
+            "The Heritage Service did not respond in time",
+        ),
+    };
+    log::debug!("check_service_reachable - finished");
+    outcome
+}
+
+/// Verify the blockchain provider (own node) answers
+pub async fn check_blockchain_provider(deadline: Instant) -> StartupCheckOutcome {
+    log::debug!("check_blockchain_provider - start");
+    // The status is computed by the blockchain provider service when it starts
+    let status = timeout_at(deadline, async {
+        loop {
+            if let Some(status) = *BLOCKCHAIN_PROVIDER_STATUS.peek() {
+                return status;
+            }
+            tokio::time::sleep(Duration::from_millis(250)).await;
+        }
+    })
+    .await;
+    let outcome = match status {
+        Ok(BlockchainProviderStatus::Connected(height)) => StartupCheckOutcome::new(
+            StartupCheckSeverity::Ok,
+            format!("The blockchain provider is reachable (block height {height})"),
+        ),
+        Ok(BlockchainProviderStatus::Disconnected) => StartupCheckOutcome::new(
+            StartupCheckSeverity::Info,
+            "The blockchain provider is not reachable (only needed for wallets using your own node)",
+        ),
+        Err(_) => StartupCheckOutcome::new(
+            StartupCheckSeverity::Warn,
+            "The blockchain provider did not respond in time",
+        ),
+    };
+    log::debug!("check_blockchain_provider - finished");
+    outcome
+}
+
+/// Verify a Ledger device can be reached through the USB transport
+pub async fn check_ledger(deadline: Instant) -> StartupCheckOutcome {
+    log::debug!("check_ledger - start");
+    let outcome = match timeout_at(deadline, LedgerStatus::current()).await {
+        Err(_) => StartupCheckOutcome::new(
+            StartupCheckSeverity::Info,
+            "The Ledger device did not respond in time",
+        ),
+        Ok(LedgerStatus::Ready(fingerprint)) => StartupCheckOutcome::new(
+            StartupCheckSeverity::Ok,
+            format!("Ledger device {fingerprint} is ready"),
+        ),
+        Ok(LedgerStatus::WrongNetwork) => StartupCheckOutcome::new(
+            StartupCheckSeverity::Warn,
+            "The Ledger Bitcoin application is opened on the wrong network",
+        ),
+        Ok(LedgerStatus::WrongApp) => StartupCheckOutcome::new(
+            StartupCheckSeverity::Warn,
+            "The Ledger is not opened on the Bitcoin application",
+        ),
+        Ok(LedgerStatus::NotReady) => StartupCheckOutcome::new(
+            StartupCheckSeverity::Info,
+            "No Ledger device detected (only needed if you use one)",
+        ),
+    };
+    log::debug!("check_ledger - finished");
+    outcome
+}
+
+/// Reference clock the local clock was compared with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockReference {
+    Ntp,
+    HeritageService,
+    /// Only tells that the local clock is late: a block can be timestamped up to
+    /// 2 hours in the future, and is usually mined minutes after its timestamp
+    BlockchainTip,
+}
+impl ClockReference {
+    pub fn name(self) -> &'static str {
+        match self {
+            ClockReference::Ntp => "the NTP time servers",
+            ClockReference::HeritageService => "the Heritage Service",
+            ClockReference::BlockchainTip => "the last block of your node",
+        }
+    }
+}
+
+/// Difference between the local clock and a reference clock
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockDrift {
+    /// Positive if the local clock is ahead of the reference
+    pub drift_secs: i64,
+    pub reference: ClockReference,
+}
+impl ClockDrift {
+    /// Whether the drift is large enough to make time-locks misleading
+    pub fn is_excessive(&self) -> bool {
+        self.drift_secs.abs() > MAX_CLOCK_SKEW_SECS
+    }
+
+    pub fn describe(&self) -> String {
+        let minutes = self.drift_secs.abs() / 60;
+        let direction = if self.drift_secs > 0 {
+            "ahead of"
+        } else {
+            "behind"
+        };
+        match self.reference {
+            ClockReference::BlockchainTip => format!(
+                "Your system clock is at least {minutes} minutes {direction} {}",
+                self.reference.name()
+            ),
+            _ => format!(
+                "Your system clock is {minutes} minutes {direction} {}",
+                self.reference.name()
+            ),
+        }
+    }
+}
+
+/// Measure the drift of the local clock, as the maturity of the Heritages depends on it
+///
+/// The NTP time servers are the preferred reference, then the `Date` header of the
+/// Heritage Service if the user is connected to it. If none can be reached, the time of
+/// the best block of a Bitcoin Core node still reveals a clock that is too late.
+///
+/// The NTP time servers are queried together and the median of their answers is kept,
+/// so that neither an unreachable server nor a wrong one skews the measure.
+pub async fn measure_clock_drift(
+    service_client_service: Coroutine<ServiceClientCommand>,
+    blockchain_provider_service: Coroutine<BlockchainProviderCommand>,
+) -> Option<ClockDrift> {
+    log::debug!("measure_clock_drift - start");
+    let ntp_drift = median_ntp_drift().await.map(|drift_secs| ClockDrift {
+        drift_secs,
+        reference: ClockReference::Ntp,
+    });
+    let remote_ts = match ntp_drift {
+        Some(_) => None,
+        None if SERVICE_AUTH_STATE
+            .peek()
+            .as_ref()
+            .is_some_and(ServiceAuthState::is_connected) =>
+        {
+            let service_api_url = get_service_config(service_client_service)
+                .await
+                .service_api_url;
+            http_date(&service_api_url)
+                .await
+                .map(|ts| (ts, ClockReference::HeritageService))
+        }
+        None => None,
+    };
+    let drift = match (ntp_drift, remote_ts) {
+        (Some(ntp_drift), _) => Some(ntp_drift),
+        (None, Some((remote_ts, reference))) => Some(ClockDrift {
+            drift_secs: chrono::Utc::now().timestamp() - remote_ts,
+            reference,
+        }),
+        (None, None) => tokio::time::timeout(
+            MEASURE_TIMEOUT,
+            bitcoin_core_tip_time(blockchain_provider_service),
+        )
+        .await
+        .ok()
+        .flatten()
+        .and_then(|tip_time| {
+            let lower_bound = tip_time - MAX_BLOCK_TIME_AHEAD_SECS;
+            let now = chrono::Utc::now().timestamp();
+            (now < lower_bound).then(|| ClockDrift {
+                drift_secs: now - lower_bound,
+                reference: ClockReference::BlockchainTip,
+            })
+        }),
+    };
+    log::debug!("measure_clock_drift - finished");
+    drift
+}
+
+/// Compare the local clock with reference clocks, as time-locks make inheritances depend on it
+///
+/// It reports the first measure of the clock check service instead of measuring again.
+pub async fn check_clock_skew(deadline: Instant) -> StartupCheckOutcome {
+    log::debug!("check_clock_skew - start");
+    let measured = timeout_at(deadline, async {
+        while !CLOCK_DRIFT_MEASURED() {
+            tokio::time::sleep(Duration::from_millis(250)).await;
+        }
+    })
+    .await;
+    let outcome = match (measured, CLOCK_DRIFT()) {
+        (Err(_), _) => StartupCheckOutcome::new(
+            StartupCheckSeverity::Info,
+            "Your system clock could not be verified in time",
+        ),
+        (Ok(()), Some(drift)) if drift.is_excessive() => StartupCheckOutcome::new(
+            StartupCheckSeverity::Warn,
+            format!("{}, synchronize it", drift.describe()),
+        ),
+        (Ok(()), Some(_)) => {
+            StartupCheckOutcome::new(StartupCheckSeverity::Ok, "Your system clock is accurate")
+        }
+        (Ok(()), None) => StartupCheckOutcome::new(
+            StartupCheckSeverity::Info,
+            "Your system clock could not be verified",
+        ),
+    };
+    log::debug!("check_clock_skew - finished");
+    outcome
+}
+
+/// Retrieve the timestamp of the `Date` header returned by the Heritage Service to a HEAD request
+async fn http_date(service_api_url: &str) -> Option<i64> {
+    let client = reqwest::Client::builder()
+        .timeout(MEASURE_TIMEOUT)
+        .build()
+        .map_err(log_error)
+        .ok()?;
+    let response = client.head(service_api_url).send().await.ok()?;
+    let date = response
+        .headers()
+        .get(reqwest::header::DATE)?
+        .to_str()
+        .ok()?;
+    chrono::DateTime::parse_from_rfc2822(date)
+        .ok()
+        .map(|date| date.timestamp())
+}
+
+/// Median of the drifts of the local clock measured against each of the [NTP_SERVERS]
+/// that answered, `None` if none did
+async fn median_ntp_drift() -> Option<i64> {
+    let answers = futures_util::future::join_all(NTP_SERVERS.map(|server| {
+        tokio::task::spawn_blocking(move || {
+            sntp_time(server).map(|ntp_ts| chrono::Utc::now().timestamp() - ntp_ts)
+        })
+    }))
+    .await;
+    let mut drifts = answers
+        .into_iter()
+        .filter_map(|answer| answer.ok().flatten())
+        .collect::<Vec<_>>();
+    log::debug!(
+        "median_ntp_drift - {} of {} time servers answered",
+        drifts.len(),
+        NTP_SERVERS.len()
+    );
+    if drifts.is_empty() {
+        return None;
+    }
+    drifts.sort_unstable();
+    let middle = drifts.len() / 2;
+    Some(if drifts.len() % 2 == 0 {
+        (drifts[middle - 1] + drifts[middle]) / 2
+    } else {
+        drifts[middle]
+    })
+}
+
+/// Retrieve the time of an NTP server with a single SNTP request
+///
+/// The reply is only trusted if it is a server reply (mode 4) from a synchronized server
+/// (stratum 1 to 15, no alarm leap indicator) that echoes the transmit timestamp of the request.
+fn sntp_time(server: &str) -> Option<i64> {
+    let addr = (server, 123).to_socket_addrs().ok()?.next()?;
+    let socket = UdpSocket::bind(if addr.is_ipv4() {
+        "0.0.0.0:0"
+    } else {
+        "[::]:0"
+    })
+    .ok()?;
+    socket.set_read_timeout(Some(Duration::from_secs(5))).ok()?;
+    socket.connect(addr).ok()?;
+    let mut request = [0u8; 48];
+    // Leap indicator 0, version 3, mode 3 (client)
+    request[0] = 0x1b;
+    // The transmit timestamp identifies the request, the server echoes it as origin timestamp
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()?;
+    let transmit_secs = (now.as_secs() as i64 + NTP_UNIX_EPOCH_DELTA) as u32;
+    request[40..44].copy_from_slice(&transmit_secs.to_be_bytes());
+    request[44..48].copy_from_slice(&now.subsec_nanos().to_be_bytes());
+    socket.send(&request).ok()?;
+    let mut reply = [0u8; 48];
+    let len = socket.recv(&mut reply).ok()?;
+    if len < reply.len() {
+        return None;
+    }
+    let leap_indicator = reply[0] >> 6;
+    let mode = reply[0] & 0x07;
+    let stratum = reply[1];
+    if mode != 4 || leap_indicator == 3 || !(1..=15).contains(&stratum) {
+        log::warn!("sntp_time: invalid reply from {server} (mode {mode}, stratum {stratum})");
+        return None;
+    }
+    if reply[24..32] != request[40..48] {
+        log::warn!("sntp_time: the reply from {server} does not answer the request");
+        return None;
+    }
+    // Seconds of the transmit timestamp
+    let ntp_secs = u32::from_be_bytes(reply[40..44].try_into().ok()?);
+    (ntp_secs != 0).then(|| ntp_secs as i64 - NTP_UNIX_EPOCH_DELTA)
+}
@@ -0,0 +1,78 @@
+use super::*;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+const ITEM_TAGS_KEY: &str = "gui_item_tags";
+
+/// The tags of the database items, indexed by the database key of the item
+/// (e.g. `wallet#<name>`) so wallets and heir wallets can share the same names
+pub type ItemTags = BTreeMap<String, BTreeSet<String>>;
+
+pub fn item_tags_key<DBI: DatabaseItem>(name: &str) -> String {
+    format!("{}{name}", DBI::item_key_prefix())
+}
+
+pub async fn load_item_tags(
+    database_service: Coroutine<DatabaseCommand>,
+) -> Result<ItemTags, String> {
+    log::debug!("load_item_tags - start");
+    let item_tags = blocking_db_service_operation(database_service, "Load the tags", |db| {
+        db.get_item::<ItemTags>(ITEM_TAGS_KEY)
+    })
+    .await
+    .map(Option::unwrap_or_default)
+    .map_err(log_error);
+    log::debug!("load_item_tags - loaded");
+    item_tags
+}
+
+/// Update the tags of the database item `name`, in place of the ones it had
+pub async fn save_item_tags<DBI: DatabaseItem>(
+    database_service: Coroutine<DatabaseCommand>,
+    name: CCStr,
+    tags: BTreeSet<String>,
+) -> Result<(), String> {
+    log::debug!("save_item_tags({name}) - start");
+    let key = item_tags_key::<DBI>(&name);
+    blocking_db_service_operation(database_service, "Save the tags", move |mut db| {
+        let mut item_tags = db.get_item::<ItemTags>(ITEM_TAGS_KEY)?.unwrap_or_default();
+        if tags.is_empty() {
+            item_tags.remove(&key);
+        } else {
+            item_tags.insert(key, tags);
+        }
+        db.update_item(ITEM_TAGS_KEY, &item_tags)
+    })
+    .await
+    .map_err(log_error)?;
+    log::debug!("save_item_tags({name}) - finished");
+    Ok(())
+}
+
+/// Move the tags of a renamed database item to its new name
+pub(super) fn rename_item_tags<DBI: DatabaseItem>(
+    db: &mut Database,
+    old_name: &str,
+    new_name: &str,
+) -> Result<(), DbError> {
+    let mut item_tags = db.get_item::<ItemTags>(ITEM_TAGS_KEY)?.unwrap_or_default();
+    match item_tags.remove(&item_tags_key::<DBI>(old_name)) {
+        Some(tags) => {
+            item_tags.insert(item_tags_key::<DBI>(new_name), tags);
+            db.update_item(ITEM_TAGS_KEY, &item_tags)
+        }
+        None => Ok(()),
+    }
+}
+
+/// Delete the tags of a deleted database item
+pub(super) fn delete_item_tags<DBI: DatabaseItem>(
+    db: &mut Database,
+    name: &str,
+) -> Result<(), DbError> {
+    let mut item_tags = db.get_item::<ItemTags>(ITEM_TAGS_KEY)?.unwrap_or_default();
+    match item_tags.remove(&item_tags_key::<DBI>(name)) {
+        Some(_) => db.update_item(ITEM_TAGS_KEY, &item_tags),
+        None => Ok(()),
+    }
+}
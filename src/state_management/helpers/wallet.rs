@@ -1,4 +1,9 @@
-use std::sync::Arc;
+use std::{collections::BTreeSet, sync::Arc};
+
+use btc_heritage_wallet::{
+    bitcoin::bip32::Fingerprint, heritage_service_api_client::AccountXPubWithStatus,
+    AnyKeyProvider, BoundFingerprint, KeyProvider, OnlineWallet,
+};
 
 use crate::prelude::alert_warn;
 
@@ -46,13 +51,11 @@ pub async fn init_wallet(
     let wallet = match wallet.retry_fingerprints_control().await {
         Ok(true) => {
             // If it returned true, then an update was made, need to save.
-            get_database(database_service)
-                .await
-                .blocking_operation(move |mut db| {
-                    wallet.save(&mut db).map_err(log_error)?;
-                    Ok::<_, String>(wallet)
-                })
-                .await?
+            blocking_db_service_operation(database_service, "Save the wallet", move |mut db| {
+                wallet.save(&mut db).map_err(log_error)?;
+                Ok::<_, String>(wallet)
+            })
+            .await?
         }
         Ok(false) => wallet,
         Err(e) => {
@@ -74,11 +77,11 @@ pub async fn get_wallet(
 
     let wallet = {
         let name = name.to_string();
-        get_database(database_service)
-            .await
-            .blocking_operation(move |db| Wallet::load(&db, &name))
-            .await
-            .map_err(log_error)?
+        blocking_db_service_operation(database_service, "Load the wallet", move |db| {
+            Wallet::load(&db, &name)
+        })
+        .await
+        .map_err(log_error)?
     };
 
     log::debug!("get_wallet({name}) - loaded");
@@ -97,6 +100,20 @@ pub async fn delete_wallet(
     wallet: &Wallet,
 ) -> Result<(), String> {
     log::debug!("delete_wallet({wallet:?}) - start");
+    if let Ok(fingerprint) = wallet.fingerprint() {
+        let cache_key = wallet_resource_cache_key(
+            APPLICATION_CONFIG.peek().network,
+            fingerprint,
+            wallet.name(),
+        );
+        blocking_db_service_operation(
+            database_service,
+            "Delete the wallet caches",
+            move |mut db| super::references::delete_wallet_resource_caches(&mut db, &cache_key),
+        )
+        .await
+        .map_err(log_error)?;
+    }
     super::database::delete_dbitem(database_service, wallet).await?;
     log::debug!("delete_wallet({wallet:?}) - finished");
     Ok(())
@@ -107,28 +124,230 @@ pub async fn create_wallet(
 ) -> Result<(), String> {
     log::debug!("create_wallet({wallet:?}) - start");
 
-    get_database(database_service)
-        .await
-        .blocking_operation(move |mut db| wallet.create(&mut db))
-        .await
-        .map_err(log_error)?;
+    blocking_db_service_operation(database_service, "Create the wallet", move |mut db| {
+        wallet.create(&mut db)
+    })
+    .await
+    .map_err(log_error)?;
 
     log::debug!("create_wallet - finished");
     Ok(())
 }
 
+/// Key of the wallet whose creation did not complete
+const PENDING_WALLET_CREATION_KEY: &str = "gui_pending_wallet_creation";
+
+/// A wallet whose creation did not complete, with what is needed to present it
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingWalletCreation {
+    pub wallet_name: CCStr,
+    /// The wallet was created on the Heritage Service
+    pub service_wallet_id: Option<CCStr>,
+}
+
+/// Keep a wallet before saving it, so its creation can be resumed if the save fails
+///
+/// The wallet may already exist outside of the database, like on the Heritage Service,
+/// and would be orphaned if it was lost.
+pub async fn save_pending_wallet_creation(
+    database_service: Coroutine<DatabaseCommand>,
+    wallet: Wallet,
+) -> Result<Wallet, String> {
+    log::debug!("save_pending_wallet_creation({}) - start", wallet.name());
+    let wallet = blocking_db_service_operation(
+        database_service,
+        "Save the pending wallet creation",
+        move |mut db| {
+            db.update_item(PENDING_WALLET_CREATION_KEY, &wallet)
+                .map(|_| wallet)
+        },
+    )
+    .await
+    .map_err(log_error)?;
+    log::debug!("save_pending_wallet_creation - finished");
+    Ok(wallet)
+}
+
+/// Retrieve the wallet whose creation did not complete, if any
+pub async fn load_pending_wallet_creation(
+    database_service: Coroutine<DatabaseCommand>,
+) -> Result<Option<Wallet>, String> {
+    log::debug!("load_pending_wallet_creation - start");
+    let wallet =
+        blocking_db_service_operation(database_service, "Load the pending wallet creation", |db| {
+            db.get_item::<Wallet>(PENDING_WALLET_CREATION_KEY)
+        })
+        .await
+        .map_err(log_error);
+    log::debug!("load_pending_wallet_creation - finished");
+    wallet
+}
+
+/// Describe the wallet whose creation did not complete, if any
+pub async fn get_pending_wallet_creation(
+    database_service: Coroutine<DatabaseCommand>,
+) -> Result<Option<PendingWalletCreation>, String> {
+    Ok(load_pending_wallet_creation(database_service)
+        .await?
+        .map(|wallet| PendingWalletCreation {
+            wallet_name: CCStr::from(wallet.name()),
+            service_wallet_id: match wallet.online_wallet() {
+                AnyOnlineWallet::Service(service_binding) => {
+                    Some(CCStr::from(service_binding.wallet_id()))
+                }
+                _ => None,
+            },
+        }))
+}
+
+/// Forget the wallet whose creation did not complete, once created or abandoned
+pub async fn clear_pending_wallet_creation(
+    database_service: Coroutine<DatabaseCommand>,
+) -> Result<(), String> {
+    log::debug!("clear_pending_wallet_creation - start");
+    blocking_db_service_operation(
+        database_service,
+        "Clear the pending wallet creation",
+        |mut db| db.delete_item::<Wallet>(PENDING_WALLET_CREATION_KEY),
+    )
+    .await
+    .map_err(log_error)?;
+    log::debug!("clear_pending_wallet_creation - finished");
+    Ok(())
+}
+
 pub async fn save_wallet(
     database_service: Coroutine<DatabaseCommand>,
     wallet: Arc<Wallet>,
 ) -> Result<(), String> {
     log::debug!("save_wallet({wallet:?}) - start");
 
-    get_database(database_service)
-        .await
-        .blocking_operation(move |mut db| wallet.save(&mut db))
-        .await
-        .map_err(log_error)?;
+    blocking_db_service_operation(database_service, "Save the wallet", move |mut db| {
+        wallet.save(&mut db)
+    })
+    .await
+    .map_err(log_error)?;
 
     log::debug!("save_wallet - finished");
     Ok(())
 }
+
+/// Account indexes already fed to the other wallets using the seed of the given fingerprint
+///
+/// Wallets reusing the same seed must derive their account xpubs from distinct accounts,
+/// otherwise they would share their addresses. Wallets that cannot be loaded are ignored.
+pub async fn used_account_indexes(
+    database_service: Coroutine<DatabaseCommand>,
+    service_client_service: Coroutine<ServiceClientCommand>,
+    blockchain_provider_service: Coroutine<BlockchainProviderCommand>,
+    fingerprint: Fingerprint,
+) -> BTreeSet<u32> {
+    log::debug!("used_account_indexes({fingerprint}) - start");
+    let wallet_names = list_wallet_names(database_service)
+        .await
+        .unwrap_or_default();
+    let mut used_account_indexes = BTreeSet::new();
+    for name in wallet_names {
+        let Ok(wallet) = get_wallet(
+            database_service,
+            service_client_service,
+            blockchain_provider_service,
+            name,
+        )
+        .await
+        else {
+            continue;
+        };
+        if wallet.fingerprint().ok() != Some(fingerprint) {
+            continue;
+        }
+        match wallet.list_account_xpubs().await {
+            Ok(account_xpubs) => {
+                used_account_indexes.extend(account_xpubs.into_iter().map(|axpub| match axpub {
+                    AccountXPubWithStatus::Used(axpub) | AccountXPubWithStatus::Unused(axpub) => {
+                        axpub.descriptor_id()
+                    }
+                }))
+            }
+            Err(e) => log::warn!("Could not list the account xpubs of {}: {e}", wallet.name()),
+        }
+    }
+    log::debug!("used_account_indexes({fingerprint}) - finished");
+    used_account_indexes
+}
+
+/// Below this number of unused Account XPubs, the pool of a wallet is considered low
+pub const LOW_UNUSED_XPUBS: usize = 3;
+/// Number of Account XPubs derived and fed when the pool of a wallet is topped up
+const TOPUP_XPUBS_COUNT: u32 = 20;
+
+/// Derive and feed new Account XPubs to `wallet` if its pool of unused ones runs low
+///
+/// Nothing is done if the key provider cannot derive them without the user: a password
+/// protected local key or a Ledger that is not connected. The new Account XPubs come after
+/// the accounts already used by the wallets sharing the same seed.
+/// Returns the number of Account XPubs fed.
+pub async fn topup_account_xpubs(
+    database_service: Coroutine<DatabaseCommand>,
+    service_client_service: Coroutine<ServiceClientCommand>,
+    blockchain_provider_service: Coroutine<BlockchainProviderCommand>,
+    wallet: &mut Wallet,
+) -> Result<usize, String> {
+    log::debug!("topup_account_xpubs({}) - start", wallet.name());
+    let Ok(fingerprint) = wallet.fingerprint() else {
+        return Ok(0);
+    };
+    let key_provider_ready = match wallet.key_provider() {
+        AnyKeyProvider::None => false,
+        AnyKeyProvider::LocalKey(local_key) => local_key.is_ready(),
+        AnyKeyProvider::Ledger(_) => ledger_is_ready() == Some(fingerprint),
+    };
+    if !key_provider_ready {
+        log::debug!("topup_account_xpubs - the key provider is not available");
+        return Ok(0);
+    }
+
+    let unused_count = wallet
+        .list_account_xpubs()
+        .await
+        .map_err(log_error)?
+        .into_iter()
+        .filter(|axpub| matches!(axpub, AccountXPubWithStatus::Unused(_)))
+        .count();
+    if unused_count >= LOW_UNUSED_XPUBS {
+        log::debug!("topup_account_xpubs - {unused_count} unused Account XPubs, nothing to do");
+        return Ok(0);
+    }
+
+    let start = used_account_indexes(
+        database_service,
+        service_client_service,
+        blockchain_provider_service,
+        fingerprint,
+    )
+    .await
+    .last()
+    .map(|index| index + 1)
+    .unwrap_or_default();
+    let account_xpubs = wallet
+        .derive_accounts_xpubs(start..start + TOPUP_XPUBS_COUNT)
+        .await
+        .map_err(log_error)?;
+    let fed_count = account_xpubs.len();
+    let wallet_online_no_fingerprint = wallet.online_wallet().fingerprint().is_err();
+    wallet
+        .feed_account_xpubs(account_xpubs)
+        .await
+        .map_err(log_error)?;
+    if wallet_online_no_fingerprint {
+        if let AnyOnlineWallet::Service(service_binding) = wallet.online_wallet() {
+            inject_serviceable_wallet(
+                service_client_service,
+                service_binding.wallet_id().to_owned(),
+                service_binding.fingerprint().ok(),
+            )
+        }
+    }
+    log::debug!("topup_account_xpubs - fed {fed_count} Account XPubs");
+    Ok(fed_count)
+}
@@ -0,0 +1,63 @@
+use super::*;
+
+fn wallet_notes_key(wallet_name: &str) -> String {
+    format!("gui_wallet_notes#{wallet_name}")
+}
+
+pub async fn load_wallet_notes(
+    database_service: Coroutine<DatabaseCommand>,
+    wallet_name: CCStr,
+) -> Result<String, String> {
+    log::debug!("load_wallet_notes({wallet_name}) - start");
+    let key = wallet_notes_key(&wallet_name);
+    let notes =
+        blocking_db_service_operation(database_service, "Load the wallet notes", move |db| {
+            db.get_item::<String>(&key)
+        })
+        .await
+        .map(Option::unwrap_or_default)
+        .map_err(log_error);
+    log::debug!("load_wallet_notes({wallet_name}) - loaded");
+    notes
+}
+
+/// Save the notes of a wallet, empty notes are removed from the database
+pub async fn save_wallet_notes(
+    database_service: Coroutine<DatabaseCommand>,
+    wallet_name: CCStr,
+    notes: String,
+) -> Result<(), String> {
+    log::debug!("save_wallet_notes({wallet_name}) - start");
+    let key = wallet_notes_key(&wallet_name);
+    blocking_db_service_operation(database_service, "Save the wallet notes", move |mut db| {
+        if notes.trim().is_empty() {
+            db.delete_item::<String>(&key).map(|_| ())
+        } else {
+            db.update_item(&key, &notes)
+        }
+    })
+    .await
+    .map_err(log_error)?;
+    log::debug!("save_wallet_notes({wallet_name}) - finished");
+    Ok(())
+}
+
+/// Move the notes of a renamed wallet to its new name
+pub(super) fn rename_wallet_notes(
+    db: &mut Database,
+    old_name: &str,
+    new_name: &str,
+) -> Result<(), DbError> {
+    let old_key = wallet_notes_key(old_name);
+    if let Some(notes) = db.get_item::<String>(&old_key)? {
+        db.update_item(&wallet_notes_key(new_name), &notes)?;
+        db.delete_item::<String>(&old_key)?;
+    }
+    Ok(())
+}
+
+/// Delete the notes of a deleted wallet
+pub(super) fn delete_wallet_notes(db: &mut Database, name: &str) -> Result<(), DbError> {
+    db.delete_item::<String>(&wallet_notes_key(name))?;
+    Ok(())
+}
@@ -0,0 +1,344 @@
+use super::*;
+
+use btc_heritage_wallet::bitcoin::{Amount, Denomination};
+use serde::{Deserialize, Serialize};
+
+/// The unit in which the amounts of a wallet are displayed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisplayDenomination {
+    Bitcoin,
+    MilliBitcoin,
+    Satoshi,
+}
+impl DisplayDenomination {
+    pub fn list() -> [Self; 3] {
+        [Self::Bitcoin, Self::MilliBitcoin, Self::Satoshi]
+    }
+    pub fn code(self) -> &'static str {
+        match self {
+            DisplayDenomination::Bitcoin => "BTC",
+            DisplayDenomination::MilliBitcoin => "mBTC",
+            DisplayDenomination::Satoshi => "sat",
+        }
+    }
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::list().into_iter().find(|d| d.code() == code)
+    }
+    pub fn denomination(self) -> Denomination {
+        match self {
+            DisplayDenomination::Bitcoin => Denomination::Bitcoin,
+            DisplayDenomination::MilliBitcoin => Denomination::MilliBitcoin,
+            DisplayDenomination::Satoshi => Denomination::Satoshi,
+        }
+    }
+}
+
+/// Settings of a wallet, `None` overrides meaning the global setting applies
+///
+/// The fee source and the block explorer have no override: the application has no global
+/// setting for them yet, fees always come from the blockchain provider or the service and
+/// transactions are not linked to any explorer.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalletSettings {
+    /// Display the amounts in this unit instead of choosing it depending on the amount
+    #[serde(default)]
+    pub denomination: Option<DisplayDenomination>,
+    /// Interval between two background synchronizations of the wallet, 0 to disable them
+    #[serde(default)]
+    pub sync_interval_minutes: Option<u32>,
+    /// Derive and feed new Account XPubs when the pool of unused ones runs low
+    #[serde(default)]
+    pub auto_topup_xpubs: bool,
+    #[serde(default)]
+    pub spend_limits: SpendLimits,
+    /// Looser spend limits waiting for their delay before replacing [WalletSettings::spend_limits]
+    #[serde(default)]
+    pub pending_spend_limits: Option<PendingSpendLimits>,
+    #[serde(default)]
+    pub broadcast_checklist: BroadcastChecklist,
+    /// Re-derive the receive addresses with the Bitcoin Core node of the blockchain provider
+    /// before displaying them
+    #[serde(default)]
+    pub address_cross_check: bool,
+    /// Confirmations a UTXO needs to be spent and counted as spendable,
+    /// [WalletSettings::DEFAULT_MIN_CONFIRMATIONS] if [None]
+    #[serde(default)]
+    pub min_confirmations: Option<u32>,
+}
+impl WalletSettings {
+    pub const DEFAULT_MIN_CONFIRMATIONS: u32 = 1;
+
+    pub fn min_confirmations(&self) -> u32 {
+        self.min_confirmations
+            .unwrap_or(Self::DEFAULT_MIN_CONFIRMATIONS)
+    }
+
+    /// The spend limits in force at `now`
+    pub fn effective_spend_limits(&self, now: u64) -> SpendLimits {
+        match self.pending_spend_limits {
+            Some(pending) if pending.apply_at <= now => pending.spend_limits,
+            _ => self.spend_limits,
+        }
+    }
+
+    /// Change the spend limits at `now`
+    ///
+    /// Tighter limits apply at once. Limits that are lifted or raised only apply after the
+    /// cooling-off delay of the limits in force, or [SpendLimits::DEFAULT_LOOSENING_MINUTES]
+    /// without one, so that they cannot be removed on the spot under coercion. In the meantime,
+    /// the tightest of the old and new limits apply.
+    pub fn change_spend_limits(&mut self, new_spend_limits: SpendLimits, now: u64) {
+        let current = self.effective_spend_limits(now);
+        if current.loosened_by(&new_spend_limits) {
+            self.spend_limits = current.tightest(&new_spend_limits);
+            self.pending_spend_limits = Some(PendingSpendLimits {
+                spend_limits: new_spend_limits,
+                apply_at: now + current.loosening_minutes() as u64 * 60,
+            });
+        } else {
+            self.spend_limits = new_spend_limits;
+            self.pending_spend_limits = None;
+        }
+    }
+}
+
+/// Limits the owner imposes on the spendings of a wallet, as a guardrail against
+/// coercion and rash decisions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpendLimits {
+    /// Maximum amount of a transaction, in sat
+    #[serde(default)]
+    pub max_per_tx: Option<u64>,
+    /// Maximum amount spent during the last 24 hours, in sat
+    #[serde(default)]
+    pub max_per_day: Option<u64>,
+    /// Transactions above this amount, in sat, need a second confirmation after
+    /// the cooling-off delay
+    #[serde(default)]
+    pub cooling_off_threshold: Option<u64>,
+    #[serde(default)]
+    pub cooling_off_minutes: u32,
+}
+impl SpendLimits {
+    /// Delay before looser limits apply when there is no cooling-off delay
+    pub const DEFAULT_LOOSENING_MINUTES: u32 = 24 * 60;
+
+    /// Delay before limits looser than these apply
+    pub fn loosening_minutes(&self) -> u32 {
+        match self.cooling_off_minutes {
+            0 => Self::DEFAULT_LOOSENING_MINUTES,
+            minutes => minutes,
+        }
+    }
+
+    /// The tightest of each limit of `self` and `other`
+    pub fn tightest(&self, other: &Self) -> Self {
+        fn lowest(a: Option<u64>, b: Option<u64>) -> Option<u64> {
+            match (a, b) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            }
+        }
+        Self {
+            max_per_tx: lowest(self.max_per_tx, other.max_per_tx),
+            max_per_day: lowest(self.max_per_day, other.max_per_day),
+            cooling_off_threshold: lowest(self.cooling_off_threshold, other.cooling_off_threshold),
+            cooling_off_minutes: self.cooling_off_minutes.max(other.cooling_off_minutes),
+        }
+    }
+
+    /// Whether `new_limits` lift or raise some of these limits
+    pub fn loosened_by(&self, new_limits: &Self) -> bool {
+        self.tightest(new_limits) != *new_limits
+    }
+
+    /// Reason why a transaction sending `amount` outside of the wallet exceeds the limits,
+    /// `spent_last_day` having already left the wallet during the last 24 hours
+    pub fn violation(&self, amount: Amount, spent_last_day: Amount) -> Option<String> {
+        if let Some(max_per_tx) = self.max_per_tx.map(Amount::from_sat) {
+            if amount > max_per_tx {
+                return Some(format!(
+                    "This transaction exceeds the limit of {max_per_tx} per transaction of this wallet"
+                ));
+            }
+        }
+        if let Some(max_per_day) = self.max_per_day.map(Amount::from_sat) {
+            if spent_last_day + amount > max_per_day {
+                return Some(format!(
+                    "This transaction exceeds the limit of {max_per_day} per 24 hours of this wallet \
+                    ({spent_last_day} already spent)"
+                ));
+            }
+        }
+        None
+    }
+
+    /// Whether a transaction sending `amount` outside of the wallet must wait for the
+    /// cooling-off delay
+    pub fn needs_cooling_off(&self, amount: Amount) -> bool {
+        self.cooling_off_minutes > 0
+            && self
+                .cooling_off_threshold
+                .is_some_and(|threshold| amount.to_sat() > threshold)
+    }
+}
+
+/// Spend limits looser than the ones in force, waiting for their delay
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingSpendLimits {
+    pub spend_limits: SpendLimits,
+    /// Timestamp at which they come into force
+    pub apply_at: u64,
+}
+
+/// Verifications the owner must confirm before broadcasting a transaction
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BroadcastChecklist {
+    /// The checklist applies to transactions sending more than this amount, in sat
+    #[serde(default)]
+    pub threshold: Option<u64>,
+    /// Items of the checklist, the default ones apply if empty
+    #[serde(default)]
+    pub items: Vec<String>,
+}
+impl BroadcastChecklist {
+    pub const DEFAULT_ITEMS: [&'static str; 3] = [
+        "I verified the addresses out-of-band with the recipients",
+        "I double-checked the amounts",
+        "The fee is acceptable",
+    ];
+
+    pub fn items(&self) -> Vec<String> {
+        if self.items.is_empty() {
+            Self::DEFAULT_ITEMS
+                .iter()
+                .map(|item| item.to_string())
+                .collect()
+        } else {
+            self.items.clone()
+        }
+    }
+
+    /// Whether a transaction sending `amount` sat must go through the checklist
+    pub fn applies_to(&self, amount: u64) -> bool {
+        self.threshold.is_some_and(|threshold| amount > threshold)
+    }
+}
+
+/// A transaction above the cooling-off threshold the owner asked to create
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoolingOffRequest {
+    /// Identifies the recipients and amount of the transaction, see [CoolingOffRequest::spend_key]
+    pub spend_key: String,
+    pub requested_at: u64,
+}
+impl CoolingOffRequest {
+    /// Identifies a transaction sending `amount` in total to the `recipients` addresses
+    pub fn spend_key(recipients: impl IntoIterator<Item = String>, amount: Amount) -> String {
+        let mut recipients = recipients.into_iter().collect::<Vec<_>>();
+        recipients.sort();
+        format!("{}:{}", recipients.join(","), amount.to_sat())
+    }
+}
+
+fn wallet_settings_key(wallet_name: &str) -> String {
+    format!("gui_wallet_settings#{wallet_name}")
+}
+
+pub async fn load_wallet_settings(
+    database_service: Coroutine<DatabaseCommand>,
+    wallet_name: CCStr,
+) -> Result<WalletSettings, String> {
+    log::debug!("load_wallet_settings({wallet_name}) - start");
+    let key = wallet_settings_key(&wallet_name);
+    let wallet_settings =
+        blocking_db_service_operation(database_service, "Load the wallet settings", move |db| {
+            db.get_item::<WalletSettings>(&key)
+        })
+        .await
+        .map(Option::unwrap_or_default)
+        .map_err(log_error);
+    log::debug!("load_wallet_settings({wallet_name}) - loaded");
+    wallet_settings
+}
+
+pub async fn save_wallet_settings(
+    database_service: Coroutine<DatabaseCommand>,
+    wallet_name: CCStr,
+    wallet_settings: WalletSettings,
+) -> Result<(), String> {
+    log::debug!("save_wallet_settings({wallet_name}) - start");
+    let key = wallet_settings_key(&wallet_name);
+    blocking_db_service_operation(
+        database_service,
+        "Save the wallet settings",
+        move |mut db| db.update_item(&key, &wallet_settings),
+    )
+    .await
+    .map_err(log_error)?;
+    log::debug!("save_wallet_settings({wallet_name}) - finished");
+    Ok(())
+}
+
+fn cooling_off_request_key(wallet_name: &str) -> String {
+    format!("gui_spend_cooling_off#{wallet_name}")
+}
+
+pub async fn load_cooling_off_request(
+    database_service: Coroutine<DatabaseCommand>,
+    wallet_name: CCStr,
+) -> Result<Option<CoolingOffRequest>, String> {
+    let key = cooling_off_request_key(&wallet_name);
+    blocking_db_service_operation(
+        database_service,
+        "Load the cooling-off request",
+        move |db| db.get_item::<CoolingOffRequest>(&key),
+    )
+    .await
+    .map_err(log_error)
+}
+
+/// Save the cooling-off request of a wallet, or remove it if `request` is [None]
+pub async fn save_cooling_off_request(
+    database_service: Coroutine<DatabaseCommand>,
+    wallet_name: CCStr,
+    request: Option<CoolingOffRequest>,
+) -> Result<(), String> {
+    let key = cooling_off_request_key(&wallet_name);
+    blocking_db_service_operation(
+        database_service,
+        "Save the cooling-off request",
+        move |mut db| match request {
+            Some(request) => db.update_item(&key, &request),
+            None => db.delete_item::<CoolingOffRequest>(&key).map(|_| ()),
+        },
+    )
+    .await
+    .map_err(log_error)
+}
+
+/// Move the settings of a renamed wallet to its new name
+pub(super) fn rename_wallet_settings(
+    db: &mut Database,
+    old_name: &str,
+    new_name: &str,
+) -> Result<(), DbError> {
+    let old_key = wallet_settings_key(old_name);
+    if let Some(wallet_settings) = db.get_item::<WalletSettings>(&old_key)? {
+        db.update_item(&wallet_settings_key(new_name), &wallet_settings)?;
+        db.delete_item::<WalletSettings>(&old_key)?;
+    }
+    let old_key = cooling_off_request_key(old_name);
+    if let Some(request) = db.get_item::<CoolingOffRequest>(&old_key)? {
+        db.update_item(&cooling_off_request_key(new_name), &request)?;
+        db.delete_item::<CoolingOffRequest>(&old_key)?;
+    }
+    Ok(())
+}
+
+/// Delete the settings of a deleted wallet
+pub(super) fn delete_wallet_settings(db: &mut Database, name: &str) -> Result<(), DbError> {
+    db.delete_item::<WalletSettings>(&wallet_settings_key(name))?;
+    db.delete_item::<CoolingOffRequest>(&cooling_off_request_key(name))?;
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use std::{process::Stdio, time::Duration};
+
+use dioxus::prelude::*;
+use tokio::io::AsyncWriteExt;
+
+use btc_heritage_wallet::{
+    bitcoin::Network,
+    btc_heritage::{bitcoincore_rpc::jsonrpc::serde_json, utils::bitcoin_network},
+    heritage_service_api_client::Fingerprint,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+
+/// A hardware wallet device enumerated by HWI
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct HwiDevice {
+    #[serde(rename = "type")]
+    pub device_type: String,
+    #[serde(default)]
+    pub model: String,
+    #[serde(default)]
+    pub path: String,
+    #[serde(default)]
+    fingerprint: Option<String>,
+    #[serde(default)]
+    pub needs_pin_sent: bool,
+    #[serde(default)]
+    pub needs_passphrase_sent: bool,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+impl HwiDevice {
+    /// Fingerprint of the seed of the device, unknown while it is locked
+    pub fn fingerprint(&self) -> Option<Fingerprint> {
+        self.fingerprint.as_ref()?.parse().ok()
+    }
+    /// Why the device cannot be used yet, if any
+    pub fn not_ready_reason(&self) -> Option<String> {
+        if let Some(ref error) = self.error {
+            Some(error.clone())
+        } else if self.needs_pin_sent {
+            Some("Unlock the device with its PIN".to_owned())
+        } else if self.needs_passphrase_sent {
+            Some("Enter the passphrase on the device".to_owned())
+        } else if self.fingerprint().is_none() {
+            Some("The device did not provide its fingerprint".to_owned())
+        } else {
+            None
+        }
+    }
+}
+
+/// Status of the hardware wallets reachable through HWI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HwiStatus {
+    /// HWI runs, with the devices currently connected (possibly none)
+    Devices(Vec<HwiDevice>),
+    /// The HWI executable could not be run
+    Unavailable(String),
+}
+impl HwiStatus {
+    async fn current() -> Self {
+        match run_hwi::<Vec<HwiDevice>>(vec!["enumerate".to_owned()], HWI_TIMEOUT).await {
+            Ok(devices) => HwiStatus::Devices(
+                devices
+                    .into_iter()
+                    // Ledger devices use the native integration
+                    .filter(|device| device.device_type != "ledger")
+                    .collect(),
+            ),
+            Err(e) => {
+                log::warn!("HWI is unavailable: {e}");
+                HwiStatus::Unavailable(e)
+            }
+        }
+    }
+}
+
+pub static HWI_STATUS: GlobalSignal<Option<HwiStatus>> = Signal::global(|| None);
+
+/// Wakes the HWI status service up for a new enumeration
+pub(super) static HWI_REFRESH: tokio::sync::Notify = tokio::sync::Notify::const_new();
+
+/// Time HWI is given to answer a command that does not wait for the user
+pub(super) const HWI_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Executable of HWI, taken from the `HWI_PATH` environment variable or searched in the `PATH`
+fn hwi_executable() -> String {
+    std::env::var("HWI_PATH").unwrap_or_else(|_| "hwi".to_owned())
+}
+
+fn hwi_chain() -> &'static str {
+    match bitcoin_network::get() {
+        Network::Bitcoin => "main",
+        Network::Signet => "signet",
+        Network::Regtest => "regtest",
+        _ => "test",
+    }
+}
+
+/// Error reported by HWI instead of the result of a command
+#[derive(Debug, Deserialize)]
+struct HwiError {
+    error: String,
+}
+
+/// Run HWI with `args` on the network of the application and parse its JSON output
+///
+/// The arguments are sent on the standard input of HWI, a PSBT can exceed the maximum
+/// length of a command line. HWI is killed if it does not answer within `timeout`,
+/// or as soon as the returned future is dropped.
+pub(super) async fn run_hwi<T: DeserializeOwned>(
+    args: Vec<String>,
+    timeout: Duration,
+) -> Result<T, String> {
+    let hwi = async move {
+        let mut child = tokio::process::Command::new(hwi_executable())
+            .arg("--chain")
+            .arg(hwi_chain())
+            .arg("--stdin")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| format!("Could not run HWI: {e}"))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // One argument per line, HWI stops reading after two blank lines
+            let input = format!("{}\n\n\n", args.join("\n"));
+            stdin
+                .write_all(input.as_bytes())
+                .await
+                .map_err(|e| format!("Could not send the command to HWI: {e}"))?;
+        }
+        child
+            .wait_with_output()
+            .await
+            .map_err(|e| format!("Could not run HWI: {e}"))
+    };
+    let output = tokio::time::timeout(timeout, hwi)
+        .await
+        .map_err(|_| "HWI did not answer in time".to_owned())??;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if let Ok(HwiError { error }) = serde_json::from_str::<HwiError>(&stdout) {
+        return Err(error);
+    }
+    serde_json::from_str(&stdout).map_err(|e| format!("Unexpected HWI output: {e}"))
+}
+
+/// The devices are only enumerated on request: HWI opens every connected device,
+/// so a continuous polling would compete with the native Ledger integration
+pub(super) fn use_hwi_status_service() {
+    use_future(async move || loop {
+        HWI_REFRESH.notified().await;
+        log::debug!("hwi_status_service: Refreshing...");
+        let new_status = HwiStatus::current().await;
+        if HWI_STATUS.peek().as_ref() != Some(&new_status) {
+            log::info!("hwi_status_service: HWI status changed to {new_status:?}");
+        }
+        // Always written, so the components waiting for the enumeration see it ended
+        *HWI_STATUS.write() = Some(new_status);
+    });
+}
@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use dioxus::prelude::*;
 
 use btc_heritage_wallet::{btc_heritage::utils::bitcoin_network, errors::Error};
@@ -15,6 +17,10 @@ pub enum LedgerStatus {
     NotReady,
 }
 impl LedgerStatus {
+    /// Query the device for its status
+    ///
+    /// Nothing is logged above the debug level as the device is polled, the status service
+    /// logs the status when it changes.
     pub(super) async fn current() -> Self {
         match btc_heritage_wallet::ledger_client().await {
             Some((ledger_client, fg)) => match ledger_client.network().await {
@@ -22,16 +28,18 @@ impl LedgerStatus {
                     if ledger_network == bitcoin_network::get() {
                         LedgerStatus::Ready(fg)
                     } else {
-                        log::warn!("The Ledger Bitcoin application is opened on the wrong network");
+                        log::debug!(
+                            "The Ledger Bitcoin application is opened on the wrong network"
+                        );
                         LedgerStatus::WrongNetwork
                     }
                 }
                 Err(Error::WrongLedgerApplication) => {
-                    log::warn!("The Ledger is not opened on the Bitcoin application");
+                    log::debug!("The Ledger is not opened on the Bitcoin application");
                     LedgerStatus::WrongApp
                 }
                 Err(e) => {
-                    log::error!("{e}");
+                    log::debug!("The Ledger did not answer: {e}");
                     LedgerStatus::NotReady
                 }
             },
@@ -42,14 +50,53 @@ impl LedgerStatus {
 
 pub static LEDGER_STATUS: GlobalSignal<Option<LedgerStatus>> = Signal::global(|| None);
 
+/// The signers (wallets, heir wallets) whose seed was verified on the connected Ledger
+///
+/// A fingerprint only identifies a seed with 32 bits, so when the Ledger is (re)connected,
+/// possibly another device than the one used at creation, the seed is verified again.
+pub(super) static LEDGER_VERIFIED_SIGNERS: GlobalSignal<HashSet<String>> =
+    Signal::global(HashSet::new);
+
+/// Wakes the ledger status service up for an immediate check
+pub(super) static LEDGER_REFRESH: tokio::sync::Notify = tokio::sync::Notify::const_new();
+
+/// Delay between two checks of the Ledger status
+///
+/// There is no portable way to be notified of USB hot-plug events, so the device is polled.
+/// When it is not ready, the user is most likely plugging it, unlocking it or opening the
+/// Bitcoin application, so it is polled more often to pick the change up promptly.
+fn refresh_delay(status: LedgerStatus) -> std::time::Duration {
+    std::time::Duration::from_secs(match status {
+        LedgerStatus::Ready(_) => 10,
+        LedgerStatus::WrongApp | LedgerStatus::WrongNetwork => 2,
+        LedgerStatus::NotReady => 3,
+    })
+}
+
 pub(super) fn use_ledger_status_service() {
     use_future(async move || loop {
         log::debug!("ledger_status_service: Refreshing...");
-        let new_status = Some(LedgerStatus::current().await);
+        let new_status = LedgerStatus::current().await;
 
-        if LEDGER_STATUS() != new_status {
-            *LEDGER_STATUS.write() = new_status;
+        let old_status = LEDGER_STATUS();
+        if old_status != Some(new_status) {
+            log::info!("ledger_status_service: Ledger status changed from {old_status:?} to {new_status:?}");
+            match new_status {
+                LedgerStatus::WrongNetwork => {
+                    log::warn!("The Ledger Bitcoin application is opened on the wrong network")
+                }
+                LedgerStatus::WrongApp => {
+                    log::warn!("The Ledger is not opened on the Bitcoin application")
+                }
+                LedgerStatus::Ready(_) | LedgerStatus::NotReady => (),
+            }
+            *LEDGER_STATUS.write() = Some(new_status);
+            if !LEDGER_VERIFIED_SIGNERS.peek().is_empty() {
+                LEDGER_VERIFIED_SIGNERS.write().clear();
+            }
         }
-        tokio::time::sleep(std::time::Duration::from_secs(10)).await
+        // Checks are only ever done here so the device is never accessed concurrently,
+        // a refresh request just cuts the wait short
+        let _ = tokio::time::timeout(refresh_delay(new_status), LEDGER_REFRESH.notified()).await;
     });
 }
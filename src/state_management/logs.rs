@@ -0,0 +1,147 @@
+use dioxus::prelude::*;
+
+use std::path::PathBuf;
+
+use btc_heritage_wallet::DatabaseSingleItem;
+use futures_util::stream::StreamExt;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::alert_error;
+
+use super::{
+    database::{DatabaseCommand, DatabaseReloadEvent, APPLICATION_CONFIG},
+    event_bus::{subscribe_event, EventBus},
+};
+
+/// Logging behavior of the application, applied without restarting it
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogConfig {
+    /// Filter with the syntax of `RUST_LOG` (e.g. `info,heritage_gui=debug`),
+    /// empty to use `RUST_LOG` or the default filter
+    pub filter: String,
+    /// Also write the logs in rotating files inside the datadir
+    pub log_to_file: bool,
+}
+impl LogConfig {
+    /// Directory of the log files
+    pub fn log_dir() -> PathBuf {
+        APPLICATION_CONFIG.peek().datadir.join("logs")
+    }
+}
+
+impl DatabaseSingleItem for LogConfig {
+    fn item_key() -> &'static str {
+        "gui_log_config"
+    }
+}
+
+pub static LOG_CONFIG: GlobalSignal<LogConfig> = Signal::global(|| LogConfig::default());
+
+/// Commands for the log config service
+#[derive(Debug)]
+pub(super) enum LogConfigCommand {
+    /// Internal trigger a save into the DB
+    Persist { config: LogConfig },
+    /// Internal trigger a refresh from the DB
+    Refresh,
+}
+
+/// Log config service coroutine
+pub(super) fn use_log_config_service(
+    event_bus: EventBus,
+    database_service: Coroutine<DatabaseCommand>,
+) -> Coroutine<LogConfigCommand> {
+    let service_handle = use_coroutine(
+        move |mut rx: UnboundedReceiver<LogConfigCommand>| async move {
+            log::info!("log_config_service (coroutine) - start");
+
+            let mut cached_value = load_config(database_service).await;
+            apply_config(&cached_value);
+            *LOG_CONFIG.write() = cached_value.clone();
+
+            // Flag to trash the first persist command that will come from the initial run of the "use_effect"
+            let mut discard_next_persit = true;
+
+            while let Some(cmd) = rx.next().await {
+                log::debug!("log_config_service (coroutine) - Processing command {cmd:?}...");
+
+                match cmd {
+                    LogConfigCommand::Persist { config } => {
+                        if discard_next_persit {
+                            log::debug!(
+                                "log_config_service (coroutine) - Ignoring first Persist cmd."
+                            );
+                            discard_next_persit = false;
+                        }
+                        if config != cached_value {
+                            log::debug!(
+                                "log_config_service (coroutine) - Config changed, persisting..."
+                            );
+                            apply_config(&config);
+                            cached_value = config.clone();
+                            match save_config(database_service, config).await {
+                                Ok(_) => (),
+                                Err(msg) => {
+                                    log::error!("{msg}");
+                                    alert_error(msg);
+                                }
+                            };
+                        } else {
+                            log::debug!("log_config_service (coroutine) - Ignoring Persist cmd: already in database.");
+                        }
+                    }
+                    LogConfigCommand::Refresh => {
+                        cached_value = load_config(database_service).await;
+                        apply_config(&cached_value);
+                        *LOG_CONFIG.write() = cached_value.clone()
+                    }
+                }
+
+                log::debug!("log_config_service (coroutine) - Command processed");
+            }
+        },
+    );
+    subscribe_event(event_bus, move |_event: DatabaseReloadEvent| {
+        service_handle.send(LogConfigCommand::Refresh);
+    });
+    use_effect(move || {
+        service_handle.send(LogConfigCommand::Persist {
+            config: LOG_CONFIG(),
+        });
+    });
+    service_handle
+}
+
+fn apply_config(config: &LogConfig) {
+    let log_dir = config.log_to_file.then(LogConfig::log_dir);
+    if let Err(msg) = crate::logger::reconfigure(&config.filter, log_dir) {
+        log::error!("{msg}");
+        alert_error(msg);
+    }
+}
+
+async fn load_config(database_service: Coroutine<DatabaseCommand>) -> LogConfig {
+    match super::helpers::blocking_db_service_operation(
+        database_service,
+        "Load the log config",
+        |db| LogConfig::load(&db),
+    )
+    .await
+    {
+        Ok(config) => config,
+        Err(_) => LogConfig::default(),
+    }
+}
+
+async fn save_config(
+    database_service: Coroutine<DatabaseCommand>,
+    config: LogConfig,
+) -> Result<(), String> {
+    super::helpers::blocking_db_service_operation(
+        database_service,
+        "Save the log config",
+        move |mut db| config.save(&mut db),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
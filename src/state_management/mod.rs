@@ -1,41 +1,106 @@
+mod activity;
+mod alert_routing;
+mod automation;
+mod background;
 mod blockchain;
+mod cancellation;
 mod clipboard;
+mod clock;
 mod database;
+mod database_queue;
+mod demo;
 mod event_bus;
+mod event_hooks;
 mod helpers;
+mod hwi;
 mod ledger;
+mod logs;
+mod notifications;
 mod onboarding;
 mod service;
 mod theme;
 
+pub(crate) use database::{startup_datadir, DatabaseCommand};
+pub(crate) use demo::remove_stale_demo_datadirs;
+
 pub fn use_init_services() {
     log::debug!("init_services - start");
     let event_bus_service = event_bus::use_event_bus_service();
+    activity::use_wallet_activity_service(event_bus_service);
     let database_service = database::use_database_service(event_bus_service);
-    let _ = service::use_service_client_service(event_bus_service, database_service);
-    let _ = blockchain::use_blockchain_provider_service(event_bus_service, database_service);
+    let _ = notifications::use_notification_service(event_bus_service, database_service);
+    let _ = alert_routing::use_alert_routing_service(event_bus_service, database_service);
+    let service_client_service =
+        service::use_service_client_service(event_bus_service, database_service);
+    let blockchain_provider_service =
+        blockchain::use_blockchain_provider_service(event_bus_service, database_service);
     let _ = onboarding::use_onboarding_service(event_bus_service, database_service);
     let _ = theme::use_theme_service(event_bus_service, database_service);
+    let _ = logs::use_log_config_service(event_bus_service, database_service);
+    let _ = event_hooks::use_event_hooks_service(event_bus_service, database_service);
     let _ = clipboard::use_clipboard_service();
     ledger::use_ledger_status_service();
+    hwi::use_hwi_status_service();
+    clock::use_clock_check_service(service_client_service, blockchain_provider_service);
+    let _ = background::use_background_sync_service(
+        event_bus_service,
+        database_service,
+        service_client_service,
+        blockchain_provider_service,
+    );
+    let _ = automation::use_automation_service(
+        event_bus_service,
+        database_service,
+        service_client_service,
+        blockchain_provider_service,
+    );
     log::debug!("init_services - finished");
 }
 
 pub mod prelude {
+    pub use super::activity::WalletActivity;
+    pub use super::alert_routing::{AlertRoute, AlertRoutingConfig};
+    pub use super::automation::AutomationConfig;
+    pub use super::background::BackgroundSyncConfig;
     pub use super::blockchain::BlockchainProviderStatus;
     pub use super::database::ApplicationConfig;
+    pub use super::database_queue::{DbOperationInfo, DbOperationPriority};
+    pub use super::event_hooks::{
+        EventHook, EventHookAction, EventHooksConfig, MonitoringEvent, MonitoringEventKind,
+    };
+    pub use super::hwi::{HwiDevice, HwiStatus};
     pub use super::ledger::LedgerStatus;
+    pub use super::logs::LogConfig;
+    pub use super::notifications::Notification;
     pub use super::onboarding::OnboardingStatus;
-    pub use super::service::ServiceStatus;
-    pub use super::theme::Theme;
+    pub use super::service::{ServiceAuthState, ServiceServiceability, ServiceStatus};
+    pub use super::theme::{Theme, ThemeCustomization, ThemePalette};
 
     pub mod state_management {
+        pub use super::super::activity::WALLET_ACTIVITY;
+        pub use super::super::alert_routing::ALERT_ROUTING_CONFIG;
+        pub use super::super::automation::AUTOMATION_CONFIG;
+        pub use super::super::background::{
+            record_wallet_sync_result, sync_wallet, WalletSyncState, BACKGROUND_SYNC_CONFIG,
+            MINIMIZED_FLAG, WALLET_SYNC_STATES,
+        };
         pub use super::super::blockchain::BLOCKCHAIN_PROVIDER_STATUS;
+        pub use super::super::clock::CLOCK_DRIFT;
         pub use super::super::database::APPLICATION_CONFIG;
+        pub use super::super::database_queue::{
+            DATABASE_OPERATIONS, OPERATION_TIMEOUT, SLOW_OPERATION_THRESHOLD,
+        };
+        pub use super::super::demo::DEMO_MODE;
+        pub use super::super::event_hooks::EVENT_HOOKS_CONFIG;
         pub use super::super::helpers::*;
+        pub use super::super::hwi::HWI_STATUS;
         pub use super::super::ledger::LEDGER_STATUS;
+        pub use super::super::logs::LOG_CONFIG;
+        pub use super::super::notifications::NOTIFICATIONS;
         pub use super::super::onboarding::ONBOARDING_STATUS;
-        pub use super::super::service::SERVICE_STATUS;
-        pub use super::super::theme::THEME;
+        pub use super::super::service::{
+            SERVICE_AUTH_STATE, SERVICE_SERVICEABILITY, SERVICE_STATUS, SERVICE_STATUS_LAST_REFRESH,
+        };
+        pub use super::super::theme::{THEME, THEME_CUSTOMIZATION};
     }
 }
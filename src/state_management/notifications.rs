@@ -0,0 +1,184 @@
+use dioxus::prelude::*;
+
+use std::collections::VecDeque;
+
+use btc_heritage_wallet::DatabaseSingleItem;
+use futures_util::stream::StreamExt;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{components::alerts::AlertClass, utils::CCStr};
+
+use super::{
+    database::{DatabaseCommand, DatabaseReloadEvent},
+    event_bus::{subscribe_event, EventBus, EventId},
+};
+
+/// Maximum number of notifications kept in the history
+const MAX_NOTIFICATIONS: usize = 200;
+
+/// Event published each time an alert is displayed to the user
+#[derive(Debug, Clone)]
+pub struct AlertEvent {
+    pub alert_class: AlertClass,
+    pub title: CCStr,
+    pub message: CCStr,
+}
+impl EventId for AlertEvent {
+    fn event_id() -> &'static str {
+        "alert"
+    }
+}
+
+/// An entry of the notification center, built from an [AlertEvent]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Notification {
+    pub id: Uuid,
+    pub alert_class: AlertClass,
+    pub title: CCStr,
+    pub message: CCStr,
+    pub timestamp: u64,
+    pub read: bool,
+}
+
+/// The notification history, as persisted in the database
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct NotificationHistory(VecDeque<Notification>);
+
+impl DatabaseSingleItem for NotificationHistory {
+    fn item_key() -> &'static str {
+        "gui_notifications"
+    }
+}
+
+/// History of the notifications, most recent first
+pub static NOTIFICATIONS: GlobalSignal<VecDeque<Notification>> =
+    Signal::global(|| VecDeque::new());
+
+/// Commands for the notification service
+#[derive(Debug)]
+pub(super) enum NotificationCommand {
+    /// Internal trigger a save into the DB
+    Persist {
+        notifications: VecDeque<Notification>,
+    },
+    /// Internal trigger a refresh from the DB
+    Refresh,
+}
+
+/// Notification service, recording every [AlertEvent] into the [NOTIFICATIONS] history
+/// and persisting the history in the database
+pub(super) fn use_notification_service(
+    event_bus: EventBus,
+    database_service: Coroutine<DatabaseCommand>,
+) -> Coroutine<NotificationCommand> {
+    let service_handle = use_coroutine(
+        move |mut rx: UnboundedReceiver<NotificationCommand>| async move {
+            log::info!("notification_service (coroutine) - start");
+
+            let mut cached_value = load_history(database_service).await;
+            {
+                // Alerts may have been recorded while the history was loading, they are the most recent
+                let mut notifications = NOTIFICATIONS.write();
+                notifications.extend(cached_value.0.iter().cloned());
+                notifications.truncate(MAX_NOTIFICATIONS);
+            }
+
+            // Flag to trash the first persist command that will come from the initial run of the "use_effect"
+            let mut discard_next_persist = true;
+
+            while let Some(cmd) = rx.next().await {
+                log::debug!("notification_service (coroutine) - Processing command {cmd:?}...");
+
+                match cmd {
+                    NotificationCommand::Persist { notifications } => {
+                        if discard_next_persist {
+                            log::debug!(
+                                "notification_service (coroutine) - Ignoring first Persist cmd."
+                            );
+                            discard_next_persist = false;
+                            continue;
+                        }
+                        if notifications != cached_value.0 {
+                            log::debug!(
+                                "notification_service (coroutine) - History changed, persisting..."
+                            );
+                            cached_value = NotificationHistory(notifications);
+                            if let Err(msg) =
+                                save_history(database_service, cached_value.clone()).await
+                            {
+                                // No alert, it would itself be recorded as a notification
+                                log::error!("{msg}");
+                            }
+                        } else {
+                            log::debug!("notification_service (coroutine) - Ignoring Persist cmd: already in database.");
+                        }
+                    }
+                    NotificationCommand::Refresh => {
+                        cached_value = load_history(database_service).await;
+                        *NOTIFICATIONS.write() = cached_value.0.clone();
+                    }
+                }
+
+                log::debug!("notification_service (coroutine) - Command processed");
+            }
+        },
+    );
+    use_hook(move || {
+        subscribe_event(event_bus, move |event: AlertEvent| {
+            let AlertEvent {
+                alert_class,
+                title,
+                message,
+            } = event;
+            let mut notifications = NOTIFICATIONS.write();
+            // Remove oldest notifications if we've reached the maximum
+            while notifications.len() >= MAX_NOTIFICATIONS {
+                notifications.pop_back();
+            }
+            notifications.push_front(Notification {
+                id: Uuid::new_v4(),
+                alert_class,
+                title,
+                message,
+                timestamp: chrono::Utc::now().timestamp() as u64,
+                read: false,
+            });
+        });
+        subscribe_event(event_bus, move |_event: DatabaseReloadEvent| {
+            service_handle.send(NotificationCommand::Refresh);
+        });
+    });
+    use_effect(move || {
+        service_handle.send(NotificationCommand::Persist {
+            notifications: NOTIFICATIONS(),
+        });
+    });
+    service_handle
+}
+
+async fn load_history(database_service: Coroutine<DatabaseCommand>) -> NotificationHistory {
+    match super::helpers::blocking_db_service_operation(
+        database_service,
+        "Load the notifications",
+        |db| NotificationHistory::load(&db),
+    )
+    .await
+    {
+        Ok(history) => history,
+        Err(_) => NotificationHistory::default(),
+    }
+}
+
+async fn save_history(
+    database_service: Coroutine<DatabaseCommand>,
+    history: NotificationHistory,
+) -> Result<(), String> {
+    super::helpers::blocking_db_service_operation(
+        database_service,
+        "Save the notifications",
+        move |mut db| history.save(&mut db),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
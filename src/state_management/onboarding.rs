@@ -121,11 +121,12 @@ pub(super) fn use_onboarding_service(
 }
 
 async fn load_status(database_service: Coroutine<DatabaseCommand>) -> OnboardingStatus {
-    let database = super::helpers::get_database(database_service).await;
-
-    match database
-        .blocking_operation(|db| OnboardingStatus::load(&db))
-        .await
+    match super::helpers::blocking_db_service_operation(
+        database_service,
+        "Load the onboarding status",
+        |db| OnboardingStatus::load(&db),
+    )
+    .await
     {
         Ok(status) => status,
         Err(e) => {
@@ -146,9 +147,11 @@ async fn save_status(
     database_service: Coroutine<DatabaseCommand>,
     status: OnboardingStatus,
 ) -> Result<OnboardingStatus, String> {
-    let database = super::helpers::get_database(database_service).await;
-    database
-        .blocking_operation(move |mut db| status.save(&mut db).map(|_| status))
-        .await
-        .map_err(|e| e.to_string())
+    super::helpers::blocking_db_service_operation(
+        database_service,
+        "Save the onboarding status",
+        move |mut db| status.save(&mut db).map(|_| status),
+    )
+    .await
+    .map_err(|e| e.to_string())
 }
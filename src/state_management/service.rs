@@ -10,7 +10,7 @@ use std::{
 use tokio::sync::oneshot;
 
 use btc_heritage_wallet::{
-    btc_heritage::bitcoincore_rpc::jsonrpc::serde_json,
+    btc_heritage::{bitcoincore_rpc::jsonrpc::serde_json, utils::timestamp_now},
     heritage_provider,
     heritage_service_api_client::{
         DeviceAuthorizationResponse, Fingerprint, HeritageServiceClient, HeritageServiceConfig,
@@ -18,9 +18,10 @@ use btc_heritage_wallet::{
     online_wallet, BoundFingerprint, DatabaseSingleItem,
 };
 
-use crate::utils::log_error;
+use crate::utils::{log_error, CCStr};
 
 use super::{
+    cancellation::CommandResult,
     database::{DatabaseCommand, DatabaseReloadEvent},
     event_bus::{subscribe_event, EventBus},
 };
@@ -34,11 +35,40 @@ pub struct UserId {
     pub email: Box<str>,
 }
 
+/// Wallets and heritages that the connected account of the Heritage Service can serve
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ServiceServiceability {
+    wallets: HashMap<String, Option<Fingerprint>>,
+    heritages: HashSet<Fingerprint>,
+}
+impl ServiceServiceability {
+    pub fn can_serve_wallet(&self, online_wallet: &online_wallet::ServiceBinding) -> bool {
+        let css_fg = self
+            .wallets
+            .get(online_wallet.wallet_id())
+            .cloned()
+            .flatten();
+        let sb_fg = online_wallet.fingerprint();
+
+        match (css_fg, sb_fg) {
+            (Some(css_fg), Ok(sb_fg)) => css_fg == sb_fg,
+            (None, Err(_)) => true,
+            _ => false,
+        }
+    }
+    pub fn can_serve_heritage(&self, service_binding: &heritage_provider::ServiceBinding) -> bool {
+        self.heritages.contains(
+            &service_binding
+                .fingerprint()
+                .expect("always present for heritage_provider sb"),
+        )
+    }
+}
+
 #[derive(Debug)]
 pub struct ConnectedServiceStatus {
     pub user_id: UserId,
-    serviceable_wallets: HashMap<String, Option<Fingerprint>>,
-    serviceable_heritages: HashSet<Fingerprint>,
+    serviceability: ServiceServiceability,
 }
 #[derive(Debug)]
 pub enum ServiceStatus {
@@ -48,35 +78,47 @@ pub enum ServiceStatus {
 impl ServiceStatus {
     pub fn can_serve_wallet(&self, online_wallet: &online_wallet::ServiceBinding) -> bool {
         match self {
-            ServiceStatus::Connected(css) => {
-                let css_fg = css
-                    .serviceable_wallets
-                    .get(online_wallet.wallet_id())
-                    .cloned()
-                    .flatten();
-                let sb_fg = online_wallet.fingerprint();
-
-                match (css_fg, sb_fg) {
-                    (Some(css_fg), Ok(sb_fg)) => css_fg == sb_fg,
-                    (None, Err(_)) => true,
-                    _ => false,
-                }
-            }
+            ServiceStatus::Connected(css) => css.serviceability.can_serve_wallet(online_wallet),
             ServiceStatus::Disconnected => false,
         }
     }
     pub fn can_serve_heritage(&self, service_binding: &heritage_provider::ServiceBinding) -> bool {
         match self {
-            ServiceStatus::Connected(css) => css.serviceable_heritages.contains(
-                &service_binding
-                    .fingerprint()
-                    .expect("always present for heritage_provider sb"),
-            ),
+            ServiceStatus::Connected(css) => css.serviceability.can_serve_heritage(service_binding),
             ServiceStatus::Disconnected => false,
         }
     }
 }
+
+/// Authentication state of the Heritage Service client
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServiceAuthState {
+    Connected { username: CCStr, email: CCStr },
+    Disconnected,
+}
+impl ServiceAuthState {
+    pub fn is_connected(&self) -> bool {
+        matches!(self, ServiceAuthState::Connected { .. })
+    }
+}
+
+/// Complete status of the Heritage Service client, [None] while it refreshes
+///
+/// It changes twice on every refresh, prefer the finer-grained signals below
+/// when only a part of the status is needed.
 pub static SERVICE_STATUS: GlobalSignal<Option<ServiceStatus>> = Signal::global(|| None);
+/// Authentication state of the Heritage Service client, [None] until first known
+///
+/// It keeps its value while the status refreshes and only changes when the user
+/// logs in or out.
+pub static SERVICE_AUTH_STATE: GlobalSignal<Option<ServiceAuthState>> = Signal::global(|| None);
+/// What the connected account can serve, only changes when the service answers differently
+pub static SERVICE_SERVICEABILITY: GlobalSignal<ServiceServiceability> =
+    Signal::global(ServiceServiceability::default);
+/// Timestamp of the last completed refresh of the status of the Heritage Service client
+///
+/// Resources querying the service read it to reload their data after each refresh.
+pub static SERVICE_STATUS_LAST_REFRESH: GlobalSignal<Option<u64>> = Signal::global(|| None);
 
 type Callback = Box<
     dyn FnOnce(
@@ -171,9 +213,17 @@ pub(super) fn use_service_client_service(
             while let Some(cmd) = rx.next().await {
                 log::debug!("service_client_service (coroutine) - Processing commmand {cmd:?}...");
                 match cmd {
-                    ServiceClientCommand::Connect { callback, result } => {
-                        match service_client.login(callback).await {
-                            Ok(()) => {
+                    ServiceClientCommand::Connect {
+                        callback,
+                        mut result,
+                    } => {
+                        // The login waits for the user to approve the connection,
+                        // stop waiting if the user left the view
+                        match result
+                            .unless_cancelled(service_client.login(callback))
+                            .await
+                        {
+                            Some(Ok(())) => {
                                 let mut database =
                                     super::helpers::get_database(database_service).await;
 
@@ -186,10 +236,11 @@ pub(super) fn use_service_client_service(
                                 };
                                 update_service_status(service_client.clone());
 
-                                result.send(Ok(())).expect("chanel failure");
+                                result.complete("Connect", Ok(()));
                             }
-                            Err(e) => {
-                                result.send(Err(e)).expect("chanel failure");
+                            Some(Err(e)) => result.complete("Connect", Err(e)),
+                            None => {
+                                log::info!("service_client_service (coroutine) - Connect cancelled")
                             }
                         }
                     }
@@ -202,19 +253,15 @@ pub(super) fn use_service_client_service(
                                     result: database_result,
                                 });
                                 match rx.await.expect("database_service error") {
-                                    Ok(_) => result.send(Ok(())).expect("chanel failure"),
-                                    Err(e) => {
-                                        result.send(Err(e)).expect("chanel failure");
-                                    }
+                                    Ok(_) => result.complete("Disconnect", Ok(())),
+                                    Err(e) => result.complete("Disconnect", Err(e)),
                                 };
                             }
-                            Err(e) => {
-                                result.send(Err(e)).expect("chanel failure");
-                            }
+                            Err(e) => result.complete("Disconnect", Err(e)),
                         }
                     }
                     ServiceClientCommand::GetServiceClient { result } => {
-                        result.send(service_client.clone()).expect("chanel failure");
+                        result.complete("GetServiceClient", service_client.clone());
                     }
                     ServiceClientCommand::RefreshStatus => {
                         update_service_status(service_client.clone());
@@ -225,11 +272,12 @@ pub(super) fn use_service_client_service(
                     } => {
                         if let Some(ServiceStatus::Connected(ref mut ss)) = *SERVICE_STATUS.write()
                         {
-                            ss.serviceable_wallets.insert(wallet_id, fingerprint);
+                            ss.serviceability.wallets.insert(wallet_id, fingerprint);
+                            set_if_changed(&SERVICE_SERVICEABILITY, ss.serviceability.clone());
                         }
                     }
                     ServiceClientCommand::GetConfig { result } => {
-                        result.send(curent_config.clone()).expect("chanel failure");
+                        result.complete("GetConfig", curent_config.clone());
                     }
                     ServiceClientCommand::UpdateConfig { config } => {
                         service_client =
@@ -282,18 +330,48 @@ fn update_service_status(client: HeritageServiceClient) {
                     .collect();
                 ServiceStatus::Connected(ConnectedServiceStatus {
                     user_id,
-                    serviceable_wallets,
-                    serviceable_heritages,
+                    serviceability: ServiceServiceability {
+                        wallets: serviceable_wallets,
+                        heritages: serviceable_heritages,
+                    },
                 })
             }
             _ => ServiceStatus::Disconnected,
         };
         log::debug!("update_service_status - set to {service_status:?}");
-        *SERVICE_STATUS.write() = Some(service_status);
+        publish_service_status(service_status);
         log::debug!("update_service_status - finished");
     });
 }
 
+/// Update the [SERVICE_STATUS] and the finer-grained signals derived from it,
+/// the latter are only written if their value actually changes
+fn publish_service_status(service_status: ServiceStatus) {
+    let (auth_state, serviceability) = match &service_status {
+        ServiceStatus::Connected(css) => (
+            ServiceAuthState::Connected {
+                username: CCStr::from(css.user_id.preferred_username.as_ref()),
+                email: CCStr::from(css.user_id.email.as_ref()),
+            },
+            css.serviceability.clone(),
+        ),
+        ServiceStatus::Disconnected => (
+            ServiceAuthState::Disconnected,
+            ServiceServiceability::default(),
+        ),
+    };
+    set_if_changed(&SERVICE_AUTH_STATE, Some(auth_state));
+    set_if_changed(&SERVICE_SERVICEABILITY, serviceability);
+    *SERVICE_STATUS.write() = Some(service_status);
+    *SERVICE_STATUS_LAST_REFRESH.write() = Some(timestamp_now());
+}
+
+fn set_if_changed<T: PartialEq + 'static>(signal: &'static GlobalSignal<T>, value: T) {
+    if *signal.peek() != value {
+        *signal.write() = value;
+    }
+}
+
 async fn create_config(database_service: Coroutine<DatabaseCommand>) -> HeritageServiceConfig {
     let default_config = HeritageServiceConfig::default();
 
@@ -312,8 +390,13 @@ async fn create_config(database_service: Coroutine<DatabaseCommand>) -> Heritage
     }
 
     // Then check DB
-    let database = super::helpers::get_database(database_service).await;
-    match HeritageServiceConfig::load(&database) {
+    match super::helpers::blocking_db_service_operation(
+        database_service,
+        "Load the Heritage Service config",
+        |db| HeritageServiceConfig::load(&db),
+    )
+    .await
+    {
         Ok(config) => config,
         Err(e) => {
             match e {
@@ -346,8 +429,14 @@ async fn service_client_from_database(
 }
 
 async fn save_config(database_service: Coroutine<DatabaseCommand>, config: &HeritageServiceConfig) {
-    let mut database = super::helpers::get_database(database_service).await;
-    match config.save(&mut database) {
+    let config = config.clone();
+    match super::helpers::blocking_db_service_operation(
+        database_service,
+        "Save the Heritage Service config",
+        move |mut db| config.save(&mut db),
+    )
+    .await
+    {
         Ok(()) => (),
         Err(e) => {
             log::error!("Could not save Heritage Service Config in database: {e}");
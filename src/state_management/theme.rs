@@ -26,11 +26,108 @@ impl DatabaseSingleItem for Theme {
 
 pub static THEME: GlobalSignal<Theme> = Signal::global(|| Theme::Dark);
 
-/// Commands for the blockchain provider service
+/// A pair of DaisyUI themes, one used in [Theme::Light] and the other in [Theme::Dark]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ThemePalette {
+    #[default]
+    Heritage,
+    Ocean,
+    Forest,
+    Pastel,
+    Corporate,
+    Nord,
+}
+impl ThemePalette {
+    pub fn list() -> [Self; 6] {
+        [
+            Self::Heritage,
+            Self::Ocean,
+            Self::Forest,
+            Self::Pastel,
+            Self::Corporate,
+            Self::Nord,
+        ]
+    }
+    pub fn name(self) -> &'static str {
+        match self {
+            ThemePalette::Heritage => "Heritage",
+            ThemePalette::Ocean => "Ocean",
+            ThemePalette::Forest => "Forest",
+            ThemePalette::Pastel => "Pastel",
+            ThemePalette::Corporate => "Corporate",
+            ThemePalette::Nord => "Nord",
+        }
+    }
+    /// Name of the DaisyUI theme to use for the given [Theme]
+    ///
+    /// Every name returned here must be enabled in `input.css`
+    pub fn daisyui_theme(self, theme: Theme) -> &'static str {
+        match (self, theme) {
+            (ThemePalette::Heritage, Theme::Light) => "light",
+            (ThemePalette::Heritage, Theme::Dark) => "dark",
+            (ThemePalette::Ocean, Theme::Light) => "winter",
+            (ThemePalette::Ocean, Theme::Dark) => "night",
+            (ThemePalette::Forest, Theme::Light) => "emerald",
+            (ThemePalette::Forest, Theme::Dark) => "forest",
+            (ThemePalette::Pastel, Theme::Light) => "cupcake",
+            (ThemePalette::Pastel, Theme::Dark) => "dim",
+            (ThemePalette::Corporate, Theme::Light) => "corporate",
+            (ThemePalette::Corporate, Theme::Dark) => "business",
+            (ThemePalette::Nord, Theme::Light) => "nord",
+            (ThemePalette::Nord, Theme::Dark) => "sunset",
+        }
+    }
+}
+impl core::str::FromStr for ThemePalette {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::list()
+            .into_iter()
+            .find(|p| p.name() == s)
+            .ok_or(())
+    }
+}
+
+/// Customization of the look of the application on top of the [Theme]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ThemeCustomization {
+    pub palette: ThemePalette,
+    /// RGB color overriding the primary color of the palette
+    pub accent_color: Option<[u8; 3]>,
+    /// Ignore the [Theme] chosen by the user and follow the one of the operating system
+    pub follow_system: bool,
+}
+impl ThemeCustomization {
+    /// The accent color as a CSS hexadecimal color
+    pub fn accent_color_hex(&self) -> Option<String> {
+        self.accent_color
+            .map(|[r, g, b]| format!("#{r:02x}{g:02x}{b:02x}"))
+    }
+    /// Inline style to apply on the element where the customization takes effect
+    pub fn style(&self) -> String {
+        self.accent_color_hex()
+            .map(|hex| format!("--color-primary: {hex};"))
+            .unwrap_or_default()
+    }
+}
+impl DatabaseSingleItem for ThemeCustomization {
+    fn item_key() -> &'static str {
+        "gui_theme_customization"
+    }
+}
+
+pub static THEME_CUSTOMIZATION: GlobalSignal<ThemeCustomization> =
+    Signal::global(|| ThemeCustomization::default());
+
+/// Commands for the theme service
 #[derive(Debug)]
 pub(super) enum DarkModeCommand {
     /// Internal trigger a save into the DB
     Persist { theme: Theme },
+    /// Internal trigger a save of the customization into the DB
+    PersistCustomization {
+        customization: ThemeCustomization,
+    },
     /// Internal trigger a refresh from the DB
     Refresh,
 }
@@ -46,6 +143,8 @@ pub(super) fn use_theme_service(
 
             let mut cached_value = load_theme(database_service).await;
             *THEME.write() = cached_value;
+            let mut cached_customization = load_customization(database_service).await;
+            *THEME_CUSTOMIZATION.write() = cached_customization;
 
             // Flag to trash the first persist command that will come from the initial run of the "use_effect"
             let mut discard_next_persit = true;
@@ -78,9 +177,29 @@ pub(super) fn use_theme_service(
                             log::debug!("darkmode_service (coroutine) - Ignoring Persist cmd: already in database.");
                         }
                     }
+                    DarkModeCommand::PersistCustomization { customization } => {
+                        if customization != cached_customization {
+                            log::debug!(
+                                "darkmode_service (coroutine) - Customization changed, persisting..."
+                            );
+                            cached_customization = customization;
+
+                            match save_customization(database_service, customization).await {
+                                Ok(_) => (),
+                                Err(msg) => {
+                                    log::error!("{msg}");
+                                    alert_error(msg);
+                                }
+                            };
+                        } else {
+                            log::debug!("darkmode_service (coroutine) - Ignoring PersistCustomization cmd: already in database.");
+                        }
+                    }
                     DarkModeCommand::Refresh => {
                         cached_value = load_theme(database_service).await;
-                        *THEME.write() = cached_value
+                        *THEME.write() = cached_value;
+                        cached_customization = load_customization(database_service).await;
+                        *THEME_CUSTOMIZATION.write() = cached_customization;
                     }
                 }
 
@@ -94,13 +213,50 @@ pub(super) fn use_theme_service(
     use_effect(move || {
         service_handle.send(DarkModeCommand::Persist { theme: THEME() });
     });
+    use_effect(move || {
+        service_handle.send(DarkModeCommand::PersistCustomization {
+            customization: THEME_CUSTOMIZATION(),
+        });
+    });
+    // Follow the theme of the operating system, as seen by the webview
+    use_future(move || async move {
+        let mut eval = document::eval(
+            r#"
+            const query = window.matchMedia("(prefers-color-scheme: dark)");
+            dioxus.send(query.matches);
+            query.addEventListener("change", (e) => dioxus.send(e.matches));
+            await new Promise(() => {});
+            "#,
+        );
+        while let Ok(system_is_dark) = eval.recv::<bool>().await {
+            log::debug!("darkmode_service - System theme is dark: {system_is_dark}");
+            *SYSTEM_THEME.write() = Some(match system_is_dark {
+                true => Theme::Dark,
+                false => Theme::Light,
+            });
+        }
+    });
+    use_effect(move || {
+        if THEME_CUSTOMIZATION.read().follow_system {
+            if let Some(system_theme) = SYSTEM_THEME() {
+                if *THEME.peek() != system_theme {
+                    *THEME.write() = system_theme;
+                }
+            }
+        }
+    });
     service_handle
 }
 
-async fn load_theme(database_service: Coroutine<DatabaseCommand>) -> Theme {
-    let database = super::helpers::get_database(database_service).await;
+/// Theme of the operating system, unknown until the webview reports it
+static SYSTEM_THEME: GlobalSignal<Option<Theme>> = Signal::global(|| None);
 
-    match database.blocking_operation(|db| Theme::load(&db)).await {
+async fn load_theme(database_service: Coroutine<DatabaseCommand>) -> Theme {
+    match super::helpers::blocking_db_service_operation(database_service, "Load the theme", |db| {
+        Theme::load(&db)
+    })
+    .await
+    {
         Ok(theme) => theme,
         Err(_) => Theme::default(),
     }
@@ -110,9 +266,37 @@ async fn save_theme(
     database_service: Coroutine<DatabaseCommand>,
     theme: Theme,
 ) -> Result<(), String> {
-    let database = super::helpers::get_database(database_service).await;
-    database
-        .blocking_operation(move |mut db| theme.save(&mut db))
-        .await
-        .map_err(|e| e.to_string())
+    super::helpers::blocking_db_service_operation(
+        database_service,
+        "Save the theme",
+        move |mut db| theme.save(&mut db),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+async fn load_customization(database_service: Coroutine<DatabaseCommand>) -> ThemeCustomization {
+    match super::helpers::blocking_db_service_operation(
+        database_service,
+        "Load the theme customization",
+        |db| ThemeCustomization::load(&db),
+    )
+    .await
+    {
+        Ok(customization) => customization,
+        Err(_) => ThemeCustomization::default(),
+    }
+}
+
+async fn save_customization(
+    database_service: Coroutine<DatabaseCommand>,
+    customization: ThemeCustomization,
+) -> Result<(), String> {
+    super::helpers::blocking_db_service_operation(
+        database_service,
+        "Save the theme customization",
+        move |mut db| customization.save(&mut db),
+    )
+    .await
+    .map_err(|e| e.to_string())
 }
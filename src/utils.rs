@@ -59,6 +59,14 @@ pub fn timestamp_to_file_string(ts: u64) -> String {
         .to_string()
 }
 
+/// Escape a text to be embedded in the printable HTML documents
+pub fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
 use btc_heritage_wallet::btc_heritage::HeirConfig;
 pub fn heir_config_type_to_string(hc: &HeirConfig) -> &'static str {
     match hc {
@@ -83,6 +91,243 @@ pub fn is_psbt_fully_signed(psbt: &PartiallySignedTransaction) -> bool {
     psbt.inputs.iter().all(is_taproot_input_signed)
 }
 
+const DESCRIPTOR_INPUT_CHARSET: &str =
+    "0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+const DESCRIPTOR_CHECKSUM_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Compute the BIP-380 checksum of a descriptor, [None] if it contains invalid characters
+pub fn descriptor_checksum(descriptor: &str) -> Option<String> {
+    fn polymod(c: u64, val: u64) -> u64 {
+        const GENERATOR: [u64; 5] = [
+            0xf5dee51989,
+            0xa9fdca3312,
+            0x1bab10e32d,
+            0x3706b1677a,
+            0x644d626ffd,
+        ];
+        let c0 = c >> 35;
+        let mut c = ((c & 0x7ffffffff) << 5) ^ val;
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (c0 >> i) & 1 == 1 {
+                c ^= generator;
+            }
+        }
+        c
+    }
+
+    let mut c = 1;
+    let mut class = 0;
+    let mut class_count = 0;
+    for ch in descriptor.chars() {
+        let pos = DESCRIPTOR_INPUT_CHARSET.find(ch)? as u64;
+        c = polymod(c, pos & 31);
+        class = class * 3 + (pos >> 5);
+        class_count += 1;
+        if class_count == 3 {
+            c = polymod(c, class);
+            class = 0;
+            class_count = 0;
+        }
+    }
+    if class_count > 0 {
+        c = polymod(c, class);
+    }
+    for _ in 0..8 {
+        c = polymod(c, 0);
+    }
+    c ^= 1;
+    Some(
+        (0..8)
+            .map(|j| DESCRIPTOR_CHECKSUM_CHARSET[((c >> (5 * (7 - j))) & 31) as usize] as char)
+            .collect(),
+    )
+}
+
+use btc_heritage_wallet::bitcoin::{
+    address::NetworkUnchecked, bip32::ChildNumber, Address, Network,
+};
+/// Name of the family of networks sharing the address and key encodings of `network`
+fn network_family(network: Network) -> &'static str {
+    match network {
+        Network::Bitcoin => "mainnet",
+        Network::Regtest => "regtest",
+        _ => "testnet",
+    }
+}
+fn network_mismatch(what: &str, found: &str) -> String {
+    format!(
+        "This is a {found} {what} but the application runs on {}",
+        network_family(btc_heritage_wallet::btc_heritage::utils::bitcoin_network::get())
+    )
+}
+
+/// Ensure an address belongs to the network of the application
+pub fn check_address_network(address: Address<NetworkUnchecked>) -> Result<Address, String> {
+    let network = btc_heritage_wallet::btc_heritage::utils::bitcoin_network::get();
+    if address.is_valid_for_network(network) {
+        return Ok(address.assume_checked());
+    }
+    match [Network::Bitcoin, Network::Regtest, Network::Testnet]
+        .into_iter()
+        .find(|n| address.is_valid_for_network(*n))
+    {
+        Some(found) => Err(network_mismatch("address", network_family(found))),
+        None => Err(format!(
+            "This address is not valid on {}",
+            network_family(network)
+        )),
+    }
+}
+
+/// Ensure the extended keys (xpub, tpub, ...) found in a text, typically descriptors,
+/// belong to the network of the application
+///
+/// Texts without extended keys are accepted.
+pub fn check_keys_network(text: &str) -> Result<(), String> {
+    const MAINNET_PREFIXES: [&str; 10] = [
+        "xpub", "ypub", "zpub", "Ypub", "Zpub", "xprv", "yprv", "zprv", "Yprv", "Zprv",
+    ];
+    const TESTNET_PREFIXES: [&str; 10] = [
+        "tpub", "upub", "vpub", "Upub", "Vpub", "tprv", "uprv", "vprv", "Uprv", "Vprv",
+    ];
+    let mainnet_app =
+        btc_heritage_wallet::btc_heritage::utils::bitcoin_network::get() == Network::Bitcoin;
+    // Extended keys are 111 base58 characters long
+    let extended_keys = text
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|token| token.len() == 111);
+    for key in extended_keys {
+        let is_mainnet_key = MAINNET_PREFIXES.iter().any(|p| key.starts_with(p));
+        let is_testnet_key = TESTNET_PREFIXES.iter().any(|p| key.starts_with(p));
+        if mainnet_app && is_testnet_key {
+            return Err(network_mismatch("extended key", "testnet"));
+        }
+        if !mainnet_app && is_mainnet_key {
+            return Err(network_mismatch("extended key", "mainnet"));
+        }
+    }
+    Ok(())
+}
+
+/// Ensure the inputs of a PSBT belong to the network of the application,
+/// using the coin type of the BIP-32 derivation paths of their keys
+pub fn check_psbt_network(psbt: &PartiallySignedTransaction) -> Result<(), String> {
+    let mainnet_app =
+        btc_heritage_wallet::btc_heritage::utils::bitcoin_network::get() == Network::Bitcoin;
+    let expected_coin_type = if mainnet_app { 0 } else { 1 };
+    let key_sources = psbt.inputs.iter().flat_map(|input| {
+        input.bip32_derivation.values().chain(
+            input
+                .tap_key_origins
+                .values()
+                .map(|(_, key_source)| key_source),
+        )
+    });
+    for (_, path) in key_sources {
+        // m/purpose'/coin_type'/...
+        let path: &[ChildNumber] = path.as_ref();
+        if let Some(ChildNumber::Hardened { index: coin_type }) = path.get(1) {
+            if (*coin_type == 0 || *coin_type == 1) && *coin_type != expected_coin_type {
+                return Err(network_mismatch(
+                    "transaction",
+                    if *coin_type == 0 {
+                        "mainnet"
+                    } else {
+                        "testnet"
+                    },
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use ring::{
+    aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN},
+    pbkdf2,
+    rand::{SecureRandom, SystemRandom},
+};
+
+/// Prefix of the data produced by [encrypt_with_passphrase]
+pub const PASSPHRASE_ENCRYPTED_PREFIX: &str = "heritage-encrypted-v1:";
+const PASSPHRASE_SALT_LEN: usize = 16;
+const PASSPHRASE_PBKDF2_ITERATIONS: u32 = 600_000;
+
+fn passphrase_key(passphrase: &str, salt: &[u8]) -> LessSafeKey {
+    let mut key = [0u8; 32];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        core::num::NonZeroU32::new(PASSPHRASE_PBKDF2_ITERATIONS).expect("not zero"),
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    LessSafeKey::new(UnboundKey::new(&AES_256_GCM, &key).expect("valid key length"))
+}
+
+/// Encrypt `data` with a key derived from `passphrase` (PBKDF2-HMAC-SHA256 and AES-256-GCM)
+///
+/// The result is [PASSPHRASE_ENCRYPTED_PREFIX] followed by the base64 encoding
+/// of the salt, the nonce and the ciphertext.
+pub fn encrypt_with_passphrase(data: &str, passphrase: &str) -> Result<String, String> {
+    let rng = SystemRandom::new();
+    let mut salt = [0u8; PASSPHRASE_SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill(&mut salt)
+        .and_then(|_| rng.fill(&mut nonce))
+        .map_err(|_| log_error("Could not generate random bytes"))?;
+
+    let mut in_out = data.as_bytes().to_vec();
+    passphrase_key(passphrase, &salt)
+        .seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::empty(),
+            &mut in_out,
+        )
+        .map_err(|_| log_error("Encryption failed"))?;
+
+    let mut payload = Vec::with_capacity(salt.len() + nonce.len() + in_out.len());
+    payload.extend_from_slice(&salt);
+    payload.extend_from_slice(&nonce);
+    payload.extend_from_slice(&in_out);
+    Ok(format!(
+        "{PASSPHRASE_ENCRYPTED_PREFIX}{}",
+        BASE64.encode(payload)
+    ))
+}
+
+/// Decrypt data produced by [encrypt_with_passphrase]
+///
+/// Whitespaces are ignored, so the data can be copied from a printed sheet.
+pub fn decrypt_with_passphrase(data: &str, passphrase: &str) -> Result<String, String> {
+    let data = data
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>();
+    let payload = data
+        .strip_prefix(PASSPHRASE_ENCRYPTED_PREFIX)
+        .ok_or_else(|| "Not encrypted data".to_owned())?;
+    let payload = BASE64
+        .decode(payload)
+        .map_err(|e| format!("Invalid encrypted data: {e}"))?;
+    if payload.len() < PASSPHRASE_SALT_LEN + NONCE_LEN {
+        return Err("Invalid encrypted data: too short".to_owned());
+    }
+    let (salt, payload) = payload.split_at(PASSPHRASE_SALT_LEN);
+    let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+
+    let mut in_out = ciphertext.to_vec();
+    let plaintext = passphrase_key(passphrase, salt)
+        .open_in_place(
+            Nonce::try_assume_unique_for_key(nonce).expect("valid nonce length"),
+            Aad::empty(),
+            &mut in_out,
+        )
+        .map_err(|_| "Wrong passphrase or corrupted data".to_owned())?;
+    String::from_utf8(plaintext.to_vec()).map_err(|e| format!("Invalid decrypted data: {e}"))
+}
+
 // pub type CheapClone<T> = std::sync::Arc<T>;
 pub type CheapClone<T> = std::rc::Rc<T>;
 
@@ -13,9 +13,13 @@ use crate::{
             BlockchainProviderServiceStatus, LedgerServiceStatus, ServiceConnectButton,
             ServiceServiceStatus,
         },
+        alerts::AlertCategory,
+        copy::{CopyTextarea, CopyToClipboardButtonIcon},
         svg::{AlertOutline, DrawSvg, InfoCircleOutline},
     },
+    onboarding::Onboarding,
     utils::CCStr,
+    Route,
 };
 
 /// Application configuration view component
@@ -36,6 +40,24 @@ pub fn AppConfigView() -> Element {
                 // Application onboarding section
                 OnboardingConfigSection {}
 
+                // Start-on-login and background synchronization section
+                BackgroundSyncConfigSection {}
+
+                // Alerts display and do-not-disturb section
+                AlertRoutingConfigSection {}
+
+                // Log level and log files section
+                LogConfigSection {}
+
+                // Local JSON-RPC automation interface section
+                AutomationConfigSection {}
+
+                // Commands and webhooks run on monitoring events section
+                EventHooksConfigSection {}
+
+                // Theme customization section
+                ThemeConfigSection {}
+
                 // Heritage Service Configuration Section
                 HeritageServiceConfigSection {}
 
@@ -117,8 +139,7 @@ fn ApplicationConfigSection() -> Element {
                 alert_success("Database directory updated successfully");
             }
             Err(e) => {
-                log::error!("Failed to update database directory: {e}");
-                alert_error(format!("Failed to update database directory: {e}"));
+                alert_failure("Failed to update database directory", e);
             }
         }
         *updating.write() = false;
@@ -908,6 +929,7 @@ fn OnboardingConfigSection() -> Element {
                                 div { class: "text-sm text-gray-500 mt-2",
                                     "The onboarding process is currently guiding you in the app."
                                 }
+                                OnboardingInProgressControls {}
                             },
                             OnboardingStatus::Completed => rsx! {
                                 "Completed"
@@ -939,3 +961,762 @@ fn OnboardingConfigSection() -> Element {
         }
     }
 }
+
+/// Start-on-login and background synchronization section
+#[component]
+fn BackgroundSyncConfigSection() -> Element {
+    let mut sync_interval_str = use_signal(String::new);
+    let sync_interval = use_memo(move || sync_interval_str.read().parse::<u32>().ok());
+
+    // Initialize the input with current value
+    use_effect(move || {
+        *sync_interval_str.write() = state_management::BACKGROUND_SYNC_CONFIG
+            .read()
+            .sync_interval_minutes
+            .to_string()
+    });
+
+    let has_changes = use_memo(move || {
+        sync_interval().is_some_and(|interval| {
+            interval != state_management::BACKGROUND_SYNC_CONFIG.read().sync_interval_minutes
+        })
+    });
+
+    rsx! {
+        div { class: "card bg-base-200 shadow-xl",
+            div { class: "card-body",
+                h2 { class: "card-title", "Background Synchronization" }
+                p { class: "text-sm text-gray-600 mb-4",
+                    "Start the application when you log in and periodically synchronize your wallets while it runs,
+                    so that the application shows you the expiring Heritage Configurations even if you rarely open it yourself.
+                    The reminders are displayed in the application and its notification center, there are no system notifications."
+                }
+
+                div { class: "flex flex-col gap-4",
+                    label { class: "label cursor-pointer justify-start gap-4",
+                        input {
+                            r#type: "checkbox",
+                            class: "toggle toggle-primary",
+                            checked: state_management::BACKGROUND_SYNC_CONFIG.read().start_on_login,
+                            onchange: move |event| {
+                                state_management::BACKGROUND_SYNC_CONFIG.write().start_on_login = event
+                                    .checked();
+                            },
+                        }
+                        span { "Start the application when I log in" }
+                    }
+                    label { class: "label cursor-pointer justify-start gap-4",
+                        input {
+                            r#type: "checkbox",
+                            class: "toggle toggle-primary",
+                            disabled: !state_management::BACKGROUND_SYNC_CONFIG.read().start_on_login,
+                            checked: state_management::BACKGROUND_SYNC_CONFIG.read().start_minimized,
+                            onchange: move |event| {
+                                state_management::BACKGROUND_SYNC_CONFIG.write().start_minimized = event
+                                    .checked();
+                            },
+                        }
+                        span { "Start with the window minimized to the taskbar" }
+                    }
+                    fieldset { class: "fieldset w-64",
+                        legend { class: "fieldset-legend", "Synchronization interval (minutes)" }
+                        input {
+                            r#type: "number",
+                            class: "input",
+                            class: if sync_interval().is_none() { "input-error" },
+                            min: "0",
+                            value: "{sync_interval_str}",
+                            oninput: move |event| *sync_interval_str.write() = event.value(),
+                        }
+                        div { class: "label", "0 disables the background synchronization" }
+                    }
+                }
+
+                div { class: "card-actions justify-end mt-6",
+                    button {
+                        class: "btn btn-primary",
+                        disabled: !has_changes(),
+                        onclick: move |_| {
+                            if let Some(interval) = sync_interval() {
+                                state_management::BACKGROUND_SYNC_CONFIG.write().sync_interval_minutes = interval;
+                            }
+                        },
+                        "Update Interval"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Alerts display and do-not-disturb section
+#[component]
+fn AlertRoutingConfigSection() -> Element {
+    rsx! {
+        div { class: "card bg-base-200 shadow-xl",
+            div { class: "card-body",
+                h2 { class: "card-title", "Alerts" }
+                p { class: "text-sm text-gray-600 mb-4",
+                    "Choose which alerts pop up and which are only recorded in the notification center,
+                    and silence the pop-ups during the night."
+                }
+
+                div { class: "flex flex-col gap-4",
+                    for category in AlertCategory::ALL {
+                        fieldset { key: "{category.name()}", class: "fieldset w-md",
+                            legend { class: "fieldset-legend", {category.name()} }
+                            select {
+                                class: "select select-bordered",
+                                value: match state_management::ALERT_ROUTING_CONFIG.read().route(category) {
+                                    AlertRoute::Toast => "toast",
+                                    AlertRoute::NotificationCenter => "notification_center",
+                                },
+                                onchange: move |event| {
+                                    *state_management::ALERT_ROUTING_CONFIG.write().route_mut(category) = match event
+                                        .value()
+                                        .as_str()
+                                    {
+                                        "notification_center" => AlertRoute::NotificationCenter,
+                                        _ => AlertRoute::Toast,
+                                    };
+                                },
+                                option { value: "toast", "Pop-up and notification center" }
+                                option { value: "notification_center", "Notification center only" }
+                            }
+                        }
+                    }
+                    label { class: "label cursor-pointer justify-start gap-4",
+                        input {
+                            r#type: "checkbox",
+                            class: "toggle toggle-primary",
+                            checked: state_management::ALERT_ROUTING_CONFIG.read().do_not_disturb,
+                            onchange: move |event| {
+                                state_management::ALERT_ROUTING_CONFIG.write().do_not_disturb = event
+                                    .checked();
+                            },
+                        }
+                        span { "Do not disturb: no pop-up between these hours" }
+                    }
+                    div { class: "flex flex-row gap-4",
+                        fieldset { class: "fieldset w-32",
+                            legend { class: "fieldset-legend", "From (hour)" }
+                            input {
+                                r#type: "number",
+                                class: "input",
+                                min: "0",
+                                max: "23",
+                                disabled: !state_management::ALERT_ROUTING_CONFIG.read().do_not_disturb,
+                                value: state_management::ALERT_ROUTING_CONFIG.read().dnd_start_hour.to_string(),
+                                oninput: move |event| {
+                                    if let Some(hour) = event.value().parse::<u32>().ok().filter(|h| *h < 24) {
+                                        state_management::ALERT_ROUTING_CONFIG.write().dnd_start_hour = hour;
+                                    }
+                                },
+                            }
+                        }
+                        fieldset { class: "fieldset w-32",
+                            legend { class: "fieldset-legend", "To (hour)" }
+                            input {
+                                r#type: "number",
+                                class: "input",
+                                min: "0",
+                                max: "23",
+                                disabled: !state_management::ALERT_ROUTING_CONFIG.read().do_not_disturb,
+                                value: state_management::ALERT_ROUTING_CONFIG.read().dnd_end_hour.to_string(),
+                                oninput: move |event| {
+                                    if let Some(hour) = event.value().parse::<u32>().ok().filter(|h| *h < 24) {
+                                        state_management::ALERT_ROUTING_CONFIG.write().dnd_end_hour = hour;
+                                    }
+                                },
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Log level and log files section
+#[component]
+fn LogConfigSection() -> Element {
+    let mut filter = use_signal(String::new);
+
+    // Initialize the input with current value
+    use_effect(move || *filter.write() = state_management::LOG_CONFIG.read().filter.clone());
+
+    let has_changes =
+        use_memo(move || *filter.read() != state_management::LOG_CONFIG.read().filter);
+
+    let log_file_path = use_memo(move || {
+        // Follow the datadir changes
+        let _ = state_management::APPLICATION_CONFIG.read();
+        crate::logger::log_file_path(&LogConfig::log_dir())
+            .to_string_lossy()
+            .into_owned()
+    });
+
+    rsx! {
+        div { class: "card bg-base-200 shadow-xl",
+            div { class: "card-body",
+                h2 { class: "card-title", "Logs" }
+                p { class: "text-sm text-gray-600 mb-4",
+                    "Choose how verbose the application logs are and keep them in files,
+                    to help investigate an issue without restarting the application."
+                }
+
+                div { class: "flex flex-col gap-4",
+                    fieldset { class: "fieldset",
+                        legend { class: "fieldset-legend", "Log level" }
+                        div { class: "join",
+                            for level in ["error", "warn", "info", "debug"] {
+                                button {
+                                    key: "{level}",
+                                    class: "btn btn-sm join-item",
+                                    class: if filter.read().trim() == level { "btn-primary" },
+                                    onclick: move |_| *filter.write() = level.to_owned(),
+                                    "{level}"
+                                }
+                            }
+                        }
+                        input {
+                            r#type: "text",
+                            class: "input w-lg font-mono",
+                            placeholder: "warn",
+                            value: "{filter}",
+                            oninput: move |event| *filter.write() = event.value(),
+                        }
+                        div { class: "label",
+                            "Per-module levels use the RUST_LOG syntax, e.g. \"info,heritage_gui=debug\". Empty uses the RUST_LOG environment variable."
+                        }
+                    }
+                    label { class: "label cursor-pointer justify-start gap-4",
+                        input {
+                            r#type: "checkbox",
+                            class: "toggle toggle-primary",
+                            checked: state_management::LOG_CONFIG.read().log_to_file,
+                            onchange: move |event| {
+                                state_management::LOG_CONFIG.write().log_to_file = event.checked();
+                            },
+                        }
+                        span { "Write the logs into rotating files" }
+                    }
+                    if state_management::LOG_CONFIG.read().log_to_file {
+                        div { class: "text-sm",
+                            "Current log file: "
+                            span { class: "font-mono break-all", "{log_file_path}" }
+                        }
+                    }
+                }
+
+                div { class: "card-actions justify-end mt-6",
+                    button {
+                        class: "btn btn-outline",
+                        onclick: move |_| {
+                            navigator().push(Route::DiagnosticsView {});
+                        },
+                        "Open Diagnostics"
+                    }
+                    button {
+                        class: "btn btn-primary",
+                        disabled: !has_changes(),
+                        onclick: move |_| {
+                            state_management::LOG_CONFIG.write().filter = filter.read().trim().to_owned();
+                        },
+                        "Update Log Level"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Local JSON-RPC automation interface section
+#[component]
+fn AutomationConfigSection() -> Element {
+    let mut port_str = use_signal(String::new);
+    let port = use_memo(move || port_str.read().parse::<u16>().ok().filter(|port| *port > 0));
+
+    // Initialize the input with current value
+    use_effect(move || {
+        *port_str.write() = state_management::AUTOMATION_CONFIG.read().port.to_string()
+    });
+
+    let has_changes = use_memo(move || {
+        port().is_some_and(|port| port != state_management::AUTOMATION_CONFIG.read().port)
+    });
+
+    let token =
+        use_memo(move || CCStr::from(state_management::AUTOMATION_CONFIG.read().token.as_str()));
+    let curl_example = use_memo(move || {
+        let config = state_management::AUTOMATION_CONFIG.read();
+        CCStr::from(format!(
+            "curl -s http://127.0.0.1:{}/ -H 'Authorization: Bearer {}' \\\n  \
+            -d '{{\"jsonrpc\": \"2.0\", \"id\": 1, \"method\": \"getexpirations\"}}'",
+            config.port, config.token
+        ))
+    });
+
+    rsx! {
+        div { class: "card bg-base-200 shadow-xl",
+            div { class: "card-body",
+                h2 { class: "card-title", "Automation Interface" }
+                p { class: "text-sm text-gray-600 mb-4",
+                    "Expose a local JSON-RPC interface so that your scripts or home-automation system can monitor
+                    your wallets: listwallets, getwalletstatus, listheritageconfigs and getexpirations.
+                    It only listens on this computer (127.0.0.1) and requires the access token."
+                }
+
+                div { class: "flex flex-col gap-4",
+                    label { class: "label cursor-pointer justify-start gap-4",
+                        input {
+                            r#type: "checkbox",
+                            class: "toggle toggle-primary",
+                            checked: state_management::AUTOMATION_CONFIG.read().enabled,
+                            onchange: move |event| {
+                                state_management::AUTOMATION_CONFIG.write().enabled = event.checked();
+                            },
+                        }
+                        span { "Enable the automation interface" }
+                    }
+                    label { class: "label cursor-pointer justify-start gap-4",
+                        input {
+                            r#type: "checkbox",
+                            class: "toggle toggle-warning",
+                            checked: state_management::AUTOMATION_CONFIG.read().allow_actions,
+                            onchange: move |event| {
+                                state_management::AUTOMATION_CONFIG.write().allow_actions = event.checked();
+                            },
+                        }
+                        span {
+                            "Also allow actions: syncwallet and createpsbt (unsigned transactions only)"
+                        }
+                    }
+                    fieldset { class: "fieldset w-64",
+                        legend { class: "fieldset-legend", "Port" }
+                        input {
+                            r#type: "number",
+                            class: "input",
+                            class: if port().is_none() { "input-error" },
+                            min: "1",
+                            max: "65535",
+                            value: "{port_str}",
+                            oninput: move |event| *port_str.write() = event.value(),
+                        }
+                    }
+                    fieldset { class: "fieldset",
+                        legend { class: "fieldset-legend", "Access Token" }
+                        div { class: "flex flex-row gap-4 items-center",
+                            span { class: "font-mono", "{token}" }
+                            CopyToClipboardButtonIcon { value: token() }
+                            button {
+                                class: "btn btn-sm btn-outline",
+                                onclick: move |_| {
+                                    state_management::AUTOMATION_CONFIG.write().token = AutomationConfig::new_token();
+                                },
+                                "Regenerate"
+                            }
+                        }
+                        div { class: "label",
+                            "Send it in the Authorization header of the requests, as a Bearer token"
+                        }
+                    }
+                    fieldset { class: "fieldset",
+                        legend { class: "fieldset-legend", "Example" }
+                        CopyTextarea { value: curl_example(), rows: 2 }
+                    }
+                }
+
+                div { class: "card-actions justify-end mt-6",
+                    button {
+                        class: "btn btn-primary",
+                        disabled: !has_changes(),
+                        onclick: move |_| {
+                            if let Some(port) = port() {
+                                state_management::AUTOMATION_CONFIG.write().port = port;
+                            }
+                        },
+                        "Update Port"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Commands and webhooks run on monitoring events section
+#[component]
+fn EventHooksConfigSection() -> Element {
+    let mut event_id = use_signal(|| MonitoringEventKind::ALL[0].id());
+    let mut is_webhook = use_signal(|| false);
+    let mut target = use_signal(String::new);
+
+    let new_hook = use_memo(move || {
+        let target = target.read().trim().to_owned();
+        if target.is_empty() {
+            return None;
+        }
+        let event = MonitoringEventKind::ALL
+            .into_iter()
+            .find(|kind| kind.id() == event_id())?;
+        let action = if is_webhook() {
+            match EventHookAction::webhook(&target) {
+                Ok(action) => action,
+                Err(e) => return Some(Err(e)),
+            }
+        } else {
+            EventHookAction::Command(target)
+        };
+        Some(Ok(EventHook {
+            event,
+            action,
+            enabled: true,
+        }))
+    });
+
+    rsx! {
+        div { class: "card bg-base-200 shadow-xl",
+            div { class: "card-body",
+                h2 { class: "card-title", "External Hooks" }
+                p { class: "text-sm text-gray-600 mb-4",
+                    "Run a command or call a webhook when the background synchronization detects an event,
+                    to integrate with ntfy, Matrix or your home monitoring. Commands receive the event in the
+                    HERITAGE_EVENT, HERITAGE_WALLET and HERITAGE_MESSAGE environment variables, webhooks receive
+                    a JSON object with the event, wallet, message and timestamp fields."
+                }
+
+                if state_management::EVENT_HOOKS_CONFIG.read().hooks.is_empty() {
+                    div { class: "text-base font-light", "No hook configured." }
+                } else {
+                    table { class: "table",
+                        thead {
+                            tr {
+                                th { "Enabled" }
+                                th { "Event" }
+                                th { "Action" }
+                                th {}
+                            }
+                        }
+                        tbody {
+                            for (index, hook) in state_management::EVENT_HOOKS_CONFIG.read().hooks.iter().cloned().enumerate() {
+                                tr { key: "{index}",
+                                    td {
+                                        input {
+                                            r#type: "checkbox",
+                                            class: "toggle toggle-primary toggle-sm",
+                                            checked: hook.enabled,
+                                            onchange: move |event| {
+                                                state_management::EVENT_HOOKS_CONFIG.write().hooks[index].enabled = event
+                                                    .checked();
+                                            },
+                                        }
+                                    }
+                                    td { {hook.event.name()} }
+                                    td { class: "font-mono break-all",
+                                        match hook.action {
+                                            EventHookAction::Command(command) => rsx! { "$ {command}" },
+                                            EventHookAction::Webhook(url) => rsx! { "POST {url}" },
+                                        }
+                                    }
+                                    td {
+                                        button {
+                                            class: "btn btn-sm btn-outline btn-error",
+                                            onclick: move |_| {
+                                                state_management::EVENT_HOOKS_CONFIG.write().hooks.remove(index);
+                                            },
+                                            "Remove"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                div { class: "flex flex-row flex-wrap gap-4 items-end mt-4",
+                    fieldset { class: "fieldset",
+                        legend { class: "fieldset-legend", "Event" }
+                        select {
+                            class: "select select-bordered",
+                            value: event_id(),
+                            onchange: move |event| {
+                                if let Some(kind) = MonitoringEventKind::ALL
+                                    .into_iter()
+                                    .find(|kind| kind.id() == event.value())
+                                {
+                                    *event_id.write() = kind.id();
+                                }
+                            },
+                            for kind in MonitoringEventKind::ALL {
+                                option { key: "{kind.id()}", value: kind.id(), {kind.name()} }
+                            }
+                        }
+                    }
+                    fieldset { class: "fieldset",
+                        legend { class: "fieldset-legend", "Action" }
+                        select {
+                            class: "select select-bordered",
+                            value: if is_webhook() { "webhook" } else { "command" },
+                            onchange: move |event| *is_webhook.write() = event.value() == "webhook",
+                            option { value: "command", "Run a command" }
+                            option { value: "webhook", "Call a webhook" }
+                        }
+                    }
+                    fieldset { class: "fieldset grow",
+                        legend { class: "fieldset-legend",
+                            if is_webhook() {
+                                "Webhook URL"
+                            } else {
+                                "Command"
+                            }
+                        }
+                        input {
+                            r#type: "text",
+                            class: "input w-full font-mono",
+                            placeholder: if is_webhook() { "https://ntfy.sh/my-topic" } else { "notify-send \"$HERITAGE_MESSAGE\"" },
+                            value: "{target}",
+                            oninput: move |event| *target.write() = event.value(),
+                        }
+                    }
+                }
+                if let Some(Err(e)) = new_hook() {
+                    div { class: "text-sm text-error", "{e}" }
+                }
+
+                div { class: "card-actions justify-end mt-6",
+                    button {
+                        class: "btn btn-primary",
+                        disabled: !matches!(new_hook(), Some(Ok(_))),
+                        onclick: move |_| {
+                            if let Some(Ok(hook)) = new_hook() {
+                                state_management::EVENT_HOOKS_CONFIG.write().hooks.push(hook);
+                                target.write().clear();
+                            }
+                        },
+                        "Add Hook"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Theme customization section, with a live preview of the pending changes
+#[component]
+fn ThemeConfigSection() -> Element {
+    let mut draft = use_signal(ThemeCustomization::default);
+
+    // Initialize the draft with current value
+    use_effect(move || *draft.write() = state_management::THEME_CUSTOMIZATION());
+
+    let has_changes = use_memo(move || draft() != state_management::THEME_CUSTOMIZATION());
+    let preview_theme = use_memo(move || draft.read().palette.daisyui_theme(state_management::THEME()));
+
+    rsx! {
+        div { class: "card bg-base-200 shadow-xl",
+            div { class: "card-body",
+                h2 { class: "card-title", "Appearance" }
+                p { class: "text-sm text-gray-600 mb-4",
+                    "Choose the color palette of the application and optionally override its accent color.
+                    Changes are previewed below before being applied."
+                }
+
+                div { class: "flex flex-row flex-wrap gap-8",
+                    div { class: "flex flex-col gap-4",
+                        fieldset { class: "fieldset w-64",
+                            legend { class: "fieldset-legend", "Palette" }
+                            select {
+                                class: "select select-bordered",
+                                onchange: move |event| {
+                                    if let Ok(palette) = event.value().parse() {
+                                        draft.write().palette = palette;
+                                    }
+                                },
+                                for palette in ThemePalette::list() {
+                                    option {
+                                        value: palette.name(),
+                                        selected: draft.read().palette == palette,
+                                        {palette.name()}
+                                    }
+                                }
+                            }
+                        }
+                        div { class: "flex flex-row gap-4 items-center",
+                            label { class: "label cursor-pointer justify-start gap-4",
+                                input {
+                                    r#type: "checkbox",
+                                    class: "toggle toggle-primary",
+                                    checked: draft.read().accent_color.is_some(),
+                                    onchange: move |event| {
+                                        draft.write().accent_color = event.checked().then_some([0xa6, 0x1d, 0x1d]);
+                                    },
+                                }
+                                span { "Custom accent color" }
+                            }
+                            if let Some(accent_color_hex) = draft.read().accent_color_hex() {
+                                input {
+                                    r#type: "color",
+                                    value: "{accent_color_hex}",
+                                    oninput: move |event| {
+                                        if let Some(color) = parse_hex_color(&event.value()) {
+                                            draft.write().accent_color = Some(color);
+                                        }
+                                    },
+                                }
+                            }
+                        }
+                        label { class: "label cursor-pointer justify-start gap-4",
+                            input {
+                                r#type: "checkbox",
+                                class: "toggle toggle-primary",
+                                checked: draft.read().follow_system,
+                                onchange: move |event| draft.write().follow_system = event.checked(),
+                            }
+                            span { "Follow the system light/dark theme" }
+                        }
+                    }
+
+                    div {
+                        class: "grow flex flex-col gap-2 p-4 rounded-box bg-base-100 text-base-content",
+                        "data-theme": preview_theme(),
+                        style: draft.read().style(),
+                        div { class: "font-bold", "Preview" }
+                        div { class: "flex flex-row flex-wrap gap-2",
+                            button { class: "btn btn-primary btn-sm", "Primary" }
+                            button { class: "btn btn-secondary btn-sm", "Secondary" }
+                            button { class: "btn btn-accent btn-sm", "Accent" }
+                            button { class: "btn btn-outline btn-sm", "Outline" }
+                        }
+                        div { class: "flex flex-row flex-wrap gap-2",
+                            span { class: "badge badge-success", "Success" }
+                            span { class: "badge badge-info", "Info" }
+                            span { class: "badge badge-warning", "Warning" }
+                            span { class: "badge badge-error", "Error" }
+                        }
+                        progress { class: "progress progress-primary", value: "60", max: "100" }
+                        div { class: "text-sm text-primary", "Heritage Wallet" }
+                    }
+                }
+
+                div { class: "card-actions justify-end mt-6",
+                    button {
+                        class: "btn btn-outline",
+                        disabled: draft() == ThemeCustomization::default(),
+                        onclick: move |_| *draft.write() = ThemeCustomization::default(),
+                        "Reset to Defaults"
+                    }
+                    button {
+                        class: "btn btn-outline",
+                        disabled: !has_changes(),
+                        onclick: move |_| *draft.write() = state_management::THEME_CUSTOMIZATION(),
+                        "Reset to Current"
+                    }
+                    button {
+                        class: "btn btn-primary",
+                        disabled: !has_changes(),
+                        onclick: move |_| *state_management::THEME_CUSTOMIZATION.write() = draft(),
+                        "Apply"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Parse a CSS `#rrggbb` color, as returned by a color input
+fn parse_hex_color(s: &str) -> Option<[u8; 3]> {
+    let hex = s.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let component = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    Some([component(0)?, component(2)?, component(4)?])
+}
+
+/// Controls to restart a chapter or skip a step of an in-progress onboarding
+#[component]
+fn OnboardingInProgressControls() -> Element {
+    let restartable_chapters = use_memo(|| match &*state_management::ONBOARDING_STATUS.read() {
+        OnboardingStatus::InProgress(onboarding) => onboarding.restartable_chapters(),
+        _ => vec![],
+    });
+    let current_chapter = use_memo(|| match &*state_management::ONBOARDING_STATUS.read() {
+        OnboardingStatus::InProgress(onboarding) => onboarding.current_chapter(),
+        _ => None,
+    });
+    let can_skip_step = use_memo(|| match &*state_management::ONBOARDING_STATUS.read() {
+        OnboardingStatus::InProgress(onboarding) => onboarding.can_skip_step(),
+        _ => false,
+    });
+    let mut selected_chapter_idx = use_signal(|| 0usize);
+    // Default the selection on the current chapter
+    use_effect(move || {
+        let current_chapter = current_chapter();
+        *selected_chapter_idx.write() = restartable_chapters
+            .read()
+            .iter()
+            .position(|c| Some(*c) == current_chapter)
+            .unwrap_or_default();
+    });
+
+    // Modify the in-progress onboarding and bring the user where the onboarding now expects the user
+    let update_onboarding = move |f: &dyn Fn(&mut Onboarding)| {
+        let next_route = match &mut *state_management::ONBOARDING_STATUS.write() {
+            OnboardingStatus::InProgress(onboarding) => {
+                f(onboarding);
+                (!onboarding.finished()).then(|| onboarding.current_route())
+            }
+            _ => None,
+        };
+        if let Some(next_route) = next_route {
+            navigator().push(next_route);
+        }
+    };
+
+    rsx! {
+        div { class: "flex flex-col gap-2 mt-4",
+            if let Some(current_chapter) = current_chapter() {
+                div { class: "text-sm",
+                    "Current chapter: "
+                    span { class: "font-bold", {current_chapter.name()} }
+                }
+            }
+            div { class: "flex flex-row gap-2 items-center",
+                select {
+                    class: "select select-bordered",
+                    onchange: move |event| {
+                        if let Ok(idx) = event.value().parse() {
+                            *selected_chapter_idx.write() = idx;
+                        }
+                    },
+                    for (idx , chapter) in restartable_chapters().into_iter().enumerate() {
+                        option {
+                            value: "{idx}",
+                            selected: idx == selected_chapter_idx(),
+                            {chapter.name()}
+                        }
+                    }
+                }
+                button {
+                    class: "btn btn-outline",
+                    disabled: restartable_chapters.read().is_empty(),
+                    onclick: move |_| {
+                        let chapter = restartable_chapters.read().get(selected_chapter_idx()).cloned();
+                        if let Some(chapter) = chapter {
+                            update_onboarding(&|onboarding| {
+                                onboarding.restart_chapter(chapter);
+                            });
+                        }
+                    },
+                    "Restart chapter"
+                }
+                button {
+                    class: "btn btn-outline",
+                    disabled: !can_skip_step(),
+                    onclick: move |_| update_onboarding(&|onboarding| onboarding.skip_step()),
+                    "Skip current step"
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,478 @@
+use crate::prelude::*;
+
+use std::collections::HashSet;
+
+use btc_heritage_wallet::{
+    btc_heritage::{bitcoincore_rpc::jsonrpc::serde_json, HeritageWalletBackup},
+    AnyOnlineWallet, OnlineWallet, Wallet,
+};
+
+use crate::{
+    components::inputs::FileInput,
+    utils::{
+        check_keys_network, decrypt_with_passphrase, descriptor_checksum, log_error, CCStr,
+        PASSPHRASE_ENCRYPTED_PREFIX,
+    },
+    Route,
+};
+
+/// Outcome of one of the verifications of a backup
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum CheckOutcome {
+    Passed(CCStr),
+    Failed(CCStr),
+    Skipped(CCStr),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct BackupCheck {
+    pub(super) title: &'static str,
+    pub(super) outcome: CheckOutcome,
+}
+impl BackupCheck {
+    pub(super) fn passed(title: &'static str, message: impl Into<CCStr>) -> Self {
+        Self {
+            title,
+            outcome: CheckOutcome::Passed(message.into()),
+        }
+    }
+    pub(super) fn failed(title: &'static str, message: impl Into<CCStr>) -> Self {
+        Self {
+            title,
+            outcome: CheckOutcome::Failed(message.into()),
+        }
+    }
+    pub(super) fn skipped(title: &'static str, message: impl Into<CCStr>) -> Self {
+        Self {
+            title,
+            outcome: CheckOutcome::Skipped(message.into()),
+        }
+    }
+}
+
+/// External and change descriptors of each Heritage Configuration of a backup
+pub(super) fn backup_descriptors(
+    backup: &HeritageWalletBackup,
+) -> Result<Vec<(String, String)>, String> {
+    let backup = serde_json::to_value(backup).map_err(log_error)?;
+    backup
+        .as_array()
+        .ok_or_else(|| "Unexpected descriptors backup format".to_owned())?
+        .iter()
+        .map(|subwallet| {
+            let descriptor = |field: &str| {
+                subwallet[field]
+                    .as_str()
+                    .map(str::to_owned)
+                    .ok_or_else(|| format!("Missing {field} in backup"))
+            };
+            Ok((
+                descriptor("external_descriptor")?,
+                descriptor("change_descriptor")?,
+            ))
+        })
+        .collect()
+}
+
+/// Verify the BIP-380 checksum of every descriptor of the backup
+fn verify_checksums(descriptors: &[(String, String)]) -> BackupCheck {
+    const TITLE: &str = "Descriptor checksums";
+    let mut count = 0;
+    for descriptor in descriptors
+        .iter()
+        .flat_map(|(external, change)| [external, change])
+    {
+        let Some((descriptor, checksum)) = descriptor.split_once('#') else {
+            return BackupCheck::failed(TITLE, format!("Missing checksum: {descriptor}"));
+        };
+        if descriptor_checksum(descriptor).as_deref() != Some(checksum) {
+            return BackupCheck::failed(
+                TITLE,
+                format!("Invalid checksum: {descriptor}#{checksum}"),
+            );
+        }
+        count += 1;
+    }
+    BackupCheck::passed(TITLE, format!("{count} descriptor checksum(s) verified"))
+}
+
+/// Verify the backup against the current state of a wallet of the application
+async fn verify_against_wallet(
+    wallet: Wallet,
+    backup: &HeritageWalletBackup,
+    descriptors: &[(String, String)],
+) -> Vec<BackupCheck> {
+    const FINGERPRINT_TITLE: &str = "Wallet fingerprint";
+    const COVERAGE_TITLE: &str = "Wallet Heritage Configurations";
+    let wallet_name = wallet.name();
+
+    let mut checks = vec![];
+    match (wallet.fingerprint(), backup.fingerprint()) {
+        (Ok(wallet_fingerprint), Ok(Some(backup_fingerprint)))
+            if wallet_fingerprint == backup_fingerprint =>
+        {
+            checks.push(BackupCheck::passed(
+                FINGERPRINT_TITLE,
+                format!("The backup belongs to wallet '{wallet_name}' ({wallet_fingerprint})"),
+            ))
+        }
+        (Ok(wallet_fingerprint), Ok(Some(backup_fingerprint))) => {
+            checks.push(BackupCheck::failed(
+                FINGERPRINT_TITLE,
+                format!(
+                    "The backup fingerprint {backup_fingerprint} differs \
+                    from the fingerprint {wallet_fingerprint} of wallet '{wallet_name}'"
+                ),
+            ));
+            return checks;
+        }
+        (Err(e), _) => {
+            checks.push(BackupCheck::failed(FINGERPRINT_TITLE, e.to_string()));
+            return checks;
+        }
+        (_, _) => {
+            checks.push(BackupCheck::failed(
+                FINGERPRINT_TITLE,
+                "Cannot extract the fingerprint of the backup",
+            ));
+            return checks;
+        }
+    }
+
+    if matches!(wallet.online_wallet(), AnyOnlineWallet::None) {
+        checks.push(BackupCheck::skipped(
+            COVERAGE_TITLE,
+            "The wallet has no online wallet to compare the descriptors with",
+        ));
+        return checks;
+    }
+    let current_descriptors = match wallet.backup_descriptors().await {
+        Ok(current_backup) => backup_descriptors(&current_backup),
+        Err(e) => Err(log_error(e)),
+    };
+    let current_descriptors = match current_descriptors {
+        Ok(current_descriptors) => current_descriptors,
+        Err(e) => {
+            checks.push(BackupCheck::failed(COVERAGE_TITLE, e));
+            return checks;
+        }
+    };
+    let backed_up = descriptors
+        .iter()
+        .map(|(external, _)| external.split('#').next().unwrap_or_default())
+        .collect::<HashSet<_>>();
+    let missing = current_descriptors
+        .iter()
+        .filter(|(external, _)| !backed_up.contains(external.split('#').next().unwrap_or_default()))
+        .count();
+    let total = current_descriptors.len();
+    checks.push(if missing == 0 {
+        BackupCheck::passed(
+            COVERAGE_TITLE,
+            format!("The backup covers the {total} Heritage Configuration(s) of the wallet"),
+        )
+    } else {
+        BackupCheck::failed(
+            COVERAGE_TITLE,
+            format!(
+                "The backup misses {missing} of the {total} Heritage Configuration(s) of the wallet, \
+                export a fresh backup"
+            ),
+        )
+    });
+    checks
+}
+
+/// Tool view verifying a previously exported descriptors backup without restoring it,
+/// so the recovery material can be tested periodically
+#[component]
+pub fn BackupCheckView() -> Element {
+    log::debug!("BackupCheckView Rendered");
+
+    let database_service = state_management::use_database_service();
+    let service_client_service = state_management::use_service_client_service();
+    let blockchain_provider_service = state_management::use_blockchain_provider_service();
+
+    let wallet_names = use_resource(move || async move {
+        state_management::list_wallet_names(database_service)
+            .await
+            .unwrap_or_default()
+    });
+
+    let mut backup_data = use_signal(String::new);
+    let mut passphrase = use_signal(String::new);
+    let mut wallet_name = use_signal(|| None::<CCStr>);
+    let mut checks = use_signal(Vec::<BackupCheck>::new);
+    let mut verifying = use_signal(|| false);
+
+    let is_encrypted = use_memo(move || {
+        backup_data
+            .read()
+            .trim_start()
+            .starts_with(PASSPHRASE_ENCRYPTED_PREFIX)
+    });
+
+    let verify = move |_| async move {
+        *verifying.write() = true;
+        checks.write().clear();
+
+        let data = if is_encrypted() {
+            const TITLE: &str = "Decryption";
+            let data = backup_data();
+            let passphrase = passphrase();
+            match tokio::task::spawn_blocking(move || decrypt_with_passphrase(&data, &passphrase))
+                .await
+                .map_err(log_error)
+                .and_then(|decrypted| decrypted)
+            {
+                Ok(decrypted) => {
+                    checks
+                        .write()
+                        .push(BackupCheck::passed(TITLE, "Decrypted with the passphrase"));
+                    decrypted
+                }
+                Err(e) => {
+                    checks.write().push(BackupCheck::failed(TITLE, e));
+                    *verifying.write() = false;
+                    return;
+                }
+            }
+        } else {
+            checks.write().push(BackupCheck::skipped(
+                "Decryption",
+                "The backup is not encrypted",
+            ));
+            backup_data()
+        };
+
+        let backup = match serde_json::from_str::<HeritageWalletBackup>(&data) {
+            Ok(backup) => {
+                checks.write().push(BackupCheck::passed(
+                    "Format",
+                    format!(
+                        "Heritage descriptors backup with {} Heritage Configuration(s)",
+                        backup.iter().count()
+                    ),
+                ));
+                backup
+            }
+            Err(e) => {
+                checks
+                    .write()
+                    .push(BackupCheck::failed("Format", e.to_string()));
+                *verifying.write() = false;
+                return;
+            }
+        };
+
+        checks.write().push(match check_keys_network(&data) {
+            Ok(_) => {
+                BackupCheck::passed("Network", "The keys match the network of the application")
+            }
+            Err(e) => BackupCheck::failed("Network", e),
+        });
+
+        let descriptors = match backup_descriptors(&backup) {
+            Ok(descriptors) => descriptors,
+            Err(e) => {
+                checks
+                    .write()
+                    .push(BackupCheck::failed("Descriptor checksums", e));
+                *verifying.write() = false;
+                return;
+            }
+        };
+        checks.write().push(verify_checksums(&descriptors));
+
+        checks.write().push(match backup.fingerprint() {
+            Ok(Some(fingerprint)) => BackupCheck::passed(
+                "Fingerprint",
+                format!("All the descriptors use the seed {fingerprint}"),
+            ),
+            Ok(None) => BackupCheck::failed("Fingerprint", "The backup has no descriptor"),
+            Err(e) => BackupCheck::failed("Fingerprint", e.to_string()),
+        });
+
+        match wallet_name() {
+            Some(wallet_name) => {
+                match state_management::get_wallet(
+                    database_service,
+                    service_client_service,
+                    blockchain_provider_service,
+                    wallet_name,
+                )
+                .await
+                {
+                    Ok(wallet) => {
+                        let wallet_checks =
+                            verify_against_wallet(wallet, &backup, &descriptors).await;
+                        checks.write().extend(wallet_checks);
+                    }
+                    Err(e) => checks
+                        .write()
+                        .push(BackupCheck::failed("Wallet fingerprint", e)),
+                }
+            }
+            None => checks.write().push(BackupCheck::skipped(
+                "Wallet fingerprint",
+                "Select a wallet to compare the backup with",
+            )),
+        }
+        *verifying.write() = false;
+    };
+
+    let all_passed = use_memo(move || {
+        let checks = checks.read();
+        !checks.is_empty()
+            && checks
+                .iter()
+                .all(|check| !matches!(check.outcome, CheckOutcome::Failed(_)))
+    });
+
+    use_drop(|| log::debug!("BackupCheckView Dropped"));
+
+    rsx! {
+        super::TitledView {
+            title: CCStr::from("Backup Check"),
+            subtitle: CCStr::from("Verify a descriptors backup without restoring it."),
+            right: rsx! {
+                div { class: "flex flex-row gap-2",
+                    Link {
+                        class: "btn btn-outline",
+                        to: Route::RecoveryRehearsalView {},
+                        "Recovery Rehearsal"
+                    }
+                    Link { class: "btn btn-outline", to: Route::XPubToolView {}, "Extended Key Tool" }
+                }
+            },
+            div { class: "container mx-auto px-8 space-y-8",
+                div { class: "text-base font-light",
+                    "A backup is only useful if it can be read when you need it. Open a descriptors backup
+                    you exported earlier, encrypted or not, to verify its integrity and that it still
+                    covers all the Heritage Configurations of its wallet. Nothing is restored or modified."
+                }
+                div { class: "flex flex-col",
+                    fieldset { class: "fieldset",
+                        legend { class: "fieldset-legend", "Backup File" }
+                        FileInput {
+                            accept: ".txt,.json",
+                            onchange: move |evt: Event<FormData>| async move {
+                                if let Some(file_engine) = evt.files().clone() {
+                                    for file in file_engine.files() {
+                                        backup_data
+                                            .set(
+                                                file_engine.read_file_to_string(&file).await.unwrap_or_default(),
+                                            );
+                                        checks.write().clear();
+                                    }
+                                }
+                            },
+                        }
+                    }
+                    fieldset { class: "fieldset w-full",
+                        legend { class: "fieldset-legend", "Backup Data" }
+                        textarea {
+                            class: "textarea textarea-bordered font-mono text-xs w-full",
+                            rows: "8",
+                            placeholder: "Paste your backup here...",
+                            value: backup_data(),
+                            oninput: move |evt| {
+                                backup_data.set(evt.value());
+                                checks.write().clear();
+                            },
+                        }
+                    }
+                    if is_encrypted() {
+                        fieldset { class: "fieldset",
+                            legend { class: "fieldset-legend", "Backup Passphrase" }
+                            input {
+                                r#type: "password",
+                                class: "input",
+                                value: passphrase(),
+                                oninput: move |evt| passphrase.set(evt.value()),
+                            }
+                        }
+                    }
+                    fieldset { class: "fieldset",
+                        legend { class: "fieldset-legend", "Compare with wallet" }
+                        select {
+                            class: "select",
+                            onchange: move |evt| {
+                                let value = evt.value();
+                                wallet_name.set((!value.is_empty()).then(|| CCStr::from(value)));
+                            },
+                            option { value: "", selected: wallet_name().is_none(), "None" }
+                            for name in wallet_names.cloned().unwrap_or_default() {
+                                option {
+                                    value: "{name}",
+                                    selected: wallet_name().as_ref() == Some(&name),
+                                    "{name}"
+                                }
+                            }
+                        }
+                    }
+                }
+                div { class: "flex justify-center",
+                    button {
+                        class: "btn btn-primary",
+                        disabled: backup_data.read().trim().is_empty() || verifying(),
+                        onclick: verify,
+                        if verifying() {
+                            span { class: "loading loading-spinner" }
+                            "Verifying..."
+                        } else {
+                            "Verify"
+                        }
+                    }
+                }
+                if !checks.read().is_empty() {
+                    div { class: "rounded-box border border-base-content/5 shadow-md p-4",
+                        h2 { class: "text-2xl font-bold mb-4", "Verification Report" }
+                        if !verifying() {
+                            if all_passed() {
+                                div { class: "alert alert-success mb-4",
+                                    "The backup passed all the verifications."
+                                }
+                            } else {
+                                div { class: "alert alert-error mb-4",
+                                    "The backup failed a verification, keep a fresh backup at hand."
+                                }
+                            }
+                        }
+                        CheckList { checks: checks() }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// List of the verifications of a backup with their outcome
+#[component]
+pub(super) fn CheckList(checks: Vec<BackupCheck>) -> Element {
+    rsx! {
+        ul { class: "flex flex-col gap-2",
+            for check in checks {
+                li { class: "flex flex-row gap-4 items-center",
+                    match check.outcome {
+                        CheckOutcome::Passed(message) => rsx! {
+                            span { class: "badge badge-success w-20", "Passed" }
+                            span { class: "font-semibold", {check.title} }
+                            span { class: "font-light", {message} }
+                        },
+                        CheckOutcome::Failed(message) => rsx! {
+                            span { class: "badge badge-error w-20", "Failed" }
+                            span { class: "font-semibold", {check.title} }
+                            span { class: "font-light text-error", {message} }
+                        },
+                        CheckOutcome::Skipped(message) => rsx! {
+                            span { class: "badge badge-ghost w-20", "Skipped" }
+                            span { class: "font-semibold", {check.title} }
+                            span { class: "font-light", {message} }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,377 @@
+use crate::prelude::*;
+
+use btc_heritage_wallet::{
+    bitcoin::{Amount, Network},
+    btc_heritage::utils::timestamp_now,
+};
+
+use crate::{
+    components::{balance::UIBtcAmount, timestamp::UITimestamp},
+    utils::CCStr,
+    Route,
+};
+
+use state_management::{FiatCurrency, PortfolioHeirWallet, PortfolioWallet};
+
+/// A wallet not synchronized for this long is flagged on the dashboard
+const STALE_SYNC_DELAY: u64 = 24 * 60 * 60;
+
+/// Dashboard aggregating all the wallets and heir wallets, the landing page of returning users
+#[component]
+pub fn DashboardView() -> Element {
+    log::debug!("DashboardView Rendered");
+
+    let database_service = state_management::use_database_service();
+    let service_client_service = state_management::use_service_client_service();
+    let blockchain_provider_service = state_management::use_blockchain_provider_service();
+
+    let mut wallets = use_resource(move || async move {
+        let wallet_names = state_management::list_wallet_names(database_service)
+            .await
+            .unwrap_or_default();
+        futures_util::future::join_all(wallet_names.into_iter().map(|name| {
+            state_management::portfolio_wallet(
+                database_service,
+                service_client_service,
+                blockchain_provider_service,
+                name,
+            )
+        }))
+        .await
+    });
+    let mut heirwallets = use_resource(move || async move {
+        let heirwallet_names = state_management::list_heirwallet_names(database_service)
+            .await
+            .unwrap_or_default();
+        futures_util::future::join_all(heirwallet_names.into_iter().map(|name| {
+            state_management::portfolio_heirwallet(database_service, service_client_service, name)
+        }))
+        .await
+    });
+
+    let mut fiat_currency = use_resource(move || async move {
+        state_management::load_fiat_currency(database_service)
+            .await
+            .unwrap_or_default()
+    });
+    // Testnet coins have no value, only fetch the exchange rate on mainnet
+    let exchange_rate = use_resource(move || async move {
+        let currency = fiat_currency().flatten()?;
+        if state_management::APPLICATION_CONFIG.read().network != Network::Bitcoin {
+            return None;
+        }
+        Some(
+            state_management::fetch_btc_exchange_rate(currency)
+                .await
+                .map(|rate| (currency, rate))
+                .map_err(CCStr::from),
+        )
+    });
+    let select_fiat_currency = move |evt: Event<FormData>| async move {
+        let currency = evt.value().parse::<FiatCurrency>().ok();
+        let opt_in = currency.is_some() && matches!(fiat_currency(), Some(None));
+        match state_management::save_fiat_currency(database_service, currency).await {
+            Ok(()) => {
+                if opt_in {
+                    alert_info(
+                        "Fiat values are fetched from mempool.space, \
+                        which can see your IP address and when you use the application",
+                    );
+                }
+                fiat_currency.restart()
+            }
+            Err(e) => alert_failure("Could not save the currency", e),
+        }
+    };
+
+    let total_balance = use_memo(move || {
+        wallets
+            .read()
+            .as_ref()
+            .map(|wallets| wallets.iter().map(|w| w.balance).sum::<Amount>())
+    });
+    let total_spendable = use_memo(move || {
+        wallets
+            .read()
+            .as_ref()
+            .map(|wallets| wallets.iter().map(|w| w.spendable).sum::<Amount>())
+    });
+    let total_inheritable = use_memo(move || {
+        heirwallets
+            .read()
+            .as_ref()
+            .map(|heirwallets| heirwallets.iter().map(|hw| hw.inheritable).sum::<Amount>())
+    });
+    let fiat_total = use_memo(move || {
+        let (currency, rate) = match exchange_rate() {
+            Some(Some(Ok(exchange_rate))) => exchange_rate,
+            _ => return None,
+        };
+        let total = total_balance()? + total_inheritable()?;
+        Some(currency.format(FiatCurrency::value_of(total, rate)))
+    });
+
+    let upcoming_expirations = use_memo(move || {
+        let mut expirations = wallets
+            .read()
+            .iter()
+            .flatten()
+            .filter_map(|w| w.expiration_ts.map(|ts| (w.name.clone(), ts)))
+            .collect::<Vec<_>>();
+        expirations.sort_by_key(|(_, ts)| *ts);
+        expirations
+    });
+
+    use_drop(|| log::debug!("DashboardView Dropped"));
+
+    rsx! {
+        super::TitledView {
+            title: CCStr::from("Dashboard"),
+            subtitle: CCStr::from("An overview of all your wallets and inheritances."),
+            right: rsx! {
+                div { class: "flex items-center gap-4",
+                    select {
+                        class: "select select-bordered w-40",
+                        title: "Fiat values are fetched from mempool.space, which can see your IP address",
+                        onchange: select_fiat_currency,
+                        option {
+                            value: "",
+                            selected: matches!(fiat_currency(), Some(None)),
+                            "No fiat value"
+                        }
+                        for currency in FiatCurrency::list() {
+                            option {
+                                value: currency.code(),
+                                selected: fiat_currency() == Some(Some(currency)),
+                                {currency.code()}
+                            }
+                        }
+                    }
+                    button {
+                        class: "btn btn-outline",
+                        onclick: move |_| {
+                            wallets.restart();
+                            heirwallets.restart();
+                        },
+                        "Refresh"
+                    }
+                }
+            },
+            div { class: "container mx-auto flex flex-col gap-8",
+                div { class: "stats stats-vertical lg:stats-horizontal shadow w-full",
+                    div { class: "stat",
+                        div { class: "stat-title", "Total Balance" }
+                        div { class: "stat-value text-2xl",
+                            LoadedComponent::<UIBtcAmount> { input: total_balance.into() }
+                        }
+                        div { class: "stat-desc", "Across all your wallets" }
+                    }
+                    div { class: "stat",
+                        div { class: "stat-title", "Spendable" }
+                        div { class: "stat-value text-2xl",
+                            LoadedComponent::<UIBtcAmount> { input: total_spendable.into() }
+                        }
+                        div { class: "stat-desc", "Confirmed balance of your wallets" }
+                    }
+                    div { class: "stat",
+                        div { class: "stat-title", "Inheritances" }
+                        div { class: "stat-value text-2xl",
+                            LoadedComponent::<UIBtcAmount> { input: total_inheritable.into() }
+                        }
+                        div { class: "stat-desc", "Heritages of your heir wallets" }
+                    }
+                    if fiat_currency().flatten().is_some() {
+                        div { class: "stat",
+                            div { class: "stat-title", "Fiat Value" }
+                            div { class: "stat-value text-2xl",
+                                match (fiat_total(), exchange_rate()) {
+                                    (Some(fiat_total), _) => rsx! { "≈ {fiat_total}" },
+                                    (None, Some(None)) => rsx! { "-" },
+                                    (None, Some(Some(Err(_)))) => rsx! { "Unavailable" },
+                                    _ => rsx! {
+                                        div { class: "skeleton h-8 w-32" }
+                                    },
+                                }
+                            }
+                            div { class: "stat-desc",
+                                match exchange_rate() {
+                                    Some(None) => rsx! { "Only available on the Bitcoin mainnet" },
+                                    Some(Some(Err(e))) => rsx! { "{e}" },
+                                    _ => rsx! { "Wallets and inheritances" },
+                                }
+                            }
+                        }
+                    }
+                }
+
+                div { class: "grid grid-cols-1 xl:grid-cols-2 gap-8",
+                    div { class: "rounded-box border border-base-content/5 shadow-md p-4",
+                        h2 { class: "text-2xl font-bold mb-4", "Upcoming Expirations" }
+                        if upcoming_expirations.read().is_empty() {
+                            div { class: "text-base font-light", "No Heritage Configuration to renew." }
+                        }
+                        for (wallet_name , expiration_ts) in upcoming_expirations() {
+                            ExpirationLine { wallet_name, expiration_ts }
+                        }
+                    }
+                    div { class: "rounded-box border border-base-content/5 shadow-md p-4",
+                        h2 { class: "text-2xl font-bold mb-4", "Inheritances" }
+                        match heirwallets() {
+                            Some(heirwallets) if heirwallets.is_empty() => rsx! {
+                                div { class: "text-base font-light", "You have no heir wallet." }
+                            },
+                            Some(heirwallets) => rsx! {
+                                for heirwallet in heirwallets {
+                                    HeirWalletLine { key: "{heirwallet.name}", heirwallet: heirwallet.clone() }
+                                }
+                            },
+                            None => rsx! {
+                                div { class: "skeleton h-16 w-full" }
+                            },
+                        }
+                    }
+                }
+
+                div { class: "rounded-box border border-base-content/5 shadow-md p-4",
+                    h2 { class: "text-2xl font-bold mb-4", "Wallets" }
+                    match wallets() {
+                        Some(wallets) if wallets.is_empty() => rsx! {
+                            div { class: "text-base font-light", "You have no wallet yet." }
+                        },
+                        Some(wallets) => rsx! {
+                            table { class: "table",
+                                thead {
+                                    tr {
+                                        th { "Wallet" }
+                                        th { "Balance" }
+                                        th { "Spendable" }
+                                        th { "Pending Transactions" }
+                                        th { "Last Sync" }
+                                    }
+                                }
+                                tbody {
+                                    for wallet in wallets {
+                                        WalletLine { key: "{wallet.name}", wallet: wallet.clone() }
+                                    }
+                                }
+                            }
+                        },
+                        None => rsx! {
+                            div { class: "skeleton h-32 w-full" }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ExpirationLine(wallet_name: CCStr, expiration_ts: u64) -> Element {
+    let now = timestamp_now();
+    let (badge_class, status) = if expiration_ts < now {
+        ("badge-error", "Expired")
+    } else if expiration_ts < now + ExpirationStatus::SOON {
+        ("badge-warning", "Expires soon")
+    } else {
+        ("badge-success", "Current")
+    };
+    let route = Route::WalletConfigurationView {
+        wallet_name: wallet_name.clone(),
+    };
+    rsx! {
+        div {
+            class: "flex items-center gap-4 p-2 rounded-box cursor-pointer hover:bg-base-200",
+            onclick: move |_| {
+                navigator().push(route.clone());
+            },
+            div { class: "font-bold grow", {wallet_name} }
+            AlwaysLoadedComponent { input: UITimestamp::new_date_only(expiration_ts) }
+            div { class: "badge {badge_class}", {status} }
+        }
+    }
+}
+
+#[component]
+fn HeirWalletLine(heirwallet: PortfolioHeirWallet) -> Element {
+    let route = Route::HeirWalletView {
+        heirwallet_name: heirwallet.name.clone(),
+    };
+    rsx! {
+        div {
+            class: "flex items-center gap-4 p-2 rounded-box cursor-pointer hover:bg-base-200",
+            onclick: move |_| {
+                navigator().push(route.clone());
+            },
+            div { class: "font-bold grow", {heirwallet.name} }
+            if let Some(e) = heirwallet.error {
+                div { class: "text-error text-sm", "{e}" }
+            } else {
+                div { class: "flex flex-col items-end",
+                    AlwaysLoadedComponent::<UIBtcAmount> { input: heirwallet.inheritable.into() }
+                    if heirwallet.spendable > Amount::ZERO {
+                        div { class: "text-sm text-success",
+                            "Spendable now: "
+                            AlwaysLoadedComponent::<UIBtcAmount> { input: heirwallet.spendable.into() }
+                        }
+                    } else if let Some(next_maturity_ts) = heirwallet.next_maturity_ts {
+                        div { class: "text-sm font-light",
+                            "Spendable from "
+                            AlwaysLoadedComponent { input: UITimestamp::new_date_only(next_maturity_ts) }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn WalletLine(wallet: PortfolioWallet) -> Element {
+    let route = Route::WalletView {
+        wallet_name: wallet.name.clone(),
+    };
+    let stale_sync = wallet
+        .last_sync_ts
+        .is_some_and(|ts| ts + STALE_SYNC_DELAY < timestamp_now());
+    let pending_transactions = wallet.pending_transactions;
+    rsx! {
+        tr {
+            class: "cursor-pointer hover:bg-base-200",
+            onclick: move |_| {
+                navigator().push(route.clone());
+            },
+            td { class: "font-bold",
+                {wallet.name}
+                if let Some(e) = wallet.error {
+                    div { class: "text-error text-xs font-normal", "{e}" }
+                }
+            }
+            if wallet.online {
+                td {
+                    AlwaysLoadedComponent::<UIBtcAmount> { input: wallet.balance.into() }
+                }
+                td {
+                    AlwaysLoadedComponent::<UIBtcAmount> { input: wallet.spendable.into() }
+                }
+                td {
+                    if pending_transactions > 0 {
+                        div { class: "badge badge-info", "{pending_transactions}" }
+                    } else {
+                        "-"
+                    }
+                }
+                td { class: if stale_sync { "text-warning" },
+                    match wallet.last_sync_ts {
+                        Some(ts) => rsx! {
+                            AlwaysLoadedComponent { input: UITimestamp::new_full(ts) }
+                        },
+                        None => rsx! { "-" },
+                    }
+                }
+            } else {
+                td { colspan: 4, class: "font-light", "Sign-only wallet" }
+            }
+        }
+    }
+}
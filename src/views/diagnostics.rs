@@ -0,0 +1,165 @@
+use crate::prelude::*;
+
+use std::path::PathBuf;
+
+use crate::{
+    components::{
+        modal::Modal,
+        svg::{AlertOutline, DrawSvg},
+    },
+    utils::CCStr,
+    Route,
+};
+
+/// Information to investigate an issue: versions, data location, logs and crash reports
+#[component]
+pub fn DiagnosticsView() -> Element {
+    log::debug!("DiagnosticsView Rendered");
+
+    let datadir = use_memo(move || state_management::APPLICATION_CONFIG.read().datadir.clone());
+    let crash_reports = use_resource(move || async move {
+        let datadir = datadir();
+        tokio::task::spawn_blocking(move || crate::crash::list_crash_reports(&datadir))
+            .await
+            .unwrap_or_default()
+    });
+    let log_file = use_memo(move || {
+        let _ = datadir.read();
+        state_management::LOG_CONFIG
+            .read()
+            .log_to_file
+            .then(|| crate::logger::log_file_path(&LogConfig::log_dir()))
+    });
+
+    use_drop(|| log::debug!("DiagnosticsView Dropped"));
+
+    rsx! {
+        super::TitledView {
+            title: CCStr::from("Diagnostics"),
+            subtitle: CCStr::from("What to provide when reporting an issue."),
+            div { class: "container mx-auto px-8 space-y-8",
+                div { class: "card bg-base-200 shadow-xl",
+                    div { class: "card-body",
+                        h2 { class: "card-title", "Application" }
+                        table { class: "table",
+                            tbody {
+                                tr {
+                                    th { "Version" }
+                                    td { class: "font-mono", {env!("CARGO_PKG_VERSION")} }
+                                }
+                                tr {
+                                    th { "Platform" }
+                                    td { class: "font-mono",
+                                        "{std::env::consts::OS} ({std::env::consts::ARCH})"
+                                    }
+                                }
+                                tr {
+                                    th { "Network" }
+                                    td { class: "font-mono",
+                                        {state_management::APPLICATION_CONFIG.read().network.to_string()}
+                                    }
+                                }
+                                tr {
+                                    th { "Data directory" }
+                                    td { class: "font-mono break-all",
+                                        {datadir().to_string_lossy().into_owned()}
+                                    }
+                                }
+                                tr {
+                                    th { "Log file" }
+                                    td { class: "font-mono break-all",
+                                        match log_file() {
+                                            Some(log_file) => rsx! {
+                                                OpenFileLink { path: log_file }
+                                            },
+                                            None => rsx! { "Disabled, enable it in the Application Configuration" },
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                div { class: "card bg-base-200 shadow-xl",
+                    div { class: "card-body",
+                        h2 { class: "card-title", "Crash Reports" }
+                        match crash_reports() {
+                            Some(crash_reports) if crash_reports.is_empty() => rsx! {
+                                div { class: "text-base font-light", "The application never crashed." }
+                            },
+                            Some(crash_reports) => rsx! {
+                                ul { class: "list",
+                                    for crash_report in crash_reports {
+                                        li {
+                                            key: "{crash_report.display()}",
+                                            class: "list-row font-mono break-all",
+                                            OpenFileLink { path: crash_report.clone() }
+                                        }
+                                    }
+                                }
+                            },
+                            None => rsx! {
+                                div { class: "skeleton h-16 w-full" }
+                            },
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn OpenFileLink(path: PathBuf) -> Element {
+    let display = path.to_string_lossy().into_owned();
+    rsx! {
+        a {
+            class: "link link-hover",
+            onclick: move |_| {
+                _ = open::that_in_background(&path);
+            },
+            {display}
+        }
+    }
+}
+
+/// Tell the user the previous run crashed and its unsaved drafts were recovered
+#[component]
+pub fn CrashRecoveryModal() -> Element {
+    let crash_report = use_hook(crate::crash::previous_crash_report);
+    let mut is_open = use_signal(|| crash_report.is_some());
+
+    rsx! {
+        Modal { is_open, persistent: true,
+            div { class: "flex flex-col gap-4 max-w-2xl",
+                div { class: "flex flex-row items-center gap-4",
+                    div { class: "text-warning",
+                        DrawSvg::<AlertOutline> {}
+                    }
+                    h2 { class: "text-2xl font-bold", "The application closed unexpectedly" }
+                }
+                div { class: "text-base",
+                    "A crash report was saved and the forms you were filling will be restored when you open them again."
+                }
+                if let Some(crash_report) = &crash_report {
+                    div { class: "text-sm font-mono break-all", {crash_report.to_string_lossy().into_owned()} }
+                }
+                div { class: "modal-action",
+                    button {
+                        class: "btn btn-outline",
+                        onclick: move |_| is_open.set(false),
+                        "Dismiss"
+                    }
+                    button {
+                        class: "btn btn-primary",
+                        onclick: move |_| {
+                            is_open.set(false);
+                            navigator().push(Route::DiagnosticsView {});
+                        },
+                        "Open Diagnostics"
+                    }
+                }
+            }
+        }
+    }
+}
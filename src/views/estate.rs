@@ -0,0 +1,304 @@
+use crate::prelude::*;
+
+use btc_heritage_wallet::{bitcoin::Amount, btc_heritage::HeirConfig};
+
+use crate::{
+    components::{balance::UIBtcAmount, timestamp::UITimestamp},
+    utils::{heir_config_type_to_string, CCStr},
+    Route,
+};
+
+/// What an heir inherits across all the wallets
+#[derive(Debug, Clone, PartialEq)]
+struct EstateHeirSummary {
+    heir_config: HeirConfig,
+    /// `None` for heirs that are neither in the database nor on the service
+    name: Option<CCStr>,
+    wallets: Vec<CCStr>,
+    protected: Amount,
+    earliest_spendable_ts: Option<u64>,
+}
+
+/// Estate-wide summary of the Heritage Configurations of all the wallets
+#[component]
+pub fn EstateView() -> Element {
+    log::debug!("EstateView Rendered");
+
+    let database_service = state_management::use_database_service();
+    let service_client_service = state_management::use_service_client_service();
+    let blockchain_provider_service = state_management::use_blockchain_provider_service();
+
+    let mut wallets = use_resource(move || async move {
+        let wallet_names = state_management::list_wallet_names(database_service)
+            .await
+            .unwrap_or_default();
+        futures_util::future::join_all(wallet_names.into_iter().map(|name| {
+            state_management::estate_wallet(
+                database_service,
+                service_client_service,
+                blockchain_provider_service,
+                name,
+            )
+        }))
+        .await
+    });
+
+    let database_heirs = helper_hooks::use_resource_database_heirs();
+    let service_heirs = helper_hooks::use_resource_service_heirs();
+    let heir_service_links = helper_hooks::use_resource_heir_service_links();
+    let heirs = helper_hooks::use_memo_heirs(database_heirs, service_heirs, heir_service_links);
+
+    let heir_summaries = use_memo(move || {
+        let wallets = wallets.read();
+        let wallets = wallets.as_ref()?;
+        let heirs = heirs.read();
+        let mut summaries: Vec<EstateHeirSummary> = vec![];
+        for wallet in wallets.iter() {
+            for ehc in wallet.heritage_configs.iter() {
+                for estate_heir in ehc.heirs.iter() {
+                    let summary = match summaries
+                        .iter()
+                        .position(|s| s.heir_config == estate_heir.heir_config)
+                    {
+                        Some(index) => &mut summaries[index],
+                        None => {
+                            summaries.push(EstateHeirSummary {
+                                heir_config: estate_heir.heir_config.clone(),
+                                name: heirs
+                                    .iter()
+                                    .find(|h| *h.heir_config == estate_heir.heir_config)
+                                    .map(|h| h.name.clone()),
+                                wallets: vec![],
+                                protected: Amount::ZERO,
+                                earliest_spendable_ts: None,
+                            });
+                            summaries.last_mut().expect("just pushed")
+                        }
+                    };
+                    if !summary.wallets.contains(&wallet.name) {
+                        summary.wallets.push(wallet.name.clone());
+                    }
+                    summary.protected += ehc.balance;
+                    if let Some(ts) = estate_heir.spendable_ts {
+                        summary.earliest_spendable_ts = Some(
+                            summary
+                                .earliest_spendable_ts
+                                .map_or(ts, |current| current.min(ts)),
+                        );
+                    }
+                }
+            }
+        }
+        summaries.sort_by_key(|s| s.earliest_spendable_ts.unwrap_or(u64::MAX));
+        Some(summaries)
+    });
+
+    let total_protected = use_memo(move || {
+        wallets.read().as_ref().map(|wallets| {
+            wallets
+                .iter()
+                .flat_map(|w| w.heritage_configs.iter())
+                .filter(|ehc| !ehc.heirs.is_empty())
+                .map(|ehc| ehc.balance)
+                .sum::<Amount>()
+        })
+    });
+    // The first heir of the current Heritage Configuration inherits when it expires
+    let earliest_expiration = use_memo(move || {
+        wallets
+            .read()
+            .iter()
+            .flatten()
+            .filter_map(|w| {
+                w.heritage_configs
+                    .iter()
+                    .find(|ehc| ehc.current)
+                    .and_then(|ehc| ehc.heirs.first())
+                    .and_then(|eh| eh.spendable_ts)
+                    .map(|ts| (w.name.clone(), ts))
+            })
+            .min_by_key(|(_, ts)| *ts)
+    });
+    let unprotected_wallets = use_memo(move || {
+        wallets
+            .read()
+            .iter()
+            .flatten()
+            .filter(|w| w.error.is_none() && !w.heritage_configs.iter().any(|ehc| ehc.current))
+            .map(|w| w.name.clone())
+            .collect::<Vec<_>>()
+    });
+    let wallets_in_error = use_memo(move || {
+        wallets
+            .read()
+            .iter()
+            .flatten()
+            .filter_map(|w| w.error.clone().map(|error| (w.name.clone(), error)))
+            .collect::<Vec<_>>()
+    });
+
+    use_drop(|| log::debug!("EstateView Dropped"));
+
+    rsx! {
+        super::TitledView {
+            title: CCStr::from("Estate"),
+            subtitle: CCStr::from("The Heritage Configurations of all your wallets, heir by heir."),
+            right: rsx! {
+                button {
+                    class: "btn btn-outline",
+                    onclick: move |_| {
+                        wallets.restart();
+                    },
+                    "Refresh"
+                }
+            },
+            div { class: "container mx-auto flex flex-col gap-8",
+                div { class: "stats stats-vertical lg:stats-horizontal shadow w-full",
+                    div { class: "stat",
+                        div { class: "stat-title", "Protected Value" }
+                        div { class: "stat-value text-2xl",
+                            LoadedComponent::<UIBtcAmount> { input: total_protected.into() }
+                        }
+                        div { class: "stat-desc", "Locked by a Heritage Configuration" }
+                    }
+                    div { class: "stat",
+                        div { class: "stat-title", "Heirs" }
+                        div { class: "stat-value text-2xl",
+                            match heir_summaries() {
+                                Some(summaries) => rsx! { "{summaries.len()}" },
+                                None => rsx! {
+                                    div { class: "skeleton h-8 w-16" }
+                                },
+                            }
+                        }
+                        div { class: "stat-desc", "Across all your wallets" }
+                    }
+                    div { class: "stat",
+                        div { class: "stat-title", "Earliest Expiration" }
+                        div { class: "stat-value text-2xl",
+                            match earliest_expiration() {
+                                Some((_, ts)) => rsx! {
+                                    AlwaysLoadedComponent { input: UITimestamp::new_date_only(ts) }
+                                },
+                                None => rsx! { "-" },
+                            }
+                        }
+                        div { class: "stat-desc",
+                            if let Some((wallet_name, _)) = earliest_expiration() {
+                                "Wallet {wallet_name}"
+                            } else {
+                                "No current Heritage Configuration"
+                            }
+                        }
+                    }
+                }
+
+                if !unprotected_wallets.read().is_empty() {
+                    div { role: "alert", class: "alert alert-warning",
+                        span { "These wallets have no Heritage Configuration, your heirs cannot inherit them:" }
+                        for wallet_name in unprotected_wallets() {
+                            WalletLink { key: "{wallet_name}", wallet_name }
+                        }
+                    }
+                }
+                for (wallet_name , error) in wallets_in_error() {
+                    div {
+                        key: "{wallet_name}",
+                        role: "alert",
+                        class: "alert alert-error",
+                        span {
+                            "Wallet "
+                            span { class: "font-bold", {wallet_name} }
+                            ": {error}"
+                        }
+                    }
+                }
+
+                div { class: "rounded-box border border-base-content/5 shadow-md p-4",
+                    h2 { class: "text-2xl font-bold mb-4", "Heirs" }
+                    match heir_summaries() {
+                        Some(summaries) if summaries.is_empty() => rsx! {
+                            div { class: "text-base font-light", "None of your wallets has heirs yet." }
+                        },
+                        Some(summaries) => rsx! {
+                            table { class: "table",
+                                thead {
+                                    tr {
+                                        th { "Heir" }
+                                        th { "Wallets" }
+                                        th { "Protected Value" }
+                                        th { "Earliest Inheritance" }
+                                    }
+                                }
+                                tbody {
+                                    for summary in summaries {
+                                        EstateHeirLine {
+                                            key: "{summary.heir_config.fingerprint()}",
+                                            summary,
+                                        }
+                                    }
+                                }
+                            }
+                        },
+                        None => rsx! {
+                            div { class: "skeleton h-32 w-full" }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn EstateHeirLine(summary: EstateHeirSummary) -> Element {
+    let heir_config_type = heir_config_type_to_string(&summary.heir_config);
+    let heir_config_fg = summary.heir_config.fingerprint();
+    rsx! {
+        tr {
+            td {
+                div { class: "font-bold",
+                    match summary.name {
+                        Some(name) => rsx! { {name} },
+                        None => rsx! { "Unknown heir" },
+                    }
+                }
+                div { class: "text-sm font-light", "{heir_config_type} - {heir_config_fg}" }
+            }
+            td {
+                div { class: "flex flex-wrap gap-2",
+                    for wallet_name in summary.wallets {
+                        WalletLink { key: "{wallet_name}", wallet_name: wallet_name.clone() }
+                    }
+                }
+            }
+            td {
+                AlwaysLoadedComponent::<UIBtcAmount> { input: summary.protected.into() }
+            }
+            td {
+                match summary.earliest_spendable_ts {
+                    Some(ts) => rsx! {
+                        AlwaysLoadedComponent { input: UITimestamp::new_date_only(ts) }
+                    },
+                    None => rsx! { "-" },
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn WalletLink(wallet_name: CCStr) -> Element {
+    let route = Route::WalletConfigurationView {
+        wallet_name: wallet_name.clone(),
+    };
+    rsx! {
+        button {
+            class: "badge badge-outline cursor-pointer hover:badge-primary",
+            onclick: move |_| {
+                navigator().push(route.clone());
+            },
+            {wallet_name}
+        }
+    }
+}
@@ -73,8 +73,7 @@ pub(super) fn DeleteHeirConfig() -> Element {
                 navigator().push(Route::HeirListView {});
             }
             Err(e) => {
-                log::info!("Heir deletion failed: {e}");
-                alert_error(format!("Heir deletion failed: {e}"));
+                alert_failure("Heir deletion failed", e);
             }
         }
 
@@ -179,8 +178,7 @@ pub(super) fn DeleteHeirSeedConfig() -> Element {
                 navigator().push(Route::HeirListView {});
             }
             Err(e) => {
-                log::info!("Heir seed striping failed: {e}");
-                alert_error(format!("Heir seed striping failed: {e}"));
+                alert_failure("Heir seed striping failed", e);
             }
         }
 
@@ -101,6 +101,7 @@ pub fn HeirConfigurationView(heir_index: usize) -> Element {
 
         log::debug!("HeirWalletConfigurationView: Processing service export configuration");
         let mut refresh_service_heirs = false;
+        let mut exported_heir = None;
         if let ExportToServiceConfig::Export {
             name,
             email,
@@ -127,14 +128,14 @@ pub fn HeirConfigurationView(heir_index: usize) -> Element {
                 .await;
             log::debug!("HeirWalletConfigurationView: Heir post request completed");
 
-            let service_heir_id = match post_heirs_result {
+            exported_heir = match post_heirs_result {
                 Ok(h) => {
                     log::debug!(
                         "HeirWalletConfigurationView: Heir exported to service successfully with ID: {}",
                         h.id
                     );
                     success("Exported the in the service successfully");
-                    Some(h.id)
+                    Some(h)
                 }
                 Err(e) => {
                     warn(format!("Could not export Heir to service: {e}"));
@@ -142,20 +143,22 @@ pub fn HeirConfigurationView(heir_index: usize) -> Element {
                 }
             };
             if !additional_contacts.is_empty() {
-                if let Some(heir_id) = service_heir_id {
+                if let Some(ref mut exported_heir) = exported_heir {
                     log::debug!(
                         "HeirWalletConfigurationView: Adding additional contacts for heir ID: {}",
-                        heir_id
+                        exported_heir.id
                     );
                     // Add contacts
                     match client
-                        .post_heir_contacts(&heir_id, additional_contacts)
+                        .post_heir_contacts(&exported_heir.id, additional_contacts.clone())
                         .await
                     {
                         Ok(_) => {
                             log::debug!(
                             "HeirWalletConfigurationView: Additional contacts added successfully"
                         );
+                            exported_heir.additional_contacts =
+                                additional_contacts.into_iter().collect();
                         }
                         Err(e) => {
                             warn(format!("Could not add additional contacts to the exported Heir in the service: {e}"));
@@ -167,8 +170,11 @@ pub fn HeirConfigurationView(heir_index: usize) -> Element {
             log::debug!("HeirWalletConfigurationView: Skipping service export");
         };
 
-        // Need to refresh to "insert" the newly created heir
-        if refresh_service_heirs {
+        if let Some(exported_heir) = exported_heir {
+            log::debug!("HeirWalletConfigurationView: Inserting the exported heir");
+            helper_hooks::upsert_service_heir(service_heirs, exported_heir);
+        } else if refresh_service_heirs {
+            // The export failed, reconcile with the service
             log::debug!("HeirWalletConfigurationView: Refreshing heir lists");
             log::debug!("service_heirs.restart()");
             service_heirs.restart();
@@ -188,14 +194,15 @@ pub fn HeirConfigurationView(heir_index: usize) -> Element {
         else {
             return abort("Invalid Current Export To Service configuration");
         };
-        let service_heir_id =
+        let current_service_heir =
             if let Some(Some(ref service_heir)) = composite_heir.read().service_heir {
-                service_heir.id.clone()
+                service_heir.clone()
             } else {
                 return abort("Invalid Current Export To Service configuration");
             };
+        let service_heir_id = current_service_heir.id.clone();
 
-        let (heir_update, contacts_to_add, contacts_to_delete) = match (
+        let (updated_heir, heir_update, contacts_to_add, contacts_to_delete) = match (
             current_export_to_service_config_state,
             export_to_service_config,
         ) {
@@ -215,6 +222,16 @@ pub fn HeirConfigurationView(heir_index: usize) -> Element {
                     additional_contacts,
                 },
             ) => {
+                // The heir as it will be in the service once updated
+                let mut updated_heir = (*current_service_heir).clone();
+                updated_heir.display_name = name.clone();
+                updated_heir.main_contact = MainContact {
+                    email: email.clone(),
+                    custom_message: custom_message.clone(),
+                };
+                updated_heir.permissions = permissions.clone();
+                updated_heir.additional_contacts = additional_contacts.iter().cloned().collect();
+
                 let heir_update = HeirUpdate {
                     display_name: (current_name != name).then_some(name),
                     main_contact: (current_email != email
@@ -241,6 +258,7 @@ pub fn HeirConfigurationView(heir_index: usize) -> Element {
                     .cloned()
                     .collect::<Vec<_>>();
                 (
+                    Some(updated_heir),
                     (heir_update.display_name.is_some()
                         || heir_update.main_contact.is_some()
                         || heir_update.permissions.is_some())
@@ -249,10 +267,11 @@ pub fn HeirConfigurationView(heir_index: usize) -> Element {
                     (!contacts_to_delete.is_empty()).then_some(contacts_to_delete),
                 )
             }
-            _ => (None, None, None),
+            _ => (None, None, None, None),
         };
 
         let mut refresh_service_heirs = false;
+        let mut all_succeeded = true;
         let client = state_management::heritage_service_client(service_client_service).await;
         if let Some(heir_update) = heir_update {
             log::debug!("HeirCreateForm: heir_update: {heir_update:?}");
@@ -263,6 +282,7 @@ pub fn HeirConfigurationView(heir_index: usize) -> Element {
                     success("Successfully updated heir in the service");
                 }
                 Err(e) => {
+                    all_succeeded = false;
                     warn(format!("Could not update the heir in the service: {e}"));
                 }
             };
@@ -280,6 +300,7 @@ pub fn HeirConfigurationView(heir_index: usize) -> Element {
                     success("Removed additional contacts in the service");
                 }
                 Err(e) => {
+                    all_succeeded = false;
                     warn(format!(
                         "Could not remove additional contacts in the service: {e}"
                     ));
@@ -298,6 +319,7 @@ pub fn HeirConfigurationView(heir_index: usize) -> Element {
                     success("Added additional contacts in the service");
                 }
                 Err(e) => {
+                    all_succeeded = false;
                     warn(format!(
                         "Could not add additional contacts in the service: {e}"
                     ));
@@ -305,11 +327,19 @@ pub fn HeirConfigurationView(heir_index: usize) -> Element {
             };
         }
 
-        // Need to refresh to "insert" the newly created heir
         if refresh_service_heirs {
-            log::debug!("HeirWalletConfigurationView: Refreshing heir lists");
-            log::debug!("service_heirs.restart()");
-            service_heirs.restart();
+            match updated_heir {
+                Some(updated_heir) if all_succeeded => {
+                    log::debug!("HeirWalletConfigurationView: Updating the heir in place");
+                    helper_hooks::upsert_service_heir(service_heirs, updated_heir);
+                }
+                _ => {
+                    // Some operations failed, reconcile with the service
+                    log::debug!("HeirWalletConfigurationView: Refreshing heir lists");
+                    log::debug!("service_heirs.restart()");
+                    service_heirs.restart();
+                }
+            }
         }
         log::debug!("HeirWalletConfigurationView: Form submission completed");
         *processing.write() = false;
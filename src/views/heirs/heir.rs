@@ -1,5 +1,7 @@
 use crate::prelude::*;
 
+use std::collections::HashMap;
+
 use btc_heritage_wallet::{
     heritage_service_api_client::{
         Heir as ServiceHeir, HeirContact, HeirPermission, HeirPermissions,
@@ -11,6 +13,7 @@ use crate::{
     components::{
         badge::UIHeirBadges,
         copy::CopyTextarea,
+        modal::InfoModal,
         onboarding::MaybeOnPathHighlight,
         quick_actions::{ShowKeyProviderMnemonic, ShowKeyProviderMnemonicFlavor},
         svg::{CheckCircle, Close, Cog, DrawSvg, SvgSize::Full, SvgSize::Size5},
@@ -116,6 +119,8 @@ pub fn HeirView(heir_index: usize) -> Element {
                 }
             }
 
+            HeirConflictsResolution {}
+
             HeirConfigComponent {}
 
             LoadedComponent::<Display<UIServiceHeir>> { input: display_heir().into() }
@@ -150,6 +155,126 @@ pub fn HeirView(heir_index: usize) -> Element {
     }
 }
 
+/// Warn about the other heirs sharing the heir configuration of this heir
+///
+/// When several service heirs share it, the user chooses the one linked to the local heir.
+#[component]
+fn HeirConflictsResolution() -> Element {
+    log::debug!("HeirConflictsResolution Rendered");
+
+    let database_service = state_management::use_database_service();
+    let composite_heir = use_context::<Memo<CompositeHeir>>();
+    let heir_service_links = use_context::<Resource<HashMap<String, String>>>();
+
+    let conflicts = use_memo(move || composite_heir.read().conflicts.clone());
+    let linked_service_heir_id = use_memo(move || {
+        composite_heir
+            .read()
+            .service_heir
+            .as_ref()
+            .and_then(|sh| sh.as_ref().map(|sh| sh.id.clone()))
+    });
+    let can_link = use_memo(move || composite_heir.read().db_heir.is_some());
+
+    let mut dialog_open = use_signal(|| false);
+    let mut in_operation = use_signal(|| false);
+
+    use_drop(|| log::debug!("HeirConflictsResolution Dropped"));
+
+    if conflicts.read().is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div { role: "alert", class: "alert alert-warning my-4 max-w-7xl mx-auto",
+            span {
+                "Other heirs share the heir configuration of this heir. \
+                The Heritage app cannot tell them apart on its own."
+            }
+            button {
+                class: "btn btn-sm",
+                onclick: move |_| dialog_open.set(true),
+                "Resolve"
+            }
+        }
+        InfoModal { is_open: dialog_open, title: "Heir Configuration Conflict",
+            div { class: "flex flex-col gap-4 max-w-2xl",
+                if !conflicts.read().local_heir_names.is_empty() {
+                    div {
+                        div { class: "font-bold", "Other heirs of this app with the same configuration" }
+                        ul { class: "list-disc list-inside",
+                            for name in conflicts.read().local_heir_names.iter() {
+                                li { key: "{name}", "{name}" }
+                            }
+                        }
+                        div { class: "text-sm font-light",
+                            "They are the same heir. Consider deleting the duplicates."
+                        }
+                    }
+                }
+                if !conflicts.read().service_heirs.is_empty() {
+                    div {
+                        div { class: "font-bold", "Heirs of the service with the same configuration" }
+                        if can_link() {
+                            div { class: "text-sm font-light mb-2",
+                                "Choose the service heir linked to this heir."
+                            }
+                        }
+                        div { class: "flex flex-col gap-2",
+                            for service_heir in conflicts.read().service_heirs.iter() {
+                                div {
+                                    key: "{service_heir.id}",
+                                    class: "flex flex-row gap-4 items-center",
+                                    div { class: "grow",
+                                        div { class: "font-semibold", "{service_heir.display_name}" }
+                                        div { class: "text-sm font-light", "{service_heir.main_contact.email}" }
+                                    }
+                                    if linked_service_heir_id().as_ref() == Some(&service_heir.id) {
+                                        span { class: "badge badge-success", "Linked" }
+                                    } else if can_link() {
+                                        button {
+                                            class: "btn btn-sm btn-primary",
+                                            disabled: in_operation(),
+                                            onclick: {
+                                                let service_heir_id = service_heir.id.clone();
+                                                move |_| {
+                                                    let service_heir_id = service_heir_id.clone();
+                                                    async move {
+                                                        *in_operation.write() = true;
+                                                        let heir_name = composite_heir.peek().name.clone();
+                                                        let mut new_links = heir_service_links.cloned().unwrap_or_default();
+                                                        new_links.insert(heir_name.to_string(), service_heir_id.clone());
+                                                        match optimistic_update(
+                                                                heir_service_links,
+                                                                new_links,
+                                                                state_management::save_heir_service_link(
+                                                                    database_service,
+                                                                    heir_name,
+                                                                    service_heir_id,
+                                                                ),
+                                                            )
+                                                            .await
+                                                        {
+                                                            Ok(()) => alert_success("Service heir linked"),
+                                                            Err(e) => alert_failure("Failed to link the service heir", e),
+                                                        }
+                                                        *in_operation.write() = false;
+                                                    }
+                                                }
+                                            },
+                                            "Link"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 #[component]
 fn HeirConfigComponent() -> Element {
     log::debug!("HeirConfig Rendered");
@@ -21,10 +21,12 @@ use crate::{
         misc::BackButton,
         svg::{AccountMultiplePlus, DrawSvg},
     },
-    utils::{log_error_ccstr, CCStr, CheapClone},
+    utils::{check_keys_network, log_error_ccstr, CCStr, CheapClone},
     Route,
 };
 
+use state_management::SeedRole;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum HeirConfigType {
     SingleHeirPubkey,
@@ -359,14 +361,23 @@ fn HeirCreateForm() -> Element {
         };
 
         log::debug!("HeirCreateForm: Creating heir database entry");
+        let fingerprint = heir_config.fingerprint();
         let heir = DbHeir::new(db_name.clone(), heir_config.clone(), key_provider);
-        match state_management::blocking_db_service_operation(database_service, move |mut db| {
-            heir.create(&mut db)
-        })
+        match state_management::blocking_db_service_operation(
+            database_service,
+            "Save the new heir",
+            move |mut db| heir.create(&mut db),
+        )
         .await
         {
             Ok(()) => {
                 success(format!("Heir '{db_name}' created successfully"));
+                state_management::warn_seed_role_conflicts(
+                    database_service,
+                    fingerprint,
+                    SeedRole::Heir,
+                )
+                .await;
             }
             Err(e) => {
                 return abort(&format!("Could not save the heir wallet to database: {e}"));
@@ -375,6 +386,7 @@ fn HeirCreateForm() -> Element {
 
         log::debug!("HeirCreateForm: Processing service export configuration");
         let mut refresh_service_heirs = false;
+        let mut exported_heir = None;
         if let ExportToServiceConfig::Export {
             name,
             email,
@@ -401,14 +413,14 @@ fn HeirCreateForm() -> Element {
                 .await;
             log::debug!("HeirCreateForm: Heir post request completed");
 
-            let service_heir_id = match post_heirs_result {
+            exported_heir = match post_heirs_result {
                 Ok(h) => {
                     log::debug!(
                         "HeirCreateForm: Heir exported to service successfully with ID: {}",
                         h.id
                     );
                     success("Exported the in the service successfully");
-                    Some(h.id)
+                    Some(h)
                 }
                 Err(e) => {
                     warn(format!("Could not export Heir to service: {e}"));
@@ -416,18 +428,20 @@ fn HeirCreateForm() -> Element {
                 }
             };
             if !additional_contacts.is_empty() {
-                if let Some(heir_id) = service_heir_id {
+                if let Some(ref mut exported_heir) = exported_heir {
                     log::debug!(
                         "HeirCreateForm: Adding additional contacts for heir ID: {}",
-                        heir_id
+                        exported_heir.id
                     );
                     // Add contacts
                     match client
-                        .post_heir_contacts(&heir_id, additional_contacts)
+                        .post_heir_contacts(&exported_heir.id, additional_contacts.clone())
                         .await
                     {
                         Ok(_) => {
                             log::debug!("HeirCreateForm: Additional contacts added successfully");
+                            exported_heir.additional_contacts =
+                                additional_contacts.into_iter().collect();
                         }
                         Err(e) => {
                             warn(format!("Could not add additional contacts to the exported Heir in the service: {e}"));
@@ -442,7 +456,10 @@ fn HeirCreateForm() -> Element {
         // Need to refresh to "insert" the newly created heir
         log::debug!("HeirCreateForm: Refreshing heir lists");
         database_heirs.restart();
-        if refresh_service_heirs {
+        if let Some(exported_heir) = exported_heir {
+            helper_hooks::upsert_service_heir(service_heirs, exported_heir);
+        } else if refresh_service_heirs {
+            // The export failed, reconcile with the service
             log::debug!("service_heirs.restart()");
             service_heirs.restart();
         }
@@ -620,12 +637,15 @@ fn HeirConfigSection(
             .parse()
             .map(HeirConfig::SingleHeirPubkey)
             .map_err(log_error_ccstr),
-        HeirConfigType::HeirXPubkey => heir_config_value
-            .read()
-            .trim()
-            .parse()
-            .map(HeirConfig::HeirXPubkey)
-            .map_err(log_error_ccstr),
+        HeirConfigType::HeirXPubkey => {
+            let heir_config_value = heir_config_value.read();
+            check_keys_network(&heir_config_value).map_err(CCStr::from)?;
+            heir_config_value
+                .trim()
+                .parse()
+                .map(HeirConfig::HeirXPubkey)
+                .map_err(log_error_ccstr)
+        }
     });
 
     let heir_config_error = use_memo(move || {
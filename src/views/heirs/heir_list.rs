@@ -1,6 +1,10 @@
 use crate::prelude::*;
 
-use btc_heritage_wallet::heritage_service_api_client::Heir as ServiceHeir;
+use std::collections::HashSet;
+
+use btc_heritage_wallet::{
+    btc_heritage::HeirConfig, heritage_service_api_client::Heir as ServiceHeir,
+};
 
 use crate::{
     components::{
@@ -24,6 +28,7 @@ pub fn HeirListView() -> Element {
                 "Heirs that you can reference in the Heritage configuration of your wallets.",
             ),
             HeirList {}
+            super::heir_report::HeirReportExport {}
 
             OnboardingInfoModal { step: OnboardingStep::ModalExplainHeirs,
                 div { class: "flex flex-col gap-4 max-w-xl text-base",
@@ -103,22 +108,47 @@ fn HeirList() -> Element {
 
     let service_heirs = use_context::<FResource<Vec<CheapClone<ServiceHeir>>>>();
     let composite_heirs = use_context::<Memo<Vec<CompositeHeir>>>();
+    let current_heir_configs = use_context::<Resource<CheapClone<HashSet<HeirConfig>>>>();
+    // Whether each heir is in a current Heritage Configuration, unknown while loading
+    let heir_items = use_memo(move || {
+        let current_heir_configs = current_heir_configs.read();
+        composite_heirs
+            .read()
+            .iter()
+            .map(|composite_heir| {
+                let in_current_config = current_heir_configs
+                    .as_ref()
+                    .map(|heir_configs| heir_configs.contains(&*composite_heir.heir_config));
+                (composite_heir.clone(), in_current_config)
+            })
+            .collect::<Vec<_>>()
+    });
     let service_loading = service_heirs.read().is_none();
+    // Heirs from the database are still listed when the service ones cannot be retrieved
+    let service_error = use_memo(move || {
+        match (
+            &*state_management::SERVICE_AUTH_STATE.read(),
+            &*service_heirs.read(),
+        ) {
+            (Some(ServiceAuthState::Connected { .. }), Some(Err(e))) => Some(e.clone()),
+            _ => None,
+        }
+    });
 
     use_drop(|| log::debug!("HeirList Dropped"));
 
     rsx! {
         div { class: "container mx-auto grid grid-cols-[repeat(auto-fill,var(--container-2xs))] gap-6 justify-center",
-            for (heir_index , composite_heir) in composite_heirs.read().iter().enumerate() {
+            for (heir_index , heir_item) in heir_items.read().iter().enumerate() {
                 MaybeHighlight {
                     step: OnboardingStep::ClickHeirCard,
-                    context_filter: OnboardingContextItemId::HeirName.item(composite_heir.name.to_string()),
+                    context_filter: OnboardingContextItemId::HeirName.item(heir_item.0.name.to_string()),
                     div {
                         class: "cursor-pointer transition-transform hover:scale-105",
                         onclick: move |_| {
                             navigator().push(Route::HeirView { heir_index });
                         },
-                        LoadedComponent::<UIHeirItem> { input: composite_heir.ref_into() }
+                        LoadedComponent::<UIHeirItem> { input: heir_item.ref_into() }
                     }
                 }
             }
@@ -141,6 +171,16 @@ fn HeirList() -> Element {
         
 
         }
+        if let Some(error) = service_error() {
+            LoadedErrorCard {
+                title: "Service Heirs",
+                error,
+                on_retry: move |_| {
+                    let mut service_heirs = service_heirs;
+                    service_heirs.restart();
+                },
+            }
+        }
     }
 }
 
@@ -152,10 +192,13 @@ struct UIHeirItem {
     badges: UIHeirBadges,
     service_loading: bool,
 }
-impl LoadedSuccessConversionMarker for TypeCouple<CompositeHeir, UIHeirItem> {}
-impl FromRef<CompositeHeir> for UIHeirItem {
-    fn from_ref(composite_heir: &CompositeHeir) -> Self {
-        let badges = UIHeirBadges::from_ref(composite_heir);
+impl LoadedSuccessConversionMarker for TypeCouple<(CompositeHeir, Option<bool>), UIHeirItem> {}
+impl FromRef<(CompositeHeir, Option<bool>)> for UIHeirItem {
+    fn from_ref((composite_heir, in_current_config): &(CompositeHeir, Option<bool>)) -> Self {
+        let mut badges = UIHeirBadges::from_ref(composite_heir);
+        if let Some(in_current_config) = *in_current_config {
+            badges = badges.with_readiness(composite_heir, in_current_config);
+        }
 
         let CompositeHeir {
             name, heir_config, ..
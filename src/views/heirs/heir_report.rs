@@ -0,0 +1,226 @@
+use crate::prelude::*;
+
+use btc_heritage_wallet::{
+    btc_heritage::{utils::timestamp_now, HeirConfig},
+    heritage_service_api_client::HeirContact,
+};
+
+use state_management::EstateWallet;
+
+use crate::{
+    components::{
+        export::{use_export_directory, write_export_files, ExportToDirectory, CAN_EXPORT},
+        svg::{DrawSvg, FileDownload, SvgSize::Size4},
+    },
+    utils::{
+        heir_config_type_to_string, html_escape, timestamp_to_date_string, timestamp_to_file_string,
+    },
+};
+
+const REPORT_STYLE: &str = "\
+    body { font-family: sans-serif; margin: 2em; color: black; }\
+    h1 { margin-bottom: 0.2em; }\
+    section { break-inside: avoid; margin-top: 1.5em; }\
+    table { border-collapse: collapse; margin: 0.5em 0; }\
+    th, td { padding: 0.2em 1em 0.2em 0; text-align: left; vertical-align: top; }\
+    .references th, .references td { border-bottom: 1px solid #999; }\
+    .notice { border: 1px solid black; padding: 0.5em 1em; }\
+    .mono { font-family: monospace; }";
+
+/// Rows of the report table listing the Heritage Configurations referencing `heir_config`
+fn heir_references_rows(heir_config: &HeirConfig, wallets: &[EstateWallet]) -> String {
+    let mut rows = String::new();
+    for wallet in wallets {
+        for ehc in wallet.heritage_configs.iter() {
+            let Some(position) = ehc
+                .heirs
+                .iter()
+                .position(|eh| eh.heir_config == *heir_config)
+            else {
+                continue;
+            };
+            let maturity = ehc.heirs[position]
+                .spendable_ts
+                .map_or_else(|| "-".to_owned(), timestamp_to_date_string);
+            rows.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{maturity}</td></tr>",
+                html_escape(&wallet.name),
+                if ehc.current { "Current" } else { "Previous" },
+                position + 1,
+            ));
+        }
+    }
+    rows
+}
+
+/// Build the printable report of the heirs, as a self-contained HTML document
+///
+/// For each heir, the report gives its key fingerprint, its contact channels and the
+/// Heritage Configurations of the wallets referencing it with the date it can inherit.
+/// Heirs referenced by a configuration but unknown to the application are listed by fingerprint.
+fn heir_report_html(heirs: &[CompositeHeir], wallets: &[EstateWallet]) -> String {
+    let mut html = format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\">\
+        <title>Heirs Report</title><style>{REPORT_STYLE}</style></head><body>\
+        <h1>Heirs Report</h1>\
+        <p>Date: {}</p>\
+        <div class=\"notice\"><p>This report lists the heirs of the Heritage wallets and when they \
+        can inherit. It contains no private key and does NOT give access to any bitcoin.</p>\
+        <p>Each heir needs its own seed mnemonic, and its password if any, to claim its inheritance \
+        with the Heritage wallet application. The maturity is the earliest date the heir can \
+        spend the bitcoins locked by a Heritage Configuration, as long as the owner does not \
+        move them before.</p></div>",
+        timestamp_to_date_string(timestamp_now()),
+    );
+
+    let mut unknown_heir_configs: Vec<&HeirConfig> = vec![];
+    for heir_config in wallets
+        .iter()
+        .flat_map(|wallet| wallet.heritage_configs.iter())
+        .flat_map(|ehc| ehc.heirs.iter())
+        .map(|eh| &eh.heir_config)
+    {
+        if !heirs.iter().any(|heir| *heir.heir_config == *heir_config)
+            && !unknown_heir_configs.contains(&heir_config)
+        {
+            unknown_heir_configs.push(heir_config);
+        }
+    }
+
+    let heir_sections = heirs
+        .iter()
+        .map(|heir| (Some(heir), &*heir.heir_config))
+        .chain(unknown_heir_configs.into_iter().map(|hc| (None, hc)));
+    for (heir, heir_config) in heir_sections {
+        let name = heir.map_or_else(|| "Unknown heir".to_owned(), |heir| html_escape(&heir.name));
+        let mut contacts = vec![];
+        if let Some(Some(service_heir)) = heir.and_then(|heir| heir.service_heir.as_ref()) {
+            contacts.push(html_escape(&format!(
+                "Email: {}",
+                service_heir.main_contact.email
+            )));
+            for contact in service_heir.additional_contacts.iter() {
+                match contact {
+                    HeirContact::Email { email } => {
+                        contacts.push(html_escape(&format!("Email: {email}")))
+                    }
+                }
+            }
+        }
+        let contacts = if contacts.is_empty() {
+            "None registered on the Heritage service".to_owned()
+        } else {
+            contacts.join("<br>")
+        };
+        html.push_str(&format!(
+            "<section><h2>{name}</h2><table>\
+            <tr><td>Type</td><td>{}</td></tr>\
+            <tr><td>Key Fingerprint</td><td class=\"mono\">{}</td></tr>\
+            <tr><td>Contacts</td><td>{contacts}</td></tr>\
+            </table>",
+            heir_config_type_to_string(heir_config),
+            heir_config.fingerprint(),
+        ));
+        let rows = heir_references_rows(heir_config, wallets);
+        if rows.is_empty() {
+            html.push_str("<p>Not referenced by any Heritage Configuration.</p>");
+        } else {
+            html.push_str(&format!(
+                "<table class=\"references\"><tr><th>Wallet</th><th>Heritage Configuration</th>\
+                <th>Position</th><th>Maturity</th></tr>{rows}</table>"
+            ));
+        }
+        html.push_str("</section>");
+    }
+
+    let failed_wallets = wallets
+        .iter()
+        .filter(|wallet| wallet.error.is_some())
+        .map(|wallet| html_escape(&wallet.name))
+        .collect::<Vec<_>>();
+    if !failed_wallets.is_empty() {
+        html.push_str(&format!(
+            "<p>The Heritage Configurations of these wallets could not be loaded and may be \
+            missing from this report: {}</p>",
+            failed_wallets.join(", ")
+        ));
+    }
+    html.push_str("</body></html>");
+    html
+}
+
+/// Component saving the report of all the heirs, to print and attach to a will
+#[component]
+pub(super) fn HeirReportExport() -> Element {
+    log::debug!("HeirReportExport Rendered");
+
+    let database_service = state_management::use_database_service();
+    let service_client_service = state_management::use_service_client_service();
+    let blockchain_provider_service = state_management::use_blockchain_provider_service();
+    let composite_heirs = use_context::<Memo<Vec<CompositeHeir>>>();
+
+    let mut saving = use_signal(|| false);
+
+    let export_directory = use_export_directory();
+    let export_path = use_memo(move || {
+        let ts_str = timestamp_to_file_string(timestamp_now());
+        format!("{}/heirs-report-{ts_str}.html", export_directory.read())
+    });
+
+    let save_report = move |_| async move {
+        *saving.write() = true;
+        let wallet_names = state_management::list_wallet_names(database_service)
+            .await
+            .unwrap_or_default();
+        let wallets = futures_util::future::join_all(wallet_names.into_iter().map(|name| {
+            state_management::estate_wallet(
+                database_service,
+                service_client_service,
+                blockchain_provider_service,
+                name,
+            )
+        }))
+        .await;
+        let report = heir_report_html(&composite_heirs.read(), &wallets);
+        let file_path = export_path();
+        if write_export_files("heirs report", [(file_path.clone(), report)]) {
+            alert_info(format!(
+                "Heirs report saved to: {file_path}. Print it from your web browser."
+            ));
+            _ = open::that_in_background(file_path.as_str());
+        }
+        *saving.write() = false;
+    };
+
+    use_drop(|| log::debug!("HeirReportExport Dropped"));
+
+    rsx! {
+        if CAN_EXPORT {
+            div { class: "rounded-box border border-base-content/5 shadow-md p-4 my-4 max-w-7xl mx-auto",
+                h2 { class: "text-2xl font-bold mb-4", "Heirs Report" }
+
+                div { class: "text-sm font-light mb-4",
+                    "Save a printable report of your heirs to attach to your will: their names, key
+                    fingerprints and contacts, the Heritage Configurations referencing them and
+                    when they can inherit. It contains no private key."
+                }
+
+                ExportToDirectory {
+                    directory: export_directory,
+                    display_path: ReadOnlySignal::from(export_path),
+                    button {
+                        class: "btn",
+                        onclick: save_report,
+                        disabled: saving() || composite_heirs.read().is_empty(),
+                        if saving() {
+                            span { class: "loading loading-spinner" }
+                        } else {
+                            DrawSvg::<FileDownload> { size: Size4 }
+                        }
+                        "Save Heirs Report"
+                    }
+                }
+            }
+        }
+    }
+}
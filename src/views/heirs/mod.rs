@@ -1,9 +1,16 @@
 use crate::prelude::*;
 
+use btc_heritage_wallet::{
+    heritage_service_api_client::Heir as ServiceHeir, AnyKeyProvider, Heir as DbHeir,
+};
+
+use crate::utils::{CCStr, CheapClone};
+
 pub mod configuration;
 pub mod heir;
 pub mod heir_create;
 pub mod heir_list;
+mod heir_report;
 
 #[component]
 pub fn HeirsWrapperLayout() -> Element {
@@ -11,15 +18,75 @@ pub fn HeirsWrapperLayout() -> Element {
 
     let database_heirs = helper_hooks::use_resource_database_heirs();
     let service_heirs = helper_hooks::use_resource_service_heirs();
-    let composite_heirs = helper_hooks::use_memo_heirs(database_heirs, service_heirs);
+    let heir_service_links = helper_hooks::use_resource_heir_service_links();
+    helper_hooks::use_debug_snapshot_with(
+        "heirs/database".to_owned(),
+        database_heirs,
+        describe_database_heirs,
+    );
+    helper_hooks::use_debug_snapshot_with(
+        "heirs/service".to_owned(),
+        service_heirs,
+        describe_service_heirs,
+    );
+    let composite_heirs =
+        helper_hooks::use_memo_heirs(database_heirs, service_heirs, heir_service_links);
+    let current_heir_configs = helper_hooks::use_resource_current_heir_configs();
 
     // Provide the heir resources to all child that may want it
     use_context_provider(|| database_heirs);
     use_context_provider(|| service_heirs);
+    use_context_provider(|| heir_service_links);
     use_context_provider(|| composite_heirs);
+    use_context_provider(|| current_heir_configs);
 
     use_drop(|| log::debug!("HeirsWrapperLayout Dropped"));
     rsx! {
         Outlet::<crate::Route> {}
     }
 }
+
+/// Debug snapshot of the database heirs
+///
+/// Only names, fingerprints and key provider kinds: a local key holds the mnemonic of the heir.
+fn describe_database_heirs(heirs: &Option<Vec<CheapClone<DbHeir>>>) -> String {
+    let Some(heirs) = heirs else {
+        return "Loading".to_owned();
+    };
+    heirs
+        .iter()
+        .map(|heir| {
+            let key_provider = match heir.key_provider() {
+                AnyKeyProvider::None => "none",
+                AnyKeyProvider::LocalKey(_) => "local key",
+                AnyKeyProvider::Ledger(_) => "ledger",
+            };
+            format!(
+                "{} - {} - key provider: {key_provider}",
+                heir.name,
+                heir.heir_config.fingerprint()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Debug snapshot of the service heirs, without their contacts
+fn describe_service_heirs(heirs: &Option<Result<Vec<CheapClone<ServiceHeir>>, CCStr>>) -> String {
+    match heirs {
+        None => "Loading".to_owned(),
+        Some(Err(e)) => format!("Error: {e}"),
+        Some(Ok(heirs)) => heirs
+            .iter()
+            .map(|heir| {
+                format!(
+                    "{} - {} - permissions: {:?}",
+                    heir.display_name,
+                    heir.heir_config.fingerprint(),
+                    heir.permissions
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
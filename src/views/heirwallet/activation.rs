@@ -0,0 +1,161 @@
+use crate::prelude::*;
+
+use std::collections::HashMap;
+
+use btc_heritage_wallet::btc_heritage::utils::timestamp_now;
+
+use state_management::{ActivationStep, ActivationWorkflow};
+
+use crate::utils::{timestamp_to_string, CCStr};
+
+/// Guided workflow for an heir once the owner has passed away
+///
+/// The progress is saved per heir wallet, and the heir can ask to be reminded
+/// when the next inheritance matures.
+#[component]
+pub(super) fn ActivationWorkflowSection(heirwallet_name: CCStr) -> Element {
+    log::debug!("ActivationWorkflowSection Rendered");
+
+    let database_service = state_management::use_database_service();
+    let heirwallet_contextualized_heritages =
+        use_context::<FMemo<HashMap<CCStr, ContextualizedHeritages>>>();
+
+    let hwn = heirwallet_name.clone();
+    let mut workflow = use_resource(move || {
+        let heirwallet_name = hwn.clone();
+        async move {
+            state_management::load_activation_workflow(database_service, heirwallet_name)
+                .await
+                .ok()
+                .flatten()
+        }
+    });
+
+    // Earliest maturity still to come among the inheritances
+    let next_maturity = use_memo(move || {
+        let now = timestamp_now();
+        heirwallet_contextualized_heritages
+            .lrmap_ok(|h| {
+                h.values()
+                    .flat_map(|ch| ch.heritages.iter())
+                    .filter_map(|h| h.maturity)
+                    .filter(|ts| *ts > now)
+                    .min()
+            })
+            .flatten()
+    });
+
+    let mut in_operation = use_signal(|| false);
+    let save = move |new_workflow: Option<ActivationWorkflow>| {
+        let heirwallet_name = heirwallet_name.clone();
+        async move {
+            *in_operation.write() = true;
+            match state_management::save_activation_workflow(
+                database_service,
+                heirwallet_name,
+                new_workflow,
+            )
+            .await
+            {
+                Ok(()) => workflow.restart(),
+                Err(e) => alert_failure("Failed to save the workflow", e),
+            }
+            *in_operation.write() = false;
+        }
+    };
+
+    use_drop(|| log::debug!("ActivationWorkflowSection Dropped"));
+
+    let Some(current_workflow) = workflow.cloned().flatten() else {
+        let save = save.clone();
+        return rsx! {
+            div { class: "collapse collapse-arrow bg-base-200 my-4 max-w-3xl mx-auto",
+                input { r#type: "checkbox" }
+                div { class: "collapse-title text-lg font-medium", "The owner has passed away?" }
+                div { class: "collapse-content flex flex-col gap-4",
+                    div { class: "text-sm",
+                        "We are sorry for your loss. A guided checklist can walk you through \
+                        claiming your inheritance, at your own pace."
+                    }
+                    button {
+                        class: "btn btn-primary self-start",
+                        disabled: in_operation(),
+                        onclick: move |_| {
+                            save(
+                                Some(ActivationWorkflow {
+                                    started_at: timestamp_now(),
+                                    ..Default::default()
+                                }),
+                            )
+                        },
+                        "Start the Guided Checklist"
+                    }
+                }
+            }
+        };
+    };
+
+    rsx! {
+        div { class: "rounded-box border border-base-content/5 shadow-md p-4 my-4 max-w-3xl mx-auto",
+            h2 { class: "text-2xl font-bold mb-4", "Claiming Your Inheritance" }
+            div { class: "flex flex-col gap-4",
+                for step in ActivationStep::list() {
+                    label { key: "{step:?}", class: "label items-start gap-4 text-wrap",
+                        input {
+                            r#type: "checkbox",
+                            class: "checkbox",
+                            disabled: in_operation(),
+                            checked: current_workflow.completed_steps.contains(&step),
+                            onchange: {
+                                let save = save.clone();
+                                let current_workflow = current_workflow.clone();
+                                move |evt: Event<FormData>| {
+                                    let mut new_workflow = current_workflow.clone();
+                                    if evt.checked() {
+                                        new_workflow.completed_steps.insert(step);
+                                    } else {
+                                        new_workflow.completed_steps.remove(&step);
+                                    }
+                                    save(Some(new_workflow))
+                                }
+                            },
+                        }
+                        div {
+                            div { class: "font-semibold text-base-content", {step.title()} }
+                            div { class: "text-sm", {step.explanation()} }
+                        }
+                    }
+                }
+            }
+            div { class: "flex flex-row flex-wrap gap-4 items-center mt-4",
+                if let Some(reminder_ts) = current_workflow.reminder_ts {
+                    div { class: "text-sm", "Reminder scheduled on {timestamp_to_string(reminder_ts)}" }
+                } else if let Some(next_maturity) = next_maturity() {
+                    button {
+                        class: "btn btn-outline btn-primary",
+                        disabled: in_operation(),
+                        onclick: {
+                            let save = save.clone();
+                            let current_workflow = current_workflow.clone();
+                            move |_| {
+                                save(
+                                    Some(ActivationWorkflow {
+                                        reminder_ts: Some(next_maturity),
+                                        ..current_workflow.clone()
+                                    }),
+                                )
+                            }
+                        },
+                        "Remind Me on {timestamp_to_string(next_maturity)}"
+                    }
+                }
+                button {
+                    class: "btn btn-outline",
+                    disabled: in_operation(),
+                    onclick: move |_| save(None),
+                    "Close the Checklist"
+                }
+            }
+        }
+    }
+}
@@ -76,8 +76,7 @@ pub(super) fn DeleteHeirWalletConfig() -> Element {
                 navigator().push(Route::HeirWalletListView {});
             }
             Err(e) => {
-                log::info!("Heir Wallet deletion failed: {e}");
-                alert_error(format!("Heir Wallet deletion failed: {e}"));
+                alert_failure("Heir Wallet deletion failed", e);
             }
         }
 
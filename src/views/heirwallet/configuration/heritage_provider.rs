@@ -0,0 +1,136 @@
+use crate::prelude::*;
+
+use btc_heritage_wallet::{btc_heritage::HeritageWalletBackup, HeirWallet};
+
+use crate::{
+    components::{
+        badge::{ExternalDependencyStatus, HeritageProviderType},
+        inputs::BackupRestoreSection,
+    },
+    utils::CCStr,
+};
+
+/// Switch the Heritage Provider of the heir wallet between the service and a local one
+///
+/// The name and the Key Provider of the heir wallet are preserved, so a family that
+/// stops using the service does not have to delete and recreate its heir wallets.
+#[component]
+pub(super) fn HeritageProviderSwitchConfig(heirwallet_name: CCStr) -> Element {
+    log::debug!("HeritageProviderSwitchConfig Rendered");
+
+    let database_service = state_management::use_database_service();
+    let service_client_service = state_management::use_service_client_service();
+
+    let mut heirwallet = use_context::<AsyncSignal<HeirWallet>>();
+    let online_status =
+        use_context::<Memo<Option<(HeritageProviderType, ExternalDependencyStatus)>>>();
+
+    let current_provider_type = use_memo(move || online_status().map(|(hpt, _)| hpt));
+
+    let heritage_wallet_backup_state: Signal<Result<HeritageWalletBackup, CCStr>> =
+        use_signal(|| Err(CCStr::from("No backup provided")));
+    let mut in_operation = use_signal(|| false);
+
+    let switch_provider = move |to_local: bool| {
+        let heirwallet_name = heirwallet_name.clone();
+        async move {
+            let backup = if to_local {
+                match heritage_wallet_backup_state() {
+                    Ok(backup) => Some(backup),
+                    Err(e) => {
+                        alert_error(e);
+                        return;
+                    }
+                }
+            } else {
+                None
+            };
+            *in_operation.write() = true;
+            match state_management::switch_heirwallet_heritage_provider(
+                database_service,
+                service_client_service,
+                heirwallet_name,
+                backup,
+            )
+            .await
+            {
+                Ok(new_heirwallet) => {
+                    heirwallet.write().replace(new_heirwallet);
+                    alert_success("Heritage Provider switched");
+                }
+                Err(e) => alert_failure("Failed to switch the Heritage Provider", e),
+            }
+            *in_operation.write() = false;
+        }
+    };
+
+    use_drop(|| log::debug!("HeritageProviderSwitchConfig Dropped"));
+
+    rsx! {
+        div { class: "rounded-box border border-base-content/5 shadow-md p-4 my-4",
+            h2 { class: "text-2xl font-bold mb-4", "Heritage Provider" }
+
+            match current_provider_type() {
+                Some(HeritageProviderType::Service) => rsx! {
+                    div { class: "text-sm font-light mb-4",
+                        "This heir wallet finds its inheritances using the Heritage Service. \
+                        To stop depending on the service, provide the Online Wallet Backup of the owner \
+                        and switch to a Local Heritage Provider."
+                    }
+                    BackupRestoreSection { heritage_wallet_backup_state, expected_fingerprint: None }
+                    button {
+                        class: "btn btn-primary mt-4",
+                        disabled: heritage_wallet_backup_state.read().is_err() || in_operation(),
+                        onclick: {
+                            let switch_provider = switch_provider.clone();
+                            move |_| switch_provider(true)
+                        },
+                        "Switch to Local Heritage Provider"
+                    }
+                },
+                Some(HeritageProviderType::LocalWallet) => rsx! {
+                    div { class: "text-sm font-light mb-4",
+                        "This heir wallet finds its inheritances using the backup of the owner wallet. \
+                        Switch to the Heritage Service to let it find them for you."
+                    }
+                    button {
+                        class: "btn btn-primary",
+                        disabled: in_operation(),
+                        onclick: {
+                            let switch_provider = switch_provider.clone();
+                            move |_| switch_provider(false)
+                        },
+                        "Switch to Heritage Service"
+                    }
+                },
+                Some(HeritageProviderType::None) => rsx! {
+                    div { class: "text-sm font-light mb-4",
+                        "This heir wallet has no Heritage Provider and can only sign transactions."
+                    }
+                    BackupRestoreSection { heritage_wallet_backup_state, expected_fingerprint: None }
+                    div { class: "flex flex-row gap-4 mt-4",
+                        button {
+                            class: "btn btn-primary",
+                            disabled: heritage_wallet_backup_state.read().is_err() || in_operation(),
+                            onclick: {
+                                let switch_provider = switch_provider.clone();
+                                move |_| switch_provider(true)
+                            },
+                            "Use a Local Heritage Provider"
+                        }
+                        button {
+                            class: "btn btn-primary",
+                            disabled: in_operation(),
+                            onclick: {
+                                let switch_provider = switch_provider.clone();
+                                move |_| switch_provider(false)
+                            },
+                            "Use the Heritage Service"
+                        }
+                    }
+                },
+                None => rsx! {},
+            }
+        }
+    }
+}
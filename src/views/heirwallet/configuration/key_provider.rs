@@ -0,0 +1,97 @@
+use crate::prelude::*;
+
+use btc_heritage_wallet::HeirWallet;
+
+use crate::{
+    components::{
+        badge::{ExternalDependencyStatus, KeyProviderType},
+        create_key_provider::{KeyProviderConfig, KeyProviderSection, KeyProviderSectionFlavor},
+    },
+    utils::CCStr,
+};
+
+/// Add the key of the heir to a watch-only heir wallet, when it is time to claim
+#[component]
+pub(super) fn AddKeyProviderConfig(heirwallet_name: CCStr) -> Element {
+    log::debug!("AddKeyProviderConfig Rendered");
+
+    let database_service = state_management::use_database_service();
+    let service_client_service = state_management::use_service_client_service();
+
+    let mut heirwallet = use_context::<AsyncSignal<HeirWallet>>();
+    let keyprovider_status =
+        use_context::<Memo<Option<(KeyProviderType, ExternalDependencyStatus)>>>();
+
+    let is_watch_only =
+        use_memo(move || matches!(keyprovider_status(), Some((KeyProviderType::None, _))));
+
+    let key_provider_config_state = use_signal(|| Err(()));
+    let wallet_component_error = use_signal(|| None::<CCStr>);
+    let config_valid = use_memo(move || {
+        key_provider_config_state
+            .read()
+            .as_ref()
+            .is_ok_and(|config| !matches!(config, KeyProviderConfig::None))
+    });
+
+    let mut in_operation = use_signal(|| false);
+    let add_key_provider = move |_| {
+        let heirwallet_name = heirwallet_name.clone();
+        async move {
+            let Ok(config) = key_provider_config_state() else {
+                return;
+            };
+            *in_operation.write() = true;
+            let result = match config.build().await {
+                Ok(key_provider) => {
+                    state_management::set_heirwallet_key_provider(
+                        database_service,
+                        service_client_service,
+                        heirwallet_name,
+                        key_provider,
+                    )
+                    .await
+                }
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(new_heirwallet) => {
+                    heirwallet.write().replace(new_heirwallet);
+                    alert_success("Key Provider added, the heir wallet can now sign");
+                }
+                Err(e) => alert_failure("Failed to add the Key Provider", e),
+            }
+            *in_operation.write() = false;
+        }
+    };
+
+    use_drop(|| log::debug!("AddKeyProviderConfig Dropped"));
+
+    if !is_watch_only() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div { class: "rounded-box border border-base-content/5 shadow-md p-4 my-4",
+            h2 { class: "text-2xl font-bold mb-4", "Add the Heir Key" }
+
+            div { class: "text-sm font-light mb-4",
+                "This heir wallet is watch-only: it follows the inheritances but cannot claim them. \
+                When it is time to claim, restore the seed of the heir or connect their Ledger device."
+            }
+
+            KeyProviderSection {
+                key_provider_config_state,
+                wallet_component_error: wallet_component_error.into(),
+                flavor: KeyProviderSectionFlavor::HeirWallet,
+            }
+
+            button {
+                class: "btn btn-primary mt-4",
+                disabled: !config_valid() || in_operation(),
+                onclick: add_key_provider,
+                "Add the Key Provider"
+            }
+        }
+    }
+}
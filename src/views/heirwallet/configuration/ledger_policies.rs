@@ -0,0 +1,269 @@
+use crate::prelude::*;
+
+use std::sync::Arc;
+
+use btc_heritage_wallet::{
+    btc_heritage::HeritageWalletBackup, ledger::WalletPolicy, AnyKeyProvider, HeirWallet,
+};
+
+use crate::{
+    components::{
+        help::{HelpPopover, HelpTopic},
+        inputs::BackupRestoreSection,
+        ledger_policies::{UILedgerPoliciesTable, UIWalletPolicy},
+        ledger_prompt::{LedgerCancel, LedgerPrompt, LedgerPromptsOverlay},
+        svg::{AlertOutline, CheckCircle, DrawSvg},
+    },
+    utils::CCStr,
+};
+
+/// Registration of the Heritage Configurations of the owner on the Ledger of the heir
+///
+/// The Ledger only signs for registered policies, so the heir registers the policies
+/// built from the descriptors backup of the owner before claiming an inheritance.
+#[component]
+pub(super) fn HeirWalletLedgerPoliciesConfig(heirwallet_name: CCStr) -> Element {
+    log::debug!("HeirWalletLedgerPoliciesConfig Rendered");
+
+    let database_service = state_management::use_database_service();
+    let service_client_service = state_management::use_service_client_service();
+
+    let mut heirwallet = use_context::<AsyncSignal<HeirWallet>>();
+
+    let uses_ledger = helper_hooks::use_memo_heirwallet_uses_ledger(heirwallet);
+    let ledger_registered_policies =
+        helper_hooks::use_memo_heirwallet_ledger_registered_policies(heirwallet);
+
+    let heritage_wallet_backup_state: Signal<Result<HeritageWalletBackup, CCStr>> =
+        use_signal(|| Err(CCStr::default()));
+    let owner_backup = use_memo(move || {
+        let rbkp = heritage_wallet_backup_state();
+        rbkp.is_ok().then_some(rbkp)
+    });
+    let ledger_unregistered_policies = helper_hooks::use_memo_ledger_unregistered_policies(
+        ledger_registered_policies,
+        owner_backup,
+    );
+
+    let mut in_operation = use_signal(|| false);
+    let mut register_modal = use_signal(|| false);
+    let mut current_policy = use_signal(|| None::<UIWalletPolicy>);
+    let mut current_policy_index = use_signal(|| 0);
+    let mut ledger_prompts = use_signal(Vec::new);
+    let current_prompt = use_memo(move || current_policy_index().checked_sub(1));
+    let ledger_cancel = use_hook(LedgerCancel::default);
+
+    let has_policies_to_register =
+        use_memo(move || match ledger_unregistered_policies.read().as_ref() {
+            Some(Ok(h)) => !h.is_empty(),
+            _ => false,
+        });
+
+    let ledger_ready = use_memo(move || state_management::ledger_is_ready().is_some());
+
+    let can_register = use_memo(move || has_policies_to_register() && ledger_ready());
+
+    let register_policies = {
+        let heirwallet_name = heirwallet_name.clone();
+        let ledger_cancel = ledger_cancel.clone();
+        move |_| {
+            let heirwallet_name = heirwallet_name.clone();
+            let ledger_cancel = ledger_cancel.clone();
+            async move {
+                let policies_ref = ledger_unregistered_policies.read();
+                let policies = match policies_ref.as_ref() {
+                    Some(Ok(policies)) => policies,
+                    Some(Err(e)) => {
+                        alert_failure("No unregistered policies available", e);
+                        return;
+                    }
+                    None => {
+                        alert_failure(
+                            "No unregistered policies available",
+                            "the policies are not loaded yet",
+                        );
+                        return;
+                    }
+                };
+
+                *in_operation.write() = true;
+                *register_modal.write() = true;
+                *current_policy_index.write() = 0usize;
+                *ledger_prompts.write() = policies
+                    .keys()
+                    .enumerate()
+                    .map(|(i, account_id)| LedgerPrompt {
+                        title: CCStr::from(format!(
+                            "Register policy {} of {}",
+                            i + 1,
+                            policies.len()
+                        )),
+                        detail: Some(CCStr::from(format!(
+                            "Review and approve the Account #{account_id}"
+                        ))),
+                    })
+                    .collect();
+
+                let close = move || {
+                    *current_policy.write() = None;
+                    *in_operation.write() = false;
+                    *register_modal.write() = false;
+                };
+
+                let mut owned_heirwallet = match state_management::get_heirwallet(
+                    database_service,
+                    service_client_service,
+                    heirwallet_name,
+                )
+                .await
+                {
+                    Ok(owned_heirwallet) => owned_heirwallet,
+                    Err(e) => {
+                        alert_failure("Failed to load the heir wallet from the Database", e);
+                        close();
+                        return;
+                    }
+                };
+                let ledger_key = match owned_heirwallet.key_provider_mut() {
+                    AnyKeyProvider::Ledger(ledger_key) => ledger_key,
+                    _ => {
+                        alert_error("Heir wallet does not use Ledger key provider");
+                        close();
+                        return;
+                    }
+                };
+                let result = ledger_cancel
+                    .run(
+                        ledger_key.register_policies(policies.values(), |wp: &WalletPolicy| {
+                            *current_policy_index.write() += 1;
+                            *current_policy.write() = Some(UIWalletPolicy::from_ref(wp));
+                        }),
+                    )
+                    .await;
+
+                match result {
+                    Ok(_) => {
+                        log::info!("Successfully registered Ledger policies");
+                        alert_success("Ledger policies registered successfully");
+                    }
+                    Err(e) => {
+                        alert_failure("Failed to register Ledger policies", e);
+                    }
+                }
+                // Temporarily puting into an Arc to share ownership with the blocking thread
+                let owned_heirwallet = Arc::new(owned_heirwallet);
+                if let Err(e) =
+                    state_management::save_heirwallet(database_service, owned_heirwallet.clone())
+                        .await
+                {
+                    alert_failure("Failed to save the heir wallet into the Database", e);
+                    close();
+                    return;
+                }
+                // Taking it back from the Arc
+                let owned_heirwallet =
+                    Arc::into_inner(owned_heirwallet).expect("save_heirwallet is finished");
+                // Putting it inside the AsyncSignal
+                heirwallet.write().replace(owned_heirwallet);
+
+                close();
+            }
+        }
+    };
+
+    use_drop(|| log::debug!("HeirWalletLedgerPoliciesConfig Dropped"));
+
+    // Only show if the heir wallet uses Ledger
+    if !uses_ledger() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div { class: "rounded-box border border-base-content/5 shadow-md p-4 my-4",
+            h2 { class: "text-2xl font-bold mb-4",
+                "Ledger Policies"
+                HelpPopover { topic: HelpTopic::LedgerPolicies }
+            }
+
+            div { class: "text-sm font-light mb-6",
+                "To claim an inheritance with your Ledger device, the Heritage Configuration of the owner that locks it must be registered on the device as a policy. "
+                "Provide the Online Wallet Backup the owner shared with you so the policies can be registered, without ever typing a mnemonic into this computer."
+            }
+
+            div { class: "collapse collapse-arrow bg-base-200 mb-4",
+                input { r#type: "checkbox", class: "collapse-input" }
+                div { class: "collapse-title text-lg font-medium", "Registered Heritage Configurations" }
+                div { class: "collapse-content",
+                    div { class: "text-sm text-base-content/70 mb-3",
+                        "The following Heritage Configurations are already registered on your Ledger device as policies:"
+                    }
+                    LoadedComponent::<UILedgerPoliciesTable> { input: ledger_registered_policies.into() }
+                }
+            }
+            div { class: "collapse collapse-arrow bg-base-200 mb-4",
+                input { r#type: "checkbox", class: "collapse-input" }
+                div { class: "collapse-title text-lg font-medium", "Owner Backup Input" }
+                div { class: "collapse-content",
+                    div { class: "text-sm text-base-content/70 mb-3",
+                        "Provide the Online Wallet Backup of the owner to register the policies of its Heritage Configurations:"
+                    }
+                    BackupRestoreSection { heritage_wallet_backup_state, expected_fingerprint: None }
+                }
+            }
+
+            if has_policies_to_register() {
+                div { class: "bg-base-100 rounded-lg p-4 mb-4 border border-warning",
+                    h3 { class: "text-lg font-semibold mb-3", "Unregistered Heritage Configurations" }
+                    div { class: "text-sm text-base-content/70 mb-3",
+                        "The following Heritage Configurations need to be registered on your Ledger device as policies:"
+                    }
+                    LoadedComponent::<UILedgerPoliciesTable> { input: ledger_unregistered_policies.into() }
+                }
+            }
+
+            div { class: "flex items-center gap-4",
+                button {
+                    class: "btn btn-primary",
+                    disabled: !can_register() || in_operation(),
+                    onclick: register_policies,
+                    if in_operation() {
+                        span { class: "loading loading-spinner loading-sm mr-2" }
+                        "Registering..."
+                    } else {
+                        DrawSvg::<CheckCircle> {}
+                        "Register Ledger Policies"
+                    }
+                }
+            }
+
+            // Registration progress overlay
+            LedgerPromptsOverlay {
+                is_open: register_modal,
+                title: "Registering Ledger Policies",
+                prompts: ledger_prompts,
+                current_prompt,
+                cancel: ledger_cancel,
+                div { class: "w-full flex flex-col gap-4",
+                    div { class: "text-center text-base italic",
+                        "Note that Ledger call it "
+                        span { class: "font-bold text-accent", "Account" }
+                    }
+
+                    div { class: "alert alert-warning",
+                        DrawSvg::<AlertOutline> {}
+                        div {
+                            div { class: "font-bold", "Verify on Ledger" }
+                            div { class: "text-sm",
+                                "Make sure the policy details displayed on your Ledger device exactly match the information shown below."
+                            }
+                        }
+                    }
+
+                    div { class: "bg-base-200 rounded-lg p-4",
+                        LoadedComponent::<UIWalletPolicy> { input: current_policy().into() }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,11 +1,15 @@
 use crate::prelude::*;
 
 mod delete;
+mod heritage_provider;
+mod key_provider;
+mod ledger_policies;
+mod owner_backups;
 
 use btc_heritage_wallet::HeirWallet;
 
 use crate::{
-    components::{inputs::RenameDatabaseItem, misc::BackButton},
+    components::{inputs::RenameDatabaseItem, misc::BackButton, tags::EditTags},
     utils::CCStr,
     Route,
 };
@@ -31,6 +35,11 @@ pub fn HeirWalletConfigurationView(heirwallet_name: CCStr) -> Element {
                 }
             },
             RenameDatabaseItem::<HeirWallet> {}
+            EditTags::<HeirWallet> {}
+            key_provider::AddKeyProviderConfig { heirwallet_name: heirwallet_name.clone() }
+            heritage_provider::HeritageProviderSwitchConfig { heirwallet_name: heirwallet_name.clone() }
+            owner_backups::OwnerBackupsConfig {}
+            ledger_policies::HeirWalletLedgerPoliciesConfig { heirwallet_name: heirwallet_name.clone() }
             delete::DeleteHeirWalletConfig {}
         }
     }
@@ -0,0 +1,68 @@
+use crate::prelude::*;
+
+use std::collections::HashMap;
+
+use btc_heritage_wallet::Heritage;
+
+use crate::{
+    components::badge::{ExternalDependencyStatus, HeritageProviderType},
+    utils::{CCStr, CheapClone},
+    Route,
+};
+
+/// Owner backup held by the Local Heritage Provider of the heir wallet
+///
+/// The Local Heritage Provider of `btc-heritage-wallet` is built from the backup of
+/// a single owner wallet. An heir inheriting from several owners creates one heir
+/// wallet per owner backup, all of them using the same seed.
+#[component]
+pub(super) fn OwnerBackupsConfig() -> Element {
+    log::debug!("OwnerBackupsConfig Rendered");
+
+    let online_status =
+        use_context::<Memo<Option<(HeritageProviderType, ExternalDependencyStatus)>>>();
+    let heirwallet_heritages =
+        use_context::<FResource<HashMap<CCStr, Vec<CheapClone<Heritage>>>>>();
+
+    let uses_local_heritage_provider = use_memo(move || {
+        online_status()
+            .map(|(online_type, _)| matches!(online_type, HeritageProviderType::LocalWallet))
+            .unwrap_or(false)
+    });
+    // Heritages are grouped by the id of the owner wallet they come from
+    let heritages_count = use_memo(move || {
+        heirwallet_heritages
+            .lrmap_ok(|heritages| heritages.values().map(Vec::len).sum::<usize>())
+            .unwrap_or_default()
+    });
+
+    use_drop(|| log::debug!("OwnerBackupsConfig Dropped"));
+
+    rsx! {
+        if uses_local_heritage_provider() {
+            div { class: "rounded-box border border-base-content/5 shadow-md p-4 my-4",
+                h2 { class: "text-2xl font-bold mb-4", "Owner Backups" }
+
+                div { class: "text-sm font-light mb-4",
+                    "This heir wallet finds its inheritances using the backup of a single owner wallet, \
+                    it cannot hold the backups of several owners. To inherit from other owners, \
+                    create a separate heir wallet for each owner backup with the same seed."
+                }
+
+                div { class: "flex flex-row gap-8 items-center",
+                    div { class: "flex flex-col",
+                        div { class: "text-base font-semibold", "Inheritances from this backup" }
+                        div { class: "text-sm font-light", "{heritages_count()}" }
+                    }
+                    button {
+                        class: "btn btn-primary",
+                        onclick: move |_| {
+                            navigator().push(Route::HeirWalletCreateView {});
+                        },
+                        "Create an Heir Wallet for Another Owner"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -2,12 +2,20 @@ use crate::prelude::*;
 
 use std::collections::HashMap;
 
+use btc_heritage_wallet::{
+    bitcoin::{address::NetworkUnchecked, Address},
+    btc_heritage::utils::timestamp_now,
+    HeirWallet, Heritage, HeritageProvider,
+};
+
 use crate::{
     components::{
         badge::{ExternalDependencyStatus, HeritageProviderType},
-        heritages::UIHeritage,
+        heritages::{HeritageAmountDisplaySelect, UIHeritage},
+        spend::signing_session::{SigningSession, SigningSessionPanel},
+        svg::{BankPlus, DrawSvg},
     },
-    utils::CCStr,
+    utils::{check_address_network, CCStr, CheapClone},
 };
 
 #[component]
@@ -16,6 +24,8 @@ pub(super) fn HeritagesList() -> Element {
 
     let heritage_provider_status =
         use_context::<Memo<Option<(HeritageProviderType, ExternalDependencyStatus)>>>();
+    let heirwallet_heritages =
+        use_context::<FResource<HashMap<CCStr, Vec<CheapClone<Heritage>>>>>();
     let heirwallet_contextualized_heritages =
         use_context::<FMemo<HashMap<CCStr, ContextualizedHeritages>>>();
 
@@ -47,14 +57,136 @@ pub(super) fn HeritagesList() -> Element {
     rsx! {
         div { class: "rounded-box border border-base-content/5 shadow-md bg-base-100 my-4 max-w-7xl mx-auto",
             // Title
-            h2 { class: "text-2xl font-bold p-4", "Inheritances List" }
+            div { class: "flex flex-row flex-wrap gap-4 items-center justify-between p-4",
+                h2 { class: "text-2xl font-bold", "Inheritances List" }
+                HeritageAmountDisplaySelect {}
+            }
             div { class: "p-4",
-                if heritage_to_display_count() == 0 {
-                    div { "No inheritances at this time" }
-                    div { class: "w-sm", {message} }
-                } else {
-                    div { class: "flex flex-col gap-6",
-                        LoadedComponent::<HashMap<CCStr,UIHeritage>> { input: heirwallet_contextualized_heritages.into() }
+                ResourceErrorBoundary { title: "Inheritances List", resource: heirwallet_heritages,
+                    if heritage_to_display_count() == 0 {
+                        div { "No inheritances at this time" }
+                        div { class: "w-sm", {message} }
+                    } else {
+                        div { class: "flex flex-col gap-6",
+                            LoadedComponent::<HashMap<CCStr,UIHeritage>> { input: heirwallet_contextualized_heritages.into() }
+                        }
+                        ClaimAllInheritances {}
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Claim every matured inheritance to a single address at once
+///
+/// Each inheritance is spent by its own transaction, all signed in a single
+/// signing session and broadcast together.
+#[component]
+fn ClaimAllInheritances() -> Element {
+    log::debug!("ClaimAllInheritances Rendered");
+
+    let heirwallet = use_context::<AsyncSignal<HeirWallet>>();
+    let heirwallet_heritages =
+        use_context::<FResource<HashMap<CCStr, Vec<CheapClone<Heritage>>>>>();
+
+    let matured_heritage_ids = use_memo(move || {
+        let now = timestamp_now();
+        heirwallet_heritages
+            .lrmap_ok(|heirwallet_heritages| {
+                let mut matured_heritage_ids = heirwallet_heritages
+                    .iter()
+                    .filter(|(_, heritages)| {
+                        heritages
+                            .iter()
+                            .any(|h| h.maturity.is_some_and(|m| m < now))
+                    })
+                    .map(|(heritage_id, _)| heritage_id.clone())
+                    .collect::<Vec<_>>();
+                matured_heritage_ids.sort();
+                matured_heritage_ids
+            })
+            .unwrap_or_default()
+    });
+
+    let mut address = use_signal(String::new);
+    let recipient_address = use_memo(move || {
+        let address = address.read();
+        match address.parse::<Address<NetworkUnchecked>>() {
+            Ok(addr) => check_address_network(addr).map(Some),
+            Err(e) if !address.is_empty() => Err(format!("Address invalid: {e}")),
+            _ => Ok(None),
+        }
+    });
+
+    let mut session = use_signal(SigningSession::default);
+    let mut preparing = use_signal(|| false);
+
+    let prepare_claims = move |_| async move {
+        let Ok(Some(address)) = recipient_address() else {
+            return;
+        };
+        *preparing.write() = true;
+        let mut new_session = SigningSession::default();
+        for heritage_id in matured_heritage_ids() {
+            let address = address.clone();
+            match heirwallet
+                .with(async |hw: &HeirWallet| hw.create_psbt(heritage_id.as_ref(), address).await)
+                .await
+            {
+                Ok((psbt, _)) => new_session.add(format!("Inheritance {heritage_id}"), psbt),
+                Err(e) => {
+                    alert_failure(format!("Failed to create the claim of {heritage_id}"), e);
+                    *preparing.write() = false;
+                    return;
+                }
+            }
+        }
+        session.set(new_session);
+        *preparing.write() = false;
+    };
+
+    use_drop(|| log::debug!("ClaimAllInheritances Dropped"));
+
+    rsx! {
+        if matured_heritage_ids.read().len() > 1 {
+            div { class: "collapse collapse-arrow bg-base-200 mt-6",
+                input { r#type: "checkbox" }
+                div { class: "collapse-title text-xl font-medium",
+                    "Claim all the matured inheritances ({matured_heritage_ids.read().len()})"
+                }
+                div { class: "collapse-content flex flex-col gap-4",
+                    fieldset { class: "fieldset",
+                        legend { class: "fieldset-legend", "Receiving address" }
+                        input {
+                            r#type: "text",
+                            class: "input w-full font-mono",
+                            class: if recipient_address.read().is_err() { "input-error" },
+                            placeholder: "An address of a wallet you control",
+                            disabled: preparing() || !session.read().psbts().is_empty(),
+                            value: address(),
+                            oninput: move |evt| address.set(evt.value()),
+                        }
+                        if let Err(e) = recipient_address() {
+                            div { class: "fieldset-label text-error", {e} }
+                        }
+                    }
+                    if session.read().psbts().is_empty() {
+                        div { class: "flex justify-center",
+                            button {
+                                class: "btn btn-primary",
+                                disabled: preparing() || !recipient_address.read().as_ref().is_ok_and(Option::is_some),
+                                onclick: prepare_claims,
+                                if preparing() {
+                                    span { class: "loading loading-spinner loading-sm" }
+                                } else {
+                                    DrawSvg::<BankPlus> {}
+                                }
+                                "Create the Transactions"
+                            }
+                        }
+                    } else {
+                        SigningSessionPanel::<HeirWallet> { session }
                     }
                 }
             }
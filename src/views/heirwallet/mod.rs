@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use crate::prelude::*;
 
+mod activation;
 pub mod configuration;
 mod heritages_list;
 pub mod spend;
@@ -33,6 +34,13 @@ pub fn HeirWalletWrapperLayout(heirwallet_name: CCStr) -> Element {
 
     let service_heritages = helper_hooks::use_resource_service_heritages();
     let heirwallet_heritages = helper_hooks::use_resource_heirwallet_heritages(heirwallet);
+    helper_hooks::use_debug_snapshot(
+        format!("heirwallet#{heirwallet_name}/heritages"),
+        heirwallet_heritages,
+    );
+    helper_hooks::use_debug_snapshot("service/heritages".to_owned(), service_heritages);
+
+    let heritage_amount_display = helper_hooks::use_resource_heritage_amount_display();
 
     let heirwallet_contextualized_heritages =
         helper_hooks::use_memo_heirwallet_contextualized_heritages(
@@ -53,6 +61,7 @@ pub fn HeirWalletWrapperLayout(heirwallet_name: CCStr) -> Element {
     use_context_provider(|| heirwallet_heritages);
 
     use_context_provider(|| heirwallet_contextualized_heritages);
+    use_context_provider(|| heritage_amount_display);
 
     use_context_provider(|| {
         OnboardingContextItemId::HeirWalletName.item(heirwallet_name.to_string())
@@ -181,6 +190,8 @@ pub fn HeirWalletView(heirwallet_name: CCStr) -> Element {
 
             heritages_list::HeritagesList {}
 
+            activation::ActivationWorkflowSection { heirwallet_name: heirwallet_name.clone() }
+
             OnboardingInfoModal { step: OnboardingStep::ModalExplainInheritancesList,
                 div { class: "flex flex-col gap-4 max-w-xl text-base",
                     p {
@@ -214,6 +225,30 @@ pub fn HeirWalletView(heirwallet_name: CCStr) -> Element {
                     }
                 }
             }
+
+            OnboardingInfoModal {
+                step: OnboardingStep::ModalHeirWaitForMaturity,
+                btn_text: "I understand",
+                div { class: "flex flex-col gap-4 max-w-xl text-base",
+                    p {
+                        "An inheritance can only be claimed once it is mature, that is after the
+                        date configured by the original owner. As long as the owner keeps using
+                        their wallet, this date keeps being pushed back."
+                    }
+                    if any_heritage_to_spend() {
+                        p {
+                            "Good news: at least one of your inheritances is already mature. The
+                            next steps will guide you through claiming it."
+                        }
+                    } else {
+                        p {
+                            "None of your inheritances is mature yet. You can close the application:
+                            the onboarding will resume when you come back to this wallet once an
+                            inheritance has become mature."
+                        }
+                    }
+                }
+            }
         }
     }
 }
@@ -69,7 +69,8 @@ pub fn HeirWalletSpendView(heirwallet_name: CCStr, heritage_id: CCStr) -> Elemen
 
     let cannot_sign_reason = use_memo(move || match keyprovider_status() {
         Some((KeyProviderType::None, _)) => Some((
-            "Your heir wallet does not have a Key Provider component.",
+            "Signing unavailable: your heir wallet is watch-only. \
+               Add the heir key in its configuration to claim the inheritance.",
             false,
         )),
         Some((KeyProviderType::LocalKey, ExternalDependencyStatus::NeedUserAction)) => Some((
@@ -79,7 +80,7 @@ pub fn HeirWalletSpendView(heirwallet_name: CCStr, heritage_id: CCStr) -> Elemen
         )),
         Some((KeyProviderType::Ledger, ExternalDependencyStatus::Unavailable)) => Some((
             "Your heir wallet uses a Ledger Hardware Wallet device, \
-               but none can currently serve your heir wallet.",
+               but none holding its seed is connected and ready.",
             false,
         )),
         Some((KeyProviderType::Ledger, ExternalDependencyStatus::NeedUserAction)) => Some((
@@ -6,6 +6,7 @@ use btc_heritage_wallet::{
 
 use crate::{
     components::{
+        alerts::{add_alert, Alert, AlertCategory},
         svg::{DrawSvg, SvgSize::Full, Sync},
         timestamp::UITimestamp,
     },
@@ -44,8 +45,7 @@ pub fn HeirWalletLocalSync() -> Element {
             match state_management::blockchain_factory(blockchain_provider_service).await {
                 Ok(bcf) => bcf,
                 Err(e) => {
-                    log::error!("{e}");
-                    alert_error(e);
+                    alert_failure("Failed to sync", e);
                     return;
                 }
             };
@@ -63,11 +63,13 @@ pub fn HeirWalletLocalSync() -> Element {
         {
             Ok(()) => {
                 log::info!("Heir Wallet synced with blockchain");
-                alert_success("Heir Wallet synced with blockchain");
+                add_alert(
+                    Alert::success("Heir Wallet synced with blockchain")
+                        .with_category(AlertCategory::Sync),
+                );
             }
             Err(e) => {
-                log::error!("{e}");
-                alert_error(e);
+                alert_failure("Failed to sync", e);
             }
         }
 
@@ -8,7 +8,7 @@ use btc_heritage_wallet::{
         AnyHeritageProvider, LocalWallet, ServiceBinding as HeritageServiceBinding,
     },
     heritage_service_api_client::Fingerprint,
-    AnyKeyProvider, BoundFingerprint, DatabaseItem, HeirWallet, LocalKey,
+    BoundFingerprint, DatabaseItem, HeirWallet, LocalKey,
 };
 
 use crate::{
@@ -27,6 +27,8 @@ use crate::{
     Route,
 };
 
+use state_management::SeedRole;
+
 /// Component for creating new heir wallets
 #[component]
 pub fn HeirWalletCreateView() -> Element {
@@ -147,33 +149,12 @@ fn HeirWalletCreateForm() -> Element {
         }
 
         let name = heirwallet_name_state().unwrap();
-        let network = bitcoin_network::get();
         let Ok(kp_config) = key_provider_config_state() else {
             return abort("Invalid Key Provider configuration");
         };
-        let kp = match kp_config {
-            KeyProviderConfig::None => AnyKeyProvider::None,
-            KeyProviderConfig::Local(local_key_creation_config) => {
-                let local_key = match local_key_creation_config {
-                    LocalKeyCreationConfig::New {
-                        word_count,
-                        password,
-                    } => LocalKey::generate(word_count, password, network),
-                    LocalKeyCreationConfig::Restore { mnemo, password } => {
-                        LocalKey::restore(mnemo, password, network)
-                    }
-                };
-                AnyKeyProvider::LocalKey(local_key)
-            }
-            KeyProviderConfig::Ledger => {
-                let ledger = match btc_heritage_wallet::LedgerKey::new(network).await {
-                    Ok(ledger) => ledger,
-                    Err(e) => {
-                        return abort(&format!("Could not create the Ledger key: {e}"));
-                    }
-                };
-                AnyKeyProvider::Ledger(ledger)
-            }
+        let kp = match kp_config.build().await {
+            Ok(kp) => kp,
+            Err(e) => return abort(&e),
         };
 
         let Ok(hp_config) = heritage_provider_config_state() else {
@@ -212,11 +193,12 @@ fn HeirWalletCreateForm() -> Element {
                 AnyHeritageProvider::Service(service_binding)
             }
             HeritageProviderConfig::Local { backup, .. } => {
-                let local_wallet =
-                    state_management::blocking_db_service_operation(database_service, move |db| {
-                        LocalWallet::create(fingerprint.unwrap(), &db, backup)
-                    })
-                    .await;
+                let local_wallet = state_management::blocking_db_service_operation(
+                    database_service,
+                    "Restore the owner backup",
+                    move |db| LocalWallet::create(fingerprint.unwrap(), &db, backup),
+                )
+                .await;
 
                 let local_wallet = match local_wallet {
                     Ok(local_wallet) => local_wallet,
@@ -238,15 +220,26 @@ fn HeirWalletCreateForm() -> Element {
         };
 
         log::debug!("Created heir wallet: {heirwallet:?}");
+        let fingerprint = heirwallet.fingerprint().ok();
 
-        match state_management::blocking_db_service_operation(database_service, move |mut db| {
-            heirwallet.create(&mut db)
-        })
+        match state_management::blocking_db_service_operation(
+            database_service,
+            "Save the new heir wallet",
+            move |mut db| heirwallet.create(&mut db),
+        )
         .await
         {
             Ok(()) => {
                 *creating.write() = false;
                 success(format!("Heir wallet '{name}' created successfully"));
+                if let Some(fingerprint) = fingerprint {
+                    state_management::warn_seed_role_conflicts(
+                        database_service,
+                        fingerprint,
+                        SeedRole::HeirWallet,
+                    )
+                    .await;
+                }
 
                 // Add context if onboarding is in progress
                 if let OnboardingStatus::InProgress(ref mut onboarding) =
@@ -428,6 +421,8 @@ fn HeritageProviderSection(
 
     let heritage_provider_is_local =
         use_memo(move || matches!(heritage_provider_type(), HeritageProviderType::Local));
+    let heritage_provider_is_service =
+        use_memo(move || matches!(heritage_provider_type(), HeritageProviderType::Service));
     let heritage_backup_is_ok = use_memo(move || heritage_wallet_backup_state.read().is_ok());
 
     use_drop(|| log::debug!("HeritageProviderSection Dropped"));
@@ -461,12 +456,16 @@ fn HeritageProviderSection(
                         }
                     }
 
-                    RadioChoice {
-                        name: "heritage_provider",
-                        state: heritage_provider_type,
-                        value: HeritageProviderType::Service,
-                        title: "Service",
-                        subtitle: "Use the Heritage Service to find inheritances",
+                    MaybeHighlight {
+                        step: OnboardingStep::SelectServiceHeritageProvider,
+                        progress: MaybeHighlightProgressType::Signal(heritage_provider_is_service.into()),
+                        RadioChoice {
+                            name: "heritage_provider",
+                            state: heritage_provider_type,
+                            value: HeritageProviderType::Service,
+                            title: "Service",
+                            subtitle: "Use the Heritage Service to find inheritances",
+                        }
                     }
                 }
 
@@ -1,12 +1,17 @@
 use crate::prelude::*;
 
-use btc_heritage_wallet::{bitcoin::Amount, btc_heritage::utils::timestamp_now};
+use btc_heritage_wallet::{bitcoin::Amount, btc_heritage::utils::timestamp_now, HeirWallet};
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 
 use crate::{
-    components::{badge::UIBadge, balance::UIBtcAmount, heritages::UIHeritage},
-    utils::CCStr,
+    components::{
+        badge::UIBadge,
+        balance::UIBtcAmount,
+        heritages::UIHeritage,
+        tags::{TagBadges, TagFilter},
+    },
+    utils::{timestamp_to_date_string, CCStr},
     views::CreateLinkButton,
     Route,
 };
@@ -15,19 +20,21 @@ use crate::{
 pub fn HeirWalletListView() -> Element {
     let service_heritages = helper_hooks::use_resource_service_heritages();
     let service_only_heritages = helper_hooks::use_memo_service_only_heritages(service_heritages);
+    let heritage_amount_display = helper_hooks::use_resource_heritage_amount_display();
 
     let display_service_section = use_memo(move || {
         match (
-            &*state_management::SERVICE_STATUS.read(),
+            &*state_management::SERVICE_AUTH_STATE.read(),
             &*service_only_heritages.read(),
         ) {
-            (Some(ServiceStatus::Connected(_)), Some(Ok(h))) if !h.is_empty() => true,
-            (Some(ServiceStatus::Connected(_)), Some(Err(_))) => true,
+            (Some(ServiceAuthState::Connected { .. }), Some(Ok(h))) if !h.is_empty() => true,
+            (Some(ServiceAuthState::Connected { .. }), Some(Err(_))) => true,
             _ => false,
         }
     });
 
     use_context_provider(|| service_heritages);
+    use_context_provider(|| heritage_amount_display);
 
     rsx! {
         super::TitledView {
@@ -40,8 +47,10 @@ pub fn HeirWalletListView() -> Element {
             super::TitledView {
                 title: CCStr::from("Service Inheritances"),
                 subtitle: CCStr::from("Orphan inheritances found on the Heritage Service."),
-                div { class: "flex flex-col gap-6 max-w-7xl mx-auto",
-                    LoadedComponent::<HashMap<CCStr,UIHeritage>> { input: service_only_heritages.into() }
+                ResourceErrorBoundary { title: "Service Inheritances", resource: service_heritages,
+                    div { class: "flex flex-col gap-6 max-w-7xl mx-auto",
+                        LoadedComponent::<HashMap<CCStr,UIHeritage>> { input: service_only_heritages.into() }
+                    }
                 }
             }
         }
@@ -56,15 +65,57 @@ fn HeirWalletList() -> Element {
 
     let service_user_id = state_management::use_service_key();
 
+    let database_service = state_management::use_database_service();
+    let item_tags = use_resource(move || async move {
+        state_management::load_item_tags(database_service)
+            .await
+            .unwrap_or_default()
+    });
+    let heirwallet_tags = move |heirwallet_name: &CCStr| {
+        item_tags
+            .read()
+            .as_ref()
+            .and_then(|item_tags| {
+                item_tags
+                    .get(&state_management::item_tags_key::<HeirWallet>(
+                        heirwallet_name,
+                    ))
+                    .cloned()
+            })
+            .unwrap_or_default()
+    };
+    let all_tags = use_memo(move || {
+        item_tags
+            .read()
+            .iter()
+            .flatten()
+            .filter(|(key, _)| key.starts_with(HeirWallet::item_key_prefix()))
+            .flat_map(|(_, tags)| tags.iter().cloned())
+            .collect::<BTreeSet<_>>()
+    });
+    let selected_tag = use_signal(|| None::<String>);
+    let filtered_heirwallet_names = use_memo(move || {
+        let mut heirwallet_names = heirwallet_names.cloned()?;
+        if let Some(ref selected_tag) = *selected_tag.read() {
+            heirwallet_names
+                .retain(|heirwallet_name| heirwallet_tags(heirwallet_name).contains(selected_tag));
+        }
+        Some(heirwallet_names)
+    });
+
     use_drop(|| log::debug!("HeirWalletList Dropped"));
 
     rsx! {
+        div { class: "container mx-auto mb-4",
+            TagFilter { tags: all_tags(), selected: selected_tag }
+        }
         div { class: "container mx-auto grid grid-cols-[repeat(auto-fill,var(--container-xs))] gap-6 justify-center",
-            if let Some(ref heirwallet_names) = *heirwallet_names.read() {
+            if let Some(heirwallet_names) = filtered_heirwallet_names() {
                 for heirwallet_name in heirwallet_names {
                     HeirWalletItem {
                         key: "{heirwallet_name}-{service_user_id()}",
                         heirwallet_name: heirwallet_name.clone(),
+                        tags: heirwallet_tags(&heirwallet_name),
                     }
                 }
             }
@@ -86,19 +137,29 @@ fn HeirWalletList() -> Element {
 }
 
 #[component]
-fn HeirWalletItem(heirwallet_name: CCStr) -> Element {
+fn HeirWalletItem(heirwallet_name: CCStr, tags: BTreeSet<String>) -> Element {
     log::debug!("HeirWalletItem Rendered");
 
     let heirwallet = helper_hooks::use_async_heirwallet(heirwallet_name.clone());
     let heirwallet_heritages = helper_hooks::use_resource_heirwallet_heritages(heirwallet);
+    let service_heritages = use_context::<FResource<HashMap<CCStr, ContextualizedHeritages>>>();
+    let heirwallet_contextualized_heritages =
+        helper_hooks::use_memo_heirwallet_contextualized_heritages(
+            heirwallet,
+            heirwallet_heritages,
+            service_heritages,
+        );
 
+    let heritages_count = use_memo(move || {
+        heirwallet_contextualized_heritages
+            .lrmap_ok(|contextualized_heritages| contextualized_heritages.len())
+    });
     let spendable = use_memo(move || {
         let now = timestamp_now();
-        heirwallet_heritages.lrmap(|heirwallet_heritages| {
-            heirwallet_heritages
+        heirwallet_contextualized_heritages.lrmap(|contextualized_heritages| {
+            contextualized_heritages
                 .values()
-                .flatten()
-                .cloned()
+                .flat_map(|ch| ch.heritages.iter())
                 .filter_map(|heritage| {
                     heritage
                         .maturity
@@ -110,15 +171,28 @@ fn HeirWalletItem(heirwallet_name: CCStr) -> Element {
         })
     });
     let total = use_memo(move || {
-        heirwallet_heritages.lrmap(|heirwallet_heritages| {
-            heirwallet_heritages
+        heirwallet_contextualized_heritages.lrmap(|contextualized_heritages| {
+            contextualized_heritages
                 .values()
-                .flatten()
-                .cloned()
+                .flat_map(|ch| ch.heritages.iter())
                 .filter_map(|heritage| heritage.value)
                 .sum::<Amount>()
         })
     });
+    // The closest maturity still in the future
+    let next_maturity = use_memo(move || {
+        let now = timestamp_now();
+        heirwallet_contextualized_heritages
+            .lrmap_ok(|contextualized_heritages| {
+                contextualized_heritages
+                    .values()
+                    .flat_map(|ch| ch.heritages.iter())
+                    .filter_map(|heritage| heritage.maturity)
+                    .filter(|ts| *ts > now)
+                    .min()
+            })
+            .flatten()
+    });
 
     let keyprovider_status = helper_hooks::use_memo_heirwallet_keyprovider_status(heirwallet);
     let heritageprovider_status = helper_hooks::use_memo_heritage_provider_status(heirwallet);
@@ -135,34 +209,53 @@ fn HeirWalletItem(heirwallet_name: CCStr) -> Element {
     use_drop(|| log::debug!("HeirWalletItem Dropped"));
 
     rsx! {
-        div {
-            class: "card card-lg border shadow-xl w-xs aspect-square cursor-pointer transition-transform hover:scale-105",
-            onclick: click,
-            div { class: "card-body",
-                div { class: "flex flex-col",
-                    div { class: "card-title text-3xl font-black", {heirwallet_name} }
-                    div { class: "text-sm font-light", {fingerprint()} }
-                }
-                div { class: "grow" }
-
-                div { class: "text-base", "Known inheritances" }
-                div { class: "flex flex-col",
-                    div { class: "text-3xl font-black",
-                        LoadedComponent::<UIBtcAmount> { input: total.into() }
+        MaybeHighlight {
+            step: OnboardingStep::ClickHeirWalletCard,
+            context_filter: OnboardingContextItemId::HeirWalletName.item(heirwallet_name.to_string()),
+            div {
+                class: "card card-lg border shadow-xl w-xs aspect-square cursor-pointer transition-transform hover:scale-105",
+                onclick: click,
+                div { class: "card-body",
+                    div { class: "flex flex-col",
+                        div { class: "card-title text-3xl font-black", "{heirwallet_name}" }
+                        div { class: "text-sm font-light", {fingerprint()} }
+                        TagBadges { tags }
                     }
-                    div { class: "text-nowrap font-light text-sm",
-                        "Spendable: "
-                        span { class: "font-bold",
-                            LoadedComponent::<UIBtcAmount> { input: spendable.into() }
+                    div { class: "grow" }
+
+                    div { class: "text-base", "Known inheritances" }
+                    div { class: "flex flex-col",
+                        div { class: "text-3xl font-black",
+                            LoadedComponent::<UIBtcAmount> { input: total.into() }
+                        }
+                        div { class: "text-nowrap font-light text-sm",
+                            "Claimable: "
+                            span { class: "font-bold",
+                                LoadedComponent::<UIBtcAmount> { input: spendable.into() }
+                            }
+                        }
+                        if let Some(heritages_count) = heritages_count() {
+                            div { class: "text-nowrap font-light text-sm",
+                                "Heritages: "
+                                span { class: "font-bold", "{heritages_count}" }
+                            }
+                        }
+                        if let Some(next_maturity) = next_maturity() {
+                            div { class: "text-nowrap font-light text-sm",
+                                "Next maturity: "
+                                span { class: "font-bold",
+                                    {timestamp_to_date_string(next_maturity)}
+                                }
+                            }
                         }
                     }
-                }
 
-                div { class: "grow" }
+                    div { class: "grow" }
 
-                div { class: "mx-auto grid grid-cols-2 gap-6",
-                    LoadedComponent::<UIBadge> { input: keyprovider_status.into() }
-                    LoadedComponent::<UIBadge> { input: heritageprovider_status.into() }
+                    div { class: "mx-auto grid grid-cols-2 gap-6",
+                        LoadedComponent::<UIBadge> { input: keyprovider_status.into() }
+                        LoadedComponent::<UIBadge> { input: heritageprovider_status.into() }
+                    }
                 }
             }
         }
@@ -4,10 +4,14 @@ use crate::prelude::*;
 use crate::{
     components::{
         app_config::AppConfig,
-        onboarding::OnboardingMessage,
+        copy::CopyTextarea,
+        modal::InfoModal,
+        notifications::NotificationCenter,
+        onboarding::{LedgerSetupGuide, OnboardingMessage, OnboardingProgressSidebar},
         svg::{Alert, DrawSvg, InfoCircle, Moon, Sun, SvgSize::Size10},
     },
     onboarding::OnboardingStep,
+    utils::CCStr,
     Route,
 };
 
@@ -43,10 +47,21 @@ pub fn MainLayout() -> Element {
     rsx! {
         div { class: "relative min-h-dvh",
             OnboardingMessage {}
+            OnboardingProgressSidebar {}
+            LedgerSetupGuide {}
+            super::diagnostics::CrashRecoveryModal {}
+            DemoModeBanner {}
+            DatabaseActivityIndicator {}
+            if crate::cli::cli_args().debug_panel {
+                DebugSnapshotPanel {}
+            }
             header { class: "bg-base-100 fixed top-0 w-full z-20 shadow-lg shadow-base-content/10",
                 NavBar {}
             }
-            main { class: "pt-12 pb-16 mx-8 text-justify", Outlet::<Route> {} }
+            main { class: "pt-12 pb-16 mx-8 text-justify",
+                ClockDriftBanner {}
+                Outlet::<Route> {}
+            }
             footer { class: "absolute bottom-px w-full h-12 px-8 z-0",
                 div { class: "h-px border-t border-solid border-gray-500" }
                 Footer {}
@@ -137,6 +152,39 @@ pub fn MainLayout() -> Element {
                 }
             }
 
+            OnboardingInfoModal {
+                step: OnboardingStep::ModalExplainHeirJourney,
+                btn_text: "Let's start",
+                div { class: "flex flex-col gap-4 max-w-xl text-base",
+                    p {
+                        "Someone designated you as one of their heirs in a Heritage Wallet.
+                        This onboarding will guide you through the three stages needed to
+                        retrieve your inheritance:"
+                    }
+                    ol { class: "list-decimal list-inside ml-4 space-y-2",
+                        li {
+                            span { class: "font-bold", "Restore your heir key" }
+                            " using the seed mnemonic phrase you were given"
+                        }
+                        li {
+                            span { class: "font-bold", "Configure your provider" }
+                            " so the application can find the inheritances you are eligible to"
+                        }
+                        li {
+                            span { class: "font-bold", "Wait and claim" }
+                            " your inheritance once it becomes mature"
+                        }
+                    }
+                    div { class: "alert alert-info text-base font-semibold",
+                        DrawSvg::<InfoCircle> {}
+                        p {
+                            "Keep your heir seed mnemonic phrase safe: it is the only way to
+                            prove you are the legitimate heir."
+                        }
+                    }
+                }
+            }
+
             OnboardingInfoModal { step: OnboardingStep::ModalLocalInheritance,
                 div { class: "flex flex-col gap-4 max-w-xl text-base",
                     p {
@@ -332,6 +380,7 @@ fn NavBar() -> Element {
                 }
             }
             div { class: "basis-10" }
+            NavLink { route: Route::DashboardView {}, "Dashboard" }
             MaybeOnPathHighlight {
                 steps: &[
                     OnboardingStep::ClickCreateWalletCard,
@@ -350,18 +399,173 @@ fn NavBar() -> Element {
                 progress: MaybeHighlightProgressType::Signal(is_heir_list.into()),
                 NavLink { route: Route::HeirListView {}, "Heirs" }
             }
+            NavLink { route: Route::EstateView {}, "Estate" }
             MaybeOnPathHighlight {
                 steps: &[OnboardingStep::ClickCreateHeirWalletCard, OnboardingStep::ClickHeirWalletCard],
                 progress: MaybeHighlightProgressType::Signal(is_heirwallet_list.into()),
                 NavLink { route: Route::HeirWalletListView {}, "Inheritances" }
             }
+            NavLink { route: Route::XPubToolView {}, "Tools" }
             div { class: "grow" }
+            NotificationCenter {}
             DarkModeToggle {}
             AppConfig {}
         }
     }
 }
 
+/// Reminds the user that the demo mode is active and allows to leave it
+#[component]
+fn DemoModeBanner() -> Element {
+    let database_service = state_management::use_database_service();
+    let mut exiting = use_signal(|| false);
+
+    let exit_demo = move |_| async move {
+        *exiting.write() = true;
+        match state_management::exit_demo_mode(database_service).await {
+            Ok(()) => {
+                navigator().push(Route::SplashScreenView {});
+            }
+            Err(e) => {
+                alert_failure("Could not exit the demo mode", e);
+            }
+        }
+        *exiting.write() = false;
+    };
+
+    rsx! {
+        if state_management::DEMO_MODE.read().is_some() {
+            div { class: "fixed z-50 bottom-14 right-8",
+                div { role: "alert", class: "alert alert-warning shadow-lg",
+                    DrawSvg::<Alert> {}
+                    div {
+                        div { class: "font-bold", "Demo mode" }
+                        div { class: "text-sm",
+                            "Sample regtest data, no real bitcoins. Nothing is kept when leaving."
+                        }
+                    }
+                    button {
+                        class: "btn btn-sm btn-outline",
+                        disabled: exiting(),
+                        onclick: exit_demo,
+                        "Exit demo"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Tells the user what the application is waiting for when a database operation is slow
+#[component]
+fn DatabaseActivityIndicator() -> Element {
+    // Refresh the durations every second while operations are pending
+    let mut tick = use_signal(|| 0u64);
+    use_future(move || async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+            if !state_management::DATABASE_OPERATIONS.peek().is_empty() {
+                *tick.write() += 1;
+            }
+        }
+    });
+
+    let slow_operation = use_memo(move || {
+        let _ = tick();
+        let operations = state_management::DATABASE_OPERATIONS.read();
+        let (name, running_for) = operations
+            .values()
+            .filter_map(|op| op.running_for().map(|running_for| (op.name, running_for)))
+            .max_by_key(|(_, running_for)| *running_for)?;
+        (running_for >= state_management::SLOW_OPERATION_THRESHOLD).then(|| {
+            let waiting = operations
+                .values()
+                .filter(|op| op.started_at.is_none())
+                .count();
+            (name, running_for, waiting)
+        })
+    });
+
+    rsx! {
+        if let Some((name, running_for, waiting)) = slow_operation() {
+            div { class: "fixed z-50 bottom-14 left-1/2 -translate-x-1/2",
+                div {
+                    role: "status",
+                    class: "alert shadow-lg",
+                    class: if running_for >= state_management::OPERATION_TIMEOUT { "alert-warning" } else { "alert-info" },
+                    span { class: "loading loading-spinner loading-sm" }
+                    div {
+                        div { class: "font-bold", "Database busy: {name} ({running_for.as_secs()}s)" }
+                        if running_for >= state_management::OPERATION_TIMEOUT {
+                            div { class: "text-sm", "This is taking longer than expected, please wait." }
+                        }
+                        if waiting > 0 {
+                            div { class: "text-sm", "{waiting} other operation(s) waiting" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Developer panel dumping the UI state to attach to bug reports,
+/// only shown with the `--debug-panel` flag
+#[component]
+fn DebugSnapshotPanel() -> Element {
+    let mut is_open = use_signal(|| false);
+    let mut snapshot = use_signal(|| CCStr::from(""));
+    let mut take_snapshot = move || snapshot.set(CCStr::from(debug_snapshot_json()));
+
+    rsx! {
+        div { class: "fixed z-50 bottom-14 left-8",
+            button {
+                class: "btn btn-sm btn-outline btn-secondary",
+                title: "Dump the UI state",
+                onclick: move |_| {
+                    take_snapshot();
+                    is_open.set(true);
+                },
+                "Debug"
+            }
+        }
+        InfoModal { is_open, title: "UI State Snapshot",
+            div { class: "flex flex-col gap-4 w-3xl",
+                div { class: "text-sm font-light",
+                    "The global state and the resources of the displayed view, to attach to a bug report. \
+                    It may contain your wallet names, addresses and amounts."
+                }
+                CopyTextarea { value: snapshot(), rows: 25 }
+                div {
+                    button {
+                        class: "btn btn-primary",
+                        onclick: move |_| take_snapshot(),
+                        "Refresh"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Warn the user the system clock drifts, the Heritage maturity dates cannot be trusted then
+#[component]
+fn ClockDriftBanner() -> Element {
+    rsx! {
+        if let Some(drift) = state_management::CLOCK_DRIFT().filter(|drift| drift.is_excessive()) {
+            div { role: "alert", class: "alert alert-error mt-4",
+                DrawSvg::<Alert> {}
+                div {
+                    div { class: "font-bold", "Your system clock is not accurate" }
+                    div { class: "text-sm",
+                        "{drift.describe()}. The maturity dates of the Heritages are computed with it: synchronize it with an Internet time server."
+                    }
+                }
+            }
+        }
+    }
+}
+
 #[component]
 fn NavLink(route: Route, children: Element) -> Element {
     rsx! {
@@ -389,10 +593,7 @@ fn DarkModeToggle() -> Element {
             r#type: "checkbox",
             name: "theme",
             class: "theme-controller hidden",
-            value: match state_management::THEME() {
-                Theme::Light => "light",
-                Theme::Dark => "dark",
-            },
+            value: state_management::THEME_CUSTOMIZATION.read().palette.daisyui_theme(state_management::THEME()),
             tabindex: "-1",
             checked: true,
         }
@@ -402,6 +603,10 @@ fn DarkModeToggle() -> Element {
                 name: "theme",
                 tabindex: "-1",
                 oninput: move |event| {
+                    // A manual choice stops following the system theme
+                    if state_management::THEME_CUSTOMIZATION.peek().follow_system {
+                        state_management::THEME_CUSTOMIZATION.write().follow_system = false;
+                    }
                     *state_management::THEME.write() = match event.checked() {
                         true => Theme::Dark,
                         false => Theme::Light,
@@ -7,16 +7,22 @@ use crate::{
 };
 
 pub mod app_config;
+pub mod backup_check;
+pub mod dashboard;
+pub mod diagnostics;
+pub mod estate;
 pub mod heirs;
 pub mod heirwallet;
 pub mod heirwallet_create;
 pub mod heirwallet_list;
 pub mod main_layout;
 pub mod onboarding;
+pub mod recovery_rehearsal;
 pub mod splashscreen;
 pub mod wallet;
 pub mod wallet_create;
 pub mod wallet_list;
+pub mod xpub_tool;
 
 #[component]
 fn TitledView(
@@ -46,20 +46,20 @@ impl OnboardingAnswers {
 fn heir_onboarding(how_public: HowPublicAnswer) -> Onboarding {
     use crate::onboarding::OnboardingStep::*;
 
-    let obb = Onboarding::builder().add_steps(match how_public {
-        HowPublicAnswer::HeritageService => &[ModalCreateAccountOnTheService, ClickConnectService],
-        HowPublicAnswer::OwnNode => &[
-            ModalLocalInheritance,
-            ModalInstallBlockchainProviderNode,
-            ConfigureBlockchainProvider,
-        ],
-    });
+    // The heir journey: restore the heir key, configure the provider, then wait and claim
+    let obb = Onboarding::builder().add_step(ModalExplainHeirJourney);
 
-    // This is common to all
+    let obb = match how_public {
+        HowPublicAnswer::HeritageService => obb,
+        // If local heritage, warn about the backup requirement first
+        HowPublicAnswer::OwnNode => obb.add_step(ModalLocalInheritance),
+    };
+
+    // Restore the heir key: this is common to all
     let obb = obb.add_steps(&[ClickCreateHeirWalletCard, InputName, RestoreKeyProviderSeed]);
 
     let obb = match how_public {
-        HowPublicAnswer::HeritageService => obb,
+        HowPublicAnswer::HeritageService => obb.add_step(SelectServiceHeritageProvider),
         // If local heritage, extra steps are required
         HowPublicAnswer::OwnNode => {
             obb.add_steps(&[SelectLocalHeritageProvider, ProvideLocalWalletBackup])
@@ -69,15 +69,24 @@ fn heir_onboarding(how_public: HowPublicAnswer) -> Onboarding {
     // This is common to all
     let obb = obb.add_step(ClickCreateHeirWalletButton);
 
+    // Configure the provider
     let obb = match how_public {
-        HowPublicAnswer::HeritageService => obb,
-        // If local heritage, ask to sync
-        HowPublicAnswer::OwnNode => obb.add_step(SynchronizeLocalHeritage),
+        HowPublicAnswer::HeritageService => {
+            obb.add_steps(&[ModalCreateAccountOnTheService, ClickConnectService])
+        }
+        // If local heritage, configure the node then come back and sync
+        HowPublicAnswer::OwnNode => obb.add_steps(&[
+            ModalInstallBlockchainProviderNode,
+            ConfigureBlockchainProvider,
+            ClickHeirWalletCard,
+            SynchronizeLocalHeritage,
+        ]),
     };
 
-    // This is common to all
+    // Wait and claim: this is common to all
     let obb = obb.add_steps(&[
         ModalExplainInheritancesList,
+        ModalHeirWaitForMaturity,
         ClickInheritanceSpendButton,
         ModalExplainInheritanceSpend,
         InputInheritanceSpendAddress,
@@ -103,6 +112,15 @@ fn owner_onboarding(how_public: HowPublicAnswer, how_private: HowPrivateAnswer)
         ],
     });
 
+    let obb = match how_private {
+        HowPrivateAnswer::LedgerDevice => obb.add_steps(&[
+            LedgerInstallBitcoinApp,
+            LedgerCheckNetwork,
+            LedgerVerifyTestDerivation,
+        ]),
+        HowPrivateAnswer::LocalStorage | HowPrivateAnswer::RestoreSeed => obb,
+    };
+
     // This is common to all
     let obb = obb.add_steps(&[ClickCreateWalletCard, ModalExplainWalletSplit, InputName]);
 
@@ -0,0 +1,621 @@
+use crate::prelude::*;
+
+use std::collections::HashSet;
+
+use btc_heritage_wallet::{
+    btc_heritage::{
+        bitcoincore_rpc::jsonrpc::serde_json, utils::bitcoin_network, BlockInclusionObjective,
+        HeritageWalletBackup,
+    },
+    heritage_provider::LocalWallet,
+    online_wallet::LocalHeritageWallet,
+    AnyHeritageProvider, AnyKeyProvider, AnyOnlineWallet, BoundFingerprint, Database, HeirWallet,
+    KeyProvider, Language, LocalKey, Mnemonic, OnlineWallet, Wallet,
+};
+
+use crate::{
+    utils::{
+        check_keys_network, decrypt_with_passphrase, log_error, CCStr, PASSPHRASE_ENCRYPTED_PREFIX,
+    },
+    Route,
+};
+
+use super::backup_check::{backup_descriptors, BackupCheck, CheckList, CheckOutcome};
+
+/// Maximum number of addresses derived from the backup and compared with the wallet
+const REHEARSAL_ADDRESS_COUNT: usize = 5;
+
+/// Name given to the items restored in the rehearsal profile
+const REHEARSAL_ITEM_NAME: &str = "Recovery Rehearsal";
+
+/// Who is rehearsing the recovery
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RehearsalRole {
+    /// The owner, restoring its wallet from its seed and descriptors backup
+    Owner,
+    /// An heir, restoring its heir wallet from its seed and the descriptors backup of the owner
+    Heir,
+}
+
+/// Restore the owner wallet in the rehearsal database and compare it with the real wallet
+async fn rehearse_owner_recovery(
+    database: Database,
+    local_key: LocalKey,
+    backup: HeritageWalletBackup,
+    wallet: Option<Wallet>,
+) -> Vec<BackupCheck> {
+    const SEED_TITLE: &str = "Seed";
+    const RESTORE_TITLE: &str = "Restoration";
+    const ADDRESSES_TITLE: &str = "Addresses";
+
+    let mut checks = vec![];
+    match (local_key.fingerprint(), backup.fingerprint()) {
+        (Ok(seed_fingerprint), Ok(Some(backup_fingerprint)))
+            if seed_fingerprint == backup_fingerprint =>
+        {
+            checks.push(BackupCheck::passed(
+                SEED_TITLE,
+                format!("The seed {seed_fingerprint} matches the backup"),
+            ))
+        }
+        (Ok(seed_fingerprint), Ok(Some(backup_fingerprint))) => {
+            checks.push(BackupCheck::failed(
+                SEED_TITLE,
+                format!(
+                    "The seed {seed_fingerprint} does not match the backup \
+                    fingerprint {backup_fingerprint}, verify the words and the password"
+                ),
+            ));
+            return checks;
+        }
+        (Err(e), _) => {
+            checks.push(BackupCheck::failed(SEED_TITLE, e.to_string()));
+            return checks;
+        }
+        (_, _) => {
+            checks.push(BackupCheck::failed(
+                SEED_TITLE,
+                "Cannot extract the fingerprint of the backup",
+            ));
+            return checks;
+        }
+    }
+
+    let local_wallet = database
+        .blocking_operation(move |db| {
+            LocalHeritageWallet::create(&db, Some(backup), BlockInclusionObjective::default())
+        })
+        .await;
+    let mut restored_wallet = match local_wallet.map_err(log_error).and_then(|local_wallet| {
+        Wallet::new(
+            REHEARSAL_ITEM_NAME.to_owned(),
+            AnyKeyProvider::LocalKey(local_key),
+            AnyOnlineWallet::Local(local_wallet),
+        )
+        .map_err(log_error)
+    }) {
+        Ok(restored_wallet) => {
+            checks.push(BackupCheck::passed(
+                RESTORE_TITLE,
+                "The wallet was restored in the isolated profile",
+            ));
+            restored_wallet
+        }
+        Err(e) => {
+            checks.push(BackupCheck::failed(RESTORE_TITLE, e));
+            return checks;
+        }
+    };
+
+    let Some(wallet) = wallet else {
+        checks.push(BackupCheck::skipped(
+            ADDRESSES_TITLE,
+            "Select a wallet to compare the restored addresses with",
+        ));
+        return checks;
+    };
+    let wallet_name = wallet.name().to_owned();
+    let known_addresses = match wallet.list_addresses().await {
+        Ok(addresses) => addresses
+            .iter()
+            .map(|wallet_address| wallet_address.address().to_string())
+            .collect::<HashSet<_>>(),
+        Err(e) => {
+            checks.push(BackupCheck::failed(ADDRESSES_TITLE, log_error(e)));
+            return checks;
+        }
+    };
+    if known_addresses.is_empty() {
+        checks.push(BackupCheck::skipped(
+            ADDRESSES_TITLE,
+            format!("Wallet '{wallet_name}' has not generated any address yet"),
+        ));
+        return checks;
+    }
+
+    // The backup remembers the last generated addresses, else derive the first ones again
+    let mut restored_addresses = match restored_wallet.list_addresses().await {
+        Ok(addresses) => addresses
+            .iter()
+            .map(|wallet_address| wallet_address.address().to_string())
+            .collect::<Vec<_>>(),
+        Err(e) => {
+            checks.push(BackupCheck::failed(ADDRESSES_TITLE, log_error(e)));
+            return checks;
+        }
+    };
+    let expected_count = REHEARSAL_ADDRESS_COUNT.min(known_addresses.len());
+    while restored_addresses.len() < expected_count {
+        match restored_wallet.get_address().await {
+            Ok(wallet_address) => restored_addresses.push(wallet_address.address().to_string()),
+            Err(e) => {
+                checks.push(BackupCheck::failed(ADDRESSES_TITLE, log_error(e)));
+                return checks;
+            }
+        }
+    }
+    restored_addresses.truncate(REHEARSAL_ADDRESS_COUNT);
+    checks.push(
+        match restored_addresses
+            .iter()
+            .find(|address| !known_addresses.contains(*address))
+        {
+            None => BackupCheck::passed(
+                ADDRESSES_TITLE,
+                format!(
+                    "The {} address(es) restored from the backup belong to wallet '{wallet_name}'",
+                    restored_addresses.len()
+                ),
+            ),
+            Some(address) => BackupCheck::failed(
+                ADDRESSES_TITLE,
+                format!(
+                    "The restored address {address} is unknown to wallet '{wallet_name}', \
+                    verify the backup is up to date"
+                ),
+            ),
+        },
+    );
+    checks
+}
+
+/// Restore the heir wallet in the rehearsal database and compare it with the real heir wallet
+async fn rehearse_heir_recovery(
+    database: Database,
+    local_key: LocalKey,
+    backup: HeritageWalletBackup,
+    heirwallet: Option<HeirWallet>,
+) -> Vec<BackupCheck> {
+    const SEED_TITLE: &str = "Seed";
+    const RESTORE_TITLE: &str = "Restoration";
+    const HEIR_WALLET_TITLE: &str = "Heir wallet";
+
+    let mut checks = vec![];
+    let fingerprint = match local_key.fingerprint() {
+        Ok(fingerprint) => fingerprint,
+        Err(e) => {
+            checks.push(BackupCheck::failed(SEED_TITLE, e.to_string()));
+            return checks;
+        }
+    };
+    // The keys of the heirs appear in the descriptors with their fingerprint as origin
+    let heir_key_origin = format!("[{fingerprint}/");
+    match backup_descriptors(&backup) {
+        Ok(descriptors) => {
+            let total = descriptors.len();
+            let inheriting = descriptors
+                .iter()
+                .filter(|(external, _)| external.contains(&heir_key_origin))
+                .count();
+            if inheriting == 0 {
+                checks.push(BackupCheck::failed(
+                    SEED_TITLE,
+                    format!(
+                        "The seed {fingerprint} is not an heir of the backup, \
+                        verify the words and the password"
+                    ),
+                ));
+                return checks;
+            }
+            checks.push(BackupCheck::passed(
+                SEED_TITLE,
+                format!(
+                    "The seed {fingerprint} is an heir in {inheriting} of \
+                    the {total} Heritage Configuration(s) of the backup"
+                ),
+            ));
+        }
+        Err(e) => {
+            checks.push(BackupCheck::failed(SEED_TITLE, e));
+            return checks;
+        }
+    }
+
+    let local_wallet = database
+        .blocking_operation(move |db| LocalWallet::create(fingerprint, &db, backup))
+        .await;
+    match local_wallet.map_err(log_error).and_then(|local_wallet| {
+        HeirWallet::new(
+            REHEARSAL_ITEM_NAME.to_owned(),
+            AnyKeyProvider::LocalKey(local_key),
+            AnyHeritageProvider::LocalWallet(local_wallet),
+        )
+        .map_err(log_error)
+    }) {
+        Ok(_) => checks.push(BackupCheck::passed(
+            RESTORE_TITLE,
+            "The heir wallet was restored in the isolated profile",
+        )),
+        Err(e) => {
+            checks.push(BackupCheck::failed(RESTORE_TITLE, e));
+            return checks;
+        }
+    }
+
+    checks.push(match heirwallet {
+        None => BackupCheck::skipped(
+            HEIR_WALLET_TITLE,
+            "Select an heir wallet to compare the restored seed with",
+        ),
+        Some(heirwallet) => match heirwallet.fingerprint() {
+            Ok(heirwallet_fingerprint) if heirwallet_fingerprint == fingerprint => {
+                BackupCheck::passed(
+                    HEIR_WALLET_TITLE,
+                    format!(
+                        "The seed belongs to heir wallet '{}' ({fingerprint})",
+                        heirwallet.name()
+                    ),
+                )
+            }
+            Ok(heirwallet_fingerprint) => BackupCheck::failed(
+                HEIR_WALLET_TITLE,
+                format!(
+                    "The seed {fingerprint} differs from the seed {heirwallet_fingerprint} \
+                    of heir wallet '{}'",
+                    heirwallet.name()
+                ),
+            ),
+            Err(e) => BackupCheck::failed(HEIR_WALLET_TITLE, e.to_string()),
+        },
+    });
+    checks
+}
+
+/// Tool view guiding the owner or an heir through a recovery "fire drill": the seed and
+/// the descriptors backup are restored in a temporary profile, isolated from the data of
+/// the application, compared with the real wallet and then deleted
+#[component]
+pub fn RecoveryRehearsalView() -> Element {
+    log::debug!("RecoveryRehearsalView Rendered");
+
+    let database_service = state_management::use_database_service();
+    let service_client_service = state_management::use_service_client_service();
+    let blockchain_provider_service = state_management::use_blockchain_provider_service();
+
+    let mut role = use_signal(|| RehearsalRole::Owner);
+    let mut item_name = use_signal(|| None::<CCStr>);
+    let item_names = use_resource(move || async move {
+        match role() {
+            RehearsalRole::Owner => state_management::list_wallet_names(database_service).await,
+            RehearsalRole::Heir => state_management::list_heirwallet_names(database_service).await,
+        }
+        .unwrap_or_default()
+    });
+
+    let mut seed_words = use_signal(String::new);
+    let mut seed_password = use_signal(String::new);
+    let mut backup_data = use_signal(String::new);
+    let mut backup_passphrase = use_signal(String::new);
+    let mut checks = use_signal(Vec::<BackupCheck>::new);
+    let mut rehearsing = use_signal(|| false);
+
+    let mnemonic = use_memo(move || {
+        let seed_words = seed_words.read();
+        let seed_words = seed_words.split_whitespace().collect::<Vec<_>>();
+        (!seed_words.is_empty()).then(|| {
+            Mnemonic::parse_in(Language::English, seed_words.join(" "))
+                .map_err(|e| CCStr::from(e.to_string()))
+        })
+    });
+    let is_encrypted = use_memo(move || {
+        backup_data
+            .read()
+            .trim_start()
+            .starts_with(PASSPHRASE_ENCRYPTED_PREFIX)
+    });
+
+    let rehearse = move |_| async move {
+        let Some(Ok(mnemonic)) = mnemonic() else {
+            return;
+        };
+        *rehearsing.write() = true;
+        checks.write().clear();
+
+        let data = if is_encrypted() {
+            let data = backup_data();
+            let passphrase = backup_passphrase();
+            match tokio::task::spawn_blocking(move || decrypt_with_passphrase(&data, &passphrase))
+                .await
+                .map_err(log_error)
+                .and_then(|decrypted| decrypted)
+            {
+                Ok(decrypted) => decrypted,
+                Err(e) => {
+                    checks.write().push(BackupCheck::failed("Decryption", e));
+                    *rehearsing.write() = false;
+                    return;
+                }
+            }
+        } else {
+            backup_data()
+        };
+        let backup = match serde_json::from_str::<HeritageWalletBackup>(&data)
+            .map_err(|e| e.to_string())
+            .and_then(|backup| check_keys_network(&data).map(|_| backup))
+        {
+            Ok(backup) => {
+                checks.write().push(BackupCheck::passed(
+                    "Backup",
+                    format!(
+                        "Descriptors backup with {} Heritage Configuration(s) read",
+                        backup.iter().count()
+                    ),
+                ));
+                backup
+            }
+            Err(e) => {
+                checks.write().push(BackupCheck::failed("Backup", e));
+                *rehearsing.write() = false;
+                return;
+            }
+        };
+
+        let profile = match state_management::create_rehearsal_profile().await {
+            Ok(profile) => {
+                checks.write().push(BackupCheck::passed(
+                    "Isolated profile",
+                    format!(
+                        "Temporary profile created in {}",
+                        profile.datadir().display()
+                    ),
+                ));
+                profile
+            }
+            Err(e) => {
+                checks
+                    .write()
+                    .push(BackupCheck::failed("Isolated profile", e));
+                *rehearsing.write() = false;
+                return;
+            }
+        };
+
+        let password = Some(seed_password()).filter(|password| !password.is_empty());
+        let local_key = LocalKey::restore(mnemonic, password, bitcoin_network::get());
+        let database = profile.database.clone();
+        let rehearsal_checks = match role() {
+            RehearsalRole::Owner => {
+                let wallet = match item_name() {
+                    Some(wallet_name) => state_management::get_wallet(
+                        database_service,
+                        service_client_service,
+                        blockchain_provider_service,
+                        wallet_name,
+                    )
+                    .await
+                    .ok(),
+                    None => None,
+                };
+                rehearse_owner_recovery(database, local_key, backup, wallet).await
+            }
+            RehearsalRole::Heir => {
+                let heirwallet = match item_name() {
+                    Some(heirwallet_name) => state_management::get_heirwallet(
+                        database_service,
+                        service_client_service,
+                        heirwallet_name,
+                    )
+                    .await
+                    .ok(),
+                    None => None,
+                };
+                rehearse_heir_recovery(database, local_key, backup, heirwallet).await
+            }
+        };
+        checks.write().extend(rehearsal_checks);
+
+        checks.write().push(
+            match state_management::remove_rehearsal_profile(profile).await {
+                Ok(()) => BackupCheck::passed(
+                    "Cleanup",
+                    "The temporary profile and everything restored in it were deleted",
+                ),
+                Err(e) => BackupCheck::failed("Cleanup", e),
+            },
+        );
+        *rehearsing.write() = false;
+    };
+
+    let all_passed = use_memo(move || {
+        let checks = checks.read();
+        !checks.is_empty()
+            && checks
+                .iter()
+                .all(|check| !matches!(check.outcome, CheckOutcome::Failed(_)))
+    });
+
+    use_drop(|| log::debug!("RecoveryRehearsalView Dropped"));
+
+    rsx! {
+        super::TitledView {
+            title: CCStr::from("Recovery Rehearsal"),
+            subtitle: CCStr::from("Practice a recovery before the moment it matters."),
+            right: rsx! {
+                Link { class: "btn btn-outline", to: Route::BackupCheckView {}, "Backup Check" }
+            },
+            div { class: "container mx-auto px-8 space-y-8",
+                div { class: "text-base font-light",
+                    "This fire drill restores your seed and a descriptors backup in a temporary profile,
+                    isolated from the data of the application, verifies that the result matches your wallet
+                    and then deletes it. Nothing is synchronized nor broadcast, and your wallets are left untouched."
+                }
+
+                div { class: "rounded-box border border-base-content/5 shadow-md p-4",
+                    h2 { class: "text-2xl font-bold mb-4", "1. Who is rehearsing" }
+                    div { class: "flex flex-row flex-wrap gap-8 items-end",
+                        fieldset { class: "fieldset",
+                            legend { class: "fieldset-legend", "Role" }
+                            label { class: "label",
+                                input {
+                                    r#type: "radio",
+                                    class: "radio",
+                                    name: "rehearsal_role",
+                                    checked: role() == RehearsalRole::Owner,
+                                    onchange: move |_| {
+                                        role.set(RehearsalRole::Owner);
+                                        item_name.set(None);
+                                        checks.write().clear();
+                                    },
+                                }
+                                "The owner, restoring a wallet"
+                            }
+                            label { class: "label",
+                                input {
+                                    r#type: "radio",
+                                    class: "radio",
+                                    name: "rehearsal_role",
+                                    checked: role() == RehearsalRole::Heir,
+                                    onchange: move |_| {
+                                        role.set(RehearsalRole::Heir);
+                                        item_name.set(None);
+                                        checks.write().clear();
+                                    },
+                                }
+                                "An heir, restoring an heir wallet"
+                            }
+                        }
+                        fieldset { class: "fieldset",
+                            legend { class: "fieldset-legend",
+                                match role() {
+                                    RehearsalRole::Owner => "Compare with wallet",
+                                    RehearsalRole::Heir => "Compare with heir wallet",
+                                }
+                            }
+                            select {
+                                class: "select",
+                                onchange: move |evt| {
+                                    let value = evt.value();
+                                    item_name.set((!value.is_empty()).then(|| CCStr::from(value)));
+                                },
+                                option { value: "", selected: item_name().is_none(), "None" }
+                                for name in item_names.cloned().unwrap_or_default() {
+                                    option {
+                                        value: "{name}",
+                                        selected: item_name().as_ref() == Some(&name),
+                                        "{name}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                div { class: "rounded-box border border-base-content/5 shadow-md p-4",
+                    h2 { class: "text-2xl font-bold mb-4", "2. Recovery material" }
+                    div { class: "text-sm font-light mb-4",
+                        match role() {
+                            RehearsalRole::Owner => "Your seed phrase, its password if any, and the descriptors backup of your wallet.",
+                            RehearsalRole::Heir => "Your seed phrase, its password if any, and the descriptors backup the owner gave you.",
+                        }
+                    }
+                    div { class: "flex flex-col",
+                        fieldset { class: "fieldset w-full",
+                            legend { class: "fieldset-legend", "Seed Phrase" }
+                            textarea {
+                                class: "textarea textarea-bordered font-mono w-full",
+                                class: if matches!(mnemonic(), Some(Err(_))) { "textarea-error" },
+                                rows: "3",
+                                placeholder: "word1 word2 word3...",
+                                value: seed_words(),
+                                oninput: move |evt| {
+                                    seed_words.set(evt.value());
+                                    checks.write().clear();
+                                },
+                            }
+                            if let Some(Err(e)) = mnemonic() {
+                                div { class: "fieldset-label text-error", "{e}" }
+                            }
+                        }
+                        fieldset { class: "fieldset",
+                            legend { class: "fieldset-legend", "Seed Password (optional)" }
+                            input {
+                                r#type: "password",
+                                class: "input",
+                                value: seed_password(),
+                                oninput: move |evt| seed_password.set(evt.value()),
+                            }
+                        }
+                        fieldset { class: "fieldset w-full",
+                            legend { class: "fieldset-legend", "Descriptors Backup" }
+                            textarea {
+                                class: "textarea textarea-bordered font-mono text-xs w-full",
+                                rows: "6",
+                                placeholder: "Paste the backup here...",
+                                value: backup_data(),
+                                oninput: move |evt| {
+                                    backup_data.set(evt.value());
+                                    checks.write().clear();
+                                },
+                            }
+                        }
+                        if is_encrypted() {
+                            fieldset { class: "fieldset",
+                                legend { class: "fieldset-legend", "Backup Passphrase" }
+                                input {
+                                    r#type: "password",
+                                    class: "input",
+                                    value: backup_passphrase(),
+                                    oninput: move |evt| backup_passphrase.set(evt.value()),
+                                }
+                            }
+                        }
+                    }
+                }
+
+                div { class: "rounded-box border border-base-content/5 shadow-md p-4",
+                    h2 { class: "text-2xl font-bold mb-4", "3. Rehearse" }
+                    div { class: "flex justify-center",
+                        button {
+                            class: "btn btn-primary",
+                            disabled: !matches!(mnemonic(), Some(Ok(_)))
+                                || backup_data.read().trim().is_empty() || rehearsing(),
+                            onclick: rehearse,
+                            if rehearsing() {
+                                span { class: "loading loading-spinner" }
+                                "Rehearsing..."
+                            } else {
+                                "Start the rehearsal"
+                            }
+                        }
+                    }
+                    if !checks.read().is_empty() {
+                        div { class: "mt-4",
+                            if !rehearsing() {
+                                if all_passed() {
+                                    div { class: "alert alert-success mb-4",
+                                        "The rehearsal succeeded: your recovery material restores your wallet."
+                                    }
+                                } else {
+                                    div { class: "alert alert-error mb-4",
+                                        "The rehearsal failed: fix your recovery material while you still can."
+                                    }
+                                }
+                            }
+                            CheckList { checks: checks() }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
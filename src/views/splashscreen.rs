@@ -7,6 +7,8 @@ use crate::{
     utils::async_sleep,
 };
 
+use state_management::{StartupCheckOutcome, StartupCheckSeverity};
+
 #[component]
 pub fn SplashScreenView() -> Element {
     log::debug!("SplashScreenView Rendered");
@@ -15,6 +17,58 @@ pub fn SplashScreenView() -> Element {
     let mut show_title = use_signal(|| false);
     let mut show_features = use_signal(|| false);
     let mut show_button = use_signal(|| false);
+    let mut starting_demo = use_signal(|| false);
+    let mut show_checks = use_signal(|| false);
+
+    let database_service = state_management::use_database_service();
+    let blockchain_provider_service = state_management::use_blockchain_provider_service();
+
+    let startup_checks = use_resource(move || async move {
+        let deadline = state_management::startup_checks_deadline();
+        let (database, disk_space, service, blockchain_provider, ledger, clock) = futures_util::join!(
+            state_management::check_database(database_service, deadline),
+            state_management::check_disk_space(deadline),
+            state_management::check_service_reachable(deadline),
+            state_management::check_blockchain_provider(deadline),
+            state_management::check_ledger(deadline),
+            state_management::check_clock_skew(deadline),
+        );
+        vec![
+            StartupCheck::new("Database", database, None),
+            StartupCheck::new("Disk space", disk_space, None),
+            StartupCheck::new(
+                "Heritage Service",
+                service,
+                Some(crate::Route::AppConfigView {}),
+            ),
+            StartupCheck::new(
+                "Blockchain provider",
+                blockchain_provider,
+                Some(crate::Route::AppConfigView {}),
+            ),
+            StartupCheck::new("Ledger", ledger, None),
+            StartupCheck::new("System clock", clock, None),
+        ]
+    });
+    let start_demo = move |_| async move {
+        *starting_demo.write() = true;
+        match state_management::enter_demo_mode(database_service, blockchain_provider_service).await
+        {
+            Ok(funded) => {
+                if !funded {
+                    alert_info(
+                        "The demo wallets have no funds: use a Bitcoin Core node running \
+                        a regtest chain as blockchain provider to fund them",
+                    );
+                }
+                navigator().push(crate::Route::WalletListView {});
+            }
+            Err(e) => {
+                alert_failure("Could not start the demo mode", e);
+            }
+        }
+        *starting_demo.write() = false;
+    };
 
     // Animation sequence
     use_future(move || async move {
@@ -25,8 +79,13 @@ pub fn SplashScreenView() -> Element {
         show_logo.set(true);
         async_sleep(2000).await;
 
-        match *state_management::ONBOARDING_STATUS.read() {
-            OnboardingStatus::Pending => {
+        // Do not hold the status across the awaits below
+        let is_pending = matches!(
+            *state_management::ONBOARDING_STATUS.read(),
+            OnboardingStatus::Pending
+        );
+        match is_pending {
+            true => {
                 // Show title
                 show_title.set(true);
                 async_sleep(1000).await;
@@ -37,13 +96,25 @@ pub fn SplashScreenView() -> Element {
 
                 // Show get started button
                 show_button.set(true);
+                show_checks.set(true);
             }
-            OnboardingStatus::InProgress(ref ob) => {
-                let next_route = ob.current_route();
-                use_navigator().push(next_route);
-            }
-            OnboardingStatus::Completed => {
-                navigator().push(crate::Route::WalletListView {});
+            false => {
+                // Wait for the startup checks before leaving the splash screen
+                while startup_checks.peek().is_none() {
+                    async_sleep(100).await;
+                }
+                let blocking = startup_checks
+                    .peek()
+                    .iter()
+                    .flatten()
+                    .any(|check| check.outcome.severity == StartupCheckSeverity::Error);
+                if blocking {
+                    // Blocking problem: let the user see it before going further
+                    show_checks.set(true);
+                } else {
+                    warn_startup_checks(&startup_checks.peek());
+                    navigator().push(next_route());
+                }
             }
         };
     });
@@ -129,6 +200,34 @@ pub fn SplashScreenView() -> Element {
                             base_class: "ml-2 group-hover:translate-x-4 transition-transform duration-300 fill-current",
                         }
                     }
+                    div { class: "mt-4",
+                        button {
+                            class: "btn btn-ghost btn-sm",
+                            disabled: starting_demo(),
+                            onclick: start_demo,
+                            if starting_demo() {
+                                span { class: "loading loading-spinner loading-sm" }
+                                "Preparing the demo..."
+                            } else {
+                                "Explore the demo with sample data"
+                            }
+                        }
+                    }
+                }
+                AppearFrom { show: show_checks,
+                    if let Some(ref checks) = *startup_checks.read() {
+                        StartupChecksSummary { checks: checks.clone() }
+                        if !show_button() {
+                            // Onboarding already done: the checks prevented the automatic start
+                            button {
+                                class: "btn btn-outline mt-4",
+                                onclick: move |_| {
+                                    navigator().push(next_route());
+                                },
+                                "Continue anyway"
+                            }
+                        }
+                    }
                 }
                 div { class: if show_button() { "transform transition-all duration-1000 ease-out scale-100 opacity-100" } else { "scale-0 opacity-0" },
                     div { class: "text-sm",
@@ -140,6 +239,84 @@ pub fn SplashScreenView() -> Element {
     }
 }
 
+/// Route to go to when leaving the splash screen of an already started onboarding
+fn next_route() -> crate::Route {
+    match *state_management::ONBOARDING_STATUS.peek() {
+        OnboardingStatus::InProgress(ref ob) => ob.current_route(),
+        _ => crate::cli::take_open_route().unwrap_or(crate::Route::DashboardView {}),
+    }
+}
+
+/// Raise an alert for each non-blocking problem found by the startup checks
+fn warn_startup_checks(checks: &Option<Vec<StartupCheck>>) {
+    for check in checks.iter().flatten() {
+        if check.outcome.severity == StartupCheckSeverity::Warn {
+            alert_warn(format!("{}: {}", check.name, check.outcome.message));
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct StartupCheck {
+    name: &'static str,
+    outcome: StartupCheckOutcome,
+    /// Where the user can fix the problem, if the check fails
+    fix_route: Option<crate::Route>,
+}
+impl StartupCheck {
+    fn new(
+        name: &'static str,
+        outcome: StartupCheckOutcome,
+        fix_route: Option<crate::Route>,
+    ) -> Self {
+        Self {
+            name,
+            outcome,
+            fix_route,
+        }
+    }
+}
+
+#[component]
+fn StartupChecksSummary(checks: Vec<StartupCheck>) -> Element {
+    rsx! {
+        div { class: "flex flex-col gap-1 text-left text-sm mb-4",
+            for check in checks {
+                div { key: "{check.name}", class: "flex flex-row gap-2 items-center",
+                    span {
+                        class: "badge badge-sm w-16",
+                        class: match check.outcome.severity {
+                            StartupCheckSeverity::Ok => "badge-success",
+                            StartupCheckSeverity::Info => "badge-info",
+                            StartupCheckSeverity::Warn => "badge-warning",
+                            StartupCheckSeverity::Error => "badge-error",
+                        },
+                        {
+                            match check.outcome.severity {
+                                StartupCheckSeverity::Ok => "OK",
+                                StartupCheckSeverity::Info => "Info",
+                                StartupCheckSeverity::Warn => "Warning",
+                                StartupCheckSeverity::Error => "Error",
+                            }
+                        }
+                    }
+                    span { class: "font-semibold w-40", "{check.name}" }
+                    span { class: "grow", "{check.outcome.message}" }
+                    if let Some(fix_route) = check.fix_route.filter(|_| check.outcome.severity >= StartupCheckSeverity::Warn) {
+                        button {
+                            class: "btn btn-xs btn-link",
+                            onclick: move |_| {
+                                navigator().push(fix_route.clone());
+                            },
+                            "Fix it"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 #[component]
 fn AppearFrom(show: ReadOnlySignal<bool>, children: Element) -> Element {
     let mut animate = use_signal(|| false);
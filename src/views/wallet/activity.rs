@@ -0,0 +1,150 @@
+use crate::prelude::*;
+
+use btc_heritage_wallet::{
+    btc_heritage::utils::timestamp_now, heritage_service_api_client::TransactionSummary,
+    online_wallet::WalletStatus,
+};
+
+use crate::utils::{timestamp_to_date_string, timestamp_to_string, CCStr, CheapClone};
+
+/// Maximum number of entries of the activity timeline
+const MAX_TIMELINE_ENTRIES: usize = 8;
+
+/// Kind of an entry of the activity timeline, deciding its marker
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ActivityKind {
+    Sync,
+    Transaction,
+    HeritageConfig,
+    Warning,
+}
+impl ActivityKind {
+    fn marker_class(self) -> &'static str {
+        match self {
+            ActivityKind::Sync => "status status-info",
+            ActivityKind::Transaction => "status status-success",
+            ActivityKind::HeritageConfig => "status status-primary",
+            ActivityKind::Warning => "status status-warning",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ActivityEntry {
+    kind: ActivityKind,
+    text: CCStr,
+    timestamp: u64,
+}
+
+/// Short human description of the time elapsed since `timestamp`
+fn time_ago(now: u64, timestamp: u64) -> String {
+    let elapsed = now.saturating_sub(timestamp);
+    match elapsed {
+        0..60 => "just now".to_owned(),
+        60..3600 => format!("{} min ago", elapsed / 60),
+        3600..86400 => format!("{} h ago", elapsed / 3600),
+        86400..604800 => format!("{} days ago", elapsed / 86400),
+        _ => timestamp_to_date_string(timestamp),
+    }
+}
+
+/// Shorten a transaction ID to its first and last characters
+fn short_txid(txid: &str) -> String {
+    match (txid.get(..6), txid.get(txid.len().saturating_sub(4)..)) {
+        (Some(start), Some(end)) if txid.len() > 10 => format!("{start}…{end}"),
+        _ => txid.to_owned(),
+    }
+}
+
+/// Compact feed of the recent activity of the wallet, assembled from its synchronization
+/// status, transactions and Heritage Configurations, and the events of the current session
+#[component]
+pub(super) fn ActivityTimeline(wallet_name: CCStr) -> Element {
+    log::debug!("ActivityTimeline Rendered");
+
+    let wallet_status = use_context::<FResource<WalletStatus>>();
+    let wallet_transactions_content =
+        use_context::<FMemo<ContentHashed<CheapClone<[TransactionSummary]>>>>();
+    let heritage_configs_with_info = use_context::<FMemo<CheapClone<[HeritageConfigWithInfo]>>>();
+
+    // Relative times must move forward even when nothing else changes
+    let mut now = use_signal(timestamp_now);
+    use_future(move || async move {
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+            now.set(timestamp_now());
+        }
+    });
+
+    let entries = use_memo(move || {
+        let mut entries = vec![];
+        if let Some(Ok(wallet_status)) = &*wallet_status.read() {
+            entries.push(ActivityEntry {
+                kind: ActivityKind::Sync,
+                text: CCStr::from("Synchronized"),
+                timestamp: wallet_status.last_sync_ts,
+            });
+        }
+        if let Some(wallet_activity) = state_management::WALLET_ACTIVITY.read().get(&wallet_name) {
+            // Confirmed transactions are taken from the history of the wallet below
+            entries.extend(
+                wallet_activity
+                    .iter()
+                    .filter(|activity| activity.kind != MonitoringEventKind::TransactionConfirmed)
+                    .map(|activity| ActivityEntry {
+                        kind: ActivityKind::Warning,
+                        text: activity.message.clone(),
+                        timestamp: activity.timestamp,
+                    }),
+            );
+        }
+        if let Some(Ok(wallet_transactions)) = &*wallet_transactions_content.read() {
+            entries.extend(wallet_transactions.iter().filter_map(|tx_sum| {
+                let confirmation_time = tx_sum.confirmation_time.as_ref()?;
+                Some(ActivityEntry {
+                    kind: ActivityKind::Transaction,
+                    text: CCStr::from(format!(
+                        "Transaction {} confirmed",
+                        short_txid(&tx_sum.txid.to_string())
+                    )),
+                    timestamp: confirmation_time.timestamp,
+                })
+            }));
+        }
+        if let Some(Ok(heritage_configs_with_info)) = &*heritage_configs_with_info.read() {
+            entries.extend(heritage_configs_with_info.iter().filter_map(|hcwi| {
+                Some(ActivityEntry {
+                    kind: ActivityKind::HeritageConfig,
+                    text: CCStr::from("Heritage Configuration first used"),
+                    timestamp: hcwi.firstuse_ts?,
+                })
+            }));
+        }
+        entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        entries.truncate(MAX_TIMELINE_ENTRIES);
+        entries
+    });
+
+    use_drop(|| log::debug!("ActivityTimeline Dropped"));
+
+    rsx! {
+        if !entries.read().is_empty() {
+            div { class: "rounded-box border border-base-content/5 shadow-md bg-base-100 my-4 p-4",
+                h2 { class: "text-2xl font-bold mb-4", "Recent Activity" }
+                ul { class: "flex flex-col gap-2",
+                    for entry in entries() {
+                        li { class: "flex flex-row gap-3 items-center text-sm",
+                            span { class: entry.kind.marker_class() }
+                            span { class: "grow", {entry.text} }
+                            span {
+                                class: "text-(--color-base-content)/60 text-nowrap",
+                                title: timestamp_to_string(entry.timestamp),
+                                {time_ago(now(), entry.timestamp)}
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
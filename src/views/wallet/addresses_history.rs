@@ -366,6 +366,7 @@ impl From<(&[TransactionStats], &[SimpleUtxo])> for UIAddressTransactionsHistory
 struct UIAddressesHistoryExpandableRow {
     address: UIBtcAddr,
     address_origin: CCStr,
+    reserved_purpose: Option<CCStr>,
     expiration_badge: LResult<UIExpirationBadge>,
     balance: LResult<UIBtcAmount>,
     utxo_count: LResult<usize>,
@@ -386,8 +387,17 @@ impl LoadedElement for UIAddressesHistoryExpandableRow {
                     div { class: "grid grid-cols-6 gap-1 px-4 py-3 items-center cursor-pointer",
 
                         // Address column
-                        div { class: "col-span-3 font-mono truncate",
-                            LoadedComponent { input: m.map(self.address.clone()) }
+                        div { class: "col-span-3 flex flex-row gap-2 items-center",
+                            div { class: "font-mono truncate",
+                                LoadedComponent { input: m.map(self.address.clone()) }
+                            }
+                            if let Some(ref purpose) = self.reserved_purpose {
+                                div {
+                                    class: "badge badge-sm badge-info text-nowrap",
+                                    title: "Reserved, never handed out by \"Receive\"",
+                                    "Reserved: {purpose}"
+                                }
+                            }
                         }
 
                         // Heritage config column
@@ -456,6 +466,7 @@ impl LoadedElement for UIAddressesHistoryExpandableRow {
         Self {
             address: UIBtcAddr::place_holder(),
             address_origin: CCStr::place_holder(),
+            reserved_purpose: None,
             expiration_badge: None,
             balance: None,
             utxo_count: None,
@@ -476,11 +487,16 @@ impl FromRef<WalletAddressWithInfo> for UIAddressesHistoryExpandableRow {
             heritage_config_infos,
             tx_stats,
             utxo_stats,
+            reserved_purpose,
         } = wallet_address_with_info;
 
         let address = UIBtcAddr::from(wallet_address.address().to_string());
         let (fg, dp) = wallet_address.origin();
         let address_origin = CCStr::from(format!("[{fg}/{dp}]"));
+        let reserved_purpose = match reserved_purpose {
+            Some(Ok(reserved_purpose)) => reserved_purpose.clone(),
+            _ => None,
+        };
 
         let expiration_badge = heritage_config_infos.lrmap(|heritage_config_infos| {
             UIExpirationBadge::from((
@@ -510,6 +526,7 @@ impl FromRef<WalletAddressWithInfo> for UIAddressesHistoryExpandableRow {
         Self {
             address,
             address_origin,
+            reserved_purpose,
             expiration_badge,
             balance,
             utxo_count,
@@ -532,7 +549,9 @@ pub(super) fn AddressesHistory() -> Element {
             addresses
                 .iter()
                 .filter(|addr| {
+                    // Reserved addresses are shown even before they receive anything
                     !active_filter
+                        || matches!(addr.reserved_purpose, Some(Ok(Some(_))))
                         || match addr.tx_stats {
                             Some(Ok(ref stats)) => !stats.is_empty(),
                             _ => false,
@@ -564,7 +583,7 @@ pub(super) fn AddressesHistory() -> Element {
                         }
                         span { class: "text-base ml-2",
                             if filter_addresses_without_tx() {
-                                "Only used addresses"
+                                "Only used and reserved addresses"
                             } else {
                                 "All generated addresses"
                             }
@@ -12,11 +12,14 @@ use crate::{
             ExternalDependencyStatus, KeyProviderType, OnlineWalletType, UIBadge, UIBadgeStyle,
         },
         modal::{ConfigModal, InfoModal},
+        quick_actions::prompt_backup_refresh,
         svg::{Cancel, DrawSvg},
     },
-    utils::{CCStr, CheapClone},
+    utils::{check_keys_network, CCStr, CheapClone},
 };
 
+use state_management::{BackupFreshness, WalletSettings, LOW_UNUSED_XPUBS};
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 struct UIXPubStatusBadge(UIBadge);
 impl LoadedElement for UIXPubStatusBadge {
@@ -95,26 +98,31 @@ impl FromRef<AccountXPubWithStatus> for UIXPubRow {
 /// Component to configure and manage Account eXtended Public Keys.
 ///
 /// Displays a table with the status and value of each account extended public key,
-/// and provides buttons to add and auto-feed more keys.
+/// and provides buttons to add and auto-feed more keys. When enabled in the wallet
+/// settings, the pool is automatically topped-up when it runs low.
 ///
 /// # Examples
 ///
 /// ```
 /// rsx! {
-///     AccountXPubConfig {}
+///     AccountXPubConfig { wallet_name }
 /// }
 /// ```
 #[component]
-pub(super) fn AccountXPubConfig() -> Element {
+pub(super) fn AccountXPubConfig(wallet_name: CCStr) -> Element {
     log::debug!("AccountXPubConfig Rendered");
 
     let service_client_service = state_management::use_service_client_service();
+    let database_service = state_management::use_database_service();
+    let wallet_settings = use_context::<Resource<WalletSettings>>();
 
     let mut wallet = use_context::<AsyncSignal<Wallet>>();
     let keyprovider_status =
         use_context::<Memo<Option<(KeyProviderType, ExternalDependencyStatus)>>>();
     let online_status = use_context::<Memo<Option<(OnlineWalletType, ExternalDependencyStatus)>>>();
 
+    let backup_freshness = try_use_context::<FResource<BackupFreshness>>();
+
     let account_xpubs = helper_hooks::use_resource_wallet_account_xpubs(wallet);
 
     let could_generate = use_memo(move || match keyprovider_status() {
@@ -151,6 +159,7 @@ pub(super) fn AccountXPubConfig() -> Element {
             .split(['\n', ' '])
             .filter(|xpub_txt| !xpub_txt.is_empty())
             .map(|xpub_txt| {
+                check_keys_network(xpub_txt)?;
                 Ok(AccountXPub::try_from(
                     xpub_txt
                         .parse::<DescriptorPublicKey>()
@@ -168,6 +177,21 @@ pub(super) fn AccountXPubConfig() -> Element {
         }
     });
 
+    let unused_count = use_memo(move || {
+        account_xpubs.lrmap_ok(|account_xpubs| {
+            account_xpubs
+                .iter()
+                .filter(|axpub| matches!(axpub, AccountXPubWithStatus::Unused(_)))
+                .count()
+        })
+    });
+    let auto_topup = use_memo(move || {
+        wallet_settings
+            .read()
+            .as_ref()
+            .is_some_and(|wallet_settings| wallet_settings.auto_topup_xpubs)
+    });
+
     let last_seen_index = use_memo(move || {
         account_xpubs
             .lrmap_ok(|account_xpubs| {
@@ -211,6 +235,10 @@ pub(super) fn AccountXPubConfig() -> Element {
             Ok(_) => {
                 log::info!("Successfully auto-fed new Account XPubs to the wallet");
                 alert_success("New Account XPubs auto-fed");
+                let wallet_name = wallet
+                    .with_peek(async |wallet| CCStr::from(wallet.name()))
+                    .await;
+                prompt_backup_refresh(database_service, wallet_name, backup_freshness).await;
                 if wallet_online_no_fingerprint {
                     *operation_progress.write() = "Updating service wallet binding...".to_owned();
                     wallet
@@ -232,10 +260,7 @@ pub(super) fn AccountXPubConfig() -> Element {
                 *add_xpubs_modal.write() = false;
             }
             Err(e) => {
-                log::error!("Failed to feed new Account XPubs to the wallet: {e}");
-                alert_error(format!(
-                    "Failed to feed new Account XPubs to the wallet: {e}"
-                ));
+                alert_failure("Failed to feed new Account XPubs to the wallet", e);
             }
         };
     };
@@ -278,8 +303,7 @@ pub(super) fn AccountXPubConfig() -> Element {
         {
             Ok(account_xpubs) => account_xpubs,
             Err(e) => {
-                log::error!("Failed to generate Account XPubs: {e}");
-                alert_error(format!("Failed to generate Account XPubs: {e}"));
+                alert_failure("Failed to generate Account XPubs", e);
                 *generating.write() = false;
                 return;
             }
@@ -299,7 +323,7 @@ pub(super) fn AccountXPubConfig() -> Element {
         );
     };
 
-    let auto_feed_xpubs = move |_| async move {
+    let mut auto_feed_xpubs = async move || {
         log::debug!("Auto-feed XPubs operation started");
         *in_operation.write() = true;
         *operation_progress.write() = "Starting auto-feed operation...".to_owned();
@@ -323,8 +347,7 @@ pub(super) fn AccountXPubConfig() -> Element {
         {
             Ok(account_xpubs) => account_xpubs,
             Err(e) => {
-                log::error!("Failed to generate new Account XPubs: {e}");
-                alert_error(format!("Failed to generate new Account XPubs: {e}"));
+                alert_failure("Failed to generate new Account XPubs", e);
                 *in_operation.write() = false;
                 *operation_progress.write() = String::new();
                 log::debug!("Auto-feed XPubs operation completed");
@@ -339,6 +362,43 @@ pub(super) fn AccountXPubConfig() -> Element {
         log::debug!("Auto-feed XPubs operation completed");
     };
 
+    // Top-up the pool when it runs low, only once per display of the configuration
+    let mut auto_topup_attempted = use_signal(|| false);
+    use_effect(move || {
+        if auto_topup()
+            && can_auto_feed()
+            && !auto_topup_attempted()
+            && unused_count().is_some_and(|count| count < LOW_UNUSED_XPUBS)
+            && !*in_operation.peek()
+        {
+            log::info!("Account XPubs pool is low, topping it up");
+            auto_topup_attempted.set(true);
+            spawn(async move { auto_feed_xpubs().await });
+        }
+    });
+
+    let toggle_auto_topup = move |evt: Event<FormData>| {
+        let wallet_name = wallet_name.clone();
+        async move {
+            let Some(current_settings) = wallet_settings.cloned() else {
+                return;
+            };
+            let new_settings = WalletSettings {
+                auto_topup_xpubs: evt.checked(),
+                ..current_settings
+            };
+            if let Err(e) = optimistic_update(
+                wallet_settings,
+                new_settings.clone(),
+                state_management::save_wallet_settings(database_service, wallet_name, new_settings),
+            )
+            .await
+            {
+                alert_failure("Failed to update the wallet settings", e);
+            }
+        }
+    };
+
     use_drop(|| log::debug!("AccountXPubConfig Dropped"));
 
     rsx! {
@@ -365,6 +425,28 @@ pub(super) fn AccountXPubConfig() -> Element {
             }
 
             if could_feed() {
+                // Pool status
+                match unused_count() {
+                    Some(0) => rsx! {
+                        div { class: "alert alert-error mb-4",
+                            "No unused Account XPub left: creating a new Heritage Configuration will fail until more are added."
+                        }
+                    },
+                    Some(count) if count < LOW_UNUSED_XPUBS => rsx! {
+                        div { class: "alert alert-warning mb-4",
+                            "Only {count} unused Account XPub(s) left, consider adding more before creating new Heritage Configurations."
+                        }
+                    },
+                    Some(count) => rsx! {
+                        div { class: "text-base mb-4",
+                            span { class: "font-bold", "{count}" }
+                            " unused Account XPubs available"
+                        }
+                    },
+                    None => rsx! {
+                        div { class: "skeleton h-6 w-64 mb-4" }
+                    },
+                }
                 // Table of XPubs
                 div { class: "h-96 overflow-x-auto bg-base-100 rounded-lg shadow mb-6",
                     table { class: "table table-pin-rows w-full",
@@ -400,7 +482,7 @@ pub(super) fn AccountXPubConfig() -> Element {
                     button {
                         class: "btn btn-secondary",
                         disabled: !can_auto_feed() || in_operation(),
-                        onclick: auto_feed_xpubs,
+                        onclick: move |_| auto_feed_xpubs(),
                         if in_operation() {
                             span { class: "loading loading-spinner loading-sm mr-2" }
                             "Auto-feeding..."
@@ -422,6 +504,19 @@ pub(super) fn AccountXPubConfig() -> Element {
                 }
             }
 
+            if could_auto_feed() {
+                label { class: "label text-sm mb-6",
+                    input {
+                        r#type: "checkbox",
+                        class: "toggle toggle-sm",
+                        checked: auto_topup(),
+                        disabled: wallet_settings.read().is_none(),
+                        onchange: toggle_auto_topup,
+                    }
+                    "Automatically auto-feed when fewer than {LOW_UNUSED_XPUBS} unused XPubs remain"
+                }
+            }
+
             // Configuration section
             if could_generate() {
                 div { class: "collapse collapse-arrow border border-base-content/10 rounded-box",
@@ -0,0 +1,256 @@
+use crate::prelude::*;
+
+use btc_heritage_wallet::btc_heritage::{
+    bitcoincore_rpc::jsonrpc::serde_json, utils::timestamp_now, HeritageWalletBackup,
+};
+
+use crate::{
+    components::{
+        export::{use_export_directory, write_export_files, ExportToDirectory},
+        inputs::InputField,
+        qrcode::qr_code_svg,
+        svg::{DrawSvg, FileDownload, SvgSize::Size4},
+    },
+    utils::{
+        encrypt_with_passphrase, html_escape, timestamp_to_date_string, timestamp_to_file_string,
+        CCStr,
+    },
+};
+
+/// Maximum number of characters in each QR code of the sheet, keeps them easy to scan once printed
+const SHEET_QR_CODE_CHUNK_LEN: usize = 800;
+
+const SHEET_STYLE: &str = "\
+    body { font-family: sans-serif; margin: 2em; color: black; }\
+    h1 { margin-bottom: 0.2em; }\
+    table { border-collapse: collapse; margin: 1em 0; }\
+    td { padding: 0.2em 1em 0.2em 0; vertical-align: top; }\
+    .notice { border: 1px solid black; padding: 0.5em 1em; }\
+    .mono { font-family: monospace; font-size: 0.8em; word-break: break-all; white-space: pre-wrap; }\
+    .qr-codes { display: flex; flex-wrap: wrap; gap: 2em; }\
+    figure { margin: 0; width: 7cm; break-inside: avoid; text-align: center; }\
+    @media print { .page-break { break-before: page; } }";
+
+/// Build the printable backup sheet of a wallet, as a self-contained HTML document
+///
+/// The backup data are printed as text and split across as many QR codes as needed.
+/// When a passphrase is given, the backup data are encrypted and the descriptors are
+/// not printed in clear.
+fn backup_sheet_html(
+    wallet_name: &str,
+    backup: &HeritageWalletBackup,
+    passphrase: Option<&str>,
+) -> Result<String, CCStr> {
+    let fingerprint = backup
+        .fingerprint()
+        .ok()
+        .flatten()
+        .map(|fg| fg.to_string())
+        .unwrap_or_else(|| "-".to_owned());
+    let subwallets = super::bitcoin_core_export::backup_descriptors(backup)?;
+    let backup_data = serde_json::to_string(backup).map_err(|e| CCStr::from(e.to_string()))?;
+    let backup_data = match passphrase {
+        Some(passphrase) => encrypt_with_passphrase(&backup_data, passphrase)?,
+        None => backup_data,
+    };
+
+    let wallet_name = html_escape(wallet_name);
+    let mut html = format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\">\
+        <title>Heritage Wallet Backup - {wallet_name}</title><style>{SHEET_STYLE}</style></head><body>\
+        <h1>Heritage Wallet Backup</h1>\
+        <table>\
+        <tr><td>Wallet</td><td><b>{wallet_name}</b></td></tr>\
+        <tr><td>Fingerprint</td><td><b>{fingerprint}</b></td></tr>\
+        <tr><td>Date</td><td>{}</td></tr>\
+        <tr><td>Heritage Configurations</td><td>{}</td></tr>\
+        <tr><td>Encryption</td><td>{}</td></tr>\
+        </table>",
+        timestamp_to_date_string(timestamp_now()),
+        subwallets.len(),
+        if passphrase.is_some() {
+            "Passphrase (AES-256-GCM)"
+        } else {
+            "None"
+        },
+    );
+
+    html.push_str(
+        "<div class=\"notice\"><p>This sheet contains the descriptors of a Heritage wallet. \
+        They allow to find the bitcoins of the wallet and to spend them using the private keys \
+        of the owner or of the heirs, but this sheet alone does NOT give access to the bitcoins.</p>\
+        <p>To restore the wallet, use the Heritage wallet application and provide the backup data \
+        below, by typing them or by scanning the QR codes in order and concatenating their content.</p>",
+    );
+    if passphrase.is_some() {
+        html.push_str(
+            "<p>The backup data are encrypted: the passphrase chosen when creating this sheet \
+            is required to restore them. The passphrase is not written on this sheet.</p>",
+        );
+    } else {
+        html.push_str(
+            "<p>Anyone reading this sheet can see the addresses, the balance and the transactions \
+            of the wallet.</p>",
+        );
+    }
+    html.push_str("</div>");
+
+    if passphrase.is_none() {
+        html.push_str("<h2>Descriptors</h2>");
+        for (number, subwallet) in (1..).zip(subwallets.iter()) {
+            html.push_str(&format!("<h3>Heritage Configuration #{number}</h3><table>"));
+            for (name, descriptor) in [
+                ("External", &subwallet.external),
+                ("Change", &subwallet.change),
+            ] {
+                let last_index = descriptor
+                    .last_index
+                    .map_or_else(|| "unused".to_owned(), |index| index.to_string());
+                html.push_str(&format!(
+                    "<tr><td>{name}</td><td class=\"mono\">{}</td></tr>\
+                    <tr><td></td><td>Last used index: {last_index}</td></tr>",
+                    html_escape(&descriptor.descriptor)
+                ));
+            }
+            html.push_str("</table>");
+        }
+    }
+
+    html.push_str(&format!(
+        "<h2>Backup Data</h2><div class=\"mono\">{}</div>",
+        html_escape(&backup_data)
+    ));
+
+    let chunks = backup_data
+        .chars()
+        .collect::<Vec<_>>()
+        .chunks(SHEET_QR_CODE_CHUNK_LEN)
+        .map(String::from_iter)
+        .collect::<Vec<_>>();
+    html.push_str("<h2 class=\"page-break\">QR Codes</h2><div class=\"qr-codes\">");
+    for (number, chunk) in (1..).zip(chunks.iter()) {
+        let svg = qr_code_svg(chunk)
+            .ok_or_else(|| CCStr::from("The backup data do not fit in the QR codes"))?;
+        html.push_str(&format!(
+            "<figure>{svg}<figcaption>Part {number} of {}</figcaption></figure>",
+            chunks.len()
+        ));
+    }
+    html.push_str("</div></body></html>");
+
+    Ok(html)
+}
+
+/// Component saving the descriptors backup as a printable sheet
+///
+/// The sheet is meant to be printed and stored with the will, so that the heirs or
+/// the executor can restore the wallet. The backup data can be protected by a passphrase.
+#[component]
+pub(super) fn BackupSheetConfig(wallet_name: CCStr) -> Element {
+    log::debug!("BackupSheetConfig Rendered");
+
+    let descriptor_backup = use_context::<FResource<HeritageWalletBackup>>();
+
+    let passphrase = use_signal(String::new);
+    let passphrase_confirm = use_signal(String::new);
+    let passphrase_error = use_memo(move || {
+        (passphrase() != passphrase_confirm()).then(|| CCStr::from("Passphrases do not match"))
+    });
+    let mut saving = use_signal(|| false);
+
+    let export_directory = use_export_directory();
+    let export_path = use_memo({
+        let wallet_name = wallet_name.clone();
+        move || {
+            let ts_str = timestamp_to_file_string(timestamp_now());
+            format!(
+                "{}/backup-{wallet_name}-{ts_str}-sheet.html",
+                export_directory.read()
+            )
+        }
+    });
+
+    let save_sheet = move |_| {
+        let wallet_name = wallet_name.clone();
+        async move {
+            let Some(Ok(backup)) = descriptor_backup.cloned() else {
+                alert_error("The descriptors backup is not loaded yet");
+                return;
+            };
+            *saving.write() = true;
+            let passphrase = passphrase();
+            let passphrase = (!passphrase.is_empty()).then_some(passphrase);
+            let sheet = tokio::task::spawn_blocking(move || {
+                backup_sheet_html(&wallet_name, &backup, passphrase.as_deref())
+            })
+            .await
+            .map_err(|e| CCStr::from(e.to_string()))
+            .and_then(|sheet| sheet);
+            let file_path = export_path();
+            match sheet {
+                Ok(sheet) => {
+                    if write_export_files("backup sheet", [(file_path.clone(), sheet)]) {
+                        alert_info(format!(
+                            "Backup sheet saved to: {file_path}. Print it from your web browser."
+                        ));
+                        _ = open::that_in_background(file_path.as_str());
+                    }
+                }
+                Err(e) => {
+                    alert_failure("Failed to save the backup sheet", e);
+                }
+            }
+            *saving.write() = false;
+        }
+    };
+
+    use_drop(|| log::debug!("BackupSheetConfig Dropped"));
+
+    rsx! {
+        div { class: "rounded-box border border-base-content/5 shadow-md p-4 my-4",
+            h2 { class: "text-2xl font-bold mb-4", "Printable Backup Sheet" }
+
+            div { class: "text-sm font-light mb-4",
+                "Save the descriptors backup as a sheet to print and store with your will. It
+                contains the descriptors in a human-readable form and as QR codes, so that your
+                heirs or the executor of your will can restore the wallet. Optionally, protect the
+                backup with a passphrase: the descriptors are then encrypted and the passphrase
+                will be required to restore the wallet. Do not forget to transmit it."
+            }
+
+            div { class: "grid grid-cols-1 lg:grid-cols-2 gap-4 max-w-2xl",
+                InputField {
+                    title: "Passphrase (optional)",
+                    value: passphrase,
+                    r#type: "password",
+                    placeholder: "Leave empty for no encryption",
+                    value_error: passphrase_error,
+                }
+                InputField {
+                    title: "Confirm Passphrase",
+                    value: passphrase_confirm,
+                    r#type: "password",
+                    placeholder: "Re-enter the passphrase",
+                    value_error: passphrase_error,
+                }
+            }
+
+            ExportToDirectory {
+                directory: export_directory,
+                display_path: ReadOnlySignal::from(export_path),
+                button {
+                    class: "btn",
+                    onclick: save_sheet,
+                    disabled: saving() || passphrase_error().is_some()
+                        || !matches!(*descriptor_backup.read(), Some(Ok(_))),
+                    if saving() {
+                        span { class: "loading loading-spinner" }
+                    } else {
+                        DrawSvg::<FileDownload> { size: Size4 }
+                    }
+                    "Save Printable Sheet"
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use crate::prelude::*;
+
+use btc_heritage_wallet::btc_heritage::{
+    bitcoincore_rpc::jsonrpc::serde_json::{self, json, Value},
+    HeritageWalletBackup,
+};
+
+use crate::{
+    components::copy::CopyTextarea,
+    utils::{descriptor_checksum, CCStr},
+};
+
+/// Number of addresses Bitcoin Core derives beyond the last used index of a descriptor
+const RANGE_LOOKAHEAD: u64 = 1000;
+
+/// A descriptor of a backup, with its checksum
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct BackupDescriptor {
+    pub descriptor: String,
+    pub last_index: Option<u64>,
+}
+
+/// The descriptors of one Heritage Configuration of a backup
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SubwalletDescriptors {
+    pub external: BackupDescriptor,
+    pub change: BackupDescriptor,
+    pub first_use_ts: Option<u64>,
+}
+
+/// Extract the descriptors of each Heritage Configuration from a descriptors backup,
+/// oldest first, ensuring they all carry a valid checksum
+pub(crate) fn backup_descriptors(
+    backup: &HeritageWalletBackup,
+) -> Result<Vec<SubwalletDescriptors>, CCStr> {
+    let backup = serde_json::to_value(backup).map_err(|e| CCStr::from(e.to_string()))?;
+    let subwallets = backup
+        .as_array()
+        .ok_or_else(|| CCStr::from("Unexpected descriptors backup format"))?;
+
+    let backup_descriptor = |subwallet: &Value, descriptor_field: &str, index_field: &str| {
+        let descriptor = subwallet
+            .get(descriptor_field)
+            .and_then(Value::as_str)
+            .ok_or_else(|| CCStr::from(format!("Missing {descriptor_field} in backup")))?;
+        let descriptor = descriptor.split('#').next().unwrap_or_default();
+        let checksum = descriptor_checksum(descriptor)
+            .ok_or_else(|| CCStr::from(format!("Invalid {descriptor_field} in backup")))?;
+        Ok::<_, CCStr>(BackupDescriptor {
+            descriptor: format!("{descriptor}#{checksum}"),
+            last_index: subwallet.get(index_field).and_then(Value::as_u64),
+        })
+    };
+
+    subwallets
+        .iter()
+        .map(|subwallet| {
+            Ok(SubwalletDescriptors {
+                external: backup_descriptor(
+                    subwallet,
+                    "external_descriptor",
+                    "last_external_index",
+                )?,
+                change: backup_descriptor(subwallet, "change_descriptor", "last_change_index")?,
+                first_use_ts: subwallet.get("first_use_ts").and_then(Value::as_u64),
+            })
+        })
+        .collect()
+}
+
+/// Convert a descriptors backup into the requests of a Bitcoin Core `importdescriptors` call
+///
+/// Each Heritage Configuration produces an external and an internal (change) descriptor.
+/// Only the descriptors of the last one are made active, so that Bitcoin Core
+/// generates new addresses with the current Heritage Configuration.
+pub(super) fn importdescriptors_requests(
+    backup: &HeritageWalletBackup,
+) -> Result<Vec<Value>, CCStr> {
+    let subwallets = backup_descriptors(backup)?;
+
+    let mut requests = Vec::with_capacity(subwallets.len() * 2);
+    for (i, subwallet) in subwallets.iter().enumerate() {
+        let active = i + 1 == subwallets.len();
+        // Bitcoin Core rescans the blockchain from the timestamp, "now" skips the rescan
+        let timestamp = match subwallet.first_use_ts {
+            Some(ts) => json!(ts),
+            None => json!("now"),
+        };
+        for (descriptor, internal) in [(&subwallet.external, false), (&subwallet.change, true)] {
+            let range_end = descriptor
+                .last_index
+                .map_or(RANGE_LOOKAHEAD - 1, |index| index + RANGE_LOOKAHEAD);
+            requests.push(json!({
+                "desc": descriptor.descriptor,
+                "timestamp": timestamp,
+                "active": active,
+                "internal": internal,
+                "range": [0, range_end],
+            }));
+        }
+    }
+    Ok(requests)
+}
+
+/// Convert a descriptors backup into a Bitcoin Core `importdescriptors` payload
+fn importdescriptors_payload(backup: &HeritageWalletBackup) -> Result<CCStr, CCStr> {
+    serde_json::to_string_pretty(&importdescriptors_requests(backup)?)
+        .map(CCStr::from)
+        .map_err(|e| CCStr::from(e.to_string()))
+}
+
+/// Component exporting the wallet descriptors as a Bitcoin Core `importdescriptors` payload
+///
+/// It allows users to follow the wallet with a watch-only wallet on their own node.
+#[component]
+pub(super) fn BitcoinCoreExportConfig() -> Element {
+    log::debug!("BitcoinCoreExportConfig Rendered");
+
+    let descriptor_backup = use_context::<FResource<HeritageWalletBackup>>();
+
+    let payload = use_memo(move || {
+        descriptor_backup
+            .lrmap(importdescriptors_payload)
+            .map(|result| result.and_then(|payload| payload))
+    });
+
+    use_drop(|| log::debug!("BitcoinCoreExportConfig Dropped"));
+
+    rsx! {
+        div { class: "rounded-box border border-base-content/5 shadow-md p-4 my-4",
+            h2 { class: "text-2xl font-bold mb-4", "Export to Bitcoin Core" }
+
+            div { class: "text-sm font-light mb-4",
+                "Follow this wallet from your own Bitcoin Core node by importing its descriptors
+                in a watch-only wallet. Create a wallet without private keys, then paste the
+                payload below:"
+            }
+            div { class: "font-mono text-sm bg-base-200 rounded-box p-2 mb-4 break-all",
+                "bitcoin-cli -named createwallet wallet_name=heritage disable_private_keys=true blank=true"
+                br {}
+                "bitcoin-cli -rpcwallet=heritage importdescriptors '<payload>'"
+            }
+
+            match payload() {
+                Some(Ok(payload)) => rsx! {
+                    CopyTextarea { value: payload, rows: 12 }
+                },
+                Some(Err(e)) => rsx! {
+                    div { class: "alert alert-error", "Could not export the descriptors: {e}" }
+                },
+                None => rsx! {
+                    div { class: "skeleton h-48 w-full" }
+                },
+            }
+        }
+    }
+}
@@ -0,0 +1,297 @@
+use crate::prelude::*;
+
+use btc_heritage_wallet::{
+    bitcoin::Amount,
+    btc_heritage::{bitcoincore_rpc::jsonrpc::serde_json::Value, HeritageWalletBackup},
+    online_wallet::{BlockchainProviderConfig, WalletStatus},
+};
+
+use crate::{
+    components::balance::UIBtcAmount,
+    utils::{CCStr, CheapClone},
+};
+
+/// Steps of the watch-only wallet setup wizard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WatchOnlySetupStep {
+    Name,
+    Import,
+    Verify,
+}
+
+/// Default name of the watch-only wallet on the node, Bitcoin Core wallet names
+/// end up in file paths and URLs so only a safe subset of characters is kept
+fn default_node_wallet_name(wallet_name: &str) -> String {
+    let name = wallet_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>();
+    format!("heritage-{name}")
+}
+
+/// Wizard creating a watch-only wallet for this wallet on the Bitcoin Core node
+/// configured as blockchain provider
+///
+/// It automates the manual steps of the Bitcoin Core export: the descriptors are
+/// imported in a dedicated descriptor wallet without private keys, then the node
+/// wallet is verified to track the addresses of this wallet.
+#[component]
+pub(super) fn BitcoinCoreWatchOnlyConfig(wallet_name: CCStr) -> Element {
+    log::debug!("BitcoinCoreWatchOnlyConfig Rendered");
+
+    let blockchain_provider_service = state_management::use_blockchain_provider_service();
+    let descriptor_backup = use_context::<FResource<HeritageWalletBackup>>();
+    let addresses_with_info = use_context::<FMemo<CheapClone<[WalletAddressWithInfo]>>>();
+    let wallet_status = use_context::<FResource<WalletStatus>>();
+
+    let blockchain_provider_config = use_resource(move || async move {
+        state_management::get_blockchain_provider_config(blockchain_provider_service).await
+    });
+    let is_bitcoin_core = use_memo(move || {
+        matches!(
+            *blockchain_provider_config.read(),
+            Some(BlockchainProviderConfig::BitcoinCore { .. })
+        )
+    });
+
+    let import_requests = use_memo(move || {
+        descriptor_backup
+            .lrmap(super::bitcoin_core_export::importdescriptors_requests)
+            .map(|result| result.and_then(|requests| requests))
+    });
+
+    let mut step = use_signal(|| WatchOnlySetupStep::Name);
+    let mut node_wallet_name = use_signal(|| default_node_wallet_name(&wallet_name));
+    let node_wallet_name_error = use_memo(move || {
+        let node_wallet_name = node_wallet_name.read();
+        if node_wallet_name.is_empty() {
+            Some(CCStr::from("Provide a name"))
+        } else if !node_wallet_name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            Some(CCStr::from(
+                "Only use letters, digits, dashes and underscores",
+            ))
+        } else {
+            None
+        }
+    });
+    let mut in_progress = use_signal(|| false);
+    let mut setup_error = use_signal(|| None::<CCStr>);
+    let mut report = use_signal(|| None::<Result<state_management::WatchOnlyWalletReport, CCStr>>);
+
+    let verify = move || async move {
+        let Some(Ok(addresses_with_info)) = addresses_with_info() else {
+            alert_error("The addresses of the wallet are not loaded yet");
+            return;
+        };
+        let addresses = addresses_with_info
+            .iter()
+            .map(|address| address.wallet_address.address().to_string())
+            .collect();
+        *in_progress.write() = true;
+        let result = state_management::verify_bitcoin_core_watch_only_wallet(
+            blockchain_provider_service,
+            CCStr::from(node_wallet_name.peek().as_str()),
+            addresses,
+        )
+        .await
+        .map_err(CCStr::from);
+        report.set(Some(result));
+        *in_progress.write() = false;
+    };
+
+    let setup = move |_| async move {
+        let Some(Ok(import_requests)) = import_requests() else {
+            alert_error("The descriptors of the wallet are not loaded yet");
+            return;
+        };
+        *in_progress.write() = true;
+        *step.write() = WatchOnlySetupStep::Import;
+        setup_error.set(None);
+        match state_management::setup_bitcoin_core_watch_only_wallet(
+            blockchain_provider_service,
+            CCStr::from(node_wallet_name.peek().as_str()),
+            Value::Array(import_requests),
+        )
+        .await
+        {
+            Ok(()) => {
+                alert_success("Watch-only wallet created on the Bitcoin Core node");
+                *step.write() = WatchOnlySetupStep::Verify;
+                verify().await;
+            }
+            Err(e) => {
+                setup_error.set(Some(CCStr::from(e)));
+                *step.write() = WatchOnlySetupStep::Name;
+            }
+        }
+        *in_progress.write() = false;
+    };
+
+    let wallet_balance = use_memo(move || {
+        wallet_status.lrmap(|wallet_status| {
+            Amount::from_sat(wallet_status.balance.total_balance().get_total())
+        })
+    });
+
+    use_drop(|| log::debug!("BitcoinCoreWatchOnlyConfig Dropped"));
+
+    rsx! {
+        div { class: "rounded-box border border-base-content/5 shadow-md p-4 my-4",
+            h2 { class: "text-2xl font-bold mb-4", "Bitcoin Core Watch-Only Wallet" }
+
+            div { class: "text-sm font-light mb-4",
+                "Create a watch-only wallet for this wallet on the Bitcoin Core node configured as
+                blockchain provider, so that you can follow it from your node or from any software
+                connected to it. The node wallet has no private key: it cannot spend the bitcoins."
+            }
+
+            if !is_bitcoin_core() {
+                div { class: "alert alert-info",
+                    "This requires a Bitcoin Core node as blockchain provider, configure it in the
+                    application settings."
+                }
+            } else {
+                ul { class: "steps w-full mb-4",
+                    li { class: "step step-primary", "Name" }
+                    li {
+                        class: "step",
+                        class: if step() != WatchOnlySetupStep::Name { "step-primary" },
+                        "Create & Import"
+                    }
+                    li {
+                        class: "step",
+                        class: if step() == WatchOnlySetupStep::Verify { "step-primary" },
+                        "Verify"
+                    }
+                }
+
+                match step() {
+                    WatchOnlySetupStep::Name => rsx! {
+                        div { class: "flex flex-col gap-2 max-w-xl",
+                            fieldset { class: "fieldset",
+                                legend { class: "fieldset-legend", "Node Wallet Name" }
+                                input {
+                                    r#type: "text",
+                                    class: "input w-full",
+                                    class: if node_wallet_name_error().is_some() { "input-error" },
+                                    value: node_wallet_name(),
+                                    oninput: move |evt| node_wallet_name.set(evt.value()),
+                                }
+                                div { class: "fieldset-label",
+                                    class: if node_wallet_name_error().is_some() { "text-error" },
+                                    if let Some(e) = node_wallet_name_error() {
+                                        {e}
+                                    } else {
+                                        "If a wallet with this name already exists on the node, the descriptors
+                                        are imported in it."
+                                    }
+                                }
+                            }
+                            if let Some(e) = setup_error() {
+                                div { class: "alert alert-error", "{e}" }
+                            }
+                            button {
+                                class: "btn btn-primary w-fit",
+                                disabled: in_progress() || node_wallet_name_error().is_some()
+                                    || !matches!(import_requests(), Some(Ok(_))),
+                                onclick: setup,
+                                "Create Watch-Only Wallet"
+                            }
+                        }
+                    },
+                    WatchOnlySetupStep::Import => rsx! {
+                        div { class: "flex items-center gap-4",
+                            span { class: "loading loading-spinner loading-lg" }
+                            div {
+                                "Importing the descriptors in the wallet "
+                                span { class: "font-bold", "{node_wallet_name}" }
+                                ". The node rescans the blockchain from the first use of this wallet,
+                                it may take a while."
+                            }
+                        }
+                    },
+                    WatchOnlySetupStep::Verify => rsx! {
+                        div { class: "flex flex-col gap-4",
+                            match report() {
+                                Some(Ok(report)) => {
+                                    let total_addresses = report.tracked_addresses + report.untracked_addresses.len();
+                                    let scanning_percent = report
+                                        .scanning_progress
+                                        .map(|progress| (progress * 100.0).round());
+                                    rsx! {
+                                        div { class: "grid grid-cols-[auto_1fr] gap-x-8 gap-y-2 text-base",
+                                            div { class: "font-semibold", "Node wallet" }
+                                            div { class: "font-mono", "{node_wallet_name}" }
+                                            div { class: "font-semibold", "Tracked addresses" }
+                                            div {
+                                                class: if report.untracked_addresses.is_empty() { "text-success" } else { "text-error" },
+                                                "{report.tracked_addresses} / {total_addresses}"
+                                            }
+                                            div { class: "font-semibold", "Node wallet balance" }
+                                            div {
+                                                AlwaysLoadedComponent::<UIBtcAmount> { input: report.balance.into() }
+                                            }
+                                            div { class: "font-semibold", "Heritage wallet balance" }
+                                            div {
+                                                LoadedComponent::<UIBtcAmount> { input: wallet_balance.into() }
+                                            }
+                                        }
+                                        if let Some(scanning_percent) = scanning_percent {
+                                            div { class: "alert alert-info",
+                                                "The node is still rescanning the blockchain ({scanning_percent}%), the
+                                                balances will match once the rescan completes."
+                                            }
+                                        }
+                                        if !report.untracked_addresses.is_empty() {
+                                            div { class: "alert alert-error flex flex-col items-start",
+                                                "The node wallet does not track these addresses of this wallet:"
+                                                for address in report.untracked_addresses.iter().take(10) {
+                                                    div { class: "font-mono text-sm", "{address}" }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                                Some(Err(e)) => rsx! {
+                                    div { class: "alert alert-error", "Could not verify the node wallet: {e}" }
+                                },
+                                None => rsx! {
+                                    div { class: "skeleton h-32 w-full" }
+                                },
+                            }
+                            div { class: "flex gap-4",
+                                button {
+                                    class: "btn btn-primary",
+                                    disabled: in_progress(),
+                                    onclick: move |_| verify(),
+                                    if in_progress() {
+                                        span { class: "loading loading-spinner" }
+                                    }
+                                    "Verify Again"
+                                }
+                                button {
+                                    class: "btn btn-outline",
+                                    disabled: in_progress(),
+                                    onclick: move |_| {
+                                        report.set(None);
+                                        *step.write() = WatchOnlySetupStep::Name;
+                                    },
+                                    "Start Over"
+                                }
+                            }
+                        }
+                    },
+                }
+            }
+        }
+    }
+}
@@ -8,10 +8,10 @@ use btc_heritage_wallet::{
     Wallet,
 };
 
-use crate::utils::feerate_sat_per_vb;
+use crate::utils::{feerate_sat_per_vb, CCStr};
 
 #[component]
-pub(super) fn BlockInclusionObjectiveConfig() -> Element {
+pub(super) fn BlockInclusionObjectiveConfig(wallet_name: CCStr) -> Element {
     let mut wallet = use_context::<AsyncSignal<Wallet>>();
     let wallet_status = use_context::<FResource<WalletStatus>>();
 
@@ -52,10 +52,7 @@ pub(super) fn BlockInclusionObjectiveConfig() -> Element {
                 alert_success("Block Inclusion Objective updated, please synchronize your wallet to update the fee rate.");
             }
             Err(e) => {
-                log::error!("Failed to update the Block Inclusion Objective: {e}");
-                alert_error(format!(
-                    "Failed to update the Block Inclusion Objective: {e}"
-                ));
+                alert_failure("Failed to update the Block Inclusion Objective", e);
             }
         };
         *in_operation.write() = false;
@@ -76,7 +73,7 @@ pub(super) fn BlockInclusionObjectiveConfig() -> Element {
                     div { class: "text-base font-semibold", "Current Fee Rate" }
                     div { class: "text-sm font-light", "{current_feerate()}" }
                 }
-                WalletSync {}
+                WalletSync { wallet_name }
             }
 
             div { class: "flex flex-row gap-4 items-end",
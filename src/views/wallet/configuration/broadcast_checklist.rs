@@ -0,0 +1,119 @@
+use crate::prelude::*;
+
+use state_management::{BroadcastChecklist, WalletSettings};
+
+use crate::utils::CCStr;
+
+/// Checklist the owner must tick before broadcasting the bigger transactions of the wallet
+#[component]
+pub(super) fn BroadcastChecklistConfig(wallet_name: CCStr) -> Element {
+    let database_service = state_management::use_database_service();
+    let wallet_settings = use_context::<Resource<WalletSettings>>();
+
+    let mut threshold_str = use_signal(String::new);
+    let mut items_str = use_signal(String::new);
+    use_effect(move || {
+        if let Some(current) = wallet_settings.cloned() {
+            let checklist = current.broadcast_checklist;
+            threshold_str.set(
+                checklist
+                    .threshold
+                    .map(|threshold| threshold.to_string())
+                    .unwrap_or_default(),
+            );
+            items_str.set(checklist.items.join("\n"));
+        }
+    });
+    // An empty threshold disables the checklist
+    let threshold = use_memo(move || {
+        let threshold_str = threshold_str.read();
+        match threshold_str.trim() {
+            "" => Some(None),
+            s => s.parse::<u64>().ok().map(Some),
+        }
+    });
+
+    let new_settings = use_memo(move || {
+        Some(WalletSettings {
+            broadcast_checklist: BroadcastChecklist {
+                threshold: threshold()?,
+                items: items_str
+                    .read()
+                    .lines()
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(str::to_owned)
+                    .collect(),
+            },
+            ..wallet_settings.cloned().unwrap_or_default()
+        })
+    });
+    let has_changes = use_memo(move || new_settings() != wallet_settings.cloned());
+
+    let mut in_operation = use_signal(|| false);
+    let save_settings = move |_| {
+        let wallet_name = wallet_name.clone();
+        async move {
+            let Some(new_settings) = new_settings() else {
+                return;
+            };
+            *in_operation.write() = true;
+            match optimistic_update(
+                wallet_settings,
+                new_settings.clone(),
+                state_management::save_wallet_settings(database_service, wallet_name, new_settings),
+            )
+            .await
+            {
+                Ok(()) => alert_success("Broadcast checklist updated"),
+                Err(e) => alert_failure("Failed to update the broadcast checklist", e),
+            }
+            *in_operation.write() = false;
+        }
+    };
+
+    rsx! {
+        div { class: "rounded-box border border-base-content/5 shadow-md p-4 my-4",
+            h2 { class: "text-2xl font-bold mb-4", "Broadcast Checklist" }
+
+            div { class: "text-sm font-light mb-4",
+                "Verifications to confirm one by one before broadcasting a transaction sending more than the threshold."
+            }
+
+            div { class: "flex flex-row flex-wrap gap-4 items-end",
+                fieldset { class: "fieldset w-64",
+                    legend { class: "fieldset-legend", "Threshold (sat)" }
+                    input {
+                        r#type: "number",
+                        min: "0",
+                        class: "input",
+                        class: if threshold().is_none() { "input-error" },
+                        disabled: in_operation(),
+                        placeholder: "Disabled",
+                        value: "{threshold_str}",
+                        oninput: move |evt| threshold_str.set(evt.value()),
+                    }
+                    div { class: "fieldset-label", "Leave empty to disable the checklist" }
+                }
+                fieldset { class: "fieldset w-lg",
+                    legend { class: "fieldset-legend", "Items, one per line" }
+                    textarea {
+                        class: "textarea w-full",
+                        rows: "4",
+                        disabled: in_operation(),
+                        placeholder: BroadcastChecklist::DEFAULT_ITEMS.join("\n"),
+                        value: "{items_str}",
+                        oninput: move |evt| items_str.set(evt.value()),
+                    }
+                    div { class: "fieldset-label", "Leave empty to use the default items" }
+                }
+                button {
+                    class: "btn btn-primary",
+                    disabled: !has_changes() || new_settings().is_none() || in_operation(),
+                    onclick: save_settings,
+                    "Save"
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use crate::prelude::*;
+
+use btc_heritage_wallet::btc_heritage::{
+    bitcoincore_rpc::jsonrpc::serde_json::{self, json},
+    utils::timestamp_now,
+    HeritageWalletBackup,
+};
+
+use crate::{
+    components::{
+        copy::CopyTextarea,
+        export::{use_export_directory, write_export_files, ExportToDirectory},
+        svg::{DrawSvg, FileDownload, SvgSize::Size4},
+    },
+    utils::{descriptor_checksum, timestamp_to_file_string, CCStr},
+};
+
+/// Maximum length of the name of a miniscript wallet on a Coldcard
+const COLDCARD_MAX_NAME_LEN: usize = 20;
+
+/// Name of the Coldcard miniscript wallet of a Heritage Configuration
+///
+/// The wallet name is shortened so that the Heritage Configuration number always fits.
+fn coldcard_wallet_name(wallet_name: &str, number: usize) -> String {
+    let suffix = format!(" #{number}");
+    let prefix = wallet_name
+        .chars()
+        .filter(|c| c.is_ascii_graphic() || *c == ' ')
+        .take(COLDCARD_MAX_NAME_LEN.saturating_sub(suffix.len()))
+        .collect::<String>();
+    format!("{}{suffix}", prefix.trim_end())
+}
+
+/// Convert a descriptors backup into one Coldcard registration file per Heritage Configuration
+///
+/// Coldcard expects a single multipath descriptor (BIP-389) covering both the external
+/// and the change addresses of a miniscript wallet.
+fn coldcard_registration_files(
+    wallet_name: &str,
+    backup: &HeritageWalletBackup,
+) -> Result<Vec<CCStr>, CCStr> {
+    (1..)
+        .zip(super::bitcoin_core_export::backup_descriptors(backup)?)
+        .map(|(number, subwallet)| {
+            let external = subwallet
+                .external
+                .descriptor
+                .split('#')
+                .next()
+                .unwrap_or_default();
+            let multipath = external.replace("/0/*", "/<0;1>/*");
+            let checksum = descriptor_checksum(&multipath)
+                .ok_or_else(|| CCStr::from("Invalid external descriptor in backup"))?;
+            serde_json::to_string_pretty(&json!({
+                "name": coldcard_wallet_name(wallet_name, number),
+                "desc": format!("{multipath}#{checksum}"),
+            }))
+            .map(CCStr::from)
+            .map_err(|e| CCStr::from(e.to_string()))
+        })
+        .collect()
+}
+
+/// Component exporting the registration files of the Heritage Configurations for a Coldcard
+///
+/// Like the Ledger policies, a Coldcard only signs for the miniscript wallets
+/// registered on the device. This is for wallets whose keys are not managed by
+/// the application but live on a Coldcard.
+#[component]
+pub(super) fn ColdcardRegistrationConfig(wallet_name: CCStr) -> Element {
+    log::debug!("ColdcardRegistrationConfig Rendered");
+
+    let descriptor_backup = use_context::<FResource<HeritageWalletBackup>>();
+
+    let registration_files = use_memo({
+        let wallet_name = wallet_name.clone();
+        move || {
+            descriptor_backup
+                .lrmap(|backup| coldcard_registration_files(&wallet_name, backup))
+                .map(|result| result.and_then(|files| files))
+        }
+    });
+
+    let export_directory = use_export_directory();
+    let export_prefix = use_memo(move || {
+        let ts_str = timestamp_to_file_string(timestamp_now());
+        format!("{}/{wallet_name}-{ts_str}", export_directory.read())
+    });
+    let display_path = use_memo(move || format!("{}-coldcard-*.json", export_prefix.read()));
+
+    let download_files = move |_| {
+        let Some(Ok(registration_files)) = registration_files() else {
+            alert_error("The registration files are not ready yet");
+            return;
+        };
+        let prefix = export_prefix.read();
+        let files = (1..)
+            .zip(registration_files.iter())
+            .map(|(number, data)| (format!("{prefix}-coldcard-{number}.json"), data.as_bytes()));
+        if write_export_files("registration files", files) {
+            alert_info(format!(
+                "Registration files saved to: {}",
+                export_directory.read()
+            ));
+        }
+    };
+
+    use_drop(|| log::debug!("ColdcardRegistrationConfig Dropped"));
+
+    rsx! {
+        div { class: "rounded-box border border-base-content/5 shadow-md p-4 my-4",
+            h2 { class: "text-2xl font-bold mb-4", "Coldcard Registration" }
+
+            div { class: "text-sm font-light mb-4",
+                "If the keys of this wallet live on a Coldcard, register each Heritage Configuration
+                on the device so that it accepts to sign the transactions of this wallet. Copy the
+                files on a microSD card, then import them from the Coldcard (Settings > Miniscript >
+                Import from File) and verify that the device displays the same descriptor. The
+                Coldcard firmware must support Taproot miniscript."
+            }
+
+            match registration_files() {
+                Some(Ok(registration_files)) => rsx! {
+                    for (number , registration_file) in (1..).zip(registration_files) {
+                        div { class: "flex flex-col gap-2 mb-4",
+                            div { class: "font-semibold", "Heritage Configuration #{number}" }
+                            CopyTextarea { value: registration_file, rows: 6 }
+                        }
+                    }
+                },
+                Some(Err(e)) => rsx! {
+                    div { class: "alert alert-error", "Could not export the descriptors: {e}" }
+                },
+                None => rsx! {
+                    div { class: "skeleton h-32 w-full mb-4" }
+                },
+            }
+
+            ExportToDirectory {
+                directory: export_directory,
+                display_path: ReadOnlySignal::from(display_path),
+                button {
+                    class: "btn",
+                    onclick: download_files,
+                    disabled: !matches!(registration_files(), Some(Ok(_))),
+                    DrawSvg::<FileDownload> { size: Size4 }
+                    "Save Registration Files"
+                }
+            }
+        }
+    }
+}
@@ -102,8 +102,7 @@ pub(super) fn DeleteWalletConfig() -> Element {
                 navigator().push(Route::WalletListView {});
             }
             Err(e) => {
-                log::info!("Wallet deletion failed: {e}");
-                alert_error(format!("Wallet deletion failed: {e}"));
+                alert_failure("Wallet deletion failed", e);
             }
         }
 
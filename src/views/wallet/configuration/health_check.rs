@@ -0,0 +1,340 @@
+use crate::prelude::*;
+
+use std::collections::BTreeMap;
+
+use btc_heritage_wallet::{
+    btc_heritage::{utils::timestamp_now, AccountXPubId, HeritageWalletBackup},
+    heritage_service_api_client::AccountXPubWithStatus,
+    online_wallet::WalletStatus,
+    LedgerPolicy, Wallet,
+};
+
+use crate::{
+    utils::{timestamp_to_date_string, CCStr, CheapClone},
+    Route,
+};
+
+use state_management::LOW_UNUSED_XPUBS;
+
+/// Ids of the configuration sections the health checks can scroll to
+pub(super) const HERITAGE_CONFIGURATION_SECTION: &str = "heritage-configuration-section";
+pub(super) const LEDGER_POLICIES_SECTION: &str = "ledger-policies-section";
+pub(super) const ACCOUNT_XPUBS_SECTION: &str = "account-xpubs-section";
+
+/// A wallet not synchronized for longer than that (7 days) is considered stale
+const SYNC_RECENCY: u64 = 7 * 24 * 60 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CheckStatus {
+    Ok,
+    Warning,
+    Error,
+}
+impl CheckStatus {
+    fn icon(self) -> &'static str {
+        match self {
+            CheckStatus::Ok => "✓",
+            CheckStatus::Warning => "!",
+            CheckStatus::Error => "✗",
+        }
+    }
+    fn class(self) -> &'static str {
+        match self {
+            CheckStatus::Ok => "text-success",
+            CheckStatus::Warning => "text-warning",
+            CheckStatus::Error => "text-error",
+        }
+    }
+}
+
+/// Where the user can go to fix a failing check
+#[derive(Debug, Clone, PartialEq)]
+enum HealthFix {
+    Route(Route, &'static str),
+    Section(&'static str, &'static str),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct HealthCheck {
+    title: &'static str,
+    status: CheckStatus,
+    message: CCStr,
+    fix: Option<HealthFix>,
+}
+
+/// Diagnostic of the readiness of the wallet, each check linking to where it can be fixed
+#[component]
+pub(super) fn HealthCheckConfig(wallet_name: CCStr) -> Element {
+    log::debug!("HealthCheckConfig Rendered");
+
+    let database_service = state_management::use_database_service();
+
+    let wallet = use_context::<AsyncSignal<Wallet>>();
+    let wallet_status = use_context::<FResource<WalletStatus>>();
+    let heritage_configs_with_info = use_context::<FMemo<CheapClone<[HeritageConfigWithInfo]>>>();
+    let backup = use_context::<FResource<HeritageWalletBackup>>();
+    let ledger_unregistered_policies =
+        use_context::<FMemo<BTreeMap<AccountXPubId, LedgerPolicy>>>();
+    let uses_ledger = helper_hooks::use_memo_wallet_uses_ledger(wallet);
+    let account_xpubs = helper_hooks::use_resource_wallet_account_xpubs(wallet);
+
+    let wn = wallet_name.clone();
+    let backup_export = use_resource(move || {
+        let wallet_name = wn.clone();
+        async move { state_management::load_backup_export(database_service, wallet_name).await }
+    });
+
+    let wallet_view = Route::WalletView {
+        wallet_name: wallet_name.clone(),
+    };
+    let checks = use_memo(move || {
+        let mut checks = Vec::new();
+
+        // Descriptors backup
+        let backup_count = backup.lrmap_ok(|backup| backup.iter().count());
+        if let (Some(Ok(backup_export)), Some(backup_count)) = (backup_export(), backup_count) {
+            let (status, message) = match backup_export {
+                None => (
+                    CheckStatus::Error,
+                    "The descriptors backup was never saved".to_owned(),
+                ),
+                Some(backup_export) if backup_export.subwallet_count < backup_count => (
+                    CheckStatus::Warning,
+                    format!(
+                        "The backup saved on {} does not cover the newest Heritage Configuration",
+                        timestamp_to_date_string(backup_export.timestamp)
+                    ),
+                ),
+                Some(backup_export) => (
+                    CheckStatus::Ok,
+                    format!(
+                        "Saved on {}",
+                        timestamp_to_date_string(backup_export.timestamp)
+                    ),
+                ),
+            };
+            checks.push(HealthCheck {
+                title: "Descriptors backup",
+                status,
+                message: CCStr::from(message),
+                fix: Some(HealthFix::Route(
+                    wallet_view.clone(),
+                    "Use \"Backup Descriptors\" on the wallet",
+                )),
+            });
+        }
+
+        // Ledger policies, only for wallets using a Ledger
+        if uses_ledger() {
+            if let Some(Ok(unregistered_count)) =
+                ledger_unregistered_policies.lrmap(|policies| policies.len())
+            {
+                let (status, message) = match unregistered_count {
+                    0 => (
+                        CheckStatus::Ok,
+                        "All the policies are registered".to_owned(),
+                    ),
+                    count => (
+                        CheckStatus::Error,
+                        format!("{count} policy(ies) not registered on the Ledger"),
+                    ),
+                };
+                checks.push(HealthCheck {
+                    title: "Ledger policies",
+                    status,
+                    message: CCStr::from(message),
+                    fix: Some(HealthFix::Section(
+                        LEDGER_POLICIES_SECTION,
+                        "Register the policies",
+                    )),
+                });
+            }
+        }
+
+        // Current Heritage Configuration, the first one being the newest
+        if let Some(Ok(current)) = heritage_configs_with_info.lrmap(|hcwi| hcwi.first().cloned()) {
+            let (status, message) = match current {
+                None => (
+                    CheckStatus::Error,
+                    "No Heritage Configuration, your heirs cannot inherit".to_owned(),
+                ),
+                Some(hcwi) => match hcwi.expiration_status {
+                    ExpirationStatus::Expired => (
+                        CheckStatus::Error,
+                        "Expired, your heirs can already spend your bitcoins".to_owned(),
+                    ),
+                    ExpirationStatus::ExpireSoon => (
+                        CheckStatus::Warning,
+                        match hcwi.expiration_ts {
+                            Some(ts) => {
+                                format!("Expires on {}", timestamp_to_date_string(ts))
+                            }
+                            None => "Expires soon".to_owned(),
+                        },
+                    ),
+                    ExpirationStatus::Current | ExpirationStatus::Outdated => {
+                        (CheckStatus::Ok, "Up to date".to_owned())
+                    }
+                },
+            };
+            checks.push(HealthCheck {
+                title: "Heritage Configuration",
+                status,
+                message: CCStr::from(message),
+                fix: Some(HealthFix::Section(
+                    HERITAGE_CONFIGURATION_SECTION,
+                    "Create a new Heritage Configuration",
+                )),
+            });
+        }
+
+        // Account XPubs pool, needed to create new Heritage Configurations
+        if let Some(Ok(unused_count)) = account_xpubs.lrmap(|account_xpubs| {
+            account_xpubs
+                .iter()
+                .filter(|axpub| matches!(axpub, AccountXPubWithStatus::Unused(_)))
+                .count()
+        }) {
+            let (status, message) = match unused_count {
+                0 => (
+                    CheckStatus::Error,
+                    "No unused Account XPub, no new Heritage Configuration can be created"
+                        .to_owned(),
+                ),
+                count if count < LOW_UNUSED_XPUBS => (
+                    CheckStatus::Warning,
+                    format!("Only {count} unused Account XPub(s) left"),
+                ),
+                count => (CheckStatus::Ok, format!("{count} unused Account XPubs")),
+            };
+            checks.push(HealthCheck {
+                title: "Account XPubs pool",
+                status,
+                message: CCStr::from(message),
+                fix: Some(HealthFix::Section(
+                    ACCOUNT_XPUBS_SECTION,
+                    "Add Account XPubs",
+                )),
+            });
+        }
+
+        // Synchronization recency
+        if let Some(Ok(last_sync_ts)) =
+            wallet_status.lrmap(|wallet_status| wallet_status.last_sync_ts)
+        {
+            let (status, message) = if last_sync_ts + SYNC_RECENCY < timestamp_now() {
+                (
+                    CheckStatus::Warning,
+                    format!(
+                        "Not synchronized since {}",
+                        timestamp_to_date_string(last_sync_ts)
+                    ),
+                )
+            } else {
+                (
+                    CheckStatus::Ok,
+                    format!("Synchronized on {}", timestamp_to_date_string(last_sync_ts)),
+                )
+            };
+            checks.push(HealthCheck {
+                title: "Synchronization",
+                status,
+                message: CCStr::from(message),
+                fix: Some(HealthFix::Route(
+                    wallet_view.clone(),
+                    "Synchronize the wallet",
+                )),
+            });
+        }
+
+        checks
+    });
+
+    // Percentage of passed checks, warnings counting for half
+    let score = use_memo(move || {
+        let checks = checks.read();
+        (!checks.is_empty()).then(|| {
+            let points = checks
+                .iter()
+                .map(|check| match check.status {
+                    CheckStatus::Ok => 2,
+                    CheckStatus::Warning => 1,
+                    CheckStatus::Error => 0,
+                })
+                .sum::<usize>();
+            points * 50 / checks.len()
+        })
+    });
+    let score_class = use_memo(move || match score() {
+        Some(score) if score >= 90 => "text-success",
+        Some(score) if score >= 50 => "text-warning",
+        _ => "text-error",
+    });
+
+    use_drop(|| log::debug!("HealthCheckConfig Dropped"));
+
+    rsx! {
+        div { class: "rounded-box border border-base-content/5 shadow-md p-4 my-4",
+            div { class: "flex flex-row items-center gap-6 mb-4",
+                div { class: "grow",
+                    h2 { class: "text-2xl font-bold", "Health Check" }
+                    div { class: "text-sm font-light",
+                        "Is this wallet ready for your heirs? Fix the failing checks to make sure."
+                    }
+                }
+                if let Some(score) = score() {
+                    div {
+                        class: "radial-progress font-bold {score_class}",
+                        style: "--value:{score};",
+                        role: "progressbar",
+                        "{score}%"
+                    }
+                } else {
+                    div { class: "skeleton h-20 w-20 rounded-full" }
+                }
+            }
+            ul { class: "flex flex-col gap-2",
+                for check in checks() {
+                    li {
+                        key: "{check.title}",
+                        class: "flex flex-row items-center gap-4",
+                        span { class: "text-xl font-black w-6 text-center {check.status.class()}",
+                            {check.status.icon()}
+                        }
+                        div { class: "grow",
+                            div { class: "font-semibold", {check.title} }
+                            div { class: "text-sm font-light", {check.message} }
+                        }
+                        if check.status != CheckStatus::Ok {
+                            match check.fix {
+                                Some(HealthFix::Route(route, label)) => rsx! {
+                                    button {
+                                        class: "btn btn-sm btn-outline",
+                                        onclick: move |_| {
+                                            navigator().push(route.clone());
+                                        },
+                                        {label}
+                                    }
+                                },
+                                Some(HealthFix::Section(section_id, label)) => rsx! {
+                                    button {
+                                        class: "btn btn-sm btn-outline",
+                                        onclick: move |_| {
+                                            document::eval(
+                                                &format!(
+                                                    "document.getElementById('{section_id}')?.scrollIntoView({{behavior: 'smooth'}});",
+                                                ),
+                                            );
+                                        },
+                                        {label}
+                                    }
+                                },
+                                None => rsx! {},
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
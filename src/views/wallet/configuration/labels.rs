@@ -0,0 +1,228 @@
+use crate::prelude::*;
+
+use btc_heritage_wallet::btc_heritage::utils::timestamp_now;
+
+use crate::{
+    components::{
+        copy::CopyTextarea,
+        export::{use_export_directory, write_export_files, ExportToDirectory},
+        inputs::FileInput,
+        svg::{DrawSvg, FileDownload, SvgSize::Size4},
+    },
+    utils::{timestamp_to_file_string, CCStr},
+};
+
+/// Component importing and exporting the labels of the wallet in the BIP-329 format
+///
+/// It lets the labels follow the wallet between the Heritage GUI, Sparrow and
+/// the other tools supporting BIP-329.
+#[component]
+pub(super) fn WalletLabelsConfig(wallet_name: CCStr) -> Element {
+    log::debug!("WalletLabelsConfig Rendered");
+
+    let database_service = state_management::use_database_service();
+
+    let labels = use_resource({
+        let wallet_name = wallet_name.clone();
+        move || {
+            let wallet_name = wallet_name.clone();
+            async move {
+                state_management::load_wallet_labels(database_service, wallet_name)
+                    .await
+                    .map_err(CCStr::from)
+            }
+        }
+    });
+    let labels_jsonl = use_memo(move || {
+        labels.lrmap(|labels| CCStr::from(state_management::wallet_labels_to_jsonl(labels)))
+    });
+    let label_counts = use_memo(move || {
+        labels.lrmap(|labels| {
+            state_management::WalletLabelType::list()
+                .into_iter()
+                .map(|label_type| {
+                    let count = labels.iter().filter(|l| l.label_type == label_type).count();
+                    (label_type.name(), count)
+                })
+                .filter(|(_, count)| *count > 0)
+                .collect::<Vec<_>>()
+        })
+    });
+
+    let mut import_data = use_signal(String::new);
+    let imported_labels = use_memo(move || {
+        let import_data = import_data.read();
+        (!import_data.trim().is_empty())
+            .then(|| state_management::parse_wallet_labels(&import_data).map_err(CCStr::from))
+    });
+    let mut importing = use_signal(|| false);
+
+    let import_labels = {
+        let wallet_name = wallet_name.clone();
+        move |_| {
+            let wallet_name = wallet_name.clone();
+            async move {
+                let Some(Ok(imported)) = imported_labels() else {
+                    return;
+                };
+                let Some(Ok(mut current)) = labels() else {
+                    alert_error("The labels of the wallet are not loaded yet");
+                    return;
+                };
+                *importing.write() = true;
+                let (added, replaced) =
+                    state_management::merge_wallet_labels(&mut current, imported);
+                match optimistic_update(
+                    labels,
+                    Ok(current.clone()),
+                    state_management::save_wallet_labels(database_service, wallet_name, current),
+                )
+                .await
+                {
+                    Ok(()) => {
+                        log::info!("Imported labels: {added} added, {replaced} replaced");
+                        alert_success(format!(
+                            "Labels imported: {added} added, {replaced} replaced"
+                        ));
+                        import_data.set(String::new());
+                    }
+                    Err(e) => {
+                        alert_failure("Failed to import the labels", e);
+                    }
+                }
+                *importing.write() = false;
+            }
+        }
+    };
+
+    let export_directory = use_export_directory();
+    let export_path = use_memo(move || {
+        let ts_str = timestamp_to_file_string(timestamp_now());
+        format!(
+            "{}/{wallet_name}-{ts_str}-labels.jsonl",
+            export_directory.read()
+        )
+    });
+
+    let export_labels = move |_| {
+        let Some(Ok(data)) = labels_jsonl() else {
+            alert_error("The labels of the wallet are not loaded yet");
+            return;
+        };
+        let file_path = export_path();
+        if write_export_files("labels", [(file_path.clone(), data.as_bytes())]) {
+            alert_info(format!("Labels saved to: {}", file_path));
+        }
+    };
+
+    use_drop(|| log::debug!("WalletLabelsConfig Dropped"));
+
+    rsx! {
+        div { class: "rounded-box border border-base-content/5 shadow-md p-4 my-4",
+            h2 { class: "text-2xl font-bold mb-4", "Labels" }
+
+            div { class: "text-sm font-light mb-4",
+                "Import and export the labels of this wallet (transactions, addresses, outputs,
+                extended public keys...) using the BIP-329 format, supported by Sparrow and
+                other wallet software."
+            }
+
+            div { class: "mb-4",
+                match label_counts() {
+                    Some(Ok(label_counts)) => rsx! {
+                        if label_counts.is_empty() {
+                            div { class: "text-base", "This wallet has no label yet." }
+                        } else {
+                            div { class: "flex flex-wrap gap-2",
+                                for (name , count) in label_counts.into_iter() {
+                                    div { class: "badge badge-outline", "{name}: {count}" }
+                                }
+                            }
+                        }
+                    },
+                    Some(Err(e)) => rsx! {
+                        div { class: "alert alert-error", "Could not load the labels: {e}" }
+                    },
+                    None => rsx! {
+                        div { class: "skeleton h-6 w-64" }
+                    },
+                }
+            }
+
+            div { class: "grid grid-cols-1 lg:grid-cols-2 gap-4",
+                div { class: "flex flex-col gap-2",
+                    h3 { class: "text-lg font-semibold", "Import" }
+                    fieldset { class: "fieldset",
+                        legend { class: "fieldset-legend", "Labels File" }
+                        FileInput {
+                            accept: ".jsonl,.json,.txt",
+                            onchange: move |evt: Event<FormData>| async move {
+                                if let Some(file_engine) = evt.files().clone() {
+                                    for file in file_engine.files() {
+                                        import_data
+                                            .set(file_engine.read_file_to_string(&file).await.unwrap_or_default());
+                                    }
+                                }
+                            },
+                        }
+                    }
+                    fieldset { class: "fieldset w-full",
+                        legend { class: "fieldset-legend", "Labels Data" }
+                        textarea {
+                            class: "textarea textarea-bordered font-mono text-xs w-full",
+                            class: if matches!(imported_labels(), Some(Err(_))) { "textarea-error" },
+                            class: if matches!(imported_labels(), Some(Ok(_))) { "textarea-success" },
+                            rows: "6",
+                            placeholder: "{{\"type\": \"addr\", \"ref\": \"bc1...\", \"label\": \"...\"}}",
+                            value: import_data(),
+                            oninput: move |evt| import_data.set(evt.value()),
+                        }
+                        match imported_labels().map(|imported| imported.map(|labels| labels.len())) {
+                            Some(Ok(count)) => rsx! {
+                                div { class: "fieldset-label text-success", "{count} labels ready to import" }
+                            },
+                            Some(Err(e)) => rsx! {
+                                div { class: "fieldset-label text-error", "{e}" }
+                            },
+                            None => rsx! {},
+                        }
+                    }
+                    button {
+                        class: "btn btn-primary w-fit",
+                        disabled: importing() || !matches!(imported_labels(), Some(Ok(_))),
+                        onclick: import_labels,
+                        if importing() {
+                            span { class: "loading loading-spinner" }
+                        }
+                        "Import Labels"
+                    }
+                }
+                div { class: "flex flex-col gap-2",
+                    h3 { class: "text-lg font-semibold", "Export" }
+                    match labels_jsonl() {
+                        Some(Ok(labels_jsonl)) => rsx! {
+                            CopyTextarea { value: labels_jsonl, rows: 6 }
+                        },
+                        Some(Err(e)) => rsx! {
+                            div { class: "alert alert-error", "Could not load the labels: {e}" }
+                        },
+                        None => rsx! {
+                            div { class: "skeleton h-32 w-full" }
+                        },
+                    }
+                    ExportToDirectory {
+                        directory: export_directory,
+                        display_path: ReadOnlySignal::from(export_path),
+                        button {
+                            class: "btn",
+                            onclick: export_labels,
+                            disabled: !matches!(labels_jsonl(), Some(Ok(_))),
+                            DrawSvg::<FileDownload> { size: Size4 }
+                            "Save Labels"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -10,164 +10,15 @@ use btc_heritage_wallet::{
 
 use crate::{
     components::{
+        help::{HelpPopover, HelpTopic},
         inputs::BackupRestoreSection,
-        modal::ConfigModal,
+        ledger_policies::{UILedgerPoliciesTable, UIWalletPolicy},
+        ledger_prompt::{LedgerCancel, LedgerPrompt, LedgerPromptsOverlay},
         svg::{AlertOutline, CheckCircle, Delete, DrawSvg},
     },
     utils::{log_error, CCStr},
 };
 
-/// UI representation of a wallet policy for display
-#[derive(Debug, Clone, PartialEq)]
-struct UIWalletPolicy {
-    name: CCStr,
-    descriptor_template: CCStr,
-    keys: Vec<CCStr>,
-}
-
-impl LoadedElement for UIWalletPolicy {
-    type Loader = TransparentLoader;
-
-    fn element<M: LoadedComponentInputMapper>(self, _m: M) -> Element {
-        rsx! {
-            div { class: "text-base flex flex-col gap-4",
-                div {
-                    h4 { class: "font-semibold text-base mb-2", "Account Name:" }
-                    div { class: "font-mono p-2", "{self.name}" }
-                }
-
-                div {
-                    h4 { class: "font-semibold text-base mb-2", "Wallet Policy:" }
-                    div { class: "font-mono p-2 break-all", "{self.descriptor_template}" }
-                }
-
-                if !self.keys.is_empty() {
-                    div {
-                        h4 { class: "font-semibold text-base mb-2", "Keys" }
-                        div { class: "flex flex-col gap-2",
-                            for (index , key) in self.keys.iter().enumerate() {
-                                div { class: "flex items-center gap-2",
-                                    div { class: "font-mono font-bold text-sm bg-primary text-primary-content rounded px-2 py-1 flex-shrink-0",
-                                        "@{index}"
-                                    }
-                                    div { class: "font-mono p-2 break-all flex-1", "{key}" }
-                                }
-                            }
-                        }
-                    }
-                } else {
-                    div {
-                        h4 { class: "font-semibold text-base mb-2", "Keys" }
-                        div { class: "text-sm text-base-content/60 italic",
-                            "Key details are not available for this policy type"
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    fn place_holder() -> Self {
-        Self {
-            name: CCStr::place_holder(),
-            descriptor_template: CCStr::place_holder(),
-            keys: vec![CCStr::place_holder()],
-        }
-    }
-}
-
-impl FromRef<WalletPolicy> for UIWalletPolicy {
-    fn from_ref(policy: &WalletPolicy) -> Self {
-        Self {
-            name: CCStr::from(policy.name.clone()),
-            descriptor_template: CCStr::from(policy.descriptor_template.clone()),
-            keys: policy
-                .keys
-                .iter()
-                .map(|k| CCStr::from(k.to_string()))
-                .collect(),
-        }
-    }
-}
-
-#[derive(Debug, Clone, PartialEq)]
-struct UILedgerPolicyLine {
-    policy_id: AccountXPubId,
-    policy: CCStr,
-}
-impl LoadedElement for UILedgerPolicyLine {
-    type Loader = TransparentLoader;
-
-    fn element<M: LoadedComponentInputMapper>(self, m: M) -> Element {
-        rsx! {
-            tr {
-                td { class: "text-center align-middle",
-                    LoadedComponent { input: m.map(self.policy_id) }
-                }
-                td { class: "font-mono text-sm break-all",
-                    LoadedComponent { input: m.map(self.policy) }
-                }
-            }
-        }
-    }
-
-    fn place_holder() -> Self {
-        Self {
-            policy_id: 0,
-            policy: CCStr::place_holder(),
-        }
-    }
-}
-
-#[derive(Debug, Clone, PartialEq)]
-struct UILedgerPoliciesTable(BTreeMap<AccountXPubId, UILedgerPolicyLine>);
-impl LoadedElement for UILedgerPoliciesTable {
-    type Loader = TransparentLoader;
-
-    fn element<M: LoadedComponentInputMapper>(self, m: M) -> Element {
-        rsx! {
-            table { class: "table table-zebra",
-                thead {
-                    tr {
-                        th { class: "w-32", "Account ID" }
-                        th { "Policy" }
-                    }
-                }
-                tbody {
-                    LoadedComponent { input: m.map(self.0) }
-                }
-            }
-        }
-    }
-
-    fn place_holder() -> Self {
-        let line = UILedgerPolicyLine::place_holder();
-        Self([(line.policy_id, line)].into())
-    }
-}
-impl LoadedSuccessConversionMarker
-    for TypeCouple<BTreeMap<AccountXPubId, LedgerPolicy>, UILedgerPoliciesTable>
-{
-}
-impl FromRef<BTreeMap<AccountXPubId, LedgerPolicy>> for UILedgerPoliciesTable {
-    fn from_ref(policies: &BTreeMap<AccountXPubId, LedgerPolicy>) -> Self {
-        Self(
-            policies
-                .iter()
-                .map(|(id, policy)| {
-                    (
-                        *id,
-                        UILedgerPolicyLine {
-                            policy_id: *id,
-                            policy: CCStr::from(policy.to_string()),
-                        },
-                    )
-                })
-                .collect(),
-        )
-    }
-}
-
 #[component]
 pub(super) fn LedgerPoliciesConfig(wallet_name: CCStr) -> Element {
     log::debug!("LedgerPoliciesConfig Rendered");
@@ -245,6 +96,9 @@ pub(super) fn LedgerPoliciesConfig(wallet_name: CCStr) -> Element {
     let mut current_policy = use_signal(|| None::<UIWalletPolicy>);
     let mut current_policy_index = use_signal(|| 0);
     let mut policies_to_register_count = use_signal(|| 0);
+    let mut ledger_prompts = use_signal(Vec::new);
+    let current_prompt = use_memo(move || current_policy_index().checked_sub(1));
+    let ledger_cancel = use_hook(LedgerCancel::default);
 
     let has_policies_to_register =
         use_memo(move || match ledger_unregistered_policies.read().as_ref() {
@@ -264,8 +118,10 @@ pub(super) fn LedgerPoliciesConfig(wallet_name: CCStr) -> Element {
 
     let register_policies = {
         let wallet_name = wallet_name.clone();
+        let ledger_cancel = ledger_cancel.clone();
         move |_| {
             let wallet_name = wallet_name.clone();
+            let ledger_cancel = ledger_cancel.clone();
             async move {
                 *in_operation.write() = true;
                 *register_modal.write() = true;
@@ -284,6 +140,20 @@ pub(super) fn LedgerPoliciesConfig(wallet_name: CCStr) -> Element {
 
                 *current_policy_index.write() = 0usize;
                 *policies_to_register_count.write() = policies.len();
+                *ledger_prompts.write() = policies
+                    .keys()
+                    .enumerate()
+                    .map(|(i, account_id)| LedgerPrompt {
+                        title: CCStr::from(format!(
+                            "Register policy {} of {}",
+                            i + 1,
+                            policies.len()
+                        )),
+                        detail: Some(CCStr::from(format!(
+                            "Review and approve the Account #{account_id}"
+                        ))),
+                    })
+                    .collect();
 
                 let Ok(mut owned_wallet) = state_management::get_wallet(
                     database_service,
@@ -303,15 +173,14 @@ pub(super) fn LedgerPoliciesConfig(wallet_name: CCStr) -> Element {
                         return;
                     }
                 };
-                let result = {
-                    ledger_key
-                        .register_policies(policies.values(), |wp: &WalletPolicy| {
+                let result = ledger_cancel
+                    .run(
+                        ledger_key.register_policies(policies.values(), |wp: &WalletPolicy| {
                             *current_policy_index.write() += 1;
                             *current_policy.write() = Some(UIWalletPolicy::from_ref(wp));
-                        })
-                        .await
-                        .map_err(log_error)
-                };
+                        }),
+                    )
+                    .await;
 
                 match result {
                     Ok(_) => {
@@ -319,8 +188,7 @@ pub(super) fn LedgerPoliciesConfig(wallet_name: CCStr) -> Element {
                         alert_success("Ledger policies registered successfully");
                     }
                     Err(e) => {
-                        log::error!("Failed to register Ledger policies: {e}");
-                        alert_error(format!("Failed to register Ledger policies: {e}"));
+                        alert_failure("Failed to register Ledger policies", e);
                     }
                 }
                 // Temporarily puting into an Arc to share ownership with the blocking thread
@@ -403,7 +271,10 @@ pub(super) fn LedgerPoliciesConfig(wallet_name: CCStr) -> Element {
 
     rsx! {
         div { class: "rounded-box border border-base-content/5 shadow-md p-4 my-4",
-            h2 { class: "text-2xl font-bold mb-4", "Ledger Policies" }
+            h2 { class: "text-2xl font-bold mb-4",
+                "Ledger Policies"
+                HelpPopover { topic: HelpTopic::LedgerPolicies }
+            }
 
             div { class: "text-sm font-light mb-6",
                 "Ledger Policies define the Bitcoin spending conditions that your Ledger device recognizes and can sign for. "
@@ -476,16 +347,14 @@ pub(super) fn LedgerPoliciesConfig(wallet_name: CCStr) -> Element {
                 }
             }
 
-            // Registration progress modal
-            ConfigModal { is_open: register_modal, title: "Registering Ledger Policies",
+            // Registration progress overlay
+            LedgerPromptsOverlay {
+                is_open: register_modal,
+                title: "Registering Ledger Policies",
+                prompts: ledger_prompts,
+                current_prompt,
+                cancel: ledger_cancel,
                 div { class: "w-full flex flex-col gap-4",
-                    div { class: "text-center text-xl",
-                        span { class: "loading loading-spinner loading-lg text-primary mx-8" }
-                        "Registering Ledger Policy "
-                        span { class: "font-black text-2xl",
-                            "{current_policy_index()}/{policies_to_register_count()}"
-                        }
-                    }
                     div { class: "text-center text-base italic",
                         "Note that Ledger call it "
                         span { class: "font-bold text-accent", "Account" }
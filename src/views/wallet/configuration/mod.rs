@@ -1,18 +1,34 @@
 use crate::prelude::*;
 
 mod account_xpubs;
+mod backup_sheet;
+pub(super) mod bitcoin_core_export;
+mod bitcoin_core_watch_only;
 mod block_inclusion_objective;
+mod broadcast_checklist;
+mod coldcard_registration;
 mod current_heritage_config;
 mod delete;
+mod health_check;
+mod labels;
 mod ledger_policies;
+mod notes;
+mod proof_of_reserves;
+mod reserved_addresses;
+mod settings_overrides;
+mod spend_limits;
+mod ur_export;
+mod wallet_software_export;
 
 use btc_heritage_wallet::Wallet;
 
 use crate::{
     components::{
-        badge::{ExternalDependencyStatus, OnlineWalletType},
+        badge::{ExternalDependencyStatus, KeyProviderType, OnlineWalletType},
+        help::{HeritageDelaysTradeOff, LedgerPoliciesLearnMore},
         inputs::RenameDatabaseItem,
         misc::BackButton,
+        tags::EditTags,
     },
     utils::CCStr,
     Route,
@@ -28,6 +44,10 @@ pub fn WalletConfigurationView(wallet_name: CCStr) -> Element {
     let online_status = use_context::<Memo<Option<(OnlineWalletType, ExternalDependencyStatus)>>>();
     let not_sign_only =
         use_memo(move || !matches!(online_status(), Some((OnlineWalletType::None, _))));
+    let keyprovider_status =
+        use_context::<Memo<Option<(KeyProviderType, ExternalDependencyStatus)>>>();
+    let external_signer =
+        use_memo(move || matches!(keyprovider_status(), Some((KeyProviderType::None, _))));
 
     use_drop(|| log::debug!("WalletConfigurationView Dropped"));
 
@@ -47,14 +67,39 @@ pub fn WalletConfigurationView(wallet_name: CCStr) -> Element {
                 }
             },
             if not_sign_only() {
-                current_heritage_config::CurrentHeritageConfig {}
+                health_check::HealthCheckConfig { wallet_name: wallet_name.clone() }
+                div { id: health_check::HERITAGE_CONFIGURATION_SECTION,
+                    current_heritage_config::CurrentHeritageConfig {}
+                }
+            }
+            div { id: health_check::LEDGER_POLICIES_SECTION,
+                ledger_policies::LedgerPoliciesConfig { wallet_name: wallet_name.clone() }
+            }
+            if not_sign_only() && external_signer() {
+                coldcard_registration::ColdcardRegistrationConfig { wallet_name: wallet_name.clone() }
+            }
+            if not_sign_only() {
+                block_inclusion_objective::BlockInclusionObjectiveConfig { wallet_name: wallet_name.clone() }
+            }
+            div { id: health_check::ACCOUNT_XPUBS_SECTION, account_xpubs::AccountXPubConfig { wallet_name: wallet_name.clone() } }
+            if not_sign_only() {
+                bitcoin_core_export::BitcoinCoreExportConfig {}
+                bitcoin_core_watch_only::BitcoinCoreWatchOnlyConfig { wallet_name: wallet_name.clone() }
+                wallet_software_export::WalletSoftwareExportConfig { wallet_name: wallet_name.clone() }
+                ur_export::UrExportConfig { wallet_name: wallet_name.clone() }
+                backup_sheet::BackupSheetConfig { wallet_name: wallet_name.clone() }
+                labels::WalletLabelsConfig { wallet_name: wallet_name.clone() }
+                reserved_addresses::ReservedAddressesConfig { wallet_name: wallet_name.clone() }
+                proof_of_reserves::ProofOfReservesConfig { wallet_name: wallet_name.clone() }
             }
-            ledger_policies::LedgerPoliciesConfig { wallet_name: wallet_name.clone() }
+            notes::WalletNotesConfig { wallet_name: wallet_name.clone() }
+            settings_overrides::SettingsOverridesConfig { wallet_name: wallet_name.clone() }
             if not_sign_only() {
-                block_inclusion_objective::BlockInclusionObjectiveConfig {}
+                spend_limits::SpendLimitsConfig { wallet_name: wallet_name.clone() }
+                broadcast_checklist::BroadcastChecklistConfig { wallet_name: wallet_name.clone() }
             }
-            account_xpubs::AccountXPubConfig {}
             RenameDatabaseItem::<Wallet> {}
+            EditTags::<Wallet> {}
             delete::DeleteWalletConfig {}
 
             OnboardingInfoModal { step: OnboardingStep::ModalExplainHeritageConfiguration,
@@ -79,20 +124,7 @@ pub fn WalletConfigurationView(wallet_name: CCStr) -> Element {
                             "Use it to space the heirs, 90 days between heirs is a reasonnable default"
                         }
                     }
-                    div { class: "bg-warning/10 p-4 rounded-lg border border-warning/20",
-                        p { class: "font-semibold mb-2 text-warning", "Important Trade-off:" }
-                        p { class: "mb-2",
-                            "Shorter delays mean your heirs (and your backup) can access funds more
-                            quickly, but you'll need to move your funds more frequently to reset
-                            the inheritance countdown while you're still active."
-                        }
-                        p {
-                            "A reasonable default is 1 or 2 years before the first heir
-                            can inherit, with 3-month intervals between subsequent heirs.
-                            This provides adequate protection while not requiring to move
-                            fund too often."
-                        }
-                    }
+                    HeritageDelaysTradeOff {}
                     div { class: "alert alert-info",
                         div { class: "flex items-start gap-2",
                             "💡"
@@ -137,26 +169,7 @@ pub fn WalletConfigurationView(wallet_name: CCStr) -> Element {
                             summary { class: "collapse-title font-medium",
                                 "Learn more about Ledger wallet policies"
                             }
-                            div { class: "collapse-content",
-                                div { class: "flex flex-col gap-3 pt-2",
-                                    p { class: "text-sm opacity-75",
-                                        "Wallet policies allows the user to securely approve
-                                        complex Bitcoin scripts once, without compromising
-                                        useability by requiring explicit user re-approval
-                                        at each transaction."
-                                    }
-                                    div {
-                                        strong { "BIP 388 - Wallet Policies: " }
-                                        a {
-                                            href: "https://github.com/bitcoin/bips/blob/master/bip-0388.mediawiki",
-                                            target: "_blank",
-                                            rel: "noopener noreferrer",
-                                            class: "text-primary hover:text-primary-focus underline",
-                                            "Technical specification for wallet policies"
-                                        }
-                                    }
-                                }
-                            }
+                            div { class: "collapse-content", LedgerPoliciesLearnMore {} }
                         }
                     }
                 }
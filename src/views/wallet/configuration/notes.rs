@@ -0,0 +1,85 @@
+use crate::prelude::*;
+
+use crate::{components::notes::NotesText, utils::CCStr};
+
+/// Free notes documenting the operational context of the wallet, displayed on its overview
+#[component]
+pub(super) fn WalletNotesConfig(wallet_name: CCStr) -> Element {
+    log::debug!("WalletNotesConfig Rendered");
+
+    let database_service = state_management::use_database_service();
+    let wallet_notes = use_context::<FResource<CCStr>>();
+
+    let mut notes = use_signal(String::new);
+    use_effect(move || {
+        if let Some(Ok(current)) = wallet_notes.cloned() {
+            notes.set(current.to_string());
+        }
+    });
+    let has_changes = use_memo(move || match &*wallet_notes.read() {
+        Some(Ok(current)) => &**current != notes.read().as_str(),
+        _ => false,
+    });
+
+    let mut in_operation = use_signal(|| false);
+    let save_notes = move |_| {
+        let wallet_name = wallet_name.clone();
+        async move {
+            let new_notes = notes();
+            *in_operation.write() = true;
+            match optimistic_update(
+                wallet_notes,
+                Ok(CCStr::from(new_notes.as_str())),
+                state_management::save_wallet_notes(database_service, wallet_name, new_notes),
+            )
+            .await
+            {
+                Ok(()) => alert_success("Wallet notes updated"),
+                Err(e) => alert_failure("Failed to update the wallet notes", e),
+            }
+            *in_operation.write() = false;
+        }
+    };
+
+    use_drop(|| log::debug!("WalletNotesConfig Dropped"));
+
+    rsx! {
+        div { class: "rounded-box border border-base-content/5 shadow-md p-4 my-4",
+            h2 { class: "text-2xl font-bold mb-4", "Notes" }
+
+            div { class: "text-sm font-light mb-4",
+                "Record the operational context of this wallet, like where the devices are stored
+                or when the Heritage Configuration must be renewed. The notes are displayed on the
+                wallet overview. Use \"# \" for headings, \"- \" for list items and **text** for bold."
+            }
+
+            div { class: "grid grid-cols-1 lg:grid-cols-2 gap-4",
+                fieldset { class: "fieldset",
+                    legend { class: "fieldset-legend", "Notes" }
+                    textarea {
+                        class: "textarea w-full font-mono",
+                        rows: "10",
+                        disabled: in_operation() || !matches!(*wallet_notes.read(), Some(Ok(_))),
+                        placeholder: "# Devices\n- Cosigner device stored at the bank\n\nRefresh the Heritage Configuration each **January**",
+                        value: "{notes}",
+                        oninput: move |evt| notes.set(evt.value()),
+                    }
+                }
+                fieldset { class: "fieldset",
+                    legend { class: "fieldset-legend", "Preview" }
+                    div { class: "rounded-box bg-base-200 p-4 h-full",
+                        NotesText { text: CCStr::from(notes.read().as_str()) }
+                    }
+                }
+            }
+            div { class: "flex justify-end mt-4",
+                button {
+                    class: "btn btn-primary",
+                    disabled: !has_changes() || in_operation(),
+                    onclick: save_notes,
+                    "Save"
+                }
+            }
+        }
+    }
+}
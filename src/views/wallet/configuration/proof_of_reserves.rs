@@ -0,0 +1,507 @@
+use crate::prelude::*;
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use btc_heritage_wallet::{
+    bitcoin::{
+        absolute::LockTime,
+        consensus::encode::serialize,
+        hashes::{sha256, Hash, HashEngine},
+        opcodes::all::OP_RETURN,
+        psbt::PartiallySignedTransaction,
+        script::Builder,
+        Address, Amount, OutPoint, Script, ScriptBuf, Sequence, Transaction, TxIn, TxOut, Txid,
+        Witness,
+    },
+    btc_heritage::{
+        bitcoincore_rpc::jsonrpc::serde_json::{self, json},
+        utils::{bitcoin_network, timestamp_now},
+    },
+    heritage_service_api_client::{NewTx, NewTxDrainTo, NewTxSpendingConfig},
+    KeyProvider, OnlineWallet, Wallet,
+};
+
+use crate::{
+    components::{
+        badge::{ExternalDependencyStatus, KeyProviderType},
+        copy::CopyTextarea,
+        export::{use_export_directory, write_export_files, ExportToDirectory},
+        inputs::InputField,
+        quick_actions::UnlockLocalKey,
+        svg::{DrawSvg, FileDownload, SvgSize::Size4},
+    },
+    utils::{timestamp_to_date_string, timestamp_to_file_string, CCStr, CheapClone},
+};
+
+/// Tag of the BIP-322 message hash
+const BIP322_TAG: &[u8] = b"BIP0322-signed-message";
+
+/// BIP-322 tagged hash of the message
+fn bip322_message_hash(message: &str) -> sha256::Hash {
+    let tag_hash = sha256::Hash::hash(BIP322_TAG);
+    let mut engine = sha256::Hash::engine();
+    engine.input(tag_hash.as_ref());
+    engine.input(tag_hash.as_ref());
+    engine.input(message.as_bytes());
+    sha256::Hash::from_engine(engine)
+}
+
+/// The BIP-322 virtual transaction committing to the message and the challenge script
+fn bip322_to_spend(message_challenge: &Script, message: &str) -> Transaction {
+    Transaction {
+        version: 0,
+        lock_time: LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: OutPoint {
+                txid: Txid::all_zeros(),
+                vout: 0xFFFFFFFF,
+            },
+            script_sig: Builder::new()
+                .push_int(0)
+                .push_slice(bip322_message_hash(message).to_byte_array())
+                .into_script(),
+            sequence: Sequence::ZERO,
+            witness: Witness::new(),
+        }],
+        output: vec![TxOut {
+            value: 0,
+            script_pubkey: message_challenge.to_owned(),
+        }],
+    }
+}
+
+/// Turn a PSBT spending all the UTXOs of the wallet into the BIP-322 "to_sign" PSBT
+///
+/// The challenge is the script of the first UTXO. The "to_sign" transaction spends the
+/// "to_spend" virtual output followed by all the UTXOs, into a single OP_RETURN output:
+/// it can never be broadcasted.
+fn bip322_to_sign_psbt(
+    psbt: PartiallySignedTransaction,
+    message: &str,
+) -> Result<PartiallySignedTransaction, String> {
+    let first_input = psbt.inputs.first().ok_or("The wallet has no UTXO")?;
+    let message_challenge = first_input
+        .witness_utxo
+        .as_ref()
+        .ok_or("Missing UTXO information in the transaction")?
+        .script_pubkey
+        .clone();
+    let to_spend = bip322_to_spend(&message_challenge, message);
+
+    let mut inputs = vec![TxIn {
+        previous_output: OutPoint {
+            txid: to_spend.txid(),
+            vout: 0,
+        },
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::ZERO,
+        witness: Witness::new(),
+    }];
+    inputs.extend(psbt.unsigned_tx.input.iter().map(|txin| TxIn {
+        script_sig: ScriptBuf::new(),
+        witness: Witness::new(),
+        ..txin.clone()
+    }));
+    let to_sign = Transaction {
+        version: 0,
+        lock_time: LockTime::ZERO,
+        input: inputs,
+        output: vec![TxOut {
+            value: 0,
+            script_pubkey: Builder::new().push_opcode(OP_RETURN).into_script(),
+        }],
+    };
+
+    let mut to_sign_psbt =
+        PartiallySignedTransaction::from_unsigned_tx(to_sign).map_err(|e| e.to_string())?;
+    // The challenge input is signed exactly like the first UTXO, with the same key
+    let mut challenge_input = first_input.clone();
+    challenge_input.witness_utxo = Some(to_spend.output[0].clone());
+    challenge_input.non_witness_utxo = None;
+    to_sign_psbt.inputs = core::iter::once(challenge_input)
+        .chain(psbt.inputs)
+        .collect();
+    Ok(to_sign_psbt)
+}
+
+/// Finalize the signed "to_sign" PSBT, all its inputs must be signed using the Taproot key path
+fn bip322_finalize(mut psbt: PartiallySignedTransaction) -> Result<Transaction, String> {
+    for (index, input) in psbt.inputs.iter_mut().enumerate() {
+        let signature = input
+            .tap_key_sig
+            .ok_or_else(|| format!("Input #{index} is not signed"))?;
+        input.final_script_witness = Some(Witness::from_slice(&[signature.to_vec()]));
+    }
+    Ok(psbt.extract_tx())
+}
+
+/// Build the proof-of-reserves package: the UTXOs and the BIP-322 "full" signature
+/// proving the control of all of them
+fn proof_of_reserves_report(
+    wallet_name: &str,
+    message: &str,
+    to_sign: &Transaction,
+    utxos: &[TxOut],
+) -> Result<String, String> {
+    let network = bitcoin_network::get();
+    let address = |script: &Script| {
+        Address::from_script(script, network)
+            .map(|address| address.to_string())
+            .map_err(|e| e.to_string())
+    };
+    let challenge_address = address(&utxos.first().ok_or("The wallet has no UTXO")?.script_pubkey)?;
+    let utxos = to_sign
+        .input
+        .iter()
+        .skip(1)
+        .zip(utxos)
+        .map(|(txin, txout)| {
+            Ok(json!({
+                "outpoint": txin.previous_output.to_string(),
+                "address": address(&txout.script_pubkey)?,
+                "amount_sat": txout.value,
+            }))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let total = Amount::from_sat(utxos.iter().filter_map(|u| u["amount_sat"].as_u64()).sum());
+    let now = timestamp_now();
+    let report = json!({
+        "format": "heritage-proof-of-reserves-v1",
+        "wallet": wallet_name,
+        "network": network.to_string(),
+        "created_at": now,
+        "created_date": timestamp_to_date_string(now),
+        "message": message,
+        "address": challenge_address,
+        "total_amount_sat": total.to_sat(),
+        "total_amount_btc": total.to_btc(),
+        "utxos": utxos,
+        "bip322_signature": BASE64.encode(serialize(to_sign)),
+        "verification": "The signature is a BIP-322 \"full\" signature of the message by the address, \
+            proving the control of the UTXOs listed as additional inputs of the signed transaction. \
+            Verify it with any BIP-322 implementation supporting proof of funds, then check that the \
+            UTXOs are still unspent on the blockchain.",
+    });
+    serde_json::to_string_pretty(&report).map_err(|e| e.to_string())
+}
+
+/// Component generating a signed proof-of-reserves for the wallet
+///
+/// All the UTXOs of the wallet are signed along with a challenge message, using BIP-322,
+/// so that an executor or an auditor can verify that the wallet controls them.
+#[component]
+pub(super) fn ProofOfReservesConfig(wallet_name: CCStr) -> Element {
+    log::debug!("ProofOfReservesConfig Rendered");
+
+    let wallet = use_context::<AsyncSignal<Wallet>>();
+    let addresses_with_info = use_context::<FMemo<CheapClone<[WalletAddressWithInfo]>>>();
+    let keyprovider_status =
+        use_context::<Memo<Option<(KeyProviderType, ExternalDependencyStatus)>>>();
+    let cannot_sign_reason = use_memo(move || match keyprovider_status() {
+        Some((KeyProviderType::None, _)) => Some(
+            "This wallet does not have a Key Provider component, \
+            the proof must be signed by the wallet holding the private keys.",
+        ),
+        Some((KeyProviderType::Ledger, ExternalDependencyStatus::Unavailable)) => {
+            Some("Connect the Ledger device of this wallet to sign the proof.")
+        }
+        Some((KeyProviderType::Ledger, ExternalDependencyStatus::NeedUserAction)) => {
+            Some("Register the Ledger policies of this wallet to sign the proof.")
+        }
+        None => Some("Wallet is not loaded."),
+        _ => None,
+    });
+    let local_key_need_password = use_memo(move || {
+        matches!(
+            keyprovider_status(),
+            Some((
+                KeyProviderType::LocalKey,
+                ExternalDependencyStatus::NeedUserAction
+            ))
+        )
+    });
+
+    let message = use_signal({
+        let wallet_name = wallet_name.clone();
+        move || {
+            format!(
+                "Proof of reserves of wallet {wallet_name} on {}",
+                timestamp_to_date_string(timestamp_now())
+            )
+        }
+    });
+    let message_error = use_memo(move || {
+        message
+            .read()
+            .is_empty()
+            .then(|| CCStr::from("Provide a message"))
+    });
+    let mut generating = use_signal(|| false);
+    let mut report = use_signal(|| None::<CCStr>);
+
+    let generate = {
+        let wallet_name = wallet_name.clone();
+        move |_| {
+            let wallet_name = wallet_name.clone();
+            async move {
+                let Some(Ok(addresses_with_info)) = addresses_with_info() else {
+                    alert_error("The addresses of the wallet are not loaded yet");
+                    return;
+                };
+                let Some(drain_to) = addresses_with_info
+                    .first()
+                    .map(|address| address.wallet_address.address().to_string())
+                else {
+                    alert_error("The wallet has no address");
+                    return;
+                };
+                *generating.write() = true;
+                let message = message();
+                let result = wallet
+                    .with(async move |wallet: &Wallet| {
+                        // A transaction draining the wallet gives a PSBT with all the UTXOs
+                        let (psbt, _) = wallet
+                            .create_psbt(NewTx {
+                                spending_config: NewTxSpendingConfig::DrainTo(NewTxDrainTo {
+                                    drain_to,
+                                }),
+                                fee_policy: None,
+                                utxo_selection: None,
+                                disable_rbf: None,
+                            })
+                            .await
+                            .map_err(|e| format!("Failed to list the UTXOs: {e}"))?;
+                        let mut to_sign = bip322_to_sign_psbt(psbt, &message)?;
+                        let utxos = to_sign
+                            .inputs
+                            .iter()
+                            .skip(1)
+                            .map(|input| input.witness_utxo.clone())
+                            .collect::<Option<Vec<_>>>()
+                            .ok_or("Missing UTXO information in the transaction")?;
+                        wallet
+                            .sign_psbt(&mut to_sign)
+                            .await
+                            .map_err(|e| format!("Failed to sign the proof: {e}"))?;
+                        let to_sign = bip322_finalize(to_sign)?;
+                        proof_of_reserves_report(&wallet_name, &message, &to_sign, &utxos)
+                    })
+                    .await;
+                match result {
+                    Ok(proof) => {
+                        log::info!("Proof of reserves generated");
+                        alert_success("Proof of reserves signed");
+                        report.set(Some(CCStr::from(proof)));
+                    }
+                    Err(e) => {
+                        alert_failure("Failed to generate the proof of reserves", e);
+                    }
+                }
+                *generating.write() = false;
+            }
+        }
+    };
+
+    let export_directory = use_export_directory();
+    let export_path = use_memo(move || {
+        let ts_str = timestamp_to_file_string(timestamp_now());
+        format!(
+            "{}/proof-of-reserves-{wallet_name}-{ts_str}.json",
+            export_directory.read()
+        )
+    });
+
+    let save_report = move |_| {
+        let Some(report) = report() else {
+            return;
+        };
+        let file_path = export_path();
+        if write_export_files(
+            "proof of reserves",
+            [(file_path.clone(), report.as_bytes())],
+        ) {
+            alert_info(format!("Proof of reserves saved to: {}", file_path));
+        }
+    };
+
+    use_drop(|| log::debug!("ProofOfReservesConfig Dropped"));
+
+    rsx! {
+        div { class: "rounded-box border border-base-content/5 shadow-md p-4 my-4",
+            h2 { class: "text-2xl font-bold mb-4", "Proof of Reserves" }
+
+            div { class: "text-sm font-light mb-4",
+                "Sign a message with all the UTXOs of this wallet, following BIP-322. The resulting
+                package lists the UTXOs and the signature, so that an executor or an auditor can
+                verify with open tools that this wallet controls them. The signed transaction is not
+                valid on the blockchain: nothing is spent."
+            }
+
+            if let Some(reason) = cannot_sign_reason() {
+                div { class: "alert alert-info mb-4", {reason} }
+            }
+            if local_key_need_password() {
+                div { class: "mb-4", UnlockLocalKey::<Wallet> {} }
+            }
+
+            div { class: "flex flex-col gap-2 max-w-2xl",
+                InputField {
+                    title: "Challenge Message",
+                    value: message,
+                    value_error: message_error,
+                }
+                button {
+                    class: "btn btn-primary w-fit",
+                    disabled: generating() || message_error().is_some() || cannot_sign_reason().is_some()
+                        || local_key_need_password(),
+                    onclick: generate,
+                    if generating() {
+                        span { class: "loading loading-spinner" }
+                    }
+                    "Sign Proof of Reserves"
+                }
+            }
+
+            if let Some(report) = report() {
+                div { class: "flex flex-col gap-2 mt-4",
+                    CopyTextarea { value: report, rows: 12 }
+                    ExportToDirectory {
+                        directory: export_directory,
+                        display_path: ReadOnlySignal::from(export_path),
+                        button { class: "btn", onclick: save_report,
+                            DrawSvg::<FileDownload> { size: Size4 }
+                            "Save Proof of Reserves"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use btc_heritage_wallet::bitcoin::psbt::Input;
+
+    // Test vectors of BIP-322, for the address bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l
+    const CHALLENGE_SCRIPT: &str = "00142b05d564e6a7a33c087f16e0f730d1440123799d";
+
+    fn challenge() -> ScriptBuf {
+        ScriptBuf::from_hex(CHALLENGE_SCRIPT).unwrap()
+    }
+
+    fn utxo(value: u64, script_pubkey: ScriptBuf) -> TxOut {
+        TxOut {
+            value,
+            script_pubkey,
+        }
+    }
+
+    /// A PSBT spending one UTXO per given TxOut, like a drain transaction
+    fn drain_psbt(utxos: &[TxOut]) -> PartiallySignedTransaction {
+        let tx = Transaction {
+            version: 2,
+            lock_time: LockTime::ZERO,
+            input: (0..utxos.len())
+                .map(|vout| TxIn {
+                    previous_output: OutPoint {
+                        txid: Txid::from_byte_array([0x42; 32]),
+                        vout: vout as u32,
+                    },
+                    script_sig: ScriptBuf::new(),
+                    sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+                    witness: Witness::new(),
+                })
+                .collect(),
+            output: vec![utxo(1000, challenge())],
+        };
+        let mut psbt = PartiallySignedTransaction::from_unsigned_tx(tx).unwrap();
+        for (input, txout) in psbt.inputs.iter_mut().zip(utxos) {
+            input.witness_utxo = Some(txout.clone());
+        }
+        psbt
+    }
+
+    #[test]
+    fn message_hash() {
+        assert_eq!(
+            bip322_message_hash("").to_string(),
+            "c90c269c4f8fcbe6880f72a721ddfbf1914268a794cbb21cfafee13770ae19f1"
+        );
+        assert_eq!(
+            bip322_message_hash("Hello World").to_string(),
+            "f0eb03b1a75ac6d9847f55c624a99169b5dccba2a31f5b23bea77ba270de0a7a"
+        );
+    }
+
+    #[test]
+    fn to_spend_txid() {
+        assert_eq!(
+            bip322_to_spend(&challenge(), "").txid().to_string(),
+            "c5680aa69bb8d860bf82d4e9cd3504b55dde018de765a91bb566283c545a99a7"
+        );
+        assert_eq!(
+            bip322_to_spend(&challenge(), "Hello World")
+                .txid()
+                .to_string(),
+            "b79d196740ad5217771c1098fc4a4b51e0535c32236c71f1ea4d61a2d603352b"
+        );
+    }
+
+    #[test]
+    fn to_sign_spends_the_challenge_then_all_the_utxos() {
+        let other_script = ScriptBuf::from_hex(
+            "51200000000000000000000000000000000000000000000000000000000000000001",
+        )
+        .unwrap();
+        let utxos = [utxo(10_000, challenge()), utxo(20_000, other_script)];
+        let psbt = drain_psbt(&utxos);
+        let to_sign = bip322_to_sign_psbt(psbt.clone(), "Hello World").unwrap();
+
+        let tx = &to_sign.unsigned_tx;
+        assert_eq!(tx.version, 0);
+        assert_eq!(tx.lock_time, LockTime::ZERO);
+        assert_eq!(tx.input.len(), 3);
+        assert_eq!(
+            tx.input[0].previous_output,
+            OutPoint {
+                txid: bip322_to_spend(&challenge(), "Hello World").txid(),
+                vout: 0,
+            }
+        );
+        assert_eq!(tx.input[0].sequence, Sequence::ZERO);
+        for (txin, original) in tx.input.iter().skip(1).zip(&psbt.unsigned_tx.input) {
+            assert_eq!(txin.previous_output, original.previous_output);
+            assert_eq!(txin.sequence, original.sequence);
+        }
+        assert_eq!(
+            tx.output,
+            vec![utxo(0, Builder::new().push_opcode(OP_RETURN).into_script())]
+        );
+
+        assert_eq!(to_sign.inputs.len(), 3);
+        assert_eq!(to_sign.inputs[0].witness_utxo, Some(utxo(0, challenge())));
+        assert_eq!(to_sign.inputs[0].non_witness_utxo, None);
+        assert_eq!(&to_sign.inputs[1..], &psbt.inputs[..]);
+    }
+
+    #[test]
+    fn to_sign_needs_utxos() {
+        assert!(bip322_to_sign_psbt(drain_psbt(&[]), "message").is_err());
+
+        let mut psbt = drain_psbt(&[utxo(10_000, challenge())]);
+        psbt.inputs[0] = Input::default();
+        assert!(bip322_to_sign_psbt(psbt, "message").is_err());
+    }
+
+    #[test]
+    fn finalize_needs_all_the_signatures() {
+        let psbt = drain_psbt(&[utxo(10_000, challenge())]);
+        let to_sign = bip322_to_sign_psbt(psbt, "message").unwrap();
+        assert_eq!(
+            bip322_finalize(to_sign).unwrap_err(),
+            "Input #0 is not signed"
+        );
+    }
+}
@@ -0,0 +1,290 @@
+use crate::prelude::*;
+
+use btc_heritage_wallet::{btc_heritage::utils::timestamp_now, OnlineWallet, Wallet};
+
+use state_management::ReservedAddress;
+
+use crate::{
+    components::{
+        export::{use_export_directory, write_export_files, ExportToDirectory},
+        qrcode::qr_code_svg,
+        svg::{DrawSvg, FileDownload, SvgSize::Size4},
+    },
+    utils::{html_escape, timestamp_to_date_string, timestamp_to_file_string, CCStr},
+};
+
+const SHEET_STYLE: &str = "\
+    body { font-family: sans-serif; margin: 2em; color: black; }\
+    h1 { margin-bottom: 0.2em; }\
+    .mono { font-family: monospace; font-size: 0.8em; word-break: break-all; }\
+    .qr-codes { display: flex; flex-wrap: wrap; gap: 2em; }\
+    figure { margin: 0; width: 7cm; break-inside: avoid; text-align: center; }\
+    figcaption b { display: block; font-size: 1.2em; margin: 0.5em 0; }";
+
+/// Build the printable sheet of the reserved addresses of a wallet, as a self-contained
+/// HTML document with a QR code and the purpose of each address
+fn reserved_addresses_sheet_html(
+    wallet_name: &str,
+    reserved_addresses: &[ReservedAddress],
+) -> Result<String, CCStr> {
+    let wallet_name = html_escape(wallet_name);
+    let mut html = format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\">\
+        <title>Reserved Addresses - {wallet_name}</title><style>{SHEET_STYLE}</style></head><body>\
+        <h1>Reserved Addresses</h1>\
+        <p>Wallet <b>{wallet_name}</b>, {}</p>\
+        <p>Each address is reserved for a single purpose. Only give it to the payer it is meant for.</p>\
+        <div class=\"qr-codes\">",
+        timestamp_to_date_string(timestamp_now()),
+    );
+    for reserved_address in reserved_addresses {
+        let svg = qr_code_svg(&format!("bitcoin:{}", reserved_address.address))
+            .ok_or_else(|| CCStr::from("The address does not fit in a QR code"))?;
+        html.push_str(&format!(
+            "<figure>{svg}<figcaption><b>{}</b><div class=\"mono\">{}</div></figcaption></figure>",
+            html_escape(&reserved_address.purpose),
+            html_escape(&reserved_address.address),
+        ));
+    }
+    html.push_str("</div></body></html>");
+
+    Ok(html)
+}
+
+/// Component reserving receive addresses for recurring purposes and saving them as a
+/// printable sheet
+///
+/// Reserved addresses can be handed out once to a payer (an employer, a DCA bot...) who
+/// keeps sending to them. They are marked in the addresses list and never proposed by
+/// the "Receive" button.
+#[component]
+pub(super) fn ReservedAddressesConfig(wallet_name: CCStr) -> Element {
+    log::debug!("ReservedAddressesConfig Rendered");
+
+    let database_service = state_management::use_database_service();
+
+    let mut wallet = use_context::<AsyncSignal<Wallet>>();
+    let reserved_addresses = use_context::<FResource<Vec<ReservedAddress>>>();
+
+    let mut purposes = use_signal(String::new);
+    let new_purposes = use_memo(move || {
+        purposes
+            .read()
+            .lines()
+            .map(str::trim)
+            .filter(|purpose| !purpose.is_empty())
+            .map(str::to_owned)
+            .collect::<Vec<_>>()
+    });
+    let mut in_operation = use_signal(|| false);
+
+    let export_directory = use_export_directory();
+    let export_path = use_memo({
+        let wallet_name = wallet_name.clone();
+        move || {
+            let ts_str = timestamp_to_file_string(timestamp_now());
+            format!(
+                "{}/reserved-addresses-{wallet_name}-{ts_str}.html",
+                export_directory.read()
+            )
+        }
+    });
+
+    let save_reserved_addresses = {
+        let wallet_name = wallet_name.clone();
+        move |updated: Vec<ReservedAddress>| {
+            let wallet_name = wallet_name.clone();
+            optimistic_update(
+                reserved_addresses,
+                Ok(updated.clone()),
+                state_management::save_reserved_addresses(database_service, wallet_name, updated),
+            )
+        }
+    };
+
+    let reserve = {
+        let save_reserved_addresses = save_reserved_addresses.clone();
+        move |_| {
+            let save_reserved_addresses = save_reserved_addresses.clone();
+            async move {
+                let Some(Ok(mut updated)) = reserved_addresses.cloned() else {
+                    alert_error("The reserved addresses are not loaded yet");
+                    return;
+                };
+                *in_operation.write() = true;
+                let mut reserved_count = 0;
+                for purpose in new_purposes() {
+                    // Always a brand new address, so that it was never handed out before
+                    match wallet
+                        .with_mut(async |wallet: &mut Wallet| wallet.get_address().await)
+                        .await
+                    {
+                        Ok(new_address) => {
+                            updated.push(ReservedAddress {
+                                address: new_address.address().to_string(),
+                                purpose,
+                                reserved_at: timestamp_now(),
+                            });
+                            reserved_count += 1;
+                        }
+                        Err(e) => {
+                            alert_failure("Failed to generate a new address", e);
+                            break;
+                        }
+                    }
+                }
+                if reserved_count > 0 {
+                    match save_reserved_addresses(updated).await {
+                        Ok(()) => {
+                            alert_success(format!("{reserved_count} address(es) reserved"));
+                            purposes.set(String::new());
+                        }
+                        Err(e) => alert_failure("Failed to save the reserved addresses", e),
+                    }
+                }
+                *in_operation.write() = false;
+            }
+        }
+    };
+
+    let release = move |address: String| {
+        let save_reserved_addresses = save_reserved_addresses.clone();
+        async move {
+            let Some(Ok(mut updated)) = reserved_addresses.cloned() else {
+                return;
+            };
+            updated.retain(|reserved_address| reserved_address.address != address);
+            *in_operation.write() = true;
+            if let Err(e) = save_reserved_addresses(updated).await {
+                alert_failure("Failed to release the address", e);
+            }
+            *in_operation.write() = false;
+        }
+    };
+
+    let save_sheet = move |_| {
+        let wallet_name = wallet_name.clone();
+        async move {
+            let Some(Ok(reserved_addresses)) = reserved_addresses.cloned() else {
+                alert_error("The reserved addresses are not loaded yet");
+                return;
+            };
+            let file_path = export_path();
+            match reserved_addresses_sheet_html(&wallet_name, &reserved_addresses) {
+                Ok(sheet) => {
+                    if write_export_files("reserved addresses sheet", [(file_path.clone(), sheet)])
+                    {
+                        alert_info(format!(
+                            "Reserved addresses sheet saved to: {file_path}. Print it from your web browser."
+                        ));
+                        _ = open::that_in_background(file_path.as_str());
+                    }
+                }
+                Err(e) => {
+                    alert_failure("Failed to save the reserved addresses sheet", e);
+                }
+            }
+        }
+    };
+
+    use_drop(|| log::debug!("ReservedAddressesConfig Dropped"));
+
+    rsx! {
+        div { class: "rounded-box border border-base-content/5 shadow-md p-4 my-4",
+            h2 { class: "text-2xl font-bold mb-4", "Reserved Addresses" }
+
+            div { class: "text-sm font-light mb-4",
+                "Reserve receive addresses for recurring payments, like a salary or a DCA bot, and
+                print them with their QR codes. Reserved addresses are marked in the addresses list
+                and never handed out by the Receive button, so that each payer keeps its own."
+            }
+
+            match reserved_addresses() {
+                Some(Ok(reserved_addresses)) if !reserved_addresses.is_empty() => rsx! {
+                    table { class: "table table-sm mb-4",
+                        thead {
+                            tr {
+                                th { "Purpose" }
+                                th { "Address" }
+                                th { "Reserved on" }
+                                th {}
+                            }
+                        }
+                        tbody {
+                            for reserved_address in reserved_addresses {
+                                tr { key: "{reserved_address.address}",
+                                    td { class: "font-semibold", "{reserved_address.purpose}" }
+                                    td { class: "font-mono text-xs break-all", "{reserved_address.address}" }
+                                    td { class: "text-nowrap",
+                                        {timestamp_to_date_string(reserved_address.reserved_at)}
+                                    }
+                                    td {
+                                        button {
+                                            class: "btn btn-xs btn-outline",
+                                            disabled: in_operation(),
+                                            onclick: {
+                                                let release = release.clone();
+                                                let address = reserved_address.address.clone();
+                                                move |_| release(address.clone())
+                                            },
+                                            "Release"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                },
+                Some(Ok(_)) => rsx! {
+                    div { class: "mb-4", "No reserved address" }
+                },
+                Some(Err(e)) => rsx! {
+                    div { class: "alert alert-error mb-4", "Could not load the reserved addresses: {e}" }
+                },
+                None => rsx! {
+                    div { class: "skeleton h-16 w-full mb-4" }
+                },
+            }
+
+            div { class: "flex flex-row flex-wrap gap-4 items-end",
+                fieldset { class: "fieldset w-lg",
+                    legend { class: "fieldset-legend", "Purposes, one per line" }
+                    textarea {
+                        class: "textarea w-full",
+                        rows: "3",
+                        disabled: in_operation(),
+                        placeholder: "Salary\nDCA bot",
+                        value: "{purposes}",
+                        oninput: move |evt| purposes.set(evt.value()),
+                    }
+                    div { class: "fieldset-label", "A new address is reserved for each purpose" }
+                }
+                button {
+                    class: "btn btn-primary",
+                    disabled: in_operation() || new_purposes.read().is_empty(),
+                    onclick: reserve,
+                    if in_operation() {
+                        span { class: "loading loading-spinner" }
+                    }
+                    "Reserve Addresses"
+                }
+            }
+
+            ExportToDirectory {
+                directory: export_directory,
+                display_path: ReadOnlySignal::from(export_path),
+                class: "flex justify-center mt-4",
+                button {
+                    class: "btn",
+                    onclick: save_sheet,
+                    disabled: !matches!(
+                        *reserved_addresses.read(),
+                        Some(Ok(ref reserved_addresses)) if !reserved_addresses.is_empty()
+                    ),
+                    DrawSvg::<FileDownload> { size: Size4 }
+                    "Save Printable Sheet"
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use crate::prelude::*;
+
+use state_management::{DisplayDenomination, WalletSettings};
+
+use crate::utils::CCStr;
+
+/// Settings of the wallet taking precedence over the global ones
+#[component]
+pub(super) fn SettingsOverridesConfig(wallet_name: CCStr) -> Element {
+    let database_service = state_management::use_database_service();
+    let wallet_settings = use_context::<Resource<WalletSettings>>();
+
+    let mut denomination = use_signal(|| None::<DisplayDenomination>);
+    let mut sync_interval_str = use_signal(String::new);
+    let mut address_cross_check = use_signal(|| false);
+    let mut min_confirmations_str = use_signal(String::new);
+    use_effect(move || {
+        if let Some(current) = wallet_settings.cloned() {
+            denomination.set(current.denomination);
+            address_cross_check.set(current.address_cross_check);
+            sync_interval_str.set(
+                current
+                    .sync_interval_minutes
+                    .map(|interval| interval.to_string())
+                    .unwrap_or_default(),
+            );
+            min_confirmations_str.set(
+                current
+                    .min_confirmations
+                    .map(|min_confirmations| min_confirmations.to_string())
+                    .unwrap_or_default(),
+            );
+        }
+    });
+    // An empty interval means the global one applies
+    let sync_interval = use_memo(move || {
+        let sync_interval_str = sync_interval_str.read();
+        match sync_interval_str.trim() {
+            "" => Some(None),
+            s => s.parse::<u32>().ok().map(Some),
+        }
+    });
+    // An empty threshold means the default one applies
+    let min_confirmations = use_memo(move || {
+        let min_confirmations_str = min_confirmations_str.read();
+        match min_confirmations_str.trim() {
+            "" => Some(None),
+            s => s.parse::<u32>().ok().filter(|v| *v > 0).map(Some),
+        }
+    });
+    let new_settings = use_memo(move || {
+        Some(WalletSettings {
+            denomination: denomination(),
+            sync_interval_minutes: sync_interval()?,
+            address_cross_check: address_cross_check(),
+            min_confirmations: min_confirmations()?,
+            ..wallet_settings.cloned().unwrap_or_default()
+        })
+    });
+    let has_changes = use_memo(move || new_settings() != wallet_settings.cloned());
+
+    let global_sync_interval = use_memo(move || {
+        match state_management::BACKGROUND_SYNC_CONFIG
+            .read()
+            .sync_interval_minutes
+        {
+            0 => "disabled".to_owned(),
+            interval => format!("{interval} minutes"),
+        }
+    });
+
+    let mut in_operation = use_signal(|| false);
+    let save_settings = move |_| {
+        let wallet_name = wallet_name.clone();
+        async move {
+            let Some(new_settings) = new_settings() else {
+                return;
+            };
+            *in_operation.write() = true;
+            match optimistic_update(
+                wallet_settings,
+                new_settings.clone(),
+                state_management::save_wallet_settings(database_service, wallet_name, new_settings),
+            )
+            .await
+            {
+                Ok(()) => alert_success("Wallet settings updated"),
+                Err(e) => alert_failure("Failed to update the wallet settings", e),
+            }
+            *in_operation.write() = false;
+        }
+    };
+
+    rsx! {
+        div { class: "rounded-box border border-base-content/5 shadow-md p-4 my-4",
+            h2 { class: "text-2xl font-bold mb-4", "Settings Overrides" }
+
+            div { class: "text-sm font-light mb-4",
+                "These settings only apply to this wallet, in place of the global settings of the application."
+            }
+
+            div { class: "flex flex-row flex-wrap gap-4 items-end",
+                fieldset { class: "fieldset w-64",
+                    legend { class: "fieldset-legend", "Amounts unit" }
+                    select {
+                        class: "select",
+                        disabled: in_operation(),
+                        onchange: move |evt| denomination.set(DisplayDenomination::from_code(&evt.value())),
+                        option { value: "", selected: denomination().is_none(), "Automatic (global)" }
+                        for d in DisplayDenomination::list() {
+                            option {
+                                value: d.code(),
+                                selected: denomination() == Some(d),
+                                {d.code()}
+                            }
+                        }
+                    }
+                }
+                fieldset { class: "fieldset w-64",
+                    legend { class: "fieldset-legend", "Background sync interval (minutes)" }
+                    input {
+                        r#type: "number",
+                        min: "0",
+                        class: "input",
+                        class: if sync_interval().is_none() { "input-error" },
+                        disabled: in_operation(),
+                        placeholder: "Global ({global_sync_interval})",
+                        value: "{sync_interval_str}",
+                        oninput: move |evt| sync_interval_str.set(evt.value()),
+                    }
+                    div { class: "fieldset-label", "Leave empty to use the global interval, 0 disables it" }
+                }
+                fieldset { class: "fieldset w-64",
+                    legend { class: "fieldset-legend", "Confirmations to spend a UTXO" }
+                    input {
+                        r#type: "number",
+                        min: "1",
+                        class: "input",
+                        class: if min_confirmations().is_none() { "input-error" },
+                        disabled: in_operation(),
+                        placeholder: "Default ({WalletSettings::DEFAULT_MIN_CONFIRMATIONS})",
+                        value: "{min_confirmations_str}",
+                        oninput: move |evt| min_confirmations_str.set(evt.value()),
+                    }
+                    div { class: "fieldset-label",
+                        "Only enforced above 1 with a connected blockchain provider, which knows the blockchain height"
+                    }
+                }
+                fieldset { class: "fieldset w-lg",
+                    legend { class: "fieldset-legend", "Receive addresses" }
+                    label { class: "label text-sm",
+                        input {
+                            r#type: "checkbox",
+                            class: "toggle toggle-sm",
+                            checked: address_cross_check(),
+                            disabled: in_operation(),
+                            onchange: move |evt| address_cross_check.set(evt.checked()),
+                        }
+                        "Cross-check with the Bitcoin Core node"
+                    }
+                    div { class: "fieldset-label",
+                        "Re-derive each receive address with the Bitcoin Core node of the blockchain provider before displaying it"
+                    }
+                }
+                button {
+                    class: "btn btn-primary",
+                    disabled: !has_changes() || new_settings().is_none() || in_operation(),
+                    onclick: save_settings,
+                    "Save"
+                }
+            }
+        }
+    }
+}
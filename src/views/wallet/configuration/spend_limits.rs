@@ -0,0 +1,201 @@
+use crate::prelude::*;
+
+use btc_heritage_wallet::btc_heritage::utils::timestamp_now;
+use state_management::{SpendLimits, WalletSettings};
+
+use crate::utils::{timestamp_to_string, CCStr};
+
+/// Parse an optional number, an empty input meaning no limit
+fn parse_optional(value: &str) -> Option<Option<u64>> {
+    match value.trim() {
+        "" => Some(None),
+        s => s.parse::<u64>().ok().filter(|v| *v > 0).map(Some),
+    }
+}
+
+fn optional_to_string(value: Option<u64>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+/// Spend limits and cooling-off delay the owner imposes on the wallet
+#[component]
+pub(super) fn SpendLimitsConfig(wallet_name: CCStr) -> Element {
+    let database_service = state_management::use_database_service();
+    let wallet_settings = use_context::<Resource<WalletSettings>>();
+
+    let mut max_per_tx_str = use_signal(String::new);
+    let mut max_per_day_str = use_signal(String::new);
+    let mut cooling_off_threshold_str = use_signal(String::new);
+    let mut cooling_off_minutes_str = use_signal(String::new);
+    // The limits in force, which may come from a looser change whose delay elapsed
+    let spend_limits = use_memo(move || {
+        wallet_settings
+            .cloned()
+            .map(|current| current.effective_spend_limits(timestamp_now()))
+    });
+    let pending_spend_limits = use_memo(move || {
+        wallet_settings
+            .cloned()
+            .and_then(|current| current.pending_spend_limits)
+            .filter(|pending| pending.apply_at > timestamp_now())
+    });
+    use_effect(move || {
+        if let Some(spend_limits) = spend_limits() {
+            max_per_tx_str.set(optional_to_string(spend_limits.max_per_tx));
+            max_per_day_str.set(optional_to_string(spend_limits.max_per_day));
+            cooling_off_threshold_str.set(optional_to_string(spend_limits.cooling_off_threshold));
+            cooling_off_minutes_str.set(spend_limits.cooling_off_minutes.to_string());
+        }
+    });
+
+    let max_per_tx = use_memo(move || parse_optional(&max_per_tx_str.read()));
+    let max_per_day = use_memo(move || parse_optional(&max_per_day_str.read()));
+    let cooling_off_threshold = use_memo(move || parse_optional(&cooling_off_threshold_str.read()));
+    let cooling_off_minutes = use_memo(move || {
+        let cooling_off_minutes_str = cooling_off_minutes_str.read();
+        match cooling_off_minutes_str.trim() {
+            "" => Some(0),
+            s => s.parse::<u32>().ok(),
+        }
+    });
+
+    let new_spend_limits = use_memo(move || {
+        Some(SpendLimits {
+            max_per_tx: max_per_tx()?,
+            max_per_day: max_per_day()?,
+            cooling_off_threshold: cooling_off_threshold()?,
+            cooling_off_minutes: cooling_off_minutes()?,
+        })
+    });
+    let has_changes = use_memo(move || new_spend_limits() != spend_limits());
+
+    let mut in_operation = use_signal(|| false);
+    let save_spend_limits = use_callback(move |new_spend_limits: SpendLimits| {
+        let wallet_name = wallet_name.clone();
+        async move {
+            let mut new_settings = wallet_settings.cloned().unwrap_or_default();
+            new_settings.change_spend_limits(new_spend_limits, timestamp_now());
+            let pending_apply_at = new_settings
+                .pending_spend_limits
+                .map(|pending| pending.apply_at);
+            *in_operation.write() = true;
+            match optimistic_update(
+                wallet_settings,
+                new_settings.clone(),
+                state_management::save_wallet_settings(database_service, wallet_name, new_settings),
+            )
+            .await
+            {
+                Ok(()) => match pending_apply_at {
+                    Some(apply_at) => alert_info(format!(
+                        "Tighter limits are in force now, the looser ones will apply on {}",
+                        timestamp_to_string(apply_at)
+                    )),
+                    None => alert_success("Spend limits updated"),
+                },
+                Err(e) => alert_failure("Failed to update the spend limits", e),
+            }
+            *in_operation.write() = false;
+        }
+    });
+
+    rsx! {
+        div { class: "rounded-box border border-base-content/5 shadow-md p-4 my-4",
+            h2 { class: "text-2xl font-bold mb-4", "Spend Limits" }
+
+            div { class: "text-sm font-light mb-4",
+                "Self-imposed limits on the transactions created and signed from this wallet, \
+                a guardrail against coercion and rash decisions. Leave a field empty for no limit."
+            }
+            div { class: "text-sm font-light mb-4",
+                "Tighter limits apply at once. Lifting or raising a limit, or shortening the delay, \
+                only applies after the cooling-off delay ({SpendLimits::DEFAULT_LOOSENING_MINUTES} minutes \
+                without one) so the limits cannot be removed on the spot."
+            }
+            if let Some(pending) = pending_spend_limits() {
+                div { class: "alert alert-warning flex flex-row justify-between mb-4",
+                    span {
+                        "Looser limits were requested, they will apply on {timestamp_to_string(pending.apply_at)}."
+                    }
+                    button {
+                        class: "btn btn-sm",
+                        disabled: in_operation(),
+                        onclick: move |_| async move {
+                            if let Some(spend_limits) = spend_limits() {
+                                save_spend_limits.call(spend_limits).await;
+                            }
+                        },
+                        "Cancel the Change"
+                    }
+                }
+            }
+
+            div { class: "flex flex-row flex-wrap gap-4 items-end",
+                fieldset { class: "fieldset w-64",
+                    legend { class: "fieldset-legend", "Maximum per transaction (sat)" }
+                    input {
+                        r#type: "number",
+                        min: "1",
+                        class: "input",
+                        class: if max_per_tx().is_none() { "input-error" },
+                        disabled: in_operation(),
+                        placeholder: "No limit",
+                        value: "{max_per_tx_str}",
+                        oninput: move |evt| max_per_tx_str.set(evt.value()),
+                    }
+                }
+                fieldset { class: "fieldset w-64",
+                    legend { class: "fieldset-legend", "Maximum per 24 hours (sat)" }
+                    input {
+                        r#type: "number",
+                        min: "1",
+                        class: "input",
+                        class: if max_per_day().is_none() { "input-error" },
+                        disabled: in_operation(),
+                        placeholder: "No limit",
+                        value: "{max_per_day_str}",
+                        oninput: move |evt| max_per_day_str.set(evt.value()),
+                    }
+                }
+                fieldset { class: "fieldset w-64",
+                    legend { class: "fieldset-legend", "Cooling-off above (sat)" }
+                    input {
+                        r#type: "number",
+                        min: "1",
+                        class: "input",
+                        class: if cooling_off_threshold().is_none() { "input-error" },
+                        disabled: in_operation(),
+                        placeholder: "Never",
+                        value: "{cooling_off_threshold_str}",
+                        oninput: move |evt| cooling_off_threshold_str.set(evt.value()),
+                    }
+                }
+                fieldset { class: "fieldset w-64",
+                    legend { class: "fieldset-legend", "Cooling-off delay (minutes)" }
+                    input {
+                        r#type: "number",
+                        min: "0",
+                        class: "input",
+                        class: if cooling_off_minutes().is_none() { "input-error" },
+                        disabled: in_operation(),
+                        value: "{cooling_off_minutes_str}",
+                        oninput: move |evt| cooling_off_minutes_str.set(evt.value()),
+                    }
+                    div { class: "fieldset-label",
+                        "Bigger transactions must be confirmed again after this delay"
+                    }
+                }
+                button {
+                    class: "btn btn-primary",
+                    disabled: !has_changes() || new_spend_limits().is_none() || in_operation(),
+                    onclick: move |_| async move {
+                        if let Some(new_spend_limits) = new_spend_limits() {
+                            save_spend_limits.call(new_spend_limits).await;
+                        }
+                    },
+                    "Save"
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use crate::prelude::*;
+
+use btc_heritage_wallet::{bitcoin::base58, btc_heritage::HeritageWalletBackup};
+
+use crate::{
+    components::qrcode::{ur_encode, AnimatedQRCode, Cbor, UR_MAX_FRAGMENT_LEN},
+    utils::{CCStr, CheapClone},
+};
+
+/// Version bytes of the mainnet extended public keys (xpub)
+const XPUB_MAINNET_VERSION: [u8; 4] = [0x04, 0x88, 0xb2, 0x1e];
+
+/// An account extended public key with its origin, as encoded by `crypto-hdkey` (BCR-2020-007)
+struct HdKey {
+    master_fingerprint: u32,
+    /// Derivation path from the master key, each step being (index, hardened)
+    origin_path: Vec<(u32, bool)>,
+    mainnet: bool,
+    parent_fingerprint: u32,
+    chain_code: Vec<u8>,
+    key_data: Vec<u8>,
+}
+impl HdKey {
+    /// Parse a key of the form `[fingerprint/path]xpub`
+    fn parse(key: &str) -> Result<Self, CCStr> {
+        let invalid = || CCStr::from(format!("Invalid account extended public key: {key}"));
+
+        let (origin, xpub) = key
+            .strip_prefix('[')
+            .and_then(|key| key.split_once(']'))
+            .ok_or_else(invalid)?;
+        let mut origin = origin.split('/');
+        let master_fingerprint = origin
+            .next()
+            .and_then(|fg| u32::from_str_radix(fg, 16).ok())
+            .ok_or_else(invalid)?;
+        let origin_path = origin
+            .map(|step| {
+                let (index, hardened) = match step.strip_suffix(['\'', 'h']) {
+                    Some(index) => (index, true),
+                    None => (step, false),
+                };
+                index.parse::<u32>().map(|index| (index, hardened))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| invalid())?;
+
+        // Ignore the derivation steps following the xpub, if any
+        let xpub = xpub.split('/').next().unwrap_or_default();
+        let data = base58::decode_check(xpub).map_err(|_| invalid())?;
+        if data.len() != 78 {
+            return Err(invalid());
+        }
+        Ok(Self {
+            master_fingerprint,
+            origin_path,
+            mainnet: data[0..4] == XPUB_MAINNET_VERSION,
+            parent_fingerprint: u32::from_be_bytes(data[5..9].try_into().expect("4 bytes")),
+            chain_code: data[13..45].to_vec(),
+            key_data: data[45..78].to_vec(),
+        })
+    }
+
+    /// Write the key as a tagged `crypto-hdkey`
+    fn write(&self, cbor: &mut Cbor) {
+        cbor.tag(303).map(if self.mainnet { 4 } else { 5 });
+        cbor.uint(3).bytes(&self.key_data);
+        cbor.uint(4).bytes(&self.chain_code);
+        if !self.mainnet {
+            // crypto-coininfo: bitcoin testnet
+            cbor.uint(5).tag(305).map(1).uint(2).uint(1);
+        }
+        // crypto-keypath
+        cbor.uint(6).tag(304).map(3).uint(1);
+        cbor.array(self.origin_path.len() * 2);
+        for (index, hardened) in self.origin_path.iter() {
+            cbor.uint(*index as u64).bool(*hardened);
+        }
+        cbor.uint(2).uint(self.master_fingerprint as u64);
+        cbor.uint(3).uint(self.origin_path.len() as u64);
+        cbor.uint(8).uint(self.parent_fingerprint as u64);
+    }
+}
+
+/// Encode a descriptor as an `output-descriptor` UR (BCR-2023-010)
+///
+/// The older `crypto-output` format cannot express the Taproot script trees of
+/// Heritage wallets, so the descriptor is transmitted in its text form.
+fn output_descriptor_ur(name: &str, descriptor: &str) -> CheapClone<[CCStr]> {
+    let mut cbor = Cbor::default();
+    cbor.map(2).uint(1).text(descriptor).uint(3).text(name);
+    ur_encode("output-descriptor", &cbor.into_bytes(), UR_MAX_FRAGMENT_LEN).into()
+}
+
+/// Encode the account extended public keys as a `crypto-account` UR (BCR-2020-015)
+fn crypto_account_ur(account_xpubs: &[String]) -> Result<CheapClone<[CCStr]>, CCStr> {
+    let hdkeys = account_xpubs
+        .iter()
+        .map(|account_xpub| HdKey::parse(account_xpub))
+        .collect::<Result<Vec<_>, _>>()?;
+    let master_fingerprint = hdkeys
+        .first()
+        .ok_or_else(|| CCStr::from("No account extended public key to export"))?
+        .master_fingerprint;
+
+    let mut cbor = Cbor::default();
+    cbor.map(2).uint(1).uint(master_fingerprint as u64);
+    cbor.uint(2).array(hdkeys.len());
+    for hdkey in hdkeys.iter() {
+        // Taproot output
+        cbor.tag(409);
+        hdkey.write(&mut cbor);
+    }
+    Ok(ur_encode("crypto-account", &cbor.into_bytes(), UR_MAX_FRAGMENT_LEN).into())
+}
+
+/// Component displaying the wallet descriptors and account extended public keys
+/// as UR-encoded QR codes, animated when they do not fit in a single QR code
+///
+/// It allows airgapped devices and mobile coordinators to import the wallet
+/// definition without exchanging files.
+#[component]
+pub(super) fn UrExportConfig(wallet_name: CCStr) -> Element {
+    log::debug!("UrExportConfig Rendered");
+
+    let descriptor_backup = use_context::<FResource<HeritageWalletBackup>>();
+    let heritage_configs_with_info = use_context::<FMemo<CheapClone<[HeritageConfigWithInfo]>>>();
+
+    let descriptor_urs = use_memo(move || {
+        descriptor_backup
+            .lrmap(|backup| {
+                super::bitcoin_core_export::backup_descriptors(backup).map(|subwallets| {
+                    (1..)
+                        .zip(subwallets)
+                        .map(|(number, subwallet)| {
+                            let name = format!("{wallet_name} - Heritage Configuration #{number}");
+                            let ur = output_descriptor_ur(&name, &subwallet.external.descriptor);
+                            (CCStr::from(name), ur)
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .map(|result| result.and_then(|urs| urs))
+    });
+    let account_ur = use_memo(move || {
+        heritage_configs_with_info
+            .lrmap(|heritage_configs_with_info| {
+                let account_xpubs = heritage_configs_with_info
+                    .iter()
+                    .map(|heritage_config_with_info| {
+                        heritage_config_with_info.account_xpub.to_string()
+                    })
+                    .collect::<Vec<_>>();
+                crypto_account_ur(&account_xpubs)
+            })
+            .map(|result| result.and_then(|ur| ur))
+    });
+
+    // None displays the current Heritage Configuration, the last one
+    let mut selected_descriptor = use_signal(|| None::<usize>);
+    let mut show_account = use_signal(|| false);
+
+    let displayed_ur = use_memo(move || {
+        if show_account() {
+            return account_ur();
+        }
+        descriptor_urs().map(|descriptor_urs| {
+            descriptor_urs.and_then(|descriptor_urs| {
+                let index = selected_descriptor().unwrap_or(descriptor_urs.len().saturating_sub(1));
+                descriptor_urs
+                    .get(index)
+                    .map(|(_, ur)| ur.clone())
+                    .ok_or_else(|| CCStr::from("No descriptor to export"))
+            })
+        })
+    });
+    let select_value = use_memo(move || {
+        if show_account() {
+            "account".to_owned()
+        } else {
+            let last = descriptor_urs()
+                .and_then(Result::ok)
+                .map_or(0, |descriptor_urs| descriptor_urs.len().saturating_sub(1));
+            selected_descriptor().unwrap_or(last).to_string()
+        }
+    });
+
+    use_drop(|| log::debug!("UrExportConfig Dropped"));
+
+    rsx! {
+        div { class: "rounded-box border border-base-content/5 shadow-md p-4 my-4",
+            h2 { class: "text-2xl font-bold mb-4", "Export as QR Code" }
+
+            div { class: "text-sm font-light mb-4",
+                "Scan the wallet definition from an airgapped device or a mobile coordinator.
+                Descriptors use the UR output-descriptor format and account extended public
+                keys the UR crypto-account format. Large data are displayed as an animated
+                QR code: keep scanning until the import completes."
+            }
+
+            fieldset { class: "fieldset mb-4",
+                legend { class: "fieldset-legend", "Data to export" }
+                select {
+                    class: "select select-bordered",
+                    value: select_value(),
+                    onchange: move |event| {
+                        let value = event.value();
+                        *show_account.write() = value == "account";
+                        *selected_descriptor.write() = value.parse().ok();
+                    },
+                    if let Some(Ok(descriptor_urs)) = descriptor_urs() {
+                        for (index , (name , _)) in descriptor_urs.into_iter().enumerate() {
+                            option { value: "{index}", "Descriptor of {name}" }
+                        }
+                    }
+                    option { value: "account", "Account Extended Public Keys" }
+                }
+                if show_account() {
+                    div { class: "label text-wrap",
+                        "The account extended public keys describe the keys of the wallet, not
+                        its addresses: use the descriptors to watch the wallet."
+                    }
+                }
+            }
+
+            div { class: "flex justify-center",
+                match displayed_ur() {
+                    Some(Ok(parts)) => rsx! {
+                        div { class: "w-80",
+                            AnimatedQRCode { parts }
+                        }
+                    },
+                    Some(Err(e)) => rsx! {
+                        div { class: "alert alert-error", "Could not export: {e}" }
+                    },
+                    None => rsx! {
+                        div { class: "skeleton size-80" }
+                    },
+                }
+            }
+        }
+    }
+}
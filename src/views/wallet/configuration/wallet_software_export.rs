@@ -0,0 +1,248 @@
+use crate::prelude::*;
+
+use btc_heritage_wallet::btc_heritage::{
+    bitcoincore_rpc::jsonrpc::serde_json::{self, json},
+    utils::timestamp_now,
+    HeritageWalletBackup,
+};
+
+use crate::{
+    components::{
+        copy::CopyTextarea,
+        export::{use_export_directory, write_export_files, ExportToDirectory},
+        svg::{DrawSvg, FileDownload, SvgSize::Size4},
+    },
+    utils::{descriptor_checksum, timestamp_to_date_string, timestamp_to_file_string, CCStr},
+};
+
+/// Convert a descriptors backup into one Specter wallet file per Heritage Configuration
+///
+/// Specter and Sparrow only handle a single descriptor per wallet, so each Heritage
+/// Configuration becomes its own watch-only wallet. Its external and change descriptors
+/// are merged into a BIP-389 `<0;1>` multipath descriptor so that both derive the change
+/// addresses of the wallet.
+fn specter_wallet_files(
+    wallet_name: &str,
+    backup: &HeritageWalletBackup,
+) -> Result<Vec<CCStr>, CCStr> {
+    super::bitcoin_core_export::backup_descriptors(backup)?
+        .iter()
+        .enumerate()
+        .map(|(i, subwallet)| {
+            let descriptor =
+                multipath_descriptor(&subwallet.external.descriptor, &subwallet.change.descriptor)
+                    .ok_or_else(|| {
+                        CCStr::from(format!(
+                            "the descriptors of the Heritage Configuration #{} \
+                            cannot be merged into a multipath descriptor",
+                            i + 1
+                        ))
+                    })?;
+            serde_json::to_string_pretty(&json!({
+                "label": format!("{wallet_name} - Heritage Configuration #{}", i + 1),
+                "blockheight": 0,
+                "descriptor": descriptor,
+                "devices": [],
+            }))
+            .map(CCStr::from)
+            .map_err(|e| CCStr::from(e.to_string()))
+        })
+        .collect()
+}
+
+/// Merge an external and a change descriptor, with their checksums, into a multipath descriptor
+///
+/// Returns [None] if the change descriptor is not the external one deriving from `/1/*`
+/// instead of `/0/*`.
+fn multipath_descriptor(external: &str, change: &str) -> Option<String> {
+    let strip_checksum = |descriptor: &str| {
+        descriptor
+            .split_once('#')
+            .map_or(descriptor, |(descriptor, _)| descriptor)
+            .to_owned()
+    };
+    let (external, change) = (strip_checksum(external), strip_checksum(change));
+    if !external.contains("/0/*") || external.replace("/0/*", "/1/*") != change {
+        return None;
+    }
+    let multipath = external.replace("/0/*", "/<0;1>/*");
+    let checksum = descriptor_checksum(&multipath)?;
+    Some(format!("{multipath}#{checksum}"))
+}
+
+/// Build the BIP-329 JSON Lines export of the wallet
+///
+/// The labels stored for the wallet come first. Addresses without a stored label get
+/// one generated from the wallet name and the expiration of their Heritage Configuration.
+fn bip329_labels(
+    wallet_name: &str,
+    stored_labels: &[state_management::WalletLabel],
+    addresses: &[WalletAddressWithInfo],
+) -> CCStr {
+    let generated_labels = addresses
+        .iter()
+        .filter(|address| {
+            let reference = address.wallet_address.address().to_string();
+            !stored_labels.iter().any(|l| {
+                l.label_type == state_management::WalletLabelType::Addr && l.reference == reference
+            })
+        })
+        .map(|address| {
+            let label = match &address.heritage_config_infos {
+                Some(Ok(infos)) => match infos.expiration_ts {
+                    Some(expiration_ts) => format!(
+                        "{wallet_name} - expires {}",
+                        timestamp_to_date_string(expiration_ts)
+                    ),
+                    None => format!("{wallet_name} - not used yet"),
+                },
+                _ => wallet_name.to_owned(),
+            };
+            json!({
+                "type": "addr",
+                "ref": address.wallet_address.address().to_string(),
+                "label": label,
+            })
+            .to_string()
+        });
+    std::iter::once(state_management::wallet_labels_to_jsonl(stored_labels))
+        .filter(|stored| !stored.is_empty())
+        .chain(generated_labels)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .into()
+}
+
+/// Component exporting the wallet for the Sparrow and Specter wallet software
+///
+/// It produces the Specter wallet files (that Sparrow also imports) and the labels
+/// of the addresses, so users can follow the wallet with the tools they already use.
+#[component]
+pub(super) fn WalletSoftwareExportConfig(wallet_name: CCStr) -> Element {
+    log::debug!("WalletSoftwareExportConfig Rendered");
+
+    let database_service = state_management::use_database_service();
+    let descriptor_backup = use_context::<FResource<HeritageWalletBackup>>();
+    let addresses_with_info = use_context::<FMemo<CheapClone<[WalletAddressWithInfo]>>>();
+
+    let wallet_files = use_memo({
+        let wallet_name = wallet_name.clone();
+        move || {
+            descriptor_backup
+                .lrmap(|backup| specter_wallet_files(&wallet_name, backup))
+                .map(|result| result.and_then(|files| files))
+        }
+    });
+    let stored_labels = use_resource({
+        let wallet_name = wallet_name.clone();
+        move || {
+            let wallet_name = wallet_name.clone();
+            async move {
+                state_management::load_wallet_labels(database_service, wallet_name)
+                    .await
+                    .map_err(CCStr::from)
+            }
+        }
+    });
+    let labels = use_memo({
+        let wallet_name = wallet_name.clone();
+        move || {
+            let stored_labels = stored_labels.read();
+            let stored_labels = match stored_labels.as_ref()? {
+                Ok(stored_labels) => stored_labels,
+                Err(e) => return Some(Err(e.clone())),
+            };
+            addresses_with_info
+                .lrmap(|addresses| bip329_labels(&wallet_name, stored_labels, addresses))
+        }
+    });
+
+    let export_directory = use_export_directory();
+    let export_prefix = use_memo(move || {
+        let ts_str = timestamp_to_file_string(timestamp_now());
+        format!("{}/{wallet_name}-{ts_str}", export_directory.read())
+    });
+    let display_path = use_memo(move || format!("{}-*", export_prefix.read()));
+
+    let download_export = move |_| {
+        let (Some(Ok(wallet_files)), Some(Ok(labels))) = (wallet_files(), labels()) else {
+            alert_error("The export is not ready yet");
+            return;
+        };
+        let prefix = export_prefix.read();
+        let files = wallet_files
+            .iter()
+            .enumerate()
+            .map(|(i, data)| {
+                let file_path = format!("{prefix}-heritage-config-{}.json", i + 1);
+                (file_path, data.as_bytes())
+            })
+            .chain(std::iter::once((
+                format!("{prefix}-labels.jsonl"),
+                labels.as_bytes(),
+            )));
+        if write_export_files("wallet software export", files) {
+            alert_info(format!("Export saved to: {}", export_directory.read()));
+        }
+    };
+
+    use_drop(|| log::debug!("WalletSoftwareExportConfig Dropped"));
+
+    rsx! {
+        div { class: "rounded-box border border-base-content/5 shadow-md p-4 my-4",
+            h2 { class: "text-2xl font-bold mb-4", "Export to Sparrow or Specter" }
+
+            div { class: "text-sm font-light mb-4",
+                "Follow this wallet from Sparrow or Specter by importing each Heritage Configuration
+                as a watch-only wallet (in Sparrow: File > Import Wallet > Specter). The labels file
+                uses the BIP-329 format: it holds the labels of the wallet and names the addresses
+                that have none. The wallet software must support Taproot descriptors with script
+                paths and multipath descriptors."
+            }
+
+            match wallet_files() {
+                Some(Ok(wallet_files)) => rsx! {
+                    for (number , wallet_file) in (1..).zip(wallet_files) {
+                        div { class: "flex flex-col gap-2 mb-4",
+                            div { class: "font-semibold", "Heritage Configuration #{number}" }
+                            CopyTextarea { value: wallet_file, rows: 6 }
+                        }
+                    }
+                },
+                Some(Err(e)) => rsx! {
+                    div { class: "alert alert-error", "Could not export the descriptors: {e}" }
+                },
+                None => rsx! {
+                    div { class: "skeleton h-32 w-full mb-4" }
+                },
+            }
+
+            div { class: "flex flex-col gap-2 mb-4",
+                div { class: "font-semibold", "Labels:" }
+                match labels() {
+                    Some(Ok(labels)) => rsx! {
+                        CopyTextarea { value: labels, rows: 6 }
+                    },
+                    Some(Err(e)) => rsx! {
+                        div { class: "alert alert-error", "Could not export the labels: {e}" }
+                    },
+                    None => rsx! {
+                        div { class: "skeleton h-32 w-full" }
+                    },
+                }
+            }
+
+            ExportToDirectory {
+                directory: export_directory,
+                display_path: ReadOnlySignal::from(display_path),
+                button {
+                    class: "btn",
+                    onclick: download_export,
+                    disabled: !matches!((wallet_files(), labels()), (Some(Ok(_)), Some(Ok(_)))),
+                    DrawSvg::<FileDownload> { size: Size4 }
+                    "Save Export"
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use crate::prelude::*;
+
+use std::collections::BTreeMap;
+
+use btc_heritage_wallet::{bitcoin::Amount, heritage_service_api_client::TransactionSummary};
+
+use crate::{
+    components::balance::UIBtcAmount,
+    utils::{feerate_sat_per_vb, CheapClone},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FeePeriod {
+    Month,
+    Year,
+}
+impl FeePeriod {
+    fn key(self, ts: u64) -> String {
+        let date = chrono::DateTime::from_timestamp(ts as i64, 0).expect("invalid timestamp");
+        match self {
+            FeePeriod::Month => date.format("%Y-%m").to_string(),
+            FeePeriod::Year => date.format("%Y").to_string(),
+        }
+    }
+}
+
+/// Fees paid by the wallet during a period
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct FeeStats {
+    tx_count: usize,
+    fees: Amount,
+    /// Fees of the transactions only moving coins between addresses of the wallet,
+    /// which are mostly the renewals of the Heritage Configuration
+    refresh_tx_count: usize,
+    refresh_fees: Amount,
+    vsize: f64,
+}
+impl FeeStats {
+    fn add(&mut self, tx_sum: &TransactionSummary) {
+        let is_refresh = tx_sum.outputs_totals.count == tx_sum.owned_outputs.len();
+        self.tx_count += 1;
+        self.fees += tx_sum.fee;
+        if is_refresh {
+            self.refresh_tx_count += 1;
+            self.refresh_fees += tx_sum.fee;
+        }
+        let fee_rate = feerate_sat_per_vb(tx_sum.fee_rate) as f64;
+        if fee_rate > 0.0 {
+            self.vsize += tx_sum.fee.to_sat() as f64 / fee_rate;
+        }
+    }
+    /// Average fee rate, weighted by the size of the transactions
+    fn average_fee_rate(&self) -> Option<f64> {
+        (self.vsize > 0.0).then(|| self.fees.to_sat() as f64 / self.vsize)
+    }
+}
+
+/// Report of the fees paid by the wallet, per month or per year
+#[component]
+pub(super) fn FeeAnalytics() -> Element {
+    log::debug!("FeeAnalytics Rendered");
+
+    let wallet_transactions_content =
+        use_context::<FMemo<ContentHashed<CheapClone<[TransactionSummary]>>>>();
+
+    let mut period = use_signal(|| FeePeriod::Month);
+    let fee_report = use_memo(move || {
+        let period = period();
+        wallet_transactions_content.lrmap(|wallet_transactions| {
+            let mut total = FeeStats::default();
+            let mut by_period = BTreeMap::<String, FeeStats>::new();
+            // Only the transactions entirely funded by the wallet have fees paid by it
+            for tx_sum in wallet_transactions.iter().filter(|tx_sum| {
+                !tx_sum.owned_inputs.is_empty()
+                    && tx_sum.inputs_totals.count == tx_sum.owned_inputs.len()
+            }) {
+                total.add(tx_sum);
+                if let Some(ref bt) = tx_sum.confirmation_time {
+                    by_period
+                        .entry(period.key(bt.timestamp))
+                        .or_default()
+                        .add(tx_sum);
+                }
+            }
+            (total, by_period.into_iter().rev().collect::<Vec<_>>())
+        })
+    });
+
+    use_drop(|| log::debug!("FeeAnalytics Dropped"));
+
+    rsx! {
+        div { class: "rounded-box border border-base-content/5 shadow-md bg-base-100 my-4 p-4",
+            div { class: "flex flex-row items-center gap-4 mb-4",
+                h2 { class: "text-2xl font-bold grow", "Fees Spending" }
+                div { class: "join",
+                    button {
+                        class: "btn btn-sm join-item",
+                        class: if period() == FeePeriod::Month { "btn-active" },
+                        onclick: move |_| period.set(FeePeriod::Month),
+                        "Monthly"
+                    }
+                    button {
+                        class: "btn btn-sm join-item",
+                        class: if period() == FeePeriod::Year { "btn-active" },
+                        onclick: move |_| period.set(FeePeriod::Year),
+                        "Yearly"
+                    }
+                }
+            }
+            match fee_report() {
+                Some(Ok((total, _))) if total.tx_count == 0 => rsx! {
+                    div { class: "text-base font-light", "This wallet did not pay any fee yet." }
+                },
+                Some(Ok((total, by_period))) => rsx! {
+                    div { class: "stats stats-vertical lg:stats-horizontal w-full",
+                        FeeStat { title: "Total Fees", stats: total }
+                        div { class: "stat",
+                            div { class: "stat-title", "Heritage Configuration Refreshes" }
+                            div { class: "stat-value text-xl",
+                                AlwaysLoadedComponent::<UIBtcAmount> { input: total.refresh_fees.into() }
+                            }
+                            div { class: "stat-desc",
+                                "{total.refresh_tx_count} transaction(s) moving coins within the wallet"
+                            }
+                        }
+                    }
+                    if total.refresh_tx_count > 0 {
+                        div { class: "text-sm font-light my-2",
+                            "Refreshing the Heritage Configuration moves your coins to new addresses. "
+                            "Rolling it when the network fee rates are low reduces what these operations cost."
+                        }
+                    }
+                    table { class: "table",
+                        thead {
+                            tr {
+                                th { "Period" }
+                                th { "Transactions" }
+                                th { "Fees" }
+                                th { "Refreshes Fees" }
+                                th { "Average Fee Rate" }
+                            }
+                        }
+                        tbody {
+                            for (period_key , stats) in by_period {
+                                tr { key: "{period_key}",
+                                    td { class: "font-bold", "{period_key}" }
+                                    td { "{stats.tx_count}" }
+                                    td {
+                                        AlwaysLoadedComponent::<UIBtcAmount> { input: stats.fees.into() }
+                                    }
+                                    td {
+                                        AlwaysLoadedComponent::<UIBtcAmount> { input: stats.refresh_fees.into() }
+                                    }
+                                    td {
+                                        match stats.average_fee_rate() {
+                                            Some(fee_rate) => rsx! { "{fee_rate:.1} sat/vB" },
+                                            None => rsx! { "-" },
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                },
+                Some(Err(e)) => rsx! {
+                    div { class: "text-error", "{e}" }
+                },
+                None => rsx! {
+                    div { class: "skeleton h-32 w-full" }
+                },
+            }
+        }
+    }
+}
+
+#[component]
+fn FeeStat(title: &'static str, stats: FeeStats) -> Element {
+    rsx! {
+        div { class: "stat",
+            div { class: "stat-title", {title} }
+            div { class: "stat-value text-xl",
+                AlwaysLoadedComponent::<UIBtcAmount> { input: stats.fees.into() }
+            }
+            div { class: "stat-desc",
+                match stats.average_fee_rate() {
+                    Some(fee_rate) => rsx! { "{stats.tx_count} transaction(s), {fee_rate:.1} sat/vB on average" },
+                    None => rsx! { "{stats.tx_count} transaction(s)" },
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use crate::prelude::*;
+
+use std::time::Duration;
+
+use btc_heritage_wallet::{
+    bitcoin::{Amount, FeeRate},
+    heritage_service_api_client::TransactionSummary,
+    online_wallet::WalletStatus,
+};
+
+use crate::{
+    components::{balance::UIBtcAmount, misc::UITxId},
+    utils::{feerate_sat_per_vb, CheapClone},
+};
+
+/// Interval between two evaluations of the unconfirmed transactions against the fee market
+const FEE_BUMP_EVALUATION_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Minimal increase of the fee rate for a replacement transaction to be relayed, in sat/vB
+const INCREMENTAL_RELAY_FEE_RATE: f64 = 1.0;
+
+/// Fee rate the unconfirmed transactions are evaluated against
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FeeRateEstimation {
+    /// Next-block estimation of the Bitcoin Core node of the blockchain provider
+    NextBlock(FeeRate),
+    /// Fee rate of the Block Inclusion Objective of the wallet at its last synchronization
+    LastSync(FeeRate),
+}
+impl FeeRateEstimation {
+    fn fee_rate(self) -> FeeRate {
+        match self {
+            FeeRateEstimation::NextBlock(fee_rate) | FeeRateEstimation::LastSync(fee_rate) => {
+                fee_rate
+            }
+        }
+    }
+    fn source(self) -> &'static str {
+        match self {
+            FeeRateEstimation::NextBlock(_) => "next block estimation of the Bitcoin Core node",
+            FeeRateEstimation::LastSync(_) => "fee rate of the wallet at its last synchronization",
+        }
+    }
+}
+
+/// Unconfirmed transaction of the wallet paying less than the estimated fee rate
+#[derive(Debug, Clone, PartialEq)]
+struct BumpCandidate {
+    txid: UITxId,
+    fee_rate: f64,
+    target_fee_rate: f64,
+    extra_fee: Amount,
+}
+
+/// Unconfirmed transactions of the wallet whose fee rate fell below the estimated
+/// next-block fee rate, with the extra cost of bumping them
+#[component]
+pub(super) fn FeeBumpRecommendations() -> Element {
+    log::debug!("FeeBumpRecommendations Rendered");
+
+    let blockchain_provider_service = state_management::use_blockchain_provider_service();
+    let wallet_status = use_context::<FResource<WalletStatus>>();
+    let wallet_transactions_content =
+        use_context::<FMemo<ContentHashed<CheapClone<[TransactionSummary]>>>>();
+
+    // Periodically evaluate the transactions again, the fee market moves with every block
+    let mut evaluation_tick = use_signal(|| 0u64);
+    use_future(move || async move {
+        loop {
+            tokio::time::sleep(FEE_BUMP_EVALUATION_INTERVAL).await;
+            *evaluation_tick.write() += 1;
+        }
+    });
+
+    let fee_rate_estimation = use_resource(move || async move {
+        evaluation_tick.read();
+        let last_sync_fee_rate = match &*wallet_status.read() {
+            Some(Ok(wallet_status)) => wallet_status.last_fee_rate,
+            _ => None,
+        };
+        match state_management::bitcoin_core_next_block_fee_rate(blockchain_provider_service).await
+        {
+            Some(fee_rate) => Some(FeeRateEstimation::NextBlock(fee_rate)),
+            None => last_sync_fee_rate.map(FeeRateEstimation::LastSync),
+        }
+    });
+
+    // Only the transactions entirely funded by the wallet can be replaced by it
+    let unconfirmed_transactions = use_memo(move || {
+        wallet_transactions_content
+            .lrmap_ok(|wallet_transactions| {
+                wallet_transactions
+                    .iter()
+                    .filter(|tx_sum| {
+                        tx_sum.confirmation_time.is_none()
+                            && !tx_sum.owned_inputs.is_empty()
+                            && tx_sum.inputs_totals.count == tx_sum.owned_inputs.len()
+                    })
+                    .map(|tx_sum| (tx_sum.txid, tx_sum.fee, tx_sum.fee_rate))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
+    });
+
+    let bump_candidates = use_memo(move || {
+        let estimation = fee_rate_estimation.cloned().flatten()?;
+        let target_fee_rate = feerate_sat_per_vb(estimation.fee_rate()) as f64;
+        Some(
+            unconfirmed_transactions
+                .read()
+                .iter()
+                .filter_map(|(txid, fee, fee_rate)| {
+                    let fee_rate = feerate_sat_per_vb(*fee_rate) as f64;
+                    if fee_rate <= 0.0 || fee_rate >= target_fee_rate {
+                        return None;
+                    }
+                    let vsize = fee.to_sat() as f64 / fee_rate;
+                    // A replacement must pay at least the incremental relay fee rate on top
+                    let target_fee_rate =
+                        target_fee_rate.max(fee_rate + INCREMENTAL_RELAY_FEE_RATE);
+                    let extra_fee =
+                        Amount::from_sat((vsize * (target_fee_rate - fee_rate)).ceil() as u64);
+                    Some(BumpCandidate {
+                        txid: UITxId::from(txid.to_string()),
+                        fee_rate,
+                        target_fee_rate,
+                        extra_fee,
+                    })
+                })
+                .collect::<Vec<_>>(),
+        )
+    });
+
+    use_drop(|| log::debug!("FeeBumpRecommendations Dropped"));
+
+    rsx! {
+        if !unconfirmed_transactions.read().is_empty() {
+            div { class: "rounded-box border border-base-content/5 shadow-md bg-base-100 my-4 p-4",
+                h2 { class: "text-2xl font-bold mb-4", "Fee Bump Recommendations" }
+                match (fee_rate_estimation.cloned().flatten(), bump_candidates()) {
+                    (Some(estimation), Some(candidates)) if candidates.is_empty() => rsx! {
+                        div { class: "text-base font-light",
+                            "Your unconfirmed transactions pay at least {feerate_sat_per_vb(estimation.fee_rate())} sat/vB, the {estimation.source()}."
+                        }
+                    },
+                    (Some(estimation), Some(candidates)) => rsx! {
+                        div { class: "text-sm font-light mb-4",
+                            "These unconfirmed transactions pay less than {feerate_sat_per_vb(estimation.fee_rate())} sat/vB, the {estimation.source()}. "
+                            "Replacing them with a higher fee would speed up their confirmation."
+                        }
+                        table { class: "table",
+                            thead {
+                                tr {
+                                    th { "Transaction" }
+                                    th { "Fee Rate" }
+                                    th { "Bumped Fee Rate" }
+                                    th { "Projected Extra Cost" }
+                                }
+                            }
+                            tbody {
+                                for candidate in candidates {
+                                    tr { key: "{candidate.txid.0}",
+                                        td {
+                                            AlwaysLoadedComponent { input: candidate.txid }
+                                        }
+                                        td { "{candidate.fee_rate:.1} sat/vB" }
+                                        td { "{candidate.target_fee_rate:.1} sat/vB" }
+                                        td {
+                                            AlwaysLoadedComponent::<UIBtcAmount> { input: candidate.extra_fee.into() }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    _ if fee_rate_estimation.read().is_none() => rsx! {
+                        div { class: "skeleton h-16 w-full" }
+                    },
+                    _ => rsx! {
+                        div { class: "text-base font-light",
+                            "No fee rate estimation is available. Synchronize the wallet to evaluate its unconfirmed transactions."
+                        }
+                    },
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use crate::prelude::*;
+
+use btc_heritage_wallet::{
+    btc_heritage::{utils::bitcoin_network, AccountXPub, HeritageWalletBackup},
+    miniscript::{Descriptor, DescriptorPublicKey},
+};
+
+use crate::{
+    components::copy::CopyToClipboardButtonIcon,
+    utils::{CCStr, CheapClone},
+};
+
+use super::configuration::bitcoin_core_export::backup_descriptors;
+
+/// Choices for the number of addresses to preview
+const PREVIEW_COUNTS: [u32; 3] = [5, 10, 20];
+
+/// The first addresses of a Heritage Configuration, external then change
+type PreviewAddresses = (Vec<CCStr>, Vec<CCStr>);
+
+/// Derive the first `count` external and change addresses of the Heritage Configuration
+/// paired with `account_xpub`, from the descriptors of the wallet backup
+fn preview_addresses(
+    backup: &HeritageWalletBackup,
+    account_xpub: &AccountXPub,
+    count: u32,
+) -> Result<PreviewAddresses, CCStr> {
+    // The descriptors reference the account xpub with its key origin,
+    // followed by the derivation steps of the addresses
+    let account_key = account_xpub.descriptor_public_key().to_string();
+    let xpub = account_key
+        .rsplit(']')
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches("/*");
+    let subwallet = backup_descriptors(backup)?
+        .into_iter()
+        .find(|subwallet| subwallet.external.descriptor.contains(xpub))
+        .ok_or_else(|| CCStr::from("No descriptor found for this Heritage Configuration"))?;
+
+    let network = bitcoin_network::get();
+    let derive = |descriptor: &str| {
+        let descriptor = descriptor
+            .parse::<Descriptor<DescriptorPublicKey>>()
+            .map_err(|e| CCStr::from(e.to_string()))?;
+        (0..count)
+            .map(|index| {
+                descriptor
+                    .at_derivation_index(index)
+                    .map_err(|e| CCStr::from(e.to_string()))?
+                    .address(network)
+                    .map(|address| CCStr::from(address.to_string()))
+                    .map_err(|e| CCStr::from(e.to_string()))
+            })
+            .collect::<Result<Vec<_>, CCStr>>()
+    };
+    Ok((
+        derive(&subwallet.external.descriptor)?,
+        derive(&subwallet.change.descriptor)?,
+    ))
+}
+
+/// Preview of the first addresses a Heritage Configuration produces
+///
+/// Owners can recognize them in a block explorer, or check them against the addresses
+/// displayed by their hardware wallet, before sending any bitcoin to the configuration.
+#[component]
+pub(super) fn HeritageConfigAddresses(
+    account_xpub: ReadOnlySignal<CheapClone<AccountXPub>>,
+) -> Element {
+    log::debug!("HeritageConfigAddresses Rendered");
+
+    let backup = use_context::<FResource<HeritageWalletBackup>>();
+
+    let mut show_preview = use_signal(|| false);
+    let mut count = use_signal(|| PREVIEW_COUNTS[0]);
+
+    let addresses = use_memo(move || {
+        if !show_preview() {
+            return None;
+        }
+        backup
+            .lrmap(|backup| preview_addresses(backup, &account_xpub.read(), count()))
+            .map(|result| result.and_then(|addresses| addresses))
+    });
+
+    use_drop(|| log::debug!("HeritageConfigAddresses Dropped"));
+
+    rsx! {
+        div { class: "mt-4",
+            if show_preview() {
+                div { class: "flex flex-row gap-4 items-center mb-2",
+                    div { class: "font-semibold grow", "Addresses Preview" }
+                    select {
+                        class: "select select-sm w-32",
+                        onchange: move |evt| {
+                            if let Ok(new_count) = evt.parsed::<u32>() {
+                                count.set(new_count);
+                            }
+                        },
+                        for preview_count in PREVIEW_COUNTS {
+                            option {
+                                value: "{preview_count}",
+                                selected: count() == preview_count,
+                                "First {preview_count}"
+                            }
+                        }
+                    }
+                    button {
+                        class: "btn btn-sm",
+                        onclick: move |_| show_preview.set(false),
+                        "Hide"
+                    }
+                }
+                div { class: "text-sm font-light mb-2",
+                    "The first addresses of this Heritage Configuration, to recognize them in a block
+                    explorer or compare them with the addresses displayed by your hardware wallet
+                    before funding it."
+                }
+                match addresses() {
+                    None => rsx! {
+                        span { class: "loading loading-spinner loading-md" }
+                    },
+                    Some(Err(e)) => rsx! {
+                        div { class: "text-error", "Could not derive the addresses: {e}" }
+                    },
+                    Some(Ok((external, change))) => rsx! {
+                        div { class: "grid grid-cols-1 lg:grid-cols-2 gap-4",
+                            AddressesPreviewTable { title: "External (Receive)", addresses: external }
+                            AddressesPreviewTable { title: "Internal (Change)", addresses: change }
+                        }
+                    },
+                }
+            } else {
+                button {
+                    class: "btn btn-sm",
+                    onclick: move |_| show_preview.set(true),
+                    "Preview Addresses"
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn AddressesPreviewTable(title: &'static str, addresses: Vec<CCStr>) -> Element {
+    rsx! {
+        div {
+            div { class: "text-sm font-semibold mb-1", {title} }
+            table { class: "table table-xs",
+                tbody {
+                    for (index , address) in addresses.into_iter().enumerate() {
+                        tr {
+                            td { class: "text-(--color-base-content)/60", "{index}" }
+                            td { class: "font-mono break-all", {address.clone()} }
+                            td {
+                                CopyToClipboardButtonIcon { value: address }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
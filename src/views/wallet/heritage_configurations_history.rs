@@ -1,6 +1,6 @@
 use crate::prelude::*;
 
-use btc_heritage_wallet::bitcoin::Amount;
+use btc_heritage_wallet::{bitcoin::Amount, btc_heritage::AccountXPub};
 
 use crate::{
     components::{
@@ -35,6 +35,7 @@ struct UIHeritageConfigurationsHistoryItem {
     firstuse: UITimestamp,
     heritage_config: UIHeritageConfig,
     associated_balance: LResult<UIBtcAmount>,
+    account_xpub: Option<CheapClone<AccountXPub>>,
 }
 impl LoadedElement for UIHeritageConfigurationsHistoryItem {
     type Loader = TransparentLoader;
@@ -78,6 +79,9 @@ impl LoadedElement for UIHeritageConfigurationsHistoryItem {
                             }
                         }
                         LoadedComponent { input: m.map(self.heritage_config) }
+                        if let Some(account_xpub) = self.account_xpub {
+                            super::heritage_config_addresses::HeritageConfigAddresses { account_xpub }
+                        }
                     }
                 }
             }
@@ -90,6 +94,7 @@ impl LoadedElement for UIHeritageConfigurationsHistoryItem {
             firstuse: UITimestamp::place_holder(),
             heritage_config: UIHeritageConfig::place_holder(),
             associated_balance: None,
+            account_xpub: None,
         }
     }
 }
@@ -101,6 +106,7 @@ impl LoadedSuccessConversionMarker
 impl FromRef<HeritageConfigWithInfo> for UIHeritageConfigurationsHistoryItem {
     fn from_ref(value: &HeritageConfigWithInfo) -> Self {
         let HeritageConfigWithInfo {
+            ref account_xpub,
             ref heritage_config,
             expiration_ts,
             expiration_status,
@@ -132,6 +138,7 @@ impl FromRef<HeritageConfigWithInfo> for UIHeritageConfigurationsHistoryItem {
             firstuse,
             heritage_config: UIHeritageConfig::from_ref(heritage_config.as_ref()),
             associated_balance,
+            account_xpub: Some(account_xpub.clone()),
         }
     }
 }
@@ -1,6 +1,11 @@
+mod activity;
 mod addresses_history;
 pub mod configuration;
+mod fee_analytics;
+mod fee_bump;
+mod heritage_config_addresses;
 mod heritage_configurations_history;
+mod notes;
 mod receive;
 pub mod spend;
 mod sync;
@@ -9,6 +14,7 @@ mod transactions_history;
 use crate::prelude::*;
 
 use btc_heritage_wallet::{online_wallet::WalletStatus, Wallet};
+use notes::WalletNotes;
 use receive::ReceiveButton;
 use sync::WalletSync;
 
@@ -16,6 +22,7 @@ use crate::{
     components::{
         badge::{ExternalDependencyStatus, KeyProviderType, OnlineWalletType, UIBadge},
         balance::UIWalletBalance,
+        misc::OnVisible,
         quick_actions::{
             BackupOnlineWallet, ShowKeyProviderMnemonic, ShowKeyProviderMnemonicFlavor,
             UnlockLocalKey,
@@ -26,26 +33,82 @@ use crate::{
     Route,
 };
 
+/// Triggers of the wallet resources only loaded when a section or a view needs them
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct WalletLazyResources {
+    pub utxos: LoadTrigger,
+    pub addresses: LoadTrigger,
+}
+impl WalletLazyResources {
+    pub fn fire_all(self) {
+        self.utxos.fire();
+        self.addresses.fire();
+    }
+}
+
 #[component]
 pub fn WalletWrapperLayout(wallet_name: CCStr) -> Element {
     log::debug!("WalletWrapperLayout Rendered");
 
     let wallet = helper_hooks::use_async_wallet(wallet_name.clone());
 
+    // The status (and balance) is loaded first so the wallet view is quickly usable,
+    // the other resources wait for it not to compete with it on the online wallet
     let wallet_status = helper_hooks::use_resource_wallet_status(wallet);
-
-    let wallet_transactions = helper_hooks::use_resource_wallet_transactions(wallet);
-    let tx_stats_by_address = helper_hooks::use_memo_tx_stats_by_address(wallet_transactions);
-
-    let wallet_utxos = helper_hooks::use_resource_wallet_utxos(wallet);
+    helper_hooks::use_debug_snapshot(format!("wallet#{wallet_name}/status"), wallet_status);
+    let secondary_trigger = helper_hooks::use_load_trigger();
+    use_effect(move || {
+        if wallet_status.read().is_some() {
+            secondary_trigger.fire();
+        }
+    });
+    let lazy_resources = WalletLazyResources {
+        utxos: helper_hooks::use_load_trigger(),
+        addresses: helper_hooks::use_load_trigger(),
+    };
+    // Only the wallet view loads the lazy resources on demand, other views need them right away
+    let on_wallet_view = matches!(use_route::<Route>(), Route::WalletView { .. });
+    use_effect(use_reactive((&on_wallet_view,), move |(on_wallet_view,)| {
+        if !on_wallet_view {
+            lazy_resources.fire_all();
+        }
+    }));
+
+    // The memos read the resources through their content so they only recompute
+    // when the data actually changes, not every time a resource refreshes
+    let wallet_transactions =
+        helper_hooks::use_resource_wallet_transactions(wallet, secondary_trigger);
+    let wallet_transactions_content = helper_hooks::use_memo_resource_content(wallet_transactions);
+    let tx_stats_by_address =
+        helper_hooks::use_memo_tx_stats_by_address(wallet_transactions_content);
+    let historical_exchange_rates =
+        helper_hooks::use_resource_historical_exchange_rates(wallet_transactions_content);
+    let wallet_settings = helper_hooks::use_resource_wallet_settings(wallet_name.clone());
+    let wallet_notes = helper_hooks::use_resource_wallet_notes(wallet_name.clone());
+    let backup_freshness = helper_hooks::use_resource_wallet_backup_freshness(wallet_name.clone());
+
+    let wallet_utxos = helper_hooks::use_memo_resource_content(
+        helper_hooks::use_resource_wallet_utxos(wallet, lazy_resources.utxos),
+    );
+    let min_confirmations = use_memo(move || {
+        wallet_settings.read().as_ref().map_or(
+            state_management::WalletSettings::DEFAULT_MIN_CONFIRMATIONS,
+            |wallet_settings| wallet_settings.min_confirmations(),
+        )
+    });
     let balance_by_heritage_config =
-        helper_hooks::use_memo_balance_by_heritage_config(wallet_utxos);
+        helper_hooks::use_memo_balance_by_heritage_config(wallet_utxos, min_confirmations);
     let utxo_stats_by_address = helper_hooks::use_memo_utxo_stats_by_address(wallet_utxos);
 
-    let wallet_subwallet_configs = helper_hooks::use_resource_wallet_subwallet_configs(wallet);
-    let heritage_configs_with_info = helper_hooks::use_memo_heritage_configs_with_info(
-        wallet_subwallet_configs,
-        balance_by_heritage_config,
+    let wallet_subwallet_configs = helper_hooks::use_memo_resource_content(
+        helper_hooks::use_resource_wallet_subwallet_configs(wallet, secondary_trigger),
+    );
+    let heritage_configs_with_info = helper_hooks::use_memo_persisted_heritage_configs_with_info(
+        wallet,
+        helper_hooks::use_memo_heritage_configs_with_info(
+            wallet_subwallet_configs,
+            balance_by_heritage_config,
+        ),
     );
     let heritage_configs_with_info_indexed_by_origin_info =
         helper_hooks::use_memo_heritage_configs_with_info_indexed_by_origin_info(
@@ -56,9 +119,10 @@ pub fn WalletWrapperLayout(wallet_name: CCStr) -> Element {
             heritage_configs_with_info,
         );
 
-    let backup = helper_hooks::use_resource_wallet_descriptor_backup(wallet);
+    let backup = helper_hooks::use_resource_wallet_descriptor_backup(wallet, secondary_trigger);
     let memo_backup = helper_hooks::use_memo_resource(backup);
-    let ledger_registered_policies = helper_hooks::use_memo_ledger_registered_policies(wallet);
+    let ledger_registered_policies =
+        helper_hooks::use_memo_ledger_registered_policies(wallet, wallet_name.clone());
     let ledger_unregistered_policies = helper_hooks::use_memo_ledger_unregistered_policies(
         ledger_registered_policies,
         memo_backup,
@@ -73,26 +137,42 @@ pub fn WalletWrapperLayout(wallet_name: CCStr) -> Element {
     let utxo_with_info = helper_hooks::use_memo_utxo_with_info(
         wallet_utxos,
         heritage_configs_with_info_indexed_by_heritage_config,
+        min_confirmations,
     );
 
-    let wallet_addresses = helper_hooks::use_resource_wallet_addresses(wallet);
-    let addresses_with_info = helper_hooks::use_memo_addresses_with_info(
-        wallet_addresses,
-        heritage_configs_with_info_indexed_by_origin_info,
-        tx_stats_by_address,
-        utxo_stats_by_address,
+    let wallet_addresses = helper_hooks::use_memo_resource_content(
+        helper_hooks::use_resource_wallet_addresses(wallet, lazy_resources.addresses),
+    );
+    let reserved_addresses =
+        helper_hooks::use_resource_wallet_reserved_addresses(wallet_name.clone());
+    let addresses_with_info = helper_hooks::use_memo_persisted_addresses_with_info(
+        wallet,
+        helper_hooks::use_memo_addresses_with_info(
+            wallet_addresses,
+            heritage_configs_with_info_indexed_by_origin_info,
+            tx_stats_by_address,
+            utxo_stats_by_address,
+            reserved_addresses,
+        ),
     );
     let addresses_set = helper_hooks::use_memo_addresses_set(wallet_addresses);
     let ready_to_use_address = helper_hooks::use_memo_ready_to_use_address(addresses_with_info);
 
     let database_heirs = helper_hooks::use_resource_database_heirs();
     let service_heirs = helper_hooks::use_resource_service_heirs();
-    let heirs = helper_hooks::use_memo_heirs(database_heirs, service_heirs);
+    let heir_service_links = helper_hooks::use_resource_heir_service_links();
+    let heirs = helper_hooks::use_memo_heirs(database_heirs, service_heirs, heir_service_links);
 
     // Provide the wallet resources to all child that may want it
     use_context_provider(|| wallet);
     use_context_provider(|| wallet_status);
     use_context_provider(|| wallet_transactions);
+    use_context_provider(|| wallet_transactions_content);
+    use_context_provider(|| historical_exchange_rates);
+    use_context_provider(|| wallet_settings);
+    use_context_provider(|| wallet_notes);
+    use_context_provider(|| backup_freshness);
+    use_context_provider(|| lazy_resources);
 
     use_context_provider(|| heritage_configs_with_info);
 
@@ -103,6 +183,7 @@ pub fn WalletWrapperLayout(wallet_name: CCStr) -> Element {
     use_context_provider(|| keyprovider_status);
     use_context_provider(|| online_status);
 
+    use_context_provider(|| reserved_addresses);
     use_context_provider(|| addresses_with_info);
     use_context_provider(|| addresses_set);
     use_context_provider(|| ready_to_use_address);
@@ -126,8 +207,10 @@ pub fn WalletView(wallet_name: CCStr) -> Element {
 
     let wallet = use_context::<AsyncSignal<Wallet>>();
     let wallet_status = use_context::<FResource<WalletStatus>>();
+    let lazy_resources = use_context::<WalletLazyResources>();
 
     let fingerprint = helper_hooks::use_memo_fingerprint(wallet);
+    let shows_stale_data = helper_hooks::use_memo_wallet_shows_stale_data(wallet);
     let keyprovider_status =
         use_context::<Memo<Option<(KeyProviderType, ExternalDependencyStatus)>>>();
     let can_show_mnemo = use_memo(move || match keyprovider_status() {
@@ -169,7 +252,7 @@ pub fn WalletView(wallet_name: CCStr) -> Element {
             subtitle: fingerprint.cloned(),
             left: rsx! {
                 if not_sign_only() {
-                    WalletSync {}
+                    WalletSync { wallet_name: wallet_name.clone() }
                 }
             },
             right: rsx! {
@@ -213,7 +296,9 @@ pub fn WalletView(wallet_name: CCStr) -> Element {
             }
             div { class: "flex flex-row flex-wrap justify-center items-center gap-4 m-4",
                 if not_sign_only() {
-                    LoadedComponent::<UIWalletBalance> { input: wallet_status.into() }
+                    ResourceErrorBoundary { title: "Wallet balance", resource: wallet_status,
+                        LoadedComponent::<UIWalletBalance> { input: wallet_status.into() }
+                    }
                 }
                 div { class: "inline-flex flex-row flex-wrap justify-center gap-4 m-4",
                     button {
@@ -226,10 +311,28 @@ pub fn WalletView(wallet_name: CCStr) -> Element {
                     }
                 }
             }
+            WalletNotes {}
             if not_sign_only() {
-                transactions_history::TransactionsHistory {}
-                heritage_configurations_history::HeritageConfigurationsHistory {}
-                addresses_history::AddressesHistory {}
+                if shows_stale_data() {
+                    div { class: "flex justify-center",
+                        div {
+                            class: "badge badge-warning badge-outline gap-2",
+                            title: "Showing the last known state of the wallet, it will be updated after the first synchronization",
+                            span { class: "loading loading-spinner loading-xs" }
+                            "Stale"
+                        }
+                    }
+                }
+                activity::ActivityTimeline { wallet_name: wallet_name.clone() }
+                fee_bump::FeeBumpRecommendations {}
+                transactions_history::TransactionsHistory { wallet_name: wallet_name.clone() }
+                fee_analytics::FeeAnalytics {}
+                OnVisible { on_visible: move |_| lazy_resources.utxos.fire(),
+                    heritage_configurations_history::HeritageConfigurationsHistory {}
+                }
+                OnVisible { on_visible: move |_| lazy_resources.fire_all(),
+                    addresses_history::AddressesHistory {}
+                }
             }
 
             OnboardingInfoModal { step: OnboardingStep::ModalFinishCreatingFirstWallet,
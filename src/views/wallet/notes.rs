@@ -0,0 +1,23 @@
+use crate::prelude::*;
+
+use crate::{components::notes::NotesText, utils::CCStr};
+
+/// Read-only display of the notes of the wallet, edited in its configuration
+#[component]
+pub(super) fn WalletNotes() -> Element {
+    let wallet_notes = use_context::<FResource<CCStr>>();
+
+    let notes = use_memo(move || match &*wallet_notes.read() {
+        Some(Ok(notes)) if !notes.trim().is_empty() => Some(notes.clone()),
+        _ => None,
+    });
+
+    rsx! {
+        if let Some(notes) = notes() {
+            div { class: "rounded-box border border-base-content/5 shadow-md bg-base-100 p-4 my-4 max-w-3xl mx-auto",
+                h2 { class: "text-xl font-bold mb-2", "Notes" }
+                NotesText { text: notes }
+            }
+        }
+    }
+}
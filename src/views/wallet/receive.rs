@@ -1,6 +1,11 @@
 use crate::prelude::*;
 
-use btc_heritage_wallet::{btc_heritage::heritage_wallet::WalletAddress, OnlineWallet, Wallet};
+use std::time::Duration;
+
+use btc_heritage_wallet::{
+    btc_heritage::{heritage_wallet::WalletAddress, HeritageWalletBackup},
+    OnlineWallet, Wallet,
+};
 
 use crate::{
     components::{
@@ -9,9 +14,21 @@ use crate::{
         qrcode::UIQRCode,
         svg::{DrawSvg, SvgSize::Size5, Sync},
     },
-    utils::CheapClone,
+    utils::{CCStr, CheapClone},
 };
 
+use state_management::WalletSettings;
+
+/// Outcome of the re-derivation of the receive address by the Bitcoin Core node
+#[derive(Debug, Clone, PartialEq)]
+enum AddressCrossCheck {
+    Disabled,
+    Pending,
+    Verified,
+    Mismatch,
+    Failed(CCStr),
+}
+
 #[component]
 pub fn ReceiveButton() -> Element {
     log::debug!("ReceiveButton Rendered");
@@ -19,6 +36,11 @@ pub fn ReceiveButton() -> Element {
     // Context resources
     let mut wallet = use_context::<AsyncSignal<Wallet>>();
     let ready_to_use_address = use_context::<Memo<Option<Option<CheapClone<WalletAddress>>>>>();
+    let lazy_resources = use_context::<super::WalletLazyResources>();
+    let wallet_settings = use_context::<Resource<WalletSettings>>();
+    let descriptor_backup = use_context::<FResource<HeritageWalletBackup>>();
+
+    let blockchain_provider_service = state_management::use_blockchain_provider_service();
 
     let mut in_operation = use_signal(|| false);
     let mut display_modal = use_signal(|| false);
@@ -38,8 +60,7 @@ pub fn ReceiveButton() -> Element {
                 *receive_address.write() = Some(CheapClone::new(new_address));
             }
             Err(e) => {
-                log::error!("Failed to generate a new address: {e}");
-                alert_error(format!("Failed to generate a new address: {e}"));
+                alert_failure("Failed to generate a new address", e);
             }
         };
         *in_operation.write() = false;
@@ -48,10 +69,26 @@ pub fn ReceiveButton() -> Element {
         *receive_address.write() = None;
         *display_modal.write() = true;
 
-        // If the ready_to_use_address is not ready, just quit
-        // Should never happen
-        let Some(ready_to_use_address) = ready_to_use_address.cloned() else {
-            return;
+        // The addresses are only loaded when needed, wait for them
+        lazy_resources.addresses.fire();
+        *in_operation.write() = true;
+        let ready_to_use_address = tokio::time::timeout(Duration::from_secs(30), async {
+            loop {
+                if let Some(ready_to_use_address) = ready_to_use_address.peek().clone() {
+                    return ready_to_use_address;
+                }
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await;
+        *in_operation.write() = false;
+        let ready_to_use_address = match ready_to_use_address {
+            Ok(ready_to_use_address) => ready_to_use_address,
+            Err(e) => {
+                alert_failure("The wallet addresses could not be loaded", e);
+                *display_modal.write() = false;
+                return;
+            }
         };
 
         if let Some(ready_to_use_address) = ready_to_use_address {
@@ -63,11 +100,59 @@ pub fn ReceiveButton() -> Element {
         }
     };
 
-    let address_string =
-        use_memo(move || receive_address.lmap(|wa| UIBtcAddr::from(wa.address().to_string())));
+    let address_cross_check_enabled = use_memo(move || {
+        wallet_settings
+            .read()
+            .as_ref()
+            .is_some_and(|wallet_settings| wallet_settings.address_cross_check)
+    });
+    // Compare the address with the one the Bitcoin Core node derives before displaying it
+    let address_cross_check = use_resource(move || async move {
+        let Some(wallet_address) = receive_address() else {
+            return AddressCrossCheck::Pending;
+        };
+        if !address_cross_check_enabled() {
+            return AddressCrossCheck::Disabled;
+        }
+        let backup = match descriptor_backup() {
+            Some(Ok(backup)) => backup,
+            Some(Err(e)) => return AddressCrossCheck::Failed(e),
+            None => return AddressCrossCheck::Pending,
+        };
+        match state_management::cross_check_wallet_address(
+            blockchain_provider_service,
+            backup,
+            wallet_address,
+        )
+        .await
+        {
+            Ok(true) => AddressCrossCheck::Verified,
+            Ok(false) => AddressCrossCheck::Mismatch,
+            Err(e) => AddressCrossCheck::Failed(CCStr::from(e)),
+        }
+    });
+    let address_displayable = use_memo(move || {
+        matches!(
+            *address_cross_check.read(),
+            Some(
+                AddressCrossCheck::Disabled
+                    | AddressCrossCheck::Verified
+                    | AddressCrossCheck::Failed(_)
+            )
+        )
+    });
 
-    let address_qrcode =
-        use_memo(move || receive_address.lmap(|wa| UIQRCode::from(wa.address().to_qr_uri())));
+    let address_string = use_memo(move || {
+        receive_address
+            .lmap(|wa| UIBtcAddr::from(wa.address().to_string()))
+            .filter(|_| address_displayable())
+    });
+
+    let address_qrcode = use_memo(move || {
+        receive_address
+            .lmap(|wa| UIQRCode::from(wa.address().to_qr_uri()))
+            .filter(|_| address_displayable())
+    });
 
     use_drop(|| log::debug!("ReceiveButton Dropped"));
 
@@ -78,7 +163,7 @@ pub fn ReceiveButton() -> Element {
             button {
                 class: "btn btn-secondary size-64 rounded-4xl uppercase text-3xl font-black",
                 onclick: receive_click,
-                disabled: in_operation() || ready_to_use_address.read().is_none(),
+                disabled: in_operation(),
                 "Receive"
             }
         }
@@ -88,6 +173,29 @@ pub fn ReceiveButton() -> Element {
                 div { class: "text-xl font-mono",
                     LoadedComponent { input: address_string.cloned().into() }
                 }
+                match address_cross_check() {
+                    Some(AddressCrossCheck::Verified) => rsx! {
+                        div { class: "text-sm text-success", "Verified by the Bitcoin Core node" }
+                    },
+                    Some(AddressCrossCheck::Mismatch) => rsx! {
+                        div { class: "alert alert-error max-w-xl",
+                            "The Bitcoin Core node derives a different address from the descriptors of this wallet. \
+                            Do not use this address and investigate before receiving bitcoins."
+                        }
+                    },
+                    Some(AddressCrossCheck::Failed(e)) => rsx! {
+                        div { class: "alert alert-warning max-w-xl",
+                            "The address could not be cross-checked with the Bitcoin Core node: {e}"
+                        }
+                    },
+                    Some(AddressCrossCheck::Pending) | None if address_cross_check_enabled() => rsx! {
+                        div { class: "flex items-center gap-2 text-sm",
+                            span { class: "loading loading-spinner loading-sm" }
+                            "Cross-checking the address with the Bitcoin Core node..."
+                        }
+                    },
+                    _ => rsx! {},
+                }
                 button {
                     class: "btn btn-secondary",
                     onclick: move |_| generate_new_address(),
@@ -48,7 +48,7 @@ pub fn WalletSpendView(wallet_name: CCStr) -> Element {
         )),
         Some((KeyProviderType::Ledger, ExternalDependencyStatus::Unavailable)) => Some((
             "Your wallet uses a Ledger Hardware Wallet device, \
-               but none can currently serve your wallet.",
+               but none holding its seed is connected and ready.",
             false,
         )),
         Some((KeyProviderType::Ledger, ExternalDependencyStatus::NeedUserAction)) => Some((
@@ -83,12 +83,12 @@ pub fn WalletSpendView(wallet_name: CCStr) -> Element {
             left: rsx! {
                 BackButton {
                     route: Route::WalletView {
-                        wallet_name: wallet_name,
+                        wallet_name: wallet_name.clone(),
                     },
                 }
             },
             SpendTabs::<Wallet> {
-                spendtabs_type: SpendTabsType::Owner,
+                spendtabs_type: SpendTabsType::Owner(wallet_name.clone()),
                 cannot_create_reason,
                 cannot_sign_reason,
                 cannot_broadcast_reason,
@@ -5,17 +5,22 @@ use std::time::Duration;
 use btc_heritage_wallet::{online_wallet::WalletStatus, OnlineWallet, Wallet};
 
 use crate::components::{
+    alerts::{add_alert, Alert, AlertCategory},
     badge::{ExternalDependencyStatus, OnlineWalletType},
     svg::{DrawSvg, SvgSize::Full, Sync},
     timestamp::LastSyncSpan,
 };
+use crate::utils::{timestamp_to_string, CCStr};
 
 /// A component that displays wallet synchronization status and provides sync functionality
 ///
 /// For Heritage Service wallets, implements a 1-minute cooldown after successful sync
 /// to respect the service's global sync lock.
+///
+/// Also displays the next scheduled background synchronization and the error of the
+/// last failed synchronization, if any, so the user knows why the balance may lag.
 #[component]
-pub fn WalletSync() -> Element {
+pub fn WalletSync(wallet_name: CCStr) -> Element {
     log::debug!("WalletSync Rendered");
 
     let mut wallet = use_context::<AsyncSignal<Wallet>>();
@@ -23,6 +28,17 @@ pub fn WalletSync() -> Element {
 
     let online_status = helper_hooks::use_memo_wallet_online_status(wallet);
 
+    let is_service =
+        use_memo(move || matches!(online_status(), Some((OnlineWalletType::Service, _))));
+    let wn = wallet_name.clone();
+    let sync_state = use_memo(move || {
+        state_management::WALLET_SYNC_STATES
+            .read()
+            .get(&wn)
+            .cloned()
+            .unwrap_or_default()
+    });
+
     let mut syncing = use_signal(|| false);
 
     let mut online_sync_cooldown_active = use_signal(|| false);
@@ -34,25 +50,29 @@ pub fn WalletSync() -> Element {
         _ => false,
     });
 
-    let click_sync = move |_| async move {
-        *syncing.write() = true;
-        match wallet
-            .with_mut(async |wallet: &mut Wallet| wallet.sync().await)
-            .await
-        {
-            Ok(_) => {
-                log::info!("Successfully synced wallet");
-                alert_info("Wallet Synced.");
-            }
-            Err(e) => {
-                alert_error(format!("Failed to sync: {e}"));
-                log::error!("Failed to sync: {e}");
+    let click_sync = move |_| {
+        let wallet_name = wallet_name.clone();
+        async move {
+            *syncing.write() = true;
+            let result = wallet
+                .with_mut(async |wallet: &mut Wallet| {
+                    state_management::sync_wallet(&wallet_name, wallet).await
+                })
+                .await;
+            match result {
+                Ok(_) => {
+                    log::info!("Successfully synced wallet");
+                    add_alert(Alert::info("Wallet Synced.").with_category(AlertCategory::Sync));
+                }
+                Err(e) => {
+                    alert_failure("Failed to sync", e);
+                }
             }
+            *syncing.write() = false;
+            *online_sync_cooldown_active.write() = true;
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            *online_sync_cooldown_active.write() = false;
         }
-        *syncing.write() = false;
-        *online_sync_cooldown_active.write() = true;
-        tokio::time::sleep(Duration::from_secs(60)).await;
-        *online_sync_cooldown_active.write() = false;
     };
 
     use_drop(|| log::debug!("WalletSync Dropped"));
@@ -62,14 +82,31 @@ pub fn WalletSync() -> Element {
             button {
                 class: "btn btn-circle btn-outline btn-primary btn-lg p-2",
                 onclick: click_sync,
-                disabled: !sync_available() || syncing(),
+                disabled: !sync_available() || syncing() || sync_state().syncing,
+                title: if is_service() { "Request sync now" } else { "Sync now" },
                 DrawSvg::<Sync> { size: Full }
             }
             div { class: "h-fit",
-                div { class: "text-base font-light", "Last synced:" }
+                div { class: "text-base font-light",
+                    if is_service() {
+                        "Last synced by the service:"
+                    } else {
+                        "Last synced:"
+                    }
+                }
                 div { class: "text-base font-bold",
                     LoadedComponent::<LastSyncSpan> { input: wallet_status.into() }
                 }
+                if let Some(next_sync_ts) = sync_state().next_sync_ts {
+                    div { class: "text-sm font-light",
+                        "Next sync: {timestamp_to_string(next_sync_ts)}"
+                    }
+                }
+                if let Some((error_ts, error)) = sync_state().last_error {
+                    div { class: "text-sm text-error max-w-sm", title: "{error}",
+                        "Sync failed on {timestamp_to_string(error_ts)}: {error}"
+                    }
+                }
             }
         }
     }
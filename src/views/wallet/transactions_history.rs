@@ -1,22 +1,33 @@
 use crate::prelude::*;
 
-use btc_heritage_wallet::heritage_service_api_client::TransactionSummary;
+use std::collections::HashMap;
+
+use btc_heritage_wallet::{
+    bitcoin::Amount, heritage_service_api_client::TransactionSummary, Wallet,
+};
 
 use crate::{
     components::{
-        balance::UIBtcAmount, misc::UITxId, timestamp::UITimestamp, transaction::UITxDetails,
+        balance::{HistoricalFiatValue, UIBtcAmount},
+        misc::UITxId,
+        timestamp::UITimestamp,
+        transaction::UITxDetails,
     },
-    utils::{CCStr, CheapClone},
+    utils::{log_error, CCStr, CheapClone},
 };
 
 /// Expandable row for transaction history display
 #[derive(Debug, Clone, PartialEq)]
 struct UITransactionsHistoryExpandableRow {
     txid: UITxId,
+    /// Key of the transaction in the [TxNotes]
+    txid_ref: CCStr,
     confirmation_time: UITimestamp,
     block_height: CCStr,
     balance_change: UIBtcAmount,
     balance_after: UIBtcAmount,
+    /// Amount moved and confirmation time, to display the value at the time
+    fiat_valuation: Option<(Amount, u64)>,
     details: UITxDetails,
 }
 
@@ -38,10 +49,16 @@ impl LoadedElement for UITransactionsHistoryExpandableRow {
                         // Transaction ID (shortened, 3 cols)
                         div { class: "col-span-3 font-mono text-base overflow-clip",
                             LoadedComponent { input: m.map(self.txid.clone()) }
+                            TxNoteLabel { txid: self.txid_ref }
                         }
                         // Amount
                         div { class: "font-bold text-center",
                             LoadedComponent { input: m.map(self.balance_change) }
+                            if let Some((amount, timestamp)) = self.fiat_valuation {
+                                div {
+                                    HistoricalFiatValue { amount, timestamp }
+                                }
+                            }
                         }
                         // Balance after
                         div { class: "font-semibold",
@@ -90,10 +107,12 @@ impl LoadedElement for UITransactionsHistoryExpandableRow {
     fn place_holder() -> Self {
         Self {
             txid: UITxId::place_holder(),
+            txid_ref: CCStr::place_holder(),
             confirmation_time: UITimestamp::place_holder(),
             block_height: CCStr::place_holder(),
             balance_change: UIBtcAmount::place_holder(),
             balance_after: UIBtcAmount::place_holder(),
+            fiat_valuation: None,
             details: UITxDetails::place_holder(),
         }
     }
@@ -105,6 +124,7 @@ impl LoadedSuccessConversionMarker
 impl FromRef<TransactionHistoryItem> for UITransactionsHistoryExpandableRow {
     fn from_ref(thi: &TransactionHistoryItem) -> Self {
         let txid = UITxId::from(thi.txid.to_string());
+        let txid_ref = CCStr::from(thi.txid.to_string());
         let (confirmation_time, block_height) = thi
             .confirmation_time
             .as_ref()
@@ -117,27 +137,85 @@ impl FromRef<TransactionHistoryItem> for UITransactionsHistoryExpandableRow {
             .unwrap_or_else(|| (UITimestamp::none(), CCStr::from("-")));
         let balance_change = UIBtcAmount::new(Some(thi.balance_change), true);
         let balance_after = UIBtcAmount::from(thi.balance_after);
+        let fiat_valuation = thi.confirmation_time.as_ref().map(|bt| {
+            (
+                Amount::from_sat(thi.balance_change.to_sat().unsigned_abs()),
+                bt.timestamp,
+            )
+        });
 
         Self {
             txid,
+            txid_ref,
             confirmation_time,
             block_height,
             balance_change,
             balance_after,
+            fiat_valuation,
             details: UITxDetails::from_ref(thi),
         }
     }
 }
 
+/// Notes of the transactions of the wallet, from their labels
+#[derive(Debug, Clone, Default, PartialEq)]
+struct TxNotes(HashMap<CCStr, CCStr>);
+
+/// Note of a transaction, if it has one
+#[component]
+fn TxNoteLabel(txid: CCStr) -> Element {
+    let tx_notes = use_context::<Memo<TxNotes>>();
+    let note = tx_notes.read().0.get(&txid).cloned();
+
+    rsx! {
+        if let Some(note) = note {
+            div { class: "text-sm font-sans italic text-(--color-base-content)/60 truncate",
+                title: "{note}",
+                {note}
+            }
+        }
+    }
+}
+
 /// Transaction history component displaying transactions in an expandable grid format
 #[component]
-pub(super) fn TransactionsHistory() -> Element {
+pub(super) fn TransactionsHistory(wallet_name: CCStr) -> Element {
     log::debug!("TransactionsHistory Rendered");
 
+    let database_service = state_management::use_database_service();
+    let wallet_labels = use_resource(move || {
+        let wallet_name = wallet_name.clone();
+        async move {
+            state_management::load_wallet_labels(database_service, wallet_name)
+                .await
+                .map_err(log_error)
+        }
+    });
+    let tx_notes = use_memo(move || {
+        let mut tx_notes = TxNotes::default();
+        if let Some(Ok(ref labels)) = *wallet_labels.read() {
+            tx_notes.0.extend(
+                labels
+                    .iter()
+                    .filter(|l| l.label_type == state_management::WalletLabelType::Tx)
+                    .filter_map(|l| {
+                        Some((CCStr::from(&l.reference), CCStr::from(l.label.as_ref()?)))
+                    }),
+            );
+        }
+        tx_notes
+    });
+    use_context_provider(|| tx_notes);
+
     let wallet_transactions = use_context::<FResource<CheapClone<[TransactionSummary]>>>();
+    let wallet_transactions_content =
+        use_context::<FMemo<ContentHashed<CheapClone<[TransactionSummary]>>>>();
 
-    let transaction_history_items =
-        helper_hooks::use_memo_transaction_history_items(wallet_transactions);
+    let wallet = use_context::<AsyncSignal<Wallet>>();
+    let transaction_history_items = helper_hooks::use_memo_persisted_transaction_history_items(
+        wallet,
+        helper_hooks::use_memo_transaction_history_items(wallet_transactions_content),
+    );
 
     use_drop(|| log::debug!("TransactionsHistory Dropped"));
 
@@ -154,8 +232,12 @@ pub(super) fn TransactionsHistory() -> Element {
                 div { "Balance" }
             }
 
-            // Expandable address rows
-            LoadedComponent::<CheapClone<[UITransactionsHistoryExpandableRow]>> { input: transaction_history_items.into() }
+            // Expandable transaction rows
+            ResourceErrorBoundary {
+                title: "Transactions History",
+                resource: wallet_transactions,
+                LoadedComponent::<CheapClone<[UITransactionsHistoryExpandableRow]>> { input: transaction_history_items.into() }
+            }
         }
     }
 }
@@ -4,14 +4,16 @@ use std::collections::HashSet;
 
 use btc_heritage_wallet::{
     btc_heritage::{
-        errors::ParseBlockInclusionObjectiveError, utils::bitcoin_network, BlockInclusionObjective,
-        HeritageWalletBackup,
+        errors::ParseBlockInclusionObjectiveError, utils::bitcoin_network, AccountXPub,
+        BlockInclusionObjective, HeritageWalletBackup,
     },
-    heritage_service_api_client::HeritageWalletMeta,
+    heritage_service_api_client::{Fingerprint, HeritageWalletMeta},
+    miniscript::DescriptorPublicKey,
     online_wallet::{LocalHeritageWallet, ServiceBinding as WalletServiceBinding},
     AnyKeyProvider, AnyOnlineWallet, BoundFingerprint, KeyProvider, LedgerKey, LocalKey,
     OnlineWallet, Wallet,
 };
+use serde::{Deserialize, Serialize};
 
 use crate::{
     components::{
@@ -24,17 +26,23 @@ use crate::{
         },
         misc::{BackButton, Divider},
         modal::InfoModal,
-        svg::{DrawSvg, WalletPlus},
+        quick_actions::prompt_backup_refresh,
+        svg::{Alert, DrawSvg, WalletPlus},
     },
-    utils::{CCStr, CheapClone, FutureFingerprints},
+    utils::{check_keys_network, CCStr, CheapClone, FutureFingerprints},
     Route,
 };
 
+use state_management::SeedRole;
+
 /// Component for creating new wallets
 #[component]
 pub fn WalletCreateView() -> Element {
     log::debug!("WalletCreateView Rendered");
 
+    // Bumped when a wallet creation fails in a resumable state
+    let pending_wallet_creation_refresh = use_signal(|| 0u32);
+
     use_drop(|| log::debug!("WalletCreateView Dropped"));
 
     rsx! {
@@ -44,7 +52,8 @@ pub fn WalletCreateView() -> Element {
             left: rsx! {
                 BackButton { route: Route::WalletListView {} }
             },
-            WalletCreateForm {}
+            PendingWalletCreationBanner { pending_wallet_creation_refresh }
+            WalletCreateForm { pending_wallet_creation_refresh }
 
             OnboardingInfoModal { step: OnboardingStep::ModalExplainWalletSplit,
                 div { class: "flex flex-col gap-4 max-w-xl text-base",
@@ -95,7 +104,7 @@ pub fn WalletCreateView() -> Element {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
 enum OnlineWalletType {
     None,
     #[default]
@@ -109,6 +118,7 @@ enum OnlineWalletCreation {
     New,
     Restore,
     BindExisting,
+    FromAccountXPubs,
 }
 
 /// Configuration for online wallet setup
@@ -123,8 +133,14 @@ enum OnlineWalletCreationConfig {
     New,
     Restore(HeritageWalletBackup),
     BindExisting(String),
+    /// Watch-only wallet of the given account xpubs, only for local online wallets
+    FromAccountXPubs(Vec<AccountXPub>),
 }
 
+/// Highest first account index of the auto-feed, keeping room for the maximum
+/// number of accounts below the hardened derivation limit
+const MAX_FIRST_ACCOUNT_INDEX: u32 = (1 << 31) - 1 - 100;
+
 /// Configuration for miscellaneous wallet options
 #[derive(Debug, Clone, PartialEq)]
 struct MiscConfig {
@@ -132,6 +148,114 @@ struct MiscConfig {
     // None => no auto_feed
     // Some(count) => auto_feed <count> key
     auto_feed: Option<u32>,
+    /// Index of the first account to auto-feed
+    first_account_index: u32,
+    /// Move the auto-fed accounts past the ones used by other wallets of the same seed
+    skip_used_accounts: bool,
+}
+
+/// Offer to resume or abandon a wallet creation that did not complete
+#[component]
+fn PendingWalletCreationBanner(pending_wallet_creation_refresh: ReadOnlySignal<u32>) -> Element {
+    log::debug!("PendingWalletCreationBanner Rendered");
+
+    let database_service = state_management::use_database_service();
+    let service_client_service = state_management::use_service_client_service();
+
+    let mut pending_wallet_creation = use_resource(move || async move {
+        let _ = pending_wallet_creation_refresh();
+        state_management::get_pending_wallet_creation(database_service)
+            .await
+            .ok()
+            .flatten()
+    });
+    let mut in_operation = use_signal(|| false);
+
+    let resume = move |_| async move {
+        *in_operation.write() = true;
+        let wallet = match state_management::load_pending_wallet_creation(database_service).await {
+            Ok(Some(wallet)) => wallet,
+            Ok(None) => {
+                pending_wallet_creation.restart();
+                *in_operation.write() = false;
+                return;
+            }
+            Err(e) => {
+                alert_failure("Could not load the pending wallet", e);
+                *in_operation.write() = false;
+                return;
+            }
+        };
+        let name = wallet.name().to_owned();
+        if let AnyOnlineWallet::Service(service_binding) = wallet.online_wallet() {
+            state_management::inject_serviceable_wallet(
+                service_client_service,
+                service_binding.wallet_id().to_owned(),
+                service_binding.fingerprint().ok(),
+            )
+        }
+        match state_management::create_wallet(database_service, wallet).await {
+            Ok(_) => {
+                if let Err(e) =
+                    state_management::clear_pending_wallet_creation(database_service).await
+                {
+                    log::warn!("Could not clear the pending wallet creation: {e}");
+                }
+                alert_success(format!("Wallet '{name}' created successfully!"));
+                navigator().push(Route::WalletView {
+                    wallet_name: CCStr::from(name),
+                });
+            }
+            Err(e) => alert_failure("Failed to create wallet", e),
+        }
+        *in_operation.write() = false;
+    };
+    let discard = move |_| async move {
+        *in_operation.write() = true;
+        match state_management::clear_pending_wallet_creation(database_service).await {
+            Ok(_) => pending_wallet_creation.restart(),
+            Err(e) => alert_failure("Could not discard the pending wallet", e),
+        }
+        *in_operation.write() = false;
+    };
+
+    use_drop(|| log::debug!("PendingWalletCreationBanner Dropped"));
+
+    rsx! {
+        if let Some(Some(state_management::PendingWalletCreation { wallet_name, service_wallet_id })) = pending_wallet_creation() {
+            div { class: "px-6 pt-8",
+                div { role: "alert", class: "alert alert-warning",
+                    DrawSvg::<Alert> {}
+                    div {
+                        div { class: "font-bold", "The creation of the wallet \"{wallet_name}\" did not complete" }
+                        div { class: "text-sm",
+                            "Resume it to save the wallet, or discard it to start over."
+                            if let Some(service_wallet_id) = service_wallet_id {
+                                " A discarded wallet stays on the Heritage Service (ID {service_wallet_id}) and can still be bound with \"Bind to Existing\"."
+                            }
+                        }
+                    }
+                    div { class: "flex gap-2",
+                        button {
+                            class: "btn btn-sm btn-outline",
+                            disabled: in_operation(),
+                            onclick: discard,
+                            "Discard"
+                        }
+                        button {
+                            class: "btn btn-sm btn-primary",
+                            disabled: in_operation(),
+                            onclick: resume,
+                            if in_operation() {
+                                span { class: "loading loading-spinner loading-sm" }
+                            }
+                            "Resume"
+                        }
+                    }
+                }
+            }
+        }
+    }
 }
 
 /// Type aliases for component state signals
@@ -142,11 +266,12 @@ type MiscConfigState = Signal<Result<MiscConfig, ()>>;
 
 /// Main wallet creation form component
 #[component]
-fn WalletCreateForm() -> Element {
+fn WalletCreateForm(mut pending_wallet_creation_refresh: Signal<u32>) -> Element {
     log::debug!("WalletCreateForm Rendered");
 
     let database_service = state_management::use_database_service();
     let service_client_service = state_management::use_service_client_service();
+    let blockchain_provider_service = state_management::use_blockchain_provider_service();
 
     let service_wallets = helper_hooks::use_resource_service_wallets();
     use_context_provider(move || service_wallets);
@@ -178,6 +303,11 @@ fn WalletCreateForm() -> Element {
         | Ok(OnlineWalletConfig::Service(OnlineWalletCreationConfig::Restore(bkp))) => {
             bkp.fingerprint().ok().flatten()
         }
+        Ok(OnlineWalletConfig::Local(OnlineWalletCreationConfig::FromAccountXPubs(
+            account_xpubs,
+        ))) => account_xpubs
+            .first()
+            .map(|axpub| axpub.descriptor_public_key().master_fingerprint()),
         _ => None,
     });
     let key_provider_fingerprint = use_memo(move || match &*key_provider_config_state.read() {
@@ -244,10 +374,14 @@ fn WalletCreateForm() -> Element {
         )
     });
 
+    // The account xpubs of a wallet created from account xpubs are provided by the user
     let show_auto_feed = use_memo(move || {
         !(matches!(
             &*online_wallet_config_state.read(),
             Ok(OnlineWalletConfig::None)
+                | Ok(OnlineWalletConfig::Local(
+                    OnlineWalletCreationConfig::FromAccountXPubs(_)
+                ))
         ) || matches!(
             &*key_provider_config_state.read(),
             Ok(KeyProviderConfig::None)
@@ -328,6 +462,8 @@ fn WalletCreateForm() -> Element {
         let Ok(MiscConfig {
             block_inclusion_objective,
             auto_feed,
+            first_account_index,
+            skip_used_accounts,
         }) = misc_config_state()
         else {
             return abort("Invalid miscelaneous configurations");
@@ -342,6 +478,8 @@ fn WalletCreateForm() -> Element {
                 | OnlineWalletConfig::Service(OnlineWalletCreationConfig::Restore(_))
         );
 
+        // Account xpubs to feed to the online wallet before anything else
+        let mut initial_account_xpubs = None;
         let ow_name = match ow_config {
             OnlineWalletConfig::Local(_) => "Local",
             OnlineWalletConfig::Service(_) => "Service",
@@ -385,6 +523,9 @@ fn WalletCreateForm() -> Element {
                         )
                         .await
                     }
+                    OnlineWalletCreationConfig::FromAccountXPubs(_) => {
+                        unreachable!("Inexistent option for Service Wallet")
+                    }
                 };
                 let service_binding = match service_binding {
                     Ok(service_binding) => service_binding,
@@ -404,15 +545,22 @@ fn WalletCreateForm() -> Element {
                         *creation_progress.write() = "Restoring Local Online Wallet".to_owned();
                         Some(bkp)
                     }
+                    OnlineWalletCreationConfig::FromAccountXPubs(account_xpubs) => {
+                        *creation_progress.write() =
+                            "Generating Watch-Only Local Online Wallet".to_owned();
+                        initial_account_xpubs = Some(account_xpubs);
+                        None
+                    }
                     OnlineWalletCreationConfig::BindExisting(_) => {
                         unreachable!("Inexistent option for Local Wallet")
                     }
                 };
-                let local_wallet =
-                    state_management::blocking_db_service_operation(database_service, move |db| {
-                        LocalHeritageWallet::create(&db, backup, block_inclusion_objective)
-                    })
-                    .await;
+                let local_wallet = state_management::blocking_db_service_operation(
+                    database_service,
+                    "Restore the local wallet",
+                    move |db| LocalHeritageWallet::create(&db, backup, block_inclusion_objective),
+                )
+                .await;
 
                 let local_wallet = match local_wallet {
                     Ok(local_wallet) => local_wallet,
@@ -432,13 +580,45 @@ fn WalletCreateForm() -> Element {
         };
 
         log::debug!("Created wallet: {wallet:?}");
+        if let Some(account_xpubs) = initial_account_xpubs {
+            *creation_progress.write() = format!(
+                "Feeding {} Account Extended Public Keys to {ow_name} Online Wallet",
+                account_xpubs.len()
+            );
+            if let Err(e) = wallet.feed_account_xpubs(account_xpubs).await {
+                return abort(&format!("Could not feed the account xpubs: {e}"));
+            }
+        }
         if show_auto_feed() {
             if let Some(count) = auto_feed {
-                log::debug!("Will auto-feed {count} xpubs");
+                let mut start = first_account_index;
+                if skip_used_accounts {
+                    *creation_progress.write() =
+                        "Looking for the accounts used by other wallets".to_owned();
+                    match wallet.fingerprint() {
+                        Ok(fingerprint) => {
+                            let used_account_indexes = state_management::used_account_indexes(
+                                database_service,
+                                service_client_service,
+                                blockchain_provider_service,
+                                fingerprint,
+                            )
+                            .await;
+                            // The accounts must be consecutive, skip past any collision
+                            while (start..start + count)
+                                .any(|index| used_account_indexes.contains(&index))
+                            {
+                                start += 1;
+                            }
+                        }
+                        Err(e) => warn(format!("Could not look for the used accounts: {e}")),
+                    }
+                }
+                log::debug!("Will auto-feed {count} xpubs from account {start}");
                 *creation_progress.write() = format!(
                     "Generating {count} Account Extended Public Keys from {kp_name} Key Provider",
                 );
-                match wallet.derive_accounts_xpubs(0..count).await {
+                match wallet.derive_accounts_xpubs(start..start + count).await {
                     Ok(account_xpubs) => {
                         *creation_progress.write() = format!(
                             "Feeding {count} Account Extended Public Keys to {ow_name} Online Wallet",
@@ -466,10 +646,41 @@ fn WalletCreateForm() -> Element {
             )
         }
 
+        // A wallet existing on the Heritage Service would be orphaned if it was not saved,
+        // so it is kept aside until it is saved to allow resuming its creation
+        let resumable = matches!(wallet.online_wallet(), AnyOnlineWallet::Service(_));
+        if resumable {
+            wallet = match state_management::save_pending_wallet_creation(database_service, wallet)
+                .await
+            {
+                Ok(wallet) => wallet,
+                Err(e) => {
+                    return abort(&format!("Failed to create wallet: {e}"));
+                }
+            };
+        }
+
         *creation_progress.write() = "Saving new Wallet in database".to_owned();
+        let fingerprint = wallet.fingerprint().ok();
         match state_management::create_wallet(database_service, wallet).await {
             Ok(_) => {
+                if resumable {
+                    if let Err(e) =
+                        state_management::clear_pending_wallet_creation(database_service).await
+                    {
+                        log::warn!("Could not clear the pending wallet creation: {e}");
+                    }
+                }
                 success(format!("Wallet '{}' created successfully!", name));
+                prompt_backup_refresh(database_service, CCStr::from(name.clone()), None).await;
+                if let Some(fingerprint) = fingerprint {
+                    state_management::warn_seed_role_conflicts(
+                        database_service,
+                        fingerprint,
+                        SeedRole::Owner,
+                    )
+                    .await;
+                }
                 // Add context if onboarding is in progress
                 if let OnboardingStatus::InProgress(ref mut onboarding) =
                     *state_management::ONBOARDING_STATUS.write()
@@ -482,6 +693,12 @@ fn WalletCreateForm() -> Element {
                     wallet_name: CCStr::from(name),
                 });
             }
+            Err(e) if resumable => {
+                *pending_wallet_creation_refresh.write() += 1;
+                return abort(&format!(
+                    "Failed to create wallet: {e}. Its creation can be resumed from this page."
+                ));
+            }
             Err(e) => {
                 return abort(&format!("Failed to create wallet: {e}"));
             }
@@ -566,7 +783,8 @@ fn WalletNameSection(wallet_name_state: WalletNameState) -> Element {
     });
 
     // Internal state - not exposed to parent
-    let wallet_name = use_signal(String::new);
+    let wallet_name =
+        helper_hooks::use_signal_with_crash_draft(CCStr::from("wallet_create/name"), String::new);
 
     // Internal validation
     let wallet_name_present = use_memo(move || !wallet_name.read().trim().is_empty());
@@ -637,7 +855,10 @@ fn OnlineWalletSection(
 ) -> Element {
     log::debug!("OnlineWalletSection Rendered");
     // Internal state - not exposed to parent
-    let online_wallet_type = use_signal(|| OnlineWalletType::Service);
+    let online_wallet_type = helper_hooks::use_signal_with_crash_draft(
+        CCStr::from("wallet_create/online_wallet_type"),
+        || OnlineWalletType::Service,
+    );
 
     let online_wallet_creation_config_state: OnlineWalletCreationConfigState =
         use_signal(|| Err(()));
@@ -757,6 +978,9 @@ fn ServiceWalletOptions(
             OnlineWalletCreation::BindExisting => {
                 service_wallet_id_state().map(OnlineWalletCreationConfig::BindExisting)
             }
+            OnlineWalletCreation::FromAccountXPubs => {
+                unreachable!("Does not exist for service online wallets")
+            }
         };
         online_wallet_creation_config_state.set(result);
     });
@@ -803,7 +1027,7 @@ fn ServiceWalletOptions(
             OnlineWalletCreation::BindExisting => rsx! {
                 ServiceWalletSelection { service_wallet_id_state }
             },
-            OnlineWalletCreation::New => rsx! {},
+            OnlineWalletCreation::New | OnlineWalletCreation::FromAccountXPubs => rsx! {},
         }
     }
 }
@@ -909,6 +1133,7 @@ fn LocalWalletOptions(
 
     let heritage_wallet_backup_state: Signal<Result<HeritageWalletBackup, CCStr>> =
         use_signal(|| Err(CCStr::default()));
+    let account_xpubs_state: Signal<Result<Vec<AccountXPub>, ()>> = use_signal(|| Err(()));
 
     // Internal validation
     let expected_fingerprint = future_fingerprints().key_provider;
@@ -920,6 +1145,9 @@ fn LocalWalletOptions(
             OnlineWalletCreation::Restore => heritage_wallet_backup_state()
                 .map(OnlineWalletCreationConfig::Restore)
                 .map_err(|_| ()),
+            OnlineWalletCreation::FromAccountXPubs => {
+                account_xpubs_state().map(OnlineWalletCreationConfig::FromAccountXPubs)
+            }
             OnlineWalletCreation::BindExisting => {
                 unreachable!("Does not exist for local online wallets")
             }
@@ -932,7 +1160,7 @@ fn LocalWalletOptions(
     rsx! {
         Divider { "Local Online Wallet Creation Options" }
 
-        RadioChoices { count: 2,
+        RadioChoices { count: 3,
             RadioChoice {
                 name: "local_mode",
                 state: online_wallet_creation,
@@ -948,6 +1176,14 @@ fn LocalWalletOptions(
                 title: "Restore from Backup",
                 subtitle: "Restore wallet from descriptors backup",
             }
+
+            RadioChoice {
+                name: "local_mode",
+                state: online_wallet_creation,
+                value: OnlineWalletCreation::FromAccountXPubs,
+                title: "From Account XPubs",
+                subtitle: "Monitor the addresses of known account extended public keys",
+            }
         }
 
         // Mode-specific options
@@ -958,6 +1194,10 @@ fn LocalWalletOptions(
                 
                 BackupRestoreSection { heritage_wallet_backup_state, expected_fingerprint }
             },
+            OnlineWalletCreation::FromAccountXPubs => rsx! {
+                Divider { "Account Extended Public Keys" }
+                AccountXPubsSection { account_xpubs_state, expected_fingerprint }
+            },
             OnlineWalletCreation::New | OnlineWalletCreation::BindExisting => {
                 rsx! {}
             }
@@ -965,6 +1205,88 @@ fn LocalWalletOptions(
     }
 }
 
+/// Account xpubs input for watch-only local wallets
+///
+/// The account xpubs must carry their key origin (fingerprint and derivation path),
+/// as exported by the "Account XPubs" page of another installation or by a hardware wallet.
+#[component]
+fn AccountXPubsSection(
+    account_xpubs_state: Signal<Result<Vec<AccountXPub>, ()>>,
+    expected_fingerprint: Option<Fingerprint>,
+) -> Element {
+    log::debug!("AccountXPubsSection Rendered");
+
+    // Internal state - not exposed to parent
+    let mut account_xpubs_text = use_signal(String::new);
+
+    // Internal validation
+    let account_xpubs = use_memo(move || {
+        let account_xpubs = account_xpubs_text
+            .read()
+            .split_whitespace()
+            .map(|xpub_txt| {
+                check_keys_network(xpub_txt)?;
+                AccountXPub::try_from(
+                    xpub_txt
+                        .parse::<DescriptorPublicKey>()
+                        .map_err(|e| e.to_string())?,
+                )
+                .map_err(|e| e.to_string())
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        let mut fingerprints = account_xpubs
+            .iter()
+            .map(|axpub| axpub.descriptor_public_key().master_fingerprint());
+        match fingerprints.next() {
+            None => Err("Provide at least one account xpub".to_owned()),
+            Some(fg) if fingerprints.any(|other| other != fg) => {
+                Err("All the account xpubs must come from the same seed".to_owned())
+            }
+            Some(fg) if expected_fingerprint.is_some_and(|efg| efg != fg) => Err(format!(
+                "The account xpubs have the fingerprint {fg} but the key provider has another one"
+            )),
+            Some(_) => Ok(account_xpubs),
+        }
+    });
+    let account_xpubs_error = use_memo(move || account_xpubs().err().map(CCStr::from));
+    let (error_display, mut signal_activity, onfocusout) =
+        use_future_error_feedback(account_xpubs_error.into());
+
+    // Update parent signal when internal state changes
+    use_effect(move || {
+        account_xpubs_state.set(account_xpubs().map_err(|_| ()));
+    });
+
+    use_drop(|| log::debug!("AccountXPubsSection Dropped"));
+
+    rsx! {
+        fieldset { class: "fieldset w-full",
+            legend { class: "fieldset-legend", "Account XPubs" }
+            textarea {
+                class: "textarea textarea-bordered font-mono text-xs w-full",
+                class: if error_display().is_some() { "textarea-error" },
+                rows: "6",
+                placeholder: "[73c5da0a/86'/0'/0']xpubDEKGYxthPqkm...HdbWQQKuAd/*\n[73c5da0a/86'/0'/1']xpubDEKGYxthPqkm...5iAYoDDScN/*",
+                value: account_xpubs_text(),
+                oninput: move |evt| {
+                    signal_activity();
+                    account_xpubs_text.set(evt.value());
+                },
+                onfocusout,
+            }
+            div {
+                class: "fieldset-label",
+                class: if error_display().is_none() { "invisible" } else { "text-error" },
+                if let Some(e) = error_display() {
+                    {e}
+                } else {
+                    "ph"
+                }
+            }
+        }
+    }
+}
+
 /// Miscellaneous options section
 #[component]
 fn MiscellaneousOptionsSection(
@@ -980,6 +1302,8 @@ fn MiscellaneousOptionsSection(
 
     let mut auto_feed_xpubs = use_signal(|| true);
     let mut auto_feed_keys_count = use_signal(|| 20);
+    let mut first_account_index = use_signal(|| 0);
+    let mut skip_used_accounts = use_signal(|| false);
 
     // Internal validation
 
@@ -988,6 +1312,8 @@ fn MiscellaneousOptionsSection(
         let result = Ok(MiscConfig {
             block_inclusion_objective: block_inclusion_objective(),
             auto_feed: auto_feed_xpubs().then(|| auto_feed_keys_count()),
+            first_account_index: first_account_index(),
+            skip_used_accounts: skip_used_accounts(),
         });
         misc_config_state.set(result);
     });
@@ -1088,6 +1414,37 @@ fn MiscellaneousOptionsSection(
                                     span { class: "label", "keys" }
                                 }
                                 div { class: "fieldset-label", "Min: 1, Max: 100, Default: 20" }
+                                div { class: "fieldset-description",
+                                    "Index of the first account to derive, to use distinct accounts for wallets sharing the same seed"
+                                }
+                                label { class: "input",
+                                    input {
+                                        r#type: "number",
+                                        min: "0",
+                                        max: "{MAX_FIRST_ACCOUNT_INDEX}",
+                                        value: first_account_index(),
+                                        oninput: move |evt| {
+                                            if let Ok(value) = evt.value().parse::<u32>() {
+                                                first_account_index.set(value.min(MAX_FIRST_ACCOUNT_INDEX));
+                                            } else {
+                                                first_account_index.set(0);
+                                            }
+                                        },
+                                    }
+                                    span { class: "label", "account" }
+                                }
+                                div { class: "fieldset-label", "Default: 0" }
+                                label { class: "label",
+                                    input {
+                                        r#type: "checkbox",
+                                        class: "checkbox checkbox-sm",
+                                        checked: skip_used_accounts(),
+                                        onchange: move |evt| skip_used_accounts.set(evt.checked()),
+                                    }
+                                    span { class: "text-base ml-2",
+                                        "Skip the accounts already used by other wallets of the same seed"
+                                    }
+                                }
                             }
                         }
                     }
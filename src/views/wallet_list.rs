@@ -1,14 +1,20 @@
 use crate::prelude::*;
 
-use btc_heritage_wallet::heritage_service_api_client::HeritageWalletMeta;
+use std::collections::{BTreeSet, HashMap};
+
+use btc_heritage_wallet::{
+    bitcoin::Amount, heritage_service_api_client::HeritageWalletMeta, Wallet,
+};
 
 use crate::{
     components::{
         badge::{ExternalDependencyStatus, KeyProviderType, OnlineWalletType, UIBadge},
-        balance::UIBalanceSummary,
+        balance::{UIBalanceSummary, UIBtcAmount},
+        heritage_configuration::UIExpirationBadge,
+        tags::{TagBadges, TagFilter},
         timestamp::LastSyncSpan,
     },
-    utils::{CCStr, CheapClone, EqCheapClone},
+    utils::{timestamp_to_date_string, CCStr, CheapClone, EqCheapClone},
     views::CreateLinkButton,
     Route,
 };
@@ -26,6 +32,20 @@ pub fn WalletListView() -> Element {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WalletSort {
+    Name,
+    Balance,
+    Expiration,
+}
+
+/// Values reported by each wallet card once loaded, to sort the list
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct WalletSortKeys {
+    balance: Option<Amount>,
+    expiration_ts: Option<u64>,
+}
+
 #[component]
 fn WalletList() -> Element {
     log::debug!("WalletList Rendered");
@@ -35,18 +55,121 @@ fn WalletList() -> Element {
 
     let service_user_id = state_management::use_service_key();
 
+    let database_service = state_management::use_database_service();
+    let item_tags = use_resource(move || async move {
+        state_management::load_item_tags(database_service)
+            .await
+            .unwrap_or_default()
+    });
+    let wallet_tags = move |wallet_name: &CCStr| {
+        item_tags
+            .read()
+            .as_ref()
+            .and_then(|item_tags| {
+                item_tags
+                    .get(&state_management::item_tags_key::<Wallet>(wallet_name))
+                    .cloned()
+            })
+            .unwrap_or_default()
+    };
+    let all_tags = use_memo(move || {
+        item_tags
+            .read()
+            .iter()
+            .flatten()
+            .filter(|(key, _)| key.starts_with(Wallet::item_key_prefix()))
+            .flat_map(|(_, tags)| tags.iter().cloned())
+            .collect::<BTreeSet<_>>()
+    });
+    let selected_tag = use_signal(|| None::<String>);
+
+    let mut sort = use_signal(|| WalletSort::Name);
+    let mut compact = use_signal(|| false);
+    let sort_keys = use_context_provider(|| Signal::new(HashMap::<CCStr, WalletSortKeys>::new()));
+    let sorted_wallet_names = use_memo(move || {
+        let mut wallet_names = wallet_names.cloned()?;
+        if let Some(ref selected_tag) = *selected_tag.read() {
+            wallet_names.retain(|wallet_name| wallet_tags(wallet_name).contains(selected_tag));
+        }
+        let sort_keys = sort_keys.read();
+        let keys = |name: &CCStr| sort_keys.get(name).copied().unwrap_or_default();
+        match sort() {
+            WalletSort::Name => wallet_names.sort(),
+            // Biggest balances first, the wallets still loading at the end
+            WalletSort::Balance => wallet_names
+                .sort_by(|a, b| keys(b).balance.cmp(&keys(a).balance).then_with(|| a.cmp(b))),
+            // Closest expirations first, the wallets without expiration at the end
+            WalletSort::Expiration => wallet_names.sort_by(|a, b| {
+                match (keys(a).expiration_ts, keys(b).expiration_ts) {
+                    (Some(a_ts), Some(b_ts)) => a_ts.cmp(&b_ts),
+                    (Some(_), None) => core::cmp::Ordering::Less,
+                    (None, Some(_)) => core::cmp::Ordering::Greater,
+                    (None, None) => core::cmp::Ordering::Equal,
+                }
+                .then_with(|| a.cmp(b))
+            }),
+        }
+        Some(wallet_names)
+    });
+
     use_drop(|| log::debug!("WalletList Dropped"));
 
     rsx! {
-        div { class: "container mx-auto grid grid-cols-[repeat(auto-fill,var(--container-xs))] gap-6 justify-center",
-            if let Some(ref wallet_names) = *wallet_names.read() {
+        div { class: "container mx-auto flex flex-row items-center gap-4 mb-4",
+            div { class: "grow",
+                TagFilter { tags: all_tags(), selected: selected_tag }
+            }
+            select {
+                class: "select select-sm w-48",
+                onchange: move |evt| {
+                    sort.set(
+                        match evt.value().as_str() {
+                            "balance" => WalletSort::Balance,
+                            "expiration" => WalletSort::Expiration,
+                            _ => WalletSort::Name,
+                        },
+                    )
+                },
+                option { value: "name", selected: sort() == WalletSort::Name, "Sort by name" }
+                option {
+                    value: "balance",
+                    selected: sort() == WalletSort::Balance,
+                    "Sort by balance"
+                }
+                option {
+                    value: "expiration",
+                    selected: sort() == WalletSort::Expiration,
+                    "Sort by expiration"
+                }
+            }
+            div { class: "join",
+                button {
+                    class: "btn btn-sm join-item",
+                    class: if !compact() { "btn-active" },
+                    onclick: move |_| compact.set(false),
+                    "Grid"
+                }
+                button {
+                    class: "btn btn-sm join-item",
+                    class: if compact() { "btn-active" },
+                    onclick: move |_| compact.set(true),
+                    "List"
+                }
+            }
+        }
+        div {
+            class: "container mx-auto",
+            class: if compact() { "flex flex-col gap-2" } else { "grid grid-cols-[repeat(auto-fill,var(--container-xs))] gap-6 justify-center" },
+            if let Some(wallet_names) = sorted_wallet_names() {
                 for wallet_name in wallet_names {
                     MaybeHighlight {
+                        key: "{wallet_name}-{service_user_id()}",
                         step: OnboardingStep::ClickWalletCardAfterHeirsCreation,
                         context_filter: OnboardingContextItemId::WalletName.item(wallet_name.to_string()),
                         WalletItem {
-                            key: "{wallet_name}-{service_user_id()}",
                             wallet_name: wallet_name.clone(),
+                            tags: wallet_tags(&wallet_name),
+                            compact: compact(),
                         }
                     }
                 }
@@ -56,6 +179,7 @@ fn WalletList() -> Element {
                     ServiceOnlyWalletItem {
                         key: "{service_only_wallet.name}-{service_user_id()}",
                         wallet_meta: service_only_wallet.clone().into(),
+                        compact: compact(),
                     }
                 }
             }
@@ -70,7 +194,7 @@ fn WalletList() -> Element {
                 CreateLinkButton {
                     route: Route::WalletCreateView {},
                     label: CCStr::from("Create Wallet"),
-                    size_classes: Some(CCStr::from("w-xs aspect-square")),
+                    size_classes: Some(CCStr::from(if compact() { "w-full" } else { "w-xs aspect-square" })),
                 }
             }
         }
@@ -78,12 +202,14 @@ fn WalletList() -> Element {
 }
 
 #[component]
-fn WalletItem(wallet_name: CCStr) -> Element {
+fn WalletItem(wallet_name: CCStr, tags: BTreeSet<String>, compact: bool) -> Element {
     log::debug!("WalletItem Rendered");
 
     let wallet = helper_hooks::use_async_wallet(wallet_name.clone());
     let wallet_status = helper_hooks::use_resource_wallet_status(wallet);
     let fingerprint = helper_hooks::use_memo_fingerprint(wallet);
+    // Opening the wallet is instant if its data is already in cache
+    helper_hooks::use_wallet_prefetch(wallet);
 
     let wn = wallet_name.clone();
     let click = move |_| {
@@ -97,8 +223,77 @@ fn WalletItem(wallet_name: CCStr) -> Element {
     let not_sign_only =
         use_memo(move || !matches!(online_status(), Some((OnlineWalletType::None, _))));
 
+    // The Heritage Configurations wait for the status not to compete with it on the online wallet
+    let secondary_trigger = helper_hooks::use_load_trigger();
+    use_effect(move || {
+        if not_sign_only() && wallet_status.read().is_some() {
+            secondary_trigger.fire();
+        }
+    });
+    let wallet_subwallet_configs =
+        helper_hooks::use_resource_wallet_subwallet_configs(wallet, secondary_trigger);
+    let expiration =
+        helper_hooks::use_memo_current_heritage_config_expiration(wallet_subwallet_configs);
+    let balance = use_memo(move || {
+        wallet_status.lrmap_ok(|wallet_status| {
+            Amount::from_sat(wallet_status.balance.total_balance().get_total())
+        })
+    });
+
+    // Report the sort keys to the list
+    let mut sort_keys = use_context::<Signal<HashMap<CCStr, WalletSortKeys>>>();
+    let wn = wallet_name.clone();
+    use_effect(move || {
+        let keys = WalletSortKeys {
+            balance: balance(),
+            expiration_ts: expiration().flatten().map(|(_, ts)| ts),
+        };
+        if sort_keys.peek().get(&wn) != Some(&keys) {
+            sort_keys.write().insert(wn.clone(), keys);
+        }
+    });
+
     use_drop(|| log::debug!("WalletItem Dropped"));
 
+    if compact {
+        return rsx! {
+            div {
+                class: "card card-border shadow-md cursor-pointer transition-colors hover:bg-base-200",
+                onclick: click,
+                div { class: "card-body flex flex-row flex-wrap items-center gap-6 py-3",
+                    div { class: "w-64",
+                        div { class: "text-xl font-black text-nowrap overflow-auto", {wallet_name} }
+                        div { class: "text-sm font-light", {fingerprint()} }
+                    }
+                    div { class: "w-40",
+                        TagBadges { tags: tags.clone() }
+                    }
+                    div { class: "w-40 text-lg font-bold",
+                        if not_sign_only() {
+                            LoadedComponent::<UIBtcAmount> { input: balance.into() }
+                        }
+                    }
+                    div { class: "w-56 text-sm font-light",
+                        if not_sign_only() {
+                            "Last Sync: "
+                            span { class: "font-semibold",
+                                LoadedComponent::<LastSyncSpan> { input: wallet_status.into() }
+                            }
+                        }
+                    }
+                    div { class: "w-24",
+                        ExpirationBadge { expiration }
+                    }
+                    div { class: "grow" }
+                    div { class: "flex flex-row gap-2",
+                        LoadedComponent::<UIBadge> { input: keyprovider_status.into() }
+                        LoadedComponent::<UIBadge> { input: online_status.into() }
+                    }
+                }
+            }
+        };
+    }
+
     rsx! {
         div {
             class: "card card-lg border shadow-xl w-xs aspect-square cursor-pointer transition-transform hover:scale-105",
@@ -109,6 +304,7 @@ fn WalletItem(wallet_name: CCStr) -> Element {
                         {wallet_name}
                     }
                     div { class: "text-sm font-light", {fingerprint()} }
+                    TagBadges { tags }
                 }
 
                 div { class: "grow" }
@@ -121,6 +317,10 @@ fn WalletItem(wallet_name: CCStr) -> Element {
                             LoadedComponent::<LastSyncSpan> { input: wallet_status.into() }
                         }
                     }
+                    div { class: "flex flex-row items-center gap-2 text-sm font-light",
+                        "Heritage Configuration: "
+                        ExpirationBadge { expiration }
+                    }
 
                     div { class: "grow" }
                 }
@@ -136,7 +336,25 @@ fn WalletItem(wallet_name: CCStr) -> Element {
 }
 
 #[component]
-fn ServiceOnlyWalletItem(wallet_meta: EqCheapClone<HeritageWalletMeta>) -> Element {
+fn ExpirationBadge(expiration: Memo<Option<Option<(ExpirationStatus, u64)>>>) -> Element {
+    match expiration() {
+        Some(Some((expiration_status, expiration_ts))) => {
+            let expiration_date = timestamp_to_date_string(expiration_ts);
+            rsx! {
+                div { title: "Expires on {expiration_date}",
+                    AlwaysLoadedComponent::<UIExpirationBadge> { input: UIExpirationBadge::from((expiration_status, true)) }
+                }
+            }
+        }
+        Some(None) => rsx! { "-" },
+        None => rsx! {
+            div { class: "skeleton h-5 w-20" }
+        },
+    }
+}
+
+#[component]
+fn ServiceOnlyWalletItem(wallet_meta: EqCheapClone<HeritageWalletMeta>, compact: bool) -> Element {
     log::debug!("ServiceOnlyWalletItem Rendered");
 
     let wallet_meta: CheapClone<HeritageWalletMeta> = wallet_meta.into();
@@ -148,6 +366,24 @@ fn ServiceOnlyWalletItem(wallet_meta: EqCheapClone<HeritageWalletMeta>) -> Eleme
 
     use_drop(|| log::debug!("ServiceOnlyWalletItem Dropped"));
 
+    if compact {
+        return rsx! {
+            div { class: "card card-border shadow-md opacity-40",
+                div { class: "card-body flex flex-row flex-wrap items-center gap-6 py-3",
+                    div { class: "w-64",
+                        div { class: "text-xl font-black text-nowrap overflow-clip", {wallet_name} }
+                        div { class: "text-sm font-light", {fingerprint} }
+                    }
+                    div { class: "text-lg font-bold text-secondary grow", "Only on Service" }
+                    div { class: "flex flex-row gap-2",
+                        LoadedComponent::<UIBadge> { input: (KeyProviderType::None, ExternalDependencyStatus::None).ref_into() }
+                        LoadedComponent::<UIBadge> { input: (OnlineWalletType::Service, ExternalDependencyStatus::None).ref_into() }
+                    }
+                }
+            }
+        };
+    }
+
     rsx! {
         div { class: "relative",
             div { class: "card card-lg border shadow-xl w-xs aspect-square opacity-40",
@@ -0,0 +1,323 @@
+use crate::prelude::*;
+
+use btc_heritage_wallet::bitcoin::{
+    base58,
+    hashes::{hash160, Hash},
+};
+
+use crate::{components::copy::CopyToClipboardButtonIcon, utils::CCStr, Route};
+
+/// Known version bytes of extended keys (SLIP-132)
+struct ExtendedKeyVersion {
+    prefix: &'static str,
+    version: [u8; 4],
+    mainnet: bool,
+    private: bool,
+    /// The kind of scripts and derivation paths the prefix is used for
+    usage: &'static str,
+}
+
+const EXTENDED_KEY_VERSIONS: &[ExtendedKeyVersion] = &[
+    ExtendedKeyVersion {
+        prefix: "xpub",
+        version: [0x04, 0x88, 0xb2, 0x1e],
+        mainnet: true,
+        private: false,
+        usage: "P2PKH (m/44'), P2TR (m/86') and Heritage wallets",
+    },
+    ExtendedKeyVersion {
+        prefix: "ypub",
+        version: [0x04, 0x9d, 0x7c, 0xb2],
+        mainnet: true,
+        private: false,
+        usage: "P2WPKH nested in P2SH (m/49')",
+    },
+    ExtendedKeyVersion {
+        prefix: "zpub",
+        version: [0x04, 0xb2, 0x47, 0x46],
+        mainnet: true,
+        private: false,
+        usage: "P2WPKH (m/84')",
+    },
+    ExtendedKeyVersion {
+        prefix: "Ypub",
+        version: [0x02, 0x95, 0xb4, 0x3f],
+        mainnet: true,
+        private: false,
+        usage: "Multi-signature P2WSH nested in P2SH",
+    },
+    ExtendedKeyVersion {
+        prefix: "Zpub",
+        version: [0x02, 0xaa, 0x7e, 0xd3],
+        mainnet: true,
+        private: false,
+        usage: "Multi-signature P2WSH",
+    },
+    ExtendedKeyVersion {
+        prefix: "tpub",
+        version: [0x04, 0x35, 0x87, 0xcf],
+        mainnet: false,
+        private: false,
+        usage: "P2PKH (m/44'), P2TR (m/86') and Heritage wallets",
+    },
+    ExtendedKeyVersion {
+        prefix: "upub",
+        version: [0x04, 0x4a, 0x52, 0x62],
+        mainnet: false,
+        private: false,
+        usage: "P2WPKH nested in P2SH (m/49')",
+    },
+    ExtendedKeyVersion {
+        prefix: "vpub",
+        version: [0x04, 0x5f, 0x1c, 0xf6],
+        mainnet: false,
+        private: false,
+        usage: "P2WPKH (m/84')",
+    },
+    ExtendedKeyVersion {
+        prefix: "Upub",
+        version: [0x02, 0x42, 0x89, 0xef],
+        mainnet: false,
+        private: false,
+        usage: "Multi-signature P2WSH nested in P2SH",
+    },
+    ExtendedKeyVersion {
+        prefix: "Vpub",
+        version: [0x02, 0x57, 0x54, 0x83],
+        mainnet: false,
+        private: false,
+        usage: "Multi-signature P2WSH",
+    },
+    ExtendedKeyVersion {
+        prefix: "xprv",
+        version: [0x04, 0x88, 0xad, 0xe4],
+        mainnet: true,
+        private: true,
+        usage: "",
+    },
+    ExtendedKeyVersion {
+        prefix: "yprv",
+        version: [0x04, 0x9d, 0x78, 0x78],
+        mainnet: true,
+        private: true,
+        usage: "",
+    },
+    ExtendedKeyVersion {
+        prefix: "zprv",
+        version: [0x04, 0xb2, 0x43, 0x0c],
+        mainnet: true,
+        private: true,
+        usage: "",
+    },
+    ExtendedKeyVersion {
+        prefix: "tprv",
+        version: [0x04, 0x35, 0x83, 0x94],
+        mainnet: false,
+        private: true,
+        usage: "",
+    },
+    ExtendedKeyVersion {
+        prefix: "uprv",
+        version: [0x04, 0x4a, 0x4e, 0x28],
+        mainnet: false,
+        private: true,
+        usage: "",
+    },
+    ExtendedKeyVersion {
+        prefix: "vprv",
+        version: [0x04, 0x5f, 0x18, 0xbc],
+        mainnet: false,
+        private: true,
+        usage: "",
+    },
+];
+
+/// The content of an extended public key
+#[derive(Debug, Clone, PartialEq)]
+struct InspectedXPub {
+    prefix: &'static str,
+    usage: &'static str,
+    mainnet: bool,
+    /// Origin given in front of the key, in the `[fingerprint/path]` form
+    origin: Option<CCStr>,
+    fingerprint: CCStr,
+    parent_fingerprint: CCStr,
+    depth: u8,
+    child_number: CCStr,
+    derivation_hint: CCStr,
+    /// The same key with the other SLIP-132 prefixes of its network
+    conversions: Vec<(&'static str, &'static str, CCStr)>,
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Parse an extended public key, with an optional origin and derivation suffix
+fn inspect_xpub(input: &str) -> Result<InspectedXPub, CCStr> {
+    let input = input.trim();
+    let (origin, key) = match input.strip_prefix('[').and_then(|i| i.split_once(']')) {
+        Some((origin, key)) => (Some(CCStr::from(format!("[{origin}]"))), key),
+        None => (None, input),
+    };
+    // Ignore the derivation steps following the key, if any
+    let key = key.split('/').next().unwrap_or_default();
+
+    let data = base58::decode_check(key)
+        .map_err(|e| CCStr::from(format!("Not a valid extended key: {e}")))?;
+    if data.len() != 78 {
+        return Err(CCStr::from("Not a valid extended key: unexpected length"));
+    }
+    let version = EXTENDED_KEY_VERSIONS
+        .iter()
+        .find(|v| data[0..4] == v.version)
+        .ok_or_else(|| CCStr::from(format!("Unknown extended key version {}", hex(&data[0..4]))))?;
+    if version.private {
+        return Err(CCStr::from(
+            "This is an extended PRIVATE key, it gives access to the bitcoins. Never share it!",
+        ));
+    }
+
+    let depth = data[4];
+    let child_number = u32::from_be_bytes(data[9..13].try_into().expect("4 bytes"));
+    let hardened = child_number & 0x8000_0000 != 0;
+    let child_index = child_number & 0x7fff_ffff;
+    let child_number = if hardened {
+        format!("{child_index}'")
+    } else {
+        child_index.to_string()
+    };
+    let derivation_hint = match (depth, hardened) {
+        (0, _) => "Master key".to_owned(),
+        (3, true) => format!(
+            "Account key, probably m/purpose'/coin'/{child_number} (e.g. m/86'/{}'/{child_number} for Heritage wallets)",
+            if version.mainnet { 0 } else { 1 }
+        ),
+        (_, true) => format!("Hardened derivation at depth {depth}"),
+        (_, false) => format!(
+            "Non-hardened derivation at depth {depth}, this is not an account key"
+        ),
+    };
+
+    let conversions = EXTENDED_KEY_VERSIONS
+        .iter()
+        .filter(|v| v.mainnet == version.mainnet && !v.private)
+        .map(|v| {
+            let mut converted = data.clone();
+            converted[0..4].copy_from_slice(&v.version);
+            (
+                v.prefix,
+                v.usage,
+                CCStr::from(base58::encode_check(&converted)),
+            )
+        })
+        .collect();
+
+    Ok(InspectedXPub {
+        prefix: version.prefix,
+        usage: version.usage,
+        mainnet: version.mainnet,
+        origin,
+        fingerprint: CCStr::from(hex(
+            &hash160::Hash::hash(&data[45..78]).to_byte_array()[0..4]
+        )),
+        parent_fingerprint: CCStr::from(hex(&data[5..9])),
+        depth,
+        child_number: CCStr::from(child_number),
+        derivation_hint: CCStr::from(derivation_hint),
+        conversions,
+    })
+}
+
+/// Tool view parsing any extended public key and converting it between
+/// the SLIP-132 prefixes
+#[component]
+pub fn XPubToolView() -> Element {
+    log::debug!("XPubToolView Rendered");
+
+    let mut input = use_signal(String::new);
+    let inspected = use_memo(move || {
+        let input = input.read();
+        (!input.trim().is_empty()).then(|| inspect_xpub(&input))
+    });
+
+    use_drop(|| log::debug!("XPubToolView Dropped"));
+
+    rsx! {
+        super::TitledView {
+            title: CCStr::from("Extended Key Tool"),
+            subtitle: CCStr::from("Inspect an extended public key and convert its format."),
+            right: rsx! {
+                Link { class: "btn btn-outline", to: Route::BackupCheckView {}, "Backup Check" }
+            },
+            div { class: "container mx-auto px-8 space-y-8",
+                div { class: "text-base font-light",
+                    "Extended public keys come with various prefixes (xpub, ypub, zpub, tpub, vpub...)
+                    depending on the wallet software that exported them. They all contain the same
+                    key: Heritage wallets expect the xpub (or tpub) format."
+                }
+                fieldset { class: "fieldset w-full",
+                    legend { class: "fieldset-legend", "Extended Public Key" }
+                    textarea {
+                        class: "textarea textarea-bordered font-mono text-sm w-full",
+                        class: if matches!(inspected(), Some(Err(_))) { "textarea-error" },
+                        class: if matches!(inspected(), Some(Ok(_))) { "textarea-success" },
+                        rows: "3",
+                        placeholder: "xpub..., zpub..., [fingerprint/86'/0'/0']xpub...",
+                        value: input(),
+                        oninput: move |evt| input.set(evt.value()),
+                    }
+                    if let Some(Err(e)) = inspected() {
+                        div { class: "fieldset-label text-error", "{e}" }
+                    }
+                }
+
+                if let Some(Ok(inspected)) = inspected() {
+                    div { class: "rounded-box border border-base-content/5 shadow-md p-4",
+                        h2 { class: "text-2xl font-bold mb-4", "Key Details" }
+                        div { class: "grid grid-cols-[auto_1fr] gap-x-8 gap-y-2 text-base",
+                            div { class: "font-semibold", "Format" }
+                            div { "{inspected.prefix} — {inspected.usage}" }
+                            div { class: "font-semibold", "Network" }
+                            div {
+                                if inspected.mainnet {
+                                    "Bitcoin (mainnet)"
+                                } else {
+                                    "Testnet, Signet or Regtest"
+                                }
+                            }
+                            if let Some(origin) = inspected.origin.as_ref() {
+                                div { class: "font-semibold", "Origin" }
+                                div { class: "font-mono", "{origin}" }
+                            }
+                            div { class: "font-semibold", "Key fingerprint" }
+                            div { class: "font-mono", "{inspected.fingerprint}" }
+                            div { class: "font-semibold", "Parent fingerprint" }
+                            div { class: "font-mono", "{inspected.parent_fingerprint}" }
+                            div { class: "font-semibold", "Depth" }
+                            div { "{inspected.depth}" }
+                            div { class: "font-semibold", "Child number" }
+                            div { class: "font-mono", "{inspected.child_number}" }
+                            div { class: "font-semibold", "Derivation" }
+                            div { "{inspected.derivation_hint}" }
+                        }
+                    }
+                    div { class: "rounded-box border border-base-content/5 shadow-md p-4",
+                        h2 { class: "text-2xl font-bold mb-4", "Conversions" }
+                        div { class: "flex flex-col gap-4",
+                            for (prefix , usage , converted) in inspected.conversions.iter() {
+                                div {
+                                    div { class: "font-semibold", "{prefix} — {usage}" }
+                                    div { class: "flex items-center gap-2",
+                                        div { class: "font-mono text-sm break-all", "{converted}" }
+                                        CopyToClipboardButtonIcon { value: converted.clone() }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}